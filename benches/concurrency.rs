@@ -0,0 +1,47 @@
+// Benchmarks for CountingSemaphore from metered_concurrency.rs: acquiring and releasing on its
+// own, and with threads contending for fewer resources than there are threads.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+use std::sync::Arc;
+use std::thread::spawn;
+
+mod harness;
+
+#[path = "../src/concurrency/metered_concurrency.rs"]
+#[allow(dead_code)]
+mod metered_concurrency;
+
+use metered_concurrency::CountingSemaphore;
+
+static NUM_THREADS: usize = 4;
+
+// Acquire and release a resource of `sem` `n` times in each of NUM_THREADS threads.
+fn contend(sem: CountingSemaphore, n: usize) {
+    let sem = Arc::new(sem);
+    let handles: Vec<_> = (0..NUM_THREADS).map(|_| {
+        let sem = sem.clone();
+        spawn(move || for _ in 0..n {
+            drop(sem.acquire().unwrap());
+        })
+    }).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn concurrency(c: &mut Criterion) {
+    harness::bench_sizes(c, "semaphore_uncontended", harness::SIZES, |_| CountingSemaphore::new(1),
+                         |sem| for _ in 0..1_000 {
+                             drop(sem.acquire().unwrap());
+                         });
+    harness::bench_sizes(c, "semaphore_contended", harness::SIZES, |n| n,
+                         |n| contend(CountingSemaphore::new(2), n / NUM_THREADS));
+    harness::bench_sizes(c, "semaphore_contended_fair", harness::SIZES, |n| n,
+                         |n| contend(CountingSemaphore::new_fair(2), n / NUM_THREADS));
+}
+
+criterion_group!(benches, concurrency);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+// Shared setup for the benchmarks under benches/.  A task registers a benchmark with
+// bench_sizes(), which times it on inputs of each of a standard set of sizes, so that results
+// stay comparable from one change to the next.  Inputs are generated from a fixed seed for the
+// same reason.
+//
+// Run with `cargo bench`, or `cargo bench --bench sorting` for a single suite.
+
+use criterion::{BatchSize, BenchmarkId, Criterion};
+
+// Input sizes for most benchmarks
+#[allow(dead_code)]
+pub static SIZES: &[usize] = &[100, 1_000, 10_000];
+
+// For tasks that only accept powers of two, such as the FFT
+#[allow(dead_code)]
+pub static POWER_OF_TWO_SIZES: &[usize] = &[128, 1_024, 8_192];
+
+// The sides of square matrices, whose multiplication takes the cube of the side
+#[allow(dead_code)]
+pub static MATRIX_SIZES: &[usize] = &[64, 128, 256, 512];
+
+// The sides of the boards of puzzles such as the n-queens, the work on which grows exponentially
+// with them
+#[allow(dead_code)]
+pub static BOARD_SIZES: &[usize] = &[8, 10, 12];
+
+// Benchmark `routine` on an input made by `setup` for each of `sizes`.  Making the input isn't
+// part of the timing, so routines are free to consume or mutate it.
+pub fn bench_sizes<I, O, S, R>(c: &mut Criterion, name: &str, sizes: &[usize], setup: S, routine: R)
+    where S: Fn(usize) -> I, R: Fn(I) -> O
+{
+    let mut group = c.benchmark_group(name);
+    for &size in sizes {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter_batched(|| setup(size), &routine, BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+// `n` pseudo-random numbers, the same ones on every run (xorshift32 from a fixed seed).
+#[allow(dead_code)]
+pub fn random_numbers(n: usize) -> Vec<u32> {
+    let mut state = 0x2545_f491u32;
+    (0..n).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    }).collect()
+}
@@ -0,0 +1,34 @@
+// Benchmarks for the three ways matrix_multiplication.rs multiplies matrices, on square matrices
+// of f64s.  Where strassen() overtakes blocked() is what its STRASSEN_THRESHOLD is set from.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+
+mod harness;
+
+#[path = "../src/math/matrix_multiplication.rs"]
+#[allow(dead_code)]
+mod matrix_multiplication;
+
+use matrix_multiplication::utils::matrix::Matrix;
+
+fn matrix(c: &mut Criterion) {
+    let pair = |n: usize| {
+        let numbers = harness::random_numbers(2 * n * n);
+        let mut numbers = numbers.into_iter().map(|x| x as f64 / u32::MAX as f64);
+        let a = Matrix::from_fn(n, n, |_, _| numbers.next().unwrap());
+        let b = Matrix::from_fn(n, n, |_, _| numbers.next().unwrap());
+        (a, b)
+    };
+    harness::bench_sizes(c, "matrix_naive", harness::MATRIX_SIZES, pair,
+                         |(a, b)| matrix_multiplication::naive(&a, &b));
+    harness::bench_sizes(c, "matrix_blocked", harness::MATRIX_SIZES, pair,
+                         |(a, b)| matrix_multiplication::blocked(&a, &b));
+    harness::bench_sizes(c, "matrix_strassen", harness::MATRIX_SIZES, pair,
+                         |(a, b)| matrix_multiplication::strassen(&a, &b));
+}
+
+criterion_group!(benches, matrix);
+criterion_main!(benches);
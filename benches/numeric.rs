@@ -0,0 +1,33 @@
+// Benchmarks for numeric tasks: the FFT, and finding primes.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+
+mod harness;
+
+#[path = "../src/math/fast_fourier_transform.rs"]
+#[allow(dead_code)]
+mod fast_fourier_transform;
+#[path = "../src/math/sieve_eratosthenes.rs"]
+#[allow(dead_code)]
+mod sieve_eratosthenes;
+
+use fast_fourier_transform::utils::complex::Complex;
+
+fn numeric(c: &mut Criterion) {
+    let signal = |n: usize| {
+        harness::random_numbers(n).into_iter().map(|x| Complex::from(x as f64)).collect::<Vec<_>>()
+    };
+    harness::bench_sizes(c, "fft", harness::POWER_OF_TWO_SIZES, signal, |mut v| {
+        fast_fourier_transform::fft(&mut v);
+        v
+    });
+    // Sieving up to 100 times the size, so the larger sizes aren't over in an instant
+    harness::bench_sizes(c, "sieve_eratosthenes", harness::SIZES, |n| n * 100,
+                         sieve_eratosthenes::simple_sieve);
+}
+
+criterion_group!(benches, numeric);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+// Benchmarks for the n-queens problem, counting the solutions on one thread and on one thread per
+// column of the first row.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+
+mod harness;
+
+#[path = "../src/puzzles/n_queens.rs"]
+#[allow(dead_code)]
+mod n_queens;
+
+fn puzzles(c: &mut Criterion) {
+    harness::bench_sizes(c, "n_queens", harness::BOARD_SIZES, |n| n as i32, n_queens::n_queens);
+    harness::bench_sizes(c, "semi_parallel_n_queens", harness::BOARD_SIZES, |n| n as i32,
+                         n_queens::semi_parallel_n_queens);
+}
+
+criterion_group!(benches, puzzles);
+criterion_main!(benches);
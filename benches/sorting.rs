@@ -0,0 +1,34 @@
+// Benchmarks for the sorting tasks, all run on the same pseudo-random input.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+
+mod harness;
+
+#[path = "../src/sorting/heap_sort.rs"]
+#[allow(dead_code)]
+mod heap_sort;
+#[path = "../src/sorting/merge-sort.rs"]
+#[allow(dead_code)]
+mod merge_sort;
+#[path = "../src/sorting/quick_sort.rs"]
+#[allow(dead_code)]
+mod quick_sort;
+
+fn sorting(c: &mut Criterion) {
+    harness::bench_sizes(c, "heap_sort", harness::SIZES, harness::random_numbers, |mut v| {
+        heap_sort::heap_sort(&mut v);
+        v
+    });
+    harness::bench_sizes(c, "merge_sort", harness::SIZES, harness::random_numbers,
+                         |v| merge_sort::merge_sort(&v));
+    harness::bench_sizes(c, "quick_sort", harness::SIZES, harness::random_numbers, |mut v| {
+        quick_sort::quick_sort(&mut v);
+        v
+    });
+}
+
+criterion_group!(benches, sorting);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+// Generates the task registry used by src/rosetta_meta.rs from the [[bin]] entries of Cargo.toml,
+// which already list every task along with a link to its Rosetta Code page.  Writing the table
+// out here means a new task only ever has to be added in one place.
+//
+// A binary that is another solution to a task already there says which with a comment under its
+// link, such as `# variant of hamming_numbers`, so that the solutions can be compared.
+//
+// Each task's status comes from its source instead: a task marked #[rosetta_task(status = "draft")]
+// is a draft, and any other is complete (see rosetta_task/src/lib.rs).  The attribute's url is
+// used when the [[bin]] entry has no link.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+#[path = "rosetta_task/src/attribute.rs"]
+#[allow(dead_code)]
+mod attribute;
+
+use attribute::Status;
+
+// One [[bin]] entry of Cargo.toml
+#[derive(Default)]
+struct Entry {
+    name: String,
+    path: String,
+    url: Option<String>,
+    features: Vec<String>,
+    variant_of: Option<String>,
+}
+
+// The quoted value of a `key = "value"` line, if that's what `line` is.
+fn value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(key)?.trim_start().strip_prefix('=')?.trim();
+    rest.strip_prefix('"')?.strip_suffix('"')
+}
+
+// The quoted values of a `key = ["a", "b"]` line, if that's what `line` is.
+fn values(line: &str, key: &str) -> Option<Vec<String>> {
+    let rest = line.strip_prefix(key)?.trim_start().strip_prefix('=')?.trim();
+    let list = rest.strip_prefix('[')?.strip_suffix(']')?;
+    list.split(',').map(str::trim).filter(|item| !item.is_empty())
+        .map(|item| Some(item.strip_prefix('"')?.strip_suffix('"')?.to_string()))
+        .collect()
+}
+
+fn bin_entries(manifest: &Path) -> Vec<Entry> {
+    let reader = BufReader::new(File::open(manifest).unwrap());
+    let mut entries = Vec::new();
+    let mut current: Option<Entry> = None;
+    for line in reader.lines() {
+        let line = line.unwrap();
+        let line = line.trim();
+        if line.starts_with('[') {
+            entries.extend(current.take());
+            if line == "[[bin]]" {
+                current = Some(Entry::default());
+            }
+        } else if let Some(ref mut entry) = current {
+            if let Some(comment) = line.strip_prefix('#').map(str::trim) {
+                if comment.starts_with("http") {
+                    entry.url = Some(comment.to_string());
+                } else if let Some(task) = comment.strip_prefix("variant of ") {
+                    entry.variant_of = Some(task.trim().to_string());
+                }
+            } else if let Some(name) = value(line, "name") {
+                entry.name = name.to_string();
+            } else if let Some(path) = value(line, "path") {
+                entry.path = path.to_string();
+            } else if let Some(features) = values(line, "required-features") {
+                entry.features = features;
+            }
+        }
+    }
+    entries.extend(current);
+    entries
+}
+
+// The category of the task at `path`, which is the directory under src/ it's in, such as "sorting"
+// for src/sorting/heap_sort.rs.  The tools directly in src/ have none.
+fn category(path: &str) -> Option<&str> {
+    let mut dirs: Vec<&str> = path.strip_prefix("src/")?.split('/').collect();
+    dirs.pop();
+    match dirs[..] {
+        [category] if category != "utils" => Some(category),
+        _ => None,
+    }
+}
+
+// What the #[rosetta_task] attribute in the task's source says, if it has one
+fn task_attribute(root: &Path, entry: &Entry) -> Option<attribute::Attribute> {
+    let source = fs::read_to_string(root.join(&entry.path)).ok()?;
+    let args = attribute::find(&source)?;
+    match attribute::parse(args) {
+        Ok(attribute) => Some(attribute),
+        Err(message) => panic!("{}: bad #[rosetta_task] attribute: {}", entry.path, message),
+    }
+}
+
+fn main() {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let root = Path::new(&root);
+    let manifest = root.join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    println!("cargo:rerun-if-changed={}", root.join("src").display());
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("tasks.rs");
+    let mut out = File::create(out).unwrap();
+    writeln!(out, "pub static TASKS: &[Task] = &[").unwrap();
+    for mut entry in bin_entries(&manifest) {
+        let mut status = Status::Complete;
+        if let Some(attribute) = task_attribute(root, &entry) {
+            status = attribute.status;
+            match (&entry.url, attribute.url) {
+                (None, url) => entry.url = url,
+                (Some(url), Some(ref other)) if url != other => {
+                    println!("cargo:warning={} links to {} in Cargo.toml but {} in its source",
+                             entry.name, url, other);
+                }
+                _ => {}
+            }
+        }
+        // Debug formatting quotes and escapes the strings just like Rust literals
+        writeln!(out, "    Task {{ name: {:?}, url: {:?}, path: {:?}, category: {:?}, \
+                       features: &{:?}, variant_of: {:?}, status: Status::{:?} }},",
+                 entry.name, entry.url, entry.path, category(&entry.path), entry.features,
+                 entry.variant_of, status).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+}
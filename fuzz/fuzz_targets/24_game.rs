@@ -0,0 +1,18 @@
+// The infix expressions of the 24 game: parsing and evaluating them, and checking which numbers
+// they use.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+
+#[path = "../../src/games/24_game.rs"]
+#[allow(dead_code)]
+mod game_24;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = game_24::Parser::new(input).parse();
+        game_24::check_values(&mut [1, 2, 3, 4], input);
+    }
+});
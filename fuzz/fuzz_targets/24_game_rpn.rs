@@ -0,0 +1,16 @@
+// The RPN expressions of the 24 game.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+
+#[path = "../../src/games/24_game_rpn.rs"]
+#[allow(dead_code)]
+mod game_24_rpn;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = game_24_rpn::check_input(input, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+});
@@ -0,0 +1,18 @@
+// Decoding a contact from JSON.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+// json.rs's `use rustc_serialize::...` is resolved from the crate root
+extern crate rustc_serialize;
+
+#[path = "../../src/strings/json.rs"]
+#[allow(dead_code)]
+mod json;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = json::decode(input);
+    }
+});
@@ -0,0 +1,17 @@
+// Parsing S-expressions.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+
+#[path = "../../src/strings/s_expressions.rs"]
+#[allow(dead_code)]
+mod s_expressions;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = s_expressions::read(input);
+        let _ = s_expressions::read_all(input);
+    }
+});
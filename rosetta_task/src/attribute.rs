@@ -0,0 +1,133 @@
+// Reading what a #[rosetta_task(...)] attribute says.  The macro checks it with this when a task
+// is compiled, and build.rs includes this file as well, to find the attribute in each task's
+// source and put its status in the registry.
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Complete, // Solves the task as Rosetta Code sets it
+    Draft,    // Still being worked on
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Status::Complete => "complete",
+            Status::Draft => "draft",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attribute {
+    pub status: Status,
+    pub url: Option<String>, // The task's Rosetta Code page
+}
+
+// Parse the inside of the attribute, such as
+// `status = "draft", url = "http://rosettacode.org/wiki/24_game/Solve"`
+pub fn parse(args: &str) -> Result<Attribute, String> {
+    let mut status = None;
+    let mut url = None;
+    let mut rest = args.trim();
+    while !rest.is_empty() {
+        let key_end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                          .unwrap_or(rest.len());
+        let (key, after) = rest.split_at(key_end);
+        let after = after.trim_start().strip_prefix('=')
+                         .ok_or_else(|| format!("expected `{} = \"...\"`", key))?.trim_start();
+        let after = after.strip_prefix('"')
+                         .ok_or_else(|| format!("the value of {} should be a string", key))?;
+        let value_end = after.find('"').ok_or_else(|| format!("unterminated value of {}", key))?;
+        let value = &after[..value_end];
+        match key {
+            "status" if status.is_none() => {
+                status = Some(match value {
+                    "complete" => Status::Complete,
+                    "draft" => Status::Draft,
+                    _ => return Err(format!("status should be \"complete\" or \"draft\", not \
+                                             {:?}", value)),
+                });
+            }
+            "url" if url.is_none() => {
+                if !value.starts_with("http://rosettacode.org/wiki/") {
+                    return Err(format!("url should be a Rosetta Code page, not {:?}", value));
+                }
+                url = Some(value.to_string());
+            }
+            "status" | "url" => return Err(format!("{} is given twice", key)),
+            _ => return Err(format!("unknown key {:?}: expected status or url", key)),
+        }
+        rest = after[value_end + 1..].trim_start();
+        if let Some(after_comma) = rest.strip_prefix(',') {
+            rest = after_comma.trim_start();
+        } else if !rest.is_empty() {
+            return Err("expected a comma between the keys".to_string());
+        }
+    }
+    match status {
+        Some(status) => Ok(Attribute { status, url }),
+        None => Err("missing status = \"complete\" or \"draft\"".to_string()),
+    }
+}
+
+// The inside of the first #[rosetta_task(...)] attribute in `source`, if there is one.  It ends
+// at the first parenthesis outside a string, since URLs can have parentheses in them.
+pub fn find(source: &str) -> Option<&str> {
+    let start = source.find("#[rosetta_task(")? + "#[rosetta_task(".len();
+    let mut in_string = false;
+    for (i, c) in source[start..].char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            ')' if !in_string => return Some(&source[start..start + i]),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find, parse, Attribute, Status};
+
+    #[test]
+    fn attributes() {
+        let args = "status = \"draft\", url = \"http://rosettacode.org/wiki/24_game/Solve\"";
+        assert_eq!(parse(args),
+                   Ok(Attribute {
+                       status: Status::Draft,
+                       url: Some("http://rosettacode.org/wiki/24_game/Solve".to_string()),
+                   }));
+        // However the compiler spaces the tokens out
+        assert_eq!(parse("status=\"complete\" ,"),
+                   Ok(Attribute { status: Status::Complete, url: None }));
+        assert_eq!(Status::Draft.to_string(), "draft");
+    }
+
+    #[test]
+    fn mistakes() {
+        assert!(parse("").is_err());
+        assert!(parse("url = \"http://rosettacode.org/wiki/A\"").is_err());
+        assert!(parse("status = \"done\"").is_err());
+        assert!(parse("status = draft").is_err());
+        assert!(parse("status = \"draft\" url = \"http://rosettacode.org/wiki/A\"").is_err());
+        assert!(parse("status = \"draft\", status = \"complete\"").is_err());
+        assert!(parse("status = \"draft\", url = \"http://example.com\"").is_err());
+        assert_eq!(parse("status = \"draft\", author = \"me\""),
+                   Err("unknown key \"author\": expected status or url".to_string()));
+    }
+
+    #[test]
+    fn find_attribute() {
+        let url = "http://rosettacode.org/wiki/Arbitrary-precision_integers_(included)";
+        let source = format!("// A task\n#[rosetta_task(status = \"draft\",\n    url = {:?})]\n\
+                              fn main() {{}}\n", url);
+        let args = find(&source).unwrap();
+        assert!(args.starts_with("status = \"draft\",\n"));
+        assert!(args.ends_with("_(included)\""));
+        assert_eq!(parse(args).unwrap().url.as_deref(), Some(url));
+        assert_eq!(parse(args).unwrap().status, Status::Draft);
+        assert_eq!(find("fn main() {}\n"), None);
+    }
+}
@@ -0,0 +1,33 @@
+// The #[rosetta_task] attribute, which keeps track in the code of how far along each task is:
+//
+//     extern crate rosetta_task;
+//
+//     use rosetta_task::rosetta_task;
+//
+//     #[rosetta_task(status = "draft", url = "http://rosettacode.org/wiki/24_game/Solve")]
+//     pub fn solve(r: &mut [Rational], target_val: isize) -> Option<String> {
+//
+// The status is "complete" or "draft", and a task without the attribute is complete.  It goes on
+// any one item of the task that its tests compile too, so not main.  The attribute leaves the item
+// as it is: it's only checked here, so that a misspelt status or a URL that isn't a Rosetta Code
+// page stops the task from compiling, and build.rs reads it out of the source for the registry
+// (see src/rosetta_meta.rs).  `cargo xtask status` lists the drafts.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[allow(dead_code)] // attribute::find is only used by build.rs
+mod attribute;
+
+#[proc_macro_attribute]
+pub fn rosetta_task(args: TokenStream, item: TokenStream) -> TokenStream {
+    match attribute::parse(&args.to_string()) {
+        Ok(_) => item,
+        Err(message) => {
+            let mut error: TokenStream = format!("compile_error!({:?});", message).parse().unwrap();
+            error.extend(item);
+            error
+        }
+    }
+}
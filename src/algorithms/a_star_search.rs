@@ -0,0 +1,118 @@
+// Implements http://rosettacode.org/wiki/A*_search_algorithm
+// The task's 8 by 8 board, where a move goes to any of the eight squares around, costs 1 and
+// costs 100 into one of the barrier's squares.  The search is guided by how many moves the goal
+// would be away without the barrier, which never overestimates, so the path it finds is a
+// cheapest one.  The open squares are kept in utils::heap's NodeQueue, by their cost so far plus
+// that estimate.  With the trace feature, ROSETTA_TRACE=1 shows each square as it's taken from
+// the queue.
+#[macro_use]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::heap::NodeQueue;
+
+type Square = (usize, usize); // (x, y)
+
+const SIZE: usize = 8;
+
+static BARRIER: &[Square] = &[(2, 4), (2, 5), (2, 6), (3, 6), (4, 6), (5, 6), (5, 5), (5, 4),
+                              (5, 3), (5, 2), (4, 2), (3, 2)];
+
+fn cost(to: Square) -> u32 {
+    if BARRIER.contains(&to) { 100 } else { 1 }
+}
+
+// The fewest moves from `from` to `to` on an empty board
+fn estimate(from: Square, to: Square) -> u32 {
+    from.0.abs_diff(to.0).max(from.1.abs_diff(to.1)) as u32
+}
+
+fn neighbors((x, y): Square) -> Vec<Square> {
+    let mut squares = Vec::new();
+    for ny in y.saturating_sub(1)..(y + 2).min(SIZE) {
+        for nx in x.saturating_sub(1)..(x + 2).min(SIZE) {
+            if (nx, ny) != (x, y) {
+                squares.push((nx, ny));
+            }
+        }
+    }
+    squares
+}
+
+fn index((x, y): Square) -> usize {
+    y * SIZE + x
+}
+
+// A cheapest path from `start` to `goal`, including both, and what it costs
+fn search(start: Square, goal: Square) -> Option<(Vec<Square>, u32)> {
+    let mut cost_so_far = vec![u32::MAX; SIZE * SIZE];
+    let mut came_from: Vec<Option<Square>> = vec![None; SIZE * SIZE];
+    let mut open = NodeQueue::new(SIZE * SIZE);
+    cost_so_far[index(start)] = 0;
+    open.push_or_decrease(index(start), estimate(start, goal));
+    while let Some((node, priority)) = open.pop() {
+        let square = (node % SIZE, node / SIZE);
+        trace!("{:?}: cost {} so far, {} with the estimate", square, cost_so_far[node], priority);
+        if square == goal {
+            let mut path = vec![goal];
+            while let Some(previous) = came_from[index(*path.last().unwrap())] {
+                path.push(previous);
+            }
+            path.reverse();
+            return Some((path, cost_so_far[node]));
+        }
+        for next in neighbors(square) {
+            let next_cost = cost_so_far[node] + cost(next);
+            if next_cost < cost_so_far[index(next)] {
+                cost_so_far[index(next)] = next_cost;
+                came_from[index(next)] = Some(square);
+                open.push_or_decrease(index(next), next_cost + estimate(next, goal));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (path, cost) = search((0, 0), (SIZE - 1, SIZE - 1)).unwrap();
+    println!("Path: {:?}", path);
+    println!("Cost: {}", cost);
+    for y in 0..SIZE {
+        let row: String = (0..SIZE).map(|x| {
+            if path.contains(&(x, y)) {
+                'x'
+            } else if BARRIER.contains(&(x, y)) {
+                '#'
+            } else {
+                '.'
+            }
+        }).collect();
+        println!("{}", row);
+    }
+}
+
+#[test]
+fn test_search() {
+    let (path, cost) = search((0, 0), (7, 7)).unwrap();
+    assert_eq!(cost, 11);
+    assert_eq!(path.len(), 12);
+    assert_eq!((path[0], path[11]), ((0, 0), (7, 7)));
+    for step in path.windows(2) {
+        assert!(neighbors(step[0]).contains(&step[1]));
+        assert!(!BARRIER.contains(&step[1]));
+    }
+    // The barrier has a gap at (2, 3), so inside it is cheap to get to, but the barrier itself
+    // isn't
+    assert_eq!(search((0, 0), (4, 4)).map(|(_, cost)| cost), Some(4));
+    assert_eq!(search((0, 0), (5, 5)).map(|(_, cost)| cost), Some(104));
+}
+
+#[test]
+fn test_estimate() {
+    assert_eq!(estimate((0, 0), (7, 7)), 7);
+    assert_eq!(estimate((6, 1), (2, 3)), 4);
+    assert_eq!(neighbors((0, 0)), vec![(1, 0), (0, 1), (1, 1)]);
+    assert_eq!(neighbors((3, 3)).len(), 8);
+}
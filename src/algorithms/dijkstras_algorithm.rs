@@ -1,30 +1,19 @@
 // Implements http://rosettacode.org/wiki/Dijkstra's_algorithm
-#![allow(unused_features)]
-#![feature(core)]
-#![feature(std_misc)]
+// The queue of vertices to visit is utils::heap's NodeQueue, which lowers a vertex's distance in
+// place when a shorter path to it turns up.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
-use std::collections::{HashMap, BinaryHeap, LinkedList};
+use std::collections::HashMap;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::iter::repeat;
-use std::cmp::Ordering;
-use std::usize;
+use utils::heap::NodeQueue;
 
 type Node = usize;
 type Cost = usize;
 type Edge = (Node, Node);
 
 
-/// The DistPair struct is for the Priority Queue.
-#[derive(Eq, PartialEq, PartialOrd)]
-struct DistPair(Node, Cost);
-impl Ord for DistPair {
-    fn cmp(&self, other:&DistPair) -> Ordering {
-        let DistPair(_, dist_a) = *self;
-        let DistPair(_, dist_b) = *other;
-        dist_b.cmp(&dist_a) //Intentionally reversed
-    }
-}
-
 /// Graph structure, represented as an Adjancency List.
 struct Graph<'a> {
     vertices: Vec<&'a str>,
@@ -77,17 +66,17 @@ impl<'a> Graph<'a> {
         };
     }
 
-    /// Implements Dijkstra's Algorithm. This uses a Priority Queue to 
-    /// determine which vertex to visit first. Terminates on discovering 
+    /// Implements Dijkstra's Algorithm. This uses a Priority Queue to
+    /// determine which vertex to visit first. Terminates on discovering
     /// the target vertex.
     ///
     /// Returns vector of vertices representing the path, or an empty vector
     /// if there's no path, or if the source or target is not in the graph.
-    fn dijkstra(&'a self, source: &str, target: &str) -> Vec<&str> {
+    fn dijkstra(&'a self, source: &str, target: &str) -> Vec<&'a str> {
         let num_vert = self.vertices.len();
-        let mut dist:Vec<usize> = repeat(usize::MAX).take(num_vert).collect(); //Close enough to infinity
-        let mut prev:HashMap<Node, Node> = HashMap::new();
-        let mut queue:BinaryHeap<DistPair> = BinaryHeap::new();
+        let mut dist: Vec<Cost> = vec![Cost::MAX; num_vert]; //Close enough to infinity
+        let mut prev: Vec<Option<Node>> = vec![None; num_vert];
+        let mut queue: NodeQueue<Cost> = NodeQueue::new(num_vert);
 
         let source_idx = match self.vertex_index(source) {
             Some(idx) => idx,
@@ -100,48 +89,31 @@ impl<'a> Graph<'a> {
         };
 
         dist[source_idx] = 0;
-        queue.push(DistPair(source_idx, dist[source_idx]));
-
-        loop{
-            match queue.pop() {
-                None     => break,
-                Some(DistPair(u, dist_u)) => {
-                    for &v in &(self.adj_list[u]) {
-                        let cost_uv = match self.costs.get(&(u, v)) {
-                            Some(&x) => x,
-                            None     => usize::MAX,
-                        };
-                        let alt = dist_u + cost_uv;
-                        if alt < dist[v] {
-                            match prev.entry(v) {
-                                Vacant(entry) => {entry.insert(u);},
-                                Occupied(entry) => {
-                                    *entry.into_mut() = u;
-                                }
-                            }
-                            dist[v] = alt;
-                            queue.push(DistPair(v, dist[v]));
-                        }
-                        if v == target_idx { break; }
-                    }   
+        queue.push_or_decrease(source_idx, 0);
+
+        while let Some((u, dist_u)) = queue.pop() {
+            if u == target_idx { break; }
+            for &v in &self.adj_list[u] {
+                let alt = dist_u + self.costs[&(u, v)];
+                if alt < dist[v] {
+                    dist[v] = alt;
+                    prev[v] = Some(u);
+                    queue.push_or_decrease(v, alt);
                 }
-            };
+            }
         }
 
-        let mut temp_path:LinkedList<&str> = LinkedList::new();
+        if dist[target_idx] == Cost::MAX {
+            return Vec::new(); // Target can't be reached from source.
+        }
+        let mut path = vec![self.vertices[target_idx]];
         let mut curr = target_idx;
-        temp_path.push_front(self.vertices[curr]);
-        loop{
-            match prev.get(&curr){
-                Some(&parent) => {
-                    curr = parent;
-                    temp_path.push_front(self.vertices[curr]);
-                    if curr == source_idx { break; }
-                },
-                None => return Vec::new(),
-            }
+        while let Some(parent) = prev[curr] {
+            curr = parent;
+            path.push(self.vertices[curr]);
         }
-        return temp_path.into_iter().collect();
+        path.reverse();
+        path
     }
 }
 
@@ -158,6 +130,19 @@ fn test_dijkstras() {
     assert!(graph.dijkstra("a", "y").is_empty());
     assert!(graph.dijkstra("e", "y").is_empty());
     assert!(graph.dijkstra("a", "e").is_empty());
+    assert_eq!(graph.dijkstra("a", "a"), vec!["a"]);
+}
+
+#[test]
+fn test_shorter_path_found_later() {
+    // d is queued at 30 through the direct edge, then lowered to 22 through b and c
+    let mut graph = Graph::new();
+    graph.add_edge("a", "d", 30);
+    graph.add_edge("a", "b", 7);
+    graph.add_edge("b", "c", 10);
+    graph.add_edge("c", "d", 5);
+    graph.add_edge("d", "e", 1);
+    assert_eq!(graph.dijkstra("a", "e"), vec!["a", "b", "c", "d", "e"]);
 }
 
 #[cfg(not(test))]
@@ -1,4 +1,14 @@
-// http://rosettacode.org/wiki/Hash_join
+//! http://rosettacode.org/wiki/Hash_join
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::hash_join::hash_join;
+//!
+//! let ages = vec![(27, "Jonah"), (18, "Alan"), (28, "Glory")];
+//! let nemeses = vec![("Jonah", "Whales"), ("Alan", "Ghosts"), ("Alan", "Zombies")];
+//! assert_eq!(hash_join(ages, nemeses),
+//!            [(27, "Jonah", "Whales"), (18, "Alan", "Ghosts"), (18, "Alan", "Zombies")]);
+//! ```
 
 use std::collections::HashMap;
 
@@ -6,7 +16,7 @@ type LeftTable<'a> = Vec<(i64, &'a str)>;
 type RightTable<'a> = Vec<(&'a str, &'a str)>;
 type ResultTable<'a> = Vec<(i64, &'a str, &'a str)>;
 
-fn hash_join<'a>(s: LeftTable<'a>, r: RightTable<'a>) -> ResultTable<'a> {
+pub fn hash_join<'a>(s: LeftTable<'a>, r: RightTable<'a>) -> ResultTable<'a> {
     let mut hash_map: HashMap<&str, Vec<&str>> = HashMap::new();
     
     //hash phase
@@ -27,7 +37,7 @@ fn hash_join<'a>(s: LeftTable<'a>, r: RightTable<'a>) -> ResultTable<'a> {
            }
         }
     }
-    return result;
+    result
 }
 
 #[cfg(not(test))]
@@ -1,13 +1,14 @@
 
 // Implement data structures for a Huffman encoding tree:
 //   http://rosettacode.org/wiki/Huffman_coding
-#![feature(std_misc)]
+// The queue of trees to combine is utils::heap's PriorityQueue, by weight.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 use std::collections::HashMap;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::BinaryHeap;
-use std::cmp::Ordering;
-use std::cmp::Ordering::{Less, Equal, Greater};
+use utils::heap::PriorityQueue;
 
 // Each HNode has a weight, representing the sum of the frequencies for all its
 // children. It is either a leaf (containing a character), or a HTree
@@ -27,32 +28,6 @@ struct HTreeData {
     right: Box<HNode>,
 }
 
-// Implementing comparison traits (Ord and all its dependencies) such that
-// the HNode with the greatest weight is the smallest in a comparison. Basically
-// reversing all the comparison operators.
-impl Ord for HNode {
-    fn cmp(&self, other: &HNode) -> Ordering {
-        match self.weight.cmp(&other.weight) {
-            Less    => Greater,
-            Equal   => Equal,
-            Greater => Less,
-        }
-    }
-}
-
-impl PartialOrd for HNode {
-    fn partial_cmp(&self, other: &HNode) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Eq for HNode {}
-impl PartialEq for HNode {
-    fn eq(&self, other: &HNode) -> bool {
-        self.weight == other.weight
-    }
-}
-
 // Takes a non-empty string (function will fail if string is empty) and computes
 // the Huffman encoding tree for that string.
 fn huffman_tree(input: &str) -> HNode {
@@ -67,14 +42,14 @@ fn huffman_tree(input: &str) -> HNode {
     }
 
     // 2. For each (character, frequency) pair in the HashMap, add a Leaf to a
-    //    PriorityQueue
-    let mut queue = BinaryHeap::<HNode>::new();
+    //    PriorityQueue, lightest first
+    let mut queue = PriorityQueue::<usize, HNode>::new();
     for (ch, freq) in freq.iter() {
         let new_node = HNode{
             weight: *freq,
             item: HItem::Leaf(*ch),
         };
-        queue.push(new_node);
+        queue.push(new_node, *freq);
     }
 
     // 3. Pop two items with the least weight from the queue, combine them into
@@ -82,8 +57,8 @@ fn huffman_tree(input: &str) -> HNode {
     //    children's weight. Continue until one item is left on the queue, and
     //    return that item.
     while queue.len() > 1 {
-        let item1 = queue.pop().unwrap();
-        let item2 = queue.pop().unwrap();
+        let (item1, _) = queue.pop().unwrap();
+        let (item2, _) = queue.pop().unwrap();
         let new_node = HNode {
             weight: item1.weight + item2.weight,
             item: HItem::Tree(HTreeData{
@@ -91,9 +66,10 @@ fn huffman_tree(input: &str) -> HNode {
                 right: Box::new(item2),
             }),
         };
-        queue.push(new_node);
+        let weight = new_node.weight;
+        queue.push(new_node, weight);
     }
-    queue.pop().unwrap()
+    queue.pop().unwrap().0
 }
 
 // Takes a Huffman Tree, traverse it and build a table with each character and
@@ -178,9 +154,9 @@ fn test_table_construction() {
     let tree = huffman_tree(to_encode);
     let mut table = HashMap::<char,String>::new();
     build_encoding_table(&tree, &mut table, "");
-    let one  = &*table['1'];
-    let two  = &*table['2'];
-    let four = &*table['4'];
+    let one  = &*table[&'1'];
+    let two  = &*table[&'2'];
+    let four = &*table[&'4'];
     assert!(four == "1");
     assert!((one == "01" && two == "00") ||
             (one == "00" && two == "01"));
@@ -1,17 +1,20 @@
 // Implements http://rosettacode.org/wiki/LZW_compression
-#![allow(unused_features)]
-#![feature(collections)]
 
 use std::collections::hash_map::HashMap;
 
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
 // Compress using LZW
 fn compress(original_str: &str) -> Vec<i32> {
    let original = original_str.as_bytes();
    let mut dict_size = 256;
    let mut dictionary = HashMap::new();
 
-   for i in (0i32..dict_size) {
-      dictionary.insert(vec!(i as u8), i);
+   for i in 0i32..dict_size {
+      dictionary.insert(vec![i as u8], i);
    }
 
    let mut result = vec![];
@@ -23,7 +26,7 @@ fn compress(original_str: &str) -> Vec<i32> {
       match dictionary.get(&wc) {
          Some(_) => w = wc,
          None => {
-            result.push(dictionary[w]);
+            result.push(dictionary[&w]);
             dictionary.insert(wc, dict_size);
             dict_size += 1;
             w = vec![c];
@@ -31,8 +34,8 @@ fn compress(original_str: &str) -> Vec<i32> {
       }
    }
 
-   if w.len() > 0 {
-      result.push(dictionary[w]);
+   if !w.is_empty() {
+      result.push(dictionary[&w]);
    }
 
    result
@@ -40,25 +43,30 @@ fn compress(original_str: &str) -> Vec<i32> {
 
 // Decompress using LZW
 fn decompress(compressed: &[i32]) -> String {
+   // Nothing compresses to nothing
+   if compressed.is_empty() {
+      return String::new();
+   }
+
    let mut dict_size = 256;
    let mut dictionary = HashMap::new();
 
-   for i in (0i32..dict_size) {
+   for i in 0i32..dict_size {
       dictionary.insert(i, vec![i as u8]);
    }
 
-   let mut w = vec![compressed[0].clone() as u8];
+   let mut w = vec![compressed[0] as u8];
    let compressed = &compressed[1..];
    let mut result = w.clone();
    for &k in compressed {
       let entry = match dictionary.get(&k) {
           Some(v) => v.clone(),
-          None if k == dict_size => { let mut new = w.clone(); new.push(w[0].clone()); new }
+          None if k == dict_size => { let mut new = w.clone(); new.push(w[0]); new }
           None => panic!("Invalid compressed string")
       };
 
-      result.extend(entry.iter().map(|&x| x.clone()));
-      w.push(entry[0].clone());
+      result.extend(entry.iter().cloned());
+      w.push(entry[0]);
       dictionary.insert(dict_size, w);
       dict_size += 1;
       w = entry;
@@ -86,10 +94,15 @@ fn main() {
 fn test_coherence() {
     for s in (50000i32..50100).map(|n| n.to_string()) {
         let s = &s[..];
-        assert_eq!(&*decompress(&*compress(s)), s);
+        assert_eq!(&*decompress(&compress(s)), s);
     }
 }
 
+#[test]
+fn test_any_coherence() {
+    utils::property::roundtrips(|s: &String| compress(s), |c| decompress(c));
+}
+
 #[test]
 fn test_example() {
     let original = "TOBEORNOTTOBEORTOBEORNOT";
@@ -0,0 +1,232 @@
+//! http://rosettacode.org/wiki/Compiler/AST_interpreter
+//!
+//! Runs the flattened syntax tree from the syntax analyzer (parser.rs) as it is, rather than
+//! compiling it for the virtual machine (vm.rs) first.  It reads the tree back in with the
+//! parser's own read_ast(), into the same Tree, and walks it, with the same semantics as the
+//! virtual machine: 32-bit numbers that wrap around, variables that start out at 0, 1 for true and
+//! 0 for false, and strings with their \n and \\ escapes decoded as they're printed.
+//!
+//! ```text
+//!     ast_interp [FILE]
+//! ```
+//!
+//! It reads the syntax tree from the file, or standard input, so a program can be run with
+//!
+//! ```text
+//!     lexer program.t | parser | ast_interp
+//! ```
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::ast_interp::interpret;
+//! use tasks::ast_interp::parser::lexer::lex;
+//! use tasks::ast_interp::parser::parse;
+//!
+//! let tree = parse(&lex("x = 6 * 7; print(\"x is \", x, \"\\n\");").unwrap()).unwrap();
+//! let mut output = Vec::new();
+//! interpret(&tree, &mut output).unwrap();
+//! assert_eq!(output, b"x is 42\n");
+//! ```
+
+use std::collections::HashMap;
+#[cfg(not(test))]
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+#[allow(dead_code)]
+#[path = "parser.rs"]
+pub mod parser;
+
+use self::parser::{Node, Tree};
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+struct Interpreter<W> {
+    variables: HashMap<String, i32>,
+    output: W,
+}
+
+impl<W: Write> Interpreter<W> {
+    // Run a statement
+    fn run(&mut self, tree: &Tree) -> io::Result<()> {
+        match *tree {
+            Tree::Empty => {}
+            Tree::Node(Node::Sequence, ref first, ref then) => {
+                self.run(first)?;
+                self.run(then)?;
+            }
+            Tree::Node(Node::Assign, ref variable, ref value) => {
+                let name = match **variable {
+                    Tree::Identifier(ref name) => name.clone(),
+                    ref tree => return Err(invalid(format!("assigning to {:?}", tree))),
+                };
+                let value = self.evaluate(value)?;
+                self.variables.insert(name, value);
+            }
+            Tree::Node(Node::If, ref condition, ref branches) => {
+                let (then, otherwise) = match **branches {
+                    Tree::Node(Node::If, ref then, ref otherwise) => (then, otherwise),
+                    ref tree => return Err(invalid(format!("an If whose branches are {:?}", tree))),
+                };
+                if self.evaluate(condition)? != 0 {
+                    self.run(then)?;
+                } else {
+                    self.run(otherwise)?;
+                }
+            }
+            Tree::Node(Node::While, ref condition, ref body) => {
+                while self.evaluate(condition)? != 0 {
+                    self.run(body)?;
+                }
+            }
+            Tree::Node(Node::Prtc, ref character, _) => {
+                let value = self.evaluate(character)?;
+                let c = char::from_u32(value as u32)
+                    .ok_or_else(|| invalid(format!("{} isn't a character", value)))?;
+                write!(self.output, "{}", c)?;
+            }
+            Tree::Node(Node::Prts, ref string, _) => match **string {
+                Tree::String(ref text) => write!(self.output, "{}", decode(text))?,
+                ref tree => return Err(invalid(format!("printing {:?} as a string", tree))),
+            },
+            Tree::Node(Node::Prti, ref value, _) => {
+                let value = self.evaluate(value)?;
+                write!(self.output, "{}", value)?;
+            }
+            ref tree => return Err(invalid(format!("{:?} isn't a statement", tree))),
+        }
+        Ok(())
+    }
+
+    // Work out the value of an expression
+    fn evaluate(&mut self, tree: &Tree) -> io::Result<i32> {
+        let (node, left, right) = match *tree {
+            Tree::Integer(value) => return Ok(value),
+            Tree::Identifier(ref name) => return Ok(self.variables.get(name).cloned().unwrap_or(0)),
+            Tree::Node(node, ref left, ref right) => (node, left, right),
+            ref tree => return Err(invalid(format!("{:?} isn't an expression", tree))),
+        };
+        let left = self.evaluate(left)?;
+        let value = match node {
+            Node::Negate => left.wrapping_neg(),
+            Node::Not => (left == 0) as i32,
+            // Both sides are always worked out, as in the code the virtual machine runs
+            _ => {
+                let right = self.evaluate(right)?;
+                match node {
+                    Node::Add => left.wrapping_add(right),
+                    Node::Subtract => left.wrapping_sub(right),
+                    Node::Multiply => left.wrapping_mul(right),
+                    Node::Divide | Node::Mod if right == 0 => {
+                        return Err(invalid("division by zero".to_string()));
+                    }
+                    Node::Divide => left.wrapping_div(right),
+                    Node::Mod => left.wrapping_rem(right),
+                    Node::Less => (left < right) as i32,
+                    Node::Greater => (left > right) as i32,
+                    Node::LessEqual => (left <= right) as i32,
+                    Node::GreaterEqual => (left >= right) as i32,
+                    Node::Equal => (left == right) as i32,
+                    Node::NotEqual => (left != right) as i32,
+                    Node::And => (left != 0 && right != 0) as i32,
+                    Node::Or => (left != 0 || right != 0) as i32,
+                    node => return Err(invalid(format!("{} isn't an expression", node.name()))),
+                }
+            }
+        };
+        Ok(value)
+    }
+}
+
+// A string of the program, with its escapes decoded
+fn decode(string: &str) -> String {
+    let mut decoded = String::new();
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        decoded.push(match c {
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some(c) => c,
+                None => '\\',
+            },
+            c => c,
+        });
+    }
+    decoded
+}
+
+// Run the program whose syntax tree is `tree`, writing what it prints to `output`
+pub fn interpret(tree: &Tree, output: impl Write) -> io::Result<()> {
+    let mut interpreter = Interpreter { variables: HashMap::new(), output };
+    interpreter.run(tree)?;
+    interpreter.output.flush()
+}
+
+// Read a flattened syntax tree from `input`, and run it, writing what it prints to `output`
+pub fn run(mut input: impl Read, output: impl Write) -> io::Result<()> {
+    let mut flattened = String::new();
+    input.read_to_string(&mut flattened)?;
+    interpret(&parser::read_ast(&flattened).map_err(invalid)?, output)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let result = match std::env::args().nth(1) {
+        Some(file) => File::open(&file).and_then(|file| run(file, io::stdout())),
+        None => run(io::stdin(), io::stdout()),
+    };
+    if let Err(err) = result {
+        eprintln!("ast_interp: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parser::lexer::utils::capture::{error_of, output_of, Captured};
+    use super::parser::lexer::utils::fixture;
+    use super::parser::lexer::{lex, PROGRAMS};
+    use super::parser::parse;
+    use super::{interpret, run};
+    use std::io;
+
+    fn interpreted(source: &str) -> io::Result<String> {
+        let output = Captured::new();
+        interpret(&parse(&lex(source)?)?, output.clone())?;
+        Ok(output.text())
+    }
+
+    #[test]
+    fn sample_programs() {
+        // They print what they do when compiled and run on the virtual machine
+        for program in PROGRAMS {
+            let ast = fixture::read(&format!("compiler/{}.ast", program));
+            let printed = fixture::read(&format!("compiler/{}.out", program));
+            assert_eq!(output_of(run, &ast), printed, "{}.ast", program);
+        }
+    }
+
+    #[test]
+    fn semantics() {
+        let printed = |source| interpreted(source).unwrap();
+        assert_eq!(printed("print(x, \" \", -7 / 2, \" \", -7 % 2);"), "0 -3 -1");
+        assert_eq!(printed("x = 2147483647; print(x + 1, \" \", x * 2);"), "-2147483648 -2");
+        assert_eq!(printed("print(3 && 4, 0 || -1, !5, 1 != 2);"), "1101");
+        let countdown = "i = 3; while (i) { putc('0' + i); i = i - 1; } if (i) ; else putc(10);";
+        assert_eq!(printed(countdown), "321\n");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(interpreted("x = 0; print(1 % x);").unwrap_err().to_string(),
+                   "division by zero");
+        assert_eq!(error_of(run, "Prts\nInteger 1\n;\n").to_string(),
+                   "printing Integer(1) as a string");
+        assert_eq!(error_of(run, "Add\nInteger 1\nInteger 2\n").to_string(),
+                   "Node(Add, Integer(1), Integer(2)) isn't a statement");
+        assert_eq!(error_of(run, "Prti\nSequence\nInteger 1\nInteger 2\n;\n").to_string(),
+                   "Sequence isn't an expression");
+    }
+}
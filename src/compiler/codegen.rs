@@ -0,0 +1,472 @@
+//! http://rosettacode.org/wiki/Compiler/code_generator
+//!
+//! The third stage of the compiler series, which reads the flattened syntax tree from the syntax
+//! analyzer (parser.rs) and compiles it for the stack machine of the last stage (vm.rs).  It
+//! writes the code out as a listing, which starts with how many variables and strings the program
+//! has and the strings themselves, and then has an instruction to a line, each with its address:
+//!
+//! ```text
+//!     Datasize: 1 Strings: 2
+//!     "count is: "
+//!     "\n"
+//!         0 push  1
+//!         5 store [0]
+//!        10 fetch [0]
+//!         ...
+//!        21 jz     (43) 65
+//! ```
+//!
+//! Variables and strings are numbered in the order they first appear.  Every instruction is a byte
+//! of opcode, and those with an operand are followed by 4 more bytes of it, a signed 32-bit number
+//! in little-endian order.  A jump's operand is where it goes to, relative to the operand itself,
+//! which the listing gives in brackets before the address it works out to.  The virtual machine
+//! reads the listing back in with read_code().
+//!
+//! ```text
+//!     codegen [FILE]
+//! ```
+//!
+//! It reads the syntax tree from the file, or standard input.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::codegen::parser::lexer::lex;
+//! use tasks::codegen::parser::parse;
+//! use tasks::codegen::{generate, read_code};
+//!
+//! let code = generate(&parse(&lex("x = 1 + 2;").unwrap()).unwrap()).unwrap();
+//! let listing = code.to_string();
+//! assert_eq!(listing, concat!("Datasize: 1 Strings: 0\n    0 push  1\n    5 push  2\n",
+//!                            "   10 add\n   11 store [0]\n   16 halt\n"));
+//! assert_eq!(read_code(&listing), Ok(code));
+//! ```
+
+use std::fmt;
+#[cfg(not(test))]
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+#[allow(dead_code)]
+#[path = "parser.rs"]
+pub mod parser;
+
+use self::parser::{Node, Tree};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Fetch,
+    Store,
+    Push,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+    Neg,
+    Not,
+    Jmp,
+    Jz,
+    Prtc,
+    Prts,
+    Prti,
+    Halt,
+}
+
+// Each instruction, with its name in the listing, in the order of their opcodes
+static OPS: &[(Op, &str)] = &[
+    (Op::Fetch, "fetch"),
+    (Op::Store, "store"),
+    (Op::Push, "push"),
+    (Op::Add, "add"),
+    (Op::Sub, "sub"),
+    (Op::Mul, "mul"),
+    (Op::Div, "div"),
+    (Op::Mod, "mod"),
+    (Op::Lt, "lt"),
+    (Op::Gt, "gt"),
+    (Op::Le, "le"),
+    (Op::Ge, "ge"),
+    (Op::Eq, "eq"),
+    (Op::Ne, "ne"),
+    (Op::And, "and"),
+    (Op::Or, "or"),
+    (Op::Neg, "neg"),
+    (Op::Not, "not"),
+    (Op::Jmp, "jmp"),
+    (Op::Jz, "jz"),
+    (Op::Prtc, "prtc"),
+    (Op::Prts, "prts"),
+    (Op::Prti, "prti"),
+    (Op::Halt, "halt"),
+];
+
+// The instruction each operator compiles to
+static OPERATORS: &[(Node, Op)] = &[
+    (Node::Multiply, Op::Mul),
+    (Node::Divide, Op::Div),
+    (Node::Mod, Op::Mod),
+    (Node::Add, Op::Add),
+    (Node::Subtract, Op::Sub),
+    (Node::Less, Op::Lt),
+    (Node::LessEqual, Op::Le),
+    (Node::Greater, Op::Gt),
+    (Node::GreaterEqual, Op::Ge),
+    (Node::Equal, Op::Eq),
+    (Node::NotEqual, Op::Ne),
+    (Node::And, Op::And),
+    (Node::Or, Op::Or),
+    (Node::Negate, Op::Neg),
+    (Node::Not, Op::Not),
+    (Node::Prtc, Op::Prtc),
+    (Node::Prts, Op::Prts),
+    (Node::Prti, Op::Prti),
+];
+
+impl Op {
+    pub fn name(self) -> &'static str {
+        OPS.iter().find(|&&(op, _)| op == self).unwrap().1
+    }
+
+    fn from_name(name: &str) -> Option<Op> {
+        OPS.iter().find(|&&(_, known)| known == name).map(|&(op, _)| op)
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Op> {
+        OPS.get(byte as usize).map(|&(op, _)| op)
+    }
+
+    pub fn has_operand(self) -> bool {
+        matches!(self, Op::Fetch | Op::Store | Op::Push | Op::Jmp | Op::Jz)
+    }
+
+    // How many bytes the instruction takes up
+    pub fn size(self) -> usize {
+        if self.has_operand() { 5 } else { 1 }
+    }
+}
+
+// A compiled program
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Code {
+    pub data_size: usize,
+    pub strings: Vec<String>, // As they were written in the program, escapes and all
+    pub bytes: Vec<u8>,
+}
+
+impl Code {
+    // The operand of the instruction at `address`
+    pub fn operand(&self, address: usize) -> i32 {
+        let mut operand = [0; 4];
+        operand.copy_from_slice(&self.bytes[address + 1..address + 5]);
+        i32::from_le_bytes(operand)
+    }
+
+    fn emit(&mut self, op: Op) {
+        self.bytes.push(OPS.iter().position(|&(known, _)| known == op).unwrap() as u8);
+    }
+
+    fn emit_with(&mut self, op: Op, operand: i32) {
+        self.emit(op);
+        self.bytes.extend_from_slice(&operand.to_le_bytes());
+    }
+
+    // Emit a jump whose operand is a hole to fill in with fix() once it's known where it goes
+    fn emit_jump(&mut self, op: Op) -> usize {
+        self.emit_with(op, 0);
+        self.bytes.len() - 4
+    }
+
+    // Fill in the jump at `hole` so that it goes to `target`
+    fn fix(&mut self, hole: usize, target: usize) {
+        let offset = target as i32 - hole as i32;
+        self.bytes[hole..hole + 4].copy_from_slice(&offset.to_le_bytes());
+    }
+}
+
+// The listing
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Datasize: {} Strings: {}", self.data_size, self.strings.len())?;
+        for string in &self.strings {
+            writeln!(f, "\"{}\"", string)?;
+        }
+        let mut address = 0;
+        while address < self.bytes.len() {
+            let op = Op::from_byte(self.bytes[address]).expect("not an opcode");
+            write!(f, "{:5} {}", address, op.name())?;
+            match op {
+                Op::Fetch | Op::Store => write!(f, " [{}]", self.operand(address))?,
+                Op::Push => write!(f, "  {}", self.operand(address))?,
+                Op::Jmp | Op::Jz => {
+                    let offset = self.operand(address);
+                    let padding = 7 - op.name().len();
+                    write!(f, "{:padding$}({}) {}", "", offset, address as i32 + 1 + offset)?;
+                }
+                _ => {}
+            }
+            writeln!(f)?;
+            address += op.size();
+        }
+        Ok(())
+    }
+}
+
+struct Generator {
+    code: Code,
+    variables: Vec<String>,
+}
+
+impl Generator {
+    fn variable(&mut self, name: &str) -> i32 {
+        let index = match self.variables.iter().position(|known| known == name) {
+            Some(index) => index,
+            None => {
+                self.variables.push(name.to_string());
+                self.variables.len() - 1
+            }
+        };
+        index as i32
+    }
+
+    fn string(&mut self, text: &str) -> i32 {
+        let strings = &mut self.code.strings;
+        let index = match strings.iter().position(|known| known == text) {
+            Some(index) => index,
+            None => {
+                strings.push(text.to_string());
+                strings.len() - 1
+            }
+        };
+        index as i32
+    }
+
+    fn generate(&mut self, tree: &Tree) -> Result<(), String> {
+        match *tree {
+            Tree::Empty => {}
+            Tree::Identifier(ref name) => {
+                let variable = self.variable(name);
+                self.code.emit_with(Op::Fetch, variable);
+            }
+            Tree::Integer(value) => self.code.emit_with(Op::Push, value),
+            Tree::String(ref text) => {
+                let string = self.string(text);
+                self.code.emit_with(Op::Push, string);
+            }
+            Tree::Node(Node::Sequence, ref first, ref then) => {
+                self.generate(first)?;
+                self.generate(then)?;
+            }
+            Tree::Node(Node::Assign, ref variable, ref value) => {
+                let variable = match **variable {
+                    Tree::Identifier(ref name) => self.variable(name),
+                    ref tree => return Err(format!("assigning to {:?}", tree)),
+                };
+                self.generate(value)?;
+                self.code.emit_with(Op::Store, variable);
+            }
+            Tree::Node(Node::If, ref condition, ref branches) => {
+                let (then, otherwise) = match **branches {
+                    Tree::Node(Node::If, ref then, ref otherwise) => (then, otherwise),
+                    ref tree => return Err(format!("an If whose branches are {:?}", tree)),
+                };
+                self.generate(condition)?;
+                let to_otherwise = self.code.emit_jump(Op::Jz);
+                self.generate(then)?;
+                if **otherwise == Tree::Empty {
+                    self.code.fix(to_otherwise, self.code.bytes.len());
+                } else {
+                    let to_end = self.code.emit_jump(Op::Jmp);
+                    self.code.fix(to_otherwise, self.code.bytes.len());
+                    self.generate(otherwise)?;
+                    self.code.fix(to_end, self.code.bytes.len());
+                }
+            }
+            Tree::Node(Node::While, ref condition, ref body) => {
+                let start = self.code.bytes.len();
+                self.generate(condition)?;
+                let to_end = self.code.emit_jump(Op::Jz);
+                self.generate(body)?;
+                let to_start = self.code.emit_jump(Op::Jmp);
+                self.code.fix(to_start, start);
+                self.code.fix(to_end, self.code.bytes.len());
+            }
+            Tree::Node(node, ref left, ref right) => {
+                let op = OPERATORS.iter().find(|&&(known, _)| known == node).unwrap().1;
+                self.generate(left)?;
+                self.generate(right)?;
+                self.code.emit(op);
+            }
+        }
+        Ok(())
+    }
+}
+
+// Compile the program whose syntax tree is `tree`
+pub fn generate(tree: &Tree) -> Result<Code, String> {
+    let mut generator = Generator { code: Code::default(), variables: Vec::new() };
+    generator.generate(tree)?;
+    generator.code.emit(Op::Halt);
+    generator.code.data_size = generator.variables.len();
+    Ok(generator.code)
+}
+
+// Read a listing back in, as the virtual machine does.  The addresses have to be where the
+// instructions before them put them.
+pub fn read_code(listing: &str) -> Result<Code, String> {
+    let mut lines = listing.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or("no Datasize line")?;
+    let sizes: Vec<&str> = header.split_whitespace().collect();
+    let (data_size, strings) = match sizes[..] {
+        ["Datasize:", data_size, "Strings:", strings] => (data_size.parse(), strings.parse()),
+        _ => return Err(format!("bad header: {}", header)),
+    };
+    let bad_header = |_| format!("bad header: {}", header);
+    let mut code = Code { data_size: data_size.map_err(bad_header)?, ..Code::default() };
+    for _ in 0..strings.map_err(bad_header)? {
+        let line = lines.next().ok_or("the strings end too soon")?.trim();
+        if line.len() < 2 || !line.starts_with('"') || !line.ends_with('"') {
+            return Err(format!("bad string: {}", line));
+        }
+        code.strings.push(line[1..line.len() - 1].to_string());
+    }
+    for line in lines {
+        let bad = || format!("bad instruction: {}", line.trim());
+        let mut words = line.split_whitespace();
+        let address: usize = words.next().and_then(|word| word.parse().ok()).ok_or_else(bad)?;
+        let op = words.next().and_then(Op::from_name).ok_or_else(bad)?;
+        if address != code.bytes.len() {
+            return Err(format!("{} is at {}, not {}", op.name(), code.bytes.len(), address));
+        }
+        let operand = words.next().map(|word| word.trim_matches(|c| "[]()".contains(c)));
+        match operand {
+            _ if !op.has_operand() => code.emit(op),
+            Some(operand) => code.emit_with(op, operand.parse().map_err(|_| bad())?),
+            None => return Err(bad()),
+        }
+    }
+    Ok(code)
+}
+
+// Read a flattened syntax tree from `input`, and write the listing of its code to `output`
+pub fn run(mut input: impl Read, mut output: impl Write) -> io::Result<()> {
+    let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut flattened = String::new();
+    input.read_to_string(&mut flattened)?;
+    let tree = parser::read_ast(&flattened).map_err(invalid)?;
+    write!(output, "{}", generate(&tree).map_err(invalid)?)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let result = match std::env::args().nth(1) {
+        Some(file) => File::open(&file).and_then(|file| run(file, io::stdout())),
+        None => run(io::stdin(), io::stdout()),
+    };
+    if let Err(err) = result {
+        eprintln!("codegen: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parser::lexer::utils::capture::{error_of, output_of};
+    use super::parser::lexer::utils::fixture;
+    use super::parser::lexer::{lex, PROGRAMS};
+    use super::parser::parse;
+    use super::{generate, read_code, run, Code};
+
+    fn compile(source: &str) -> Code {
+        generate(&parse(&lex(source).unwrap()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn sample_programs() {
+        for program in PROGRAMS {
+            let ast = fixture::read(&format!("compiler/{}.ast", program));
+            let listing = fixture::read(&format!("compiler/{}.asm", program));
+            assert_eq!(output_of(run, &ast), listing, "{}.ast", program);
+            // And the listing reads back in as the same code
+            let source = fixture::read(&format!("compiler/{}.t", program));
+            assert_eq!(read_code(&listing).unwrap(), compile(&source));
+        }
+    }
+
+    // The lines of a listing, put together
+    fn listing(lines: &[&str]) -> String {
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    #[test]
+    fn numbering() {
+        let code = compile("b = 1; a = b; print(\"x\", a, \"y\", \"x\", b);");
+        assert_eq!(code.data_size, 2);
+        assert_eq!(code.strings, ["x", "y"]);
+        assert_eq!(code.to_string(), listing(&[
+            "Datasize: 2 Strings: 2",
+            "\"x\"",
+            "\"y\"",
+            "    0 push  1",
+            "    5 store [0]",
+            "   10 fetch [0]",
+            "   15 store [1]",
+            "   20 push  0",
+            "   25 prts",
+            "   26 fetch [1]",
+            "   31 prti",
+            "   32 push  1",
+            "   37 prts",
+            "   38 push  0",
+            "   43 prts",
+            "   44 fetch [0]",
+            "   49 prti",
+            "   50 halt",
+        ]));
+        assert_eq!(compile("").to_string(), listing(&["Datasize: 0 Strings: 0", "    0 halt"]));
+    }
+
+    #[test]
+    fn jumps() {
+        // Each jump's operand is relative to itself, one byte into the instruction
+        assert_eq!(compile("if (1) putc(65); else { putc(66); }").to_string(), listing(&[
+            "Datasize: 0 Strings: 0",
+            "    0 push  1",
+            "    5 jz     (15) 21",
+            "   10 push  65",
+            "   15 prtc",
+            "   16 jmp    (10) 27",
+            "   21 push  66",
+            "   26 prtc",
+            "   27 halt",
+        ]));
+        assert_eq!(compile("while (0) ;").to_string(), listing(&[
+            "Datasize: 0 Strings: 0",
+            "    0 push  0",
+            "    5 jz     (9) 15",
+            "   10 jmp    (-11) 0",
+            "   15 halt",
+        ]));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error_of(run, "Assign\nInteger 1\nInteger 2\n").to_string(),
+                   "assigning to Integer(1)");
+        assert_eq!(error_of(run, "Sequence\n;\n").to_string(), "the tree ends too soon");
+        let bad = |listing| read_code(listing).unwrap_err();
+        assert_eq!(bad("Datasize: 0\n"), "bad header: Datasize: 0");
+        assert_eq!(bad("Datasize: 0 Strings: 1\n    0 halt\n"), "bad string: 0 halt");
+        assert_eq!(bad("Datasize: 0 Strings: 0\n    0 push\n"), "bad instruction: 0 push");
+        assert_eq!(bad("Datasize: 0 Strings: 0\n    0 pop\n"), "bad instruction: 0 pop");
+        assert_eq!(bad("Datasize: 0 Strings: 0\n    0 push  1\n    1 halt\n"),
+                   "halt is at 5, not 1");
+    }
+}
@@ -0,0 +1,474 @@
+//! http://rosettacode.org/wiki/Compiler/lexical_analyzer
+//!
+//! The first stage of the compiler series, which compiles a small C-like language for the virtual
+//! machine of the last stage.  Each stage reads what the one before it wrote, so this one turns a
+//! program into the token stream the syntax analyzer (parser.rs) reads, a line for each token with
+//! the line and column it starts at:
+//!
+//! ```text
+//!         4      1 Keyword_print
+//!         4      6 LeftParen
+//!         4      7 String          "Hello, World!\n"
+//! ```
+//!
+//! Comments are dropped, keywords are told apart from identifiers, and a character literal such as
+//! 'a' or '\n' is an Integer of its character code.  A string is written out as it was in the
+//! source, escapes and all, with only \n and \\ allowed in it; the virtual machine decodes them in
+//! the end.  The later stages read the token stream back in with read_tokens().
+//!
+//! ```text
+//!     lexer [FILE]
+//! ```
+//!
+//! It reads the program from the file, or standard input.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::lexer::{lex, Kind};
+//!
+//! let tokens = lex("print(42);").unwrap();
+//! let kinds: Vec<Kind> = tokens.iter().map(|token| token.kind).collect();
+//! assert_eq!(kinds, [Kind::Print, Kind::LeftParen, Kind::Integer, Kind::RightParen,
+//!                    Kind::Semicolon, Kind::EndOfInput]);
+//! assert_eq!(tokens[2].text, "42");
+//! assert_eq!(lex("x = $;").unwrap_err().to_string(), "(1,5) error: unrecognized character '$'");
+//! ```
+
+use std::error;
+use std::fmt;
+#[cfg(not(test))]
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+// The later stages use it from here, since it can't be declared twice
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+// The sample programs in tests/fixtures/compiler/, which each stage is tested on
+#[cfg(test)]
+pub static PROGRAMS: &[&str] = &["hello", "count", "print", "gcd", "factorial", "fizzbuzz",
+                                 "primes", "operators"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    EndOfInput,
+    Multiply,
+    Divide,
+    Mod,
+    Add,
+    Subtract,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    Not,
+    Assign,
+    And,
+    Or,
+    If,
+    Else,
+    While,
+    Print,
+    Putc,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Semicolon,
+    Comma,
+    Identifier,
+    Integer,
+    String,
+}
+
+// Each kind of token, with its name in the token stream
+static NAMES: &[(Kind, &str)] = &[
+    (Kind::EndOfInput, "End_of_input"),
+    (Kind::Multiply, "Op_multiply"),
+    (Kind::Divide, "Op_divide"),
+    (Kind::Mod, "Op_mod"),
+    (Kind::Add, "Op_add"),
+    (Kind::Subtract, "Op_subtract"),
+    (Kind::Less, "Op_less"),
+    (Kind::LessEqual, "Op_lessequal"),
+    (Kind::Greater, "Op_greater"),
+    (Kind::GreaterEqual, "Op_greaterequal"),
+    (Kind::Equal, "Op_equal"),
+    (Kind::NotEqual, "Op_notequal"),
+    (Kind::Not, "Op_not"),
+    (Kind::Assign, "Op_assign"),
+    (Kind::And, "Op_and"),
+    (Kind::Or, "Op_or"),
+    (Kind::If, "Keyword_if"),
+    (Kind::Else, "Keyword_else"),
+    (Kind::While, "Keyword_while"),
+    (Kind::Print, "Keyword_print"),
+    (Kind::Putc, "Keyword_putc"),
+    (Kind::LeftParen, "LeftParen"),
+    (Kind::RightParen, "RightParen"),
+    (Kind::LeftBrace, "LeftBrace"),
+    (Kind::RightBrace, "RightBrace"),
+    (Kind::Semicolon, "Semicolon"),
+    (Kind::Comma, "Comma"),
+    (Kind::Identifier, "Identifier"),
+    (Kind::Integer, "Integer"),
+    (Kind::String, "String"),
+];
+
+static KEYWORDS: &[(&str, Kind)] = &[
+    ("if", Kind::If),
+    ("else", Kind::Else),
+    ("while", Kind::While),
+    ("print", Kind::Print),
+    ("putc", Kind::Putc),
+];
+
+impl Kind {
+    pub fn name(self) -> &'static str {
+        NAMES.iter().find(|&&(kind, _)| kind == self).unwrap().1
+    }
+
+    fn from_name(name: &str) -> Option<Kind> {
+        NAMES.iter().find(|&&(_, known)| known == name).map(|&(kind, _)| kind)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: Kind,
+    pub line: usize,
+    pub column: usize,
+    // The name of an Identifier, the value of an Integer in decimal, or a String as it was
+    // written, without its quotes; empty for every other kind
+    pub text: String,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:5}  {:5} ", self.line, self.column)?;
+        match self.kind {
+            Kind::Identifier => write!(f, "{:<15} {}", self.kind.name(), self.text),
+            Kind::Integer => write!(f, "{:<15}  {:>4}", self.kind.name(), self.text),
+            Kind::String => write!(f, "{:<15} \"{}\"", self.kind.name(), self.text),
+            kind => write!(f, "{}", kind.name()),
+        }
+    }
+}
+
+// An error in the program, with the line and column it's at
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{}) error: {}", self.line, self.column, self.message)
+    }
+}
+
+impl error::Error for Error {}
+
+// So that the stages' run() can hand the errors of the ones before them on as their own
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    at: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.at += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    // Take the next character if it's `c`
+    fn next_if(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Skip whitespace and comments
+    fn skip(&mut self) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('/') if self.chars.get(self.at + 1) == Some(&'*') => {
+                    let (line, column) = (self.line, self.column);
+                    self.next();
+                    self.next();
+                    loop {
+                        match self.next() {
+                            Some('*') if self.next_if('/') => break,
+                            Some(_) => {}
+                            None => return Err(Error {
+                                line,
+                                column,
+                                message: "end of input in comment".to_string(),
+                            }),
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    // The next token, which starts at `line` and `column`
+    fn token(&mut self, line: usize, column: usize) -> Result<Token, Error> {
+        let error = |message: String| Err(Error { line, column, message });
+        let token = |kind, text: String| Ok(Token { kind, line, column, text });
+        let c = match self.next() {
+            Some(c) => c,
+            None => return token(Kind::EndOfInput, String::new()),
+        };
+        let kind = match c {
+            '*' => Kind::Multiply,
+            '/' => Kind::Divide,
+            '%' => Kind::Mod,
+            '+' => Kind::Add,
+            '-' => Kind::Subtract,
+            '<' if self.next_if('=') => Kind::LessEqual,
+            '<' => Kind::Less,
+            '>' if self.next_if('=') => Kind::GreaterEqual,
+            '>' => Kind::Greater,
+            '=' if self.next_if('=') => Kind::Equal,
+            '=' => Kind::Assign,
+            '!' if self.next_if('=') => Kind::NotEqual,
+            '!' => Kind::Not,
+            '&' if self.next_if('&') => Kind::And,
+            '|' if self.next_if('|') => Kind::Or,
+            '(' => Kind::LeftParen,
+            ')' => Kind::RightParen,
+            '{' => Kind::LeftBrace,
+            '}' => Kind::RightBrace,
+            ';' => Kind::Semicolon,
+            ',' => Kind::Comma,
+            '\'' => {
+                let value = match self.next() {
+                    Some('\'') => return error("empty character constant".to_string()),
+                    Some('\\') => match self.next() {
+                        Some('n') => '\n',
+                        Some('\\') => '\\',
+                        Some(c) => return error(format!("unknown escape sequence \\{}", c)),
+                        None => return error("end of input in character constant".to_string()),
+                    },
+                    Some(c) => c,
+                    None => return error("end of input in character constant".to_string()),
+                };
+                if !self.next_if('\'') {
+                    return error("multi-character constant".to_string());
+                }
+                return token(Kind::Integer, (value as u32).to_string());
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match self.next() {
+                        Some('"') => return token(Kind::String, text),
+                        Some('\\') => match self.next() {
+                            Some(c) if c == 'n' || c == '\\' => {
+                                text.push('\\');
+                                text.push(c);
+                            }
+                            Some(c) => return error(format!("unknown escape sequence \\{}", c)),
+                            None => return error("end of input in string".to_string()),
+                        },
+                        Some('\n') => return error("end of line in string".to_string()),
+                        Some(c) => text.push(c),
+                        None => return error("end of input in string".to_string()),
+                    }
+                }
+            }
+            c if c.is_ascii_digit() => {
+                let mut text = c.to_string();
+                while let Some(c) = self.peek().filter(|&c| c.is_ascii_alphanumeric() || c == '_') {
+                    text.push(c);
+                    self.next();
+                }
+                if !text.chars().all(|c| c.is_ascii_digit()) {
+                    return error(format!("invalid number {}", text));
+                }
+                return match text.parse::<i32>() {
+                    Ok(value) => token(Kind::Integer, value.to_string()),
+                    Err(_) => error(format!("number {} is too big", text)),
+                };
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut text = c.to_string();
+                while let Some(c) = self.peek().filter(|&c| c.is_ascii_alphanumeric() || c == '_') {
+                    text.push(c);
+                    self.next();
+                }
+                return match KEYWORDS.iter().find(|&&(keyword, _)| keyword == text) {
+                    Some(&(_, kind)) => token(kind, String::new()),
+                    None => token(Kind::Identifier, text),
+                };
+            }
+            c => return error(format!("unrecognized character '{}'", c)),
+        };
+        token(kind, String::new())
+    }
+}
+
+// The tokens of `source`, ending with an EndOfInput
+pub fn lex(source: &str) -> Result<Vec<Token>, Error> {
+    let mut lexer = Lexer { chars: source.chars().collect(), at: 0, line: 1, column: 1 };
+    let mut tokens = Vec::new();
+    loop {
+        lexer.skip()?;
+        let token = lexer.token(lexer.line, lexer.column)?;
+        let end = token.kind == Kind::EndOfInput;
+        tokens.push(token);
+        if end {
+            return Ok(tokens);
+        }
+    }
+}
+
+// Split the first word off `s`, and what comes after it from the next word on
+fn word(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    (&s[..end], s[end..].trim_start())
+}
+
+// Read a token stream back in, as the syntax analyzer does
+pub fn read_tokens(stream: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    for line in stream.lines().filter(|line| !line.trim().is_empty()) {
+        let bad = || format!("bad token: {}", line.trim());
+        let (at_line, rest) = word(line);
+        let (at_column, rest) = word(rest);
+        let (name, value) = word(rest);
+        let kind = Kind::from_name(name).ok_or_else(bad)?;
+        let text = match kind {
+            Kind::Identifier if !value.is_empty() => value.to_string(),
+            Kind::Integer if value.parse::<i32>().is_ok() => value.to_string(),
+            Kind::String if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') => {
+                value[1..value.len() - 1].to_string()
+            }
+            Kind::Identifier | Kind::Integer | Kind::String => return Err(bad()),
+            _ if value.is_empty() => String::new(),
+            _ => return Err(bad()),
+        };
+        let line = at_line.parse().map_err(|_| bad())?;
+        let column = at_column.parse().map_err(|_| bad())?;
+        tokens.push(Token { kind, line, column, text });
+    }
+    Ok(tokens)
+}
+
+// Read a program from `input`, and write its tokens to `output`
+pub fn run(mut input: impl Read, mut output: impl Write) -> io::Result<()> {
+    let mut source = String::new();
+    input.read_to_string(&mut source)?;
+    for token in lex(&source)? {
+        writeln!(output, "{}", token)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(test))]
+fn main() {
+    let result = match std::env::args().nth(1) {
+        Some(file) => File::open(&file).and_then(|file| run(file, io::stdout())),
+        None => run(io::stdin(), io::stdout()),
+    };
+    if let Err(err) = result {
+        eprintln!("lexer: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lex, read_tokens, run, Kind, PROGRAMS};
+    use super::utils::capture::{error_of, output_of};
+    use super::utils::fixture;
+
+    #[test]
+    fn sample_programs() {
+        // tokens.t has every kind of token, though it isn't a program
+        for &program in PROGRAMS.iter().chain(&["tokens"]) {
+            let source = fixture::read(&format!("compiler/{}.t", program));
+            let tokens = fixture::read(&format!("compiler/{}.lex", program));
+            assert_eq!(output_of(run, &source), tokens, "{}.t", program);
+            // And the token stream reads back in as the same tokens
+            assert_eq!(read_tokens(&tokens).unwrap(), lex(&source).unwrap());
+        }
+    }
+
+    #[test]
+    fn tokens() {
+        let kinds = |source| {
+            lex(source).unwrap().iter().map(|token| token.kind).collect::<Vec<_>>()
+        };
+        assert_eq!(kinds("a<=b=!c!=d"), vec![Kind::Identifier, Kind::LessEqual, Kind::Identifier,
+                                             Kind::Assign, Kind::Not, Kind::Identifier,
+                                             Kind::NotEqual, Kind::Identifier, Kind::EndOfInput]);
+        assert_eq!(kinds("whiles/**/while"), vec![Kind::Identifier, Kind::While,
+                                                  Kind::EndOfInput]);
+        let texts = |source| lex(source).unwrap().into_iter().map(|token| token.text)
+                                        .collect::<Vec<_>>();
+        assert_eq!(texts("'a' '\\n' '\\\\' 2147483647"), ["97", "10", "92", "2147483647", ""]);
+        assert_eq!(texts("\"a\\nb\\\\\""), ["a\\nb\\\\", ""]);
+    }
+
+    #[test]
+    fn positions() {
+        let tokens = lex("if (x)\n\t{ /* a\ncomment */ y }").unwrap();
+        let positions: Vec<_> = tokens.iter().map(|token| (token.line, token.column)).collect();
+        assert_eq!(positions, [(1, 1), (1, 4), (1, 5), (1, 6), (2, 2), (3, 12), (3, 14), (3, 15)]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source| lex(source).unwrap_err().to_string();
+        assert_eq!(error("x = '';"), "(1,5) error: empty character constant");
+        assert_eq!(error("x = 'ab';"), "(1,5) error: multi-character constant");
+        assert_eq!(error("'\\t'"), "(1,1) error: unknown escape sequence \\t");
+        assert_eq!(error("print(\"\\q\")"), "(1,7) error: unknown escape sequence \\q");
+        assert_eq!(error("\n  \"abc\n\""), "(2,3) error: end of line in string");
+        assert_eq!(error("\"abc"), "(1,1) error: end of input in string");
+        assert_eq!(error("x /* never\nends"), "(1,3) error: end of input in comment");
+        assert_eq!(error("x = 12ab;"), "(1,5) error: invalid number 12ab");
+        assert_eq!(error("2147483648"), "(1,1) error: number 2147483648 is too big");
+        assert_eq!(error("a & b"), "(1,3) error: unrecognized character '&'");
+        assert_eq!(error("#"), "(1,1) error: unrecognized character '#'");
+        assert_eq!(error_of(run, "x = 1 $").to_string(),
+                   "(1,7) error: unrecognized character '$'");
+        assert_eq!(read_tokens("    1      1 Integer  x"), Err("bad token: 1      1 Integer  x"
+                                                                  .to_string()));
+        assert!(read_tokens("    1      1 Op_power").is_err());
+    }
+}
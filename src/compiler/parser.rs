@@ -0,0 +1,449 @@
+//! http://rosettacode.org/wiki/Compiler/syntax_analyzer
+//!
+//! The second stage of the compiler series, which reads the token stream from the lexical analyzer
+//! (lexer.rs) and writes out the program's abstract syntax tree, flattened: a node's name, and
+//! then its two children the same way, with a leaf giving its value after its name and a missing
+//! child written as ";".  So "count = 1;" on its own is
+//!
+//! ```text
+//!     Sequence
+//!     ;
+//!     Assign
+//!     Identifier    count
+//!     Integer       1
+//! ```
+//!
+//! Statements are parsed by recursive descent, and expressions by precedence climbing, with every
+//! binary operator associating to the left.  A sequence of statements is a left-leaning chain of
+//! Sequence nodes, starting from a missing one, as is the list of things to print, and an if
+//! statement's second child is another If, of the statements to run if it's true and if it isn't.
+//! The code generator and the AST interpreter read the tree back in with read_ast().
+//!
+//! ```text
+//!     parser [FILE]
+//! ```
+//!
+//! It reads the token stream from the file, or standard input.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::parser::lexer::lex;
+//! use tasks::parser::{parse, read_ast};
+//!
+//! let tree = parse(&lex("x = 1 + 2;").unwrap()).unwrap();
+//! let flattened = tree.to_string();
+//! assert_eq!(flattened, "Sequence\n;\nAssign\nIdentifier    x\n\
+//!                        Add\nInteger       1\nInteger       2\n");
+//! assert_eq!(read_ast(&flattened), Ok(tree));
+//! ```
+
+use std::fmt;
+#[cfg(not(test))]
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+#[allow(dead_code)]
+#[path = "lexer.rs"]
+pub mod lexer;
+
+use self::lexer::{Error, Kind, Token};
+
+// The nodes that have children
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Node {
+    Sequence,
+    If,
+    Prtc,
+    Prts,
+    Prti,
+    While,
+    Assign,
+    Negate,
+    Not,
+    Multiply,
+    Divide,
+    Mod,
+    Add,
+    Subtract,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+}
+
+static NAMES: &[(Node, &str)] = &[
+    (Node::Sequence, "Sequence"),
+    (Node::If, "If"),
+    (Node::Prtc, "Prtc"),
+    (Node::Prts, "Prts"),
+    (Node::Prti, "Prti"),
+    (Node::While, "While"),
+    (Node::Assign, "Assign"),
+    (Node::Negate, "Negate"),
+    (Node::Not, "Not"),
+    (Node::Multiply, "Multiply"),
+    (Node::Divide, "Divide"),
+    (Node::Mod, "Mod"),
+    (Node::Add, "Add"),
+    (Node::Subtract, "Subtract"),
+    (Node::Less, "Less"),
+    (Node::LessEqual, "LessEqual"),
+    (Node::Greater, "Greater"),
+    (Node::GreaterEqual, "GreaterEqual"),
+    (Node::Equal, "Equal"),
+    (Node::NotEqual, "NotEqual"),
+    (Node::And, "And"),
+    (Node::Or, "Or"),
+];
+
+// Each binary operator's token, with its node and its precedence: the higher, the tighter it binds
+static BINARY: &[(Kind, Node, u8)] = &[
+    (Kind::Multiply, Node::Multiply, 13),
+    (Kind::Divide, Node::Divide, 13),
+    (Kind::Mod, Node::Mod, 13),
+    (Kind::Add, Node::Add, 12),
+    (Kind::Subtract, Node::Subtract, 12),
+    (Kind::Less, Node::Less, 10),
+    (Kind::LessEqual, Node::LessEqual, 10),
+    (Kind::Greater, Node::Greater, 10),
+    (Kind::GreaterEqual, Node::GreaterEqual, 10),
+    (Kind::Equal, Node::Equal, 9),
+    (Kind::NotEqual, Node::NotEqual, 9),
+    (Kind::And, Node::And, 5),
+    (Kind::Or, Node::Or, 4),
+];
+
+// The precedence of the unary operators, which bind tighter than any binary one
+const UNARY: u8 = 14;
+
+impl Node {
+    pub fn name(self) -> &'static str {
+        NAMES.iter().find(|&&(node, _)| node == self).unwrap().1
+    }
+
+    fn from_name(name: &str) -> Option<Node> {
+        NAMES.iter().find(|&&(_, known)| known == name).map(|&(node, _)| node)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tree {
+    Empty,
+    Identifier(String),
+    Integer(i32),
+    String(String), // As it was written, escapes and all
+    Node(Node, Box<Tree>, Box<Tree>),
+}
+
+impl Tree {
+    fn node(node: Node, left: Tree, right: Tree) -> Tree {
+        Tree::Node(node, Box::new(left), Box::new(right))
+    }
+}
+
+// The flattened tree, a line for each node
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Tree::Empty => writeln!(f, ";"),
+            Tree::Identifier(ref name) => writeln!(f, "{:<14}{}", "Identifier", name),
+            Tree::Integer(value) => writeln!(f, "{:<14}{}", "Integer", value),
+            Tree::String(ref text) => writeln!(f, "{:<14}\"{}\"", "String", text),
+            Tree::Node(node, ref left, ref right) => {
+                writeln!(f, "{}", node.name())?;
+                write!(f, "{}{}", left, right)
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    at: usize,
+}
+
+impl<'a> Parser<'a> {
+    // The token stream always ends with End_of_input, which is never taken
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.at]
+    }
+
+    fn next(&mut self) -> &'a Token {
+        let token = self.peek();
+        if token.kind != Kind::EndOfInput {
+            self.at += 1;
+        }
+        token
+    }
+
+    fn error(token: &Token, message: String) -> Error {
+        Error { line: token.line, column: token.column, message }
+    }
+
+    fn expect(&mut self, kind: Kind, what: &str) -> Result<&'a Token, Error> {
+        let token = self.next();
+        if token.kind == kind {
+            Ok(token)
+        } else {
+            Err(Parser::error(token, format!("expecting {} {}, found {}", kind.name(), what,
+                                             token.kind.name())))
+        }
+    }
+
+    fn statement(&mut self) -> Result<Tree, Error> {
+        let token = self.next();
+        let tree = match token.kind {
+            Kind::Semicolon => Tree::Empty,
+            Kind::Identifier => {
+                self.expect(Kind::Assign, "in an assignment")?;
+                let value = self.expression(0)?;
+                self.expect(Kind::Semicolon, "after an assignment")?;
+                Tree::node(Node::Assign, Tree::Identifier(token.text.clone()), value)
+            }
+            Kind::While => {
+                let condition = self.parenthesized()?;
+                Tree::node(Node::While, condition, self.statement()?)
+            }
+            Kind::If => {
+                let condition = self.parenthesized()?;
+                let then = self.statement()?;
+                let otherwise = if self.peek().kind == Kind::Else {
+                    self.next();
+                    self.statement()?
+                } else {
+                    Tree::Empty
+                };
+                Tree::node(Node::If, condition, Tree::node(Node::If, then, otherwise))
+            }
+            Kind::Print => {
+                self.expect(Kind::LeftParen, "after print")?;
+                let mut list = Tree::Empty;
+                loop {
+                    let item = if self.peek().kind == Kind::String {
+                        Tree::node(Node::Prts, Tree::String(self.next().text.clone()), Tree::Empty)
+                    } else {
+                        Tree::node(Node::Prti, self.expression(0)?, Tree::Empty)
+                    };
+                    list = Tree::node(Node::Sequence, list, item);
+                    if self.peek().kind != Kind::Comma {
+                        break;
+                    }
+                    self.next();
+                }
+                self.expect(Kind::RightParen, "after what's printed")?;
+                self.expect(Kind::Semicolon, "after print")?;
+                list
+            }
+            Kind::Putc => {
+                let character = self.parenthesized()?;
+                self.expect(Kind::Semicolon, "after putc")?;
+                Tree::node(Node::Prtc, character, Tree::Empty)
+            }
+            Kind::LeftBrace => {
+                let mut block = Tree::Empty;
+                while self.peek().kind != Kind::RightBrace && self.peek().kind != Kind::EndOfInput {
+                    block = Tree::node(Node::Sequence, block, self.statement()?);
+                }
+                self.expect(Kind::RightBrace, "at the end of a block")?;
+                block
+            }
+            kind => return Err(Parser::error(token, format!("expecting the start of a statement, \
+                                                             found {}", kind.name()))),
+        };
+        Ok(tree)
+    }
+
+    fn parenthesized(&mut self) -> Result<Tree, Error> {
+        self.expect(Kind::LeftParen, "before a condition")?;
+        let tree = self.expression(0)?;
+        self.expect(Kind::RightParen, "after a condition")?;
+        Ok(tree)
+    }
+
+    // An expression whose binary operators all have at least the precedence `least`
+    fn expression(&mut self, least: u8) -> Result<Tree, Error> {
+        let token = self.next();
+        let mut tree = match token.kind {
+            Kind::Identifier => Tree::Identifier(token.text.clone()),
+            Kind::Integer => Tree::Integer(token.text.parse().unwrap()),
+            Kind::LeftParen => {
+                let tree = self.expression(0)?;
+                self.expect(Kind::RightParen, "after an expression")?;
+                tree
+            }
+            Kind::Add => self.expression(UNARY)?,
+            Kind::Subtract => Tree::node(Node::Negate, self.expression(UNARY)?, Tree::Empty),
+            Kind::Not => Tree::node(Node::Not, self.expression(UNARY)?, Tree::Empty),
+            kind => return Err(Parser::error(token, format!("expecting an expression, found {}",
+                                                            kind.name()))),
+        };
+        while let Some(&(_, node, precedence)) = BINARY.iter().find(|&&(kind, _, precedence)| {
+            kind == self.peek().kind && precedence >= least
+        }) {
+            self.next();
+            tree = Tree::node(node, tree, self.expression(precedence + 1)?);
+        }
+        Ok(tree)
+    }
+}
+
+// The syntax tree of the program whose tokens are `tokens`
+pub fn parse(tokens: &[Token]) -> Result<Tree, Error> {
+    assert_eq!(tokens.last().map(|token| token.kind), Some(Kind::EndOfInput),
+               "the tokens of a program end with End_of_input");
+    let mut parser = Parser { tokens, at: 0 };
+    let mut program = Tree::Empty;
+    while parser.peek().kind != Kind::EndOfInput {
+        program = Tree::node(Node::Sequence, program, parser.statement()?);
+    }
+    Ok(program)
+}
+
+// Read a flattened tree back in, as the code generator does
+pub fn read_ast(flattened: &str) -> Result<Tree, String> {
+    fn read<'a, I: Iterator<Item = &'a str>>(lines: &mut I) -> Result<Tree, String> {
+        let line = lines.next().ok_or("the tree ends too soon")?;
+        let (name, value) = match line.find(' ') {
+            Some(end) => (&line[..end], line[end..].trim()),
+            None => (line.trim(), ""),
+        };
+        let bad = || format!("bad node: {}", line);
+        Ok(match name {
+            ";" => Tree::Empty,
+            "Identifier" if !value.is_empty() => Tree::Identifier(value.to_string()),
+            "Integer" => Tree::Integer(value.parse().map_err(|_| bad())?),
+            "String" if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') => {
+                Tree::String(value[1..value.len() - 1].to_string())
+            }
+            _ => {
+                let node = Node::from_name(name).filter(|_| value.is_empty()).ok_or_else(bad)?;
+                let left = read(lines)?;
+                Tree::node(node, left, read(lines)?)
+            }
+        })
+    }
+    let mut lines = flattened.lines().filter(|line| !line.trim().is_empty());
+    let tree = read(&mut lines)?;
+    match lines.next() {
+        Some(line) => Err(format!("more after the end of the tree: {}", line)),
+        None => Ok(tree),
+    }
+}
+
+// Read a token stream from `input`, and write the flattened tree of its program to `output`
+pub fn run(mut input: impl Read, mut output: impl Write) -> io::Result<()> {
+    let mut stream = String::new();
+    input.read_to_string(&mut stream)?;
+    let tokens = lexer::read_tokens(&stream)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+    if tokens.last().map(|token| token.kind) != Some(Kind::EndOfInput) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "no End_of_input token"));
+    }
+    write!(output, "{}", parse(&tokens)?)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let result = match std::env::args().nth(1) {
+        Some(file) => File::open(&file).and_then(|file| run(file, io::stdout())),
+        None => run(io::stdin(), io::stdout()),
+    };
+    if let Err(err) = result {
+        eprintln!("parser: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::lexer::lex;
+    use super::lexer::utils::capture::{error_of, output_of};
+    use super::lexer::utils::fixture;
+    use super::{parse, read_ast, run, Node, Tree};
+
+    // The tree of `source`, flattened
+    fn flattened(source: &str) -> String {
+        parse(&lex(source).unwrap()).unwrap().to_string()
+    }
+
+    fn error(source: &str) -> String {
+        parse(&lex(source).unwrap()).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn sample_programs() {
+        for program in super::lexer::PROGRAMS {
+            let tokens = fixture::read(&format!("compiler/{}.lex", program));
+            let ast = fixture::read(&format!("compiler/{}.ast", program));
+            assert_eq!(output_of(run, &tokens), ast, "{}.lex", program);
+            // And the flattened tree reads back in as the same tree
+            let source = fixture::read(&format!("compiler/{}.t", program));
+            assert_eq!(read_ast(&ast).unwrap(), parse(&lex(&source).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        let tree = |source: &str| parse(&lex(&format!("x = {};", source)).unwrap()).unwrap();
+        let value = |source| match tree(source) {
+            Tree::Node(Node::Sequence, _, assign) => match *assign {
+                Tree::Node(Node::Assign, _, value) => *value,
+                tree => panic!("{:?}", tree),
+            },
+            tree => panic!("{:?}", tree),
+        };
+        let node = |node, left, right| Tree::Node(node, Box::new(left), Box::new(right));
+        let (a, b, c) = (Tree::Identifier("a".to_string()), Tree::Identifier("b".to_string()),
+                         Tree::Identifier("c".to_string()));
+        assert_eq!(value("a - b - c"),
+                   node(Node::Subtract, node(Node::Subtract, a.clone(), b.clone()), c.clone()));
+        assert_eq!(value("a + b * c"),
+                   node(Node::Add, a.clone(), node(Node::Multiply, b.clone(), c.clone())));
+        assert_eq!(value("a || b && c"),
+                   node(Node::Or, a.clone(), node(Node::And, b.clone(), c.clone())));
+        assert_eq!(value("-a * +b"),
+                   node(Node::Multiply, node(Node::Negate, a.clone(), Tree::Empty), b.clone()));
+        assert_eq!(value("!(a < b)"), node(Node::Not, node(Node::Less, a, b), Tree::Empty));
+        assert_eq!(value("--3"), node(Node::Negate, node(Node::Negate, Tree::Integer(3),
+                                                         Tree::Empty), Tree::Empty));
+    }
+
+    #[test]
+    fn statements() {
+        assert_eq!(flattened(""), ";\n");
+        assert_eq!(flattened(";"), "Sequence\n;\n;\n");
+        assert_eq!(flattened("if (a) {} else ;"),
+                   "Sequence\n;\nIf\nIdentifier    a\nIf\n;\n;\n");
+        assert_eq!(flattened("putc(65);"), "Sequence\n;\nPrtc\nInteger       65\n;\n");
+        assert_eq!(flattened("print(\"a\", 1);"),
+                   "Sequence\n;\nSequence\nSequence\n;\nPrts\nString        \"a\"\n;\n\
+                    Prti\nInteger       1\n;\n");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error("x = 1"), "(1,6) error: expecting Semicolon after an assignment, \
+                                    found End_of_input");
+        assert_eq!(error("x 1;"), "(1,3) error: expecting Op_assign in an assignment, found \
+                                   Integer");
+        assert_eq!(error("while x < 1 ;"), "(1,7) error: expecting LeftParen before a \
+                                            condition, found Identifier");
+        assert_eq!(error("print(1 2);"), "(1,9) error: expecting RightParen after what's \
+                                          printed, found Integer");
+        assert_eq!(error("x = (1 + );"), "(1,10) error: expecting an expression, found \
+                                          RightParen");
+        assert_eq!(error("{ x = 1;"), "(1,9) error: expecting RightBrace at the end of a block, \
+                                       found End_of_input");
+        assert_eq!(error("else;"), "(1,1) error: expecting the start of a statement, found \
+                                    Keyword_else");
+        assert_eq!(error("x = \"s\";"), "(1,5) error: expecting an expression, found String");
+        assert_eq!(error_of(run, "    1      1 Semicolon\n").to_string(), "no End_of_input token");
+        assert_eq!(read_ast("Sequence\n;\n"), Err("the tree ends too soon".to_string()));
+        assert_eq!(read_ast("Power\n;\n;\n"), Err("bad node: Power".to_string()));
+        assert_eq!(read_ast(";\n;\n"), Err("more after the end of the tree: ;".to_string()));
+    }
+}
@@ -0,0 +1,230 @@
+//! http://rosettacode.org/wiki/Compiler/virtual_machine_interpreter
+//!
+//! The last stage of the compiler series, which reads the listing from the code generator
+//! (codegen.rs), turns it back into code, and runs it.  The machine has a stack of signed 32-bit
+//! numbers, which arithmetic wraps around on as it would in C with two's complement, a slot for
+//! each of the program's variables, all starting out at 0, and its strings, which it decodes the
+//! \n and \\ escapes of when it prints them.  A comparison or a logical operator leaves 1 for true
+//! and 0 for false, and jz takes any number but 0 as true.
+//!
+//! ```text
+//!     vm [FILE]
+//! ```
+//!
+//! It reads the listing from the file, or standard input, and runs it, so a program can be
+//! compiled and run with
+//!
+//! ```text
+//!     lexer program.t | parser | codegen | vm
+//! ```
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::vm::codegen::parser::lexer::lex;
+//! use tasks::vm::codegen::parser::parse;
+//! use tasks::vm::codegen::generate;
+//! use tasks::vm::execute;
+//!
+//! let code = generate(&parse(&lex("x = 6 * 7; print(\"x is \", x, \"\\n\");").unwrap()).unwrap());
+//! let mut output = Vec::new();
+//! execute(&code.unwrap(), &mut output).unwrap();
+//! assert_eq!(output, b"x is 42\n");
+//! ```
+
+#[cfg(not(test))]
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+#[allow(dead_code)]
+#[path = "codegen.rs"]
+pub mod codegen;
+
+use self::codegen::{Code, Op};
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// A string of the program, with its escapes decoded
+fn decode(string: &str) -> String {
+    let mut decoded = String::new();
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        decoded.push(match c {
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some(c) => c,
+                None => '\\',
+            },
+            c => c,
+        });
+    }
+    decoded
+}
+
+// Run `code`, writing what it prints to `output`
+pub fn execute(code: &Code, mut output: impl Write) -> io::Result<()> {
+    let strings: Vec<String> = code.strings.iter().map(|string| decode(string)).collect();
+    let mut data = vec![0i32; code.data_size];
+    let mut stack: Vec<i32> = Vec::new();
+    let mut pc = 0;
+    loop {
+        let byte = *code.bytes.get(pc).ok_or_else(|| invalid(format!("no code at {}", pc)))?;
+        let op = Op::from_byte(byte).ok_or_else(|| invalid(format!("bad opcode at {}", pc)))?;
+        let at = pc;
+        pc += op.size();
+        let operand = if op.has_operand() { code.operand(at) } else { 0 };
+        let mut pop = || stack.pop().ok_or_else(|| invalid(format!("empty stack at {}", at)));
+        let no_variable = || invalid(format!("no variable {} at {}", operand, at));
+        match op {
+            Op::Fetch => {
+                let value = *data.get(operand as usize).ok_or_else(no_variable)?;
+                stack.push(value);
+            }
+            Op::Store => {
+                let value = pop()?;
+                *data.get_mut(operand as usize).ok_or_else(no_variable)? = value;
+            }
+            Op::Push => stack.push(operand),
+            Op::Neg => {
+                let value = pop()?;
+                stack.push(value.wrapping_neg());
+            }
+            Op::Not => {
+                let value = pop()?;
+                stack.push((value == 0) as i32);
+            }
+            Op::Jmp => pc = (at as i64 + 1 + operand as i64) as usize,
+            Op::Jz => {
+                if pop()? == 0 {
+                    pc = (at as i64 + 1 + operand as i64) as usize;
+                }
+            }
+            Op::Prtc => {
+                let value = pop()?;
+                let c = char::from_u32(value as u32)
+                    .ok_or_else(|| invalid(format!("{} isn't a character", value)))?;
+                write!(output, "{}", c)?;
+            }
+            Op::Prts => {
+                let index = pop()?;
+                let string = strings.get(index as usize)
+                    .ok_or_else(|| invalid(format!("no string {} at {}", index, at)))?;
+                write!(output, "{}", string)?;
+            }
+            Op::Prti => {
+                let value = pop()?;
+                write!(output, "{}", value)?;
+            }
+            Op::Halt => break,
+            _ => {
+                let right = pop()?;
+                let left = pop()?;
+                let result = match op {
+                    Op::Add => left.wrapping_add(right),
+                    Op::Sub => left.wrapping_sub(right),
+                    Op::Mul => left.wrapping_mul(right),
+                    Op::Div | Op::Mod if right == 0 => {
+                        return Err(invalid(format!("division by zero at {}", at)));
+                    }
+                    Op::Div => left.wrapping_div(right),
+                    Op::Mod => left.wrapping_rem(right),
+                    Op::Lt => (left < right) as i32,
+                    Op::Gt => (left > right) as i32,
+                    Op::Le => (left <= right) as i32,
+                    Op::Ge => (left >= right) as i32,
+                    Op::Eq => (left == right) as i32,
+                    Op::Ne => (left != right) as i32,
+                    Op::And => (left != 0 && right != 0) as i32,
+                    Op::Or => (left != 0 || right != 0) as i32,
+                    _ => unreachable!(),
+                };
+                stack.push(result);
+            }
+        }
+    }
+    output.flush()
+}
+
+// Read a listing from `input`, and run its code, writing what it prints to `output`
+pub fn run(mut input: impl Read, output: impl Write) -> io::Result<()> {
+    let mut listing = String::new();
+    input.read_to_string(&mut listing)?;
+    execute(&codegen::read_code(&listing).map_err(invalid)?, output)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let result = match std::env::args().nth(1) {
+        Some(file) => File::open(&file).and_then(|file| run(file, io::stdout())),
+        None => run(io::stdin(), io::stdout()),
+    };
+    if let Err(err) = result {
+        eprintln!("vm: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::codegen::generate;
+    use super::codegen::parser::lexer::utils::capture::{error_of, output_of, Captured};
+    use super::codegen::parser::lexer::utils::fixture;
+    use super::codegen::parser::lexer::{lex, PROGRAMS};
+    use super::codegen::parser::parse;
+    use super::{execute, run};
+    use std::io;
+
+    // Compile `source` and run it, all in one go
+    fn compile_and_run(source: &str) -> io::Result<String> {
+        let tree = parse(&lex(source)?)?;
+        let code = generate(&tree).unwrap();
+        let output = Captured::new();
+        execute(&code, output.clone())?;
+        Ok(output.text())
+    }
+
+    #[test]
+    fn sample_programs() {
+        for program in PROGRAMS {
+            let listing = fixture::read(&format!("compiler/{}.asm", program));
+            let printed = fixture::read(&format!("compiler/{}.out", program));
+            assert_eq!(output_of(run, &listing), printed, "{}.asm", program);
+            // And from the start, through every stage
+            let source = fixture::read(&format!("compiler/{}.t", program));
+            assert_eq!(compile_and_run(&source).unwrap(), printed, "{}.t", program);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let printed = |source| compile_and_run(source).unwrap();
+        assert_eq!(printed("print(7 / 2, \" \", -7 / 2, \" \", -7 % 2, \" \", 7 % -2);"),
+                   "3 -3 -1 1");
+        // Wrapping around, as 32-bit two's complement does
+        assert_eq!(printed("x = 2147483647; print(x + 1, \" \", -(x + 1), \" \", x * 2);"),
+                   "-2147483648 -2147483648 -2");
+        assert_eq!(printed("x = -2147483647 - 1; print(x / -1, \" \", x % -1);"),
+                   "-2147483648 0");
+        assert_eq!(printed("print(3 && 4, 3 && 0, 0 || 0, 0 || -1, !5, !0, 1 != 2, 2 <= 2);"),
+                   "10010111");
+        assert_eq!(printed("putc('h'); putc(105); print(\"\\\\n\\n\");"), "hi\\n\n");
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source| compile_and_run(source).unwrap_err().to_string();
+        assert_eq!(error("x = 0; print(1 / x);"), "division by zero at 20");
+        assert_eq!(error("putc(-1);"), "-1 isn't a character");
+        assert_eq!(error("print(1;"), "(1,8) error: expecting RightParen after what's printed, \
+                                       found Semicolon");
+        let error = |listing| error_of(run, listing).to_string();
+        assert_eq!(error("Datasize: 0 Strings: 0\n    0 add\n    1 halt\n"), "empty stack at 0");
+        assert_eq!(error("Datasize: 0 Strings: 0\n    0 fetch [0]\n    5 halt\n"),
+                   "no variable 0 at 0");
+        assert_eq!(error("Datasize: 0 Strings: 0\n    0 push  3\n    5 prts\n    6 halt\n"),
+                   "no string 3 at 5");
+        assert_eq!(error("Datasize: 0 Strings: 0\n    0 jmp    (10) 11\n"), "no code at 11");
+        assert_eq!(error("Datasize: 0\n"), "bad header: Datasize: 0");
+    }
+}
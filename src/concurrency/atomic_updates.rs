@@ -9,31 +9,25 @@
 // (previously I tried, in order, std::sync::RwLock, std::sync::Mutex, and std::sync::Semaphore)
 // and this type still appears to have quite a bit of overhead.
 
-#![feature(core)]
-#![feature(old_io)]
-#![feature(std_misc)] 
-extern crate rand;
-
-use std::old_io::timer::Timer;
-use std::iter::AdditiveIterator;
-use rand::{Rng, weak_rng};
-use rand::distributions::{IndependentSample, Range};
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::duration::Duration;
-use std::thread::spawn;
+use std::thread;
+use std::time::Duration;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::rng::Rng;
 
 // The reason I used a module here is simply to keep it clearer who can access what.  Rust
 // protects against data races just fine, but it's not as good at protecting against deadlocks or
 // other types of race conditions.
 mod buckets {
-    use std::sync::atomic::{AtomicUint, Ordering};
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Mutex;
 
-    // We hardcode the number of buckets mostly for convenience.  Now that Rust has dynamically
-    // sized types, this is possibly no longer a problem.
-
-    // To expand: in Rust, there are two special kinds, Sync and Send, used for concurrency.
+    // On sharing the buckets between threads: in Rust, there are two special kinds, Sync and
+    // Send, used for concurrency.
     //
     // If T is Sync, and you take an immutable reference of type &T, then it's safe to share between
     // threads.  Most types are Sync unless they contain non-threadsafe interior mutability: for
@@ -47,14 +41,9 @@ mod buckets {
     // safe to send a type between tasks if it has any non-static references, because one doesn't
     // know when the data it's referencing will be deallocated if it's on some other task's stack.
     //
-    // Usually, that's a reasonable assumption.  But it means that if we want to put a slice in a
-    // structure and share it between tasks, it can't be a &[T]--it has to be a bare [T].  Arc has
-    // Send + Sync bounds for this reason.  We could also use a Vec, but this would introduce
-    // unnecessary indirection.  With dynamically sized types, this problem may be solved--we can
-    // have a dynamically sized struct that doesn't contain explicit references.  But until that is
-    // fully baked, this seems like the sanest solution.
-    //
-    // (Another way to solve this would be associated constants, something Rust does not have yet).
+    // Usually, that's a reasonable assumption, and it would mean sharing the buckets through an
+    // Arc.  Scoped threads are the exception: they are guaranteed to finish before whatever they
+    // borrow goes away, so they can share a plain &Buckets from the stack.
     pub const N_BUCKETS: usize = 20;
 
     // We don't really have to hardcode the workers.  This is left over from the Go implementation.
@@ -63,38 +52,29 @@ mod buckets {
     pub const N_WORKERS: usize = 2;
 
     struct Bucket {
-        data: AtomicUint,       // The actual data.  It is atomic because it is read (not written)
-                                // outside the Mutex, unless a consistent snapshot is required.
-        mutex: Mutex<()>,       // The mutex used to synchronize writes and snapshot reads of the bucket.
-                                // As the D solution says, using a per-bucket Mutex dramatically improves
-                                // scalability compared to the alternatives.
+        data: AtomicUsize, // The actual data.  It is atomic because it is read (not written)
+                           // outside the Mutex, unless a consistent snapshot is required.
+        mutex: Mutex<()>,  // The mutex used to synchronize writes and snapshot reads of the
+                           // bucket.  As the D solution says, using a per-bucket Mutex
+                           // dramatically improves scalability compared to the alternatives.
     }
 
     pub struct Buckets {
-        buckets: [Bucket; N_BUCKETS ], // Buckets containing values to be transferred.
-        transfers: [AtomicUint; N_WORKERS ], // Statistics about total transfers this go-around.
+        buckets: [Bucket; N_BUCKETS],        // Buckets containing values to be transferred.
+        transfers: [AtomicUsize; N_WORKERS], // Statistics about total transfers this go-around.
     }
 
     impl Buckets {
         // Create a new Buckets instance.
         pub fn new(buckets: [usize; N_BUCKETS]) -> Buckets {
-            // The unsafe initialization here is required because Bucket is not Clone (it can't be,
-            // since neither AtomicUint nor Mutex are) and we would otherwise have to literally
-            // write out N_BUCKETS different values, which would be painful.  As a result, we have
-            // to be careful not to allow any failure here, or we'll segfault (by Drop-ing empty
-            // buckets).
-            let mut buckets_: [Bucket; N_BUCKETS] = unsafe { ::std::mem::uninitialized() };
-            let mut transfers: [AtomicUint; N_WORKERS] = unsafe { ::std::mem::uninitialized() };
-            for (dest, &src) in buckets_.iter_mut().zip(buckets.iter()){
-                let bucket = Bucket { data: AtomicUint::new(src), mutex: Mutex::new(()) };
-                // If we don't use an unsafe write(), the uninitialized mutex in the bucket will be
-                // dropped.
-                unsafe { ::std::ptr::write(dest as *mut _, bucket) }
+            // Bucket is not Copy (it can't be, since neither AtomicUsize nor Mutex are), so the
+            // arrays are built an element at a time rather than with [x; N].
+            Buckets {
+                buckets: buckets.map(|value| {
+                    Bucket { data: AtomicUsize::new(value), mutex: Mutex::new(()) }
+                }),
+                transfers: ::std::array::from_fn(|_| AtomicUsize::new(0)),
             }
-            for t in (&mut transfers[..]).iter_mut() {
-                *t = AtomicUint::new(0);
-            }
-            Buckets { buckets: buckets_, transfers: transfers }
         }
 
         // Get the value of the bucket at index i, or None if out of bounds.
@@ -114,8 +94,8 @@ mod buckets {
             // We know this won't fail, and the compiler seems to know as well.  However, if it
             // *did* fail, it wouldn't fail while we were holding mutexes (which can cause
             // problems since they may need to poison other tasks).
-            let ref b1 = self.buckets[from];
-            let ref b2 = self.buckets[to];
+            let b1 = &self.buckets[from];
+            let b2 = &self.buckets[to];
             // It's very important to lock our Mutexes in the same order everywhere to avoid
             // deadlock.  We arbitrarily choose the convention that we lock in ascending index
             // order.
@@ -142,7 +122,7 @@ mod buckets {
 
         // Acquire a consistent snapshot of the state of the bucket list.  This should maintain the
         // invariant that total buckets are conserved.  Also returns the list of transfer counts.
-        pub fn snapshot(&self) -> ([usize; N_BUCKETS], [usize; N_WORKERS ]) {
+        pub fn snapshot(&self) -> ([usize; N_BUCKETS], [usize; N_WORKERS]) {
             // Since this method is called relatively rarely, we aren't too concerned about
             // performance here.
             let mut buckets = [0; N_BUCKETS];
@@ -172,7 +152,7 @@ mod buckets {
 fn make_buckets(initial_sum: usize) -> buckets::Buckets {
     let mut buckets = [0; buckets::N_BUCKETS];
     let mut dist = initial_sum;
-    for (i, b) in (&mut buckets[..]).iter_mut().enumerate() {
+    for (i, b) in buckets.iter_mut().enumerate() {
         let v = dist / (buckets::N_BUCKETS - i);
         *b = v;
         dist -= v;
@@ -182,15 +162,13 @@ fn make_buckets(initial_sum: usize) -> buckets::Buckets {
 
 // The equalize task--it chooses two random buckets and tries to make their values the same.
 fn equalize(bl: &buckets::Buckets, running: &AtomicBool, worker: usize) {
-    // We preallocate the Range for improved performance.
-    let between = Range::new(0, buckets::N_BUCKETS);
-    // We use the weak random number generator for improved performance.
-    let ref mut r = weak_rng();
+    // We use a small, fast random number generator for improved performance.
+    let r = &mut Rng::from_time();
     // Running is read Relaxed because it's not important that the task stop right away as long as
     // it happens eventually.
     while running.load(Ordering::Relaxed) {
-        let b1 = between.ind_sample(r);
-        let b2 = between.ind_sample(r);
+        let b1 = r.gen_range(0, buckets::N_BUCKETS);
+        let b2 = r.gen_range(0, buckets::N_BUCKETS);
         let v1 = bl.get(b1).unwrap();
         let v2 = bl.get(b2).unwrap();
         if v1 > v2 {
@@ -203,81 +181,102 @@ fn equalize(bl: &buckets::Buckets, running: &AtomicBool, worker: usize) {
 
 // The randomize task--it chooses two random buckets and randomly redistributes their values.
 fn randomize(bl: &buckets::Buckets, running: &AtomicBool, worker: usize) {
-    // We preallocate the Range for improved performance.
-    let between = Range::new(0, buckets::N_BUCKETS);
-    // We use the weak random number generator for improved performance.
-    let ref mut r = weak_rng();
+    // We use a small, fast random number generator for improved performance.
+    let r = &mut Rng::from_time();
     // Running is read Relaxed because it's not important that the task stop right away as long as
     // it happens eventually.
     while running.load(Ordering::Relaxed) {
-        let b1 = between.ind_sample(r);
-        let b2 = between.ind_sample(r);
+        let b1 = r.gen_range(0, buckets::N_BUCKETS);
+        let b2 = r.gen_range(0, buckets::N_BUCKETS);
         bl.transfer(b1, b2, r.gen_range(0, bl.get(b1).unwrap() + 1), worker);
     }
 }
 
 // The display task--for a total time of `duration`, it displays information about the update
 // process and checks to make sure that the invariant (that the total remains constant) is
-// preserved.  It prints an update `nticks` times, evenly spaced.
-fn display(bl: &buckets::Buckets, running: &AtomicBool, original_total: usize, duration: Duration, nticks: i32) {
+// preserved.  It prints an update `nticks` times, evenly spaced, and returns the number of
+// transfers made.
+fn display(bl: &buckets::Buckets, running: &AtomicBool, original_total: usize, duration: Duration,
+           nticks: u32) -> usize {
     println!("transfers, N. transfers, buckets, buckets sum:");
 
-    let mut timer = Timer::new().unwrap();
     let duration = duration / nticks;
-    for _ in (0..nticks) {
+    let mut total_transfers = 0;
+    for _ in 0..nticks {
+        // Sleep before printing, so the first update has something to show.
+        thread::sleep(duration);
         // Get a consistent snapshot
         let (s, tc) = bl.snapshot();
         // Sum up the buckets
-        let sum = s.iter().map( |&i| i ).sum();
+        let sum: usize = s.iter().sum();
         // Sum up the transfers.
-        let n_transfers = tc.iter().map( |&i| i ).sum();
+        let n_transfers: usize = tc.iter().sum();
+        total_transfers += n_transfers;
         // Print the relevant information.
         println!("{:?}, {}, {:?}, {}", tc, n_transfers, s, sum);
-        // Check the invariant, failing if necessary.
-        assert_eq!(sum, original_total);
-        // Sleep before printing again.
-        timer.sleep(duration);
+        // Check the invariant, failing if necessary.  The updater tasks are stopped first, so
+        // that they don't run on while the failure propagates.
+        if sum != original_total {
+            running.store(false, Ordering::Relaxed);
+            panic!("the buckets add up to {} rather than {}", sum, original_total);
+        }
     }
     // We're done--cleanly exit the other update tasks.
     running.store(false, Ordering::Relaxed);
+    total_transfers
 }
 
-// Putting together all three tasks.
-fn perform_atomic_updates(duration: Duration, original_total: usize, num_ticks: i32)
-{
+// Putting together all three tasks.  Returns the number of transfers made.
+fn perform_atomic_updates(duration: Duration, original_total: usize, num_ticks: u32) -> usize {
     // Worker IDs for the two updater tasks.
     const ID_EQUALIZE: usize = 0;
     const ID_RANDOMIZE: usize = 1;
 
     // `running` is an atomic boolean that we use to signal when to stop to the updater tasks.
     let running = AtomicBool::new(true);
-    // We use an Arc here in order to share a reference to the buckets between threads.  Since the
-    // Buckets are already Sync, and we don't need &mut references to them, there's no need to
-    // create a Mutex here.
-    let arc = Arc::new((make_buckets(original_total), running));
-    // Cloning the arc bumps the reference count.
-    let arc_ = arc.clone();
-    // Start off the equalize task
-    spawn(move || { equalize(&arc_.0, &arc_.1, ID_EQUALIZE) });
-    let arc_ = arc.clone();
-    // Start off the randomize task
-    spawn(move || { randomize(&arc_.0, &arc_.1, ID_RANDOMIZE) });
-    let (ref bl, ref running) = *arc;
-    // Run the display task in the current thread, so failure propagates to the user.
-    display(bl, running, original_total, duration, num_ticks);
+    // Since the Buckets are already Sync, and we don't need &mut references to them, there's no
+    // need to create a Mutex here.
+    let bl = make_buckets(original_total);
+    thread::scope(|scope| {
+        let (bl, running) = (&bl, &running);
+        // Start off the equalize task
+        scope.spawn(move || equalize(bl, running, ID_EQUALIZE));
+        // Start off the randomize task
+        scope.spawn(move || randomize(bl, running, ID_RANDOMIZE));
+        // Run the display task in the current thread, so failure propagates to the user.
+        display(bl, running, original_total, duration, num_ticks)
+    })
 }
 
 const ORIGINAL_TOTAL: usize = 1000;
-const NUM_TICKS: i32 = 10;
+const NUM_TICKS: u32 = 10;
 
 #[cfg(not(test))]
 fn main() {
     // Run for 10 seconds
-    perform_atomic_updates(Duration::seconds(10), ORIGINAL_TOTAL, NUM_TICKS);
+    perform_atomic_updates(Duration::from_secs(10), ORIGINAL_TOTAL, NUM_TICKS);
 }
 
 #[test]
 fn test_atomic_updates() {
     // Run for 1/10th of a second
-    perform_atomic_updates(Duration::seconds(1) / 10, ORIGINAL_TOTAL, NUM_TICKS);
+    assert!(perform_atomic_updates(Duration::from_millis(100), ORIGINAL_TOTAL, NUM_TICKS) > 0);
+}
+
+#[test]
+fn test_transfers() {
+    let bl = make_buckets(ORIGINAL_TOTAL);
+    assert_eq!(bl.get(0), Some(ORIGINAL_TOTAL / buckets::N_BUCKETS));
+    assert_eq!(bl.get(buckets::N_BUCKETS), None);
+    // Never more than the bucket holds
+    bl.transfer(0, 1, 1000, 0);
+    assert_eq!((bl.get(0), bl.get(1)), (Some(0), Some(100)));
+    // Moving nothing, or out of range, is left alone
+    bl.transfer(2, 2, 10, 0);
+    bl.transfer(2, buckets::N_BUCKETS, 10, 0);
+    let (snapshot, transfers) = bl.snapshot();
+    assert_eq!(snapshot.iter().sum::<usize>(), ORIGINAL_TOTAL);
+    assert_eq!(transfers, [1, 0]);
+    // The counts start again after each snapshot
+    assert_eq!(bl.snapshot().1, [0, 0]);
 }
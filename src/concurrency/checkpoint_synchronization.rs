@@ -0,0 +1,175 @@
+//! Implements http://rosettacode.org/wiki/Checkpoint_synchronization
+//!
+//! Workers each make a part, taking a random time over it, and the parts are assembled at a
+//! checkpoint once every worker has made theirs, before any of them starts on the next round.
+//! Rust's Barrier would do for a fixed set of workers, but here workers can join and leave between
+//! rounds, so the checkpoint is a count of workers and of arrivals behind a Mutex, with a Condvar
+//! to wait on for the rest.  A worker who joins takes part from the round under way, and one who
+//! leaves is no longer waited for, which completes the round if everyone else has arrived.
+//!
+//! ```
+//! # extern crate tasks;
+//! use std::time::Duration;
+//! use tasks::checkpoint_synchronization::assemble;
+//!
+//! // Worker 0 makes parts for rounds 0 and 1, and worker 1 joins in for round 1
+//! let mut parts = assemble(&[(0, 2), (1, 1)], Duration::from_millis(1), 42);
+//! parts.sort();
+//! assert_eq!(parts, [(0, 0), (1, 0), (1, 1)]);
+//! ```
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::rng::Rng;
+
+pub struct Checkpoint {
+    state: Mutex<State>,
+    passed: Condvar, // Notified when a round is complete, or the last worker leaves
+}
+
+struct State {
+    workers: usize,
+    arrived: usize, // How many of the workers are waiting at the checkpoint
+    round: usize,
+}
+
+impl Default for Checkpoint {
+    fn default() -> Checkpoint {
+        Checkpoint::new()
+    }
+}
+
+impl Checkpoint {
+    pub fn new() -> Checkpoint {
+        Checkpoint {
+            state: Mutex::new(State { workers: 0, arrived: 0, round: 0 }),
+            passed: Condvar::new(),
+        }
+    }
+
+    // Add a worker once round `round` is under way, or at once if it already is or there's no one
+    // left to get to it.  Returns the round they start in.
+    pub fn join(&self, round: usize) -> usize {
+        let mut state = self.state.lock().unwrap();
+        while state.round < round && state.workers > 0 {
+            state = self.passed.wait(state).unwrap();
+        }
+        state.workers += 1;
+        state.round
+    }
+
+    // Remove a worker who isn't waiting at the checkpoint, such as one whose last wait() has
+    // returned
+    pub fn leave(&self) {
+        let mut state = self.state.lock().unwrap();
+        assert!(state.workers > state.arrived, "no worker is away from the checkpoint to leave");
+        state.workers -= 1;
+        if state.workers == 0 || state.arrived == state.workers {
+            self.pass(&mut state);
+        }
+    }
+
+    // Wait at the checkpoint until every worker has arrived, and return the round completed
+    pub fn wait(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let round = state.round;
+        state.arrived += 1;
+        if state.arrived == state.workers {
+            self.pass(&mut state);
+        }
+        while state.round == round {
+            state = self.passed.wait(state).unwrap();
+        }
+        round
+    }
+
+    // Let the workers waiting at the checkpoint go on to the next round
+    fn pass(&self, state: &mut State) {
+        if state.workers > 0 {
+            state.arrived = 0;
+            state.round += 1;
+        }
+        self.passed.notify_all();
+    }
+}
+
+// A worker who has joined the checkpoint in round `first`, and makes a part in each of `rounds`
+// rounds, taking up to `most` over each
+fn worker(checkpoint: &Checkpoint, id: usize, first: usize, rounds: usize, most: Duration,
+          seed: u64, parts: &Mutex<Vec<(usize, usize)>>) {
+    println!("Worker {} joins in round {}", id, first);
+    let mut rng = Rng::with_stream(seed, id as u64);
+    for round in first..first + rounds {
+        let took = Duration::from_micros(rng.gen_range(0, most.as_micros() as u64 + 1));
+        thread::sleep(took);
+        println!("Worker {} made a part for round {} in {:?}", id, round, took);
+        parts.lock().unwrap().push((round, id));
+        assert_eq!(checkpoint.wait(), round);
+    }
+    checkpoint.leave();
+    println!("Worker {} leaves", id);
+}
+
+// Run a worker for each (round, rounds) in `schedule`, who joins in that round and makes parts
+// for that many rounds.  Returns the round and worker of each part, in the order they were made.
+pub fn assemble(schedule: &[(usize, usize)], most: Duration, seed: u64) -> Vec<(usize, usize)> {
+    let checkpoint = Checkpoint::new();
+    let parts = Mutex::new(Vec::new());
+    let mut order: Vec<usize> = (0..schedule.len()).collect();
+    order.sort_by_key(|&id| schedule[id].0);
+    thread::scope(|scope| {
+        for id in order {
+            let (join, rounds) = schedule[id];
+            // Joined here rather than on the worker's thread, so that the round can't be over
+            // before it starts
+            let first = checkpoint.join(join);
+            let (checkpoint, parts) = (&checkpoint, &parts);
+            scope.spawn(move || worker(checkpoint, id, first, rounds, most, seed, parts));
+        }
+    });
+    parts.into_inner().unwrap()
+}
+
+pub fn checkpoint() {
+    let seed = Rng::from_time().next_u64();
+    let schedule = [(0, 4), (0, 6), (0, 2), (2, 5), (5, 3)];
+    let parts = assemble(&schedule, Duration::from_millis(50), seed);
+    for round in 0..parts.last().map_or(0, |&(round, _)| round + 1) {
+        let ids: Vec<usize> = parts.iter().filter(|part| part.0 == round).map(|part| part.1)
+                                   .collect();
+        println!("Round {}: assembled the parts from workers {:?}", round, ids);
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    checkpoint();
+}
+
+#[test]
+fn test_checkpoint() {
+    checkpoint();
+}
+
+#[test]
+fn test_rounds() {
+    let schedule = [(0, 3), (0, 10), (1, 4), (4, 2), (8, 5), (20, 2)];
+    let parts = assemble(&schedule, Duration::from_millis(2), 52);
+    // Nobody made a part for a round before every part for the round before it was made
+    assert!(parts.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    for (id, &(join, count)) in schedule.iter().enumerate() {
+        let rounds: Vec<usize> = parts.iter().filter(|part| part.1 == id).map(|part| part.0)
+                                      .collect();
+        // One part in each of their rounds, from the one they joined in
+        assert_eq!(rounds.len(), count);
+        assert!(rounds.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        assert!(rounds[0] >= join || id == 5);
+    }
+    // The last worker was let in before round 20, since everyone else had left by then
+    assert_eq!(parts[parts.len() - 2], (13, 5));
+}
@@ -0,0 +1,189 @@
+//! Concurrent computing, printing the results in the order the tasks were started
+//!
+//! Each word is still looked after by a task of its own, which takes a random time over it, but
+//! rather than printing it as soon as it's done, the tasks hand their words to a sequencer.  The
+//! sequencer keeps those that come in early in a reorder buffer, and prints each word as soon as
+//! the ones before it have been printed, without waiting for all the tasks to finish.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::concurrent_computing_ordered::{in_order, Reorder};
+//!
+//! let mut emitted = Vec::new();
+//! in_order(vec!["Enjoy", "Rosetta", "Code"], |word| word.len(), |i, len| emitted.push((i, len)));
+//! assert_eq!(emitted, [(0, 5), (1, 7), (2, 4)]);
+//!
+//! let mut reorder = Reorder::new();
+//! assert_eq!(reorder.push(1, 'b'), []);
+//! assert_eq!(reorder.waiting(), 1);
+//! assert_eq!(reorder.push(0, 'a'), [(0, 'a'), (1, 'b')]);
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::channel;
+use std::thread;
+#[cfg(not(test))]
+use std::time::Duration;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+#[cfg(not(test))]
+use utils::rng::Rng;
+
+// Results numbered in the order their tasks were started, let out in that order
+pub struct Reorder<T> {
+    next: usize,               // The number of the next result to let out
+    early: BTreeMap<usize, T>, // Results that came in before those ahead of them
+}
+
+impl<T> Reorder<T> {
+    pub fn new() -> Reorder<T> {
+        Reorder { next: 0, early: BTreeMap::new() }
+    }
+
+    // Take in result number `index`, and return the results that can now be let out, with their
+    // numbers, in order: none if one before it is still to come.
+    pub fn push(&mut self, index: usize, result: T) -> Vec<(usize, T)> {
+        assert!(index >= self.next && !self.early.contains_key(&index),
+                "result {} came in twice", index);
+        self.early.insert(index, result);
+        let mut ready = Vec::new();
+        while let Some(result) = self.early.remove(&self.next) {
+            ready.push((self.next, result));
+            self.next += 1;
+        }
+        ready
+    }
+
+    // How many results are being held back
+    pub fn waiting(&self) -> usize {
+        self.early.len()
+    }
+}
+
+impl<T> Default for Reorder<T> {
+    fn default() -> Reorder<T> {
+        Reorder::new()
+    }
+}
+
+// Run `work` on each of `inputs` concurrently, a thread each, and call `emit` with each result,
+// along with its index, in the order of `inputs`, as soon as those before it have been emitted
+pub fn in_order<T, R, W, E>(inputs: Vec<T>, work: W, mut emit: E)
+    where T: Send, R: Send, W: Fn(T) -> R + Sync, E: FnMut(usize, R)
+{
+    let (sender, results) = channel();
+    thread::scope(|scope| {
+        for (index, input) in inputs.into_iter().enumerate() {
+            let (sender, work) = (sender.clone(), &work);
+            scope.spawn(move || sender.send((index, work(input))).unwrap());
+        }
+        drop(sender);
+        let mut reorder = Reorder::new();
+        for (index, result) in results {
+            for (index, result) in reorder.push(index, result) {
+                emit(index, result);
+            }
+        }
+    });
+}
+
+#[cfg(not(test))]
+fn main() {
+    let mut rng = Rng::from_time();
+    let words = vec!["Enjoy", "Rosetta", "Code"];
+    let inputs: Vec<_> = words.into_iter().map(|word| {
+        (word, Duration::from_millis(rng.gen_range(0, 256)))
+    }).collect();
+    in_order(inputs, |(word, delay)| {
+        thread::sleep(delay);
+        word
+    }, |_, word| println!("{}", word));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{in_order, Reorder};
+    use std::sync::mpsc::channel;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+    use utils::rng::Rng;
+
+    #[test]
+    fn reorder() {
+        let mut reorder = Reorder::new();
+        assert_eq!(reorder.push(2, 'c'), vec![]);
+        assert_eq!(reorder.push(1, 'b'), vec![]);
+        assert_eq!(reorder.waiting(), 2);
+        assert_eq!(reorder.push(0, 'a'), vec![(0, 'a'), (1, 'b'), (2, 'c')]);
+        assert_eq!(reorder.push(3, 'd'), vec![(3, 'd')]);
+        assert_eq!(reorder.waiting(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "result 1 came in twice")]
+    fn twice() {
+        let mut reorder = Reorder::new();
+        reorder.push(1, ());
+        reorder.push(1, ());
+    }
+
+    #[test]
+    fn any_order() {
+        let mut rng = Rng::new(57);
+        let mut indexes: Vec<usize> = (0..1000).collect();
+        rng.shuffle(&mut indexes);
+        let mut reorder = Reorder::new();
+        let mut out = Vec::new();
+        for index in indexes {
+            out.extend(reorder.push(index, index * 2));
+        }
+        assert_eq!(out, (0..1000).map(|index| (index, index * 2)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn printed_in_order() {
+        let mut rng = Rng::new(7);
+        let delays: Vec<u64> = (0..20).map(|_| rng.gen_range(0, 20)).collect();
+        let mut emitted = Vec::new();
+        in_order(delays.iter().cloned().enumerate().collect(), |(i, delay)| {
+            thread::sleep(Duration::from_millis(delay));
+            i * i
+        }, |index, result| emitted.push((index, result)));
+        assert_eq!(emitted, (0..20).map(|i| (i, i * i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn printed_as_soon_as_possible() {
+        // The second task finishes first, but waits for the first to be printed.  The third only
+        // finishes once the second has been printed, which it could never be if printing waited
+        // for every task.  Channels rather than sleeps put the tasks in that order, so that each
+        // step follows from the one before, however slow the machine.
+        let (one_done, after_one) = channel();
+        let (one_printed, after_one_printed) = channel();
+        let mut one_printed = Some(one_printed);
+        let log = Mutex::new(Vec::new());
+        let inputs = vec![(0, Some(after_one), None), (1, None, Some(one_done)),
+                          (2, Some(after_one_printed), None)];
+        in_order(inputs, |(i, wait, signal)| {
+            if let Some(wait) = wait {
+                wait.recv().unwrap();
+            }
+            log.lock().unwrap().push(format!("done {}", i));
+            if let Some(signal) = signal {
+                signal.send(()).unwrap();
+            }
+            i
+        }, |i, _| {
+            log.lock().unwrap().push(format!("print {}", i));
+            if i == 1 {
+                one_printed.take().unwrap().send(()).unwrap();
+            }
+        });
+        assert_eq!(log.into_inner().unwrap(),
+                   vec!["done 1", "done 0", "print 0", "print 1", "done 2", "print 2"]);
+    }
+}
@@ -0,0 +1,194 @@
+// Implements http://rosettacode.org/wiki/Dining_philosophers
+// Each philosopher needs the forks on both sides of them to eat.  If they all picked up their left
+// fork at once, each would wait forever for the right one, held by their neighbour.  So a waiter
+// lets at most all but one of them sit down at a time, using a CountingSemaphore from
+// metered_concurrency.rs: however the forks are picked up, one of those at the table has both of
+// theirs free, eats, and puts them back.
+//
+//     dining_philosophers [--philosophers N] [--meals N] [--eat-ms MS] [--think-ms MS]
+
+use metered_concurrency::CountingSemaphore;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Sender};
+use std::thread::{self, sleep};
+use std::time::Duration;
+
+#[allow(dead_code)]
+mod metered_concurrency;
+
+// The utils module as metered_concurrency.rs declares it, since it can't be declared twice
+use metered_concurrency::utils;
+use utils::args::Args;
+
+const NAMES: [&str; 5] = ["Aristotle", "Kant", "Spinoza", "Marx", "Russell"];
+
+#[derive(Clone, Debug, PartialEq)]
+struct Config {
+    philosophers: usize,
+    meals: usize, // How many times each of them eats
+    eat: Duration,
+    think: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            philosophers: NAMES.len(),
+            meals: 3,
+            eat: Duration::from_millis(100),
+            think: Duration::from_millis(100),
+        }
+    }
+}
+
+fn parse_args(mut args: Args) -> Result<Config, String> {
+    let mut config = Config::default();
+    match args.value("--philosophers")? {
+        // A lone philosopher has only the one fork
+        Some(philosophers) if philosophers < 2 => {
+            return Err("--philosophers must be at least 2".to_string());
+        }
+        Some(philosophers) => config.philosophers = philosophers,
+        None => {}
+    }
+    if let Some(meals) = args.value("--meals")? {
+        config.meals = meals;
+    }
+    if let Some(eat) = args.value("--eat-ms")? {
+        config.eat = Duration::from_millis(eat);
+    }
+    if let Some(think) = args.value("--think-ms")? {
+        config.think = Duration::from_millis(think);
+    }
+    args.finish()?;
+    Ok(config)
+}
+
+fn name(seat: usize) -> String {
+    match NAMES.get(seat) {
+        Some(name) => name.to_string(),
+        None => format!("Philosopher {}", seat + 1),
+    }
+}
+
+struct Table {
+    forks: Vec<Mutex<usize>>, // Each counts how many times it's been picked up
+    waiter: CountingSemaphore,
+}
+
+fn philosopher(table: &Table, seat: usize, config: &Config, events: &Sender<String>) {
+    let name = name(seat);
+    // The fork on their left is numbered like their seat, and the one on their right is the
+    // next philosopher's left fork
+    let (left, right) = (seat, (seat + 1) % table.forks.len());
+    for _ in 0..config.meals {
+        events.send(format!("{} is thinking.", name)).unwrap();
+        sleep(config.think);
+        // The waiter's semaphore is never closed, so this can't fail
+        let _seated = table.waiter.acquire().unwrap();
+        let mut left = table.forks[left].lock().unwrap();
+        let mut right = table.forks[right].lock().unwrap();
+        *left += 1;
+        *right += 1;
+        events.send(format!("{} is eating.", name)).unwrap();
+        sleep(config.eat);
+    }
+    events.send(format!("{} is done eating.", name)).unwrap();
+}
+
+// Seat the philosophers and have each eat `config.meals` times, writing what they do to `output`
+// as they do it.  Returns how many times each fork was picked up.
+fn dine(config: &Config, mut output: impl Write) -> io::Result<Vec<usize>> {
+    assert!(config.philosophers >= 2, "dining takes at least two philosophers");
+    let table = Table {
+        forks: (0..config.philosophers).map(|_| Mutex::new(0)).collect(),
+        waiter: CountingSemaphore::new(config.philosophers - 1),
+    };
+    let (sender, events) = channel();
+    thread::scope(|scope| -> io::Result<()> {
+        for seat in 0..config.philosophers {
+            let (table, sender) = (&table, sender.clone());
+            scope.spawn(move || philosopher(table, seat, config, &sender));
+        }
+        drop(sender);
+        // Ends once every philosopher has left the table and dropped their sender
+        for event in events {
+            writeln!(output, "{}", event)?;
+        }
+        Ok(())
+    })?;
+    Ok(table.forks.into_iter().map(|fork| fork.into_inner().unwrap()).collect())
+}
+
+#[cfg(not(test))]
+fn main() {
+    let config = parse_args(Args::from_env()).unwrap_or_else(|message| {
+        utils::args::exit_with_usage(&message, "dining_philosophers [--philosophers N] \
+                                                [--meals N] [--eat-ms MS] [--think-ms MS]")
+    });
+    let stdout = io::stdout();
+    dine(&config, stdout.lock()).unwrap();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dine, name, parse_args, Config};
+    use std::io;
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use std::time::Duration;
+    use utils::args::Args;
+    use utils::capture::Captured;
+
+    // Dine as `config` says on another thread, failing if that takes so long that the
+    // philosophers must have deadlocked
+    fn dine_in_time(config: Config) -> Vec<usize> {
+        let (sender, receiver) = channel();
+        thread::spawn(move || sender.send(dine(&config, io::sink()).unwrap()).unwrap());
+        receiver.recv_timeout(Duration::from_secs(60)).expect("the philosophers deadlocked")
+    }
+
+    fn hungry(philosophers: usize, meals: usize) -> Config {
+        let (eat, think) = (Duration::from_millis(0), Duration::from_millis(0));
+        Config { philosophers, meals, eat, think }
+    }
+
+    #[test]
+    fn no_deadlock() {
+        // Each fork is picked up by the philosophers on both sides of it
+        assert_eq!(dine_in_time(hungry(5, 5000)), vec![10000; 5]);
+        assert_eq!(dine_in_time(hungry(2, 5000)), vec![10000; 2]);
+        assert_eq!(dine_in_time(hungry(16, 1000)), vec![2000; 16]);
+    }
+
+    #[test]
+    fn everyone_eats() {
+        let output = Captured::new();
+        assert_eq!(dine(&hungry(7, 2), output.clone()).unwrap(), vec![4; 7]);
+        let text = output.text();
+        for seat in 0..7 {
+            let eating = format!("{} is eating.", name(seat));
+            assert_eq!(text.lines().filter(|&line| line == eating).count(), 2);
+            assert!(text.contains(&format!("{} is done eating.", name(seat))));
+        }
+        assert!(text.contains("Russell is thinking.\n"));
+        assert!(text.contains("Philosopher 7 is eating.\n"));
+    }
+
+    #[test]
+    fn arguments() {
+        let args = |args: &[&str]| parse_args(Args::new(args.iter().map(|arg| arg.to_string())));
+        assert_eq!(args(&[]), Ok(Config::default()));
+        assert_eq!(args(&["--philosophers", "8", "--meals=10", "--eat-ms", "0"]),
+                   Ok(Config {
+                       philosophers: 8,
+                       meals: 10,
+                       eat: Duration::from_millis(0),
+                       ..Config::default()
+                   }));
+        assert_eq!(args(&["--philosophers", "1"]),
+                   Err("--philosophers must be at least 2".to_string()));
+        assert!(args(&["--meals", "lots"]).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Implements http://rosettacode.org/wiki/Events
+//!
+//! Rust uses condition variables (Condvars) for asynchronous event processing.  A task waits on a
+//! condvar while it holds a Mutex, and is woken when another task signals it.  The one subtlety is
+//! that condvar signals are only received if there is actually a task waiting on the signal, so a
+//! signal sent before anyone waits would be lost.  An Event fixes that by keeping its state in the
+//! Mutex: setting it takes effect whether or not anyone is waiting yet, and waiting on an event
+//! that is already set returns at once.
+//!
+//! A manual-reset event stays set, releasing every task that waits on it, until it's reset.  An
+//! auto-reset event releases a single task, and is reset by it on the way out, as in the Win32
+//! events the task is modelled on.
+//!
+//! ```
+//! # extern crate tasks;
+//! use std::sync::Arc;
+//! use std::thread;
+//! use std::time::Duration;
+//! use tasks::events::{Event, Reset};
+//!
+//! let event = Arc::new(Event::new(Reset::Auto));
+//! let waiting = event.clone();
+//! let task = thread::spawn(move || waiting.wait());
+//! event.set();
+//! task.join().unwrap();
+//! // The task the event released reset it
+//! assert!(!event.is_set());
+//! assert!(!event.wait_timeout(Duration::from_millis(1)));
+//! ```
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{sleep, spawn};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reset {
+    Manual, // Stays set until reset() is called
+    Auto,   // Reset as soon as it has released one waiting task
+}
+
+pub struct Event {
+    set: Mutex<bool>,
+    signal: Condvar,
+    reset: Reset,
+}
+
+impl Event {
+    // An event that starts out reset
+    pub fn new(reset: Reset) -> Event {
+        Event { set: Mutex::new(false), signal: Condvar::new(), reset }
+    }
+
+    // Set the event, releasing every task waiting on it, or with an auto-reset event one of them
+    // (or the next to wait, if none is waiting yet)
+    pub fn set(&self) {
+        *self.set.lock().unwrap() = true;
+        match self.reset {
+            Reset::Manual => self.signal.notify_all(),
+            Reset::Auto => self.signal.notify_one(),
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.set.lock().unwrap() = false;
+    }
+
+    pub fn is_set(&self) -> bool {
+        *self.set.lock().unwrap()
+    }
+
+    // Wait until the event is set
+    pub fn wait(&self) {
+        let mut set = self.set.lock().unwrap();
+        while !*set {
+            set = self.signal.wait(set).unwrap();
+        }
+        if self.reset == Reset::Auto {
+            *set = false;
+        }
+    }
+
+    // Wait until the event is set, for at most `timeout`.  Returns whether it was set in time.
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let set = self.set.lock().unwrap();
+        let (mut set, _) = self.signal.wait_timeout_while(set, timeout, |set| !*set).unwrap();
+        if !*set {
+            return false;
+        }
+        if self.reset == Reset::Auto {
+            *set = false;
+        }
+        true
+    }
+}
+
+// Given a duration to wait before sending an event from one task to another, returns the elapsed
+// time before the waiting task received it.
+fn handle_event(duration: Duration) -> Duration {
+    let event = Arc::new(Event::new(Reset::Manual));
+    let start = Instant::now();
+    let waiting = event.clone();
+    // The secondary task waits for the event, which the main task sets after `duration`.  The
+    // event may even be set before the task gets as far as waiting: it sees that it's set.
+    let task = spawn(move || {
+        waiting.wait();
+        start.elapsed()
+    });
+    sleep(duration);
+    event.set();
+    task.join().unwrap()
+}
+
+#[cfg(not(test))]
+pub fn main() {
+    let duration = Duration::from_secs(1); // Process event after one second.
+    println!("{:?} elapsed before event triggered", handle_event(duration));
+}
+
+
+#[test]
+pub fn test_events() {
+    let duration = Duration::from_millis(100); // Process event after one tenth of a second.
+    // Make sure it really did take at least that long for the event to be processed.
+    let out = handle_event(duration);
+    assert!(duration <= out);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Event, Reset};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn manual_reset() {
+        let event = Event::new(Reset::Manual);
+        let released = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    event.wait();
+                    released.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            thread::sleep(Duration::from_millis(20));
+            assert_eq!(released.load(Ordering::SeqCst), 0);
+            event.set();
+        });
+        // Every waiting task was released, and the event is still set
+        assert_eq!(released.load(Ordering::SeqCst), 4);
+        assert!(event.is_set());
+        event.wait();
+        event.reset();
+        assert!(!event.wait_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn auto_reset() {
+        let event = Event::new(Reset::Auto);
+        // Set before anyone waits, so the first to wait goes straight through
+        event.set();
+        assert!(event.wait_timeout(Duration::from_millis(10)));
+        assert!(!event.is_set());
+        assert!(!event.wait_timeout(Duration::from_millis(10)));
+        // Each set() releases one task
+        let released = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..3 {
+                scope.spawn(|| {
+                    event.wait();
+                    released.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            for count in 1..4 {
+                event.set();
+                while released.load(Ordering::SeqCst) < count {
+                    thread::yield_now();
+                }
+                thread::sleep(Duration::from_millis(10));
+                assert_eq!(released.load(Ordering::SeqCst), count);
+            }
+        });
+    }
+
+    #[test]
+    fn timeout() {
+        let event = Event::new(Reset::Manual);
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| event.wait_timeout(Duration::from_secs(10)));
+            thread::sleep(Duration::from_millis(10));
+            event.set();
+            assert!(waiter.join().unwrap());
+        });
+        event.reset();
+        assert!(!event.wait_timeout(Duration::from_millis(20)));
+    }
+}
@@ -0,0 +1,2398 @@
+//! Implements http://rosettacode.org/wiki/Metered_concurrency
+//!
+//! Rust has a perfectly good Semaphore type already.  It lacks count(), though, so we can't use it
+//! directly.
+//!
+//! Waiting tasks are parked on a condition variable rather than spinning, so workers that hold a
+//! resource for a long time don't cost the waiters any CPU.  A semaphore can still be made to poll
+//! like a spinlock, with a pluggable Backoff strategy deciding how long to sleep between attempts.
+//! Such a semaphore can be given a MockClock, which tests advance by hand instead of sleeping.
+//!
+//! Built the same way is a CyclicBarrier, which makes a fixed number of tasks wait for each other
+//! before any of them continues, and can be reused for each new phase of work.  A RateLimiter
+//! reuses the semaphore as a token bucket, with a background task putting tokens back in on a
+//! timer.  A NamedSemaphore meters resources between separate processes rather than threads, using
+//! shared memory and a futex on Linux, and the system's named semaphores elsewhere.
+//!
+//! There is also an AsyncCountingSemaphore, whose acquire() returns a future instead of blocking.
+//! To keep the example self-contained, a minimal poll-based Future trait and a tiny
+//! single-threaded executor are defined below to drive the same demo asynchronously, rather than
+//! pulling in an async runtime.
+//!
+//! CountingSemaphore's synchronisation primitives come from the `sync` module below, so that under
+//! `--cfg loom` they are swapped for loom's and the loom_* tests can model-check every
+//! interleaving:
+//!
+//! ```text
+//!     RUSTFLAGS="--cfg loom" cargo test --release --bin metered_concurrency loom_
+//! ```
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::metered_concurrency::CountingSemaphore;
+//!
+//! let semaphore = CountingSemaphore::new(2);
+//! let first = semaphore.acquire().unwrap();
+//! let _second = semaphore.acquire().unwrap();
+//! assert_eq!(semaphore.count(), 0);
+//! assert!(semaphore.try_acquire().is_none());
+//! drop(first);
+//! assert_eq!(semaphore.count(), 1);
+//! assert!(semaphore.try_acquire().is_some());
+//! ```
+
+extern crate rand;
+#[cfg(unix)]
+extern crate libc;
+#[cfg(loom)]
+extern crate loom;
+
+use std::cmp;
+use std::collections::VecDeque;
+#[cfg(unix)]
+use std::ffi::CString;
+use std::io::{self, Write};
+use std::mem;
+use std::sync::{Arc, Mutex, Condvar, Weak};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use std::thread::{self, sleep, spawn, Thread};
+use std::sync::mpsc::channel;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::args::Args;
+
+#[cfg(not(loom))]
+mod sync {
+    pub use std::sync::{Condvar, Mutex};
+    pub use std::sync::atomic::AtomicUsize;
+}
+
+#[cfg(loom)]
+mod sync {
+    pub use loom::sync::{Condvar, Mutex};
+    pub use loom::sync::atomic::AtomicUsize;
+}
+
+pub struct CountingSemaphore {
+    lock: sync::Mutex<State>, // Resource count and wait queue, guarded by the lock
+    cond: sync::Condvar,      // Signaled whenever a resource is released
+    count: sync::AtomicUsize,   // Mirror of the remaining count, so count() doesn't need the lock
+    wakeups: sync::AtomicUsize, // Number of times a waiting task was woken up
+    fair: bool,          // Whether resources are handed out in the order they were requested
+    max: sync::AtomicUsize, // Total resource count, changed by forget() and add_permits()
+    backoff: Option<Box<dyn Backoff>>, // If set, poll for resources instead of waiting on `cond`
+    clock: Box<dyn Clock>, // Source of time for timeouts, polling delays and stats
+}
+
+struct State {
+    count: usize,           // Remaining resource count, the authoritative copy
+    queue: VecDeque<usize>, // Tickets of the waiting tasks, oldest first (fair mode only)
+    next_ticket: usize,     // Ticket to give the next task that has to wait
+    greedy: usize,          // Number of waiting tasks that want more than one resource
+    closed: bool,           // Whether close() has been called
+    stats: Option<Tally>,   // Contention statistics, if instrumented
+}
+
+// How many resources a task is waiting for: a number of them, or every one the semaphore has,
+// however many that is by the time they are all free, since forget() and add_permits() can change
+// it while the task waits.
+#[derive(Clone, Copy)]
+enum Want {
+    Exactly(usize),
+    All,
+}
+
+impl Want {
+    fn count(self, sem: &CountingSemaphore) -> usize {
+        match self {
+            Want::Exactly(n) => n,
+            Want::All => sem.max(),
+        }
+    }
+
+    // Whether the task counts as greedy in State, which doesn't change while it waits
+    fn greedy(self) -> bool {
+        match self {
+            Want::Exactly(n) => n > 1,
+            Want::All => true,
+        }
+    }
+}
+
+// Running totals behind CountingSemaphore::stats().
+struct Tally {
+    acquisitions: usize,
+    failed_attempts: usize,
+    total_wait: Duration,
+    min_wait: Duration,
+    max_wait: Duration,
+}
+
+// How contended an instrumented semaphore has been.  A failed attempt is any time a task looked
+// for resources and found too few of them (or, when fair, found someone ahead of it).
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub acquisitions: usize,
+    pub failed_attempts: usize,
+    pub min_wait: Duration,
+    pub mean_wait: Duration,
+    pub max_wait: Duration,
+}
+
+pub struct CountingSemaphoreGuard<'a> {
+    sem: &'a CountingSemaphore, // A reference to the owning semaphore.
+    permits: usize,             // How many resources to give back when dropped
+}
+
+// Like CountingSemaphoreGuard, but keeps the semaphore alive itself instead of borrowing it, so
+// it can be moved into spawned threads or stored in structs.
+pub struct OwnedSemaphoreGuard {
+    sem: Arc<CountingSemaphore>, // A shared reference to the owning semaphore.
+    permits: usize,              // How many resources to give back when dropped
+}
+
+// Decides how long a polling task sleeps between its attempts to acquire a resource.
+pub trait Backoff: Send + Sync {
+    // The delay before retrying for the `attempt`th time (counting from 1).  A zero delay means
+    // just yielding to other tasks.
+    fn delay(&self, attempt: u32) -> Duration;
+}
+
+// Sleep `attempt` times the given duration, a la Servo's spinlock contention.
+pub struct Linear(pub Duration);
+
+// Double the delay with every attempt, up to `max`.
+pub struct Exponential {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+// Sleep a random duration of up to what Exponential would, so that tasks which failed together
+// don't all retry at the same moment.
+pub struct ExponentialWithJitter {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+// Never sleep, only yield.
+pub struct YieldOnly;
+
+impl Backoff for Linear {
+    fn delay(&self, attempt: u32) -> Duration {
+        self.0 * attempt
+    }
+}
+
+// `base` * 2^(attempt - 1), capped at `max`
+fn exponential_delay(base: Duration, max: Duration, attempt: u32) -> Duration {
+    match 2u32.checked_pow(attempt - 1).and_then(|factor| base.checked_mul(factor)) {
+        Some(delay) => cmp::min(delay, max),
+        None => max,
+    }
+}
+
+impl Backoff for Exponential {
+    fn delay(&self, attempt: u32) -> Duration {
+        exponential_delay(self.base, self.max, attempt)
+    }
+}
+
+impl Backoff for ExponentialWithJitter {
+    fn delay(&self, attempt: u32) -> Duration {
+        exponential_delay(self.base, self.max, attempt).mul_f64(rand::random::<f64>())
+    }
+}
+
+impl Backoff for YieldOnly {
+    fn delay(&self, _attempt: u32) -> Duration {
+        Duration::ZERO
+    }
+}
+
+// Tells the time, and sleeps.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration);
+}
+
+// The system clock.
+pub struct RealClock;
+
+// A clock that stands still until told to move, so timing can be tested without waiting.
+// Sleeping moves it forward by exactly the time asked for, and is recorded.
+pub struct MockClock {
+    state: Mutex<MockState>,
+}
+
+struct MockState {
+    now: Instant,
+    sleeps: Vec<Duration>, // Every sleep so far, oldest first
+}
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        sleep(duration);
+    }
+}
+
+impl MockClock {
+    // Create a clock that stands at the current time.
+    pub fn new() -> MockClock {
+        MockClock { state: Mutex::new(MockState { now: Instant::now(), sleeps: Vec::new() }) }
+    }
+
+    // Move the clock forward without it counting as a sleep.
+    pub fn advance(&self, duration: Duration) {
+        self.state.lock().unwrap().now += duration;
+    }
+
+    // Return the durations slept so far, oldest first
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.state.lock().unwrap().sleeps.clone()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> MockClock {
+        MockClock::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    fn sleep(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.now += duration;
+        state.sleeps.push(duration);
+    }
+}
+
+// So that a test can keep hold of the clock it hands to a semaphore.
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+// Why a resource could not be acquired.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AcquireError {
+    Closed,   // The semaphore was closed before a resource became available
+    TimedOut, // No resource became available before the deadline
+}
+
+impl CountingSemaphore {
+    // Create a semaphore with `max` available resources.  Whichever waiting task gets to the lock
+    // first after a release gets the resource, so under heavy contention a task can starve.
+    pub fn new(max: usize) -> CountingSemaphore {
+        CountingSemaphore::with_fairness(max, false)
+    }
+
+    // Create a semaphore with `max` available resources that are handed out to waiting tasks in
+    // strict FIFO order.
+    pub fn new_fair(max: usize) -> CountingSemaphore {
+        CountingSemaphore::with_fairness(max, true)
+    }
+
+    // Create a semaphore with `max` available resources, whose waiting tasks repeatedly poll for
+    // a resource and sleep in between as `backoff` decides.  Polling tasks take resources in no
+    // particular order.
+    pub fn with_backoff<B: Backoff + 'static>(max: usize, backoff: B) -> CountingSemaphore {
+        let mut sem = CountingSemaphore::with_fairness(max, false);
+        sem.backoff = Some(Box::new(backoff));
+        sem
+    }
+
+    // Turn on recording of contention statistics, to be read back with stats().
+    pub fn instrumented(self) -> CountingSemaphore {
+        self.lock.lock().unwrap().stats = Some(Tally {
+            acquisitions: 0,
+            failed_attempts: 0,
+            total_wait: Duration::ZERO,
+            min_wait: Duration::ZERO,
+            max_wait: Duration::ZERO,
+        });
+        self
+    }
+
+    // Use `clock` instead of the system clock for timeouts, delays between polling attempts and
+    // contention statistics.  Only a polling semaphore can be given one: a task parked on the
+    // condition variable is woken up by real time passing, whatever the clock says.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> CountingSemaphore {
+        assert!(self.backoff.is_some(), "only a polling semaphore can be given a clock");
+        self.clock = Box::new(clock);
+        self
+    }
+
+    fn with_fairness(max: usize, fair: bool) -> CountingSemaphore {
+        CountingSemaphore {
+            lock: sync::Mutex::new(State {
+                count: max,
+                queue: VecDeque::new(),
+                next_ticket: 0,
+                greedy: 0,
+                closed: false,
+                stats: None,
+            }),
+            cond: sync::Condvar::new(),
+            count: sync::AtomicUsize::new(max),
+            wakeups: sync::AtomicUsize::new(0),
+            fair,
+            max: sync::AtomicUsize::new(max),
+            backoff: None,
+            clock: Box::new(RealClock),
+        }
+    }
+
+    // Acquire a resource, returning a RAII CountingSemaphoreGuard, or Err(Closed) if the
+    // semaphore is closed while (or before) waiting.
+    pub fn acquire(&self) -> Result<CountingSemaphoreGuard<'_>, AcquireError> {
+        self.acquire_many(1)
+    }
+
+    // Acquire `n` resources at once, returning a guard that releases all of them when dropped.
+    // Resources are only ever taken all together, so two tasks can't each end up holding part of
+    // what the other needs.
+    pub fn acquire_many(&self, n: usize) -> Result<CountingSemaphoreGuard<'_>, AcquireError> {
+        // Otherwise we would wait forever
+        let max = self.max();
+        assert!(n <= max, "requested {} resources out of {}", n, max);
+        self.acquire_until(Want::Exactly(n), None)
+            .map(|permits| CountingSemaphoreGuard { sem: self, permits })
+    }
+
+    // Reader-writer style: acquire_shared() takes a single resource, so any number of readers up
+    // to max can hold the semaphore at once, while acquire_exclusive() takes every resource, so a
+    // writer waits for all readers to drain and keeps new ones out until it is done.  On a fair
+    // semaphore, readers that arrive after a waiting writer queue up behind it instead of
+    // starving it.
+    //
+    // Take a single resource, alongside any other shared holders.
+    pub fn acquire_shared(&self) -> Result<CountingSemaphoreGuard<'_>, AcquireError> {
+        self.acquire_many(1)
+    }
+
+    // Take every resource at once, waiting for all shared holders to release theirs.  A holder
+    // that forgets its guard meanwhile leaves fewer to wait for, rather than some that never come.
+    pub fn acquire_exclusive(&self) -> Result<CountingSemaphoreGuard<'_>, AcquireError> {
+        self.acquire_until(Want::All, None)
+            .map(|permits| CountingSemaphoreGuard { sem: self, permits })
+    }
+
+    // Acquire a resource, returning a guard that holds on to the semaphore by itself, so that it
+    // can be moved into a spawned thread or kept in a struct.
+    pub fn acquire_owned(self: &Arc<Self>) -> Result<OwnedSemaphoreGuard, AcquireError> {
+        self.acquire_until(Want::Exactly(1), None)
+            .map(|permits| OwnedSemaphoreGuard { sem: self.clone(), permits })
+    }
+
+    // Acquire a resource, giving up once `timeout` has elapsed.
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<CountingSemaphoreGuard<'_>, AcquireError> {
+        let deadline = self.clock.now() + timeout;
+        self.acquire_until(Want::Exactly(1), Some(deadline))
+            .map(|permits| CountingSemaphoreGuard { sem: self, permits })
+    }
+
+    // Attempt to acquire a resource without blocking.  Returns None if no resources are free (or,
+    // for a fair semaphore, if other tasks are already waiting for one), or if it is closed.
+    pub fn try_acquire(&self) -> Option<CountingSemaphoreGuard<'_>> {
+        let start = self.clock.now();
+        let mut state = self.lock.lock().unwrap();
+        if self.may_skip_queue(&state, 1) {
+            self.take(&mut state, 1, start);
+            Some(CountingSemaphoreGuard { sem: self, permits: 1 })
+        } else {
+            state.record_failure();
+            None
+        }
+    }
+
+    // Return remaining resource count
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    // Return total resource count, whether remaining or held
+    pub fn max(&self) -> usize {
+        self.max.load(Ordering::SeqCst)
+    }
+
+    // Add `n` resources to the semaphore, raising its total as well as its remaining count and
+    // waking tasks that can now go ahead.  This gives back resources a guard forgot, or grows the
+    // semaphore while it is in use.
+    pub fn add_permits(&self, n: usize) {
+        // Raised first, so that the count is never seen above the total
+        self.max.fetch_add(n, Ordering::SeqCst);
+        self.release(n);
+    }
+
+    // The contention statistics recorded so far, or None if the semaphore isn't instrumented.
+    pub fn stats(&self) -> Option<Stats> {
+        self.lock.lock().unwrap().stats.as_ref().map(|tally| Stats {
+            acquisitions: tally.acquisitions,
+            failed_attempts: tally.failed_attempts,
+            min_wait: tally.min_wait,
+            mean_wait: if tally.acquisitions == 0 {
+                Duration::ZERO
+            } else {
+                tally.total_wait / tally.acquisitions as u32
+            },
+            max_wait: tally.max_wait,
+        })
+    }
+
+    // Close the semaphore: every waiting task is woken up with Err(Closed), and no more resources
+    // are handed out.  Guards that are already held stay valid.
+    pub fn close(&self) {
+        let mut state = self.lock.lock().unwrap();
+        state.closed = true;
+        self.cond.notify_all();
+    }
+
+    // Sleep until the resources wanted are available (and, if fair, every earlier waiter has been
+    // served), or until `deadline`, and return how many were taken.
+    fn acquire_until(&self, want: Want, deadline: Option<Instant>) -> Result<usize, AcquireError> {
+        let start = self.clock.now();
+        if let Some(ref backoff) = self.backoff {
+            return self.poll_until(&**backoff, want, start, deadline);
+        }
+        let mut state = self.lock.lock().unwrap();
+        if state.closed {
+            return Err(AcquireError::Closed);
+        }
+        let n = want.count(self);
+        if self.may_skip_queue(&state, n) {
+            self.take(&mut state, n, start);
+            return Ok(n);
+        }
+        state.record_failure();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        if self.fair {
+            state.queue.push_back(ticket);
+        }
+        let greedy = want.greedy();
+        if greedy {
+            state.greedy += 1;
+        }
+        loop {
+            match deadline {
+                None => state = self.cond.wait(state).unwrap(),
+                Some(deadline) => {
+                    let now = self.clock.now();
+                    if now >= deadline {
+                        self.stop_waiting(&mut state, ticket, greedy);
+                        return Err(AcquireError::TimedOut);
+                    }
+                    state = self.cond.wait_timeout(state, deadline - now).unwrap().0;
+                }
+            }
+            self.wakeups.fetch_add(1, Ordering::SeqCst);
+            if state.closed {
+                self.stop_waiting(&mut state, ticket, greedy);
+                return Err(AcquireError::Closed);
+            }
+            // The loop guards against spurious wakeups as well as against another task grabbing
+            // the resource before we got the lock.
+            let n = want.count(self);
+            if state.count >= n && (!self.fair || state.queue.front() == Some(&ticket)) {
+                if self.fair {
+                    state.queue.pop_front();
+                }
+                if greedy {
+                    state.greedy -= 1;
+                }
+                self.take(&mut state, n, start);
+                if self.fair && state.count > 0 {
+                    // More than one resource is free, so the next in line can go too
+                    self.cond.notify_all();
+                }
+                return Ok(n);
+            }
+            state.record_failure();
+        }
+    }
+
+    // Like acquire_until, but instead of waiting to be woken up, retry after every delay given by
+    // `backoff`.
+    fn poll_until(&self, backoff: &dyn Backoff, want: Want, start: Instant,
+                  deadline: Option<Instant>) -> Result<usize, AcquireError> {
+        let mut attempt = 0;
+        loop {
+            {
+                let mut state = self.lock.lock().unwrap();
+                if state.closed {
+                    return Err(AcquireError::Closed);
+                }
+                let n = want.count(self);
+                if state.count >= n {
+                    self.take(&mut state, n, start);
+                    return Ok(n);
+                }
+                state.record_failure();
+            }
+            attempt += 1;
+            let mut delay = backoff.delay(attempt);
+            if let Some(deadline) = deadline {
+                let now = self.clock.now();
+                if now >= deadline {
+                    return Err(AcquireError::TimedOut);
+                }
+                // Don't oversleep the deadline
+                delay = cmp::min(delay, deadline - now);
+            }
+            if delay > Duration::ZERO {
+                self.clock.sleep(delay);
+            } else {
+                thread::yield_now();
+            }
+            self.wakeups.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // Give up waiting for resources: leave the queue, and let whoever is now first check if it's
+    // their turn.
+    fn stop_waiting(&self, state: &mut State, ticket: usize, greedy: bool) {
+        if self.fair {
+            let pos = state.queue.iter().position(|&t| t == ticket).unwrap();
+            state.queue.remove(pos);
+            self.cond.notify_all();
+        }
+        if greedy {
+            state.greedy -= 1;
+        }
+    }
+
+    // Take `n` resources that a guard forgot out of the total.  Whoever waits for all of them now
+    // has fewer to wait for, so they're woken to check, with the lock held so that none of them
+    // misses it between reading the total and going back to sleep.
+    fn forget_permits(&self, n: usize) {
+        let state = self.lock.lock().unwrap();
+        self.max.fetch_sub(n, Ordering::SeqCst);
+        if state.greedy > 0 {
+            self.cond.notify_all();
+        }
+    }
+
+    // Whether a task that just arrived may take `n` resources without waiting.
+    fn may_skip_queue(&self, state: &State, n: usize) -> bool {
+        !state.closed && state.count >= n && (!self.fair || state.queue.is_empty())
+    }
+
+    // Hand out `n` resources to a task that started asking for them at `start`.  Must be called
+    // with the lock held and at least `n` remaining.
+    fn take(&self, state: &mut State, n: usize, start: Instant) {
+        state.count -= n;
+        self.count.store(state.count, Ordering::SeqCst);
+        if let Some(ref mut tally) = state.stats {
+            let wait = self.clock.now() - start;
+            if tally.acquisitions == 0 || wait < tally.min_wait {
+                tally.min_wait = wait;
+            }
+            tally.max_wait = cmp::max(tally.max_wait, wait);
+            tally.total_wait += wait;
+            tally.acquisitions += 1;
+        }
+    }
+
+    // Release `n` resources back to the pool and wake a waiting task (if any) to claim them.
+    // Everyone has to be woken if the one task we picked might not be able to use them: for a
+    // fair semaphore only the head of the queue may take them, a task waiting for several
+    // resources may need more than were released, and several released resources may be enough
+    // for several tasks.
+    fn release(&self, n: usize) {
+        let mut state = self.lock.lock().unwrap();
+        state.count += n;
+        self.count.store(state.count, Ordering::SeqCst);
+        if self.fair || state.greedy > 0 || n > 1 {
+            self.cond.notify_all();
+        } else {
+            self.cond.notify_one();
+        }
+    }
+}
+
+impl State {
+    fn record_failure(&mut self) {
+        if let Some(ref mut tally) = self.stats {
+            tally.failed_attempts += 1;
+        }
+    }
+}
+
+impl<'a> CountingSemaphoreGuard<'a> {
+    // Give up the guard without releasing its resources.  They are taken out of the semaphore
+    // altogether, until add_permits() puts them back.
+    pub fn forget(mut self) {
+        self.sem.forget_permits(self.permits);
+        self.permits = 0;
+    }
+}
+
+impl<'a> Drop for CountingSemaphoreGuard<'a> {
+    // When the guard is dropped, its resources are released back to the pool.
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.sem.release(self.permits);
+        }
+    }
+}
+
+impl OwnedSemaphoreGuard {
+    // Like CountingSemaphoreGuard::forget.
+    pub fn forget(mut self) {
+        self.sem.forget_permits(self.permits);
+        self.permits = 0;
+    }
+}
+
+impl Drop for OwnedSemaphoreGuard {
+    // When the guard is dropped, its resource is released back to the pool.
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.sem.release(self.permits);
+        }
+    }
+}
+
+pub struct CyclicBarrier {
+    lock: Mutex<BarrierState>,
+    cond: Condvar, // Signaled when the last task of a generation arrives
+    parties: usize, // How many tasks have to arrive before they may all continue
+}
+
+struct BarrierState {
+    arrived: usize,    // Tasks that have arrived in the current generation
+    generation: usize, // Bumped every time the barrier trips
+}
+
+impl CyclicBarrier {
+    // Create a barrier for `parties` tasks.
+    pub fn new(parties: usize) -> CyclicBarrier {
+        assert!(parties > 0, "a barrier needs at least one party");
+        CyclicBarrier {
+            lock: Mutex::new(BarrierState { arrived: 0, generation: 0 }),
+            cond: Condvar::new(),
+            parties,
+        }
+    }
+
+    // Wait until all parties have called wait(), then let them all continue.  Returns true for
+    // exactly one task per phase (the last to arrive), which can be used to elect a leader.
+    pub fn wait(&self) -> bool {
+        let mut state = self.lock.lock().unwrap();
+        let generation = state.generation;
+        state.arrived += 1;
+        if state.arrived == self.parties {
+            // Trip the barrier, and get it ready for the next phase straight away
+            state.arrived = 0;
+            state.generation += 1;
+            self.cond.notify_all();
+            return true;
+        }
+        // Comparing generations rather than counts tells spurious wakeups apart from the barrier
+        // having tripped, even if fast tasks have already started arriving for the next phase.
+        while state.generation == generation {
+            state = self.cond.wait(state).unwrap();
+        }
+        false
+    }
+}
+
+// A token bucket: up to `burst` tokens can be taken at once, after which they are refilled at a
+// steady rate.  Each token is a resource of the semaphore that is never released by whoever takes
+// it; only the refill task puts them back.
+pub struct RateLimiter {
+    sem: Arc<CountingSemaphore>,
+}
+
+impl RateLimiter {
+    // Create a rate limiter that starts out full, and adds a token every `interval` until it is
+    // full again.
+    pub fn new(burst: usize, interval: Duration) -> RateLimiter {
+        assert!(burst > 0, "a rate limiter needs room for at least one token");
+        let sem = Arc::new(CountingSemaphore::new(burst));
+        let bucket = Arc::downgrade(&sem);
+        spawn(move || refill(bucket, interval));
+        RateLimiter { sem }
+    }
+
+    // Create a rate limiter that lets `per_second` tokens through every second, one at a time.
+    // Panics if `per_second` is 0, since no interval between tokens would be slow enough.
+    pub fn per_second(per_second: u32) -> RateLimiter {
+        assert!(per_second > 0, "a rate limiter has to let at least one token through a second");
+        RateLimiter::new(1, Duration::from_secs(1) / per_second)
+    }
+
+    // Take a token, waiting for the next refill if there are none left.
+    pub fn acquire(&self) {
+        // The semaphore is never closed, so this can't fail
+        mem::forget(self.sem.acquire().unwrap());
+    }
+
+    // Take a token if there is one, without waiting.
+    pub fn try_acquire(&self) -> bool {
+        self.sem.try_acquire().map(mem::forget).is_some()
+    }
+
+    // Return how many tokens are left
+    pub fn count(&self) -> usize {
+        self.sem.count()
+    }
+}
+
+// Put a token back in `bucket` every `interval`, until the RateLimiter it belongs to is dropped.
+// Nobody else ever releases resources of the bucket, so a count below max can't rise before we
+// release ours.
+fn refill(bucket: Weak<CountingSemaphore>, interval: Duration) {
+    loop {
+        sleep(interval);
+        match bucket.upgrade() {
+            Some(sem) => if sem.count() < sem.max() {
+                sem.release(1);
+            },
+            None => return,
+        }
+    }
+}
+
+// A semaphore that lives outside any one process, so that separate programs can meter their use of
+// a resource between them.  Every process opens it by the same name, and the first one to do so
+// creates it.  Resources held by a process that dies without releasing them are lost.
+pub struct NamedSemaphore {
+    inner: named::Semaphore,
+}
+
+pub struct NamedSemaphoreGuard<'a> {
+    sem: &'a NamedSemaphore, // A reference to the owning semaphore.
+}
+
+impl NamedSemaphore {
+    // Open the semaphore called `name`, creating it with `max` available resources if no process
+    // has it yet.
+    pub fn open(name: &str, max: u32) -> io::Result<NamedSemaphore> {
+        named::Semaphore::open(name, max).map(|inner| NamedSemaphore { inner })
+    }
+
+    // Acquire a resource, waiting for another process (or task) to release one if need be.
+    pub fn acquire(&self) -> io::Result<NamedSemaphoreGuard<'_>> {
+        self.inner.wait().map(|()| NamedSemaphoreGuard { sem: self })
+    }
+
+    // Attempt to acquire a resource without blocking.
+    pub fn try_acquire(&self) -> io::Result<Option<NamedSemaphoreGuard<'_>>> {
+        let taken = self.inner.try_wait()?;
+        Ok(if taken { Some(NamedSemaphoreGuard { sem: self }) } else { None })
+    }
+
+    // Remove the name, so that the next open() creates a new semaphore.  Processes that already
+    // have the old one open can keep using it.
+    pub fn unlink(name: &str) -> io::Result<()> {
+        named::Semaphore::unlink(name)
+    }
+}
+
+impl<'a> Drop for NamedSemaphoreGuard<'a> {
+    // When the guard is dropped, its resource is released back to the pool.
+    fn drop(&mut self) {
+        self.sem.inner.post();
+    }
+}
+
+// The name as a C string, for the platforms that want one.
+#[cfg(unix)]
+fn c_name(name: &str) -> io::Result<CString> {
+    CString::new(format!("/{}", name)).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidInput, "semaphore names can't contain NUL")
+    })
+}
+
+// On Linux, the count is kept in shared memory, and waiters sleep on it with a futex.
+#[cfg(target_os = "linux")]
+mod named {
+    use super::libc;
+    use std::io;
+    use std::mem;
+    use std::ptr;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::thread;
+
+    #[repr(C)]
+    struct Shared {
+        ready: AtomicU32, // Set once the creator has initialised `count`
+        count: AtomicU32, // Remaining resource count, and the futex word waiters sleep on
+    }
+
+    pub struct Semaphore {
+        shared: *const Shared, // Mapped into every process that has the semaphore open
+    }
+
+    // The mapping is only ever accessed atomically
+    unsafe impl Send for Semaphore {}
+    unsafe impl Sync for Semaphore {}
+
+    impl Semaphore {
+        pub fn open(name: &str, max: u32) -> io::Result<Semaphore> {
+            let path = super::c_name(name)?;
+            let size = mem::size_of::<Shared>();
+            unsafe {
+                let mut created = true;
+                let flags = libc::O_RDWR | libc::O_CREAT | libc::O_EXCL;
+                let mut fd = libc::shm_open(path.as_ptr(), flags, 0o600);
+                if fd < 0 && io::Error::last_os_error().raw_os_error() == Some(libc::EEXIST) {
+                    created = false;
+                    fd = libc::shm_open(path.as_ptr(), libc::O_RDWR, 0);
+                }
+                if fd < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                let result = Semaphore::map(fd, size, created);
+                libc::close(fd);
+                let shared = result? as *const Shared;
+                if created {
+                    (*shared).count.store(max, Ordering::SeqCst);
+                    (*shared).ready.store(1, Ordering::SeqCst);
+                } else {
+                    while (*shared).ready.load(Ordering::SeqCst) == 0 {
+                        thread::yield_now();
+                    }
+                }
+                Ok(Semaphore { shared })
+            }
+        }
+
+        // Map `fd`, first sizing it if we created it, or else waiting for whoever did.  Touching a
+        // mapping past the end of the file would kill the process.
+        unsafe fn map(fd: libc::c_int, size: usize, created: bool)
+                      -> io::Result<*mut libc::c_void> {
+            if created {
+                if libc::ftruncate(fd, size as libc::off_t) < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            } else {
+                loop {
+                    let mut stat: libc::stat = mem::zeroed();
+                    if libc::fstat(fd, &mut stat) < 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    if stat.st_size as usize >= size {
+                        break;
+                    }
+                    thread::yield_now();
+                }
+            }
+            let addr = libc::mmap(ptr::null_mut(), size, libc::PROT_READ | libc::PROT_WRITE,
+                                  libc::MAP_SHARED, fd, 0);
+            if addr == libc::MAP_FAILED {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(addr)
+            }
+        }
+
+        fn count(&self) -> &AtomicU32 {
+            unsafe { &(*self.shared).count }
+        }
+
+        pub fn wait(&self) -> io::Result<()> {
+            while !self.take() {
+                // Returns straight away if the count is no longer zero by the time the kernel
+                // looks, so a release can't slip in between the check and going to sleep.  The
+                // futex isn't private, since the other waiters are in other processes.
+                unsafe {
+                    libc::syscall(libc::SYS_futex, self.count() as *const AtomicU32,
+                                  libc::FUTEX_WAIT, 0, ptr::null::<libc::timespec>());
+                }
+            }
+            Ok(())
+        }
+
+        pub fn try_wait(&self) -> io::Result<bool> {
+            Ok(self.take())
+        }
+
+        // Take a resource if there are any left.
+        fn take(&self) -> bool {
+            let count = self.count();
+            let mut remaining = count.load(Ordering::SeqCst);
+            while remaining > 0 {
+                match count.compare_exchange(remaining, remaining - 1, Ordering::SeqCst,
+                                             Ordering::SeqCst) {
+                    Ok(_) => return true,
+                    Err(now) => remaining = now,
+                }
+            }
+            false
+        }
+
+        pub fn post(&self) {
+            let count = self.count();
+            count.fetch_add(1, Ordering::SeqCst);
+            unsafe {
+                libc::syscall(libc::SYS_futex, count as *const AtomicU32, libc::FUTEX_WAKE, 1);
+            }
+        }
+
+        pub fn unlink(name: &str) -> io::Result<()> {
+            let path = super::c_name(name)?;
+            if unsafe { libc::shm_unlink(path.as_ptr()) } < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for Semaphore {
+        fn drop(&mut self) {
+            unsafe {
+                libc::munmap(self.shared as *mut libc::c_void, mem::size_of::<Shared>());
+            }
+        }
+    }
+}
+
+// Elsewhere on Unix (notably macOS, which has no futexes), use a POSIX named semaphore.
+#[cfg(all(unix, not(target_os = "linux")))]
+mod named {
+    use super::libc;
+    use std::io;
+
+    pub struct Semaphore {
+        sem: *mut libc::sem_t,
+    }
+
+    // sem_t is made to be shared between threads
+    unsafe impl Send for Semaphore {}
+    unsafe impl Sync for Semaphore {}
+
+    impl Semaphore {
+        pub fn open(name: &str, max: u32) -> io::Result<Semaphore> {
+            let path = super::c_name(name)?;
+            // The mode and value are ignored if the semaphore already exists
+            let sem = unsafe {
+                libc::sem_open(path.as_ptr(), libc::O_CREAT, 0o600 as libc::c_uint,
+                               max as libc::c_uint)
+            };
+            if sem == libc::SEM_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Semaphore { sem })
+        }
+
+        pub fn wait(&self) -> io::Result<()> {
+            while unsafe { libc::sem_wait(self.sem) } < 0 {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+            Ok(())
+        }
+
+        pub fn try_wait(&self) -> io::Result<bool> {
+            if unsafe { libc::sem_trywait(self.sem) } == 0 {
+                return Ok(true);
+            }
+            let error = io::Error::last_os_error();
+            match error.raw_os_error() {
+                Some(libc::EAGAIN) => Ok(false),
+                _ => Err(error),
+            }
+        }
+
+        pub fn post(&self) {
+            unsafe {
+                libc::sem_post(self.sem);
+            }
+        }
+
+        pub fn unlink(name: &str) -> io::Result<()> {
+            let path = super::c_name(name)?;
+            if unsafe { libc::sem_unlink(path.as_ptr()) } < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for Semaphore {
+        fn drop(&mut self) {
+            unsafe {
+                libc::sem_close(self.sem);
+            }
+        }
+    }
+}
+
+// On Windows, use a named kernel semaphore.
+#[cfg(windows)]
+mod named {
+    use std::ffi::OsStr;
+    use std::io;
+    use std::os::raw::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr;
+
+    type Handle = *mut c_void;
+
+    const INFINITE: u32 = 0xFFFF_FFFF;
+    const WAIT_OBJECT_0: u32 = 0;
+    const WAIT_TIMEOUT: u32 = 0x102;
+
+    extern "system" {
+        fn CreateSemaphoreW(attributes: *mut c_void, initial: i32, max: i32, name: *const u16)
+                            -> Handle;
+        fn WaitForSingleObject(handle: Handle, millis: u32) -> u32;
+        fn ReleaseSemaphore(handle: Handle, count: i32, previous: *mut i32) -> i32;
+        fn CloseHandle(handle: Handle) -> i32;
+    }
+
+    pub struct Semaphore {
+        handle: Handle,
+    }
+
+    // Kernel objects may be used from any thread
+    unsafe impl Send for Semaphore {}
+    unsafe impl Sync for Semaphore {}
+
+    impl Semaphore {
+        pub fn open(name: &str, max: u32) -> io::Result<Semaphore> {
+            let name = format!("Local\\{}", name);
+            let name: Vec<u16> = OsStr::new(&name).encode_wide().chain(Some(0)).collect();
+            // Opens the semaphore instead if it already exists, ignoring the counts
+            let handle = unsafe {
+                CreateSemaphoreW(ptr::null_mut(), max as i32, max as i32, name.as_ptr())
+            };
+            if handle.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Semaphore { handle })
+        }
+
+        pub fn wait(&self) -> io::Result<()> {
+            match unsafe { WaitForSingleObject(self.handle, INFINITE) } {
+                WAIT_OBJECT_0 => Ok(()),
+                _ => Err(io::Error::last_os_error()),
+            }
+        }
+
+        pub fn try_wait(&self) -> io::Result<bool> {
+            match unsafe { WaitForSingleObject(self.handle, 0) } {
+                WAIT_OBJECT_0 => Ok(true),
+                WAIT_TIMEOUT => Ok(false),
+                _ => Err(io::Error::last_os_error()),
+            }
+        }
+
+        pub fn post(&self) {
+            unsafe {
+                ReleaseSemaphore(self.handle, 1, ptr::null_mut());
+            }
+        }
+
+        // The semaphore goes away by itself once every handle to it is closed.
+        pub fn unlink(_name: &str) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Drop for Semaphore {
+        fn drop(&mut self) {
+            unsafe {
+                CloseHandle(self.handle);
+            }
+        }
+    }
+}
+
+// The result of polling a future: either its output, or a promise that the waker it was polled
+// with will be called once it is worth polling again.
+pub enum Poll<T> {
+    Ready(T),
+    Pending,
+}
+
+// Something that can be told a future is ready to make progress.
+pub trait Wake: Send + Sync {
+    fn wake(&self);
+}
+
+pub trait Future {
+    type Output;
+    fn poll(&mut self, waker: &Arc<dyn Wake>) -> Poll<Self::Output>;
+}
+
+pub struct AsyncCountingSemaphore {
+    lock: Mutex<AsyncState>,
+    count: AtomicUsize, // Mirror of the remaining count, so count() doesn't need the lock
+}
+
+struct AsyncState {
+    count: usize,            // Remaining resource count
+    waiters: Vec<Arc<dyn Wake>>, // Wakers of the futures waiting for a resource
+}
+
+// The future returned by AsyncCountingSemaphore::acquire.
+pub struct Acquire<'a> {
+    sem: &'a AsyncCountingSemaphore,
+}
+
+pub struct AsyncCountingSemaphoreGuard<'a> {
+    sem: &'a AsyncCountingSemaphore, // A reference to the owning semaphore.
+}
+
+impl AsyncCountingSemaphore {
+    // Create a semaphore with `max` available resources.
+    pub fn new(max: usize) -> AsyncCountingSemaphore {
+        AsyncCountingSemaphore {
+            lock: Mutex::new(AsyncState { count: max, waiters: Vec::new() }),
+            count: AtomicUsize::new(max),
+        }
+    }
+
+    // Returns a future that resolves to a RAII AsyncCountingSemaphoreGuard once a resource is
+    // available.  Nothing happens until the future is polled.
+    pub fn acquire(&self) -> Acquire<'_> {
+        Acquire { sem: self }
+    }
+
+    // Return remaining resource count
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = AsyncCountingSemaphoreGuard<'a>;
+
+    fn poll(&mut self, waker: &Arc<dyn Wake>) -> Poll<AsyncCountingSemaphoreGuard<'a>> {
+        let mut state = self.sem.lock.lock().unwrap();
+        if state.count == 0 {
+            // Register interest; the next release will wake us up to try again.
+            state.waiters.push(waker.clone());
+            Poll::Pending
+        } else {
+            state.count -= 1;
+            self.sem.count.store(state.count, Ordering::SeqCst);
+            Poll::Ready(AsyncCountingSemaphoreGuard { sem: self.sem })
+        }
+    }
+}
+
+impl<'a> Drop for AsyncCountingSemaphoreGuard<'a> {
+    // When the guard is dropped, a resource is released back to the pool.  Every waiting future
+    // is woken: the first to be polled gets the resource and the rest simply register again.
+    // Waking just one would lose the resource if that future happened to be dropped unpolled.
+    fn drop(&mut self) {
+        let waiters = {
+            let mut state = self.sem.lock.lock().unwrap();
+            state.count += 1;
+            self.sem.count.store(state.count, Ordering::SeqCst);
+            mem::take(&mut state.waiters)
+        };
+        for waiter in waiters.iter() {
+            waiter.wake();
+        }
+    }
+}
+
+// A future that completes after `duration`, using a helper thread as the timer.
+pub struct Sleep {
+    duration: Duration,
+    done: Option<Arc<AtomicBool>>,
+}
+
+impl Sleep {
+    pub fn new(duration: Duration) -> Sleep {
+        Sleep { duration, done: None }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(&mut self, waker: &Arc<dyn Wake>) -> Poll<()> {
+        match self.done {
+            Some(ref done) => if done.load(Ordering::SeqCst) { Poll::Ready(()) } else { Poll::Pending },
+            None => {
+                let done = Arc::new(AtomicBool::new(false));
+                let (duration, done_, waker) = (self.duration, done.clone(), waker.clone());
+                spawn(move || {
+                    sleep(duration);
+                    done_.store(true, Ordering::SeqCst);
+                    waker.wake();
+                });
+                self.done = Some(done);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// Wakes a task of the executor by putting it back on the ready list.
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<Vec<usize>>>,
+    thread: Thread,
+}
+
+impl Wake for TaskWaker {
+    fn wake(&self) {
+        self.ready.lock().unwrap().push(self.id);
+        self.thread.unpark();
+    }
+}
+
+// Run all `tasks` to completion on the current thread, parking it whenever no task is ready.
+pub fn run_to_completion<'a>(mut tasks: Vec<Box<dyn Future<Output=()> + 'a>>) {
+    let ready = Arc::new(Mutex::new((0..tasks.len()).collect::<Vec<_>>()));
+    let wakers: Vec<Arc<dyn Wake>> = (0..tasks.len()).map(|id| {
+        Arc::new(TaskWaker { id, ready: ready.clone(), thread: thread::current() }) as Arc<dyn Wake>
+    }).collect();
+    let mut done = vec![false; tasks.len()];
+    let mut remaining = tasks.len();
+    while remaining > 0 {
+        let batch = mem::take(&mut *ready.lock().unwrap());
+        if batch.is_empty() {
+            // A wake() between taking the batch and parking leaves an unpark token behind, so
+            // this can't miss a wakeup.
+            thread::park();
+            continue;
+        }
+        for id in batch {
+            if done[id] {
+                continue;
+            }
+            if let Poll::Ready(()) = tasks[id].poll(&wakers[id]) {
+                done[id] = true;
+                remaining -= 1;
+            }
+        }
+    }
+}
+
+// Write a line of a demo's output, which its workers share.  Like println!(), it panics if the
+// line can't be written.
+macro_rules! say {
+    ($output:expr, $($arg:tt)*) => (writeln!($output.lock().unwrap(), $($arg)*).unwrap())
+}
+
+// A worker of the async demo: acquire, hold the resource for a while, release.
+enum Worker<'a> {
+    Acquiring(usize, Acquire<'a>),
+    Holding(usize, AsyncCountingSemaphoreGuard<'a>, Sleep),
+    Done,
+}
+
+struct AsyncWorker<'a> {
+    sem: &'a AsyncCountingSemaphore,
+    max: usize,
+    duration: Duration,
+    state: Worker<'a>,
+    output: &'a Mutex<dyn Write + Send + 'a>,
+}
+
+impl<'a> Future for AsyncWorker<'a> {
+    type Output = ();
+
+    fn poll(&mut self, waker: &Arc<dyn Wake>) -> Poll<()> {
+        loop {
+            match mem::replace(&mut self.state, Worker::Done) {
+                Worker::Acquiring(i, mut acquire) => match acquire.poll(waker) {
+                    Poll::Ready(guard) => {
+                        let count = self.sem.count();
+                        // Make sure the count is legal
+                        assert!(count < self.max);
+                        say!(self.output, "Async worker {} after acquire: count = {}", i, count);
+                        self.state = Worker::Holding(i, guard, Sleep::new(self.duration));
+                    }
+                    Poll::Pending => {
+                        self.state = Worker::Acquiring(i, acquire);
+                        return Poll::Pending;
+                    }
+                },
+                Worker::Holding(i, guard, mut sleep) => match sleep.poll(waker) {
+                    Poll::Ready(()) => {
+                        // Release the resource
+                        drop(guard);
+                        let count = self.sem.count();
+                        assert!(count <= self.max);
+                        say!(self.output, "Async worker {} after release: count = {}", i, count);
+                        return Poll::Ready(());
+                    }
+                    Poll::Pending => {
+                        self.state = Worker::Holding(i, guard, sleep);
+                        return Poll::Pending;
+                    }
+                },
+                Worker::Done => return Poll::Ready(()),
+            }
+        }
+    }
+}
+
+// The same demo as metered(), but with every worker driven by a single-threaded executor.
+fn metered_async(duration: Duration, output: &Mutex<dyn Write + Send + '_>) {
+    static MAX_COUNT: usize = 4; // Total available resources
+    static NUM_WORKERS: usize = 10; // Number of workers contending for the resources
+    fn worker<'a>(i: usize, sem: &'a AsyncCountingSemaphore, duration: Duration,
+                  output: &'a Mutex<dyn Write + Send + 'a>) -> Box<dyn Future<Output=()> + 'a> {
+        Box::new(AsyncWorker {
+            sem,
+            max: MAX_COUNT,
+            duration,
+            state: Worker::Acquiring(i, sem.acquire()),
+            output,
+        })
+    }
+
+    let sem = AsyncCountingSemaphore::new(MAX_COUNT);
+    run_to_completion((0..NUM_WORKERS).map(|i| worker(i, &sem, duration, output)).collect());
+    assert_eq!(sem.count(), MAX_COUNT);
+}
+
+// Settings for the metered() demo, taken from the command line.
+#[derive(Debug, PartialEq)]
+struct Config {
+    permits: usize,            // Total available resources
+    workers: usize,            // Number of workers contending for the resources
+    hold: Duration,            // How long each worker holds on to its resource
+    backoff: Option<Duration>, // Poll with this linear backoff instead of parking waiters
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { permits: 4, workers: 10, hold: Duration::from_secs(2), backoff: None }
+    }
+}
+
+// Parse the arguments following the program name.  Anything not given keeps its default.
+fn parse_args(mut args: Args) -> Result<Config, String> {
+    let mut config = Config::default();
+    match args.value("--permits")? {
+        Some(0) => return Err("--permits must be positive".to_string()),
+        Some(permits) => config.permits = permits,
+        None => {}
+    }
+    if let Some(workers) = args.value("--workers")? {
+        config.workers = workers;
+    }
+    if let Some(hold) = args.value("--hold-ms")? {
+        config.hold = Duration::from_millis(hold);
+    }
+    match args.value("--backoff-ms")? {
+        Some(0) => return Err("--backoff-ms must be positive".to_string()),
+        Some(backoff) => config.backoff = Some(Duration::from_millis(backoff)),
+        None => {}
+    }
+    args.finish()?;
+    Ok(config)
+}
+
+// Run the workers as `config` says, and return how long each of them waited for its resource.
+fn metered(config: &Config, output: &Mutex<dyn Write + Send + '_>) -> Vec<Duration> {
+    let max_count = config.permits;
+    // Create a shared reference to the semaphore, recording how long workers had to wait
+    let sem = match config.backoff {
+        Some(backoff) => CountingSemaphore::with_backoff(max_count, Linear(backoff)),
+        None => CountingSemaphore::new(max_count),
+    };
+    let sem = Arc::new(sem.instrumented());
+    // Create a channel for the workers to report their wait times to the main task
+    let (tx, rx) = channel();
+    thread::scope(|scope| for i in 0..config.workers {
+        let sem = sem.clone();
+        let tx = tx.clone();
+        let duration = config.hold;
+        scope.spawn(move || {
+            // Acquire the resource
+            let start = Instant::now();
+            let guard = sem.acquire().unwrap();
+            let waited = start.elapsed();
+            let count = sem.count();
+            // Make sure the count is legal
+            assert!(count < max_count);
+            say!(output, "Worker {} after acquire: count = {}", i, count);
+            // Sleep for `duration`
+            sleep(duration);
+            // Release the resource
+            drop(guard);
+            // Make sure the count is legal
+            let count = sem.count();
+            assert!(count <= max_count);
+            say!(output, "Worker {} after release: count = {}", i, count);
+            // Notify the main task of completion
+            tx.send((i, waited)).unwrap();
+        });
+    });
+    drop(tx);
+    // Wait for all the subtasks to finish
+    let mut waits = vec![Duration::ZERO; config.workers];
+    for (i, waited) in rx.iter() {
+        waits[i] = waited;
+    }
+    let stats = sem.stats().unwrap();
+    assert_eq!(stats.acquisitions, config.workers);
+    say!(output, "{} acquisitions, {} failed attempts", stats.acquisitions, stats.failed_attempts);
+    say!(output, "Wait time: min = {}ms, mean = {}ms, max = {}ms", stats.min_wait.as_millis(),
+         stats.mean_wait.as_millis(), stats.max_wait.as_millis());
+    say!(output, "");
+    say!(output, "Worker | Waited (ms)");
+    say!(output, "-------+------------");
+    for (i, waited) in waits.iter().enumerate() {
+        say!(output, "{:>6} | {:>11}", i, waited.as_millis());
+    }
+    waits
+}
+
+// NUM_WORKERS workers do NUM_PHASES phases of work, each waiting for the others at the end of
+// every phase.  Returns how many workers reported having finished each phase.
+fn phased(duration: Duration, output: &Mutex<dyn Write + Send + '_>) -> Vec<usize> {
+    static NUM_WORKERS: usize = 5;
+    static NUM_PHASES: usize = 3;
+    let barrier = Arc::new(CyclicBarrier::new(NUM_WORKERS));
+    let finished = Arc::new(Mutex::new(vec![0; NUM_PHASES]));
+    thread::scope(|scope| for i in 0..NUM_WORKERS {
+        let barrier = barrier.clone();
+        let finished = finished.clone();
+        scope.spawn(move || {
+            for phase in 0..NUM_PHASES {
+                // Workers take different amounts of time, but none gets more than a phase ahead
+                sleep(duration * (i as u32 + 1));
+                finished.lock().unwrap()[phase] += 1;
+                if barrier.wait() {
+                    // Everyone is done with this phase by the time the barrier trips
+                    let done = finished.lock().unwrap()[phase];
+                    assert_eq!(done, NUM_WORKERS);
+                    say!(output, "Worker {} was last to finish phase {}", i, phase);
+                }
+            }
+        });
+    });
+    let finished = finished.lock().unwrap().clone();
+    finished
+}
+
+// NUM_WORKERS workers make NUM_REQUESTS simulated requests between them, with no more than
+// `per_second` let through every second.  Returns when each request was let through, relative to
+// the start.
+fn rate_limited(per_second: u32, output: &Mutex<dyn Write + Send + '_>) -> Vec<Duration> {
+    static NUM_WORKERS: usize = 3;
+    static NUM_REQUESTS: usize = 4; // Per worker
+    let limiter = Arc::new(RateLimiter::per_second(per_second));
+    let start = Instant::now();
+    let (tx, rx) = channel();
+    thread::scope(|scope| for i in 0..NUM_WORKERS {
+        let limiter = limiter.clone();
+        let tx = tx.clone();
+        scope.spawn(move || {
+            for request in 0..NUM_REQUESTS {
+                limiter.acquire();
+                let at = start.elapsed();
+                say!(output, "Worker {} sent request {} at {}ms", i, request, at.as_millis());
+                tx.send(at).unwrap();
+            }
+        });
+    });
+    drop(tx);
+    let mut times: Vec<_> = rx.iter().collect();
+    times.sort();
+    times
+}
+
+// All of the demos, one after the other, with `config` for metered() and its async twin, and
+// phases half as long as the resources are held for in phased().
+fn run(config: &Config, output: impl Write + Send) -> io::Result<()> {
+    let output = Mutex::new(output);
+    metered(config, &output);
+    // And again, with the workers as futures on a single thread
+    metered_async(config.hold, &output);
+    // Workers synchronizing at a barrier between phases
+    phased(config.hold / 2, &output);
+    // Simulated requests, rate limited to 5 per second
+    rate_limited(5, &output);
+    output.into_inner().unwrap().flush()
+}
+
+#[test]
+fn test_run() {
+    let config = Config { workers: 3, hold: Duration::from_millis(10), ..Config::default() };
+    let output = utils::capture::output_of(|_, output| run(&config, output), "");
+    let lines: Vec<_> = output.lines().collect();
+    // 3 workers in metered() and 10 in metered_async() acquire and release
+    assert_eq!(lines.iter().filter(|line| line.contains(" after acquire: ")).count(), 13);
+    assert_eq!(lines.iter().filter(|line| line.contains(" after release: ")).count(), 13);
+    assert!(lines.contains(&"3 acquisitions, 0 failed attempts"));
+    assert!(lines.contains(&"Worker | Waited (ms)"));
+    assert_eq!(lines.iter().filter(|line| line.contains(" was last to finish phase ")).count(), 3);
+    assert_eq!(lines.iter().filter(|line| line.contains(" sent request ")).count(), 12);
+}
+
+#[test]
+fn test_metered_concurrency() {
+    // Hold each resource for 1/20 of a second per worker
+    let config = Config { hold: Duration::from_secs(1) / 20, ..Config::default() };
+    let waits = metered(&config, &Mutex::new(io::sink()));
+    assert_eq!(waits.len(), 10);
+    // The first four got a resource right away; the last two had to wait for two rounds
+    let mut waits: Vec<_> = waits.into_iter().map(|w| w.as_millis()).collect();
+    waits.sort();
+    assert!(waits[3] < 40 && waits[9] >= 90);
+}
+
+#[test]
+fn test_metered_concurrency_backoff() {
+    let config = Config {
+        permits: 2,
+        workers: 5,
+        hold: Duration::from_millis(20),
+        backoff: Some(Duration::from_millis(1)),
+    };
+    assert_eq!(metered(&config, &Mutex::new(io::sink())).len(), 5);
+}
+
+#[test]
+fn test_parse_args() {
+    let parse = |args: &[&str]| parse_args(Args::new(args.iter().map(|s| s.to_string())));
+    assert_eq!(parse(&[]), Ok(Config::default()));
+    assert_eq!(parse(&["--workers", "3", "--hold-ms", "250", "--permits", "2", "--backoff-ms", "5"]),
+               Ok(Config {
+                   permits: 2,
+                   workers: 3,
+                   hold: Duration::from_millis(250),
+                   backoff: Some(Duration::from_millis(5)),
+               }));
+    assert!(parse(&["--permits", "0"]).is_err());
+    assert!(parse(&["--workers"]).is_err());
+    assert!(parse(&["--workers", "many"]).is_err());
+    assert!(parse(&["--verbose", "1"]).is_err());
+}
+
+#[test]
+fn test_try_acquire() {
+    let sem = CountingSemaphore::new(2);
+    let first = sem.try_acquire();
+    assert!(first.is_some());
+    let second = sem.try_acquire();
+    assert!(second.is_some());
+    assert_eq!(sem.count(), 0);
+    // Every resource is taken, so a non-blocking attempt must fail without changing the count
+    assert!(sem.try_acquire().is_none());
+    assert_eq!(sem.count(), 0);
+    drop(first);
+    assert_eq!(sem.count(), 1);
+    assert!(sem.try_acquire().is_some());
+    // The guard above was dropped immediately, so the resource is back in the pool
+    assert_eq!(sem.count(), 1);
+    drop(second);
+    assert_eq!(sem.count(), 2);
+}
+
+#[test]
+fn test_try_acquire_contended() {
+    static MAX_COUNT: usize = 3;
+    static NUM_WORKERS: usize = 16;
+    let sem = Arc::new(CountingSemaphore::new(MAX_COUNT));
+    // Hold every resource so that contending workers can only ever fail
+    let held: Vec<_> = (0..MAX_COUNT).map(|_| sem.try_acquire().unwrap()).collect();
+    let (tx, rx) = channel();
+    for _ in 0..NUM_WORKERS {
+        let sem = sem.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            tx.send(sem.try_acquire().is_some()).unwrap();
+        });
+    }
+    for _ in 0..NUM_WORKERS {
+        assert!(!rx.recv().unwrap());
+    }
+    drop(held);
+    assert_eq!(sem.count(), MAX_COUNT);
+
+    // With the resources free again, the workers race for them: at most MAX_COUNT may win at
+    // any one time, and the count must never underflow.
+    let (tx, rx) = channel();
+    for _ in 0..NUM_WORKERS {
+        let sem = sem.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let guard = sem.try_acquire();
+            let won = guard.is_some();
+            if won {
+                assert!(sem.count() < MAX_COUNT);
+                sleep(Duration::from_millis(10));
+            }
+            // Release before reporting back, so the count is settled once everyone has reported
+            drop(guard);
+            tx.send(won).unwrap();
+        });
+    }
+    let won = (0..NUM_WORKERS).filter(|_| rx.recv().unwrap()).count();
+    assert!(won >= 1);
+    assert_eq!(sem.count(), MAX_COUNT);
+}
+
+#[test]
+fn test_acquire_timeout() {
+    let sem = CountingSemaphore::new(1);
+    let guard = sem.acquire_timeout(Duration::from_millis(10));
+    assert!(guard.is_ok());
+    // The only resource is held, so a second attempt has to time out
+    assert_eq!(sem.acquire_timeout(Duration::from_millis(10)).err(), Some(AcquireError::TimedOut));
+    drop(guard);
+    assert!(sem.acquire_timeout(Duration::from_millis(10)).is_ok());
+    assert_eq!(sem.count(), 1);
+}
+
+#[test]
+fn test_acquire_timeout_full_contention() {
+    static MAX_COUNT: usize = 2;
+    static NUM_WORKERS: usize = 8;
+    let sem = Arc::new(CountingSemaphore::new(MAX_COUNT));
+    // Leak every resource for the duration of the test
+    let held: Vec<_> = (0..MAX_COUNT).map(|_| sem.acquire().unwrap()).collect();
+    let timeout = Duration::from_millis(50);
+    let (tx, rx) = channel();
+    for _ in 0..NUM_WORKERS {
+        let sem = sem.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let start = Instant::now();
+            let timed_out = sem.acquire_timeout(timeout).is_err();
+            let elapsed = start.elapsed();
+            tx.send((timed_out, elapsed)).unwrap();
+        });
+    }
+    for _ in 0..NUM_WORKERS {
+        let (timed_out, elapsed) = rx.recv().unwrap();
+        assert!(timed_out);
+        // It must wait at least the requested time, but return promptly afterwards
+        assert!(elapsed >= timeout);
+        assert!(elapsed < timeout * 4);
+    }
+    drop(held);
+    assert_eq!(sem.count(), MAX_COUNT);
+}
+
+#[test]
+fn test_no_busy_waiting() {
+    static NUM_WORKERS: usize = 8;
+    let sem = Arc::new(CountingSemaphore::new(1));
+    // Hold the only resource for a long time while the workers pile up behind it
+    let guard = sem.acquire().unwrap();
+    let (tx, rx) = channel();
+    for _ in 0..NUM_WORKERS {
+        let sem = sem.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let guard = sem.acquire().unwrap();
+            sleep(Duration::from_millis(20));
+            drop(guard);
+            tx.send(()).unwrap();
+        });
+    }
+    sleep(Duration::from_secs(1));
+    // Parked waiters are not woken while the resource is held.  A spinlock with a 1ms backoff
+    // would have polled the count hundreds of times by now.
+    assert_eq!(sem.wakeups.load(Ordering::SeqCst), 0);
+    drop(guard);
+    for _ in 0..NUM_WORKERS {
+        rx.recv().unwrap();
+    }
+    // Each release wakes a single waiter, so (spurious wakeups aside) there is about one wakeup
+    // per worker rather than one per backoff interval.
+    assert!(sem.wakeups.load(Ordering::SeqCst) < NUM_WORKERS * 4);
+    assert_eq!(sem.count(), 1);
+}
+
+#[test]
+fn test_fair_fifo_order() {
+    static NUM_WORKERS: usize = 8;
+    let sem = Arc::new(CountingSemaphore::new_fair(1));
+    let order = Arc::new(Mutex::new(Vec::new()));
+    // Hold the only resource while the workers line up behind it
+    let guard = sem.acquire().unwrap();
+    let (tx, rx) = channel();
+    for i in 0..NUM_WORKERS {
+        let sem_ = sem.clone();
+        let order = order.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let guard = sem_.acquire().unwrap();
+            order.lock().unwrap().push(i);
+            drop(guard);
+            tx.send(()).unwrap();
+        });
+        // Don't start the next worker until this one is queued, so request order is known
+        while sem.lock.lock().unwrap().queue.len() < i + 1 {
+            sleep(Duration::from_millis(1));
+        }
+    }
+    // Every resource is promised to a queued task, so no one may jump the line
+    drop(guard);
+    for _ in 0..NUM_WORKERS {
+        rx.recv().unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), (0..NUM_WORKERS).collect::<Vec<_>>());
+    assert_eq!(sem.count(), 1);
+}
+
+#[test]
+fn test_fair_timeout_leaves_queue() {
+    let sem = Arc::new(CountingSemaphore::new_fair(1));
+    let guard = sem.acquire().unwrap();
+    // The first waiter gives up; it must not keep the second waiter from its turn
+    assert_eq!(sem.acquire_timeout(Duration::from_millis(10)).err(), Some(AcquireError::TimedOut));
+    assert_eq!(sem.lock.lock().unwrap().queue.len(), 0);
+    let sem_ = sem.clone();
+    let (tx, rx) = channel();
+    spawn(move || {
+        let acquired = sem_.acquire_timeout(Duration::from_secs(5)).is_ok();
+        tx.send(acquired).unwrap();
+    });
+    sleep(Duration::from_millis(20));
+    drop(guard);
+    assert!(rx.recv().unwrap());
+    assert!(sem.try_acquire().is_some());
+    assert_eq!(sem.count(), 1);
+}
+
+#[test]
+fn test_acquire_many_all_or_nothing() {
+    let sem = Arc::new(CountingSemaphore::new(4));
+    let first = sem.acquire().unwrap();
+    let second = sem.acquire().unwrap();
+    let sem_ = sem.clone();
+    let (tx, rx) = channel();
+    spawn(move || {
+        let guard = sem_.acquire_many(3).unwrap();
+        tx.send(sem_.count()).unwrap();
+        drop(guard);
+    });
+    sleep(Duration::from_millis(50));
+    // Only two resources are free, and the waiter must not have grabbed them in the meantime
+    assert_eq!(sem.count(), 2);
+    drop(first);
+    assert_eq!(rx.recv().unwrap(), 0);
+    drop(second);
+    // The guard gives back all three resources at once
+    while sem.count() != 4 {
+        sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn test_acquire_many_mixed_weights() {
+    static MAX_COUNT: usize = 4;
+    static NUM_WORKERS: usize = 24;
+    for &fair in [false, true].iter() {
+        let sem = Arc::new(if fair {
+            CountingSemaphore::new_fair(MAX_COUNT)
+        } else {
+            CountingSemaphore::new(MAX_COUNT)
+        });
+        let (tx, rx) = channel();
+        for i in 0..NUM_WORKERS {
+            let sem = sem.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                // Weights cycle through 1..MAX_COUNT, so some workers need the whole pool
+                let weight = i % MAX_COUNT + 1;
+                for _ in 0..5 {
+                    let guard = sem.acquire_many(weight).unwrap();
+                    assert!(sem.count() <= MAX_COUNT - weight);
+                    sleep(Duration::from_millis(1));
+                    drop(guard);
+                }
+                tx.send(()).unwrap();
+            });
+        }
+        // If partial acquisition could happen this would deadlock
+        for _ in 0..NUM_WORKERS {
+            rx.recv().unwrap();
+        }
+        assert_eq!(sem.count(), MAX_COUNT);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_acquire_many_over_max() {
+    let _ = CountingSemaphore::new(2).acquire_many(3);
+}
+
+#[test]
+fn test_writer_waits_for_readers() {
+    static NUM_READERS: usize = 3;
+    let sem = Arc::new(CountingSemaphore::new_fair(NUM_READERS));
+    let readers: Vec<_> = (0..NUM_READERS).map(|_| sem.acquire_shared().unwrap()).collect();
+    let (tx, rx) = channel();
+    let sem_ = sem.clone();
+    let writer = spawn(move || {
+        let guard = sem_.acquire_exclusive().unwrap();
+        tx.send(sem_.count()).unwrap();
+        drop(guard);
+    });
+    sleep(Duration::from_millis(20));
+    // New readers can't get in ahead of the waiting writer
+    assert!(sem.try_acquire().is_none());
+    for reader in readers {
+        assert!(rx.try_recv().is_err());
+        drop(reader);
+        sleep(Duration::from_millis(10));
+    }
+    // Only once every reader is gone does the writer get in, holding everything
+    assert_eq!(rx.recv().unwrap(), 0);
+    writer.join().unwrap();
+    assert_eq!(sem.count(), NUM_READERS);
+    let guard = sem.acquire_exclusive().unwrap();
+    assert!(sem.try_acquire().is_none());
+    drop(guard);
+    assert!(sem.acquire_shared().is_ok());
+}
+
+#[test]
+fn test_acquire_owned() {
+    // A guard that outlives the function that acquired it
+    struct Job {
+        _permit: OwnedSemaphoreGuard,
+        id: usize,
+    }
+
+    static MAX_COUNT: usize = 2;
+    static NUM_WORKERS: usize = 6;
+    let sem = Arc::new(CountingSemaphore::new(MAX_COUNT));
+    let (tx, rx) = channel();
+    for i in 0..NUM_WORKERS {
+        // Acquire on this thread, then hand the guard over to the worker
+        let job = Job { _permit: sem.acquire_owned().unwrap(), id: i };
+        assert!(sem.count() < MAX_COUNT);
+        let tx = tx.clone();
+        spawn(move || {
+            sleep(Duration::from_millis(10));
+            let id = job.id;
+            // Dropping the job releases the resource from this thread
+            drop(job);
+            tx.send(id).unwrap();
+        });
+    }
+    let mut done: Vec<usize> = (0..NUM_WORKERS).map(|_| rx.recv().unwrap()).collect();
+    done.sort();
+    assert_eq!(done, (0..NUM_WORKERS).collect::<Vec<_>>());
+    assert_eq!(sem.count(), MAX_COUNT);
+    // The guard keeps the semaphore alive even once every other handle is gone
+    let guard = sem.acquire_owned().unwrap();
+    drop(sem);
+    assert_eq!(guard.sem.count(), MAX_COUNT - 1);
+}
+
+#[test]
+fn test_forget_and_add_permits() {
+    let sem = Arc::new(CountingSemaphore::new(3));
+    // A forgotten resource is gone for good, rather than held
+    sem.acquire().unwrap().forget();
+    sem.acquire_owned().unwrap().forget();
+    assert_eq!((sem.count(), sem.max()), (1, 1));
+    let guard = sem.acquire().unwrap();
+    assert!(sem.try_acquire().is_none());
+    // Giving one back by hand lets a waiter through while the other is still held
+    let sem_ = sem.clone();
+    let waiter = spawn(move || sem_.acquire().map(|_| ()).is_ok());
+    sleep(Duration::from_millis(20));
+    sem.add_permits(1);
+    assert!(waiter.join().unwrap());
+    drop(guard);
+    assert_eq!((sem.count(), sem.max()), (2, 2));
+}
+
+#[test]
+fn test_forget_while_writer_waits() {
+    // For both kinds of semaphore, a writer waiting for every resource has to go ahead once the
+    // last reader leaves, even though one of them forgot theirs rather than giving it back
+    for &fair in &[false, true] {
+        let sem = Arc::new(if fair { CountingSemaphore::new_fair(2) }
+                           else { CountingSemaphore::new(2) });
+        let first = sem.acquire_shared().unwrap();
+        let second = sem.acquire_shared().unwrap();
+        let (tx, rx) = channel();
+        let sem_ = sem.clone();
+        let writer = spawn(move || {
+            let guard = sem_.acquire_exclusive().unwrap();
+            tx.send(guard.permits).unwrap();
+        });
+        sleep(Duration::from_millis(20));
+        drop(first);
+        sleep(Duration::from_millis(20));
+        assert!(rx.try_recv().is_err());
+        // Nothing is released now, so only forgetting can wake the writer
+        second.forget();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(1), "fair: {}", fair);
+        writer.join().unwrap();
+        assert_eq!((sem.count(), sem.max()), (1, 1));
+    }
+}
+
+#[test]
+fn test_add_permits_under_load() {
+    static NUM_WORKERS: usize = 12;
+    let sem = Arc::new(CountingSemaphore::new(2));
+    let held = Arc::new(AtomicUsize::new(0));
+    let most_held = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..NUM_WORKERS).map(|_| {
+        let sem = sem.clone();
+        let held = held.clone();
+        let most_held = most_held.clone();
+        spawn(move || {
+            let guard = sem.acquire().unwrap();
+            let now = held.fetch_add(1, Ordering::SeqCst) + 1;
+            most_held.fetch_max(now, Ordering::SeqCst);
+            assert!(sem.count() <= sem.max());
+            sleep(Duration::from_millis(20));
+            held.fetch_sub(1, Ordering::SeqCst);
+            drop(guard);
+        })
+    }).collect();
+    // Grow the semaphore while the workers are queueing up for it
+    sleep(Duration::from_millis(10));
+    sem.add_permits(2);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(most_held.load(Ordering::SeqCst) > 2);
+    assert!(most_held.load(Ordering::SeqCst) <= 4);
+    assert_eq!((sem.count(), sem.max()), (4, 4));
+}
+
+#[test]
+fn test_close_wakes_waiters() {
+    static NUM_WORKERS: usize = 6;
+    for &fair in [false, true].iter() {
+        let sem = Arc::new(if fair { CountingSemaphore::new_fair(2) } else { CountingSemaphore::new(2) });
+        let held = sem.acquire_many(2).unwrap();
+        let (tx, rx) = channel();
+        for i in 0..NUM_WORKERS {
+            let sem = sem.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                // A mix of the different ways to wait
+                let result = match i % 3 {
+                    0 => sem.acquire().map(|_| ()),
+                    1 => sem.acquire_many(2).map(|_| ()),
+                    _ => sem.acquire_timeout(Duration::from_secs(10)).map(|_| ()),
+                };
+                tx.send(result).unwrap();
+            });
+        }
+        sleep(Duration::from_millis(50));
+        sem.close();
+        for _ in 0..NUM_WORKERS {
+            assert_eq!(rx.recv().unwrap(), Err(AcquireError::Closed));
+        }
+        // A guard held across close() is still released normally
+        drop(held);
+        assert_eq!(sem.count(), 2);
+    }
+}
+
+#[test]
+fn test_closed_never_hands_out_guards() {
+    let sem = CountingSemaphore::new(3);
+    let guard = sem.acquire().unwrap();
+    sem.close();
+    // Even though resources are free, a closed semaphore refuses every kind of request
+    assert!(sem.acquire().is_err());
+    assert!(sem.acquire_many(2).is_err());
+    assert_eq!(sem.acquire_timeout(Duration::from_millis(10)).err(), Some(AcquireError::Closed));
+    assert!(sem.try_acquire().is_none());
+    // Releasing a resource doesn't reopen it either
+    drop(guard);
+    assert!(sem.acquire().is_err());
+    assert_eq!(sem.count(), 3);
+}
+
+#[test]
+fn test_backoff_delays() {
+    let ms = Duration::from_millis;
+    let linear = Linear(ms(2));
+    assert_eq!((1..5).map(|i| linear.delay(i)).collect::<Vec<_>>(), vec![ms(2), ms(4), ms(6), ms(8)]);
+    let exponential = Exponential { base: ms(1), max: ms(5) };
+    assert_eq!((1..5).map(|i| exponential.delay(i)).collect::<Vec<_>>(), vec![ms(1), ms(2), ms(4), ms(5)]);
+    // Even absurd attempt counts stay at the cap instead of overflowing
+    assert_eq!(exponential.delay(1000), ms(5));
+    let jitter = ExponentialWithJitter { base: ms(1), max: ms(5) };
+    for i in 1..50 {
+        let delay = jitter.delay(i);
+        assert!(delay >= Duration::ZERO && delay <= exponential.delay(i));
+    }
+    assert_eq!(YieldOnly.delay(7), Duration::ZERO);
+}
+
+// How many tasks contended_wakeups() has waiting
+#[cfg(test)]
+static NUM_WAITERS: usize = 4;
+
+// Hold the only resource of `sem` for 200ms with NUM_WAITERS tasks waiting for it, and return
+// how many times the waiters woke up.
+#[cfg(test)]
+fn contended_wakeups(sem: CountingSemaphore) -> usize {
+    let sem = Arc::new(sem);
+    let guard = sem.acquire().unwrap();
+    let (tx, rx) = channel();
+    for _ in 0..NUM_WAITERS {
+        let sem = sem.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            drop(sem.acquire().unwrap());
+            tx.send(()).unwrap();
+        });
+    }
+    sleep(Duration::from_millis(200));
+    drop(guard);
+    for _ in 0..NUM_WAITERS {
+        rx.recv().unwrap();
+    }
+    assert_eq!(sem.count(), 1);
+    sem.wakeups.load(Ordering::SeqCst)
+}
+
+#[test]
+fn test_backoff_wake_counts() {
+    let ms = Duration::from_millis;
+    let parked = contended_wakeups(CountingSemaphore::new(1));
+    let linear = contended_wakeups(CountingSemaphore::with_backoff(1, Linear(ms(1))));
+    let exponential = contended_wakeups(CountingSemaphore::with_backoff(1, Exponential { base: ms(1), max: ms(1000) }));
+    let jitter = contended_wakeups(CountingSemaphore::with_backoff(1, ExponentialWithJitter { base: ms(1), max: ms(1000) }));
+    let yielding = contended_wakeups(CountingSemaphore::with_backoff(1, YieldOnly));
+    // Every waiter wakes at least once, since the resource is taken whenever one arrives
+    for &wakeups in &[parked, linear, exponential, jitter, yielding] {
+        assert!(wakeups >= NUM_WAITERS, "{} wakeups", wakeups);
+    }
+    // Parked waiters only wake when the resource is released.  Sleeps are never shorter than
+    // asked for, so over 200ms a linear backoff of 1ms, 2ms and so on retries at most about 20
+    // times per waiter, an exponential one about 8 times, and jitter, which only ever shortens
+    // the exponential delays, more often than that.  Yielding retries as often as the scheduler
+    // lets it.
+    assert!(parked <= 3 * NUM_WAITERS, "parked {}", parked);
+    assert!(linear <= 25 * NUM_WAITERS, "linear {}", linear);
+    assert!(exponential <= 12 * NUM_WAITERS, "exponential {}", exponential);
+    assert!(parked < linear && exponential < linear && linear < yielding,
+            "parked {}, linear {}, exponential {}, yield {}", parked, linear, exponential, yielding);
+}
+
+#[test]
+fn test_backoff_timeout_and_close() {
+    let sem = CountingSemaphore::with_backoff(1, Linear(Duration::from_millis(40)));
+    let guard = sem.acquire().unwrap();
+    let start = Instant::now();
+    assert_eq!(sem.acquire_timeout(Duration::from_millis(50)).err(), Some(AcquireError::TimedOut));
+    // The second 80ms sleep would overshoot, so it must be cut short
+    assert!(start.elapsed() < Duration::from_millis(100));
+    sem.close();
+    assert_eq!(sem.acquire().err(), Some(AcquireError::Closed));
+    drop(guard);
+}
+
+#[test]
+fn test_backoff_mock_clock() {
+    let ms = Duration::from_millis;
+    let clock = Arc::new(MockClock::new());
+    let sem = CountingSemaphore::with_backoff(1, Exponential { base: ms(1), max: ms(8) })
+        .instrumented()
+        .with_clock(clock.clone());
+    let start = clock.now();
+    let guard = sem.acquire().unwrap();
+    // Doubling sleeps up to the cap, the last one cut short at the deadline, and not a moment of
+    // real time spent on any of them
+    assert_eq!(sem.acquire_timeout(ms(20)).err(), Some(AcquireError::TimedOut));
+    assert_eq!(clock.sleeps(), vec![ms(1), ms(2), ms(4), ms(8), ms(5)]);
+    assert_eq!(clock.now() - start, ms(20));
+    drop(guard);
+    clock.advance(ms(100));
+    assert!(sem.acquire_timeout(ms(20)).is_ok());
+    assert_eq!(clock.sleeps().len(), 5);
+    let stats = sem.stats().unwrap();
+    assert_eq!((stats.acquisitions, stats.failed_attempts), (2, 6));
+    assert_eq!(stats.max_wait, Duration::ZERO);
+}
+
+#[test]
+fn test_linear_backoff_mock_clock() {
+    let ms = Duration::from_millis;
+    let clock = Arc::new(MockClock::new());
+    let sem = CountingSemaphore::with_backoff(1, Linear(ms(10))).with_clock(clock.clone());
+    let _guard = sem.acquire().unwrap();
+    assert_eq!(sem.acquire_timeout(ms(45)).err(), Some(AcquireError::TimedOut));
+    assert_eq!(clock.sleeps(), vec![ms(10), ms(20), ms(15)]);
+}
+
+#[test]
+#[should_panic]
+fn test_mock_clock_needs_backoff() {
+    CountingSemaphore::new(1).with_clock(MockClock::new());
+}
+
+#[test]
+fn test_stats() {
+    assert!(CountingSemaphore::new(1).stats().is_none());
+    for sem in vec![CountingSemaphore::new(1).instrumented(),
+                    CountingSemaphore::new_fair(1).instrumented(),
+                    CountingSemaphore::with_backoff(1, Linear(Duration::from_millis(1))).instrumented()]
+                   .into_iter() {
+        let sem = Arc::new(sem);
+        let guard = sem.acquire().unwrap();
+        // Fails as long as the resource is held
+        assert!(sem.try_acquire().is_none());
+        let sem_ = sem.clone();
+        let (tx, rx) = channel();
+        spawn(move || {
+            drop(sem_.acquire().unwrap());
+            tx.send(()).unwrap();
+        });
+        sleep(Duration::from_millis(50));
+        drop(guard);
+        rx.recv().unwrap();
+        let stats = sem.stats().unwrap();
+        assert_eq!(stats.acquisitions, 2);
+        // The try_acquire, plus at least one failure of the waiting worker
+        assert!(stats.failed_attempts >= 2);
+        // The first acquire didn't wait at all, the second one about 50ms
+        assert!(stats.min_wait < Duration::from_millis(10));
+        assert!(stats.max_wait >= Duration::from_millis(40));
+        assert!(stats.mean_wait > stats.min_wait && stats.mean_wait < stats.max_wait);
+    }
+}
+
+#[test]
+fn test_phased() {
+    assert_eq!(phased(Duration::from_millis(5), &Mutex::new(io::sink())), vec![5, 5, 5]);
+}
+
+#[test]
+fn test_barrier_one_leader_per_phase() {
+    static NUM_WORKERS: usize = 8;
+    static NUM_PHASES: usize = 50;
+    let barrier = Arc::new(CyclicBarrier::new(NUM_WORKERS));
+    let handles: Vec<_> = (0..NUM_WORKERS).map(|_| {
+        let barrier = barrier.clone();
+        spawn(move || (0..NUM_PHASES).filter(|_| barrier.wait()).count())
+    }).collect();
+    let leaders: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(leaders, NUM_PHASES);
+    // A single party never waits
+    let alone = CyclicBarrier::new(1);
+    assert!(alone.wait() && alone.wait());
+}
+
+#[test]
+fn test_rate_limited() {
+    // 12 requests at 50 per second: the first goes through right away, and the rest one every 20ms
+    let times = rate_limited(50, &Mutex::new(io::sink()));
+    assert_eq!(times.len(), 12);
+    assert!(times[0] < Duration::from_millis(15));
+    assert!(times[11] >= Duration::from_millis(11 * 20));
+    for pair in times.windows(2) {
+        assert!(pair[1] - pair[0] >= Duration::from_millis(15));
+    }
+}
+
+#[test]
+fn test_rate_limiter_burst() {
+    let limiter = RateLimiter::new(3, Duration::from_millis(30));
+    // A full bucket lets a burst through at once, then runs dry
+    assert!(limiter.try_acquire() && limiter.try_acquire() && limiter.try_acquire());
+    assert!(!limiter.try_acquire());
+    assert_eq!(limiter.count(), 0);
+    // Waiting for a refill
+    let start = Instant::now();
+    limiter.acquire();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    // Once left alone, the bucket fills up to its burst size but no further
+    sleep(Duration::from_millis(200));
+    assert_eq!(limiter.count(), 3);
+}
+
+#[test]
+#[should_panic(expected = "at least one token through a second")]
+fn test_rate_limiter_per_second_zero() {
+    RateLimiter::per_second(0);
+}
+
+// Tells a copy of the test binary started by test_named_semaphore_across_processes that it is a
+// child, and which semaphore and directory to use.
+#[cfg(test)]
+static NAMED_CHILD_VAR: &str = "METERED_CONCURRENCY_NAMED_CHILD";
+#[cfg(test)]
+static NAMED_MAX: u32 = 2;
+
+#[test]
+fn test_named_semaphore_across_processes() {
+    use std::process::Command;
+    static NUM_CHILDREN: usize = 5;
+    let name = format!("metered_concurrency_test_{}", std::process::id());
+    let dir = std::env::temp_dir().join(&name);
+    std::fs::create_dir_all(&dir).unwrap();
+    let sem = NamedSemaphore::open(&name, NAMED_MAX).unwrap();
+    // Each child runs just named_semaphore_child, which stands in for the child's main()
+    let children: Vec<_> = (0..NUM_CHILDREN).map(|_| {
+        Command::new(std::env::current_exe().unwrap())
+            .args(["named_semaphore_child", "--test-threads", "1"])
+            .env(NAMED_CHILD_VAR, format!("{}|{}", name, dir.display()))
+            .spawn()
+            .unwrap()
+    }).collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+    // Every child gave back what it took
+    let guards: Vec<_> = (0..NAMED_MAX).map(|_| sem.try_acquire().unwrap().unwrap()).collect();
+    assert!(sem.try_acquire().unwrap().is_none());
+    drop(guards);
+    NamedSemaphore::unlink(&name).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// While holding a resource, each child leaves a file named after itself in the directory, so
+// counting the files there counts (at most) the processes holding a resource.
+#[test]
+fn named_semaphore_child() {
+    let var = match std::env::var(NAMED_CHILD_VAR) {
+        Ok(var) => var,
+        Err(_) => return, // Not a child
+    };
+    let mut parts = var.splitn(2, '|');
+    let name = parts.next().unwrap();
+    let dir = std::path::PathBuf::from(parts.next().unwrap());
+    let sem = NamedSemaphore::open(name, NAMED_MAX).unwrap();
+    let mine = dir.join(std::process::id().to_string());
+    for _ in 0..3 {
+        let guard = sem.acquire().unwrap();
+        std::fs::File::create(&mine).unwrap();
+        let holders = std::fs::read_dir(&dir).unwrap().count();
+        assert!(holders <= NAMED_MAX as usize, "{} processes hold a resource", holders);
+        sleep(Duration::from_millis(10));
+        std::fs::remove_file(&mine).unwrap();
+        drop(guard);
+    }
+}
+
+#[test]
+fn test_metered_concurrency_async() {
+    metered_async(Duration::from_secs(1) / 20, &Mutex::new(io::sink()));
+}
+
+#[test]
+fn test_async_acquire_wakes_waiter() {
+    // Records whether it was woken
+    struct Flag(AtomicBool);
+    impl Wake for Flag {
+        fn wake(&self) { self.0.store(true, Ordering::SeqCst); }
+    }
+
+    let sem = AsyncCountingSemaphore::new(1);
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = flag.clone() as Arc<dyn Wake>;
+    let guard = match sem.acquire().poll(&waker) {
+        Poll::Ready(guard) => guard,
+        Poll::Pending => panic!("a resource was free"),
+    };
+    assert_eq!(sem.count(), 0);
+    let mut waiting = sem.acquire();
+    assert!(match waiting.poll(&waker) { Poll::Pending => true, Poll::Ready(_) => false });
+    assert!(!flag.0.load(Ordering::SeqCst));
+    // Releasing the resource must wake the pending future, which can then complete
+    drop(guard);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(match waiting.poll(&waker) { Poll::Ready(_) => true, Poll::Pending => false });
+    assert_eq!(sem.count(), 1);
+}
+
+// Under loom, check every interleaving of a few tasks contending for a semaphore: the remaining
+// count must stay between zero and max (a usize underflow wraps in release builds, so it also shows
+// up as a count above max), at most max tasks may hold a resource at once, and every guard must
+// give its resource back.
+#[cfg(loom)]
+fn loom_contend(sem: CountingSemaphore, permits: &[usize]) {
+    use loom::sync::Arc;
+    use loom::sync::atomic::AtomicUsize;
+
+    let max = sem.max();
+    let sem = Arc::new(sem);
+    let held = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = permits.iter().map(|&n| {
+        let sem = sem.clone();
+        let held = held.clone();
+        loom::thread::spawn(move || {
+            let guard = sem.acquire_many(n).unwrap();
+            assert!(held.fetch_add(n, Ordering::SeqCst) + n <= max);
+            assert!(sem.count() <= max - n);
+            held.fetch_sub(n, Ordering::SeqCst);
+            drop(guard);
+            assert!(sem.count() <= max);
+        })
+    }).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(sem.count(), max);
+    assert_eq!(held.load(Ordering::SeqCst), 0);
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_single_permit() {
+    loom::model(|| loom_contend(CountingSemaphore::new(1), &[1, 1]));
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_single_permit_fair() {
+    loom::model(|| loom_contend(CountingSemaphore::new_fair(1), &[1, 1]));
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_mixed_weights() {
+    loom::model(|| loom_contend(CountingSemaphore::new(2), &[2, 1]));
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_close_wakes_waiter() {
+    use loom::sync::Arc;
+
+    loom::model(|| {
+        let sem = Arc::new(CountingSemaphore::new(1));
+        let guard = sem.acquire().unwrap();
+        let sem_ = sem.clone();
+        let waiter = loom::thread::spawn(move || sem_.acquire().is_err());
+        sem.close();
+        drop(guard);
+        assert!(waiter.join().unwrap());
+        assert_eq!(sem.count(), 1);
+    });
+}
+
+#[cfg(not(test))]
+fn main() {
+    // By default, hold each resource for 2 seconds per worker
+    let config = parse_args(Args::from_env()).unwrap_or_else(|message| {
+        utils::args::exit_with_usage(&message, "metered_concurrency [--permits N] [--workers N] \
+                                                [--hold-ms MS] [--backoff-ms MS]")
+    });
+    run(&config, io::stdout()).unwrap();
+}
@@ -12,6 +12,7 @@ extern crate test;
 use std::sync::Future;
 use prime_decomposition::factor;
 
+#[path = "../math/prime_decomposition.rs"]
 mod prime_decomposition;
 
 // Returns the minimal prime factor of a number
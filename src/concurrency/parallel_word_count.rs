@@ -0,0 +1,190 @@
+//! Counting the words of a text in parallel, map-reduce style
+//!
+//! The text is split into as many pieces as there are workers, each ending between two words, and
+//! each piece is counted on a thread of its own (the map step), into a map of its own from each
+//! word to how often it appears.  The maps are then merged (the reduce step).  The workers are
+//! scoped threads, so they can borrow their pieces of the text rather than each being handed a
+//! copy.  Words are runs of letters, digits and apostrophes, counted without regard to case.
+//!
+//! ```text
+//!     parallel_word_count [--workers N] [--top N] [FILE]...
+//! ```
+//!
+//! Without any files, it counts the words of the Jabberwocky, from tests/fixtures/input.txt.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::parallel_word_count::{count, parallel_count};
+//!
+//! let text = "The cat sat on the mat.  The mat didn't mind.";
+//! let counts = count(text);
+//! assert_eq!((counts["the"], counts["mat"], counts["didn't"]), (3, 2, 1));
+//! assert_eq!(parallel_count(text, 4), counts);
+//! ```
+
+use std::collections::HashMap;
+#[cfg(not(test))]
+use std::fs;
+use std::thread;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::args::Args;
+
+fn in_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '’'
+}
+
+// The words of `text`, in lower case
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c| !in_word(c))
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '’'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+// Count the words of `text` on a single thread
+pub fn count(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in words(text) {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+// Split `text` into at most `n` pieces of about the same length, none of which ends in the middle
+// of a word
+fn split(text: &str, n: usize) -> Vec<&str> {
+    let mut pieces = Vec::with_capacity(n);
+    let mut rest = text;
+    for left in (1..=n).rev() {
+        if rest.is_empty() {
+            break;
+        }
+        let mut end = if left == 1 { rest.len() } else { rest.len() / left };
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        // On to the end of the word it's in, if it's in one
+        while let Some(c) = rest[end..].chars().next().filter(|&c| in_word(c)) {
+            end += c.len_utf8();
+        }
+        pieces.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    pieces
+}
+
+// Add the counts in `from` to those in `into`
+fn merge(into: &mut HashMap<String, usize>, from: HashMap<String, usize>) {
+    for (word, count) in from {
+        *into.entry(word).or_insert(0) += count;
+    }
+}
+
+// Count the words of `text` with `workers` threads
+pub fn parallel_count(text: &str, workers: usize) -> HashMap<String, usize> {
+    assert!(workers > 0, "counting takes at least one worker");
+    let partial: Vec<HashMap<String, usize>> = thread::scope(|scope| {
+        let counting: Vec<_> = split(text, workers).into_iter().map(|piece| {
+            scope.spawn(move || count(piece))
+        }).collect();
+        counting.into_iter().map(|worker| worker.join().unwrap()).collect()
+    });
+    let mut counts = HashMap::new();
+    for part in partial {
+        merge(&mut counts, part);
+    }
+    counts
+}
+
+// The `n` most common words, most common first, and then in alphabetical order
+fn top(counts: &HashMap<String, usize>, n: usize) -> Vec<(&str, usize)> {
+    let mut top: Vec<(&str, usize)> = counts.iter().map(|(word, &count)| (&word[..], count))
+                                            .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    top.truncate(n);
+    top
+}
+
+// The number of workers, how many of the most common words to show, and the files to count
+fn parse_args(mut args: Args) -> Result<(usize, usize, Vec<String>), String> {
+    let workers = match args.value("--workers")? {
+        Some(0) => return Err("--workers must be positive".to_string()),
+        Some(workers) => workers,
+        None => thread::available_parallelism().map_or(4, |n| n.get()),
+    };
+    let top = args.value("--top")?.unwrap_or(10);
+    Ok((workers, top, args.free()?))
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (workers, n, files) = parse_args(Args::from_env()).unwrap_or_else(|message| {
+        utils::args::exit_with_usage(&message, "parallel_word_count [--workers N] [--top N] \
+                                                [FILE]...")
+    });
+    let text = if files.is_empty() {
+        utils::fixture::read("input.txt")
+    } else {
+        files.iter().map(|file| fs::read_to_string(file).unwrap_or_else(|error| {
+            eprintln!("can't read {}: {}", file, error);
+            std::process::exit(1)
+        })).collect::<Vec<_>>().join("\n")
+    };
+    let counts = parallel_count(&text, workers);
+    println!("{} words, {} of them different (workers: {})",
+             counts.values().sum::<usize>(), counts.len(), workers);
+    for (word, count) in top(&counts, n) {
+        println!("{:7} {}", count, word);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{count, parallel_count, parse_args, split, top};
+    use utils::args::Args;
+    use utils::fixture;
+
+    #[test]
+    fn words() {
+        let counts = count("The cat's hat; THE 'hat', the cat.");
+        assert_eq!(top(&counts, 5), vec![("the", 3), ("hat", 2), ("cat", 1), ("cat's", 1)]);
+        assert!(count(" \n.,").is_empty());
+    }
+
+    #[test]
+    fn pieces() {
+        let text = "one two three fóur five";
+        for n in 1..30 {
+            let pieces = split(text, n);
+            assert!(pieces.len() <= n);
+            assert_eq!(pieces.concat(), text);
+            // No word is cut in two
+            let words: Vec<&str> = pieces.iter().flat_map(|piece| piece.split_whitespace())
+                                         .collect();
+            assert_eq!(words, vec!["one", "two", "three", "fóur", "five"]);
+        }
+        assert!(split("", 3).is_empty());
+    }
+
+    #[test]
+    fn same_as_one_thread() {
+        let text = fixture::read("input.txt").repeat(50) + &fixture::read("unixdict.txt");
+        let expected = count(&text);
+        assert_eq!(expected["jabberwock"], 150);
+        for workers in [1, 2, 3, 7, 16, 100] {
+            assert_eq!(parallel_count(&text, workers), expected, "with {} workers", workers);
+        }
+    }
+
+    #[test]
+    fn arguments() {
+        let args = |args: &[&str]| parse_args(Args::new(args.iter().map(|arg| arg.to_string())));
+        assert_eq!(args(&["--workers", "3", "a.txt", "--top=5", "b.txt"]),
+                   Ok((3, 5, vec!["a.txt".to_string(), "b.txt".to_string()])));
+        assert_eq!(args(&["--workers", "0"]), Err("--workers must be positive".to_string()));
+    }
+}
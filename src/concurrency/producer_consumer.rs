@@ -0,0 +1,232 @@
+//! The producer-consumer problem, with a bounded blocking queue built by hand
+//!
+//! Producers put items in a queue of fixed capacity and consumers take them out.  A channel would
+//! do the job, but here the queue is a VecDeque behind a Mutex, with one Condvar for consumers to
+//! wait on while it's empty and another for producers to wait on while it's full.  Any number of
+//! either can share it.  When the producers are done, the queue is closed: the consumers take out
+//! what's left, and then pop() returns None to tell them to stop.
+//!
+//! ```
+//! # extern crate tasks;
+//! use std::time::Duration;
+//! use tasks::producer_consumer::{produce_and_consume, BoundedQueue};
+//!
+//! let queue = BoundedQueue::new(1);
+//! assert_eq!(queue.try_push('a'), Ok(()));
+//! assert_eq!(queue.try_push('b'), Err('b'));
+//! queue.close();
+//! assert_eq!((queue.pop(), queue.pop()), (Some('a'), None));
+//!
+//! // Two producers with three items each, and one consumer, who takes all six
+//! let taken = produce_and_consume(2, 1, 3, 2, Duration::from_millis(0));
+//! let mut items = taken[0].clone();
+//! items.sort();
+//! assert_eq!(items, [0, 1, 2, 3, 4, 5]);
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub struct BoundedQueue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar, // Notified when an item is pushed, or the queue is closed
+    not_full: Condvar,  // Notified when an item is popped, or the queue is closed
+    capacity: usize,
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize) -> BoundedQueue<T> {
+        assert!(capacity > 0, "a queue needs room for at least one item");
+        BoundedQueue {
+            state: Mutex::new(State { items: VecDeque::with_capacity(capacity), closed: false }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    // Add `item` at the back, first waiting for room if the queue is full.  Gives the item back
+    // if the queue is closed.
+    pub fn push(&self, item: T) -> Result<(), T> {
+        let mut state = self.state.lock().unwrap();
+        while state.items.len() == self.capacity && !state.closed {
+            state = self.not_full.wait(state).unwrap();
+        }
+        if state.closed {
+            return Err(item);
+        }
+        state.items.push_back(item);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    // Add `item` at the back if there's room, or give it back if the queue is full or closed
+    pub fn try_push(&self, item: T) -> Result<(), T> {
+        let mut state = self.state.lock().unwrap();
+        if state.items.len() == self.capacity || state.closed {
+            return Err(item);
+        }
+        state.items.push_back(item);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    // Take out the item at the front, waiting for one if the queue is empty.  Returns None once
+    // the queue is closed and empty.
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                self.not_full.notify_one();
+                return Some(item);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.not_empty.wait(state).unwrap();
+        }
+    }
+
+    // Refuse any more items, and wake everyone waiting: producers to have their items back, and
+    // consumers to finish what's left
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Have `producers` producers each make `items` items, numbered from 0 across all of them, and
+// `consumers` consumers take them out of a queue of room for `capacity`, resting for `rest`
+// between items.  Returns the items each consumer took, in the order they took them.
+pub fn produce_and_consume(producers: usize, consumers: usize, items: usize, capacity: usize,
+                           rest: Duration) -> Vec<Vec<usize>> {
+    let queue = BoundedQueue::new(capacity);
+    thread::scope(|scope| {
+        let queue = &queue;
+        let taken: Vec<_> = (0..consumers).map(|consumer| scope.spawn(move || {
+            let mut taken = Vec::new();
+            while let Some(item) = queue.pop() {
+                println!("Consumer {} took item {}", consumer, item);
+                taken.push(item);
+                thread::sleep(rest);
+            }
+            println!("Consumer {} is done", consumer);
+            taken
+        })).collect();
+        let made: Vec<_> = (0..producers).map(|producer| scope.spawn(move || {
+            for item in producer * items..(producer + 1) * items {
+                queue.push(item).expect("the queue was closed before the producers were done");
+                println!("Producer {} made item {}", producer, item);
+                thread::sleep(rest);
+            }
+        })).collect();
+        for producer in made {
+            producer.join().unwrap();
+        }
+        queue.close();
+        taken.into_iter().map(|consumer| consumer.join().unwrap()).collect()
+    })
+}
+
+#[cfg(not(test))]
+fn main() {
+    let taken = produce_and_consume(3, 2, 5, 4, Duration::from_millis(20));
+    for (consumer, items) in taken.iter().enumerate() {
+        println!("Consumer {} took {} items: {:?}", consumer, items.len(), items);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{produce_and_consume, BoundedQueue};
+    use std::iter;
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn in_order() {
+        let queue = BoundedQueue::new(3);
+        assert!(queue.is_empty());
+        for i in 0..3 {
+            assert_eq!(queue.try_push(i), Ok(()));
+        }
+        assert_eq!(queue.try_push(3), Err(3));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(), Some(0));
+        assert_eq!(queue.try_push(3), Ok(()));
+        queue.close();
+        assert_eq!(queue.push(4), Err(4));
+        let rest: Vec<i32> = iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(rest, vec![1, 2, 3]);
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn blocks_while_full() {
+        let queue = BoundedQueue::new(1);
+        queue.push("first").unwrap();
+        let (sender, pushed) = channel();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                queue.push("second").unwrap();
+                sender.send(()).unwrap();
+            });
+            assert!(pushed.recv_timeout(Duration::from_millis(50)).is_err());
+            assert_eq!(queue.pop(), Some("first"));
+            pushed.recv_timeout(Duration::from_secs(10)).unwrap();
+        });
+        assert_eq!(queue.pop(), Some("second"));
+    }
+
+    #[test]
+    fn close_wakes_everyone() {
+        let empty = BoundedQueue::<u8>::new(1);
+        let full = BoundedQueue::new(1);
+        full.push(1).unwrap();
+        thread::scope(|scope| {
+            let popping = scope.spawn(|| empty.pop());
+            let pushing = scope.spawn(|| full.push(2));
+            thread::sleep(Duration::from_millis(20));
+            empty.close();
+            full.close();
+            assert_eq!(popping.join().unwrap(), None);
+            assert_eq!(pushing.join().unwrap(), Err(2));
+        });
+        // What was in the queue when it was closed can still be taken out
+        assert_eq!(full.pop(), Some(1));
+    }
+
+    #[test]
+    fn every_item_once() {
+        let taken = produce_and_consume(4, 3, 2500, 8, Duration::from_millis(0));
+        assert_eq!(taken.len(), 3);
+        let mut items: Vec<usize> = taken.iter().flatten().cloned().collect();
+        items.sort();
+        assert_eq!(items, (0..10000).collect::<Vec<_>>());
+        // Each producer's items were made, and so taken out, in order
+        for consumer in &taken {
+            for producer in 0..4 {
+                let theirs: Vec<_> = consumer.iter().filter(|&&item| item / 2500 == producer)
+                                             .collect();
+                assert!(theirs.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Implements http://rosettacode.org/wiki/Rendezvous
+//!
+//! Two writers print their verses a line at a time on a shared printer.  Printing is a rendezvous:
+//! the writer waits until the printer has printed the line, or failed to.  Each printer is a
+//! thread taking jobs from a channel, with a channel of its own in each job to answer on.  The main
+//! printer has ink for five lines, and once it runs out it hands its jobs over to the reserve
+//! printer, which has ink for five more.  When that runs out too, the writer who asked is told so
+//! with an OutOfInk error, and stops.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::rendezvous::{rendezvous, OutOfInk};
+//!
+//! // Each printer has ink for a line, so the reserve prints the second and the third can't be
+//! let (results, printed) = rendezvous(&[&["One", "Two", "Three"]], 1);
+//! assert_eq!(results, [Err(OutOfInk)]);
+//! assert_eq!(printed, [("Main", "One".to_string()), ("Reserve", "Two".to_string())]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+
+const HUMPTY_DUMPTY: [&str; 4] = ["Humpty Dumpty sat on a wall.",
+                                  "Humpty Dumpty had a great fall.",
+                                  "All the king's horses and all the king's men",
+                                  "Couldn't put Humpty together again."];
+
+const MOTHER_GOOSE: [&str; 7] = ["Old Mother Goose,",
+                                 "When she wanted to wander,",
+                                 "Would ride through the air,",
+                                 "On a very fine gander.",
+                                 "Jack's mother came in,",
+                                 "And caught the goose soon,",
+                                 "And mounting its back,"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutOfInk;
+
+impl fmt::Display for OutOfInk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("out of ink")
+    }
+}
+
+impl Error for OutOfInk {}
+
+// A line that was printed, and the printer that printed it
+type Printed = (&'static str, String);
+
+struct Job {
+    line: String,
+    done: Sender<Result<(), OutOfInk>>, // Told once the line is printed, or can't be
+}
+
+#[derive(Clone)]
+pub struct Printer {
+    jobs: Sender<Job>,
+}
+
+impl Printer {
+    // Start a printer called `name` with ink for `ink` lines, which sends each line it prints to
+    // `printed`, and hands its jobs over to `backup`, if there is one, once it's out of ink.  It
+    // stops when every handle to it has been dropped.
+    pub fn start(name: &'static str, ink: usize, backup: Option<Printer>,
+                 printed: Sender<Printed>) -> Printer {
+        let (jobs, queue) = channel::<Job>();
+        thread::spawn(move || {
+            let mut ink = ink;
+            for job in queue {
+                if ink > 0 {
+                    ink -= 1;
+                    println!("{}: {}", name, job.line);
+                    printed.send((name, job.line)).unwrap();
+                    job.done.send(Ok(())).unwrap();
+                } else if let Some(ref backup) = backup {
+                    // The backup answers the writer, who is still waiting
+                    backup.jobs.send(job).unwrap();
+                } else {
+                    job.done.send(Err(OutOfInk)).unwrap();
+                }
+            }
+        });
+        Printer { jobs }
+    }
+
+    // Print `line`, waiting until it has been printed
+    pub fn print(&self, line: &str) -> Result<(), OutOfInk> {
+        let (done, answer) = channel();
+        self.jobs.send(Job { line: line.to_string(), done }).unwrap();
+        answer.recv().unwrap()
+    }
+}
+
+// Print `verse` a line at a time, stopping at the first line that can't be printed
+fn write(printer: &Printer, verse: &[&str]) -> Result<(), OutOfInk> {
+    for line in verse {
+        printer.print(line)?;
+    }
+    Ok(())
+}
+
+// Have a writer for each of `verses` print it on a main printer backed by a reserve one, each
+// with ink for `ink` lines.  Returns how each writer got on, and which printer printed each line,
+// in the order they were printed.
+pub fn rendezvous(verses: &[&[&str]], ink: usize) -> (Vec<Result<(), OutOfInk>>, Vec<Printed>) {
+    let (printed, lines) = channel();
+    let reserve = Printer::start("Reserve", ink, None, printed.clone());
+    let main = Printer::start("Main", ink, Some(reserve), printed);
+    let results = thread::scope(|scope| {
+        let writers: Vec<_> = verses.iter().map(|verse| {
+            let main = main.clone();
+            scope.spawn(move || write(&main, verse))
+        }).collect();
+        writers.into_iter().map(|writer| writer.join().unwrap()).collect()
+    });
+    // The printers stop once the main one is dropped, and so do the lines
+    drop(main);
+    (results, lines.into_iter().collect())
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (results, _) = rendezvous(&[&HUMPTY_DUMPTY, &MOTHER_GOOSE], 5);
+    for (writer, result) in ["Humpty Dumpty", "Mother Goose"].iter().zip(results) {
+        if let Err(error) = result {
+            println!("{}: {}", writer, error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rendezvous, OutOfInk, HUMPTY_DUMPTY, MOTHER_GOOSE};
+
+    #[test]
+    fn one_writer_runs_out() {
+        let (results, printed) = rendezvous(&[&HUMPTY_DUMPTY, &MOTHER_GOOSE], 5);
+        // 11 lines between them, and ink for 10
+        assert_eq!(results.iter().filter(|result| result.is_err()).count(), 1);
+        assert_eq!(printed.len(), 10);
+        // The main printer ran out before the reserve was used
+        assert!(printed[..5].iter().all(|&(printer, _)| printer == "Main"));
+        assert!(printed[5..].iter().all(|&(printer, _)| printer == "Reserve"));
+        // Each writer's lines were printed in order, all of them unless they ran out
+        for (verse, result) in [&HUMPTY_DUMPTY[..], &MOTHER_GOOSE[..]].iter().zip(&results) {
+            let theirs: Vec<&str> = printed.iter().map(|(_, line)| line.as_str())
+                                           .filter(|line| verse.contains(line)).collect();
+            match *result {
+                Ok(()) => assert_eq!(&theirs[..], *verse),
+                Err(OutOfInk) => assert_eq!(&theirs[..], &verse[..theirs.len()]),
+            }
+        }
+    }
+
+    #[test]
+    fn enough_ink() {
+        let (results, printed) = rendezvous(&[&HUMPTY_DUMPTY, &MOTHER_GOOSE], 6);
+        assert_eq!(results, vec![Ok(()), Ok(())]);
+        assert_eq!(printed.len(), 11);
+        assert_eq!(printed.iter().filter(|&&(printer, _)| printer == "Reserve").count(), 5);
+    }
+
+    #[test]
+    fn no_ink() {
+        let (results, printed) = rendezvous(&[&HUMPTY_DUMPTY], 0);
+        assert_eq!(results, vec![Err(OutOfInk)]);
+        assert!(printed.is_empty());
+        assert_eq!(OutOfInk.to_string(), "out of ink");
+    }
+}
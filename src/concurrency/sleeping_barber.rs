@@ -0,0 +1,235 @@
+// The sleeping barber problem
+//
+// A barber cuts hair one customer at a time, and sleeps in the barber's chair while there's no
+// one to serve.  Customers arriving while the barber is busy wait in a waiting room with a few
+// chairs, and leave if every chair is taken.  The chairs are a CountingSemaphore from
+// metered_concurrency.rs: a customer who can't try_acquire() one is turned away, and one who can
+// holds it until the barber calls them in.  The barber sleeps on a Condvar until someone is
+// waiting.  The shop closes once all the customers for the day have come, and the barber finishes
+// with those still waiting.
+//
+//     sleeping_barber [--customers N] [--chairs N] [--cut-ms MS] [--between-ms MS]
+
+use metered_concurrency::CountingSemaphore;
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::sync::mpsc::{channel, Sender};
+use std::thread::{self, sleep};
+use std::time::Duration;
+
+#[allow(dead_code)]
+mod metered_concurrency;
+
+// The utils module as metered_concurrency.rs declares it, since it can't be declared twice
+use metered_concurrency::utils;
+use utils::args::Args;
+use utils::rng::Rng;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Config {
+    customers: usize,  // How many come in the day
+    chairs: usize,     // In the waiting room
+    cut: Duration,     // How long a haircut takes
+    between: Duration, // The most time between one customer and the next
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            customers: 20,
+            chairs: 3,
+            cut: Duration::from_millis(100),
+            between: Duration::from_millis(100),
+        }
+    }
+}
+
+fn parse_args(mut args: Args) -> Result<Config, String> {
+    let mut config = Config::default();
+    if let Some(customers) = args.value("--customers")? {
+        config.customers = customers;
+    }
+    match args.value("--chairs")? {
+        Some(0) => return Err("--chairs must be positive".to_string()),
+        Some(chairs) => config.chairs = chairs,
+        None => {}
+    }
+    if let Some(cut) = args.value("--cut-ms")? {
+        config.cut = Duration::from_millis(cut);
+    }
+    if let Some(between) = args.value("--between-ms")? {
+        config.between = Duration::from_millis(between);
+    }
+    args.finish()?;
+    Ok(config)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Stats {
+    served: usize,
+    turned_away: usize,
+    naps: usize,         // How many times the barber fell asleep
+    most_waiting: usize, // The most customers in the waiting room at once
+}
+
+struct Shop {
+    chairs: CountingSemaphore,
+    waiting: Mutex<Room>,
+    arrived: Condvar, // Wakes the barber when someone sits down, or the shop closes
+}
+
+struct Room {
+    customers: VecDeque<(usize, Sender<()>)>, // Told when the barber calls them, and when done
+    closed: bool,
+}
+
+fn barber(shop: &Shop, cut: Duration) -> (usize, usize) {
+    let (mut served, mut naps) = (0, 0);
+    loop {
+        let mut room = shop.waiting.lock().unwrap();
+        if room.customers.is_empty() && !room.closed {
+            println!("The barber falls asleep");
+            naps += 1;
+            while room.customers.is_empty() && !room.closed {
+                room = shop.arrived.wait(room).unwrap();
+            }
+        }
+        let (customer, call) = match room.customers.pop_front() {
+            Some(customer) => customer,
+            None => break, // The shop is closed, and there's no one left
+        };
+        drop(room);
+        call.send(()).unwrap();
+        println!("The barber cuts customer {}'s hair", customer);
+        sleep(cut);
+        call.send(()).unwrap();
+        served += 1;
+    }
+    (served, naps)
+}
+
+// A customer who comes into the shop.  Returns how many were in the waiting room with them, if
+// they got a chair.
+fn customer(shop: &Shop, id: usize) -> Option<usize> {
+    let chair = match shop.chairs.try_acquire() {
+        Some(chair) => chair,
+        None => {
+            println!("Customer {} is turned away", id);
+            return None;
+        }
+    };
+    let (call, called) = channel();
+    let waiting = {
+        let mut room = shop.waiting.lock().unwrap();
+        room.customers.push_back((id, call));
+        room.customers.len()
+    };
+    println!("Customer {} sits down in the waiting room ({} waiting)", id, waiting);
+    shop.arrived.notify_one();
+    // Into the barber's chair, leaving this one for someone else
+    called.recv().unwrap();
+    drop(chair);
+    called.recv().unwrap();
+    println!("Customer {} leaves with a haircut", id);
+    Some(waiting)
+}
+
+// Run the shop for a day, with customers arriving as `config` says, and `rng` deciding when
+fn open_shop(config: &Config, rng: &mut Rng) -> Stats {
+    let shop = Shop {
+        chairs: CountingSemaphore::new(config.chairs),
+        waiting: Mutex::new(Room { customers: VecDeque::new(), closed: false }),
+        arrived: Condvar::new(),
+    };
+    thread::scope(|scope| {
+        let shop = &shop;
+        let barber = scope.spawn(move || barber(shop, config.cut));
+        let customers: Vec<_> = (0..config.customers).map(|id| {
+            let gap = rng.gen_range(0, config.between.as_micros() as u64 + 1);
+            sleep(Duration::from_micros(gap));
+            scope.spawn(move || customer(shop, id))
+        }).collect();
+        let waiting: Vec<Option<usize>> = customers.into_iter().map(|c| c.join().unwrap())
+                                                   .collect();
+        shop.waiting.lock().unwrap().closed = true;
+        shop.arrived.notify_one();
+        let (served, naps) = barber.join().unwrap();
+        Stats {
+            served,
+            turned_away: waiting.iter().filter(|waiting| waiting.is_none()).count(),
+            naps,
+            most_waiting: waiting.iter().flatten().cloned().max().unwrap_or(0),
+        }
+    })
+}
+
+#[cfg(not(test))]
+fn main() {
+    let config = parse_args(Args::from_env()).unwrap_or_else(|message| {
+        utils::args::exit_with_usage(&message, "sleeping_barber [--customers N] [--chairs N] \
+                                                [--cut-ms MS] [--between-ms MS]")
+    });
+    let stats = open_shop(&config, &mut Rng::from_time());
+    println!();
+    println!("Customers served:      {}", stats.served);
+    println!("Customers turned away: {}", stats.turned_away);
+    println!("Times the barber slept: {}", stats.naps);
+    println!("Most customers waiting: {} of {} chairs", stats.most_waiting, config.chairs);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{open_shop, parse_args, Config};
+    use std::time::Duration;
+    use utils::args::Args;
+    use utils::rng::Rng;
+
+    fn config(customers: usize, chairs: usize, cut: u64, between: u64) -> Config {
+        let (cut, between) = (Duration::from_millis(cut), Duration::from_millis(between));
+        Config { customers, chairs, cut, between }
+    }
+
+    #[test]
+    fn everyone_accounted_for() {
+        for seed in 0..5 {
+            let stats = open_shop(&config(50, 3, 2, 2), &mut Rng::new(seed));
+            assert_eq!(stats.served + stats.turned_away, 50);
+            assert!(stats.most_waiting <= 3);
+            assert!(stats.naps >= 1);
+        }
+    }
+
+    #[test]
+    fn full_waiting_room() {
+        // They all come at once, and the barber is slow, so only those in the waiting room are
+        // served, and the one or two called in from it before the rest came
+        let stats = open_shop(&config(10, 2, 200, 0), &mut Rng::new(58));
+        assert!((2..=4).contains(&stats.served), "{:?}", stats);
+        assert_eq!(stats.served + stats.turned_away, 10);
+        assert_eq!(stats.most_waiting, 2);
+    }
+
+    #[test]
+    fn enough_chairs() {
+        let stats = open_shop(&config(10, 10, 5, 0), &mut Rng::new(58));
+        assert_eq!((stats.served, stats.turned_away), (10, 0));
+    }
+
+    #[test]
+    fn sleeps_between_customers() {
+        // The barber finishes each haircut long before the next customer comes
+        let stats = open_shop(&Config { between: Duration::from_millis(40), ..config(5, 1, 0, 0) },
+                              &mut Rng::new(1));
+        assert_eq!(stats.served, 5);
+        assert!(stats.naps >= 2, "{:?}", stats);
+    }
+
+    #[test]
+    fn arguments() {
+        let args = |args: &[&str]| parse_args(Args::new(args.iter().map(|arg| arg.to_string())));
+        assert_eq!(args(&[]), Ok(Config::default()));
+        assert_eq!(args(&["--customers=5", "--chairs", "1", "--cut-ms", "3", "--between-ms=4"]),
+                   Ok(config(5, 1, 3, 4)));
+        assert_eq!(args(&["--chairs", "0"]), Err("--chairs must be positive".to_string()));
+    }
+}
@@ -0,0 +1,95 @@
+// http://rosettacode.org/wiki/Synchronous_concurrency
+//
+// Reader unit reads lines from tests/fixtures/input.txt, send lines one at a time to printer
+// unit, which then prints lines, keeping track of lines printed. At the end of
+// the file, the reader unit requests number of lines printed from the printer
+// unit, and then prints them.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::io::{self, BufRead, Write};
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Sender, Receiver};
+use std::thread;
+
+enum Message {
+    Line(String),
+    End
+}
+
+fn printer(i_snd: Sender<usize>, msg_rcv: Receiver<Message>, output: &Mutex<dyn Write + Send + '_>)
+           -> io::Result<()> {
+    let mut count = 0;
+    loop {
+        // The reader only hangs up early if it failed, and it reports that itself
+        match msg_rcv.recv() {
+            Ok(Message::Line(line)) => {
+                writeln!(output.lock().unwrap(), "{}", line)?;
+                count += 1;
+            }
+            Ok(Message::End) => {break;}
+            Err(_) => return Ok(()),
+        }
+    }
+    // The reader waits for the count, unless it has failed
+    let _ = i_snd.send(count);
+    Ok(())
+}
+
+fn reader(msg_snd: Sender<Message>, i_rcv: Receiver<usize>, input: impl BufRead,
+          output: &Mutex<dyn Write + Send + '_>) -> io::Result<()> {
+    for line in input.lines() {
+        // The printer only hangs up early if it failed, and it reports that itself
+        if msg_snd.send(Message::Line(line?)).is_err() {
+            return Ok(());
+        }
+    }
+    let _ = msg_snd.send(Message::End);
+    if let Ok(count) = i_rcv.recv() {
+        writeln!(output.lock().unwrap(), "Total Lines: {}", count)?;
+    }
+    Ok(())
+}
+
+// Run the reader and printer units on `input`, each on a thread of its own, and have them print
+// to `output`
+fn run(input: impl BufRead + Send, output: impl Write + Send) -> io::Result<()> {
+    let output = Mutex::new(output);
+    let (msg_snd, msg_rcv) = channel();
+    let (i_snd, i_rcv) = channel();
+    thread::scope(|scope| {
+        let output = &output;
+        let printer = scope.spawn(move || printer(i_snd, msg_rcv, output));
+        let reader = scope.spawn(move || reader(msg_snd, i_rcv, input, output));
+        reader.join().unwrap()?;
+        printer.join().unwrap()
+    })
+}
+
+#[cfg(not(test))]
+fn main() {
+    if let Err(err) = run(utils::fixture::open("input.txt"), io::stdout()) {
+        eprintln!("synchronous_concurrency: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::run;
+    use utils::capture::{error_of, output_of};
+
+    #[test]
+    fn counts_lines() {
+        assert_eq!(output_of(run, "one\ntwo\n\nfour"), "one\ntwo\n\nfour\nTotal Lines: 4\n");
+        assert_eq!(output_of(run, ""), "Total Lines: 0\n");
+    }
+
+    #[test]
+    fn bad_input() {
+        // Not UTF-8
+        let error = error_of(|_, output| run(&b"fine\n\xff\n"[..], output), "");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,211 @@
+//! A fixed-size thread pool, in the spirit of http://rosettacode.org/wiki/Synchronous_concurrency
+//!
+//! Jobs are handed to the worker threads over a channel.  Since a channel never fills up, a
+//! CountingSemaphore from metered_concurrency.rs caps how many jobs may be in flight (queued or
+//! running) at once: execute() blocks until one of them finishes, so a fast producer can't queue up
+//! an unbounded amount of work.
+//!
+//! ```
+//! # extern crate tasks;
+//! use std::sync::mpsc::channel;
+//! use tasks::thread_pool::ThreadPool;
+//!
+//! let pool = ThreadPool::new(2, 4);
+//! let (squares, results) = channel();
+//! for i in 0..10 {
+//!     let squares = squares.clone();
+//!     pool.execute(move || squares.send(i * i).unwrap());
+//!     assert!(pool.in_flight() <= 4);
+//! }
+//! pool.join();
+//! drop(squares);
+//! assert_eq!(results.iter().sum::<i32>(), 285);
+//! ```
+
+use self::metered_concurrency::{CountingSemaphore, OwnedSemaphoreGuard};
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::{sleep, spawn, JoinHandle};
+use std::time::Duration;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[allow(dead_code)]
+mod metered_concurrency;
+
+// A job, along with the resource it holds for as long as it is in flight
+type Job = (Box<dyn FnOnce() + Send>, OwnedSemaphoreGuard);
+
+pub struct ThreadPool {
+    sender: Option<Sender<Job>>,   // Dropped to tell the workers there are no more jobs
+    workers: Vec<JoinHandle<()>>,  // One per thread
+    in_flight: Arc<CountingSemaphore>, // One resource per job that may be in flight
+    max_in_flight: usize,
+}
+
+impl ThreadPool {
+    // Create a pool of `threads` worker threads, with at most `max_in_flight` jobs queued or
+    // running at any time.
+    pub fn new(threads: usize, max_in_flight: usize) -> ThreadPool {
+        assert!(threads > 0, "a thread pool needs at least one thread");
+        assert!(max_in_flight > 0, "a thread pool needs to accept at least one job");
+        let (sender, receiver) = channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..threads).map(|_| {
+            let receiver = receiver.clone();
+            spawn(move || work(&receiver))
+        }).collect();
+        ThreadPool {
+            sender: Some(sender),
+            workers,
+            in_flight: Arc::new(CountingSemaphore::new(max_in_flight)),
+            max_in_flight,
+        }
+    }
+
+    // Run `job` on one of the worker threads, first waiting for room if too many jobs are
+    // already in flight.
+    pub fn execute<F: FnOnce() + Send + 'static>(&self, job: F) {
+        // The semaphore is never closed, so this can't fail
+        let permit = self.in_flight.acquire_owned().unwrap();
+        self.sender.as_ref().unwrap().send((Box::new(job), permit)).unwrap();
+    }
+
+    // Return how many jobs are queued or running
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.in_flight.count()
+    }
+
+    // Wait for every job executed so far to finish, then stop the worker threads.
+    pub fn join(mut self) {
+        self.shut_down();
+    }
+
+    fn shut_down(&mut self) {
+        // Once the queue is empty, every worker's recv() fails and it exits
+        drop(self.sender.take());
+        for worker in mem::take(&mut self.workers) {
+            worker.join().unwrap();
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    // A pool that is dropped without being joined still lets its jobs finish.
+    fn drop(&mut self) {
+        self.shut_down();
+    }
+}
+
+// Run jobs from `receiver` until the pool is shut down.
+fn work(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // The lock is only held while waiting for a job, not while running it
+        let next = receiver.lock().unwrap().recv();
+        match next {
+            // The job's resource is released once it has run
+            Ok((job, _permit)) => job(),
+            Err(_) => return,
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    static NUM_JOBS: usize = 20;
+    let pool = ThreadPool::new(4, 8);
+    let (tx, rx) = channel();
+    for i in 0..NUM_JOBS {
+        let tx = tx.clone();
+        pool.execute(move || {
+            sleep(Duration::from_millis(100));
+            tx.send((i, i * i)).unwrap();
+        });
+        println!("Submitted job {}, {} in flight", i, pool.in_flight());
+    }
+    drop(tx);
+    for (i, square) in rx.iter() {
+        println!("Job {} computed {}", i, square);
+    }
+    pool.join();
+}
+
+#[test]
+fn test_runs_every_job() {
+    static NUM_JOBS: usize = 500;
+    let pool = ThreadPool::new(4, 16);
+    let done = Arc::new(AtomicUsize::new(0));
+    for _ in 0..NUM_JOBS {
+        let done = done.clone();
+        pool.execute(move || { done.fetch_add(1, Ordering::SeqCst); });
+    }
+    // join() waits for the jobs that are still queued
+    pool.join();
+    assert_eq!(done.load(Ordering::SeqCst), NUM_JOBS);
+}
+
+#[test]
+fn test_caps_jobs_in_flight() {
+    static NUM_JOBS: usize = 200;
+    static MAX_IN_FLIGHT: usize = 6;
+    let pool = ThreadPool::new(3, MAX_IN_FLIGHT);
+    let running = Arc::new(AtomicUsize::new(0));
+    let most_running = Arc::new(AtomicUsize::new(0));
+    for _ in 0..NUM_JOBS {
+        let running = running.clone();
+        let most_running = most_running.clone();
+        pool.execute(move || {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most_running.fetch_max(now, Ordering::SeqCst);
+            sleep(Duration::from_millis(1));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+        assert!(pool.in_flight() <= MAX_IN_FLIGHT);
+    }
+    pool.join();
+    // No more jobs can run at once than there are threads
+    assert!(most_running.load(Ordering::SeqCst) <= 3);
+    assert_eq!(running.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_execute_blocks_when_full() {
+    let pool = ThreadPool::new(1, 2);
+    let (tx, rx) = channel::<()>();
+    let rx = Arc::new(Mutex::new(rx));
+    // Two jobs that won't finish until told to: one running, one queued
+    for _ in 0..2 {
+        let rx = rx.clone();
+        pool.execute(move || rx.lock().unwrap().recv().unwrap());
+    }
+    assert_eq!(pool.in_flight(), 2);
+    let pool = Arc::new(pool);
+    let pool_ = pool.clone();
+    let (submitted_tx, submitted) = channel();
+    let submitter = spawn(move || {
+        pool_.execute(|| ());
+        submitted_tx.send(()).unwrap();
+    });
+    sleep(Duration::from_millis(50));
+    assert!(submitted.try_recv().is_err());
+    // Letting a job finish makes room for the third
+    tx.send(()).unwrap();
+    submitted.recv().unwrap();
+    tx.send(()).unwrap();
+    submitter.join().unwrap();
+    drop(pool); // Joins the pool
+}
+
+#[test]
+fn test_drop_finishes_jobs() {
+    let done = Arc::new(AtomicUsize::new(0));
+    {
+        let pool = ThreadPool::new(2, 4);
+        for _ in 0..100 {
+            let done = done.clone();
+            pool.execute(move || { done.fetch_add(1, Ordering::SeqCst); });
+        }
+    }
+    assert_eq!(done.load(Ordering::SeqCst), 100);
+}
@@ -0,0 +1,374 @@
+//! AES (Rijndael) as FIPS 197 specifies it, for 128, 192 and 256-bit keys
+//!
+//! A block is 16 bytes, held as a 4x4 state column by column.  Encrypting it adds the first round
+//! key, then runs 10, 12 or 14 rounds, by the key's size, of
+//!
+//! ```text
+//!     SubBytes        each byte replaced through the S-box
+//!     ShiftRows       row r rotated left by r
+//!     MixColumns      each column multiplied by a fixed matrix over GF(2^8)
+//!     AddRoundKey     the round's key XORed in
+//! ```
+//!
+//! with the last round leaving out MixColumns.  Decrypting undoes each step in reverse.  The
+//! S-box isn't copied from the standard but worked out the way it's defined, from the inverse of
+//! each byte in GF(2^8) and an affine transformation, as are its inverse and the round constants.
+//!
+//! ECB and CBC chain blocks together for longer messages, padded to a whole number of blocks as
+//! PKCS #7 does it.  ECB encrypts each block on its own, so equal blocks give equal ciphertext,
+//! which is why CBC XORs each plaintext block with the ciphertext before it, or an initialization
+//! vector for the first block.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::aes::Aes;
+//!
+//! // FIPS 197's example in appendix B
+//! let aes = Aes::new(&[0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
+//!                      0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c]).unwrap();
+//! assert_eq!(aes.rounds(), 10);
+//! let mut block = [0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d,
+//!                  0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34];
+//! aes.encrypt_block(&mut block);
+//! assert_eq!(block, [0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb,
+//!                    0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32]);
+//!
+//! let iv = [0; 16];
+//! let ciphertext = aes.encrypt_cbc(&iv, b"Attack at dawn");
+//! assert_eq!(ciphertext.len(), 16);
+//! assert_eq!(aes.decrypt_cbc(&iv, &ciphertext).unwrap(), b"Attack at dawn");
+//! assert!(Aes::new(b"too short").is_err());
+//! ```
+
+// The product of two elements of GF(2^8), modulo x^8 + x^4 + x^3 + x + 1
+fn multiply(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a = (a << 1) ^ if a & 0x80 != 0 { 0x1b } else { 0 };
+        b >>= 1;
+    }
+    product
+}
+
+// The S-box and its inverse
+fn s_boxes() -> ([u8; 256], [u8; 256]) {
+    let mut s_box = [0u8; 256];
+    let mut inverse = [0u8; 256];
+    for (x, s) in s_box.iter_mut().enumerate() {
+        let reciprocal = (1..=255u8).find(|&y| multiply(x as u8, y) == 1).unwrap_or(0);
+        *s = (1..5).fold(reciprocal ^ 0x63, |s, shift| s ^ reciprocal.rotate_left(shift));
+        inverse[*s as usize] = x as u8;
+    }
+    (s_box, inverse)
+}
+
+pub struct Aes {
+    s_box: [u8; 256],
+    inverse_s_box: [u8; 256],
+    // The round keys, one of 16 bytes for each round and one before them
+    round_keys: Vec<[u8; 16]>,
+}
+
+impl Aes {
+    // A cipher with `key`, which has to be 16, 24 or 32 bytes long
+    pub fn new(key: &[u8]) -> Result<Aes, String> {
+        if ![16, 24, 32].contains(&key.len()) {
+            return Err(format!("an AES key is 16, 24 or 32 bytes long, not {}", key.len()));
+        }
+        let (s_box, inverse_s_box) = s_boxes();
+        Ok(Aes { round_keys: expand_key(key, &s_box), s_box, inverse_s_box })
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    pub fn encrypt_block(&self, block: &mut [u8; 16]) {
+        add_round_key(block, &self.round_keys[0]);
+        for round in 1..=self.rounds() {
+            for byte in block.iter_mut() {
+                *byte = self.s_box[*byte as usize];
+            }
+            shift_rows(block);
+            if round < self.rounds() {
+                mix_columns(block, [2, 3, 1, 1]);
+            }
+            add_round_key(block, &self.round_keys[round]);
+        }
+    }
+
+    pub fn decrypt_block(&self, block: &mut [u8; 16]) {
+        add_round_key(block, &self.round_keys[self.rounds()]);
+        for round in (0..self.rounds()).rev() {
+            unshift_rows(block);
+            for byte in block.iter_mut() {
+                *byte = self.inverse_s_box[*byte as usize];
+            }
+            add_round_key(block, &self.round_keys[round]);
+            if round > 0 {
+                mix_columns(block, [14, 11, 13, 9]);
+            }
+        }
+    }
+
+    pub fn encrypt_ecb(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut blocks = pad(plaintext);
+        for block in &mut blocks {
+            self.encrypt_block(block);
+        }
+        blocks.concat()
+    }
+
+    pub fn decrypt_ecb(&self, ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        let mut blocks = split(ciphertext)?;
+        for block in &mut blocks {
+            self.decrypt_block(block);
+        }
+        unpad(blocks)
+    }
+
+    pub fn encrypt_cbc(&self, iv: &[u8; 16], plaintext: &[u8]) -> Vec<u8> {
+        let mut previous = *iv;
+        let mut blocks = pad(plaintext);
+        for block in &mut blocks {
+            add_round_key(block, &previous);
+            self.encrypt_block(block);
+            previous = *block;
+        }
+        blocks.concat()
+    }
+
+    pub fn decrypt_cbc(&self, iv: &[u8; 16], ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        let mut previous = *iv;
+        let mut blocks = split(ciphertext)?;
+        for block in &mut blocks {
+            let encrypted = *block;
+            self.decrypt_block(block);
+            add_round_key(block, &previous);
+            previous = encrypted;
+        }
+        unpad(blocks)
+    }
+}
+
+fn expand_key(key: &[u8], s_box: &[u8; 256]) -> Vec<[u8; 16]> {
+    let nk = key.len() / 4;
+    let rounds = nk + 6;
+    let mut words: Vec<[u8; 4]> = key.chunks(4).map(|w| [w[0], w[1], w[2], w[3]]).collect();
+    let mut round_constant = 1u8;
+    for i in nk..4 * (rounds + 1) {
+        let mut word = words[i - 1];
+        if i % nk == 0 {
+            word.rotate_left(1);
+            for byte in &mut word {
+                *byte = s_box[*byte as usize];
+            }
+            word[0] ^= round_constant;
+            round_constant = multiply(round_constant, 2);
+        } else if nk > 6 && i % nk == 4 {
+            for byte in &mut word {
+                *byte = s_box[*byte as usize];
+            }
+        }
+        for (byte, earlier) in word.iter_mut().zip(&words[i - nk]) {
+            *byte ^= earlier;
+        }
+        words.push(word);
+    }
+    words.chunks(4)
+        .map(|four| {
+            let mut round_key = [0; 16];
+            for (i, word) in four.iter().enumerate() {
+                round_key[4 * i..4 * i + 4].copy_from_slice(word);
+            }
+            round_key
+        })
+        .collect()
+}
+
+fn add_round_key(block: &mut [u8; 16], key: &[u8; 16]) {
+    for (byte, key_byte) in block.iter_mut().zip(key) {
+        *byte ^= key_byte;
+    }
+}
+
+// The state's byte in row `row` and column `column` is block[row + 4 * column]
+fn shift_rows(block: &mut [u8; 16]) {
+    let old = *block;
+    for row in 1..4 {
+        for column in 0..4 {
+            block[row + 4 * column] = old[row + 4 * ((column + row) % 4)];
+        }
+    }
+}
+
+fn unshift_rows(block: &mut [u8; 16]) {
+    let old = *block;
+    for row in 1..4 {
+        for column in 0..4 {
+            block[row + 4 * ((column + row) % 4)] = old[row + 4 * column];
+        }
+    }
+}
+
+// Multiply each column by the circulant matrix whose first row is `row`: [2, 3, 1, 1] for
+// MixColumns, and [14, 11, 13, 9] for its inverse
+fn mix_columns(block: &mut [u8; 16], row: [u8; 4]) {
+    for column in block.chunks_exact_mut(4) {
+        let old = [column[0], column[1], column[2], column[3]];
+        for (i, byte) in column.iter_mut().enumerate() {
+            *byte = (0..4).fold(0, |sum, j| sum ^ multiply(row[(j + 4 - i) % 4], old[j]));
+        }
+    }
+}
+
+// The message in blocks, with n bytes of n after it to fill the last, a whole block of them if
+// it was already full
+fn pad(message: &[u8]) -> Vec<[u8; 16]> {
+    let padding = 16 - message.len() % 16;
+    let mut padded = message.to_vec();
+    padded.resize(message.len() + padding, padding as u8);
+    split(&padded).unwrap()
+}
+
+fn split(ciphertext: &[u8]) -> Result<Vec<[u8; 16]>, String> {
+    if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(16) {
+        return Err(format!("{} bytes isn't a whole number of blocks", ciphertext.len()));
+    }
+    Ok(ciphertext.chunks(16)
+        .map(|chunk| {
+            let mut block = [0; 16];
+            block.copy_from_slice(chunk);
+            block
+        })
+        .collect())
+}
+
+fn unpad(blocks: Vec<[u8; 16]>) -> Result<Vec<u8>, String> {
+    let mut message = blocks.concat();
+    let padding = *message.last().unwrap() as usize;
+    let start = message.len().wrapping_sub(padding);
+    if padding == 0 || padding > 16 || message[start..].iter().any(|&byte| byte as usize != padding)
+    {
+        return Err("the padding is wrong, so the key or the ciphertext is".to_string());
+    }
+    message.truncate(start);
+    Ok(message)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(not(test))]
+fn main() {
+    let key = b"YELLOW SUBMARINE";
+    let aes = Aes::new(key).unwrap();
+    let iv = [0u8; 16];
+    let message = "Two roads diverged in a yellow wood, and sorry I could not travel both";
+    let ciphertext = aes.encrypt_cbc(&iv, message.as_bytes());
+    println!("Key:       {:?}", String::from_utf8_lossy(key));
+    println!("Plaintext: {}", message);
+    println!("CBC:       {}", hex(&ciphertext));
+    println!("ECB:       {}", hex(&aes.encrypt_ecb(message.as_bytes())));
+    let decrypted = aes.decrypt_cbc(&iv, &ciphertext).unwrap();
+    println!("Decrypted: {}", String::from_utf8_lossy(&decrypted));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hex, multiply, s_boxes, Aes};
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn block(hex: &str) -> [u8; 16] {
+        let mut block = [0; 16];
+        block.copy_from_slice(&bytes(hex));
+        block
+    }
+
+    #[test]
+    fn field() {
+        assert_eq!(multiply(0x57, 0x83), 0xc1);
+        assert_eq!(multiply(0x57, 0x13), 0xfe);
+        let (s_box, inverse) = s_boxes();
+        assert_eq!(s_box[..4], [0x63, 0x7c, 0x77, 0x7b]);
+        assert_eq!(s_box[0x53], 0xed);
+        assert_eq!(s_box[0xff], 0x16);
+        assert!((0..256).all(|x| inverse[s_box[x] as usize] as usize == x));
+    }
+
+    #[test]
+    fn fips_197() {
+        // Appendix B's example, and appendix C's for each key size
+        let vectors = [
+            ("2b7e151628aed2a6abf7158809cf4f3c", "3243f6a8885a308d313198a2e0370734",
+             "3925841d02dc09fbdc118597196a0b32"),
+            ("000102030405060708090a0b0c0d0e0f", "00112233445566778899aabbccddeeff",
+             "69c4e0d86a7b0430d8cdb78070b4c55a"),
+            ("000102030405060708090a0b0c0d0e0f1011121314151617",
+             "00112233445566778899aabbccddeeff", "dda97ca4864cdfe06eaf70a0ec0d7191"),
+            ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+             "00112233445566778899aabbccddeeff", "8ea2b7ca516745bfeafc49904b496089"),
+        ];
+        for &(key, plaintext, ciphertext) in &vectors {
+            let aes = Aes::new(&bytes(key)).unwrap();
+            let mut data = block(plaintext);
+            aes.encrypt_block(&mut data);
+            assert_eq!(hex(&data), ciphertext, "key {}", key);
+            aes.decrypt_block(&mut data);
+            assert_eq!(hex(&data), plaintext, "key {}", key);
+        }
+        assert_eq!(Aes::new(&[0; 32]).unwrap().rounds(), 14);
+    }
+
+    #[test]
+    fn key_expansion() {
+        // FIPS 197's appendix A.1: the last round key for 2b7e1516...
+        let aes = Aes::new(&bytes("2b7e151628aed2a6abf7158809cf4f3c")).unwrap();
+        assert_eq!(hex(&aes.round_keys[10]), "d014f9a8c9ee2589e13f0cc8b6630ca6");
+        assert!(Aes::new(&[0; 20]).is_err());
+    }
+
+    #[test]
+    fn modes() {
+        // SP 800-38A's first CBC block, which the padding comes after
+        let aes = Aes::new(&bytes("2b7e151628aed2a6abf7158809cf4f3c")).unwrap();
+        let iv = block("000102030405060708090a0b0c0d0e0f");
+        let plaintext = bytes("6bc1bee22e409f96e93d7e117393172a");
+        let ciphertext = aes.encrypt_cbc(&iv, &plaintext);
+        assert_eq!(hex(&ciphertext[..16]), "7649abac8119b246cee98e9b12e9197d");
+        assert_eq!(ciphertext.len(), 32);
+        assert_eq!(aes.decrypt_cbc(&iv, &ciphertext).unwrap(), plaintext);
+        // And SP 800-38A's first ECB block
+        assert_eq!(hex(&aes.encrypt_ecb(&plaintext)[..16]), "3ad77bb40d7a3660a89ecaf32466ef97");
+
+        for length in 0..40 {
+            let message: Vec<u8> = (0..length as u8).collect();
+            let encrypted = aes.encrypt_ecb(&message);
+            assert_eq!(encrypted.len(), (length / 16 + 1) * 16);
+            assert_eq!(aes.decrypt_ecb(&encrypted).unwrap(), message);
+            assert_eq!(aes.decrypt_cbc(&iv, &aes.encrypt_cbc(&iv, &message)).unwrap(), message);
+        }
+        // Equal blocks stand out in ECB, but not in CBC
+        let repeated = [7u8; 32];
+        let ecb = aes.encrypt_ecb(&repeated);
+        assert_eq!(ecb[..16], ecb[16..32]);
+        let cbc = aes.encrypt_cbc(&iv, &repeated);
+        assert_ne!(cbc[..16], cbc[16..32]);
+    }
+
+    #[test]
+    fn bad_ciphertext() {
+        let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+        assert!(aes.decrypt_ecb(&[0; 15]).is_err());
+        assert!(aes.decrypt_ecb(&[]).is_err());
+        let other = Aes::new(b"yellow submarine").unwrap();
+        assert!(other.decrypt_ecb(&aes.encrypt_ecb(b"attack at dawn")).is_err());
+    }
+}
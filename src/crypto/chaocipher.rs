@@ -0,0 +1,157 @@
+//! Implements http://rosettacode.org/wiki/Chaocipher
+//!
+//! Two alphabets are in play, the left one for ciphertext and the right one for plaintext.  A
+//! letter is enciphered as the letter of the left alphabet in the same place as it is in the right
+//! one, and then both alphabets are scrambled around that place:
+//!
+//! ```text
+//!     left     turned so that the ciphertext letter is first, the zenith, and then the second
+//!              letter taken out and put back fourteenth, at the nadir
+//!     right    turned so that the letter after the plaintext letter is first, and then the third
+//!              letter taken out and put back at the nadir
+//! ```
+//!
+//! Deciphering looks the ciphertext letter up in the left alphabet instead, and then scrambles
+//! them the same way, so both sides stay in step.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::chaocipher::{Chaocipher, LEFT, RIGHT};
+//!
+//! let plaintext = "WELLDONEISBETTERTHANWELLSAID";
+//! let ciphertext = Chaocipher::new(LEFT, RIGHT).unwrap().encrypt(plaintext);
+//! assert_eq!(ciphertext, "OAHQHCNYNXTSZJRRHJBYHQKSOUJY");
+//! assert_eq!(Chaocipher::new(LEFT, RIGHT).unwrap().decrypt(&ciphertext), plaintext);
+//! assert!(Chaocipher::new("ABC", RIGHT).is_err());
+//! ```
+
+pub static LEFT: &str = "HXUCZVAMDSLKPEFJRIGTWOBNYQ";
+pub static RIGHT: &str = "PTLNBQDEOYSFAVZKGJRIHWXUMC";
+
+const NADIR: usize = 13;
+
+pub struct Chaocipher {
+    left: [u8; 26],
+    right: [u8; 26],
+}
+
+impl Chaocipher {
+    // The starting alphabets, which have to be the letters A to Z in some order
+    pub fn new(left: &str, right: &str) -> Result<Chaocipher, String> {
+        Ok(Chaocipher { left: alphabet(left)?, right: alphabet(right)? })
+    }
+
+    // The alphabets as they stand, left and right
+    pub fn alphabets(&self) -> (String, String) {
+        (String::from_utf8_lossy(&self.left).into_owned(),
+         String::from_utf8_lossy(&self.right).into_owned())
+    }
+
+    fn permute(&mut self, index: usize) {
+        self.left.rotate_left(index);
+        self.left[1..NADIR + 1].rotate_left(1);
+        self.right.rotate_left((index + 1) % 26);
+        self.right[2..NADIR + 1].rotate_left(1);
+    }
+
+    // A letter enciphered, or None for anything that isn't a capital letter
+    pub fn encrypt_letter(&mut self, letter: u8) -> Option<u8> {
+        let index = self.right.iter().position(|&c| c == letter)?;
+        let cipher = self.left[index];
+        self.permute(index);
+        Some(cipher)
+    }
+
+    pub fn decrypt_letter(&mut self, letter: u8) -> Option<u8> {
+        let index = self.left.iter().position(|&c| c == letter)?;
+        let plain = self.right[index];
+        self.permute(index);
+        Some(plain)
+    }
+
+    // The text's letters, as capitals, enciphered, leaving out anything else
+    pub fn encrypt(&mut self, plaintext: &str) -> String {
+        plaintext.bytes()
+            .filter_map(|c| self.encrypt_letter(c.to_ascii_uppercase()))
+            .map(char::from)
+            .collect()
+    }
+
+    pub fn decrypt(&mut self, ciphertext: &str) -> String {
+        ciphertext.bytes()
+            .filter_map(|c| self.decrypt_letter(c.to_ascii_uppercase()))
+            .map(char::from)
+            .collect()
+    }
+}
+
+fn alphabet(letters: &str) -> Result<[u8; 26], String> {
+    let mut alphabet = [0; 26];
+    let mut sorted = letters.as_bytes().to_vec();
+    sorted.sort();
+    if sorted != (b'A'..=b'Z').collect::<Vec<_>>() {
+        return Err(format!("{} isn't an alphabet", letters));
+    }
+    alphabet.copy_from_slice(letters.as_bytes());
+    Ok(alphabet)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let plaintext = "WELLDONEISBETTERTHANWELLSAID";
+    let mut chaocipher = Chaocipher::new(LEFT, RIGHT).unwrap();
+    println!("{:<28}{:<28}Plain Cipher", "Left", "Right");
+    let mut ciphertext = String::new();
+    for letter in plaintext.bytes() {
+        let (left, right) = chaocipher.alphabets();
+        let cipher = chaocipher.encrypt_letter(letter).unwrap() as char;
+        println!("{}  {}  {}     {}", left, right, letter as char, cipher);
+        ciphertext.push(cipher);
+    }
+    println!("\nPlaintext: {}", plaintext);
+    println!("Encrypted: {}", ciphertext);
+    let mut chaocipher = Chaocipher::new(LEFT, RIGHT).unwrap();
+    println!("Decrypted: {}", chaocipher.decrypt(&ciphertext));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Chaocipher, LEFT, RIGHT};
+
+    fn chaocipher() -> Chaocipher {
+        Chaocipher::new(LEFT, RIGHT).unwrap()
+    }
+
+    #[test]
+    fn task() {
+        let ciphertext = chaocipher().encrypt("WELLDONEISBETTERTHANWELLSAID");
+        assert_eq!(ciphertext, "OAHQHCNYNXTSZJRRHJBYHQKSOUJY");
+        assert_eq!(chaocipher().decrypt(&ciphertext), "WELLDONEISBETTERTHANWELLSAID");
+    }
+
+    #[test]
+    fn permutations() {
+        // The alphabets after the first letter, as the task shows them
+        let mut chaocipher = chaocipher();
+        assert_eq!(chaocipher.encrypt_letter(b'W'), Some(b'O'));
+        let (left, right) = chaocipher.alphabets();
+        assert_eq!(left, "ONYQHXUCZVAMDBSLKPEFJRIGTW");
+        assert_eq!(right, "XUCPTLNBQDEOYMSFAVZKGJRIHW");
+    }
+
+    #[test]
+    fn round_trip() {
+        let plaintext = "The quick brown fox jumps over the lazy dog!";
+        let ciphertext = chaocipher().encrypt(plaintext);
+        assert_eq!(ciphertext.len(), 35);
+        assert_eq!(chaocipher().decrypt(&ciphertext), "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG");
+        let mut other = Chaocipher::new(RIGHT, LEFT).unwrap();
+        assert_ne!(other.encrypt(plaintext), ciphertext);
+    }
+
+    #[test]
+    fn bad_alphabets() {
+        assert!(Chaocipher::new("ABC", RIGHT).is_err());
+        assert!(Chaocipher::new(LEFT, "AACDEFGHIJKLMNOPQRSTUVWXYZ").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Diffie-Hellman key exchange, over utils::bignum
+//!
+//! Alice and Bob agree on a group, a prime p and a generator g, in the open.  Each picks a secret
+//! exponent, a for Alice and b for Bob, and sends the other g^a or g^b mod p.  Then each raises
+//! what the other sent to their own secret, and both get g^(ab) mod p, which someone who only saw
+//! g^a and g^b can't work out without solving the discrete logarithm problem.
+//!
+//! The textbook example uses p = 23 and g = 5, small enough to follow by hand, and main() then
+//! does the same with the 1536-bit group of RFC 3526, which is the size that's actually used.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::diffie_hellman::utils::bignum::BigUint;
+//! use tasks::diffie_hellman::{Group, Party};
+//!
+//! let group = Group::textbook();
+//! let alice = Party::new(&group, BigUint::from(6u32));
+//! let bob = Party::new(&group, BigUint::from(15u32));
+//! assert_eq!(alice.public, BigUint::from(8u32));
+//! assert_eq!(bob.public, BigUint::from(19u32));
+//! let shared = alice.shared_secret(&bob.public).unwrap();
+//! assert_eq!(shared, BigUint::from(2u32));
+//! assert_eq!(bob.shared_secret(&alice.public), Ok(shared));
+//! assert!(alice.shared_secret(&BigUint::from(1u32)).is_err());
+//! ```
+
+// pub, for the BigUints of the example above
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::bignum::BigUint;
+use self::utils::rng::Rng;
+
+pub struct Group {
+    pub prime: BigUint,
+    pub generator: BigUint,
+}
+
+// The 1536-bit MODP group of RFC 3526, whose prime is 2^1536 - 2^1472 - 1 + 2^64 * (2^1406 pi +
+// 741804), in hexadecimal
+static RFC_3526_PRIME: &str = "\
+    FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 020BBEA6 3B139B22 \
+    514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 4FE1356D 6D51C245 E485B576 625E7EC6 \
+    F44C42E9 A637ED6B 0BFF5CB6 F406B7ED EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D \
+    C2007CB8 A163BF05 98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB \
+    9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA237327 FFFFFFFF FFFFFFFF";
+
+fn from_hex(hex: &str) -> BigUint {
+    let digits: Vec<u8> = hex.bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| (c as char).to_digit(16).expect("a hexadecimal digit") as u8)
+        .collect();
+    let bytes: Vec<u8> = digits.rchunks(2).rev()
+        .map(|pair| pair.iter().fold(0, |byte, &digit| byte << 4 | digit))
+        .collect();
+    BigUint::from_bytes_be(&bytes)
+}
+
+impl Group {
+    pub fn textbook() -> Group {
+        Group { prime: BigUint::from(23u32), generator: BigUint::from(5u32) }
+    }
+
+    pub fn rfc_3526() -> Group {
+        Group { prime: from_hex(RFC_3526_PRIME), generator: BigUint::from(2u32) }
+    }
+
+    // A random secret exponent, from 2 to p - 2
+    pub fn secret(&self, rng: &mut Rng) -> BigUint {
+        let bytes: Vec<u8> = (0..self.prime.bits() / 8 + 8).map(|_| rng.next_u32() as u8).collect();
+        let range = &self.prime - &BigUint::from(3u32);
+        &(&BigUint::from_bytes_be(&bytes) % &range) + &BigUint::from(2u32)
+    }
+}
+
+// One side of the exchange
+pub struct Party<'a> {
+    group: &'a Group,
+    secret: BigUint,
+    // What's sent to the other side
+    pub public: BigUint,
+}
+
+impl<'a> Party<'a> {
+    pub fn new(group: &'a Group, secret: BigUint) -> Party<'a> {
+        let public = group.generator.pow_mod(&secret, &group.prime);
+        Party { group, secret, public }
+    }
+
+    // The secret shared with the side that sent `public`.  Anything from 2 to p - 2 is a
+    // possible value, but 0, 1 or p - 1 would make the secret easy to guess, and anything else
+    // isn't a number mod p, so they're refused.
+    pub fn shared_secret(&self, public: &BigUint) -> Result<BigUint, String> {
+        let one = BigUint::one();
+        if *public <= one || *public >= &self.group.prime - &one {
+            return Err(format!("{} isn't a public key for this group", public));
+        }
+        Ok(public.pow_mod(&self.secret, &self.group.prime))
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let textbook = Group::textbook();
+    let alice = Party::new(&textbook, BigUint::from(6u32));
+    let bob = Party::new(&textbook, BigUint::from(15u32));
+    println!("p = 23, g = 5, a = 6, b = 15");
+    println!("Alice sends A = {}, Bob sends B = {}", alice.public, bob.public);
+    println!("Alice gets {}, Bob gets {}\n", alice.shared_secret(&bob.public).unwrap(),
+             bob.shared_secret(&alice.public).unwrap());
+
+    let group = Group::rfc_3526();
+    let mut rng = Rng::from_time();
+    let alice = Party::new(&group, group.secret(&mut rng));
+    let bob = Party::new(&group, group.secret(&mut rng));
+    let alices = alice.shared_secret(&bob.public).unwrap();
+    let bobs = bob.shared_secret(&alice.public).unwrap();
+    println!("With RFC 3526's {}-bit group and random secrets:", group.prime.bits());
+    println!("Alice sends A = {}\n", alice.public);
+    println!("Bob sends B = {}\n", bob.public);
+    println!("They share {}", alices);
+    assert_eq!(alices, bobs);
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::bignum::BigUint;
+    use super::utils::rng::Rng;
+    use super::{Group, Party};
+
+    #[test]
+    fn textbook() {
+        let group = Group::textbook();
+        let alice = Party::new(&group, BigUint::from(6u32));
+        let bob = Party::new(&group, BigUint::from(15u32));
+        assert_eq!(alice.public, BigUint::from(8u32));
+        assert_eq!(bob.public, BigUint::from(19u32));
+        assert_eq!(alice.shared_secret(&bob.public), Ok(BigUint::from(2u32)));
+        assert_eq!(bob.shared_secret(&alice.public), Ok(BigUint::from(2u32)));
+    }
+
+    #[test]
+    fn random_secrets_agree() {
+        let mut rng = Rng::new(80);
+        for group in &[Group::textbook(), Group::rfc_3526()] {
+            for _ in 0..2 {
+                let alice = Party::new(group, group.secret(&mut rng));
+                let bob = Party::new(group, group.secret(&mut rng));
+                let shared = alice.shared_secret(&bob.public).unwrap();
+                assert_eq!(bob.shared_secret(&alice.public).unwrap(), shared);
+                assert!(shared < group.prime);
+            }
+        }
+    }
+
+    #[test]
+    fn rfc_group() {
+        let group = Group::rfc_3526();
+        assert_eq!(group.prime.bits(), 1536);
+        // It's a safe prime, (p - 1) / 2 being prime too, so 2 has order (p - 1) / 2
+        let half = &(&group.prime - &BigUint::one()) / &BigUint::from(2u32);
+        assert_eq!(group.generator.pow_mod(&half, &group.prime), BigUint::one());
+    }
+
+    #[test]
+    fn weak_public_keys() {
+        let group = Group::textbook();
+        let alice = Party::new(&group, BigUint::from(6u32));
+        for &public in &[0u32, 1, 22, 23, 40] {
+            assert!(alice.shared_secret(&BigUint::from(public)).is_err(), "{}", public);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Implements http://rosettacode.org/wiki/Enigma_machine
+//!
+//! The three-rotor Enigma I.  A key press first turns the rotors, then sends a current through
+//! the plugboard, the rotors from right to left, the reflector, back through the rotors from left
+//! to right and the plugboard again, to light up a lamp.  The reflector makes the machine its own
+//! inverse, so the same settings decipher what they encipher, and means no letter is ever
+//! enciphered as itself.
+//!
+//! The rightmost rotor turns with every key press, and the one to its left turns when it passes
+//! its notch, like an odometer.  The middle rotor's own notch turns the left rotor, but the pawl
+//! that does that pushes the middle rotor as well, so the middle rotor turns again on the next key
+//! press: the double step that makes its period 25 instead of 26.
+//!
+//! The ring setting turns a rotor's wiring relative to its letters and notch, so it moves where
+//! the rotor turns over without changing where it starts.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::enigma::{Enigma, I, II, III, REFLECTOR_B};
+//!
+//! let machine = || Enigma::new([&I, &II, &III], &REFLECTOR_B, "AAA", "AAA", "").unwrap();
+//! let mut enigma = machine();
+//! assert_eq!(enigma.encipher("aaaaa"), "BDZGO");
+//! assert_eq!(enigma.positions(), "AAF");
+//! // Enciphering is its own inverse
+//! assert_eq!(machine().encipher("BDZGO"), "AAAAA");
+//! assert!(Enigma::new([&I, &II, &III], &REFLECTOR_B, "AAA", "AAA", "AB AC").is_err());
+//! ```
+
+// A rotor, or a reflector: the letter each letter is wired to, and the letters at which it turns
+// the rotor to its left
+pub struct Wiring {
+    pub name: &'static str,
+    pub wiring: &'static str,
+    pub notches: &'static str,
+}
+
+pub static I: Wiring = Wiring { name: "I", wiring: "EKMFLGDQVZNTOWYHXUSPAIBRCJ", notches: "Q" };
+pub static II: Wiring = Wiring { name: "II", wiring: "AJDKSIRUXBLHWTMCQGZNPYFVOE", notches: "E" };
+pub static III: Wiring =
+    Wiring { name: "III", wiring: "BDFHJLCPRTXVZNYEIWGAKMUSQO", notches: "V" };
+pub static IV: Wiring = Wiring { name: "IV", wiring: "ESOVPZJAYQUIRHXLNFTGKDCMWB", notches: "J" };
+pub static V: Wiring = Wiring { name: "V", wiring: "VZBRGITYUPSDNHLXAWMJQOFECK", notches: "Z" };
+
+pub static REFLECTOR_A: Wiring =
+    Wiring { name: "A", wiring: "EJMZALYXVBWFCRQUONTSPIKHGD", notches: "" };
+pub static REFLECTOR_B: Wiring =
+    Wiring { name: "B", wiring: "YRUHQSLDPXNGOKMIEBFZCWVJAT", notches: "" };
+pub static REFLECTOR_C: Wiring =
+    Wiring { name: "C", wiring: "FVPJIAOYEDRZXWGCTKUQSBNMHL", notches: "" };
+
+// Letters as 0 to 25
+fn index(letter: u8) -> Result<usize, String> {
+    if letter.is_ascii_alphabetic() {
+        Ok((letter.to_ascii_uppercase() - b'A') as usize)
+    } else {
+        Err(format!("{:?} isn't a letter", letter as char))
+    }
+}
+
+fn permutation(wiring: &str) -> Result<[usize; 26], String> {
+    let mut permutation = [0; 26];
+    let mut seen = [false; 26];
+    if wiring.len() != 26 {
+        return Err(format!("{} doesn't wire up 26 letters", wiring));
+    }
+    for (i, letter) in wiring.bytes().enumerate() {
+        let to = index(letter)?;
+        if seen[to] {
+            return Err(format!("{} wires two letters to {}", wiring, letter as char));
+        }
+        seen[to] = true;
+        permutation[i] = to;
+    }
+    Ok(permutation)
+}
+
+struct Rotor {
+    forward: [usize; 26],
+    backward: [usize; 26],
+    notches: Vec<usize>,
+    ring: usize,
+    position: usize,
+}
+
+impl Rotor {
+    fn new(wiring: &Wiring, ring: u8, position: u8) -> Result<Rotor, String> {
+        let forward = permutation(wiring.wiring)?;
+        let mut backward = [0; 26];
+        for (i, &to) in forward.iter().enumerate() {
+            backward[to] = i;
+        }
+        let notches = wiring.notches.bytes().map(index).collect::<Result<_, _>>()?;
+        Ok(Rotor { forward, backward, notches, ring: index(ring)?, position: index(position)? })
+    }
+
+    fn at_notch(&self) -> bool {
+        self.notches.contains(&self.position)
+    }
+
+    fn step(&mut self) {
+        self.position = (self.position + 1) % 26;
+    }
+
+    // Where the current entering at `contact` comes out, through `table`
+    fn pass(&self, table: &[usize; 26], contact: usize) -> usize {
+        let offset = 26 + self.position - self.ring;
+        (table[(contact + offset) % 26] + 26 - offset % 26) % 26
+    }
+}
+
+pub struct Enigma {
+    // From left to right
+    rotors: [Rotor; 3],
+    reflector: [usize; 26],
+    plugboard: [usize; 26],
+}
+
+impl Enigma {
+    // A machine with `rotors` from left to right, `rings` and `positions` as three letters each,
+    // like "AAA", and `plugboard` as the pairs of letters that are swapped, like "AV BS CG"
+    pub fn new(rotors: [&Wiring; 3],
+               reflector: &Wiring,
+               rings: &str,
+               positions: &str,
+               plugboard: &str)
+               -> Result<Enigma, String> {
+        if rings.len() != 3 || positions.len() != 3 {
+            return Err("the rings and the positions are three letters each".to_string());
+        }
+        let (rings, positions) = (rings.as_bytes(), positions.as_bytes());
+        let rotor = |i: usize| Rotor::new(rotors[i], rings[i], positions[i]);
+        let reflector = permutation(reflector.wiring)?;
+        if (0..26).any(|i| reflector[i] == i || reflector[reflector[i]] != i) {
+            return Err("a reflector swaps letters in pairs".to_string());
+        }
+
+        let mut board: [usize; 26] = [0; 26];
+        for (i, to) in board.iter_mut().enumerate() {
+            *to = i;
+        }
+        for pair in plugboard.split_whitespace() {
+            let letters = pair.bytes().map(index).collect::<Result<Vec<_>, _>>()?;
+            match letters[..] {
+                [a, b] if a != b && board[a] == a && board[b] == b => {
+                    board[a] = b;
+                    board[b] = a;
+                }
+                _ => return Err(format!("{} can't be plugged in", pair)),
+            }
+        }
+        Ok(Enigma { rotors: [rotor(0)?, rotor(1)?, rotor(2)?], reflector, plugboard: board })
+    }
+
+    // The letters showing in the windows
+    pub fn positions(&self) -> String {
+        self.rotors.iter().map(|rotor| (b'A' + rotor.position as u8) as char).collect()
+    }
+
+    fn step(&mut self) {
+        if self.rotors[1].at_notch() {
+            self.rotors[0].step();
+            self.rotors[1].step();
+        } else if self.rotors[2].at_notch() {
+            self.rotors[1].step();
+        }
+        self.rotors[2].step();
+    }
+
+    fn press(&mut self, key: usize) -> usize {
+        self.step();
+        let mut contact = self.plugboard[key];
+        for rotor in self.rotors.iter().rev() {
+            contact = rotor.pass(&rotor.forward, contact);
+        }
+        contact = self.reflector[contact];
+        for rotor in &self.rotors {
+            contact = rotor.pass(&rotor.backward, contact);
+        }
+        self.plugboard[contact]
+    }
+
+    // The text's letters enciphered, or deciphered, which is the same, in capitals.  Anything
+    // else is left out, since the machine had no keys for it.
+    pub fn encipher(&mut self, text: &str) -> String {
+        text.bytes()
+            .filter_map(|c| index(c).ok())
+            .map(|key| (b'A' + self.press(key) as u8) as char)
+            .collect()
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let plugboard = "AV BS CG DL FU HZ IN KM OW RX";
+    let machine = || Enigma::new([&II, &IV, &V], &REFLECTOR_B, "BUL", "BLA", plugboard).unwrap();
+    println!("Rotors {} {} {}, reflector {}, rings BUL, start BLA, plugboard {}",
+             II.name, IV.name, V.name, REFLECTOR_B.name, plugboard);
+    let plaintext = "Attack at dawn, the bridges are to be taken intact";
+    let mut enigma = machine();
+    let ciphertext = enigma.encipher(plaintext);
+    println!("Plaintext: {}", plaintext);
+    println!("Encrypted: {}", ciphertext);
+    println!("Rotors now at {}", enigma.positions());
+    println!("Decrypted: {}", machine().encipher(&ciphertext));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Enigma, I, II, III, IV, REFLECTOR_B, REFLECTOR_C, V};
+
+    fn machine(rings: &str, positions: &str, plugboard: &str) -> Enigma {
+        Enigma::new([&I, &II, &III], &REFLECTOR_B, rings, positions, plugboard).unwrap()
+    }
+
+    #[test]
+    fn known_ciphertext() {
+        assert_eq!(machine("AAA", "AAA", "").encipher("AAAAA"), "BDZGO");
+        assert_eq!(machine("BBB", "AAA", "").encipher("AAAAA"), "EWTYX");
+        assert_eq!(machine("AAA", "AAA", "").encipher("Hello, world!"), "ILBDAAMTAZ");
+    }
+
+    #[test]
+    fn double_step() {
+        let mut enigma = machine("AAA", "ADU", "");
+        let mut positions = Vec::new();
+        for _ in 0..4 {
+            enigma.encipher("A");
+            positions.push(enigma.positions());
+        }
+        // The middle rotor turns at E with the left one, and again at once
+        assert_eq!(positions, ["ADV", "AEW", "BFX", "BFY"]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let plugboard = "AV BS CG DL FU HZ IN KM OW RX";
+        let plaintext = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG".repeat(20);
+        let settings = [([&I, &II, &III], &REFLECTOR_B), ([&V, &IV, &II], &REFLECTOR_C)];
+        for &(rotors, reflector) in &settings {
+            let enigma = || Enigma::new(rotors, reflector, "XMV", "QEV", plugboard).unwrap();
+            let ciphertext = enigma().encipher(&plaintext);
+            assert_eq!(enigma().encipher(&ciphertext), plaintext);
+            assert!(ciphertext.bytes().zip(plaintext.bytes()).all(|(c, p)| c != p));
+        }
+        // The plugboard makes a difference
+        assert_ne!(machine("AAA", "AAA", "AB").encipher("AAAAA"), "BDZGO");
+    }
+
+    #[test]
+    fn bad_settings() {
+        let bad = |rings, positions, plugboard| {
+            Enigma::new([&I, &II, &III], &REFLECTOR_B, rings, positions, plugboard).is_err()
+        };
+        assert!(bad("AA", "AAA", ""));
+        assert!(bad("AAA", "A1A", ""));
+        assert!(bad("AAA", "AAA", "AB AC"));
+        assert!(bad("AAA", "AAA", "AA"));
+        assert!(bad("AAA", "AAA", "ABC"));
+        assert!(Enigma::new([&I, &II, &III], &I, "AAA", "AAA", "").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! HMAC (RFC 2104) over any of utils' hashes, and PBKDF2 (RFC 8018) over HMAC
+//!
+//! HMAC keys a hash by hashing the message after the key XORed with 0x36 bytes, and then the
+//! result of that after the key XORed with 0x5c bytes,
+//!
+//! ```text
+//!     HMAC(K, m) = H((K ^ opad) || H((K ^ ipad) || m))
+//! ```
+//!
+//! with the key padded with zeros to the hash's block size, or hashed first if it's longer.
+//!
+//! PBKDF2 stretches a password into a key that takes `iterations` HMACs per block to work out, to
+//! slow down anyone guessing passwords.  Each block of the key is the XOR of
+//!
+//! ```text
+//!     U1 = HMAC(password, salt || the block's number, from 1, as 4 big-endian bytes)
+//!     U2 = HMAC(password, U1)
+//!     ...
+//! ```
+//!
+//! up to U_iterations, and the last block is cut short to make the key as long as asked for.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::hmac_pbkdf2::utils::digest::hex;
+//! use tasks::hmac_pbkdf2::utils::sha256::Sha256;
+//! use tasks::hmac_pbkdf2::{hmac, pbkdf2};
+//!
+//! assert_eq!(hex(&hmac::<Sha256>(b"key", b"The quick brown fox jumps over the lazy dog")),
+//!            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+//! assert_eq!(hex(&pbkdf2::<Sha256>(b"password", b"salt", 1, 32)),
+//!            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b");
+//! ```
+
+// pub, for the hash of the example above
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::digest::{hex, Digest};
+use self::utils::sha256::Sha256;
+
+// An HMAC, fed its message in pieces like the hash it's over
+#[derive(Clone)]
+pub struct Hmac<D: Digest + Clone> {
+    inner: D,
+    outer: D,
+}
+
+impl<D: Digest + Clone> Hmac<D> {
+    pub fn new(key: &[u8]) -> Hmac<D> {
+        let mut padded = vec![0; D::BLOCK_SIZE];
+        if key.len() > D::BLOCK_SIZE {
+            let hashed = D::digest(key);
+            padded[..hashed.as_ref().len()].copy_from_slice(hashed.as_ref());
+        } else {
+            padded[..key.len()].copy_from_slice(key);
+        }
+        let (mut inner, mut outer) = (D::default(), D::default());
+        let pad = |byte: u8| -> Vec<u8> { padded.iter().map(|k| k ^ byte).collect() };
+        inner.update(&pad(0x36));
+        outer.update(&pad(0x5c));
+        Hmac { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> D::Output {
+        let Hmac { inner, mut outer } = self;
+        outer.update(inner.finalize().as_ref());
+        outer.finalize()
+    }
+}
+
+pub fn hmac<D: Digest + Clone>(key: &[u8], message: &[u8]) -> D::Output {
+    let mut hmac = Hmac::<D>::new(key);
+    hmac.update(message);
+    hmac.finalize()
+}
+
+// A key of `length` bytes derived from `password` and `salt`
+pub fn pbkdf2<D: Digest + Clone>(password: &[u8],
+                                 salt: &[u8],
+                                 iterations: u32,
+                                 length: usize)
+                                 -> Vec<u8> {
+    assert!(iterations > 0, "PBKDF2 takes at least one iteration");
+    // The password's HMAC is keyed once, and cloned for each HMAC with it
+    let keyed = Hmac::<D>::new(password);
+    let mut key = Vec::with_capacity(length);
+    let mut block = 1u32;
+    while key.len() < length {
+        let mut hmac = keyed.clone();
+        hmac.update(salt);
+        hmac.update(&block.to_be_bytes());
+        let mut u = hmac.finalize();
+        let mut sum = u.as_ref().to_vec();
+        for _ in 1..iterations {
+            let mut hmac = keyed.clone();
+            hmac.update(u.as_ref());
+            u = hmac.finalize();
+            for (byte, next) in sum.iter_mut().zip(u.as_ref()) {
+                *byte ^= next;
+            }
+        }
+        let wanted = sum.len().min(length - key.len());
+        key.extend_from_slice(&sum[..wanted]);
+        block += 1;
+    }
+    key
+}
+
+#[cfg(not(test))]
+fn main() {
+    let message = b"The quick brown fox jumps over the lazy dog";
+    println!("HMAC-SHA256(\"key\", {:?})", String::from_utf8_lossy(message));
+    println!("    = {}", hex(&hmac::<Sha256>(b"key", message)));
+    for &iterations in &[1, 2, 4096] {
+        println!("PBKDF2-HMAC-SHA256(\"password\", \"salt\", {}, 32)", iterations);
+        println!("    = {}", hex(&pbkdf2::<Sha256>(b"password", b"salt", iterations, 32)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::md5::Md5;
+    use super::utils::sha1::Sha1;
+    use super::{hex, hmac, pbkdf2, Hmac, Sha256};
+
+    #[test]
+    fn rfc_4231() {
+        let long_key = [0xaa; 131];
+        let vectors: [(&[u8], &[u8], &str); 4] = [
+            (&[0x0b; 20], b"Hi There",
+             "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+            (b"Jefe", b"what do ya want for nothing?",
+             "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+            // A key longer than a block, which is hashed first
+            (&long_key, b"Test Using Larger Than Block-Size Key - Hash Key First",
+             "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+            (b"key", b"The quick brown fox jumps over the lazy dog",
+             "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"),
+        ];
+        for &(key, message, mac) in &vectors {
+            assert_eq!(hex(&hmac::<Sha256>(key, message)), mac);
+        }
+    }
+
+    #[test]
+    fn rfc_2202() {
+        assert_eq!(hex(&hmac::<Md5>(&[0x0b; 16], b"Hi There")),
+                   "9294727a3638bb1c13f48ef8158bfc9d");
+        assert_eq!(hex(&hmac::<Md5>(b"Jefe", b"what do ya want for nothing?")),
+                   "750c783e6ab0b503eaa86e310a5db738");
+        assert_eq!(hex(&hmac::<Sha1>(&[0x0b; 20], b"Hi There")),
+                   "b617318655057264e28bc0b6fb378c8ef146be00");
+        assert_eq!(hex(&hmac::<Sha1>(b"Jefe", b"what do ya want for nothing?")),
+                   "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79");
+    }
+
+    #[test]
+    fn streaming() {
+        let mut mac = Hmac::<Sha256>::new(b"Jefe");
+        for piece in ["what ", "do ya want ", "", "for nothing?"].iter() {
+            mac.update(piece.as_bytes());
+        }
+        assert_eq!(mac.finalize(), hmac::<Sha256>(b"Jefe", b"what do ya want for nothing?"));
+    }
+
+    #[test]
+    fn pbkdf2_sha256() {
+        let vectors = [
+            (1, "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"),
+            (2, "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"),
+            (4096, "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"),
+        ];
+        for &(iterations, key) in &vectors {
+            assert_eq!(hex(&pbkdf2::<Sha256>(b"password", b"salt", iterations, 32)), key);
+        }
+        // RFC 7914's, two blocks long
+        assert_eq!(hex(&pbkdf2::<Sha256>(b"passwd", b"salt", 1, 64)),
+                   "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+                    49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783");
+        // Shorter keys are the start of longer ones
+        let long = pbkdf2::<Sha256>(b"password", b"salt", 2, 40);
+        assert_eq!(long[..32], pbkdf2::<Sha256>(b"password", b"salt", 2, 32)[..]);
+        assert_eq!(hex(&pbkdf2::<Sha1>(b"password", b"salt", 2, 20)),
+                   "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957");
+    }
+}
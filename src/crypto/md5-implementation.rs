@@ -0,0 +1,52 @@
+// Implements http://rosettacode.org/wiki/MD5/Implementation
+//
+// MD5 as RFC 1321 specifies it, checked against the RFC's test suite.  The hashing itself is
+// utils::md5::Md5, where hmac_pbkdf2.rs can use it too.
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::digest::{hex, Digest};
+use utils::md5::Md5;
+
+#[cfg(not(test))]
+fn main() {
+    let inputs: [&[u8]; 7] = [
+        b"",
+        b"a",
+        b"abc",
+        b"message digest",
+        b"abcdefghijklmnopqrstuvwxyz",
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+        b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+    ];
+    for input in &inputs {
+        println!("{}  {:?}", hex(&Md5::digest(input)), String::from_utf8_lossy(input));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hex, Digest, Md5};
+
+    #[test]
+    fn rfc_test_suite() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "d41d8cd98f00b204e9800998ecf8427e"),
+            (b"a", "0cc175b9c0f1b6a831c399e269772661"),
+            (b"abc", "900150983cd24fb0d6963f7d28e17f72"),
+            (b"message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (b"abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+             "d174ab98d277d9f5a5611c2c9f419d9f"),
+            (b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+             "57edf4a22be3c955ac49da2e2107b67a"),
+        ];
+        for &(message, digest) in &vectors {
+            assert_eq!(hex(&Md5::digest(message)), digest);
+        }
+        assert_eq!(hex(&Md5::digest(b"The quick brown fox jumps over the lazy dog")),
+                   "9e107d9d372bb6826bd81d3542a419d6");
+    }
+}
@@ -0,0 +1,363 @@
+//! Implements http://rosettacode.org/wiki/One-time_pad
+//!
+//! A pad is a text file of pages, one per line, each of 60 random letters in groups of five.  Lines
+//! starting with # are comments, and a page that has been used is marked by a - in front of it.
+//! Each letter of a message is added to the pad's letter, mod 26, to encrypt it, and subtracted to
+//! decrypt it, so a message takes up as many pages as it needs, from the first one left.  The
+//! ciphertext starts with the number of its first page, so that whoever has the other copy of the
+//! pad knows where to start, and it's refused if any of those pages have been used already on that
+//! side, since reusing a page gives away both messages.
+//!
+//! The letters come from /dev/urandom, the operating system's cryptographically secure generator.
+//!
+//! ```text
+//!     one_time_pad generate FILE PAGES     makes a pad
+//!     one_time_pad list FILE               shows which pages are left
+//!     one_time_pad encrypt FILE TEXT...
+//!     one_time_pad decrypt FILE TEXT...
+//! ```
+//!
+//! With no arguments it shows all that with a pad in the temporary directory.
+//!
+//! ```
+//! # extern crate tasks;
+//! use std::{env, fs, io, process};
+//! use tasks::one_time_pad::{generate, Pad};
+//!
+//! // A pad of nothing but D, which no one should ever use
+//! let path = env::temp_dir().join(format!("one_time_pad_example_{}.1tp", process::id()));
+//! generate(&path, 2, &mut io::repeat(3)).unwrap();
+//! fs::copy(&path, path.with_extension("copy")).unwrap();
+//! let ciphertext = Pad::open(&path).unwrap().encrypt("Hello, world").unwrap();
+//! assert_eq!(ciphertext, "1 KHOOR ZRUOG");
+//! let mut copy = Pad::open(&path.with_extension("copy")).unwrap();
+//! assert_eq!(copy.decrypt(&ciphertext), Ok("HELLOWORLD".to_string()));
+//! // Each page is only used once
+//! assert_eq!(Pad::open(&path).unwrap().unused(), [2]);
+//! assert!(copy.decrypt(&ciphertext).is_err());
+//! fs::remove_file(&path).unwrap();
+//! fs::remove_file(path.with_extension("copy")).unwrap();
+//! ```
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const PAGE_LETTERS: usize = 60;
+const GROUP: usize = 5;
+
+// The letters in groups of five
+fn grouped(letters: &[u8]) -> String {
+    let groups: Vec<String> = letters.chunks(GROUP)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect();
+    groups.join(" ")
+}
+
+fn letters(text: &str) -> Vec<u8> {
+    text.bytes().filter(u8::is_ascii_alphabetic).map(|c| c.to_ascii_uppercase() - b'A').collect()
+}
+
+// Make a pad of `pages` pages at `path`, with letters from `random`.  Bytes from 234 up are
+// thrown away, so that each letter is equally likely.
+pub fn generate<R: Read>(path: &Path, pages: usize, random: &mut R) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!("{} is already there, and pads are never overwritten", path.display()));
+    }
+    let mut text = format!("# One-time pad, {} pages of {} letters\n", pages, PAGE_LETTERS);
+    let mut bytes = [0; 64];
+    for _ in 0..pages {
+        let mut page = Vec::with_capacity(PAGE_LETTERS);
+        while page.len() < PAGE_LETTERS {
+            random.read_exact(&mut bytes).map_err(|e| format!("no random numbers: {}", e))?;
+            page.extend(bytes.iter().filter(|&&byte| byte < 234).map(|byte| b'A' + byte % 26));
+        }
+        page.truncate(PAGE_LETTERS);
+        text.push_str(&grouped(&page));
+        text.push('\n');
+    }
+    fs::write(path, text).map_err(|e| format!("can't write {}: {}", path.display(), e))
+}
+
+pub struct Pad {
+    path: PathBuf,
+    lines: Vec<String>,
+    // Which lines are pages, in order
+    pages: Vec<usize>,
+}
+
+impl Pad {
+    pub fn open(path: &Path) -> Result<Pad, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        let pages = (0..lines.len()).filter(|&i| !lines[i].starts_with('#')).collect();
+        Ok(Pad { path: path.to_path_buf(), lines, pages })
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        let path = &self.path;
+        fs::write(path, text).map_err(|e| format!("can't write {}: {}", path.display(), e))
+    }
+
+    // Whether the page, numbered from 1, has been used, which a page the pad doesn't have hasn't
+    pub fn is_used(&self, page: usize) -> bool {
+        match page.checked_sub(1).and_then(|i| self.pages.get(i)) {
+            Some(&line) => self.lines[line].starts_with('-'),
+            None => false,
+        }
+    }
+
+    // The numbers of the pages that haven't been used, from 1
+    pub fn unused(&self) -> Vec<usize> {
+        (1..=self.pages.len()).filter(|&page| !self.is_used(page)).collect()
+    }
+
+    // Take pages from `first` on for `length` letters, marking them used in the file, or refuse
+    // if any of them was used already
+    fn take(&mut self, first: usize, length: usize) -> Result<Vec<u8>, String> {
+        let count = length.div_ceil(PAGE_LETTERS);
+        if first == 0 || first - 1 + count > self.pages.len() {
+            return Err(format!("the pad doesn't have {} pages from page {}", count, first));
+        }
+        let pages = first..first + count;
+        if let Some(page) = pages.clone().find(|&page| self.is_used(page)) {
+            return Err(format!("page {} has been used already", page));
+        }
+        // A page cut short, by editing the file say, would leave the end of the message without a
+        // key, so it's refused before any page is marked
+        let key: Vec<Vec<u8>> =
+            pages.clone().map(|page| letters(&self.lines[self.pages[page - 1]])).collect();
+        for (page, letters) in pages.clone().zip(&key) {
+            if letters.len() != PAGE_LETTERS {
+                return Err(format!("page {} has {} letters rather than {}", page, letters.len(),
+                                   PAGE_LETTERS));
+            }
+        }
+        for page in pages {
+            self.lines[self.pages[page - 1]].insert(0, '-');
+        }
+        let key = key.concat();
+        self.save()?;
+        Ok(key)
+    }
+
+    // The message's letters encrypted with the first pages left, after the number of the first
+    pub fn encrypt(&mut self, plaintext: &str) -> Result<String, String> {
+        let plaintext = letters(plaintext);
+        if plaintext.is_empty() {
+            return Err("there are no letters to encrypt".to_string());
+        }
+        let first = *self.unused().first().ok_or("the pad has been used up")?;
+        let key = self.take(first, plaintext.len())?;
+        let ciphertext: Vec<u8> = plaintext.iter()
+            .zip(key)
+            .map(|(letter, key)| b'A' + (letter + key) % 26)
+            .collect();
+        Ok(format!("{} {}", first, grouped(&ciphertext)))
+    }
+
+    pub fn decrypt(&mut self, ciphertext: &str) -> Result<String, String> {
+        let mut words = ciphertext.splitn(2, char::is_whitespace);
+        let first = words.next()
+            .and_then(|number| number.parse().ok())
+            .ok_or("the ciphertext doesn't start with its page")?;
+        let ciphertext = letters(words.next().unwrap_or(""));
+        let key = self.take(first, ciphertext.len())?;
+        Ok(ciphertext.iter()
+            .zip(key)
+            .map(|(letter, key)| (b'A' + (letter + 26 - key) % 26) as char)
+            .collect())
+    }
+}
+
+#[cfg(not(test))]
+fn run(args: &[String]) -> Result<String, String> {
+    let usage = "usage: one_time_pad generate FILE PAGES | list FILE | encrypt FILE TEXT... | \
+                 decrypt FILE TEXT...";
+    if args.len() < 2 {
+        return Err(usage.to_string());
+    }
+    let path = Path::new(&args[1]);
+    let text = args[2..].join(" ");
+    match &args[0][..] {
+        "generate" => {
+            let pages = text.parse().map_err(|_| usage.to_string())?;
+            let mut random = fs::File::open("/dev/urandom")
+                .map_err(|e| format!("can't open /dev/urandom: {}", e))?;
+            generate(path, pages, &mut random)?;
+            Ok(format!("Made {} with {} pages", path.display(), pages))
+        }
+        "list" => {
+            let pad = Pad::open(path)?;
+            let unused: Vec<String> = pad.unused().iter().map(|page| page.to_string()).collect();
+            Ok(format!("Pages left: {}", unused.join(" ")))
+        }
+        "encrypt" => Pad::open(path)?.encrypt(&text),
+        "decrypt" => Pad::open(path)?.decrypt(&text),
+        _ => Err(usage.to_string()),
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        match run(&args) {
+            Ok(output) => println!("{}", output),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let dir = std::env::temp_dir();
+    let alice = dir.join(format!("one_time_pad_alice_{}.1tp", std::process::id()));
+    let bob = dir.join(format!("one_time_pad_bob_{}.1tp", std::process::id()));
+    let mut random = fs::File::open("/dev/urandom").unwrap();
+    generate(&alice, 5, &mut random).unwrap();
+    fs::copy(&alice, &bob).unwrap();
+    println!("{}", fs::read_to_string(&alice).unwrap());
+
+    let message = "Meet me at the old bridge at midnight, and bring the plans for the new one";
+    let ciphertext = Pad::open(&alice).unwrap().encrypt(message).unwrap();
+    println!("Alice sends: {}", ciphertext);
+    println!("Bob reads:   {}", Pad::open(&bob).unwrap().decrypt(&ciphertext).unwrap());
+    println!("Pages left:  {:?}", Pad::open(&alice).unwrap().unused());
+    println!("Reading it again: {}", Pad::open(&bob).unwrap().decrypt(&ciphertext).unwrap_err());
+    fs::remove_file(alice).unwrap();
+    fs::remove_file(bob).unwrap();
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{generate, Pad, PAGE_LETTERS};
+    use std::fs;
+    use std::io::{self, Read};
+    use std::path::PathBuf;
+
+    // The in-crate generator in place of /dev/urandom, so the pads are the same on every run
+    struct Random(Rng);
+
+    impl Read for Random {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            for byte in buffer.iter_mut() {
+                *byte = self.0.next_u32() as u8;
+            }
+            Ok(buffer.len())
+        }
+    }
+
+    // A pad in the temporary directory, and a copy of it, removed when dropped
+    struct Pads(PathBuf, PathBuf);
+
+    impl Pads {
+        fn new(name: &str, pages: usize) -> Pads {
+            let path = |side| std::env::temp_dir()
+                .join(format!("one_time_pad_{}_{}_{}.1tp", name, side, std::process::id()));
+            let pads = Pads(path("sender"), path("receiver"));
+            let _ = fs::remove_file(&pads.0);
+            generate(&pads.0, pages, &mut Random(Rng::new(84))).unwrap();
+            fs::copy(&pads.0, &pads.1).unwrap();
+            pads
+        }
+    }
+
+    impl Drop for Pads {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+            let _ = fs::remove_file(&self.1);
+        }
+    }
+
+    #[test]
+    fn pad_file() {
+        let pads = Pads::new("file", 3);
+        let text = fs::read_to_string(&pads.0).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with('#'));
+        for page in &lines[1..] {
+            assert_eq!(page.split(' ').count(), PAGE_LETTERS / 5);
+            assert!(page.split(' ').all(|group| group.len() == 5));
+            assert!(page.bytes().all(|c| c == b' ' || c.is_ascii_uppercase()));
+        }
+        assert_ne!(lines[1], lines[2]);
+        assert_eq!(Pad::open(&pads.0).unwrap().unused(), [1, 2, 3]);
+        // An existing pad isn't overwritten
+        assert!(generate(&pads.0, 1, &mut Random(Rng::new(0))).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let pads = Pads::new("round_trip", 4);
+        let message = "The quick brown fox jumps over the lazy dog, and then it does it again, \
+                       and again";
+        let ciphertext = Pad::open(&pads.0).unwrap().encrypt(message).unwrap();
+        assert!(ciphertext.starts_with("1 "));
+        let plaintext = Pad::open(&pads.1).unwrap().decrypt(&ciphertext).unwrap();
+        assert_eq!(plaintext, "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOGANDTHENITDOESITAGAINANDAGAIN");
+        // 63 letters take two pages, on both sides
+        assert_eq!(Pad::open(&pads.0).unwrap().unused(), [3, 4]);
+        assert_eq!(Pad::open(&pads.1).unwrap().unused(), [3, 4]);
+
+        let reply = Pad::open(&pads.1).unwrap().encrypt("Got it").unwrap();
+        assert!(reply.starts_with("3 "));
+        assert_eq!(Pad::open(&pads.0).unwrap().decrypt(&reply).unwrap(), "GOTIT");
+    }
+
+    #[test]
+    fn no_reuse() {
+        let pads = Pads::new("no_reuse", 2);
+        let ciphertext = Pad::open(&pads.0).unwrap().encrypt("Once only").unwrap();
+        Pad::open(&pads.1).unwrap().decrypt(&ciphertext).unwrap();
+        assert_eq!(Pad::open(&pads.1).unwrap().decrypt(&ciphertext),
+                   Err("page 1 has been used already".to_string()));
+
+        let mut pad = Pad::open(&pads.0).unwrap();
+        pad.encrypt("Last page").unwrap();
+        assert!(pad.encrypt("Nothing left").is_err());
+        let long = "A".repeat(2 * PAGE_LETTERS);
+        assert!(Pad::open(&pads.1).unwrap().encrypt(&long).is_err());
+        assert!(Pad::open(&pads.1).unwrap().decrypt("7 ABCDE").is_err());
+        assert!(Pad::open(&pads.1).unwrap().decrypt("ABCDE").is_err());
+        // Pages the pad doesn't have haven't been used
+        let pad = Pad::open(&pads.1).unwrap();
+        assert!(pad.is_used(1) && !pad.is_used(2));
+        assert!(!pad.is_used(0) && !pad.is_used(3) && !pad.is_used(usize::MAX));
+    }
+
+    #[test]
+    fn short_pages() {
+        let pads = Pads::new("short_pages", 3);
+        // Take some letters off the second page, and blank the third
+        let text = fs::read_to_string(&pads.1).unwrap();
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+        lines[2].truncate(17);
+        lines[3].clear();
+        fs::write(&pads.1, lines.join("\n") + "\n").unwrap();
+
+        let ciphertext = Pad::open(&pads.0).unwrap().encrypt(&"A".repeat(80)).unwrap();
+        assert_eq!(Pad::open(&pads.1).unwrap().decrypt(&ciphertext),
+                   Err(format!("page 2 has 15 letters rather than {}", PAGE_LETTERS)));
+        // Nothing was marked used by refusing
+        let mut pad = Pad::open(&pads.1).unwrap();
+        assert_eq!(pad.unused(), [1, 2, 3]);
+        assert_eq!(pad.decrypt("3 ABCDE"),
+                   Err(format!("page 3 has 0 letters rather than {}", PAGE_LETTERS)));
+        // The first page is whole, so the part of the message on it can be read
+        assert!(pad.decrypt(&ciphertext[..30]).is_ok());
+        assert_eq!(pad.encrypt("Second page"),
+                   Err(format!("page 2 has 15 letters rather than {}", PAGE_LETTERS)));
+    }
+}
@@ -0,0 +1,208 @@
+//! Implements http://rosettacode.org/wiki/Playfair_cipher
+//!
+//! The key square is the 5x5 grid of the key's letters, without repeats, followed by the rest of
+//! the alphabet.  That's 25 letters, so one has to go: either J, which is written as I, or Q,
+//! which is dropped.  The plaintext is split into pairs of letters, with an X put between two of
+//! the same letter that would fall in one pair, and after a last letter left on its own.  Each pair
+//! is then enciphered by where its letters are in the square:
+//!
+//! ```text
+//!     in the same row        each is replaced by the letter to its right
+//!     in the same column     each is replaced by the letter below it
+//!     otherwise              each is replaced by the letter in its own row and the other's column
+//! ```
+//!
+//! wrapping around the square, and deciphering goes the other way.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::playfair::{Playfair, Variant};
+//!
+//! let playfair = Playfair::new("Playfair example", Variant::JAsI);
+//! assert_eq!(playfair.rows()[0], "PLAYF");
+//! let ciphertext = playfair.encrypt("Hide the gold in the tree stump");
+//! assert_eq!(ciphertext, "BM OD ZB XD NA BE KU DM UI XM MO UV IF");
+//! assert_eq!(playfair.decrypt(&ciphertext), "HI DE TH EG OL DI NT HE TR EX ES TU MP");
+//! ```
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    // J is written as I
+    JAsI,
+    // Q is left out
+    NoQ,
+}
+
+pub struct Playfair {
+    square: [u8; 25],
+    // Where each letter is in the square, as row * 5 + column
+    positions: [Option<usize>; 26],
+    variant: Variant,
+}
+
+impl Playfair {
+    pub fn new(key: &str, variant: Variant) -> Playfair {
+        let mut playfair = Playfair { square: [0; 25], positions: [None; 26], variant };
+        let mut filled = 0;
+        for letter in playfair.letters(key).into_iter().chain(b'A'..=b'Z') {
+            let letter = match playfair.letter(letter) {
+                Some(letter) => letter,
+                None => continue,
+            };
+            let index = (letter - b'A') as usize;
+            if playfair.positions[index].is_none() {
+                playfair.positions[index] = Some(filled);
+                playfair.square[filled] = letter;
+                filled += 1;
+            }
+        }
+        playfair
+    }
+
+    // The letter the square uses for `c`, if it has one
+    fn letter(&self, c: u8) -> Option<u8> {
+        match (c.to_ascii_uppercase(), self.variant) {
+            (b'J', Variant::JAsI) => Some(b'I'),
+            (b'Q', Variant::NoQ) => None,
+            (c, _) if c.is_ascii_uppercase() => Some(c),
+            _ => None,
+        }
+    }
+
+    fn letters(&self, text: &str) -> Vec<u8> {
+        text.bytes().filter_map(|c| self.letter(c)).collect()
+    }
+
+    // The rows of the square, for showing it
+    pub fn rows(&self) -> Vec<String> {
+        self.square.chunks(5).map(|row| String::from_utf8_lossy(row).into_owned()).collect()
+    }
+
+    // The plaintext's pairs of letters, with the fillers put in
+    pub fn digraphs(&self, plaintext: &str) -> Vec<[u8; 2]> {
+        let letters = self.letters(plaintext);
+        let mut digraphs = Vec::new();
+        let mut i = 0;
+        while i < letters.len() {
+            let first = letters[i];
+            let second = match letters.get(i + 1) {
+                Some(&second) if second != first => {
+                    i += 1;
+                    second
+                }
+                _ => filler(first),
+            };
+            digraphs.push([first, second]);
+            i += 1;
+        }
+        digraphs
+    }
+
+    fn position(&self, letter: u8) -> (usize, usize) {
+        let position = self.positions[(letter - b'A') as usize].unwrap();
+        (position / 5, position % 5)
+    }
+
+    // The pair `digraph` moved `step` places through its row or column, 1 to encipher and 4 to
+    // decipher
+    fn transform(&self, digraph: [u8; 2], step: usize) -> [u8; 2] {
+        let (row_a, column_a) = self.position(digraph[0]);
+        let (row_b, column_b) = self.position(digraph[1]);
+        let at = |row: usize, column: usize| self.square[row * 5 + column];
+        if row_a == row_b {
+            [at(row_a, (column_a + step) % 5), at(row_b, (column_b + step) % 5)]
+        } else if column_a == column_b {
+            [at((row_a + step) % 5, column_a), at((row_b + step) % 5, column_b)]
+        } else {
+            [at(row_a, column_b), at(row_b, column_a)]
+        }
+    }
+
+    // The ciphertext, in pairs of letters with a space between each
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        let digraphs: Vec<[u8; 2]> = self.digraphs(plaintext)
+            .into_iter()
+            .map(|digraph| self.transform(digraph, 1))
+            .collect();
+        spaced(&digraphs)
+    }
+
+    // The plaintext, fillers and all, the same way
+    pub fn decrypt(&self, ciphertext: &str) -> String {
+        let letters = self.letters(ciphertext);
+        let digraphs: Vec<[u8; 2]> = letters.chunks(2)
+            .map(|pair| {
+                let second = pair.get(1).cloned().unwrap_or_else(|| filler(pair[0]));
+                self.transform([pair[0], second], 4)
+            })
+            .collect();
+        spaced(&digraphs)
+    }
+}
+
+// The letter to put after `letter` to keep it out of a pair with itself
+fn filler(letter: u8) -> u8 {
+    if letter == b'X' { b'Z' } else { b'X' }
+}
+
+fn spaced(digraphs: &[[u8; 2]]) -> String {
+    let pairs: Vec<String> = digraphs.iter()
+        .map(|digraph| String::from_utf8_lossy(digraph).into_owned())
+        .collect();
+    pairs.join(" ")
+}
+
+#[cfg(not(test))]
+fn main() {
+    let playfair = Playfair::new("Playfair example", Variant::JAsI);
+    for row in playfair.rows() {
+        println!("{}", row);
+    }
+    let plaintext = "Hide the gold in...the TREESTUMP!!!";
+    let ciphertext = playfair.encrypt(plaintext);
+    println!("\nPlaintext: {}", plaintext);
+    println!("Encrypted: {}", ciphertext);
+    println!("Decrypted: {}", playfair.decrypt(&ciphertext));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Playfair, Variant};
+
+    #[test]
+    fn key_square() {
+        let playfair = Playfair::new("Playfair example", Variant::JAsI);
+        assert_eq!(playfair.rows(), ["PLAYF", "IREXM", "BCDGH", "KNOQS", "TUVWZ"]);
+        let playfair = Playfair::new("Playfair example", Variant::NoQ);
+        assert_eq!(playfair.rows(), ["PLAYF", "IREXM", "BCDGH", "JKNOS", "TUVWZ"]);
+    }
+
+    #[test]
+    fn digraphs() {
+        let playfair = Playfair::new("", Variant::JAsI);
+        let pairs = |text| -> Vec<String> {
+            let digraphs = playfair.digraphs(text);
+            digraphs.iter().map(|pair| String::from_utf8_lossy(pair).into_owned()).collect()
+        };
+        assert_eq!(pairs("tree stump"), ["TR", "EX", "ES", "TU", "MP"]);
+        assert_eq!(pairs("hello"), ["HE", "LX", "LO"]);
+        assert_eq!(pairs("odd"), ["OD", "DX"]);
+        assert_eq!(pairs("axx"), ["AX", "XZ"]);
+        assert_eq!(pairs("jig-saw"), ["IX", "IG", "SA", "WX"]);
+    }
+
+    #[test]
+    fn task() {
+        let playfair = Playfair::new("Playfair example", Variant::JAsI);
+        let ciphertext = playfair.encrypt("Hide the gold in...the TREESTUMP!!!");
+        assert_eq!(ciphertext, "BM OD ZB XD NA BE KU DM UI XM MO UV IF");
+        assert_eq!(playfair.decrypt(&ciphertext), "HI DE TH EG OL DI NT HE TR EX ES TU MP");
+    }
+
+    #[test]
+    fn without_q() {
+        let playfair = Playfair::new("Quick brown fox", Variant::NoQ);
+        let ciphertext = playfair.encrypt("Just a quiet jog together");
+        assert_eq!(playfair.decrypt(&ciphertext), "JU ST AU IE TJ OG TO GE TH ER");
+    }
+}
@@ -0,0 +1,117 @@
+//! Implements http://rosettacode.org/wiki/RC4
+//!
+//! The key-scheduling algorithm shuffles a permutation of the 256 bytes by the key, and then the
+//! pseudo-random generation algorithm keeps swapping its bytes to give the keystream, which Rc4
+//! iterates over.  Encrypting XORs the keystream into the data, so decrypting is the same thing.
+//! RC4 is long broken, and only here for the task.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::rc4::{decrypt, encrypt};
+//!
+//! let ciphertext = encrypt(b"Key", b"Plaintext");
+//! assert_eq!(ciphertext, [0xbb, 0xf3, 0x16, 0xe8, 0xd9, 0x40, 0xaf, 0x0a, 0xd3]);
+//! assert_eq!(decrypt(b"Key", &ciphertext), b"Plaintext");
+//! ```
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+pub struct Rc4 {
+    s: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl Rc4 {
+    // The keystream for `key`, which has to be from 1 to 256 bytes long
+    pub fn new(key: &[u8]) -> Rc4 {
+        assert!(!key.is_empty() && key.len() <= 256, "an RC4 key is 1 to 256 bytes long");
+        let mut s = [0u8; 256];
+        for (i, byte) in s.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut j = 0u8;
+        for i in 0..256 {
+            j = j.wrapping_add(s[i]).wrapping_add(key[i % key.len()]);
+            s.swap(i, j as usize);
+        }
+        Rc4 { s, i: 0, j: 0 }
+    }
+}
+
+impl Iterator for Rc4 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.s[self.i as usize]);
+        self.s.swap(self.i as usize, self.j as usize);
+        Some(self.s[self.s[self.i as usize].wrapping_add(self.s[self.j as usize]) as usize])
+    }
+}
+
+// `data` encrypted with `key`, or decrypted, which is the same
+pub fn apply(key: &[u8], data: &[u8]) -> Vec<u8> {
+    data.iter().zip(Rc4::new(key)).map(|(byte, key_byte)| byte ^ key_byte).collect()
+}
+
+pub fn encrypt(key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    apply(key, plaintext)
+}
+
+pub fn decrypt(key: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    apply(key, ciphertext)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+#[cfg(not(test))]
+fn main() {
+    let examples = [("Key", "Plaintext"), ("Wiki", "pedia"), ("Secret", "Attack at dawn")];
+    for &(key, plaintext) in &examples {
+        let ciphertext = encrypt(key.as_bytes(), plaintext.as_bytes());
+        println!("{:6}  {:14}  {}", key, plaintext, hex(&ciphertext));
+        assert_eq!(decrypt(key.as_bytes(), &ciphertext), plaintext.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::property;
+    use super::{apply, decrypt, encrypt, hex, Rc4};
+
+    #[test]
+    fn test_vectors() {
+        let keystream: Vec<u8> = Rc4::new(b"Key").take(10).collect();
+        assert_eq!(hex(&keystream), "EB9F7781B734CA72A719");
+        assert_eq!(hex(&encrypt(b"Key", b"Plaintext")), "BBF316E8D940AF0AD3");
+        assert_eq!(hex(&encrypt(b"Wiki", b"pedia")), "1021BF0420");
+        assert_eq!(hex(&encrypt(b"Secret", b"Attack at dawn")), "45A01F645FC35B383552544B9BF5");
+        let ciphertext = [0xbb, 0xf3, 0x16, 0xe8, 0xd9, 0x40, 0xaf, 0x0a, 0xd3];
+        assert_eq!(decrypt(b"Key", &ciphertext), b"Plaintext");
+    }
+
+    #[test]
+    fn rfc_6229() {
+        // The first bytes of the keystream for the 40-bit key 0102030405
+        let keystream: Vec<u8> = Rc4::new(&[1, 2, 3, 4, 5]).take(16).collect();
+        assert_eq!(hex(&keystream), "B2396305F03DC027CCC3524A0A1118A8");
+    }
+
+    #[test]
+    fn roundtrips() {
+        property::roundtrips(|data: &Vec<u8>| apply(b"secret", data),
+                             |data| apply(b"secret", data));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_key() {
+        Rc4::new(b"");
+    }
+}
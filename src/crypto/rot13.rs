@@ -1,5 +1,10 @@
 // Implements http://rosettacode.org/wiki/Rot-13
 
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
 fn rot13 (string: &str) -> String {
     fn rot13u8 (c: char) -> char {
         let d = c as u8;
@@ -37,3 +42,8 @@ fn test_coherence() {
         decoded == s
     }));
 }
+
+#[test]
+fn test_any_coherence() {
+    utils::property::roundtrips(|s: &String| rot13(s), |s| rot13(s));
+}
@@ -0,0 +1,256 @@
+//! Implements http://rosettacode.org/wiki/RSA_code
+//!
+//! Textbook RSA, with the task's key, over utils::bignum.  A message is encrypted as
+//! message^e mod n and decrypted as ciphertext^d mod n, which works for any number below n.  Text
+//! is turned into numbers by reading its bytes as the digits of a number in base 256.  One number
+//! only holds as many bytes as fit below n, so longer text is split into blocks, each with a 1 byte
+//! in front of it, so that a block starting with zero bytes comes back with them.
+//!
+//! generate_keys() makes a key pair of its own from two random primes p and q, which
+//! miller_rabin_primality_test.rs finds: n = p q, e = 65537, and d is e's inverse modulo
+//! (p - 1)(q - 1), so that by Euler's theorem m^(e d) = m mod n.
+//!
+//! There's no padding scheme such as OAEP, so this is only for the task, not for keeping secrets.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::rsa::task_keys;
+//!
+//! let (public, private) = task_keys();
+//! let blocks = public.encrypt(b"Rosetta Code");
+//! assert_ne!(blocks, private.encrypt(b"Rosetta Code"));
+//! assert_eq!(private.decrypt(&blocks), Ok(b"Rosetta Code".to_vec()));
+//! // Decrypting with the wrong key gives blocks of nonsense
+//! assert!(public.decrypt(&blocks).is_err());
+//! ```
+
+#[allow(dead_code)]
+#[path = "../math/miller_rabin_primality_test.rs"]
+mod miller_rabin_primality_test;
+
+use self::miller_rabin_primality_test::is_probable_prime;
+use self::miller_rabin_primality_test::utils;
+use self::utils::bignum::{BigInt, BigUint};
+use self::utils::rng::Rng;
+
+// Rounds of Miller-Rabin for each prime, leaving a chance of at most 4^-40 that one isn't
+const ROUNDS: usize = 40;
+
+pub struct Key {
+    pub modulus: BigUint,
+    pub exponent: BigUint,
+}
+
+// The task's key pair
+pub fn task_keys() -> (Key, Key) {
+    let modulus: BigUint = "9516311845790656153499716760847001433441357".parse().unwrap();
+    let public = Key { modulus: modulus.clone(), exponent: BigUint::from(65537u32) };
+    let private = Key {
+        modulus,
+        exponent: "5617843187844953170308463622230283376298685".parse().unwrap(),
+    };
+    (public, private)
+}
+
+// A key pair of its own, with a modulus of `bits` bits
+pub fn generate_keys(bits: usize, rng: &mut Rng) -> (Key, Key) {
+    // Enough for a block to hold a byte of text
+    assert!(bits >= 32, "a key of {} bits is too small", bits);
+    let exponent = BigUint::from(65537u32);
+    loop {
+        let p = random_prime(bits / 2, &exponent, rng);
+        let q = random_prime(bits - bits / 2, &exponent, rng);
+        if p == q {
+            continue;
+        }
+        let one = BigUint::one();
+        let totient = &(&p - &one) * &(&q - &one);
+        // p - 1 and q - 1 aren't multiples of the prime e, so e has an inverse
+        let private = inverse(&exponent, &totient).unwrap();
+        let modulus = &p * &q;
+        return (Key { modulus: modulus.clone(), exponent }, Key { modulus, exponent: private });
+    }
+}
+
+// A random prime of exactly `bits` bits, from 3 2^(bits - 2) up, so that two of them multiply
+// to a number of exactly twice that many bits, and with p - 1 not a multiple of `exponent`
+fn random_prime(bits: usize, exponent: &BigUint, rng: &mut Rng) -> BigUint {
+    let one = BigUint::one();
+    let two = BigUint::from(2u32);
+    let base = &BigUint::from(3u32) * &two.pow(bits as u32 - 2);
+    let range = two.pow(bits as u32 - 3);
+    loop {
+        let bytes: Vec<u8> = (0..bits / 8 + 8).map(|_| rng.next_u32() as u8).collect();
+        let odd = &(&two * &(&BigUint::from_bytes_be(&bytes) % &range)) + &one;
+        let candidate = &base + &odd;
+        if !(&(&candidate - &one) % exponent).is_zero() &&
+           is_probable_prime(&candidate, ROUNDS, rng) {
+            return candidate;
+        }
+    }
+}
+
+// The inverse of a modulo m, by the extended Euclidean algorithm, if they're coprime
+fn inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let m = BigInt::from(m.clone());
+    let (mut r0, mut r1) = (m.clone(), BigInt::from(a.clone()));
+    let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+    while !r1.is_zero() {
+        let (quotient, remainder) = r0.div_rem(&r1);
+        let t2 = &t0 - &(&quotient * &t1);
+        r0 = r1;
+        r1 = remainder;
+        t0 = t1;
+        t1 = t2;
+    }
+    if r0 != BigInt::one() {
+        return None;
+    }
+    let t = if t0.is_negative() { &t0 + &m } else { t0 };
+    Some(t.magnitude().clone())
+}
+
+impl Key {
+    // `number`, which has to be below the modulus, encrypted or decrypted with the key
+    pub fn apply(&self, number: &BigUint) -> BigUint {
+        assert!(*number < self.modulus, "the number is too big for the key");
+        number.pow_mod(&self.exponent, &self.modulus)
+    }
+
+    // How many bytes of text a block holds, leaving room for the 1 in front
+    fn block_size(&self) -> usize {
+        let size = (self.modulus.bits() - 1) / 8;
+        assert!(size > 1, "the key is too small for any text");
+        size - 1
+    }
+
+    pub fn encrypt(&self, text: &[u8]) -> Vec<BigUint> {
+        text.chunks(self.block_size())
+            .map(|chunk| {
+                let mut block = vec![1];
+                block.extend_from_slice(chunk);
+                self.apply(&BigUint::from_bytes_be(&block))
+            })
+            .collect()
+    }
+
+    pub fn decrypt(&self, blocks: &[BigUint]) -> Result<Vec<u8>, String> {
+        let mut text = Vec::new();
+        for block in blocks {
+            if *block >= self.modulus {
+                return Err(format!("{} is too big to be a block of this key's", block));
+            }
+            let bytes = self.apply(block).to_bytes_be();
+            match bytes.split_first() {
+                Some((&1, chunk)) => text.extend_from_slice(chunk),
+                _ => return Err(format!("{} isn't a block of this key's", block)),
+            }
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (public, private) = task_keys();
+    let text = "Rosetta Code";
+    let number = BigUint::from_bytes_be(text.as_bytes());
+    let encrypted = public.apply(&number);
+    let decrypted = private.apply(&encrypted);
+    println!("Text:      {}", text);
+    println!("Number:    {}", number);
+    println!("Encrypted: {}", encrypted);
+    println!("Decrypted: {}", decrypted);
+    println!("As text:   {}", String::from_utf8_lossy(&decrypted.to_bytes_be()));
+
+    let (public, private) = generate_keys(256, &mut Rng::from_time());
+    println!("\nWith a key of its own, n = {}, d = {}", public.modulus, private.exponent);
+    let encrypted = public.encrypt(text.as_bytes());
+    let decrypted = private.decrypt(&encrypted).unwrap();
+    println!("Encrypted: {}", encrypted[0]);
+    println!("As text:   {}", String::from_utf8_lossy(&decrypted));
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::bignum::BigUint;
+    use super::utils::rng::Rng;
+    use super::{generate_keys, inverse, task_keys};
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn task() {
+        let (public, private) = task_keys();
+        let number = BigUint::from_bytes_be(b"Rosetta Code");
+        assert_eq!(number, big("25512506514985639724585018469"));
+        let encrypted = public.apply(&number);
+        assert_eq!(encrypted, big("916709442744356653386978770799029131264344"));
+        assert_eq!(private.apply(&encrypted).to_bytes_be(), b"Rosetta Code");
+    }
+
+    #[test]
+    fn blocks() {
+        let (public, private) = task_keys();
+        let text = b"\0\0The quick brown fox jumps over the lazy dog, which doesn't fit in a block";
+        let blocks = public.encrypt(text);
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(private.decrypt(&blocks).unwrap(), &text[..]);
+        assert_eq!(public.encrypt(b"Rosetta Code"),
+                   [big("4578440774380197288537855929195084179511612")]);
+        assert!(public.encrypt(b"").is_empty());
+        // Decrypting with the wrong exponent doesn't give a block
+        assert!(public.decrypt(&blocks).is_err());
+    }
+
+    #[test]
+    fn generated() {
+        let mut rng = Rng::new(79);
+        for &bits in &[32, 64, 127, 256] {
+            let (public, private) = generate_keys(bits, &mut rng);
+            assert_eq!(public.modulus.bits(), bits);
+            assert_eq!(public.modulus, private.modulus);
+            let text = b"The quick brown fox jumps over the lazy dog";
+            assert_eq!(private.decrypt(&public.encrypt(text)).unwrap(), &text[..]);
+            // Either key undoes the other
+            let number = BigUint::from(0xdead_beef_u64);
+            assert_eq!(public.apply(&private.apply(&number)), number);
+        }
+        // Different random primes make different keys
+        let (first, _) = generate_keys(64, &mut rng);
+        let (second, _) = generate_keys(64, &mut rng);
+        assert!(first.modulus != second.modulus);
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(inverse(&BigUint::from(3u32), &BigUint::from(11u32)), Some(BigUint::from(4u32)));
+        assert_eq!(inverse(&BigUint::from(6u32), &BigUint::from(9u32)), None);
+        // Modulo the prime 2^127 - 1, where everything but 0 has one
+        let m = &BigUint::from(2u32).pow(127) - &BigUint::one();
+        let e = BigUint::from(65537u32);
+        assert_eq!(&(&e * &inverse(&e, &m).unwrap()) % &m, BigUint::one());
+        assert_eq!(inverse(&BigUint::zero(), &m), None);
+    }
+
+    #[test]
+    fn too_big_to_decrypt() {
+        // Decrypting takes blocks from anywhere, so it says no rather than panicking
+        let (_, private) = task_keys();
+        for n in &["9516311845790656153499716760847001433441357",
+                   "9516311845790656153499716760847001433441358"] {
+            let blocks = [big("4578440774380197288537855929195084179511612"), big(n)];
+            assert_eq!(private.decrypt(&blocks),
+                       Err(format!("{} is too big to be a block of this key's", n)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the number is too big for the key")]
+    fn too_big() {
+        let (public, _) = task_keys();
+        public.apply(&big("9516311845790656153499716760847001433441357"));
+    }
+}
@@ -0,0 +1,50 @@
+// Implements http://rosettacode.org/wiki/SHA-1
+//
+// SHA-1 as FIPS 180-4 specifies it, checked against the standard's examples and a few others.
+// The hashing itself is utils::sha1::Sha1, where hmac_pbkdf2.rs can use it too.
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::digest::{hex, Digest};
+use utils::sha1::Sha1;
+
+#[cfg(not(test))]
+fn main() {
+    println!("{}", hex(&Sha1::digest(b"Rosetta Code")));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hex, Digest, Sha1};
+
+    #[test]
+    fn known_sha1s() {
+        let vectors: [(&[u8], &str); 5] = [
+            (b"Rosetta Code", "48c98f7e5a6e736d790ab740dfc3f51a61abe2b5"),
+            (b"His money is twice tainted: 'taint yours and 'taint mine.",
+             "597f6a540010f94c15d71806a99a2c8710e747bd"),
+            (b"The quick brown fox jumps over the lazy dog",
+             "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12"),
+            (b"The quick brown fox jumps over the lazy cog",
+             "de9f2c7fd25e1b3afad3e85a0bd17d9b100db4b3"),
+            (b"", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+        ];
+        for &(message, digest) in &vectors {
+            assert_eq!(hex(&Sha1::digest(message)), digest);
+        }
+    }
+
+    #[test]
+    fn fips_vectors() {
+        assert_eq!(hex(&Sha1::digest(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(hex(&Sha1::digest(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+                   "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+        let mut hasher = Sha1::default();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(hex(&hasher.finalize()), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+}
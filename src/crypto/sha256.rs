@@ -0,0 +1,52 @@
+// Implements http://rosettacode.org/wiki/SHA-256
+//
+// SHA-256 as FIPS 180-4 specifies it, checked against NIST's examples.  The hashing itself is
+// utils::sha256::Sha256, where hmac_pbkdf2.rs can use it too.
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::digest::{hex, Digest};
+use utils::sha256::Sha256;
+
+#[cfg(not(test))]
+fn main() {
+    println!("{}", hex(&Sha256::digest(b"Rosetta code")));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hex, Digest, Sha256};
+
+    #[test]
+    fn test_rosetta() {
+        assert_eq!(hex(&Sha256::digest(b"Rosetta code")),
+                   "764faf5c61ac315f1497f9dfa542713965b785e5cc2f707d6468d7d1124cdfcf");
+    }
+
+    #[test]
+    fn nist_vectors() {
+        let vectors: [(&[u8], &str); 4] = [
+            (b"", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            // Two blocks, since the padding doesn't fit after 56 bytes
+            (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+             "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+            (b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklm\
+               nopqrlmnopqrsmnopqrstnopqrstu",
+             "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"),
+        ];
+        for &(message, digest) in &vectors {
+            let text = String::from_utf8_lossy(message);
+            assert_eq!(hex(&Sha256::digest(message)), digest, "{:?}", text);
+        }
+        // A million a's
+        let mut hasher = Sha256::default();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(hex(&hasher.finalize()),
+                   "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+    }
+}
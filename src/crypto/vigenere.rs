@@ -0,0 +1,201 @@
+//! Implements http://rosettacode.org/wiki/Vigen%C3%A8re_cipher
+//! and, with vigenere_cryptanalysis.rs, which uses this file's crack(),
+//! http://rosettacode.org/wiki/Vigen%C3%A8re_cipher/Cryptanalysis
+//!
+//! As the task has it, only letters are enciphered, upper and lower case alike, and everything
+//! else is dropped, so the ciphertext is all capitals, and so is the decrypted plaintext.  Each
+//! letter is shifted by the next letter of the key, A for no shift, going round the key again and
+//! again.
+//!
+//! Cracking a ciphertext takes two steps.  Every key length's letters are enciphered with one
+//! shift each, so for the right length each column of the text (every length-th letter) keeps
+//! English's uneven letter frequencies, which its index of coincidence, the chance that two of its
+//! letters picked at random are the same, shows: about 0.066 for English, against 0.038 for
+//! letters picked at random.  Then each column's shift is the one that makes its frequencies
+//! closest to English's, by the chi-squared statistic.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::vigenere::{crack, decrypt, encrypt, CIPHERTEXT};
+//!
+//! let plaintext = "Beware the Jabberwock, my son! The jaws that bite, the claws that catch!";
+//! let ciphertext = encrypt(plaintext, "VIGENERECIPHER");
+//! assert_eq!(ciphertext, "WMCEEIKLGRPIFVMEUGXQPWQVIOIAVEYXUEKFKBTALVXTGAFXYEVKPAGY");
+//! assert_eq!(decrypt(&ciphertext, "VIGENERECIPHER"),
+//!            "BEWARETHEJABBERWOCKMYSONTHEJAWSTHATBITETHECLAWSTHATCATCH");
+//! let (key, _) = crack(CIPHERTEXT, 20);
+//! assert_eq!(key, "THECHESHIRECAT");
+//! ```
+
+// How often each letter turns up in English text
+static ENGLISH: [f64; 26] = [
+    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153,
+    0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056,
+    0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+];
+
+// The letters of `text`, from 0 for A to 25 for Z
+fn letters(text: &str) -> Vec<u8> {
+    text.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase() - b'A')
+        .collect()
+}
+
+fn text(letters: &[u8]) -> String {
+    letters.iter().map(|&letter| (letter + b'A') as char).collect()
+}
+
+fn shift(text: &str, key: &str, decrypting: bool) -> String {
+    let key = letters(key);
+    assert!(!key.is_empty(), "the key has no letters");
+    let shifted: Vec<u8> = letters(text).iter().zip(key.iter().cycle())
+        .map(|(&letter, &k)| if decrypting { (letter + 26 - k) % 26 } else { (letter + k) % 26 })
+        .collect();
+    self::text(&shifted)
+}
+
+pub fn encrypt(plaintext: &str, key: &str) -> String {
+    shift(plaintext, key, false)
+}
+
+pub fn decrypt(ciphertext: &str, key: &str) -> String {
+    shift(ciphertext, key, true)
+}
+
+fn counts(letters: &[u8]) -> [usize; 26] {
+    let mut counts = [0; 26];
+    for &letter in letters {
+        counts[letter as usize] += 1;
+    }
+    counts
+}
+
+pub fn index_of_coincidence(letters: &[u8]) -> f64 {
+    let n = letters.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let pairs: usize = counts(letters).iter().map(|&count| count * count.saturating_sub(1)).sum();
+    pairs as f64 / (n * (n - 1)) as f64
+}
+
+// Every `length`-th letter, starting from each of the first `length`
+fn columns(letters: &[u8], length: usize) -> Vec<Vec<u8>> {
+    (0..length).map(|start| letters[start..].iter().step_by(length).cloned().collect()).collect()
+}
+
+// The likeliest length of the key `letters` were enciphered with, up to `longest`: the shortest
+// whose columns have an index of coincidence nearly as high as any, since multiples of the
+// right length do as well as it does
+pub fn key_length(letters: &[u8], longest: usize) -> usize {
+    let longest = longest.min(letters.len() / 2).max(1);
+    let indices: Vec<f64> = (1..=longest)
+        .map(|length| {
+            let columns = columns(letters, length);
+            columns.iter().map(|column| index_of_coincidence(column)).sum::<f64>() / length as f64
+        })
+        .collect();
+    let best = indices.iter().cloned().fold(0.0, f64::max);
+    indices.iter().position(|&index| index >= 0.9 * best).unwrap() + 1
+}
+
+// How far the frequencies of `letters`, shifted back by `shift`, are from English's
+fn chi_squared(letters: &[u8], shift: u8) -> f64 {
+    let counts = counts(letters);
+    let n = letters.len() as f64;
+    (0..26)
+        .map(|letter| {
+            let observed = counts[(letter + shift as usize) % 26] as f64;
+            let expected = n * ENGLISH[letter];
+            (observed - expected) * (observed - expected) / expected
+        })
+        .sum()
+}
+
+// The likeliest key for `ciphertext`, with keys of up to `longest` letters, and the plaintext it
+// gives
+pub fn crack(ciphertext: &str, longest: usize) -> (String, String) {
+    let letters = letters(ciphertext);
+    let length = key_length(&letters, longest);
+    let key: Vec<u8> = columns(&letters, length)
+        .iter()
+        .map(|column| {
+            let score = |shift: &u8| chi_squared(column, *shift);
+            (0..26).min_by(|a, b| score(a).partial_cmp(&score(b)).unwrap()).unwrap()
+        })
+        .collect();
+    let key = text(&key);
+    let plaintext = decrypt(ciphertext, &key);
+    (key, plaintext)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let plaintext = "Beware the Jabberwock, my son! The jaws that bite, the claws that catch!";
+    let key = "VIGENERECIPHER";
+    let ciphertext = encrypt(plaintext, key);
+    println!("Original:  {}", plaintext);
+    println!("Encrypted: {}", ciphertext);
+    println!("Decrypted: {}", decrypt(&ciphertext, key));
+}
+
+// The task's ciphertext for the cryptanalysis
+pub static CIPHERTEXT: &str = "\
+MOMUD EKAPV TQEFM OEVHP AJMII CDCTI FGYAG JSPXY ALUYM NSMYH VUXJE LEPXJ FXGCM JHKDZ RYICU HYPUS
+PGIGM OIYHF WHTCQ KMLRD ITLXZ LJFVQ GHOLW CUHLO MDSOE KTALU VYLNZ RFGBX PHVGA LWQIS FGRPH JOOFW
+GUBYI LAPLA LCAFA AMKLG CETDW VOELJ IKGJB XPHVG ALWQC SNWBU BYHCU HKOCE XJEYK BQKVY KIIEH GRLGH
+XEOLW AWFOJ ILOVV RHPKD WIHKN ATUHN VRYAQ DIVHX FHRZV QWMWV LGSHN NLVZS JLAKI FHXUF XJLXM TBLQV
+RXXHR FZXGV LRAJI EXPRV OSMNP KEPDT LPRWM JAZPK LQUZA ALGZX GVLKL GJTUI ITDSU REZXJ ERXZS HMPST
+MTEOE PAPJH SMFNB YVQUZ AALGA YDNMP AQOWT UHDBV TSMUE UIMVH QGVRW AEFSP EMPVE PKXZY WLKJA GWALT
+VYYOB YIXOK IHPDS EVLEV RVSGB JOGYW FHKBL GLXYA MVKIS KIEHY IMAPX UOISK PVAGN MZHPW TTZPV XFCCD
+TUHJH WLAPF YULTB UXJLN SIJVV YOVDJ SOLXG TGRVO SFRII CTMKO JFCQF KTINQ BWVHG TENLH HOGCS PSFPV
+GJOKM SIFPR ZPAAS ATPTZ FTPPD PORRF TAXZP KALQA WMIUD BWNCT LEFKO ZQDLX BUXJL ASIMR PNMBF ZCYLV
+WAPVF QRHZV ZGZEF KBYIO OFXYE VOWGB BXVCB XBAWG LQKCM ICRRX MACUO IKHQU AJEGL OIJHH XPVZW JEWBA
+FWAML ZZRXJ EKAHV FASMU LVVUT TGK";
+
+#[cfg(test)]
+mod test {
+    use super::{crack, decrypt, encrypt, index_of_coincidence, key_length, letters, CIPHERTEXT};
+
+    #[test]
+    fn cipher() {
+        let plaintext = "Beware the Jabberwock, my son! The jaws that bite, the claws that catch!";
+        let ciphertext = encrypt(plaintext, "VIGENERECIPHER");
+        assert_eq!(ciphertext, "WMCEEIKLGRPIFVMEUGXQPWQVIOIAVEYXUEKFKBTALVXTGAFXYEVKPAGY");
+        assert_eq!(decrypt(&ciphertext, "vigenerecipher"),
+                   "BEWARETHEJABBERWOCKMYSONTHEJAWSTHATBITETHECLAWSTHATCATCH");
+        // Only the key's letters count
+        assert_eq!(encrypt("attack at dawn", "L-E-M-O-N"), "LXFOPVEFRNHR");
+    }
+
+    #[test]
+    fn coincidence() {
+        assert_eq!(index_of_coincidence(&letters("aaaa")), 1.0);
+        assert_eq!(index_of_coincidence(&letters("abcd")), 0.0);
+        let english = letters(&decrypt(CIPHERTEXT, "THECHESHIRECAT"));
+        let ciphered = letters(CIPHERTEXT);
+        assert!(index_of_coincidence(&english) > 0.06);
+        assert!(index_of_coincidence(&ciphered) < 0.045);
+        assert_eq!(key_length(&ciphered, 40), 14);
+    }
+
+    #[test]
+    fn task() {
+        let (key, plaintext) = crack(CIPHERTEXT, 26);
+        assert_eq!(key, "THECHESHIRECAT");
+        assert!(plaintext.starts_with("THISWASTHEPOEMTHATALICEREADJABBERWOCKYTWASBRILLIG"));
+    }
+
+    #[test]
+    fn own_ciphertext() {
+        let plaintext = "It was the best of times, it was the worst of times, it was the age of \
+                         wisdom, it was the age of foolishness, it was the epoch of belief, it \
+                         was the epoch of incredulity, it was the season of Light, it was the \
+                         season of Darkness, it was the spring of hope, it was the winter of \
+                         despair, we had everything before us, we had nothing before us";
+        let (key, cracked) = crack(&encrypt(plaintext, "DICKENS"), 20);
+        assert_eq!(key, "DICKENS");
+        assert_eq!(cracked, decrypt(&encrypt(plaintext, "DICKENS"), "DICKENS"));
+    }
+}
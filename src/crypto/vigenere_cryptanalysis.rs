@@ -0,0 +1,20 @@
+// Implements http://rosettacode.org/wiki/Vigen%C3%A8re_cipher/Cryptanalysis
+// with the crack() of vigenere.rs, which explains how it works
+//
+//     vigenere_cryptanalysis [CIPHERTEXT]
+//
+// Without a ciphertext, it cracks the task's one.
+
+#[allow(dead_code)]
+#[path = "vigenere.rs"]
+mod vigenere;
+
+#[cfg(not(test))]
+fn main() {
+    let ciphertext = std::env::args().nth(1).unwrap_or_else(|| vigenere::CIPHERTEXT.to_string());
+    let (key, plaintext) = vigenere::crack(&ciphertext, 26);
+    println!("Key: {}\n", key);
+    for line in plaintext.as_bytes().chunks(80) {
+        println!("{}", String::from_utf8_lossy(line));
+    }
+}
@@ -0,0 +1,38 @@
+// Implements http://rosettacode.org/wiki/Priority_queue
+// The queue is utils::heap's binary heap, which pops the lowest priority first, so a task with
+// priority 1 is done before one with priority 5.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::heap::PriorityQueue;
+
+// The task's example: what to do, with how urgent it is
+fn chores() -> PriorityQueue<u32, &'static str> {
+    let mut queue = PriorityQueue::new();
+    queue.push("Clear drains", 3);
+    queue.push("Feed cat", 4);
+    queue.push("Make tea", 5);
+    queue.push("Solve RC tasks", 1);
+    queue.push("Tax return", 2);
+    queue
+}
+
+#[cfg(not(test))]
+fn main() {
+    let mut queue = chores();
+    while let Some((task, priority)) = queue.pop() {
+        println!("{} {}", priority, task);
+    }
+}
+
+#[test]
+fn test_chores() {
+    let mut queue = chores();
+    assert_eq!(queue.peek(), Some((&"Solve RC tasks", &1)));
+    let mut order = Vec::new();
+    while let Some((task, _)) = queue.pop() {
+        order.push(task);
+    }
+    assert_eq!(order, ["Solve RC tasks", "Tax return", "Clear drains", "Feed cat", "Make tea"]);
+}
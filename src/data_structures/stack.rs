@@ -1,4 +1,9 @@
 // Implements http://rosettacode.org/wiki/Stack
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
 #[derive(Debug)]
 struct Stack<T> {
     // We use a vector because of simplicity
@@ -75,3 +80,19 @@ fn test_basic() {
     // The element at the top is now 8
     assert!(stack.peek().unwrap() == &8);
 }
+
+#[test]
+fn test_any_operations() {
+    // Some(x) pushes x and None pops, which should do to the stack what it does to a Vec
+    utils::property::keeps_invariant(
+        || (Stack::new(), Vec::new()),
+        |(stack, pushed): &mut (Stack<i32>, Vec<i32>), push: &Option<i32>| {
+            match *push {
+                Some(x) => { stack.push(x); pushed.push(x); },
+                None => { stack.pop(); pushed.pop(); }
+            }
+        },
+        |(stack, pushed)| {
+            stack.peek() == pushed.last() && stack.empty() == pushed.is_empty()
+        });
+}
@@ -1,47 +1,61 @@
-// Implements http://rosettacode.org/wiki/24_game
-// with a recursive descent parser for a simple calculator (+ - * /)
-// using the shunting yard algorithm as explained on
-// http://www.engr.mun.ca/~theo/Misc/exp_parsing.htm
-// It follows operator precedence (i.e. 2 + 3 * 3 = 11),
-// understands negation (-5 + 6 = 1), ignores whitespace
-// and allows the use of parentheses
+//! Implements http://rosettacode.org/wiki/24_game
+//! with a recursive descent parser for a simple calculator (+ - * /)
+//! using the shunting yard algorithm as explained on
+//! http://www.engr.mun.ca/~theo/Misc/exp_parsing.htm
+//! It follows operator precedence (i.e. 2 + 3 * 3 = 11),
+//! understands negation (-5 + 6 = 1), ignores whitespace
+//! and allows the use of parentheses
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::game_24::{check_values, Parser};
+//!
+//! let answer = "(8 - 4) * (3 + 3)";
+//! assert!(check_values(&mut [3, 4, 8, 3], answer));
+//! assert!(!check_values(&mut [3, 4, 8, 2], answer));
+//! assert_eq!(Parser::new(answer).parse(), Ok(24.0));
+//! assert_eq!(Parser::new("2 + 3 * 3").parse(), Ok(11.0));
+//! assert!(Parser::new("(2 + 3").parse().is_err());
+//! ```
 
 // We use a glob import in our test module. Seperating tests into a seperate
 // module enforces visibility restrictions so the test module can only access
 // publically exported code, the same as any user of the code.
 
-#![allow(unused_features)] // feature(rand) is used only in main
-
-
-#![feature(unicode)]
-#![feature(collections)]
-#![feature(old_io)]
+// For main's random numbers
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
-extern crate rand;
-
-use std::cmp::Ordering::{self, Greater};
-use std::char::CharExt;
+use std::cmp::Ordering::{self, Greater, Less};
 
 #[cfg(not(test))]
 fn main() {
-    use std::old_io;
+    use std::io;
+    use self::utils::rng::Rng;
 
-    let mut rng = rand::thread_rng();
-    let mut input = old_io::stdin();
+    let mut rng = Rng::from_time();
+    let input = io::stdin();
 
     loop {
-        let mut sample = rand::sample(&mut rng, (1u32..10), 4);
+        // 4 different numbers from 1 to 9
+        let mut sample: Vec<u32> = (1..10).collect();
+        rng.shuffle(&mut sample);
+        sample.truncate(4);
 
         println!("make 24 by combining the following 4 numbers with + - * / or (q)uit");
         println!("{:?}", sample);
 
-        let line = input.read_line().unwrap();
+        let mut line = String::new();
+        if input.read_line(&mut line).unwrap() == 0 {
+            break;
+        }
         match line.trim() {
             "q" => break,
             input => {
                 if check_values(&mut sample[..], input) {
                     match Parser::new(input).parse() {
-                        Ok(i) if i == 24. => println!("you made it!"),
+                        Ok(24.) => println!("you made it!"),
                         Ok(i) => println!("you entered {}, try again!", i),
                         Err(s)  => println!("{}", s)
                     };
@@ -70,7 +84,7 @@ pub fn check_values(sample:&mut [u32], input:&str) -> bool {
 }
 
 // the tokens that our parser is going to recognize
-#[derive(PartialEq,Eq,Debug, Copy)]
+#[derive(PartialEq,Eq,Debug, Clone, Copy)]
 pub enum Token {
     LParen,
     RParen,
@@ -84,10 +98,7 @@ pub enum Token {
 impl Token {
    // are tokens associated to a binary operation?
    fn is_binary(&self) -> bool {
-        match *self {
-            Token::Plus | Token::Minus | Token::Slash | Token::Star => true,
-            _ => false
-        }
+        matches!(*self, Token::Plus | Token::Minus | Token::Slash | Token::Star)
     }
 }
 
@@ -114,15 +125,15 @@ impl Tokenable for char {
 // Lexer reads an expression like (a + b) / c * d
 // as an iterator on the tokens that compose it
 // Int(a), LParen, Plus, Int(b), RParen...
-#[derive(Copy)]
+#[derive(Clone, Copy)]
 pub struct Lexer<'a> {
     input: &'a str,
     offset: usize
 }
 
 impl <'a> Lexer<'a> {
-    pub fn new(input: &str) -> Lexer {
-        Lexer { input: input, offset: 0 }
+    pub fn new(input: &str) -> Lexer<'_> {
+        Lexer { input, offset: 0 }
     }
 
     fn expect(&mut self, expected:&[Token]) -> Result<Token, String> {
@@ -149,27 +160,25 @@ impl <'a> Iterator for Lexer<'a> {
 
         let (tok, cur_offset) = match remaining.next() {
             // Found a digit. if there are others, transform them to `u32`
-            Some((mut offset, ch)) if ch.is_numeric() => {
+            Some((offset, ch)) if ch.is_ascii_digit() => {
                 let mut val = ch.to_digit(10).unwrap();
-                let mut more = false;
+                let mut end = offset + 1;
 
                 for (idx, ch) in remaining {
-                    more = true;
-                    if CharExt::is_numeric(ch) {
-                        let digit = CharExt::to_digit(ch, 10).unwrap();
-                        val = val * 10 + digit;
-                    } else {
-                        offset = idx;
-                        break;
+                    match ch.to_digit(10) {
+                        // numbers too big for a `u32` stay at `u32::MAX` rather than
+                        // overflowing, they aren't worth 24 anyway
+                        Some(digit) => {
+                            val = val.saturating_mul(10).saturating_add(digit);
+                            end = idx + 1;
+                        },
+                        None => break
                     }
                 }
-                if !more {
-                    offset = 1;
-                }
-                (Some(Token::Int(val)), offset)
+                (Some(Token::Int(val)), end)
             },
             // found non-digit, try transforming it to the corresponding token
-            Some((o, ch)) => (ch.as_token(), o + 1),
+            Some((o, ch)) => (ch.as_token(), o + ch.len_utf8()),
             _   => (None, 0)
         };
 
@@ -182,7 +191,7 @@ impl <'a> Iterator for Lexer<'a> {
 // Operators are a "higher level" concept than tokens as they define the
 // semantics of the expression language e.g. token "Minus" can correspond to
 // the unary Neg Operator (-a) or to the binary Sub operator (a - b)
-#[derive(PartialEq, Eq, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Operator {
     Neg,
     Add,
@@ -212,6 +221,9 @@ impl Operator {
 impl PartialOrd for Operator {
     fn partial_cmp(&self, other: &Operator) -> Option<Ordering> {
         match (self.precedence(), other.precedence()) {
+            // a unary operator is pushed before its operand is read, so nothing can be
+            // applied in its place yet
+            _ if *other == Operator::Neg => Some(Less),
             (a, b) if a == b => Some(Greater),
             (a, b) => a.partial_cmp(&b)
         }
@@ -230,7 +242,7 @@ pub struct Parser<'a> {
 }
 
 impl <'a> Parser<'a> {
-    pub fn new(input: &str) -> Parser {
+    pub fn new(input: &str) -> Parser<'_> {
         Parser {
             operators: vec![],
             operands: vec![],
@@ -240,15 +252,15 @@ impl <'a> Parser<'a> {
 
     pub fn parse(&mut self) -> Result<f32, String> {
         self.operators.push(Operator::Sentinel);
-        try!(self.e());
-        return match self.operands.last() {
+        self.e()?;
+        match self.operands.last() {
             Some(r) => Ok(*r),
             None => Err("something went wrong, got no result".to_string())
         }
     }
 
     fn e(&mut self) -> Result<(), String> {
-        try!(self.p());
+        self.p()?;
 
         loop {
             match self.lexer.peekable().peek() {
@@ -266,7 +278,7 @@ impl <'a> Parser<'a> {
 
                     // Consume the peeked value
                     self.lexer.next();
-                    try!(self.p());
+                    self.p()?;
                 }
                 _ => break
             }
@@ -285,13 +297,13 @@ impl <'a> Parser<'a> {
             Some(Token::Int(n)) => self.operands.push(n as f32),
             Some(Token::LParen) => {
                 self.operators.push(Operator::Sentinel);
-                try!(self.e());
-                try!(self.lexer.expect(&[Token::RParen]));
+                self.e()?;
+                self.lexer.expect(&[Token::RParen])?;
                 self.operators.pop();
             },
             Some(Token::Minus) => {
                 self.push_operator(Operator::Neg);
-                try!(self.p());
+                self.p()?;
             },
             Some(e) => return Err(format!("unexpected token {:?}", e)),
             _ => return Err("unexpected end of command".to_string())
@@ -340,6 +352,7 @@ impl <'a> Parser<'a> {
 mod test {
     use super::{Token, Lexer, Parser};
     use super::Operator::{Add, Sub, Mul, Div};
+    use std::cmp::Ordering::Less;
     use super::{check_values};
     use super::Token::{LParen, RParen, Plus, Slash, Star, Int};
 
@@ -353,7 +366,7 @@ mod test {
         assert!(Add > Sub);
         assert!(Sub > Add);
 
-        assert!(!(Mul < Div));
+        assert_ne!(Mul.partial_cmp(&Div), Some(Less));
     }
 
     #[test]
@@ -386,6 +399,10 @@ mod test {
         t(tok, Slash, 9);
         t(tok, Int(2), 10);
         t(tok, RParen, 11);
+
+        let tok = &mut Lexer::new(" 12");
+        t(tok, Int(12), 3);
+        assert_eq!(tok.next(), None);
     }
 
     #[test]
@@ -401,6 +418,11 @@ mod test {
         t("2++12", Err("unexpected token Plus".to_string()));
         t("-2+12", Ok(10.));
         t("-2*(2+3)", Ok(-10.));
+        t("2*-3", Ok(-6.));
+        t("--2", Ok(2.));
+        t("1+23", Ok(24.));
+        t("99999999999", Ok(4294967295.));
+        t("é", Err("unexpected end of command".to_string()));
 
         // Testing precedence
         t("4 / 2 * 2", Ok(4.));
@@ -1,18 +1,33 @@
-// Implements http://rosettacode.org/wiki/24_game
-// Uses RPN expression
-#![allow(unused_features)] // feature(rand) is used only in main
-#![feature(str_words)]
-#![feature(old_io)]
+//! Implements http://rosettacode.org/wiki/24_game
+//!
+//! Uses RPN expression
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::game_24_rpn::check_input;
+//!
+//! assert_eq!(check_input("4 3 * 6 2 * +", &[4, 3, 6, 2]), Ok(()));
+//! assert_eq!(check_input("4 3 + 6 2 * +", &[4, 3, 6, 2]),
+//!            Err("Wrong answer. Result: 19".to_string()));
+//! ```
 
-extern crate rand;
+// For main's random numbers
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 #[cfg(not(test))]
 fn main() {
-    use rand::{thread_rng, Rng};
-    use std::old_io;
+    use std::io::{self, Write};
+    use self::utils::rng::Rng;
 
-    let mut rng = thread_rng();
-    let mut reader = old_io::stdin();
+    let mut rng = Rng::from_time();
+    let reader = io::stdin();
+    let read_line = || {
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("Failed to read line!");
+        line
+    };
 
     // generating 4 numbers
     let choices: Vec<u32> = (0u32..4).map(
@@ -22,25 +37,29 @@ fn main() {
 
     // start the game loop
     loop {
-        print!("Your numbers: {}, {}, {}, {}\n", choices[0], choices[1], choices[2], choices[3]);
-        let expr = reader.read_line().ok().expect("Failed to read line!");
+        println!("Your numbers: {}, {}, {}, {}", choices[0], choices[1], choices[2], choices[3]);
+        let expr = read_line();
         match check_input(&expr[..], &choices[..]) {
             Ok(()) => { println!("Good job!"); break; },
             Err(e) => println!("{}", e)
         }
         print!("Try again? (y/n): ");
-        let choice = reader.read_line().ok().expect("Failed to read line!");
+        io::stdout().flush().unwrap();
+        let choice = read_line();
         if choice.trim() != "y" { break; }
     }
 }
 
-fn check_input(expr: &str, choices: &[u32]) -> Result<(), String> {
+pub fn check_input(expr: &str, choices: &[u32]) -> Result<(), String> {
     let mut stack: Vec<u32> = Vec::new();
-    for token in expr.words() {
+    for token in expr.split_whitespace() {
         if is_operator(token) {
             let (a, b) = (stack.pop(), stack.pop());
             match (a, b) {
-                (Some(x), Some(y)) => stack.push(evaluate(y, x, token)),
+                (Some(x), Some(y)) => match evaluate(y, x, token) {
+                    Some(n) => stack.push(n),
+                    None => return Err(format!("Cannot compute {} {} {}", y, token, x))
+                },
                 (_, _) => return Err("Not a valid RPN expression!".to_string())
             }
         } else {
@@ -58,24 +77,23 @@ fn check_input(expr: &str, choices: &[u32]) -> Result<(), String> {
     }
 
     let ans = stack.pop();
-    if stack.len() > 0 {
+    if !stack.is_empty() {
         return Err("Not a valid RPN expression!".to_string());
     }
     match ans {
-        Some(x) => {
-            if x == 24 { return Ok(()); }
-            return Err(format!("Wrong answer. Result: {}", x));
-        }
-        None => return Err("Error encountered!".to_string()),
+        Some(24) => Ok(()),
+        Some(x) => Err(format!("Wrong answer. Result: {}", x)),
+        None => Err("Error encountered!".to_string()),
     }
 }
 
-fn evaluate(a: u32, b: u32, op: &str) -> u32 {
+// None if there's no result in a `u32`: negative, too big or divided by zero
+fn evaluate(a: u32, b: u32, op: &str) -> Option<u32> {
     match op {
-        "+" => a + b,
-        "-" => a - b,
-        "*" => a * b,
-        "/" => a / b,
+        "+" => a.checked_add(b),
+        "-" => a.checked_sub(b),
+        "*" => a.checked_mul(b),
+        "/" => a.checked_div(b),
         _   => unreachable!()
     }
 }
@@ -102,4 +120,8 @@ fn test_check_input() {
 
     // invalid RPN expression
     assert_eq!(check_input("4 3 + 6 2 *", &v1), Err("Not a valid RPN expression!".to_string()));
+
+    // operations without a result
+    assert_eq!(check_input("3 4 -", &v1), Err("Cannot compute 3 - 4".to_string()));
+    assert_eq!(check_input("6 4 4 - /", &v1), Err("Cannot compute 6 / 0".to_string()));
 }
@@ -0,0 +1,202 @@
+//! http://rosettacode.org/wiki/24_game/Solve
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::game_24_solve::{solve, Rational};
+//!
+//! let mut numbers = [1, 3, 7, 9].iter().map(|&n| Rational::from_integer(n)).collect::<Vec<_>>();
+//! assert_eq!(solve(&mut numbers, 24), Some("(9 / (3 / (1 + 7)))".to_string()));
+//! assert_eq!(solve(&mut [Rational::from_integer(1); 4], 24), None);
+//! ```
+
+// modeled after the scala solution
+// http://rosettacode.org/wiki/24_game/Solve#Scala
+extern crate rosetta_task;
+
+use rosetta_task::rosetta_task;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+// A fraction in lowest terms, with a positive denominator, so that equal fractions are equal
+// structurally
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numer: isize,
+    denom: isize,
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+impl Rational {
+    // Panics if `denom` is 0
+    pub fn new(numer: isize, denom: isize) -> Rational {
+        assert!(denom != 0, "{}/0 isn't a number", numer);
+        let g = gcd(numer, denom) * denom.signum();
+        Rational { numer: numer / g, denom: denom / g }
+    }
+
+    pub fn from_integer(n: isize) -> Rational {
+        Rational { numer: n, denom: 1 }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // Denominators are positive, so cross-multiplying keeps the order
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(self.numer * other.denom + other.numer * self.denom,
+                      self.denom * other.denom)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Rational) -> Rational {
+        Rational::new(self.numer * other.denom - other.numer * self.denom,
+                      self.denom * other.denom)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(self.numer * other.numer, self.denom * other.denom)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    // Panics if `other` is 0
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(self.numer * other.denom, self.denom * other.numer)
+    }
+}
+
+// Integers are written without a denominator
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+// convenience macro to create a fixed-sized vector
+// of rationals by writing:
+// rational![1, 2, ...] instead of
+// [Rational::from_integer(1), Rational::from_integer(2), ...]
+macro_rules! rationals(
+    ($($e:expr),+) => ([$(Rational::from_integer($e)),+])
+);
+
+#[cfg(not(test))]
+fn main() {
+    let mut r = rationals![1, 3, 7, 9];
+    let sol = solve(&mut r[..], 24).unwrap_or_else(|| "no solution found".to_string());
+    println!("{}", sol);
+}
+// for a vector of rationals r, find the combination of arithmentic
+// operations that yield target_val as a result (if such combination exists)
+#[rosetta_task(status = "draft", url = "http://rosettacode.org/wiki/24_game/Solve")]
+pub fn solve(r: &mut [Rational], target_val: isize) -> Option<String> {
+    //need to sort because next_permutation()
+    // returns permutations in lexicographic order
+    r.sort();
+    loop {
+        let all_ops = compute_all_operations(r);
+        for &(res, ref ops) in &all_ops {
+            if res==Rational::from_integer(target_val) {return Some(ops.to_string());}
+        }
+        if ! next_permutation(r) {return None;}
+    }
+}
+
+// Rearranges `v` into the next permutation in lexicographic order, or returns false if it's the
+// last one already
+fn next_permutation<T: Ord>(v: &mut [T]) -> bool {
+    // The longest decreasing suffix can't be rearranged into anything later, so the element
+    // before it is the one to increase, by swapping it with the next larger one in the suffix
+    let i = match v.windows(2).rposition(|w| w[0] < w[1]) {
+        Some(i) => i,
+        None => return false,
+    };
+    let j = v.iter().rposition(|x| *x > v[i]).unwrap();
+    v.swap(i, j);
+    // Which leaves the suffix still decreasing, and its first permutation is increasing
+    v[i + 1..].reverse();
+    true
+}
+// applies all the valid combinations of + - * and / to the
+// numbers in l and for each combination creates a tuple
+// with the result and the expression in String form
+// returns all (result, expression in string form)
+// results in a vector
+fn compute_all_operations(l: &[Rational]) -> Vec<(Rational, String)> {
+    match l {
+        []         => vec![],
+        [x]  => vec![(*x, (format!("{}", x)))],
+        [x,rest @ ..] => {
+            let x = *x;
+            let mut rt=Vec::new();
+            for &(y, ref exp) in &compute_all_operations(rest) {
+                let mut sub=vec![(x * y, "*"),(x + y, "+"), (x - y, "-")];
+                if !y.is_zero() {sub.push( (x/y, "/")); }
+                for &(z, ref op) in &sub {
+                    let aux = (z, (format!("({} {} {})", x, op, exp )));
+                    rt.push(aux);
+                }
+            }
+            rt
+        }
+    }
+}
+#[test]
+fn test_rationals_macro() {
+    assert_eq!(
+    // without the rationals! macro
+    [Rational::from_integer(1),
+    Rational::from_integer(2),
+    Rational::from_integer(3),
+    Rational::from_integer(4)],
+    // with the rationals! macro
+    (rationals![1, 2, 3, 4]));
+}
+
+#[test]
+fn test_rational() {
+    let half = Rational::new(2, 4);
+    assert_eq!(half, Rational::new(-1, -2));
+    assert_eq!(half.to_string(), "1/2");
+    assert_eq!((half + half).to_string(), "1");
+    assert_eq!((Rational::from_integer(1) / Rational::new(-3, 1)).to_string(), "-1/3");
+    assert!(Rational::new(1, 3) < half && half < Rational::from_integer(1));
+}
+
+#[test]
+fn test_solve() {
+    let mut r = rationals![1, 3, 7, 9];
+    assert_eq!(
+        solve(&mut r[..], 24),
+        Some("(9 / (3 / (1 + 7)))".to_string()));
+}
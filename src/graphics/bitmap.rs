@@ -0,0 +1,83 @@
+//! Implements http://rosettacode.org/wiki/Basic_bitmap_storage
+//!
+//! The bitmap is utils::image::Image, which the other graphics tasks draw on too.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::canvas::{Canvas, Color};
+//! use utils::image::Image;
+//!
+//! let mut image = Image::new(10, 10);
+//! image.set(5, 2, Color::WHITE);
+//! assert_eq!(image.get(5, 2), Color::WHITE);
+//! assert_eq!(image.get(2, 5), Color::BLACK);
+//! ```
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::canvas::{Canvas, Color};
+use utils::image::Image;
+
+#[cfg(not(test))]
+pub fn main() {
+    let mut image = Image::new(10, 10);
+
+    for y in 0..10 {
+        for x in 5..10 {
+            image.set(x, y, Color::WHITE);
+        }
+    }
+
+    for y in 0..10 {
+        for x in 0..10 {
+            if image.get(x, y) == Color::BLACK {
+                print!("#");
+            } else {
+                print!(".");
+            }
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Canvas, Color, Image};
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let image = Image::new(10, 10);
+        let _ = image.get(10, 11);
+    }
+
+    #[test]
+    fn getting() {
+        let image = Image::new(3, 4);
+        for x in 0..3 {
+            for y in 0..4 {
+                assert_eq!(image.get(x, y), Color::BLACK);
+            }
+        }
+    }
+
+    #[test]
+    fn setting() {
+        let mut image = Image::new(3, 3);
+        image.set(0, 0, Color::rgb(1, 1, 1));
+        assert_eq!(image.get(0, 0), Color::rgb(1, 1, 1));
+    }
+
+    #[test]
+    fn filling() {
+        let mut image = Image::new(4, 3);
+        let fill = Color::rgb(3, 2, 5);
+        image.fill(fill);
+        for x in 0..4 {
+            for y in 0..3 {
+                assert_eq!(image.get(x, y), fill);
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Implements http://rosettacode.org/wiki/Conway's_Game_of_Life
+//!
+//! The board wraps around at the edges.  It is drawn on a utils::canvas::Canvas, so that the
+//! wasm/ crate can animate it in a browser as well, and with --animate it runs in the terminal.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::conways_game_of_life::Life;
+//!
+//! // A blinker turns from a row into a column, and back
+//! let mut life = Life::from_pattern(&["     ", "     ", " ### ", "     ", "     "]);
+//! life.step();
+//! assert_eq!(life, Life::from_pattern(&["     ", "  #  ", "  #  ", "  #  ", "     "]));
+//! life.step();
+//! assert_eq!(life.population(), 3);
+//! assert!(life.get(1, 2) && !life.get(2, 1));
+//! ```
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::canvas::{Canvas, Color};
+#[cfg(not(test))]
+use utils::canvas::TextCanvas;
+use utils::rng::Rng;
+#[cfg(not(test))]
+use utils::args::Args;
+#[cfg(not(test))]
+use utils::term::{FrameTimer, Screen, Terminal};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Life {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>, // Row by row, true for the live ones
+}
+
+impl Life {
+    // A board with every cell dead
+    pub fn new(width: usize, height: usize) -> Life {
+        Life { width, height, cells: vec![false; width * height] }
+    }
+
+    // A board with the cells marked '#' in `rows` alive
+    pub fn from_pattern(rows: &[&str]) -> Life {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut life = Life::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                life.set(x, y, c == '#');
+            }
+        }
+        life
+    }
+
+    // A board on which each cell is alive with probability `density`
+    pub fn random(width: usize, height: usize, density: f64, rng: &mut Rng) -> Life {
+        let mut life = Life::new(width, height);
+        for cell in &mut life.cells {
+            *cell = rng.gen_bool(density);
+        }
+        life
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        self.cells[y * self.width + x] = alive;
+    }
+
+    // Return how many cells are alive
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&alive| alive).count()
+    }
+
+    // Advance one generation: a live cell with two or three live neighbors survives, and a dead
+    // one with exactly three comes alive.
+    pub fn step(&mut self) {
+        let mut next = vec![false; self.cells.len()];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                next[y * self.width + x] =
+                    matches!((self.get(x, y), self.neighbors(x, y)), (true, 2) | (_, 3));
+            }
+        }
+        self.cells = next;
+    }
+
+    fn neighbors(&self, x: usize, y: usize) -> usize {
+        let mut count = 0;
+        // Offsets of width - 1 and height - 1 wrap around to the cells before
+        for dy in [self.height - 1, 0, 1].iter() {
+            for dx in [self.width - 1, 0, 1].iter() {
+                if (*dx, *dy) != (0, 0) &&
+                   self.get((x + dx) % self.width, (y + dy) % self.height) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    // Draw the board over the whole canvas, live cells in white, scaling it to fit.
+    pub fn draw<C: Canvas>(&self, canvas: &mut C) {
+        let (width, height) = (canvas.width(), canvas.height());
+        for y in 0..height {
+            for x in 0..width {
+                let alive = self.get(x * self.width / width, y * self.height / height);
+                canvas.set(x, y, if alive { Color::WHITE } else { Color::BLACK });
+            }
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let mut args = Args::from_env();
+    let animated = args.flag("--animate");
+    if let Err(message) = args.finish() {
+        utils::args::exit_with_usage(&message, "conways_game_of_life [--animate]");
+    }
+    if animated {
+        animate().unwrap();
+        return;
+    }
+    // The task asks for a blinker, for three generations
+    let mut life = Life::from_pattern(&["     ", "     ", " ### ", "     ", "     "]);
+    for generation in 0..3 {
+        let mut canvas = TextCanvas::new(life.width(), life.height());
+        life.draw(&mut canvas);
+        println!("Generation {}:\n{}\n", generation, canvas);
+        life.step();
+    }
+}
+
+// Run a random board in the terminal, until it's interrupted
+#[cfg(not(test))]
+fn animate() -> std::io::Result<()> {
+    let mut life = Life::random(80, 48, 0.3, &mut Rng::from_time());
+    let mut screen = Screen::new(life.width(), life.height());
+    let mut terminal = Terminal::new(std::io::stdout());
+    let mut timer = FrameTimer::new(10);
+    loop {
+        life.draw(&mut screen);
+        terminal.draw(&screen)?;
+        life.step();
+        timer.wait();
+    }
+}
+
+#[test]
+fn test_blinker() {
+    let mut life = Life::from_pattern(&["     ", "  #  ", "  #  ", "  #  ", "     "]);
+    let start = life.clone();
+    life.step();
+    assert_eq!(life, Life::from_pattern(&["     ", "     ", " ### ", "     ", "     "]));
+    life.step();
+    assert_eq!(life, start);
+}
+
+#[test]
+fn test_still_life() {
+    let block = Life::from_pattern(&["    ", " ## ", " ## ", "    "]);
+    let mut life = block.clone();
+    life.step();
+    assert_eq!(life, block);
+}
+
+#[test]
+fn test_glider_wraps() {
+    let glider = Life::from_pattern(&[" #    ", "  #   ", "###   ", "      ", "      ", "      "]);
+    let mut life = glider.clone();
+    // Every 4 generations it moves one cell diagonally, so in 24 it's back where it started
+    for _ in 0..24 {
+        life.step();
+        assert_eq!(life.population(), 5);
+    }
+    assert_eq!(life, glider);
+}
+
+#[test]
+fn test_draw() {
+    use utils::canvas::PixelCanvas;
+
+    let life = Life::from_pattern(&["# ", " #"]);
+    let mut canvas = PixelCanvas::new(4, 4);
+    life.draw(&mut canvas);
+    assert_eq!(canvas.get(1, 1), Color::WHITE);
+    assert_eq!(canvas.get(2, 1), Color::BLACK);
+    assert_eq!(canvas.get(3, 3), Color::WHITE);
+}
+
+#[test]
+fn test_random() {
+    let life = Life::random(50, 40, 0.25, &mut Rng::new(1));
+    assert!(life.population() > 400 && life.population() < 600);
+    assert_eq!(life, Life::random(50, 40, 0.25, &mut Rng::new(1)));
+}
@@ -0,0 +1,168 @@
+// Implements http://rosettacode.org/wiki/Hough_transform
+//
+// Contributed by Gavin Baker <gavinb@antonym.org>
+// Adapted from the Go version
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+// Simple 8-bit grayscale image
+
+struct ImageGray8 {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+// Read a line, with its newline
+fn read_line<R: BufRead>(file: &mut R) -> String {
+    let mut line = String::new();
+    file.read_line(&mut line).unwrap();
+    line
+}
+
+fn load_pgm(filename: &str) -> ImageGray8 {
+
+    // Open file
+
+    let mut file = BufReader::new(File::open(filename).unwrap());
+
+    // Read header
+    let magic_in = read_line(&mut file);
+    let width_in = read_line(&mut file);
+    let height_in = read_line(&mut file);
+    let maxval_in = read_line(&mut file);
+
+    assert_eq!(magic_in, "P5\n");
+    assert_eq!(maxval_in, "255\n");
+
+    // Parse header
+
+    let width = width_in.trim().parse::<usize>().unwrap();
+    let height: usize = height_in.trim().parse::<usize>().unwrap();
+
+    println!("Reading pgm file {}: {} x {}", filename, width, height);
+
+    // Create image and allocate buffer
+
+    let mut img = ImageGray8 {
+        width,
+        height,
+        data: vec![0u8; width*height],
+    };
+
+    // Read image data
+
+    match file.read_exact(&mut img.data) {
+        Ok(()) => println!("Read {} bytes", img.data.len()),
+        Err(e) => println!("error reading: {}", e)
+    }
+
+    img
+}
+
+fn save_pgm(img: &ImageGray8, filename: &str) {
+
+    // Open file
+
+    let mut file = BufWriter::new(File::create(filename).unwrap());
+
+    // Write header
+
+    if let Err(e) = writeln!(file, "P5\n{}\n{}\n255", img.width, img.height) {
+        println!("Failed to write header: {}", e);
+    }
+
+    println!("Writing pgm file {}: {} x {}", filename, img.width, img.height);
+
+    // Write binary image data
+
+    if let Err(e) = file.write_all(&img.data) {
+        println!("Failed to image data: {}", e);
+    }
+}
+
+fn hough(image: &ImageGray8, out_width: usize, out_height: usize) -> ImageGray8 {
+
+    let in_width = image.width;
+    let in_height = image.height;
+
+    // Allocate accumulation buffer
+
+    let out_height = (out_height/2) * 2;
+    let mut accum = ImageGray8 {
+        width: out_width,
+        height: out_height,
+        data: vec![255; out_width*out_height],
+    };
+
+    // Transform extents
+
+    let rmax = (in_width as f64).hypot(in_height as f64);
+    let dr = rmax / (out_height/2) as f64;
+    let dth = std::f64::consts::PI / out_width as f64;
+
+    // Process input image in raster order
+
+    for y in 0..in_height {
+        for x in 0..in_width {
+            let in_idx = y*in_width+x;
+            let col = image.data[in_idx];
+            if col == 255 {
+                continue;
+            }
+
+            // Project into rho,theta space
+
+            for jtx in 0..out_width {
+                let th = dth * (jtx as f64);
+                let r = (x as f64)*(th.cos()) + (y as f64)*(th.sin());
+
+                let iry = out_height/2 - (r/dr+0.5).floor() as usize;
+                let out_idx = jtx + iry * out_width;
+                let col = accum.data[out_idx];
+                if col > 0 {
+                    accum.data[out_idx] = col - 1;
+                }
+            }
+        }
+    }
+    accum
+}
+
+#[cfg(not(test))]
+fn main() {
+
+    let image = load_pgm(&utils::fixture::path("Pentagon.pgm").to_string_lossy());
+
+    let accum = hough(&image, 460, 360);
+
+    save_pgm(&accum, "hough.pgm");
+}
+
+#[test]
+fn test_hough() {
+    // A single black pixel at the origin is at distance 0 in every direction, so it darkens the
+    // middle row of the accumulator and nothing else
+    let mut data = vec![255; 4 * 3];
+    data[0] = 0;
+    let accum = hough(&ImageGray8 { width: 4, height: 3, data }, 6, 5);
+    assert_eq!((accum.width, accum.height), (6, 4));
+    for (i, &col) in accum.data.iter().enumerate() {
+        assert_eq!(col, if i / 6 == 2 { 254 } else { 255 }, "at {}", i);
+    }
+}
+
+#[test]
+fn test_pgm_roundtrip() {
+    let image = load_pgm(&utils::fixture::path("Pentagon.pgm").to_string_lossy());
+    let path = std::env::temp_dir().join(format!("hough_transform-{}.pgm", std::process::id()));
+    save_pgm(&image, &path.to_string_lossy());
+    let saved = load_pgm(&path.to_string_lossy());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!((saved.width, saved.height), (image.width, image.height));
+    assert!(saved.data == image.data);
+}
@@ -0,0 +1,113 @@
+//! Implements http://rosettacode.org/wiki/Mandelbrot_set
+//!
+//! Drawn on a utils::canvas::Canvas, so that the wasm/ crate can show it in a browser as well.
+//! Given a file name ending in .ppm or .bmp, it saves a picture of the set there instead of
+//! printing it.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::mandelbrot_set::escape_time;
+//!
+//! // 0 and -1 are in the set, going round 0, -1, 0, ...; 1 goes 0, 1, 2, 5 and is out
+//! assert_eq!(escape_time(0.0, 0.0), None);
+//! assert_eq!(escape_time(-1.0, 0.0), None);
+//! assert_eq!(escape_time(1.0, 0.0), Some(3));
+//! ```
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::canvas::{Canvas, Color};
+#[cfg(not(test))]
+use utils::canvas::TextCanvas;
+#[cfg(not(test))]
+use utils::args::Args;
+#[cfg(not(test))]
+use utils::image::Image;
+
+static MAX_ITERATIONS: u32 = 256;
+
+// How many iterations of z -> z^2 + c it takes z to escape the circle of radius 2, starting from
+// 0, or None if it stays inside for MAX_ITERATIONS, in which case c is taken to be in the set.
+pub fn escape_time(c_re: f64, c_im: f64) -> Option<u32> {
+    let (mut re, mut im) = (0.0f64, 0.0f64);
+    for i in 0..MAX_ITERATIONS {
+        if re * re + im * im > 4.0 {
+            return Some(i);
+        }
+        let next_re = re * re - im * im + c_re;
+        im = 2.0 * re * im + c_im;
+        re = next_re;
+    }
+    None
+}
+
+// Draw the set over the whole canvas, covering -2.5 to 1 on the real axis and -1.25 to 1.25 on the
+// imaginary one: in white, and the points around it the brighter the longer they took to escape.
+pub fn draw<C: Canvas>(canvas: &mut C) {
+    let (width, height) = (canvas.width(), canvas.height());
+    for y in 0..height {
+        for x in 0..width {
+            let c_re = -2.5 + 3.5 * x as f64 / width as f64;
+            let c_im = 1.25 - 2.5 * y as f64 / height as f64;
+            let color = match escape_time(c_re, c_im) {
+                None => Color::WHITE,
+                Some(i) => {
+                    let level = (i as f64 / MAX_ITERATIONS as f64).sqrt();
+                    Color::rgb((level * 160.0) as u8, (level * 200.0) as u8, (level * 255.0) as u8)
+                }
+            };
+            canvas.set(x, y, color);
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let usage = "mandelbrot_set [FILE.ppm | FILE.bmp]";
+    let paths: Vec<String> = Args::from_env().free()
+        .unwrap_or_else(|message| utils::args::exit_with_usage(&message, usage));
+    if paths.len() > 1 {
+        utils::args::exit_with_usage("too many arguments", usage);
+    }
+    if let Some(path) = paths.first() {
+        let mut image = Image::new(700, 500);
+        draw(&mut image);
+        let file = std::io::BufWriter::new(std::fs::File::create(path).unwrap());
+        if path.ends_with(".bmp") {
+            image.write_bmp(file).unwrap();
+        } else {
+            image.write_ppm(file).unwrap();
+        }
+        return;
+    }
+    // Characters are about twice as tall as they are wide
+    let mut canvas = TextCanvas::new(78, 31);
+    draw(&mut canvas);
+    println!("{}", canvas);
+}
+
+#[test]
+fn test_escape_time() {
+    assert_eq!(escape_time(0.0, 0.0), None);
+    assert_eq!(escape_time(-1.0, 0.0), None);
+    assert_eq!(escape_time(-2.0, 0.0), None);
+    assert_eq!(escape_time(1.0, 0.0), Some(3));
+    assert_eq!(escape_time(0.0, 2.0), Some(2));
+    assert_eq!(escape_time(3.0, 0.0), Some(1));
+}
+
+#[test]
+fn test_draw() {
+    use utils::canvas::PixelCanvas;
+
+    let mut canvas = PixelCanvas::new(35, 25);
+    draw(&mut canvas);
+    // (-1, 0) is in the set, but (1, 1) isn't
+    assert_eq!(canvas.get(15, 12), Color::WHITE);
+    assert!(canvas.get(34, 2) != Color::WHITE);
+    // The set is symmetric about the real axis
+    for x in 0..35 {
+        assert_eq!(canvas.get(x, 1), canvas.get(x, 24));
+    }
+}
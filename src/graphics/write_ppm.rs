@@ -0,0 +1,69 @@
+//! Implements http://rosettacode.org/wiki/Write_ppm_file
+//!
+//! The writing is done by utils::image, which saves the other graphics tasks' images as well.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::write_ppm::red_and_blue;
+//!
+//! let mut ppm = Vec::new();
+//! red_and_blue().write_ppm(&mut ppm).unwrap();
+//! assert!(ppm.starts_with(b"P6\n64 64\n255\n"));
+//! // The first pixel is red, and the last is blue
+//! assert_eq!(ppm[13..16], [255, 0, 0]);
+//! assert_eq!(ppm[ppm.len() - 3..], [0, 0, 255]);
+//! ```
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::canvas::{Canvas, Color};
+use utils::image::Image;
+
+// A 64x64 image, the left side of which is red, and the right side of which is blue
+pub fn red_and_blue() -> Image {
+    let mut image = Image::new(64, 64);
+    image.fill(Color::rgb(255, 0, 0));
+    for y in 0..64 {
+        for x in 32..64 {
+            image.set(x, y, Color::rgb(0, 0, 255));
+        }
+    }
+    image
+}
+
+#[cfg(not(test))]
+pub fn main() {
+    let file = std::fs::File::create("./test_image.ppm").unwrap();
+    red_and_blue().write_ppm(std::io::BufWriter::new(file)).unwrap();
+}
+
+#[cfg(test)]
+mod test {
+    use super::red_and_blue;
+    use std::env;
+    use std::fs::{self, File};
+    use utils::canvas::{Canvas, Color};
+    use utils::image::Image;
+
+    #[test]
+    fn write_ppm() {
+        let mut image = Image::new(2, 1);
+        image.set(0, 0, Color::rgb(1, 2, 3));
+        image.set(1, 0, Color::rgb(4, 5, 6));
+        let fname = env::temp_dir().join(format!("test-{}.ppm", std::process::id()));
+        image.write_ppm(File::create(&fname).unwrap()).unwrap();
+
+        let written = fs::read(&fname).unwrap();
+        fs::remove_file(&fname).unwrap();
+        assert_eq!(written, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+
+    #[test]
+    fn halves() {
+        let image = red_and_blue();
+        assert_eq!(image.get(31, 63), Color::rgb(255, 0, 0));
+        assert_eq!(image.get(32, 0), Color::rgb(0, 0, 255));
+        assert_eq!(image.to_ppm().len(), "P6\n64 64\n255\n".len() + 64 * 64 * 3);
+    }
+}
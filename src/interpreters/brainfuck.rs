@@ -0,0 +1,372 @@
+//! http://rosettacode.org/wiki/Execute_Brain****
+//!
+//! The program is parsed first, into its eight instructions (anything else is a comment), with
+//! each bracket given the position of its partner, so an unmatched bracket is found before the
+//! program starts rather than when it gets there, and a jump doesn't have to scan for its partner.
+//! The tape starts out as a single cell and grows in either direction as the program moves off its
+//! ends.  What happens when a cell goes past 0 or 255, and what reading at the end of the input
+//! does, differ from one implementation to the next, so both are configurable.
+//!
+//! ```text
+//!     brainfuck [--cells wrap|saturate|checked] [--eof unchanged|zero|max] [FILE]
+//! ```
+//!
+//! Without a file, it runs Hello World.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::brainfuck::{parse, run, Cells, Config, Error};
+//!
+//! // 8 times 8, plus 1
+//! let program = parse("++++++++[>++++++++<-]>+.").unwrap();
+//! let mut output = Vec::new();
+//! run(&program, Config::default(), &b""[..], &mut output).unwrap();
+//! assert_eq!(output, b"A");
+//!
+//! // A cell that goes below 0 is an error, if they're checked
+//! let config = Config { cells: Cells::Checked, ..Config::default() };
+//! let checked = run(&parse("+--").unwrap(), config, &b""[..], Vec::new());
+//! assert!(matches!(checked, Err(Error::Overflow(2))));
+//! assert!(matches!(parse("[[]"), Err(Error::Unmatched('[', 0))));
+//! ```
+
+use std::collections::VecDeque;
+use std::error;
+use std::fmt;
+#[cfg(not(test))]
+use std::fs;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::args::Args;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Left,
+    Right,
+    Increment,
+    Decrement,
+    Output,
+    Input,
+    Open(usize),  // Jumps past the matching Close, at the given index, if the cell is zero
+    Close(usize), // Jumps back past the matching Open, unless the cell is zero
+}
+
+// A parsed program: its instructions, and where in the source each came from
+#[derive(Debug)]
+pub struct Program {
+    ops: Vec<Op>,
+    positions: Vec<usize>,
+}
+
+// What a cell does when it's incremented past 255 or decremented past 0
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Cells {
+    #[default]
+    Wrap,     // Goes round to 0 or 255
+    Saturate, // Stays where it is
+    Checked,  // Stops the program with an error
+}
+
+// What an input instruction does at the end of the input
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Eof {
+    #[default]
+    Unchanged, // Leaves the cell as it was
+    Zero,      // Sets it to 0
+    Max,       // Sets it to 255, the nearest there is to C's -1
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Config {
+    pub cells: Cells,
+    pub eof: Eof,
+}
+
+impl FromStr for Cells {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Cells, ()> {
+        match s {
+            "wrap" => Ok(Cells::Wrap),
+            "saturate" => Ok(Cells::Saturate),
+            "checked" => Ok(Cells::Checked),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromStr for Eof {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Eof, ()> {
+        match s {
+            "unchanged" => Ok(Eof::Unchanged),
+            "zero" => Ok(Eof::Zero),
+            "max" => Ok(Eof::Max),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unmatched(char, usize), // A bracket without a partner, and its position in the source
+    Overflow(usize),        // A cell went out of range with Cells::Checked, at that position
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Unmatched(bracket, at) => write!(f, "unmatched '{}' at {}", bracket, at),
+            Error::Overflow(at) => write!(f, "cell out of range at {}", at),
+            Error::Io(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+// So that tasks built on this can hand its errors on as their own
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        match err {
+            Error::Io(err) => err,
+            err => io::Error::new(io::ErrorKind::InvalidData, err.to_string()),
+        }
+    }
+}
+
+pub fn parse(source: &str) -> Result<Program, Error> {
+    let mut program = Program { ops: Vec::new(), positions: Vec::new() };
+    let mut open = Vec::new(); // The indexes of the Opens still to be matched
+    for (at, c) in source.char_indices() {
+        let op = match c {
+            '<' => Op::Left,
+            '>' => Op::Right,
+            '+' => Op::Increment,
+            '-' => Op::Decrement,
+            '.' => Op::Output,
+            ',' => Op::Input,
+            '[' => {
+                open.push(program.ops.len());
+                Op::Open(0) // Filled in once the Close is found
+            }
+            ']' => {
+                let partner = open.pop().ok_or(Error::Unmatched(']', at))?;
+                program.ops[partner] = Op::Open(program.ops.len());
+                Op::Close(partner)
+            }
+            _ => continue,
+        };
+        program.ops.push(op);
+        program.positions.push(at);
+    }
+    match open.pop() {
+        Some(unmatched) => Err(Error::Unmatched('[', program.positions[unmatched])),
+        None => Ok(program),
+    }
+}
+
+// Run `program`, reading its input from `input` and writing its output to `output`
+pub fn run(program: &Program, config: Config, input: impl Read, mut output: impl Write)
+           -> Result<(), Error> {
+    let mut tape = VecDeque::from(vec![0u8]);
+    let mut cell = 0;
+    let mut bytes = io::BufReader::new(input).bytes();
+    let mut pc = 0;
+    while pc < program.ops.len() {
+        match program.ops[pc] {
+            Op::Left if cell == 0 => tape.push_front(0),
+            Op::Left => cell -= 1,
+            Op::Right => {
+                cell += 1;
+                if cell == tape.len() {
+                    tape.push_back(0);
+                }
+            }
+            Op::Increment | Op::Decrement => {
+                let value = tape[cell];
+                let (changed, overflowed) = if program.ops[pc] == Op::Increment {
+                    value.overflowing_add(1)
+                } else {
+                    value.overflowing_sub(1)
+                };
+                tape[cell] = match (overflowed, config.cells) {
+                    (false, _) | (true, Cells::Wrap) => changed,
+                    (true, Cells::Saturate) => value,
+                    (true, Cells::Checked) => return Err(Error::Overflow(program.positions[pc])),
+                };
+            }
+            Op::Output => output.write_all(&[tape[cell]])?,
+            Op::Input => {
+                // Whatever the program has written should be seen before it waits for input
+                output.flush()?;
+                match (bytes.next().transpose()?, config.eof) {
+                    (Some(byte), _) => tape[cell] = byte,
+                    (None, Eof::Unchanged) => {}
+                    (None, Eof::Zero) => tape[cell] = 0,
+                    (None, Eof::Max) => tape[cell] = 255,
+                }
+            }
+            Op::Open(close) if tape[cell] == 0 => pc = close,
+            Op::Close(open) if tape[cell] != 0 => pc = open,
+            Op::Open(_) | Op::Close(_) => {}
+        }
+        pc += 1;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+// Prints "Hello World!" and a newline
+static HELLO_WORLD: &str = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.\
+                            >>.<-.<.+++.------.--------.>>+.>++.";
+
+fn parse_args(mut args: Args) -> Result<(Config, Option<String>), String> {
+    let mut config = Config::default();
+    if let Some(cells) = args.value("--cells")? {
+        config.cells = cells;
+    }
+    if let Some(eof) = args.value("--eof")? {
+        config.eof = eof;
+    }
+    let mut files = args.free()?;
+    if files.len() > 1 {
+        return Err("only one program can be run at a time".to_string());
+    }
+    Ok((config, files.pop()))
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (config, file) = parse_args(Args::from_env()).unwrap_or_else(|message| {
+        utils::args::exit_with_usage(&message, "brainfuck [--cells wrap|saturate|checked] \
+                                                [--eof unchanged|zero|max] [FILE]")
+    });
+    let source = match file {
+        Some(file) => fs::read_to_string(&file).unwrap_or_else(|error| {
+            eprintln!("can't read {}: {}", file, error);
+            std::process::exit(1)
+        }),
+        None => HELLO_WORLD.to_string(),
+    };
+    let stdin = io::stdin();
+    if let Err(err) = parse(&source).and_then(|program| {
+        run(&program, config, stdin.lock(), io::stdout())
+    }) {
+        eprintln!("brainfuck: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, parse_args, run, Cells, Config, Eof, Error, HELLO_WORLD};
+    use std::io;
+    use utils::args::Args;
+    use utils::capture::output_of;
+
+    // Run `source` on `input`, and return its output, which needn't be text
+    fn execute(source: &str, config: Config, input: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut output = Vec::new();
+        run(&parse(source)?, config, input, &mut output)?;
+        Ok(output)
+    }
+
+    #[test]
+    fn hello_world() {
+        let program = parse(HELLO_WORLD).unwrap();
+        assert_eq!(output_of(|input, output| {
+            Ok(run(&program, Config::default(), input, output)?)
+        }, ""), "Hello World!\n");
+    }
+
+    #[test]
+    fn programs() {
+        let config = Config { eof: Eof::Zero, ..Config::default() };
+        // Reverses its input, which takes the end of it to read as 0
+        let reverse = "Reverse: >,[>,]<[.<]";
+        assert_eq!(execute(reverse, config, b"stressed").unwrap(), b"desserts");
+        // Multiplies two bytes, in nested loops: for each of the first, the second is added into
+        // the product and a copy, which is then moved back
+        let multiply = ",>,<[>[>+>+<<-]>>[<<+>>-]<<<-]>>.";
+        assert_eq!(execute(multiply, config, &[7, 9]).unwrap(), vec![63]);
+        assert_eq!(execute(multiply, config, &[0, 9]).unwrap(), vec![0]);
+        // Prints each byte of its input in decimal, up to a zero byte, by dividing it by ten
+        // twice, with the divmod algorithm from esolangs.org: n 10 0 0 0 becomes
+        // 0 (10 - n % 10) (n % 10) (n / 10)
+        let divmod = "[->-[>+>>]>[+[-<+>]>+>>]<<<<<]";
+        let decimal = format!(",[>++++++++++<{divmod}>[-]>>>++++++++++<{divmod}>[-]\
+                               >>>++++++++[<++++++>-]<.\
+                               >++++++++[<<++++++>>-]<<.\
+                               >>++++++++[<<<<<++++++>>>>>-]<<<<<.\
+                               >>>>>++++++++++.[-]<[-]<[-]<<<[-]<<,]");
+        assert_eq!(execute(&decimal, config, &[47, 7, 255, 100, 10]).unwrap(),
+                   b"047\n007\n255\n100\n010\n");
+        assert_eq!(execute(&decimal, config, &[1, 0, 2]).unwrap(), b"001\n");
+    }
+
+    #[test]
+    fn tape_grows_both_ways() {
+        assert_eq!(execute("<<<+++.>>>>>>>>>>++.<<<<<<<<<<.", Config::default(), b"").unwrap(),
+                   vec![3, 2, 3]);
+        assert_eq!(execute(&">".repeat(100_000), Config::default(), b"").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn cells() {
+        let config = |cells| Config { cells, ..Config::default() };
+        assert_eq!(execute("-.+.", config(Cells::Wrap), b"").unwrap(), vec![255, 0]);
+        assert_eq!(execute("-.+.", config(Cells::Saturate), b"").unwrap(), vec![0, 1]);
+        match execute("+-ab-", config(Cells::Checked), b"") {
+            Err(Error::Overflow(4)) => {}
+            result => panic!("{:?}", result),
+        }
+        let up = format!("{}.+.", "+".repeat(255));
+        assert_eq!(execute(&up, config(Cells::Wrap), b"").unwrap(), vec![255, 0]);
+        assert_eq!(execute(&up, config(Cells::Saturate), b"").unwrap(), vec![255, 255]);
+    }
+
+    #[test]
+    fn end_of_input() {
+        let config = |eof| Config { eof, ..Config::default() };
+        assert_eq!(execute("+++,.,.", config(Eof::Unchanged), b"a").unwrap(), b"aa");
+        assert_eq!(execute("+++,.", config(Eof::Unchanged), b"").unwrap(), vec![3]);
+        assert_eq!(execute("+++,.", config(Eof::Zero), b"").unwrap(), vec![0]);
+        assert_eq!(execute("+++,.", config(Eof::Max), b"").unwrap(), vec![255]);
+    }
+
+    #[test]
+    fn unmatched() {
+        let error = |source| parse(source).unwrap_err().to_string();
+        assert_eq!(error("+[[-]"), "unmatched '[' at 1");
+        assert_eq!(error("+[-]]"), "unmatched ']' at 4");
+        assert_eq!(error("é]"), "unmatched ']' at 2");
+        let err: io::Error = parse("[").unwrap_err().into();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn arguments() {
+        let args = |args: &[&str]| parse_args(Args::new(args.iter().map(|arg| arg.to_string())));
+        assert_eq!(args(&[]), Ok((Config::default(), None)));
+        let config = Config { cells: Cells::Checked, eof: Eof::Zero };
+        assert_eq!(args(&["--cells=checked", "--eof", "zero", "cat.bf"]),
+                   Ok((config, Some("cat.bf".to_string()))));
+        assert_eq!(args(&["--cells", "big"]), Err("invalid value for --cells: big".to_string()));
+        assert_eq!(args(&["a.bf", "b.bf"]),
+                   Err("only one program can be run at a time".to_string()));
+    }
+}
@@ -0,0 +1,570 @@
+//! A minimal Lisp, which goes on from reading S-expressions (s_expressions.rs) to evaluating them.
+//!
+//! An expression is evaluated in an environment: a frame of variables, and the environment the
+//! frame was made in, which is looked in for anything the frame doesn't have.  Calling a lambda
+//! makes a frame for its parameters inside the environment the lambda was made in, so variables
+//! are scoped lexically, and a lambda is a closure over the variables around it.
+//!
+//! The special forms are
+//!
+//! ```text
+//!     (quote x), or 'x          x, without evaluating it
+//!     (if test then else)       then, unless test is #f, in which case else, or () without one
+//!     (define name value)       sets name in the innermost frame, giving the name
+//!     (define (name x...) ...)  the same as (define name (lambda (x...) ...))
+//!     (lambda (x...) body...)   a procedure, which gives the value of the last of its body
+//! ```
+//!
+//! and the builtins are arithmetic (+ - * /) on integers and floats, comparisons (= < > <= >=),
+//! lists (car cdr cons list null?), equal? and not.  Integers are 64 bits, and overflowing them is
+//! an error.  Only #f is false.  Calls in tail position don't use up any stack, so a loop can be
+//! written as a recursive procedure, but other recursion only goes MAX_DEPTH deep.
+//!
+//! ```text
+//!     tiny_lisp [FILE]
+//! ```
+//!
+//! It runs the program in the file, writing the value of each expression at the top level, or
+//! without one, reads expressions a line at a time and writes their values, as a REPL.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::tiny_lisp::run;
+//!
+//! let program = "(define (factorial n) (if (< n 2) 1 (* n (factorial (- n 1)))))\n(factorial 10)";
+//! let mut output = Vec::new();
+//! run(program.as_bytes(), &mut output).unwrap();
+//! assert_eq!(output, b"factorial\n3628800\n");
+//! assert!(run(&b"(car 6)"[..], Vec::new()).is_err());
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(not(test))]
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+use std::rc::Rc;
+
+#[allow(dead_code)]
+#[path = "../strings/s_expressions.rs"]
+mod s_expressions;
+
+use self::s_expressions::Value;
+
+// How deeply evaluation can recurse, other than by tail calls, before it's an error rather than
+// running out of stack
+pub const MAX_DEPTH: usize = 500;
+
+type Builtin = fn(&[Object]) -> Result<Object, String>;
+
+#[derive(Clone)]
+pub enum Object {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Symbol(String),
+    List(Vec<Object>),
+    Lambda(Rc<Lambda>),
+    Builtin(&'static str, Builtin),
+}
+
+pub struct Lambda {
+    parameters: Vec<String>,
+    body: Vec<Object>,
+    env: Env,
+}
+
+// An environment, which lambdas made in it share
+#[derive(Clone)]
+pub struct Env(Rc<RefCell<Frame>>);
+
+struct Frame {
+    variables: HashMap<String, Object>,
+    outer: Option<Env>,
+}
+
+impl Env {
+    // The environment at the top level, with the builtins in it
+    pub fn global() -> Env {
+        let env = Env::inside(None);
+        for &(name, builtin) in BUILTINS {
+            env.define(name, Object::Builtin(name, builtin));
+        }
+        env.define("#t", Object::Bool(true));
+        env.define("#f", Object::Bool(false));
+        env
+    }
+
+    fn inside(outer: Option<Env>) -> Env {
+        Env(Rc::new(RefCell::new(Frame { variables: HashMap::new(), outer })))
+    }
+
+    fn get(&self, name: &str) -> Option<Object> {
+        let frame = self.0.borrow();
+        match frame.variables.get(name) {
+            Some(value) => Some(value.clone()),
+            None => frame.outer.as_ref().and_then(|outer| outer.get(name)),
+        }
+    }
+
+    fn define(&self, name: &str, value: Object) {
+        self.0.borrow_mut().variables.insert(name.to_string(), value);
+    }
+}
+
+impl From<&Value> for Object {
+    fn from(value: &Value) -> Object {
+        match *value {
+            Value::Atom(ref name) => Object::Symbol(name.clone()),
+            Value::String(ref text) => Object::String(text.clone()),
+            Value::Int(n) => Object::Int(n),
+            Value::Float(x) => Object::Float(x),
+            Value::List(ref items) => Object::List(items.iter().map(Object::from).collect()),
+        }
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Object::Int(n) => write!(f, "{}", Value::Int(n)),
+            Object::Float(x) => write!(f, "{}", Value::Float(x)),
+            Object::Bool(b) => write!(f, "{}", if b { "#t" } else { "#f" }),
+            Object::String(ref text) => write!(f, "{}", Value::String(text.clone())),
+            Object::Symbol(ref name) => write!(f, "{}", name),
+            Object::List(ref items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+            Object::Lambda(_) => write!(f, "#<lambda>"),
+            Object::Builtin(name, _) => write!(f, "#<builtin {}>", name),
+        }
+    }
+}
+
+// The arguments of builtin `name`, if there are `count` of them
+fn arguments<'a>(name: &str, args: &'a [Object], count: usize) -> Result<&'a [Object], String> {
+    if args.len() == count {
+        Ok(args)
+    } else {
+        Err(format!("{} takes {} arguments, not {}", name, count, args.len()))
+    }
+}
+
+fn float(name: &str, x: &Object) -> Result<f64, String> {
+    match *x {
+        Object::Int(n) => Ok(n as f64),
+        Object::Float(x) => Ok(x),
+        ref x => Err(format!("{} needs numbers, not {}", name, x)),
+    }
+}
+
+// `args` combined from left to right by `ints`, while they're integers that it doesn't overflow
+// on, and `floats` once there's a float among them.  One argument is combined with `identity`.
+fn arithmetic(name: &str, args: &[Object], identity: i64, ints: fn(i64, i64) -> Option<i64>,
+              floats: fn(f64, f64) -> f64)
+              -> Result<Object, String> {
+    let (first, rest) = match args.len() {
+        0 => return Ok(Object::Int(identity)),
+        1 => (Object::Int(identity), args),
+        _ => (args[0].clone(), &args[1..]),
+    };
+    float(name, &first)?;
+    rest.iter().try_fold(first, |left, right| match (&left, right) {
+        (&Object::Int(a), &Object::Int(b)) => {
+            ints(a, b).map(Object::Int).ok_or_else(|| format!("integer overflow in {}", name))
+        }
+        _ => Ok(Object::Float(floats(float(name, &left)?, float(name, right)?))),
+    })
+}
+
+fn add(args: &[Object]) -> Result<Object, String> {
+    arithmetic("+", args, 0, i64::checked_add, |a, b| a + b)
+}
+
+fn subtract(args: &[Object]) -> Result<Object, String> {
+    if args.is_empty() {
+        return Err("- needs an argument".to_string());
+    }
+    arithmetic("-", args, 0, i64::checked_sub, |a, b| a - b)
+}
+
+fn multiply(args: &[Object]) -> Result<Object, String> {
+    arithmetic("*", args, 1, i64::checked_mul, |a, b| a * b)
+}
+
+// An integer divided by one it's a multiple of stays an integer, but otherwise it's a float
+fn divide(args: &[Object]) -> Result<Object, String> {
+    let (first, rest) = match args.len() {
+        0 => return Err("/ needs an argument".to_string()),
+        1 => (Object::Int(1), args),
+        _ => (args[0].clone(), &args[1..]),
+    };
+    float("/", &first)?;
+    rest.iter().try_fold(first, |left, right| {
+        if float("/", right)? == 0.0 {
+            return Err("division by zero".to_string());
+        }
+        match (&left, right) {
+            (&Object::Int(a), &Object::Int(b)) if a.checked_rem(b) == Some(0) => {
+                Ok(Object::Int(a / b))
+            }
+            _ => Ok(Object::Float(float("/", &left)? / float("/", right)?)),
+        }
+    })
+}
+
+// Whether each of `args` is related to the next one as `test` says, comparing them as floats
+fn compare(name: &str, args: &[Object], test: fn(f64, f64) -> bool) -> Result<Object, String> {
+    let numbers = args.iter().map(|x| float(name, x)).collect::<Result<Vec<f64>, _>>()?;
+    Ok(Object::Bool(numbers.windows(2).all(|pair| test(pair[0], pair[1]))))
+}
+
+// Whether `a` and `b` are the same data, or the same procedure
+fn equal(a: &Object, b: &Object) -> bool {
+    match (a, b) {
+        (&Object::Int(a), &Object::Int(b)) => a == b,
+        (&Object::Float(a), &Object::Float(b)) => a == b,
+        (&Object::Bool(a), &Object::Bool(b)) => a == b,
+        (Object::String(a), Object::String(b)) => a == b,
+        (Object::Symbol(a), Object::Symbol(b)) => a == b,
+        (Object::List(a), Object::List(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equal(a, b))
+        }
+        (Object::Lambda(a), Object::Lambda(b)) => Rc::ptr_eq(a, b),
+        (&Object::Builtin(a, _), &Object::Builtin(b, _)) => a == b,
+        _ => false,
+    }
+}
+
+fn list<'a>(name: &str, x: &'a Object) -> Result<&'a [Object], String> {
+    match *x {
+        Object::List(ref items) => Ok(items),
+        ref x => Err(format!("{} needs a list, not {}", name, x)),
+    }
+}
+
+static BUILTINS: &[(&str, Builtin)] = &[
+    ("+", add),
+    ("-", subtract),
+    ("*", multiply),
+    ("/", divide),
+    ("=", |args| compare("=", args, |a, b| a == b)),
+    ("<", |args| compare("<", args, |a, b| a < b)),
+    (">", |args| compare(">", args, |a, b| a > b)),
+    ("<=", |args| compare("<=", args, |a, b| a <= b)),
+    (">=", |args| compare(">=", args, |a, b| a >= b)),
+    ("car", |args| {
+        let items = list("car", &arguments("car", args, 1)?[0])?;
+        items.first().cloned().ok_or_else(|| "car of ()".to_string())
+    }),
+    ("cdr", |args| {
+        let items = list("cdr", &arguments("cdr", args, 1)?[0])?;
+        if items.is_empty() {
+            return Err("cdr of ()".to_string());
+        }
+        Ok(Object::List(items[1..].to_vec()))
+    }),
+    ("cons", |args| {
+        let args = arguments("cons", args, 2)?;
+        let mut items = vec![args[0].clone()];
+        items.extend_from_slice(list("cons", &args[1])?);
+        Ok(Object::List(items))
+    }),
+    ("list", |args| Ok(Object::List(args.to_vec()))),
+    ("null?", |args| Ok(Object::Bool(list("null?", &arguments("null?", args, 1)?[0])?.is_empty()))),
+    ("equal?", |args| {
+        let args = arguments("equal?", args, 2)?;
+        Ok(Object::Bool(equal(&args[0], &args[1])))
+    }),
+    ("not", |args| Ok(Object::Bool(equal(&arguments("not", args, 1)?[0], &Object::Bool(false))))),
+];
+
+// The arguments of special form `name`, which has between `least` and `most` of them
+fn form<'a>(name: &str, items: &'a [Object], least: usize, most: usize)
+            -> Result<&'a [Object], String> {
+    let args = &items[1..];
+    if args.len() < least || args.len() > most {
+        return Err(format!("bad {}: {}", name, Object::List(items.to_vec())));
+    }
+    Ok(args)
+}
+
+fn lambda(parameters: &Object, body: &[Object], env: &Env) -> Result<Object, String> {
+    let parameters = match *parameters {
+        Object::List(ref names) => names.iter().map(|name| match *name {
+            Object::Symbol(ref name) => Ok(name.clone()),
+            ref x => Err(format!("a lambda's parameters have to be symbols, not {}", x)),
+        }).collect::<Result<_, _>>()?,
+        ref x => return Err(format!("a lambda's parameters have to be a list, not {}", x)),
+    };
+    Ok(Object::Lambda(Rc::new(Lambda { parameters, body: body.to_vec(), env: env.clone() })))
+}
+
+// The value of `expression` in `env`
+pub fn eval(expression: &Object, env: &Env) -> Result<Object, String> {
+    evaluate(expression, env, 0)
+}
+
+// What evaluating an expression comes to: its value, or for a tail call, another expression to
+// evaluate in its place
+enum Step {
+    Value(Object),
+    Tail(Object, Env),
+}
+
+fn evaluate(expression: &Object, env: &Env, depth: usize) -> Result<Object, String> {
+    if depth == MAX_DEPTH {
+        return Err("too deep a recursion".to_string());
+    }
+    // A tail call goes round this loop again, rather than recursing
+    let mut next = step(expression, env, depth)?;
+    loop {
+        next = match next {
+            Step::Value(value) => return Ok(value),
+            Step::Tail(expression, env) => step(&expression, &env, depth)?,
+        };
+    }
+}
+
+// Each form has a function of its own, and so does applying a procedure, so that a recursive
+// call doesn't have what all of them need on the stack at once
+fn step(expression: &Object, env: &Env, depth: usize) -> Result<Step, String> {
+    let items = match *expression {
+        Object::Symbol(ref name) => {
+            return env.get(name).map(Step::Value).ok_or_else(|| format!("{} isn't defined", name));
+        }
+        Object::List(ref items) if !items.is_empty() => items,
+        ref x => return Ok(Step::Value(x.clone())),
+    };
+    let special = match items[0] {
+        Object::Symbol(ref name) => name.as_str(),
+        _ => "",
+    };
+    match special {
+        "quote" => Ok(Step::Value(form("quote", items, 1, 1)?[0].clone())),
+        "if" => if_form(items, env, depth),
+        "define" => define_form(items, env, depth).map(Step::Value),
+        "lambda" => {
+            let args = form("lambda", items, 2, usize::MAX)?;
+            lambda(&args[0], &args[1..], env).map(Step::Value)
+        }
+        _ => call(items, env, depth),
+    }
+}
+
+fn if_form(items: &[Object], env: &Env, depth: usize) -> Result<Step, String> {
+    let args = form("if", items, 2, 3)?;
+    let test = evaluate(&args[0], env, depth + 1)?;
+    Ok(match (equal(&test, &Object::Bool(false)), args.get(2)) {
+        (false, _) => Step::Tail(args[1].clone(), env.clone()),
+        (true, Some(otherwise)) => Step::Tail(otherwise.clone(), env.clone()),
+        (true, None) => Step::Value(Object::List(vec![])),
+    })
+}
+
+fn define_form(items: &[Object], env: &Env, depth: usize) -> Result<Object, String> {
+    let args = form("define", items, 2, usize::MAX)?;
+    let (name, value) = match args[0] {
+        Object::Symbol(ref name) if args.len() == 2 => (name, evaluate(&args[1], env, depth + 1)?),
+        Object::List(ref signature) if !signature.is_empty() => {
+            let name = match signature[0] {
+                Object::Symbol(ref name) => name,
+                ref x => return Err(format!("can't define {}", x)),
+            };
+            let parameters = Object::List(signature[1..].to_vec());
+            (name, lambda(&parameters, &args[1..], env)?)
+        }
+        _ => return Err(format!("bad define: {}", Object::List(items.to_vec()))),
+    };
+    env.define(name, value);
+    Ok(Object::Symbol(name.clone()))
+}
+
+// Call a procedure, with a lambda's last expression left as a tail call
+fn call(items: &[Object], env: &Env, depth: usize) -> Result<Step, String> {
+    let procedure = evaluate(&items[0], env, depth + 1)?;
+    let mut args = Vec::with_capacity(items.len() - 1);
+    for arg in &items[1..] {
+        args.push(evaluate(arg, env, depth + 1)?);
+    }
+    apply(procedure, args, depth)
+}
+
+// The rest of a call, once what's called and its arguments have been evaluated
+fn apply(procedure: Object, args: Vec<Object>, depth: usize) -> Result<Step, String> {
+    let lambda = match procedure {
+        Object::Builtin(_, builtin) => return builtin(&args).map(Step::Value),
+        Object::Lambda(lambda) => lambda,
+        x => return Err(format!("{} isn't a procedure", x)),
+    };
+    if args.len() != lambda.parameters.len() {
+        return Err(format!("a lambda of {} arguments called with {}",
+                           lambda.parameters.len(), args.len()));
+    }
+    let frame = Env::inside(Some(lambda.env.clone()));
+    for (name, arg) in lambda.parameters.iter().zip(args) {
+        frame.define(name, arg);
+    }
+    let (last, rest) = lambda.body.split_last().unwrap();
+    for expression in rest {
+        evaluate(expression, &frame, depth + 1)?;
+    }
+    Ok(Step::Tail(last.clone(), frame))
+}
+
+// Evaluate each expression in `text` in `env`, writing its value to `output`
+fn evaluate_all(text: &str, env: &Env, mut output: impl Write) -> io::Result<()> {
+    let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+    for value in s_expressions::read_all(text).map_err(invalid)? {
+        let result = eval(&Object::from(&value), env).map_err(invalid)?;
+        writeln!(output, "{}", result)?;
+    }
+    Ok(())
+}
+
+// Run the program in `input`, writing the value of each expression in it to `output`
+pub fn run(mut input: impl Read, output: impl Write) -> io::Result<()> {
+    let mut program = String::new();
+    input.read_to_string(&mut program)?;
+    evaluate_all(&program, &Env::global(), output)
+}
+
+// Read expressions from `input` a line at a time, writing their values, or what's wrong with
+// them, to `output`
+pub fn repl(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let env = Env::global();
+    write!(output, "> ")?;
+    output.flush()?;
+    for line in input.lines() {
+        if let Err(error) = evaluate_all(&line?, &env, &mut output) {
+            writeln!(output, "error: {}", error)?;
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+    writeln!(output)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let result = match std::env::args().nth(1) {
+        Some(file) => File::open(&file).and_then(|file| run(file, io::stdout())),
+        None => {
+            let stdin = io::stdin();
+            repl(stdin.lock(), io::stdout())
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("tiny_lisp: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::s_expressions::read;
+    use super::s_expressions::utils::capture::output_of;
+    use super::{eval, repl, run, Env, Object};
+
+    // The value of each expression in `program`, evaluated in turn in the same environment
+    fn values(program: &str) -> Vec<String> {
+        let env = Env::global();
+        program.lines()
+            .map(|line| match eval(&Object::from(&read(line).unwrap()), &env) {
+                Ok(value) => value.to_string(),
+                Err(message) => format!("error: {}", message),
+            })
+            .collect()
+    }
+
+    fn value(program: &str) -> String {
+        values(program).pop().unwrap()
+    }
+
+    #[test]
+    fn special_forms() {
+        assert_eq!(values("(quote (a b))\n'c\n(if 0 'yes 'no)\n(if #f 'yes 'no)\n(if #f 'yes)"),
+                   ["(a b)", "c", "yes", "no", "()"]);
+        assert_eq!(values("(define x 3)\nx\n(define (f) x)\n(f)\n((lambda (x y) y x) 1 2)"),
+                   ["x", "3", "f", "3", "1"]);
+    }
+
+    #[test]
+    fn builtins() {
+        assert_eq!(values("(+ 1 2 3)\n(- 10 4 3)\n(- 5)\n(* 1.5 4)\n(/ 12 4)\n(/ 7 2)\n(/ 2)"),
+                   ["6", "3", "-5", "6.0", "3", "3.5", "0.5"]);
+        assert_eq!(values("(< 1 2 3)\n(< 1 3 2)\n(= 2 2.0)\n(>= 3 3 1)"),
+                   ["#t", "#f", "#t", "#t"]);
+        assert_eq!(values("(car '(1 2))\n(cdr '(1 2))\n(cons 0 '(1))\n(list 1 \"a\" 'b)"),
+                   ["1", "(2)", "(0 1)", "(1 \"a\" b)"]);
+        assert_eq!(values("(null? '())\n(equal? '(1 (2)) (list 1 (list 2)))\n(not 0)\n(not #f)"),
+                   ["#t", "#t", "#f", "#t"]);
+        assert_eq!(values("+\n(lambda (x) x)"), ["#<builtin +>", "#<lambda>"]);
+    }
+
+    #[test]
+    fn scoping() {
+        // Variables are looked up where a lambda was made, not where it's called
+        assert_eq!(value("(define x 'global)\n(define (get) x)\n(define (f x) (get))\n(f 'local)"),
+                   "global");
+        // A closure keeps the variables it was made with, and each has its own
+        let counter = "(define (adder n) (lambda (x) (+ x n)))\n\
+                       (define add2 (adder 2))\n(define add5 (adder 5))\n(list (add2 1) (add5 1))";
+        assert_eq!(value(counter), "(3 6)");
+        // A parameter hides the variable outside, and a define inside a lambda stays there
+        assert_eq!(values("(define y 1)\n((lambda (y) (define z y) z) 2)\ny\nz"),
+                   ["y", "2", "1", "error: z isn't defined"]);
+        // An inner procedure sees its outer one's parameters
+        let inner = "(define (outer a) (define (inner b) (+ a b)) (inner 10))\n(outer 5)";
+        assert_eq!(value(inner), "15");
+    }
+
+    #[test]
+    fn recursion() {
+        let factorial = "(define (fact n) (if (<= n 1) 1 (* n (fact (- n 1)))))\n(fact 20)";
+        assert_eq!(value(factorial), "2432902008176640000");
+        let fibonacci = "(define (fib n) (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2)))))\n\
+                         (fib 15)";
+        assert_eq!(value(fibonacci), "610");
+        let map = "(define (map f xs) (if (null? xs) '() (cons (f (car xs)) (map f (cdr xs)))))\n\
+                   (map (lambda (x) (* x x)) '(1 2 3))";
+        assert_eq!(value(map), "(1 4 9)");
+        // Tail calls don't use up any stack, however many there are
+        let count = "(define (count n total) (if (= n 0) total (count (- n 1) (+ total 1))))\n\
+                     (count 100000 0)";
+        assert_eq!(value(count), "100000");
+        // But other recursion can only go so deep
+        assert_eq!(value("(define (sum n) (if (= n 0) 0 (+ n (sum (- n 1)))))\n(sum 100000)"),
+                   "error: too deep a recursion");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(values("(undefined 1)\n(1 2)\n((lambda (x) x))\n(car '())\n(+ 1 'a)"),
+                   ["error: undefined isn't defined", "error: 1 isn't a procedure",
+                    "error: a lambda of 1 arguments called with 0", "error: car of ()",
+                    "error: + needs numbers, not a"]);
+        assert_eq!(values("(/ 1 0)\n(* 9223372036854775807 2)\n(if)\n(lambda (1) 1)"),
+                   ["error: division by zero", "error: integer overflow in *",
+                    "error: bad if: (if)",
+                    "error: a lambda's parameters have to be symbols, not 1"]);
+    }
+
+    #[test]
+    fn programs() {
+        assert_eq!(output_of(run, "(define (square x) (* x x))\n(square 12)\n\"done\"\n"),
+                   "square\n144\n\"done\"\n");
+        assert_eq!(output_of(repl, "(define x 6)\n(* x 7)\n(car x)\n(+ 1\n"),
+                   "> x\n> 42\n> error: car needs a list, not 6\n\
+                    > error: line 1, column 5: expected ')'\n> \n");
+    }
+}
@@ -0,0 +1,499 @@
+//! http://rosettacode.org/wiki/Universal_Turing_machine
+//!
+//! A machine is described by its states, its symbols (the first of which is the blank), the state
+//! it starts in, the states it halts in, and its rules, each of which says what to write, which
+//! way to move and which state to go to, given the state the machine is in and the symbol under
+//! the head.  The description is checked once, when the machine is made, and the rules put into a
+//! table.  The tape is unbounded both ways: it starts out as the input, or a single blank if
+//! there's none, and a blank is added at whichever end the head moves off.  Since a machine needn't
+//! ever halt, it's run for at most a given number of steps.
+//!
+//! ```text
+//!     universal_turing_machine [--trace] [--limit STEPS] [MACHINE]...
+//! ```
+//!
+//! It runs the given machines, or all of them, from the task: the simple incrementer and the
+//! three-state busy beaver, along with the four- and five-state busy beavers, a binary incrementer
+//! and a sorting machine.  With --trace it shows the tape after every step.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::universal_turing_machine::{Description, Machine, Move};
+//!
+//! // The task's simple incrementer, which adds a 1 to a number in unary
+//! let incrementer = Machine::new(&Description {
+//!     states: &["q0", "qf"],
+//!     symbols: &['B', '1'],
+//!     initial: "q0",
+//!     halting: &["qf"],
+//!     rules: &[("q0", '1', '1', Move::Right, "q0"), ("q0", 'B', '1', Move::Stay, "qf")],
+//! }).unwrap();
+//! let run = incrementer.run("111", 100).unwrap();
+//! assert_eq!((run.state.as_str(), run.steps), ("qf", 4));
+//! assert_eq!(run.tape.contents(), "1111");
+//! assert_eq!(run.tape.to_string(), "111[1]");
+//! assert!(incrementer.run("111", 2).is_err());
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::error;
+use std::fmt;
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::args::Args;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Move {
+    Left,
+    Right,
+    Stay,
+}
+
+// A rule: in `state`, reading `read`, write `write`, move, and go to `next`
+pub type Rule = (&'static str, char, char, Move, &'static str);
+
+// A machine as the task describes one, before it's checked
+pub struct Description {
+    pub states: &'static [&'static str],
+    pub symbols: &'static [char], // The first is the blank
+    pub initial: &'static str,
+    pub halting: &'static [&'static str],
+    pub rules: &'static [Rule],
+}
+
+pub struct Machine {
+    blank: char,
+    initial: &'static str,
+    halting: &'static [&'static str],
+    table: HashMap<(&'static str, char), (char, Move, &'static str)>,
+}
+
+impl Machine {
+    // Check that `description` is of a machine, and make it
+    pub fn new(description: &Description) -> Result<Machine, String> {
+        let state = |state: &str| {
+            if description.states.contains(&state) {
+                Ok(())
+            } else {
+                Err(format!("unknown state {}", state))
+            }
+        };
+        let symbol = |symbol: char| {
+            if description.symbols.contains(&symbol) {
+                Ok(())
+            } else {
+                Err(format!("unknown symbol {}", symbol))
+            }
+        };
+        let blank = *description.symbols.first().ok_or("a machine needs a blank symbol")?;
+        state(description.initial)?;
+        for &halting in description.halting {
+            state(halting)?;
+        }
+        let mut table = HashMap::new();
+        for &(from, read, write, direction, next) in description.rules {
+            state(from)?;
+            state(next)?;
+            symbol(read)?;
+            symbol(write)?;
+            if description.halting.contains(&from) {
+                return Err(format!("a rule for halting state {}", from));
+            }
+            if table.insert((from, read), (write, direction, next)).is_some() {
+                return Err(format!("two rules for reading {} in state {}", read, from));
+            }
+        }
+        Ok(Machine { blank, initial: description.initial, halting: description.halting, table })
+    }
+
+    // Run the machine on `input` for at most `limit` steps, calling `step` with each state it
+    // goes through and the tape at the time, starting with the initial ones
+    pub fn run_with<F>(&self, input: &str, limit: usize, mut step: F) -> Result<Run, Error>
+        where F: FnMut(&str, &Tape)
+    {
+        let mut tape = Tape::new(input, self.blank);
+        let mut state = self.initial;
+        let mut steps = 0;
+        step(state, &tape);
+        while !self.halting.contains(&state) {
+            if steps == limit {
+                return Err(Error::StepLimit(tape));
+            }
+            let read = tape.read();
+            let &(write, direction, next) = self.table.get(&(state, read))
+                .ok_or_else(|| Error::NoRule(state.to_string(), read))?;
+            tape.write(write);
+            tape.shift(direction);
+            state = next;
+            steps += 1;
+            step(state, &tape);
+        }
+        Ok(Run { state: state.to_string(), steps, tape })
+    }
+
+    pub fn run(&self, input: &str, limit: usize) -> Result<Run, Error> {
+        self.run_with(input, limit, |_, _| {})
+    }
+}
+
+// The tape, as much of it as the machine has been to
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tape {
+    cells: VecDeque<char>,
+    head: usize,
+    blank: char,
+}
+
+impl Tape {
+    fn new(input: &str, blank: char) -> Tape {
+        let mut cells: VecDeque<char> = input.chars().collect();
+        if cells.is_empty() {
+            cells.push_back(blank);
+        }
+        Tape { cells, head: 0, blank }
+    }
+
+    fn read(&self) -> char {
+        self.cells[self.head]
+    }
+
+    fn write(&mut self, symbol: char) {
+        self.cells[self.head] = symbol;
+    }
+
+    fn shift(&mut self, direction: Move) {
+        match direction {
+            Move::Left if self.head == 0 => self.cells.push_front(self.blank),
+            Move::Left => self.head -= 1,
+            Move::Right => {
+                self.head += 1;
+                if self.head == self.cells.len() {
+                    self.cells.push_back(self.blank);
+                }
+            }
+            Move::Stay => {}
+        }
+    }
+
+    // What's on the tape, from the first symbol that isn't a blank to the last
+    pub fn contents(&self) -> String {
+        let cells: String = self.cells.iter().collect();
+        cells.trim_matches(self.blank).to_string()
+    }
+}
+
+// Every cell the machine has been to, with the one under the head in brackets
+impl fmt::Display for Tape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, cell) in self.cells.iter().enumerate() {
+            if i == self.head {
+                write!(f, "[{}]", cell)?;
+            } else {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// How a machine that halted ended up
+#[derive(Debug, PartialEq)]
+pub struct Run {
+    pub state: String,
+    pub steps: usize,
+    pub tape: Tape,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    StepLimit(Tape),      // It was still going after the most steps it was allowed, leaving this
+    NoRule(String, char), // It read a symbol it had no rule for, in a state that doesn't halt
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::StepLimit(_) => write!(f, "didn't halt in time"),
+            Error::NoRule(ref state, read) => {
+                write!(f, "no rule for reading {} in state {}", read, state)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+// Adds a 1 to a number in unary
+static SIMPLE_INCREMENTER: Description = Description {
+    states: &["q0", "qf"],
+    symbols: &['B', '1'],
+    initial: "q0",
+    halting: &["qf"],
+    rules: &[
+        ("q0", '1', '1', Move::Right, "q0"),
+        ("q0", 'B', '1', Move::Stay, "qf"),
+    ],
+};
+
+static THREE_STATE_BUSY_BEAVER: Description = Description {
+    states: &["a", "b", "c", "halt"],
+    symbols: &['0', '1'],
+    initial: "a",
+    halting: &["halt"],
+    rules: &[
+        ("a", '0', '1', Move::Right, "b"),
+        ("a", '1', '1', Move::Left, "c"),
+        ("b", '0', '1', Move::Left, "a"),
+        ("b", '1', '1', Move::Right, "b"),
+        ("c", '0', '1', Move::Left, "b"),
+        ("c", '1', '1', Move::Stay, "halt"),
+    ],
+};
+
+static FOUR_STATE_BUSY_BEAVER: Description = Description {
+    states: &["A", "B", "C", "D", "H"],
+    symbols: &['0', '1'],
+    initial: "A",
+    halting: &["H"],
+    rules: &[
+        ("A", '0', '1', Move::Right, "B"),
+        ("A", '1', '1', Move::Left, "B"),
+        ("B", '0', '1', Move::Left, "A"),
+        ("B", '1', '0', Move::Left, "C"),
+        ("C", '0', '1', Move::Right, "H"),
+        ("C", '1', '1', Move::Left, "D"),
+        ("D", '0', '1', Move::Right, "D"),
+        ("D", '1', '0', Move::Right, "A"),
+    ],
+};
+
+// The task's bonus: it takes 47,176,870 steps
+static FIVE_STATE_BUSY_BEAVER: Description = Description {
+    states: &["A", "B", "C", "D", "E", "H"],
+    symbols: &['0', '1'],
+    initial: "A",
+    halting: &["H"],
+    rules: &[
+        ("A", '0', '1', Move::Right, "B"),
+        ("A", '1', '1', Move::Left, "C"),
+        ("B", '0', '1', Move::Right, "C"),
+        ("B", '1', '1', Move::Right, "B"),
+        ("C", '0', '1', Move::Right, "D"),
+        ("C", '1', '0', Move::Left, "E"),
+        ("D", '0', '1', Move::Left, "A"),
+        ("D", '1', '1', Move::Left, "D"),
+        ("E", '0', '1', Move::Stay, "H"),
+        ("E", '1', '0', Move::Left, "A"),
+    ],
+};
+
+// Adds 1 to a number in binary: it goes to the end of it, and carries back from there
+static BINARY_INCREMENTER: Description = Description {
+    states: &["right", "carry", "done"],
+    symbols: &['B', '0', '1'],
+    initial: "right",
+    halting: &["done"],
+    rules: &[
+        ("right", '0', '0', Move::Right, "right"),
+        ("right", '1', '1', Move::Right, "right"),
+        ("right", 'B', 'B', Move::Left, "carry"),
+        ("carry", '1', '0', Move::Left, "carry"),
+        ("carry", '0', '1', Move::Stay, "done"),
+        ("carry", 'B', '1', Move::Stay, "done"),
+    ],
+};
+
+// Sorts a run of a's and b's, the task's optional extra: each b that comes before an a is swapped
+// with the last a
+static SORTER: Description = Description {
+    states: &["A", "B", "C", "D", "E", "H"],
+    symbols: &['0', 'a', 'b', '*'],
+    initial: "A",
+    halting: &["H"],
+    rules: &[
+        ("A", 'a', 'a', Move::Right, "A"),
+        ("A", 'b', '*', Move::Right, "B"),
+        ("A", '0', '0', Move::Left, "E"),
+        ("B", 'a', 'a', Move::Right, "B"),
+        ("B", 'b', 'b', Move::Right, "B"),
+        ("B", '0', '0', Move::Left, "C"),
+        ("C", 'a', 'b', Move::Left, "D"),
+        ("C", 'b', 'b', Move::Left, "C"),
+        ("C", '*', 'b', Move::Stay, "H"),
+        ("D", 'a', 'a', Move::Left, "D"),
+        ("D", 'b', 'b', Move::Left, "D"),
+        ("D", '*', 'a', Move::Right, "A"),
+        ("E", 'a', 'a', Move::Left, "E"),
+        ("E", '0', '0', Move::Right, "H"),
+    ],
+};
+
+// Each of the machines, with its name and the input it's run on
+static MACHINES: &[(&str, &Description, &str)] = &[
+    ("simple_incrementer", &SIMPLE_INCREMENTER, "111"),
+    ("three_state_busy_beaver", &THREE_STATE_BUSY_BEAVER, ""),
+    ("four_state_busy_beaver", &FOUR_STATE_BUSY_BEAVER, ""),
+    ("five_state_busy_beaver", &FIVE_STATE_BUSY_BEAVER, ""),
+    ("binary_incrementer", &BINARY_INCREMENTER, "1011"),
+    ("sorter", &SORTER, "babbababaa"),
+];
+
+// Whether to trace the machines, the most steps to let them take, and which to run
+fn parse_args(mut args: Args) -> Result<(bool, usize, Vec<String>), String> {
+    let trace = args.flag("--trace");
+    let limit = args.value("--limit")?.unwrap_or(100_000_000);
+    let names: Vec<String> = args.free()?;
+    for name in &names {
+        if !MACHINES.iter().any(|&(machine, _, _)| machine == name) {
+            return Err(format!("no machine called {}", name));
+        }
+    }
+    Ok((trace, limit, names))
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (trace, limit, names) = parse_args(Args::from_env()).unwrap_or_else(|message| {
+        utils::args::exit_with_usage(&message, "universal_turing_machine [--trace] \
+                                                [--limit STEPS] [MACHINE]...")
+    });
+    for &(name, description, input) in MACHINES {
+        if !names.is_empty() && !names.iter().any(|chosen| chosen == name) {
+            continue;
+        }
+        println!("{}, on \"{}\":", name, input);
+        let machine = Machine::new(description).unwrap();
+        let outcome = machine.run_with(input, limit, |state, tape| {
+            if trace {
+                println!("  {:>6} {}", state, tape);
+            }
+        });
+        match outcome {
+            Ok(run) => println!("  halted in state {} after {} steps, leaving {}",
+                                run.state, run.steps, run.tape.contents()),
+            Err(err) => println!("  {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_args, Description, Error, Machine, Move, Rule, BINARY_INCREMENTER,
+                FIVE_STATE_BUSY_BEAVER, FOUR_STATE_BUSY_BEAVER, SIMPLE_INCREMENTER, SORTER,
+                THREE_STATE_BUSY_BEAVER};
+    use utils::args::Args;
+
+    fn machine(description: &Description) -> Machine {
+        Machine::new(description).unwrap()
+    }
+
+    #[test]
+    fn simple_incrementer() {
+        let run = machine(&SIMPLE_INCREMENTER).run("111", 100).unwrap();
+        assert_eq!((&run.state[..], run.steps), ("qf", 4));
+        assert_eq!(run.tape.to_string(), "111[1]");
+        assert_eq!(machine(&SIMPLE_INCREMENTER).run("", 100).unwrap().tape.contents(), "1");
+    }
+
+    #[test]
+    fn three_state_busy_beaver() {
+        let mut snapshots = Vec::new();
+        let run = machine(&THREE_STATE_BUSY_BEAVER).run_with("", 100, |state, tape| {
+            snapshots.push(format!("{} {}", state, tape));
+        }).unwrap();
+        assert_eq!(run.steps, 13);
+        assert_eq!(run.tape.contents(), "111111");
+        assert_eq!(&snapshots[..5], ["a [0]", "b 1[0]", "a [1]1", "c [0]11", "b [0]111"]);
+        assert_eq!(snapshots.last().unwrap(), "halt 111[1]11");
+    }
+
+    #[test]
+    fn four_state_busy_beaver() {
+        let run = machine(&FOUR_STATE_BUSY_BEAVER).run("", 1000).unwrap();
+        assert_eq!(run.steps, 107);
+        assert_eq!(run.tape.contents().matches('1').count(), 13);
+        assert_eq!(run.tape.to_string(), "1[0]111111111111");
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "slow"), ignore)]
+    fn five_state_busy_beaver() {
+        let run = machine(&FIVE_STATE_BUSY_BEAVER).run("", 100_000_000).unwrap();
+        assert_eq!(run.steps, 47_176_870);
+        assert_eq!(run.tape.contents().matches('1').count(), 4098);
+    }
+
+    #[test]
+    fn binary_incrementer() {
+        let increment = |input| {
+            let run = machine(&BINARY_INCREMENTER).run(input, 1000).unwrap();
+            (run.tape.contents(), run.tape.to_string())
+        };
+        assert_eq!(increment("1011"), ("1100".to_string(), "1[1]00B".to_string()));
+        assert_eq!(increment("111"), ("1000".to_string(), "[1]000B".to_string()));
+        assert_eq!(increment("0"), ("1".to_string(), "[1]B".to_string()));
+        assert_eq!(increment(""), ("1".to_string(), "[1]B".to_string()));
+    }
+
+    #[test]
+    fn sorter() {
+        let run = machine(&SORTER).run("babbababaa", 1000).unwrap();
+        assert_eq!(run.tape.contents(), "aaaaabbbbb");
+    }
+
+    #[test]
+    fn step_limit() {
+        // Goes right for ever
+        static RUNAWAY: Description = Description {
+            states: &["go", "stop"],
+            symbols: &['0'],
+            initial: "go",
+            halting: &["stop"],
+            rules: &[("go", '0', '0', Move::Right, "go")],
+        };
+        match machine(&RUNAWAY).run("", 50) {
+            Err(Error::StepLimit(tape)) => assert_eq!(tape.to_string().len(), 51 + 2),
+            outcome => panic!("{:?}", outcome),
+        }
+        let error = machine(&THREE_STATE_BUSY_BEAVER).run("", 12).unwrap_err();
+        assert_eq!(error.to_string(), "didn't halt in time");
+        assert!(machine(&THREE_STATE_BUSY_BEAVER).run("", 13).is_ok());
+    }
+
+    #[test]
+    fn descriptions() {
+        let machine = |rules: &'static [Rule], halting: &'static [&'static str]| {
+            Machine::new(&Description {
+                states: &["a", "h"],
+                symbols: &['0', '1'],
+                initial: "a",
+                halting,
+                rules,
+            })
+        };
+        let error = |rules, halting| machine(rules, halting).err().unwrap();
+        assert_eq!(error(&[("a", '0', '1', Move::Left, "b")], &["h"]), "unknown state b");
+        assert_eq!(error(&[("a", '0', 'x', Move::Left, "a")], &["h"]), "unknown symbol x");
+        assert_eq!(error(&[("a", '0', '1', Move::Left, "h"), ("a", '0', '0', Move::Right, "a")],
+                         &["h"]),
+                   "two rules for reading 0 in state a");
+        assert_eq!(error(&[("h", '0', '1', Move::Left, "a")], &["h"]),
+                   "a rule for halting state h");
+        assert_eq!(error(&[], &["x"]), "unknown state x");
+        // A machine that's fine, but gets stuck once it has written a 1
+        let stuck = machine(&[("a", '0', '1', Move::Stay, "a")], &["h"]).unwrap();
+        assert_eq!(stuck.run("", 10).unwrap_err().to_string(), "no rule for reading 1 in state a");
+    }
+
+    #[test]
+    fn arguments() {
+        let args = |args: &[&str]| parse_args(Args::new(args.iter().map(|arg| arg.to_string())));
+        assert_eq!(args(&[]), Ok((false, 100_000_000, vec![])));
+        assert_eq!(args(&["--trace", "sorter", "--limit=5"]),
+                   Ok((true, 5, vec!["sorter".to_string()])));
+        assert_eq!(args(&["turing"]), Err("no machine called turing".to_string()));
+    }
+}
@@ -0,0 +1,53 @@
+//! Implements http://rosettacode.org/wiki/A%2BB
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::a_plus_b::run;
+//!
+//! let mut output = Vec::new();
+//! run(&b"2 3\n"[..], &mut output).unwrap();
+//! assert_eq!(output, b"5\n");
+//! assert!(run(&b"2 three\n"[..], Vec::new()).is_err());
+//! ```
+use std::io::{self, BufRead, Read, Write};
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+// Read two integers from the first line of `input`, and write their sum to `output`
+pub fn run(input: impl Read, mut output: impl Write) -> io::Result<()> {
+    let mut line = String::new();
+    io::BufReader::new(input).read_line(&mut line)?;
+    let words = line.split_whitespace().take(2)
+                            .map(|i| i.parse::<i32>().ok())
+                            .collect::<Vec<Option<i32>>>();
+
+    let sum = match &words[..] {
+        [Some(x), Some(y)] => x + y,
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Please enter 2 integers"))
+    };
+
+    writeln!(output, "{}", sum)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let stdin = io::stdin();
+    run(stdin.lock(), io::stdout()).unwrap();
+}
+
+#[test]
+fn test_sum() {
+    assert_eq!(utils::capture::output_of(run, "2 3\n"), "5\n");
+    assert_eq!(utils::capture::output_of(run, "-1000 1000"), "0\n");
+}
+
+#[test]
+fn test_not_two_integers() {
+    for input in &["2\n", "2 three\n", ""] {
+        let err = utils::capture::error_of(run, input);
+        assert_eq!(err.to_string(), "Please enter 2 integers");
+    }
+}
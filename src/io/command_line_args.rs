@@ -1,9 +1,9 @@
-// Implements http://rosettacode.org/wiki/Command-line_arguments
-
-use std::env;
-
-fn main(){
-    for arg in env::args() {
-        println!("{}", arg);
-    }
-}
+// Implements http://rosettacode.org/wiki/Command-line_arguments
+
+use std::env;
+
+fn main(){
+    for arg in env::args() {
+        println!("{}", arg);
+    }
+}
@@ -0,0 +1,38 @@
+//! Implements http://rosettacode.org/wiki/Input_loop
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::input_loop::run;
+//!
+//! let mut output = Vec::new();
+//! run(&b"one\r\ntwo\nthree"[..], &mut output).unwrap();
+//! assert_eq!(output, b"one\ntwo\nthree\n");
+//! ```
+use std::io::{self, BufRead, Read, Write};
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+// Copy `input` to `output` a line at a time
+pub fn run(input: impl Read, mut output: impl Write) -> io::Result<()> {
+    for line in io::BufReader::new(input).lines() {
+        writeln!(output, "{}", line?)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(test))]
+fn main() {
+    let stdin = io::stdin();
+    run(stdin.lock(), io::stdout()).unwrap();
+}
+
+#[test]
+fn test_lines() {
+    assert_eq!(utils::capture::output_of(run, "one\ntwo\n\nthree\n"), "one\ntwo\n\nthree\n");
+    // The last line doesn't need to end in a newline
+    assert_eq!(utils::capture::output_of(run, "one\ntwo"), "one\ntwo\n");
+    assert_eq!(utils::capture::output_of(run, ""), "");
+}
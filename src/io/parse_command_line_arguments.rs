@@ -0,0 +1,99 @@
+//! Implements http://rosettacode.org/wiki/Parse_command-line_arguments
+//!
+//! The task's example is netcat's `nc -v -n -z -w 1 192.168.1.2 1-1000`, parsed here with
+//! utils::args, which the other tasks that take arguments use too.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::parse_command_line_arguments::parse;
+//! use tasks::parse_command_line_arguments::utils::args::Args;
+//!
+//! let args = |line: &str| Args::new(line.split_whitespace().map(String::from));
+//! let netcat = parse(args("-v -n -z -w 1 192.168.1.2 1-1000")).unwrap();
+//! assert_eq!(format!("{:?}", netcat),
+//!            "Netcat { verbose: true, numeric: true, scan: true, wait: Some(1), \
+//!             host: \"192.168.1.2\", ports: (1, 1000) }");
+//! assert!(parse(args("-v 192.168.1.2")).is_err());
+//! ```
+// pub, for the Args of the example above
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::args::Args;
+
+#[derive(Debug, PartialEq)]
+pub struct Netcat {
+    verbose: bool,        // -v
+    numeric: bool,        // -n: don't look names up
+    scan: bool,           // -z: only see which ports are listening
+    wait: Option<u32>,    // -w SECS: how long to wait for a connection
+    host: String,
+    ports: (u16, u16),    // Either one port or a range, such as 1-1000
+}
+
+pub fn parse(mut args: Args) -> Result<Netcat, String> {
+    let verbose = args.flag("-v");
+    let numeric = args.flag("-n");
+    let scan = args.flag("-z");
+    let wait = args.value("-w")?;
+    let free: Vec<String> = args.free()?;
+    let (host, ports) = match free.as_slice() {
+        [host, ports] => (host.clone(), ports),
+        _ => return Err("expected a host and a port or range of ports".to_string()),
+    };
+    let port = |port: &str| port.parse().map_err(|_| format!("invalid port: {}", port));
+    let ports = match ports.split_once('-') {
+        Some((low, high)) => (port(low)?, port(high)?),
+        None => (port(ports)?, port(ports)?),
+    };
+    Ok(Netcat { verbose, numeric, scan, wait, host, ports })
+}
+
+#[cfg(not(test))]
+fn main() {
+    match parse(Args::from_env()) {
+        Ok(netcat) => println!("{:#?}", netcat),
+        Err(message) => utils::args::exit_with_usage(&message, "nc [-v] [-n] [-z] [-w SECS] \
+                                                              HOST PORT[-PORT]"),
+    }
+}
+
+#[cfg(test)]
+fn parse_strs(args: &[&str]) -> Result<Netcat, String> {
+    parse(Args::new(args.iter().map(|arg| arg.to_string())))
+}
+
+#[test]
+fn test_example() {
+    assert_eq!(parse_strs(&["-v", "-n", "-z", "-w", "1", "192.168.1.2", "1-1000"]),
+               Ok(Netcat {
+                   verbose: true,
+                   numeric: true,
+                   scan: true,
+                   wait: Some(1),
+                   host: "192.168.1.2".to_string(),
+                   ports: (1, 1000),
+               }));
+}
+
+#[test]
+fn test_defaults() {
+    assert_eq!(parse_strs(&["example.com", "80"]),
+               Ok(Netcat {
+                   verbose: false,
+                   numeric: false,
+                   scan: false,
+                   wait: None,
+                   host: "example.com".to_string(),
+                   ports: (80, 80),
+               }));
+}
+
+#[test]
+fn test_errors() {
+    assert!(parse_strs(&["-x", "example.com", "80"]).is_err());
+    assert!(parse_strs(&["example.com"]).is_err());
+    assert!(parse_strs(&["example.com", "1-lots"]).is_err());
+    assert!(parse_strs(&["-w", "example.com", "80"]).is_err());
+}
@@ -0,0 +1,31 @@
+// Implements http://rosettacode.org/wiki/Walk_a_directory/Recursively
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::fs;
+use std::path::Path;
+
+use utils::regex::Regex;
+
+fn walk(path: &Path, regex: &Regex) {
+    let result = match fs::read_dir(path) {
+        Ok(result) => result,
+        Err(_) => return
+    };
+
+    for entry in result.filter_map(Result::ok) {
+        let subpath = entry.path();
+        if let Some(filename) = subpath.file_name().and_then(|name| name.to_str()) {
+            if regex.is_match(filename) {
+                println!("{}", subpath.display());
+            }
+        }
+
+        walk(&subpath, regex);
+    }
+}
+
+fn main() {
+    walk(Path::new("."), &Regex::new(r".*\.rs$").unwrap());
+}
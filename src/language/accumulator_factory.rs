@@ -1,6 +1,19 @@
+//! Implements http://rosettacode.org/wiki/Accumulator_factory
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::accumulator_factory::accum;
+//!
+//! let mut x = accum(1f32);
+//! x(5.);
+//! assert_eq!(x(2.3), 8.3);
+//!
+//! let mut n = accum(1);
+//! assert_eq!(n(2), 3);
+//! ```
 use std::ops::Add;
 
-pub fn accum<'a, T>(mut n: T) -> Box<FnMut(T) -> T + 'a> 
+pub fn accum<'a, T>(mut n: T) -> Box<dyn FnMut(T) -> T + 'a>
     where T: 'a + Add<T, Output=T> + Copy
 {
     Box::new(move |i: T| { n = n + i; n })
@@ -22,6 +35,6 @@ fn accumulate() -> f32 {
     // switching at the moment).
     let mut g = accum(1f32);
     g(5.);
-    accum(3i32);
+    let _ = accum(3i32);
     g(2.3)
 }
\ No newline at end of file
@@ -1,18 +1,18 @@
-// Implements http://rosettacode.org/wiki/Enumerations
-
-#[allow(dead_code)]
-enum Fruits {
-    Apple,
-    Banana,
-    Cherry
-}
-
-#[allow(dead_code)]
-enum Days {
-    Monday = 0,
-    Tuesday = 1,
-    Wednesday = 2
-    // You know the rest...
-}
-
-fn main() {}
+// Implements http://rosettacode.org/wiki/Enumerations
+
+#[allow(dead_code)]
+enum Fruits {
+    Apple,
+    Banana,
+    Cherry
+}
+
+#[allow(dead_code)]
+enum Days {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2
+    // You know the rest...
+}
+
+fn main() {}
@@ -1,14 +1,31 @@
 // Dummy main library
-// It also contains a test module, which checks if all source files are covered by `Cargo.toml`
-#![allow(unused_features)]
-#![feature(old_path)]
-#![feature(core)]
-#![feature(old_io)]
-#![feature(plugin)]
-#![feature(collections)]
-#![plugin(regex_macros)]
+// It lists the categories the tasks are sorted into, each a directory under src/, and contains a
+// test module, which checks that every source file is covered by `Cargo.toml` and that every task
+// is in one of the categories.
+//
+// The tasks stay binaries of their own rather than modules of this one: the crate's library is
+// factorial_plugin, which compile_time_calculation.rs needs as a plugin.  The registry in
+// rosetta_meta.rs has each task under its Rosetta Code name along with its category.
 
-extern crate regex;
+// Each category's directory, and what goes in it
+pub static CATEGORIES: &[(&str, &str)] = &[
+    ("algorithms", "Searching, compression, dynamic programming and graph algorithms"),
+    ("compiler", "The compiler series: a lexer, parser, code generator and virtual machine"),
+    ("concurrency", "Threads, synchronization and message passing"),
+    ("crypto", "Ciphers, hashes and checksums"),
+    ("data_structures", "Collections and the operations on them"),
+    ("games", "Games to play at the terminal"),
+    ("graphics", "Images, fractals and cellular automata"),
+    ("interpreters", "Interpreters for esoteric and small languages, and abstract machines"),
+    ("io", "Files, standard input and output, and the command line"),
+    ("language", "Features of the language itself: functions, closures, loops and so on"),
+    ("math", "Number theory, sequences, arithmetic and numerical methods"),
+    ("net", "Sockets, HTTP and DNS"),
+    ("puzzles", "Puzzles and classic recreational problems"),
+    ("sorting", "Sorting algorithms"),
+    ("strings", "Text processing and parsing"),
+    ("system", "The operating system and the machine"),
+];
 
 #[allow(dead_code)]
 #[cfg(not(test))]
@@ -16,9 +33,13 @@ fn main() { }
 
 #[cfg(test)]
 mod test {
+    use super::CATEGORIES;
     use std::collections::HashSet;
-    use std::old_io::BufferedReader;
-    use std::old_io::fs::{self, File};
+    use std::fs;
+    use std::path::Path;
+
+    // The directories under src/ that aren't categories of tasks
+    static NOT_CATEGORIES: &[&str] = &["utils"];
 
     // A test to check if all source files are covered by `Cargo.toml`
     #[test]
@@ -27,7 +48,7 @@ mod test {
         let bins = get_toml_paths();
         let not_covered = get_not_covered(&sources, &bins);
 
-        if not_covered.len() > 0 {
+        if !not_covered.is_empty() {
             println!("Error, the following source files are not covered by Cargo.toml:");
 
             for source in not_covered.iter() {
@@ -38,30 +59,56 @@ mod test {
         }
     }
 
-    // Returns the names of the source files in the `src` directory
+    // A test to check if every directory of tasks is a category, and every category has tasks
+    #[test]
+    fn check_categories() {
+        let categories: HashSet<&str> = CATEGORIES.iter().map(|&(name, _)| name).collect();
+        for dir in get_source_dirs() {
+            assert!(categories.contains(&dir[..]), "src/{} isn't in CATEGORIES", dir);
+        }
+        for sources in categories.iter().map(|category| rust_files(&format!("src/{}", category))) {
+            assert!(!sources.is_empty());
+        }
+    }
+
+    // Returns the directories under `src` that tasks go in
+    fn get_source_dirs() -> Vec<String> {
+        fs::read_dir("./src").unwrap().map(|entry| entry.unwrap().path())
+            .filter(|path| path.is_dir())
+            .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+            .filter(|name| !NOT_CATEGORIES.contains(&&name[..]))
+            .collect()
+    }
+
+    // Returns the paths of the source files in the `src` directory and its categories
     fn get_source_files() -> HashSet<String> {
-        let paths = fs::readdir(&Path::new("./src")).unwrap();
-        paths.iter().map(|p| p.filename_str().unwrap().to_string())
-                    .filter(|s| s[..].ends_with(".rs")).collect()
+        let mut sources = rust_files("src");
+        for dir in get_source_dirs() {
+            sources.extend(rust_files(&format!("src/{}", dir)));
+        }
+        sources
+    }
+
+    // Returns the paths of the `.rs` files in `dir`, such as "src/sorting/heap_sort.rs"
+    fn rust_files(dir: &str) -> HashSet<String> {
+        fs::read_dir(Path::new(".").join(dir)).unwrap().map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+            .map(|path| format!("{}/{}", dir, path.file_name().unwrap().to_str().unwrap()))
+            .collect()
     }
 
     // Returns the paths of the source files referenced in Cargo.toml
     fn get_toml_paths() -> HashSet<String> {
-        let c_toml = File::open(&Path::new("./Cargo.toml")).unwrap();
-        let mut reader = BufferedReader::new(c_toml);
-        let regex = regex!("path = \"(.*)\"");
-        reader.lines().filter_map(|l| {
-            let l = l.unwrap();
-            regex.captures(&l).map(|c| c.at(1).map(|s| Path::new(s))
-                                               .unwrap()
-                                               .filename_str()
-                                               .unwrap()
-                                               .to_string())
+        let c_toml = fs::read_to_string("./Cargo.toml").unwrap();
+        c_toml.lines().filter_map(|l| {
+            let path = l.trim().strip_prefix("path = \"")?.strip_suffix('"')?;
+            Some(path.to_string())
         }).collect()
     }
 
-    // Returns the filenames of the source files which are not covered by Cargo.toml
-    fn get_not_covered<'a>(sources: &'a HashSet<String>, paths: &'a HashSet<String>) -> HashSet<&'a String> {
+    // Returns the paths of the source files which are not covered by Cargo.toml
+    fn get_not_covered<'a>(sources: &'a HashSet<String>, paths: &'a HashSet<String>)
+                           -> HashSet<&'a String> {
         sources.difference(paths).collect()
     }
 }
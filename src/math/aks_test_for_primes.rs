@@ -1,4 +1,12 @@
-// http://rosettacode.org/wiki/AKS_test_for_primes
+//! http://rosettacode.org/wiki/AKS_test_for_primes
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::aks_test_for_primes::is_prime;
+//!
+//! let primes: Vec<usize> = (0..20).filter(|&p| is_prime(p)).collect();
+//! assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19]);
+//! ```
 #![allow(unused_features)]
 
 
@@ -8,8 +16,8 @@ pub fn is_prime(p: usize) -> bool {
     } else {
         let mut c = coefficients(p);
         c[0] -= 1;
-        for i in (0..(c.len() + 1) / 2) {
-            if (c[i] % (p as i64)) != 0 {
+        for x in &c[..c.len().div_ceil(2)] {
+            if (x % (p as i64)) != 0 {
                 return false
             }
         }
@@ -37,7 +45,7 @@ fn coefficients(p: usize) -> Vec<i64> {
     } else {
         let mut result = vec![1, -1];
         let zero = Some(0i64);
-        for _ in (1..p) {
+        for _ in 1..p {
             result = {
                 let a = result.iter().chain(zero.iter());
                 let b = zero.iter().chain(result.iter());
@@ -50,7 +58,7 @@ fn coefficients(p: usize) -> Vec<i64> {
 
 #[test]
 fn test_solution() {
-    let exp_coefficients =  vec![
+    let exp_coefficients =  [
                             vec![1i64],
                             vec![1, -1],
                             vec![1, -2, 1],
@@ -66,5 +74,5 @@ fn test_solution() {
     }
 
     let primes: Vec<usize> = (1..51).filter(|&i| is_prime(i)).collect();
-    assert_eq!(exp_primes, primes);
+    assert_eq!(primes, exp_primes);
 }
@@ -0,0 +1,27 @@
+// Implements http://rosettacode.org/wiki/Arbitrary-precision_integers_(included)
+// with the BigUint written from scratch in utils/bignum.rs
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::bignum::BigUint;
+
+// 5^4^3^2, with the powers taken from the right
+fn power_tower() -> BigUint {
+    BigUint::from(5u32).pow(4u32.pow(3u32.pow(2)))
+}
+
+#[cfg(not(test))]
+fn main() {
+    let digits = power_tower().to_string();
+    println!("5^4^3^2 = {}...{}", &digits[..20], &digits[digits.len() - 20..]);
+    println!("It has {} digits", digits.len());
+}
+
+#[test]
+fn test_power_tower() {
+    let digits = power_tower().to_string();
+    assert_eq!(&digits[..20], "62060698786608744707");
+    assert_eq!(&digits[digits.len() - 20..], "92256259918212890625");
+    assert_eq!(digits.len(), 183231);
+}
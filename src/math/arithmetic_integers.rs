@@ -0,0 +1,64 @@
+//! Implements http://rosettacode.org/wiki/Arithmetic/Integer
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::arithmetic_integers::run;
+//!
+//! let mut output = Vec::new();
+//! run(&b"-7 2\n"[..], &mut output).unwrap();
+//! assert_eq!(String::from_utf8(output).unwrap(),
+//!            "a + b = -5\na - b = -9\na * b = -14\na / b = -3\na % b = -1\n");
+//! assert!(run(&b"7 0\n"[..], Vec::new()).is_err());
+//! ```
+use std::io::{self, BufRead, Read, Write};
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+// Read two integers a and b from the first line of `input`, and write what the arithmetic
+// operators make of them to `output`
+pub fn run(input: impl Read, mut output: impl Write) -> io::Result<()> {
+    let mut line = String::new();
+    io::BufReader::new(input).read_line(&mut line)?;
+    let words = line.split_whitespace().take(2)
+                                        .map(|s| s.parse().ok())
+                                        .collect::<Vec<Option<i32>>>();
+
+    let (a, b) = match &words[..] {
+            [Some(x), Some(y)] => (x, y),
+            _ => return Err(invalid_input("Please enter 2 integers"))
+    };
+    if *b == 0 {
+        return Err(invalid_input("Cannot divide by 0"));
+    }
+
+    writeln!(output, "a + b = {}", a + b)?;
+    writeln!(output, "a - b = {}", a - b)?;
+    writeln!(output, "a * b = {}", a * b)?;
+    writeln!(output, "a / b = {}", a / b)?;
+    writeln!(output, "a % b = {}", a % b)
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let stdin = io::stdin();
+    run(stdin.lock(), io::stdout()).unwrap();
+}
+
+#[test]
+fn test_arithmetic() {
+    assert_eq!(utils::capture::output_of(run, "-7 2\n"),
+               "a + b = -5\na - b = -9\na * b = -14\na / b = -3\na % b = -1\n");
+}
+
+#[test]
+fn test_bad_input() {
+    assert_eq!(utils::capture::error_of(run, "7\n").to_string(), "Please enter 2 integers");
+    assert_eq!(utils::capture::error_of(run, "7 0\n").to_string(), "Cannot divide by 0");
+}
@@ -2,15 +2,16 @@
 //
 // Contributed by Gavin Baker <gavinb@antonym.org>
 //
-#![feature(old_path)]
-#![feature(old_io)]
+// The numbers are the first 1000 Fibonacci numbers, from tests/fixtures/fib1000.txt.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
-use std::old_io::{File, BufferedReader};
-use std::num::Float;
+use std::io::BufRead;
 
 // Calculate the expected frequency of a digit according to Benford's Law
 fn benford_freq(d: u64) -> f32 {
-    assert!(d >= 1 && d <= 9);
+    assert!((1..=9).contains(&d));
 
     (1.0 + 1.0/(d as f32)).log10()
 }
@@ -19,7 +20,7 @@ fn benford_freq(d: u64) -> f32 {
 fn first_digit_of(n: u64) -> usize {
     let mut d = n;
     while d > 9 {
-        d = d / 10;
+        d /= 10;
     }
     d as usize
 }
@@ -52,15 +53,13 @@ fn main() {
     // Calculate expected frequencies of all digits according to Benford's Law
 
     let mut expected_distrib = [0f32; 10];
-    for digit in (1..10) {
-        expected_distrib[digit] = benford_freq(digit as u64);
+    for (digit, expected) in expected_distrib.iter_mut().enumerate().skip(1) {
+        *expected = benford_freq(digit as u64);
     }
 
     // Load data from the Fibonacci sequence
 
-    let filename = "../src/resources/fib1000.txt";
-    let path = Path::new(filename);
-    let mut file = BufferedReader::new(File::open(&path));
+    let file = utils::fixture::open("fib1000.txt");
 
     let fibs: Vec<u64> = file.lines().map(|x| {
         let s = x.unwrap();
@@ -76,7 +75,7 @@ fn main() {
     println!("\nBenford's Law - Digit Distribution");
     println!("\nFirst 1000 Numbers in the Fibonacci Sequence\n");
     println!("digit    expect     found     delta");
-    for digit in (1..10) {
+    for digit in 1..10 {
         let expected_pc = expected_distrib[digit] * 100.0;
         let found_pc = found_distrib[digit] * 100.0;
         let delta_pc = expected_pc - found_pc;
@@ -0,0 +1,161 @@
+//! Implements http://rosettacode.org/wiki/Cholesky_decomposition
+//!
+//! A symmetric positive-definite matrix A is L L^T for a lower triangular L, which is worked out a
+//! row at a time from
+//!
+//! ```text
+//!     L[j][j] = sqrt(A[j][j] - sum of L[j][k]^2 for k < j)
+//!     L[i][j] = (A[i][j] - sum of L[i][k] L[j][k] for k < j) / L[j][j],    i > j
+//! ```
+//!
+//! It takes half the work of an LU decomposition and needs no pivoting.  What's under the square
+//! root is positive exactly when A is positive definite, so that's how it finds out when A isn't.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::cholesky_decomposition::{cholesky, examples};
+//!
+//! let l = cholesky(&examples()[0]).unwrap();
+//! assert_eq!((l[(0, 0)], l[(1, 0)], l[(1, 1)], l[(2, 0)], l[(2, 1)], l[(2, 2)]),
+//!            (5.0, 3.0, 3.0, -1.0, 1.0, 3.0));
+//! assert_eq!(l[(0, 1)], 0.0);
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::fmt;
+use utils::matrix::Matrix;
+
+// Elements this far apart, relative to the biggest, still count as equal
+const EPSILON: f64 = 1e-12;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CholeskyError {
+    // Where the matrix differs from its transpose
+    NotSymmetric(usize, usize),
+    // The row whose diagonal element would be the square root of something that isn't positive
+    NotPositiveDefinite(usize),
+}
+
+impl fmt::Display for CholeskyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CholeskyError::NotSymmetric(i, j) => {
+                write!(f, "the matrix isn't symmetric: ({}, {}) and ({}, {}) differ", i, j, j, i)
+            }
+            CholeskyError::NotPositiveDefinite(i) => {
+                write!(f, "the matrix isn't positive definite, as row {} shows", i)
+            }
+        }
+    }
+}
+
+// The lower triangular L with L L^T = a
+pub fn cholesky(a: &Matrix<f64>) -> Result<Matrix<f64>, CholeskyError> {
+    assert!(a.is_square(), "only square matrices have Cholesky decompositions, not {}x{}",
+            a.rows(), a.cols());
+    let n = a.rows();
+    let scale = (0..n).flat_map(|i| a.row(i).iter()).fold(0.0, |max: f64, x| max.max(x.abs()));
+    for i in 0..n {
+        for j in 0..i {
+            if (a[(i, j)] - a[(j, i)]).abs() > EPSILON * scale {
+                return Err(CholeskyError::NotSymmetric(i, j));
+            }
+        }
+    }
+    let mut l = Matrix::zeros(n, n);
+    for i in 0..n {
+        for j in 0..i + 1 {
+            let sum: f64 = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum();
+            if i == j {
+                let square = a[(i, i)] - sum;
+                if square <= EPSILON * scale {
+                    return Err(CholeskyError::NotPositiveDefinite(i));
+                }
+                l[(i, i)] = square.sqrt();
+            } else {
+                l[(i, j)] = (a[(i, j)] - sum) / l[(j, j)];
+            }
+        }
+    }
+    Ok(l)
+}
+
+// The task's two matrices
+pub fn examples() -> Vec<Matrix<f64>> {
+    vec![
+        Matrix::from_rows(vec![vec![25.0, 15.0, -5.0], vec![15.0, 18.0, 0.0],
+                               vec![-5.0, 0.0, 11.0]]),
+        Matrix::from_rows(vec![vec![18.0, 22.0, 54.0, 42.0], vec![22.0, 70.0, 86.0, 62.0],
+                               vec![54.0, 86.0, 174.0, 134.0], vec![42.0, 62.0, 134.0, 106.0]]),
+    ]
+}
+
+#[cfg(not(test))]
+fn main() {
+    for (i, a) in examples().iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{:.5}", cholesky(a).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{cholesky, examples, CholeskyError, Matrix};
+
+    fn assert_close(a: &Matrix<f64>, b: &Matrix<f64>, tolerance: f64) {
+        let difference = (a - b).into_vec();
+        assert!(difference.iter().all(|x| x.abs() < tolerance), "\n{}\n\nisn't\n\n{}", a, b);
+    }
+
+    #[test]
+    fn task() {
+        let examples = examples();
+        assert_eq!(cholesky(&examples[0]),
+                   Ok(Matrix::from_rows(vec![vec![5.0, 0.0, 0.0], vec![3.0, 3.0, 0.0],
+                                             vec![-1.0, 1.0, 3.0]])));
+        // The task gives this one to five places
+        let l = Matrix::from_rows(vec![vec![4.24264, 0.0, 0.0, 0.0],
+                                       vec![5.18545, 6.56591, 0.0, 0.0],
+                                       vec![12.72792, 3.04604, 1.64974, 0.0],
+                                       vec![9.89949, 1.62455, 1.84971, 1.39262]]);
+        assert_close(&cholesky(&examples[1]).unwrap(), &l, 1e-5);
+    }
+
+    #[test]
+    fn random_matrices() {
+        // B B^T is positive definite for any B with independent rows, which a random one has
+        let mut rng = Rng::new(92);
+        for n in 1..30 {
+            let b = Matrix::from_fn(n, n, |_, _| rng.next_f64() * 2.0 - 1.0);
+            let a = &(&b * &b.transpose()) + &Matrix::identity(n).scale(1e-3);
+            let l = cholesky(&a).unwrap();
+            assert_close(&(&l * &l.transpose()), &a, 1e-12);
+            for i in 0..n {
+                assert!(l[(i, i)] > 0.0);
+                assert!(l.row(i)[i + 1..].iter().all(|&x| x == 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn not_spd() {
+        let a = Matrix::from_rows(vec![vec![4.0, 1.0], vec![2.0, 3.0]]);
+        assert_eq!(cholesky(&a), Err(CholeskyError::NotSymmetric(1, 0)));
+        // Symmetric, but with eigenvalues 3 and -1
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
+        assert_eq!(cholesky(&a), Err(CholeskyError::NotPositiveDefinite(1)));
+        // Only positive semi-definite
+        let a = Matrix::from_rows(vec![vec![1.0, 1.0], vec![1.0, 1.0]]);
+        assert_eq!(cholesky(&a), Err(CholeskyError::NotPositiveDefinite(1)));
+        assert_eq!(cholesky(&Matrix::from_rows(vec![vec![-4.0]])),
+                   Err(CholeskyError::NotPositiveDefinite(0)));
+        assert_eq!(CholeskyError::NotPositiveDefinite(1).to_string(),
+                   "the matrix isn't positive definite, as row 1 shows");
+    }
+}
@@ -0,0 +1,213 @@
+//! Eigenvalues of real matrices, with the QR decomposition qr_decomposition.rs shares with its task
+//!
+//! power_iteration() finds the eigenvalue of biggest absolute value and its eigenvector by
+//! multiplying a vector by A over and over, which stretches it most along that eigenvector until
+//! it's all that's left.  It converges as fast as the second biggest eigenvalue shrinks next to
+//! the biggest, and not at all when two of them are equally big.
+//!
+//! symmetric_eigenvalues() finds all of a symmetric matrix's, which are real, with the QR
+//! algorithm: factor A = Q R and multiply the factors the other way round, R Q = Q^T A Q, which has
+//! the same eigenvalues and is closer to diagonal.  Subtracting a shift from the diagonal first,
+//! and adding it back after, makes it converge far faster the nearer the shift is to an eigenvalue:
+//! Wilkinson's shift, the eigenvalue of the bottom right 2x2 block nearer its corner, makes the
+//! last off-diagonal element go to zero in a few steps.  Then that corner is an eigenvalue, and the
+//! rest of the matrix goes on without it.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::eigenvalues::qr_decomposition::utils::matrix::Matrix;
+//! use tasks::eigenvalues::{power_iteration, symmetric_eigenvalues};
+//!
+//! let a = Matrix::from_rows(vec![vec![2.0, 1.0], vec![1.0, 2.0]]);
+//! let eigenvalues = symmetric_eigenvalues(&a);
+//! assert!((eigenvalues[0] - 1.0).abs() < 1e-9 && (eigenvalues[1] - 3.0).abs() < 1e-9);
+//! let (lambda, v) = power_iteration(&a, 1e-12).unwrap();
+//! assert!((lambda - 3.0).abs() < 1e-9);
+//! assert!((v[0] - v[1]).abs() < 1e-6);
+//! ```
+
+// pub, for the Matrix of the example above
+#[allow(dead_code)]
+#[path = "qr_decomposition.rs"]
+pub mod qr_decomposition;
+
+use self::qr_decomposition::qr;
+use self::qr_decomposition::utils::matrix::Matrix;
+
+// Steps of either iteration to give up after
+const MAX_ITERATIONS: usize = 10_000;
+
+fn multiply(a: &Matrix<f64>, v: &[f64]) -> Vec<f64> {
+    (0..a.rows()).map(|i| a.row(i).iter().zip(v).map(|(x, y)| x * y).sum()).collect()
+}
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+// The biggest eigenvalue of `a` and a unit eigenvector, once |A v - lambda v| is under
+// `tolerance` times |lambda|, or None if that doesn't happen
+pub fn power_iteration(a: &Matrix<f64>, tolerance: f64) -> Option<(f64, Vec<f64>)> {
+    assert!(a.is_square(), "only square matrices have eigenvalues, not {}x{}", a.rows(),
+            a.cols());
+    let n = a.rows();
+    // Any start will do that isn't orthogonal to the eigenvector, and all ones rarely is
+    let mut v = vec![1.0 / (n as f64).sqrt(); n];
+    for _ in 0..MAX_ITERATIONS {
+        let av = multiply(a, &v);
+        // The Rayleigh quotient, v^T A v, is the best estimate of the eigenvalue from v
+        let lambda: f64 = v.iter().zip(&av).map(|(x, y)| x * y).sum();
+        let residual: Vec<f64> = av.iter().zip(&v).map(|(y, x)| y - lambda * x).collect();
+        if norm(&residual) <= tolerance * lambda.abs() {
+            return Some((lambda, v));
+        }
+        let length = norm(&av);
+        if length == 0.0 {
+            return None;
+        }
+        v = av.iter().map(|x| x / length).collect();
+    }
+    None
+}
+
+// The eigenvalues of the symmetric matrix `a`, smallest first
+pub fn symmetric_eigenvalues(a: &Matrix<f64>) -> Vec<f64> {
+    assert!(a.is_square(), "only square matrices have eigenvalues, not {}x{}", a.rows(),
+            a.cols());
+    let n = a.rows();
+    let scale = (0..n).flat_map(|i| a.row(i).iter()).fold(0.0, |max: f64, x| max.max(x.abs()));
+    for i in 0..n {
+        for j in 0..i {
+            assert!((a[(i, j)] - a[(j, i)]).abs() <= 1e-12 * scale, "the matrix isn't symmetric");
+        }
+    }
+    let mut eigenvalues = Vec::with_capacity(n);
+    let mut a = a.clone();
+    let mut iterations = 0;
+    // The top left m x m block is what's left to find the eigenvalues of
+    let mut m = n;
+    while m > 1 {
+        let corner = a[(m - 1, m - 1)];
+        if a.row(m - 1)[..m - 1].iter().all(|x| x.abs() <= f64::EPSILON * scale) {
+            eigenvalues.push(corner);
+            m -= 1;
+            a = a.submatrix(0..m, 0..m);
+            continue;
+        }
+        iterations += 1;
+        assert!(iterations <= MAX_ITERATIONS, "the QR algorithm didn't converge");
+        let shift = wilkinson_shift(a[(m - 2, m - 2)], a[(m - 1, m - 2)], corner);
+        let shifted = &a - &Matrix::identity(m).scale(shift);
+        let (q, r) = qr(&shifted);
+        a = &(&r * &q) + &Matrix::identity(m).scale(shift);
+        // R Q is symmetric, but only up to rounding, which would otherwise build up
+        a = Matrix::from_fn(m, m, |i, j| (a[(i, j)] + a[(j, i)]) / 2.0);
+    }
+    if m == 1 {
+        eigenvalues.push(a[(0, 0)]);
+    }
+    eigenvalues.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    eigenvalues
+}
+
+// The eigenvalue of [[a, b], [b, c]] nearer c
+fn wilkinson_shift(a: f64, b: f64, c: f64) -> f64 {
+    let d = (a - c) / 2.0;
+    let sign = if d >= 0.0 { 1.0 } else { -1.0 };
+    c - sign * b * b / (d.abs() + d.hypot(b))
+}
+
+#[cfg(not(test))]
+fn main() {
+    let a = Matrix::from_rows(vec![vec![2.0, -1.0, 0.0], vec![-1.0, 2.0, -1.0],
+                                   vec![0.0, -1.0, 2.0]]);
+    println!("A\n{}\n", a);
+    let (lambda, v) = power_iteration(&a, 1e-10).unwrap();
+    let v: Vec<String> = v.iter().map(|x| format!("{:.6}", x)).collect();
+    println!("Power iteration: eigenvalue {:.6}, eigenvector ({})", lambda, v.join(", "));
+    let eigenvalues: Vec<String> =
+        symmetric_eigenvalues(&a).iter().map(|x| format!("{:.6}", x)).collect();
+    println!("QR algorithm: eigenvalues {}", eigenvalues.join(", "));
+    println!("which are 2 - sqrt(2), 2 and 2 + sqrt(2)");
+}
+
+#[cfg(test)]
+mod test {
+    use super::qr_decomposition::utils::rng::Rng;
+    use super::{power_iteration, qr, symmetric_eigenvalues, Matrix};
+    use std::f64::consts::PI;
+
+    fn assert_close(a: &[f64], b: &[f64], tolerance: f64) {
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() <= tolerance), "{:?} isn't {:?}", a, b);
+    }
+
+    #[test]
+    fn two_by_two() {
+        let a = Matrix::from_rows(vec![vec![2.0, 1.0], vec![1.0, 2.0]]);
+        assert_close(&symmetric_eigenvalues(&a), &[1.0, 3.0], 1e-12);
+        let (lambda, v) = power_iteration(&a, 1e-12).unwrap();
+        assert!((lambda - 3.0).abs() < 1e-12);
+        assert_close(&v, &[0.5f64.sqrt(), 0.5f64.sqrt()], 1e-12);
+        // Not symmetric, with eigenvalues 5 and 2
+        let a = Matrix::from_rows(vec![vec![4.0, 1.0], vec![2.0, 3.0]]);
+        let (lambda, v) = power_iteration(&a, 1e-12).unwrap();
+        assert!((lambda - 5.0).abs() < 1e-10 && (v[0] - v[1]).abs() < 1e-10, "{} {:?}", lambda, v);
+    }
+
+    #[test]
+    fn diagonal() {
+        let d = [3.0, -1.0, 4.0, 1.5, -9.0];
+        let a = Matrix::from_fn(5, 5, |i, j| if i == j { d[i] } else { 0.0 });
+        assert_close(&symmetric_eigenvalues(&a), &[-9.0, -1.0, 1.5, 3.0, 4.0], 0.0);
+        let (lambda, _) = power_iteration(&a, 1e-10).unwrap();
+        assert!((lambda + 9.0).abs() < 1e-9);
+        assert_eq!(symmetric_eigenvalues(&Matrix::from_rows(vec![vec![7.0]])), [7.0]);
+        assert!(symmetric_eigenvalues(&Matrix::zeros(0, 0)).is_empty());
+    }
+
+    #[test]
+    fn second_difference() {
+        // The matrix with 2 on the diagonal and -1 beside it has 2 - 2 cos(k pi / (n + 1))
+        for &n in &[2, 3, 10, 25] {
+            let a = Matrix::from_fn(n, n, |i, j| match (i as isize - j as isize).abs() {
+                0 => 2.0,
+                1 => -1.0,
+                _ => 0.0,
+            });
+            let expected: Vec<f64> =
+                (1..n + 1).map(|k| 2.0 - 2.0 * (k as f64 * PI / (n + 1) as f64).cos()).collect();
+            assert_close(&symmetric_eigenvalues(&a), &expected, 1e-10);
+        }
+    }
+
+    #[test]
+    fn similar_to_diagonal() {
+        // Q D Q^T, for a random orthogonal Q, has the eigenvalues on D's diagonal
+        let mut rng = Rng::new(94);
+        for &n in &[3, 8, 20] {
+            let (q, _) = qr(&Matrix::from_fn(n, n, |_, _| rng.next_f64() - 0.5));
+            let mut d: Vec<f64> = (0..n).map(|_| rng.next_f64() * 20.0 - 10.0).collect();
+            let diagonal = Matrix::from_fn(n, n, |i, j| if i == j { d[i] } else { 0.0 });
+            let a = &(&q * &diagonal) * &q.transpose();
+            // Rounding leaves it a hair off symmetric
+            let a = Matrix::from_fn(n, n, |i, j| (a[(i, j)] + a[(j, i)]) / 2.0);
+            d.sort_by(|x, y| x.partial_cmp(y).unwrap());
+            assert_close(&symmetric_eigenvalues(&a), &d, 1e-9);
+        }
+    }
+
+    #[test]
+    fn no_dominant_eigenvalue() {
+        // 1 and -1 are equally big, so the vector flips between them for ever
+        let a = Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.0, -1.0]]);
+        assert_eq!(power_iteration(&a, 1e-10), None);
+        assert_close(&symmetric_eigenvalues(&a), &[-1.0, 1.0], 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the matrix isn't symmetric")]
+    fn not_symmetric() {
+        symmetric_eigenvalues(&Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+    }
+}
@@ -0,0 +1,182 @@
+//! Implements http://rosettacode.org/wiki/Fast_Fourier_transform
+//!
+//! The radix-2 Cooley-Tukey FFT, done in place rather than recursively.  Splitting a transform
+//! into the transforms of the even and the odd elements again and again ends with the elements in
+//! bit-reversed order: element 0b011 of eight ends up where 0b110 is.  So fft() swaps each element
+//! with its bit reversal first, and then combines pairs into transforms of two, those into
+//! transforms of four, and so on, each time with the butterfly
+//!
+//! ```text
+//!     X[k]         = E[k] + w^k O[k]
+//!     X[k + len/2] = E[k] - w^k O[k],    w = e^(-2 pi i / len)
+//! ```
+//!
+//! which makes n log n operations in all, against the n^2 of the discrete Fourier transform as
+//! it's defined, dft().  The inverse is the same with w = e^(2 pi i / len), divided by n.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::fast_fourier_transform::utils::complex::Complex;
+//! use tasks::fast_fourier_transform::{dft, fft, inverse_fft};
+//!
+//! let input: Vec<Complex> = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0].iter()
+//!     .map(|&x| Complex::from(x))
+//!     .collect();
+//! let mut data = input.clone();
+//! fft(&mut data);
+//! assert_eq!(data[0], Complex::from(4.0));
+//! assert!(data.iter().zip(dft(&input)).all(|(x, y)| (*x - y).abs() < 1e-12));
+//! inverse_fft(&mut data);
+//! assert!(data.iter().zip(&input).all(|(x, y)| (*x - *y).abs() < 1e-12));
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::complex::Complex;
+use std::f64::consts::PI;
+
+// Put the element at each index at the index with its bits the other way round
+fn bit_reverse(data: &mut [Complex]) {
+    let bits = data.len().trailing_zeros();
+    if bits == 0 {
+        return;
+    }
+    for i in 0..data.len() {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        // Each pair is swapped once, from its smaller index
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+}
+
+// The butterflies, with the twiddle factors turning `direction` ways round the circle
+fn transform(data: &mut [Complex], direction: f64) {
+    assert!(data.len().is_power_of_two() || data.is_empty(),
+            "the radix-2 FFT takes a power of two elements, not {}", data.len());
+    bit_reverse(data);
+    let mut len = 2;
+    while len <= data.len() {
+        let step = Complex::from_polar(1.0, direction * 2.0 * PI / len as f64);
+        for block in data.chunks_mut(len) {
+            let (even, odd) = block.split_at_mut(len / 2);
+            let mut twiddle = Complex::new(1.0, 0.0);
+            for (e, o) in even.iter_mut().zip(odd) {
+                let t = twiddle * *o;
+                *o = *e - t;
+                *e += t;
+                twiddle *= step;
+            }
+        }
+        len *= 2;
+    }
+}
+
+// Replace `data`, whose length is a power of two, by its discrete Fourier transform
+pub fn fft(data: &mut [Complex]) {
+    transform(data, -1.0);
+}
+
+// Undo fft()
+pub fn inverse_fft(data: &mut [Complex]) {
+    transform(data, 1.0);
+    let n = data.len() as f64;
+    for x in data {
+        *x = x.scale(1.0 / n);
+    }
+}
+
+// The discrete Fourier transform straight from its definition, for any length
+pub fn dft(data: &[Complex]) -> Vec<Complex> {
+    let n = data.len();
+    (0..n)
+        .map(|k| {
+            data.iter().enumerate().fold(Complex::new(0.0, 0.0), |sum, (j, &x)| {
+                let angle = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                sum + x * Complex::from_polar(1.0, angle)
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(test))]
+fn main() {
+    let mut data: Vec<Complex> =
+        [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0].iter().map(|&x| Complex::from(x)).collect();
+    fft(&mut data);
+    for x in &data {
+        println!("{:.4}", x);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{bit_reverse, dft, fft, inverse_fft, Complex};
+
+    fn close(a: &[Complex], b: &[Complex], tolerance: f64) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (*x - *y).abs() < tolerance)
+    }
+
+    fn random(rng: &mut Rng, n: usize) -> Vec<Complex> {
+        (0..n)
+            .map(|_| Complex::new(2.0 * rng.next_f64() - 1.0, 2.0 * rng.next_f64() - 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn transform() {
+        let mut data: Vec<Complex> =
+            [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0].iter().map(|&x| Complex::from(x)).collect();
+        let target = [(4.0, 0.0), (1.0, -2.41421), (0.0, 0.0), (1.0, -0.41421),
+                      (0.0, 0.0), (1.0, 0.41421), (0.0, 0.0), (1.0, 2.41421)];
+        let target: Vec<Complex> = target.iter().map(|&(re, im)| Complex::new(re, im)).collect();
+        fft(&mut data);
+        assert!(close(&data, &target, 1e-5), "{:?}", data);
+    }
+
+    #[test]
+    fn bit_reversal() {
+        let mut data: Vec<Complex> = (0..8).map(|i| Complex::from(i as f64)).collect();
+        bit_reverse(&mut data);
+        let order: Vec<f64> = data.iter().map(|x| x.re).collect();
+        assert_eq!(order, [0.0, 4.0, 2.0, 6.0, 1.0, 5.0, 3.0, 7.0]);
+    }
+
+    #[test]
+    fn against_dft() {
+        let mut rng = Rng::new(88);
+        for bits in 0..11 {
+            let input = random(&mut rng, 1 << bits);
+            let mut output = input.clone();
+            fft(&mut output);
+            assert!(close(&output, &dft(&input), 1e-9), "{} elements", input.len());
+            inverse_fft(&mut output);
+            assert!(close(&output, &input, 1e-12), "{} elements", input.len());
+        }
+    }
+
+    #[test]
+    fn impulse_and_constant() {
+        // An impulse has every frequency equally, and a constant only the zero frequency
+        let mut impulse = vec![Complex::new(0.0, 0.0); 16];
+        impulse[0] = Complex::from(1.0);
+        fft(&mut impulse);
+        assert!(close(&impulse, &[Complex::from(1.0); 16], 1e-12));
+        let mut constant = vec![Complex::from(2.0); 16];
+        fft(&mut constant);
+        assert!((constant[0] - Complex::from(32.0)).abs() < 1e-12);
+        assert!(constant[1..].iter().all(|x| x.abs() < 1e-12));
+        let mut empty: Vec<Complex> = Vec::new();
+        fft(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "takes a power of two elements, not 6")]
+    fn not_a_power_of_two() {
+        fft(&mut [Complex::new(0.0, 0.0); 6]);
+    }
+}
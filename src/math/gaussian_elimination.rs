@@ -0,0 +1,170 @@
+//! Implements http://rosettacode.org/wiki/Gaussian_elimination
+//!
+//! Solves A x = b by subtracting multiples of each row from the rows below it until A is upper
+//! triangular, and then working out x from the last row up.  Each column's pivot is the one of the
+//! rows left with the biggest element in that column, swapped into place (partial pivoting): that
+//! makes every multiplier at most 1, so rounding errors aren't magnified, and it's only when the
+//! whole rest of a column is zero that there's no pivot and the matrix is singular.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::gaussian_elimination::{example, solve};
+//!
+//! let (a, b) = example();
+//! let x = solve(&a, &b).unwrap();
+//! assert!((x[0] - -0.01).abs() < 1e-12);
+//! assert!((x[1] - 1.602790394502114).abs() < 1e-9);
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::matrix::Matrix;
+
+// Pivots this small compared to the biggest element count as zero
+const EPSILON: f64 = 1e-12;
+
+// Reduce `a` to upper triangular form, doing the same to `b`, or return false if `a` is singular
+pub fn eliminate(a: &mut Matrix<f64>, b: &mut [f64]) -> bool {
+    assert!(a.is_square(), "a system of equations needs a square matrix, not {}x{}", a.rows(),
+            a.cols());
+    assert_eq!(a.rows(), b.len(), "{} equations but {} right hand sides", a.rows(), b.len());
+    let n = a.rows();
+    let scale = (0..n).flat_map(|i| a.row(i).iter()).fold(0.0, |max: f64, x| max.max(x.abs()));
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[(i, col)].abs().partial_cmp(&a[(j, col)].abs()).unwrap())
+            .unwrap();
+        if a[(pivot, col)].abs() <= EPSILON * scale {
+            return false;
+        }
+        a.swap_rows(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[(row, col)] / a[(col, col)];
+            a[(row, col)] = 0.0;
+            for k in col + 1..n {
+                a[(row, k)] -= factor * a[(col, k)];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    true
+}
+
+// Solve U x = y for upper triangular U, from the last unknown up
+pub fn back_substitute(u: &Matrix<f64>, y: &[f64]) -> Vec<f64> {
+    let n = u.rows();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let known: f64 = (i + 1..n).map(|j| u[(i, j)] * x[j]).sum();
+        x[i] = (y[i] - known) / u[(i, i)];
+    }
+    x
+}
+
+// The x with a x = b, or None if a is singular
+pub fn solve(a: &Matrix<f64>, b: &[f64]) -> Option<Vec<f64>> {
+    let (mut u, mut y) = (a.clone(), b.to_vec());
+    if eliminate(&mut u, &mut y) {
+        Some(back_substitute(&u, &y))
+    } else {
+        None
+    }
+}
+
+// The task's system, whose 3.14 is only pi by chance
+#[allow(clippy::approx_constant)]
+pub fn example() -> (Matrix<f64>, Vec<f64>) {
+    let a = Matrix::from_rows(vec![
+        vec![1.00, 0.00, 0.00, 0.00, 0.00, 0.00],
+        vec![1.00, 0.63, 0.39, 0.25, 0.16, 0.10],
+        vec![1.00, 1.26, 1.58, 1.98, 2.49, 3.13],
+        vec![1.00, 1.88, 3.55, 6.70, 12.62, 23.80],
+        vec![1.00, 2.51, 6.32, 15.88, 39.90, 100.28],
+        vec![1.00, 3.14, 9.87, 31.01, 97.41, 306.02],
+    ]);
+    (a, vec![-0.01, 0.61, 0.91, 0.99, 0.60, 0.02])
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (a, b) = example();
+    let x = solve(&a, &b).expect("the task's matrix isn't singular");
+    for (i, x) in x.iter().enumerate() {
+        println!("x{} = {:.15}", i + 1, x);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{eliminate, example, solve, Matrix};
+
+    // |A x - b|, relative to |b|
+    fn residual(a: &Matrix<f64>, x: &[f64], b: &[f64]) -> f64 {
+        let ax = (a * &Matrix::new(x.len(), 1, x.to_vec())).into_vec();
+        let error: f64 = ax.iter().zip(b).map(|(p, q)| (p - q) * (p - q)).sum();
+        let size: f64 = b.iter().map(|q| q * q).sum();
+        (error / size).sqrt()
+    }
+
+    #[test]
+    fn task() {
+        let (a, b) = example();
+        let x = solve(&a, &b).unwrap();
+        let expected = [-0.01, 1.602790394502114, -1.6132030599055613, 1.2454941213714368,
+                        -0.4909897195846576, 0.065760696175232];
+        for (x, expected) in x.iter().zip(&expected) {
+            assert!((x - expected).abs() < 1e-9, "{} isn't {}", x, expected);
+        }
+        assert!(residual(&a, &x, &b) < 1e-12);
+    }
+
+    #[test]
+    fn pivoting() {
+        // Without swapping rows the first pivot would be zero
+        let a = Matrix::from_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        assert_eq!(solve(&a, &[2.0, 3.0]), Some(vec![3.0, 2.0]));
+        // And here tiny, which loses the answer to rounding
+        let a = Matrix::from_rows(vec![vec![1e-20, 1.0], vec![1.0, 1.0]]);
+        let x = solve(&a, &[1.0, 2.0]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12, "{:?}", x);
+    }
+
+    #[test]
+    fn singular() {
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0],
+                                       vec![7.0, 8.0, 9.0]]);
+        assert_eq!(solve(&a, &[1.0, 2.0, 3.0]), None);
+        assert_eq!(solve(&Matrix::zeros(2, 2), &[0.0, 0.0]), None);
+        let mut u = Matrix::from_rows(vec![vec![2.0, 4.0], vec![1.0, 2.0]]);
+        assert!(!eliminate(&mut u, &mut [1.0, 1.0]));
+        assert_eq!(solve(&Matrix::zeros(0, 0), &[]), Some(vec![]));
+    }
+
+    #[test]
+    fn random_systems() {
+        // Strictly diagonally dominant, so well conditioned, and shuffled so they need pivoting
+        let mut rng = Rng::new(90);
+        for n in 1..40 {
+            let mut a = Matrix::from_fn(n, n, |_, _| rng.next_f64() * 2.0 - 1.0);
+            for i in 0..n {
+                a[(i, i)] += if rng.gen_bool(0.5) { n as f64 } else { -(n as f64) };
+            }
+            let mut order: Vec<usize> = (0..n).collect();
+            rng.shuffle(&mut order);
+            let a = Matrix::from_fn(n, n, |i, j| a[(order[i], j)]);
+            let b: Vec<f64> = (0..n).map(|_| rng.next_f64() * 10.0 - 5.0).collect();
+            let x = solve(&a, &b).unwrap();
+            assert!(residual(&a, &x, &b) < 1e-12, "{}x{}", n, n);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "3 equations but 2 right hand sides")]
+    fn mismatched() {
+        solve(&Matrix::identity(3), &[1.0, 2.0]);
+    }
+}
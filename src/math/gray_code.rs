@@ -1,6 +1,9 @@
 // Implements http://rosettacode.org/wiki/Gray_code
-#![feature(core)]
-#![allow(unused_features)]
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 // Encode an usize
 fn gray_encode(integer: usize) -> usize {
@@ -17,7 +20,7 @@ fn gray_decode(integer: usize) -> usize {
 
 #[cfg(not(test))]
 fn main() {
-    for i in (0..32) {
+    for i in 0..32 {
         println!("{:2} {:0>5} {:0>5} {:2}", i, i, gray_encode(i),
             gray_decode(i));
     }
@@ -27,3 +30,8 @@ fn main() {
 fn test_coherence() {
     assert!((0..1000).all(|x| gray_decode(gray_encode(x)) == x));
 }
+
+#[test]
+fn test_any_coherence() {
+    utils::property::roundtrips(|&n: &usize| gray_encode(n), |&n| gray_decode(n));
+}
@@ -1,15 +1,25 @@
-// http://rosettacode.org/wiki/Hamming_numbers
-#![allow(unused_features)]
-#![feature(collections)]
-
+//! http://rosettacode.org/wiki/Hamming_numbers
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::hamming_numbers::utils::bignum::BigUint;
+//! use tasks::hamming_numbers::{Hamming, HammingNumber};
+//!
+//! let first: Vec<String> = Hamming::<BigUint>::new(128).take(20)
+//!     .map(|h| h.to_biguint().to_string())
+//!     .collect();
+//! assert_eq!(first.join(" "), "1 2 3 4 5 6 8 9 10 12 15 16 18 20 24 25 27 30 32 36");
+//! assert_eq!(Hamming::<BigUint>::new(128).nth(1690).unwrap().to_string(), "2125764000");
+//! ```
+
+// hamming_numbers_alt.rs uses it from here, since it can't be declared twice
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
 
-extern crate num;
-use num::bigint::{BigUint, ToBigUint};
-use num::traits::One;
-use num::one;
+use self::utils::bignum::BigUint;
 use std::cmp::min;
 use std::collections::VecDeque;
-use std::ops::Mul;
 
 // needed because hamming_numbers_alt uses this as a library
 #[allow(dead_code)]
@@ -20,8 +30,8 @@ fn main() {
 
     for (idx, h) in hamming.enumerate().take(1_000_000) {
         match idx + 1 {
-            1...20 => print!("{} ", h.to_biguint().unwrap()),
-            i @ 1691 | i @ 1000000 => println!("\n{}th number: {}", i, h.to_biguint().unwrap()),
+            1..=20 => print!("{} ", h.to_biguint()),
+            i @ 1691 | i @ 1000000 => println!("\n{}th number: {}", i, h.to_biguint()),
             _ =>  continue
         }
     }
@@ -29,19 +39,35 @@ fn main() {
 
 //representing a Hamming number as a BigUint
 impl HammingNumber for BigUint {
+    fn one() -> BigUint {
+        BigUint::one()
+    }
+
     // returns the multipliers 2, 3 and 5 in the representation for the HammingNumber
     fn multipliers() -> (BigUint, BigUint, BigUint) {
-        (2u8.to_biguint().unwrap(),
-        3u8.to_biguint().unwrap(),
-        5u8.to_biguint().unwrap())
+        (BigUint::from(2u32), BigUint::from(3u32), BigUint::from(5u32))
+    }
+
+    fn times(&self, other: &BigUint) -> BigUint {
+        self * other
+    }
+
+    fn to_biguint(&self) -> BigUint {
+        self.clone()
     }
 }
 
 /// representation of a Hamming number
 /// allows to abstract on how the hamming number is stored
 /// i.e. as BigUint directly or just as the powers of 2, 3 and 5 used to build it
-pub trait HammingNumber : Eq + Ord + ToBigUint + Mul<Output=Self> + One + Clone {
+pub trait HammingNumber : Eq + Ord + Clone {
+    /// 1, the first Hamming number
+    fn one() -> Self;
     fn multipliers() -> (Self, Self, Self);
+    /// the product of two Hamming numbers, which is one too
+    fn times(&self, other: &Self) -> Self;
+    /// the value of the number
+    fn to_biguint(&self) -> BigUint;
 }
 
 /// Hamming numbers are multiples of 2, 3 or 5.
@@ -65,9 +91,9 @@ impl<T: HammingNumber> Hamming<T> {
             q5: VecDeque::with_capacity(n)
         };
 
-        h.q2.push_back(one());
-        h.q3.push_back(one());
-        h.q5.push_back(one());
+        h.q2.push_back(T::one());
+        h.q3.push_back(T::one());
+        h.q5.push_back(T::one());
 
         h
     }
@@ -75,9 +101,9 @@ impl<T: HammingNumber> Hamming<T> {
     /// Pushes the next multiple of `n` (x2, x3, x5) to the queues
     pub fn enqueue(&mut self, n: T) {
         let (two, three, five) : (T, T, T) = HammingNumber::multipliers();
-        self.q2.push_back(two * n.clone());
-        self.q3.push_back(three * n.clone());
-        self.q5.push_back(five * n.clone());
+        self.q2.push_back(two.times(&n));
+        self.q3.push_back(three.times(&n));
+        self.q5.push_back(five.times(&n));
     }
 }
 
@@ -116,34 +142,35 @@ impl<T: HammingNumber> Iterator for Hamming<T> {
 #[test]
 fn create() {
     let mut h = Hamming::<BigUint>::new(5);
-    h.q2.push_back(one::<BigUint>());
-    h.q2.push_back(one::<BigUint>() * 3.to_biguint().unwrap());
+    h.q2.push_back(BigUint::one());
+    h.q2.push_back(&BigUint::one() * &BigUint::from(3u32));
 
-    assert_eq!(h.q2.pop_front().unwrap(), one::<BigUint>());
+    assert_eq!(h.q2.pop_front().unwrap(), BigUint::one());
 }
 
 #[test]
 fn try_enqueue() {
     let mut h = Hamming::<BigUint>::new(5);
     let (two, three, five): (BigUint, BigUint, BigUint) = HammingNumber::multipliers();
-    h.enqueue(one::<BigUint>());
-    h.enqueue((one::<BigUint>() * two.clone()));
-
-    assert!(h.q2.pop_front().unwrap() == one::<BigUint>());
-    assert!(h.q3.pop_front().unwrap() == one::<BigUint>());
-    assert!(h.q5.pop_front().unwrap() == one::<BigUint>());
-    assert!(h.q2.pop_front().unwrap() == one::<BigUint>() * two);
-    assert!(h.q3.pop_front().unwrap() == one::<BigUint>() * three);
-    assert!(h.q5.pop_front().unwrap() == one::<BigUint>() * five);
+    h.enqueue(BigUint::one());
+    h.enqueue(&BigUint::one() * &two);
+
+    assert!(h.q2.pop_front().unwrap() == BigUint::one());
+    assert!(h.q3.pop_front().unwrap() == BigUint::one());
+    assert!(h.q5.pop_front().unwrap() == BigUint::one());
+    assert!(h.q2.pop_front().unwrap() == &BigUint::one() * &two);
+    assert!(h.q3.pop_front().unwrap() == &BigUint::one() * &three);
+    assert!(h.q5.pop_front().unwrap() == &BigUint::one() * &five);
  }
 
 #[test]
 fn hamming_iter() {
     let mut hamming = Hamming::<BigUint>::new(20);
-    assert!(hamming.nth(19).unwrap().to_biguint() == 36.to_biguint());
+    assert!(hamming.nth(19).unwrap().to_biguint() == BigUint::from(36u32));
 }
 
-#[ignore] // Please run this if you modify the file.  It is too slow to run normally.
+// Please run this if you modify the file, with `--features slow`.  It is too slow to run normally.
+#[cfg_attr(not(feature = "slow"), ignore)]
 #[test]
 
 fn hamming_iter_1million() {
@@ -151,6 +178,6 @@ fn hamming_iter_1million() {
     // one-million-th hamming number has index 999_999 because indexes are zero-based
     assert_eq!(hamming.nth(999_999).unwrap().to_biguint(),
         "519312780448388736089589843750000000000000000000000000000000000000000000000000000000"
-        .parse::<BigUint>().ok()
+        .parse::<BigUint>().unwrap()
         );
 }
@@ -1,23 +1,29 @@
-// Implements http://rosettacode.org/wiki/Hamming_numbers
-// alternate version: uses a more efficient representation of Hamming numbers:
-// instead of storing them as BigUint directly, it stores the three exponents
-// i, j and k for 2^i * 3^j * 5 ^k and the logarithm of the number for comparisons
-#![allow(unused_features)]
-#![allow(unused_attributes)]
-#![feature(collections)]
-
-
-extern crate num;
-
-use hamming_numbers::{Hamming, HammingNumber};
-use num::pow;
-use num::traits::One;
-use num::bigint::{BigUint, ToBigUint};
+//! Implements http://rosettacode.org/wiki/Hamming_numbers
+//! alternate version: uses a more efficient representation of Hamming numbers:
+//! instead of storing them as BigUint directly, it stores the three exponents
+//! i, j and k for 2^i * 3^j * 5 ^k and the logarithm of the number for comparisons
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::hamming_numbers_alt::hamming_numbers::{Hamming, HammingNumber};
+//! use tasks::hamming_numbers_alt::HammingTriple;
+//!
+//! let first: Vec<String> = Hamming::<HammingTriple>::new(128).take(20)
+//!     .map(|h| h.to_biguint().to_string())
+//!     .collect();
+//! assert_eq!(first.join(" "), "1 2 3 4 5 6 8 9 10 12 15 16 18 20 24 25 27 30 32 36");
+//! let h = Hamming::<HammingTriple>::new(128).nth(1690).unwrap();
+//! assert_eq!(h.to_biguint().to_string(), "2125764000");
+//! ```
+use self::hamming_numbers::{Hamming, HammingNumber};
+use self::hamming_numbers::utils::bignum::BigUint;
 use std::ops::Mul;
 use std::cmp::Ordering;
 use std::cmp::Ordering::{Less, Equal, Greater};
 
-mod hamming_numbers;
+// pub, for the Hamming iterator of the example above
+#[path = "hamming_numbers.rs"]
+pub mod hamming_numbers;
 
 #[cfg(not(test))]
 fn main() {
@@ -26,15 +32,15 @@ fn main() {
 
     for (idx, h) in hamming.enumerate().take(1_000_000) {
         match idx + 1 {
-            1...20 => print!("{} ", h.to_biguint().unwrap()),
-            i @ 1691 | i @ 1_000_000 => println!("\n{}th number: {}", i, h.to_biguint().unwrap()),
+            1..=20 => print!("{} ", h.to_biguint()),
+            i @ 1691 | i @ 1_000_000 => println!("\n{}th number: {}", i, h.to_biguint()),
             _ =>  continue
         }
     }
 }
 
 // we store these to calculate the ln of a hamming number
-pub const LN_2: f64 = 0.693147180559945309417232121458176568075500134360255254120680_f64;
+pub const LN_2: f64 = std::f64::consts::LN_2;
 pub const LN_3: f64 = 1.098612288668109691395245236922525704647490557822749451734694_f64;
 pub const LN_5: f64 = 1.609437912434100374600759333226187639525601354268517721912647_f64;
 
@@ -63,37 +69,37 @@ impl Mul for HammingTriple {
     }
 }
 
-impl One for HammingTriple {
+impl HammingNumber for HammingTriple {
     // 1 as an HammingNumber is 2^0 * 3^0 * 5^0
     // ln(1) = 0
     fn one() -> HammingTriple {
         HammingTriple::new(0, 0, 0)
     }
-}
 
-impl HammingNumber for HammingTriple {
     fn multipliers() -> (HammingTriple, HammingTriple, HammingTriple) {
         (HammingTriple { pow_2: 1, pow_3: 0, pow_5: 0, ln: LN_2 },
         HammingTriple { pow_2: 0, pow_3: 1, pow_5: 0, ln: LN_3 },
         HammingTriple { pow_2: 0, pow_3: 0, pow_5: 1, ln: LN_5 })
     }
-}
 
-impl ToBigUint for HammingTriple {
-   // calculate the value as a BigUint
-    fn to_biguint(&self) -> Option<BigUint> {
-        Some(pow(2u8.to_biguint().unwrap(), self.pow_2) *
-        pow(3u8.to_biguint().unwrap(), self.pow_3) *
-        pow(5u8.to_biguint().unwrap(), self.pow_5))
+    fn times(&self, other: &HammingTriple) -> HammingTriple {
+        *self * *other
+    }
+
+    // calculate the value as a BigUint
+    fn to_biguint(&self) -> BigUint {
+        &(&BigUint::from(2u32).pow(self.pow_2 as u32) *
+        &BigUint::from(3u32).pow(self.pow_3 as u32)) *
+        &BigUint::from(5u32).pow(self.pow_5 as u32)
     }
 }
 
 impl HammingTriple {
     fn new(pow_2: usize, pow_3: usize, pow_5: usize) -> HammingTriple {
         HammingTriple {
-            pow_2: pow_2,
-            pow_3: pow_3,
-            pow_5: pow_5,
+            pow_2,
+            pow_3,
+            pow_5,
             ln: (pow_2 as f64) * LN_2 + (pow_3 as f64) * LN_3 + (pow_5 as f64) * LN_5
         }
     }
@@ -115,8 +121,9 @@ impl PartialEq for HammingTriple {
 
 impl Eq for HammingTriple {}
 
-impl PartialOrd for HammingTriple {
-    fn partial_cmp(&self, other: &HammingTriple) -> Option<Ordering> {
+impl HammingTriple {
+    // Compare by the powers, or by the logarithms if they're far enough apart to tell
+    fn cmp_quickly(&self, other: &HammingTriple) -> Option<Ordering> {
         if self == other { Some(Equal) }
         else if self.pow_2 >= other.pow_2 && self.pow_3 >= other.pow_3 &&
             self.pow_5 >= other.pow_5 { Some(Greater) }
@@ -128,13 +135,19 @@ impl PartialOrd for HammingTriple {
     }
 }
 
+impl PartialOrd for HammingTriple {
+    fn partial_cmp(&self, other: &HammingTriple) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for HammingTriple {
     fn cmp(&self, other: &HammingTriple) -> Ordering {
         // as a last resort we need to calculate the BigUint values and compare them.
         // This should be rare. The reason is that for very big values floating point precision
         // could make hamming_1.ln == hamming_2.ln even if the two numbers are actually different
-        self.partial_cmp(other).unwrap_or_else( ||
-            self.to_biguint().unwrap().cmp(&other.to_biguint().unwrap())
+        self.cmp_quickly(other).unwrap_or_else( ||
+            self.to_biguint().cmp(&other.to_biguint())
         )
     }
 }
@@ -142,7 +155,7 @@ impl Ord for HammingTriple {
 #[test]
 fn hamming_iter() {
     let mut hamming = Hamming::<HammingTriple>::new(20);
-    assert!(hamming.nth(19).unwrap().to_biguint() == 36u8.to_biguint());
+    assert!(hamming.nth(19).unwrap().to_biguint() == BigUint::from(36u32));
 }
 
 #[test]
@@ -152,6 +165,6 @@ fn hamming_iter_1million() {
     // one-million-th hamming number has index 999_999 because indexes are zero-based
     assert_eq!(hamming.nth(999_999).unwrap().to_biguint(),
         "519312780448388736089589843750000000000000000000000000000000000000000000000000000000"
-        .parse::<BigUint>().ok()
+        .parse::<BigUint>().unwrap()
         );
 }
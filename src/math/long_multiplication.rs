@@ -0,0 +1,224 @@
+//! Implements http://rosettacode.org/wiki/Long_multiplication
+//!
+//! Numbers are vectors of decimal digits, least significant first, and multiplied the way it's
+//! done on paper: the first number times each digit of the second, each row shifted one place
+//! further left, and the rows added up, carrying tens as it goes.  That's quadratic in the number
+//! of digits, so for long numbers karatsuba() splits each into a high and a low half,
+//!
+//! ```text
+//!     (a * 10^m + b) * (c * 10^m + d) = ac * 10^2m + ((a + b)(c + d) - ac - bd) * 10^m + bd
+//! ```
+//!
+//! which takes three multiplications of half the size instead of four.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::long_multiplication::{karatsuba, multiply, parse, power_of_two, to_string};
+//!
+//! let two_to_the_64 = power_of_two(64);
+//! assert_eq!(to_string(&two_to_the_64), "18446744073709551616");
+//! let product = multiply(&two_to_the_64, &two_to_the_64);
+//! assert_eq!(to_string(&product), "340282366920938463463374607431768211456");
+//! assert_eq!(karatsuba(&two_to_the_64, &two_to_the_64), product);
+//! assert_eq!(parse("340282366920938463463374607431768211456"), Some(product));
+//! assert_eq!(parse("12a"), None);
+//! ```
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+// Below this many digits, karatsuba() multiplies the long way
+const KARATSUBA_THRESHOLD: usize = 32;
+
+// The digits of a decimal number, or None if it isn't one
+pub fn parse(number: &str) -> Option<Vec<u8>> {
+    if number.is_empty() || !number.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut digits: Vec<u8> = number.bytes().rev().map(|c| c - b'0').collect();
+    trim(&mut digits);
+    Some(digits)
+}
+
+pub fn to_string(digits: &[u8]) -> String {
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().map(|&digit| (b'0' + digit) as char).collect()
+}
+
+// Drop leading zeros, so that zero has no digits at all
+fn trim(digits: &mut Vec<u8>) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+}
+
+pub fn add(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let column = a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0) + carry;
+        sum.push(column % 10);
+        carry = column / 10;
+    }
+    if carry > 0 {
+        sum.push(carry);
+    }
+    sum
+}
+
+// a - b, where b is at most a
+fn subtract(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &digit) in a.iter().enumerate() {
+        let take = b.get(i).unwrap_or(&0) + borrow;
+        if digit >= take {
+            difference.push(digit - take);
+            borrow = 0;
+        } else {
+            difference.push(digit + 10 - take);
+            borrow = 1;
+        }
+    }
+    assert_eq!(borrow, 0, "subtracting a bigger number");
+    trim(&mut difference);
+    difference
+}
+
+// `number` times 10^places
+fn shift(number: &[u8], places: usize) -> Vec<u8> {
+    if number.is_empty() {
+        return Vec::new();
+    }
+    let mut shifted = vec![0; places];
+    shifted.extend_from_slice(number);
+    shifted
+}
+
+// A row of the working: `number` times the single digit `digit`, shifted `places` left
+pub fn partial_product(number: &[u8], digit: u8, places: usize) -> Vec<u8> {
+    let mut row = Vec::with_capacity(number.len() + 1);
+    let mut carry = 0;
+    for &d in number {
+        let product = d * digit + carry;
+        row.push(product % 10);
+        carry = product / 10;
+    }
+    if carry > 0 {
+        row.push(carry);
+    }
+    trim(&mut row);
+    shift(&row, places)
+}
+
+// The long way: a row for each digit of b, added up
+pub fn multiply(a: &[u8], b: &[u8]) -> Vec<u8> {
+    b.iter()
+        .enumerate()
+        .map(|(places, &digit)| partial_product(a, digit, places))
+        .fold(Vec::new(), |sum, row| add(&sum, &row))
+}
+
+pub fn karatsuba(a: &[u8], b: &[u8]) -> Vec<u8> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return multiply(a, b);
+    }
+    let half = a.len().max(b.len()) / 2;
+    let split = |number: &[u8]| -> (Vec<u8>, Vec<u8>) {
+        let (low, high) = number.split_at(half.min(number.len()));
+        let mut low = low.to_vec();
+        trim(&mut low);
+        (high.to_vec(), low)
+    };
+    let (a_high, a_low) = split(a);
+    let (b_high, b_low) = split(b);
+    let high = karatsuba(&a_high, &b_high);
+    let low = karatsuba(&a_low, &b_low);
+    let middle = karatsuba(&add(&a_high, &a_low), &add(&b_high, &b_low));
+    let middle = subtract(&subtract(&middle, &high), &low);
+    let mut product = add(&add(&shift(&high, 2 * half), &shift(&middle, half)), &low);
+    trim(&mut product);
+    product
+}
+
+// 2^n, doubling digit by digit
+pub fn power_of_two(n: u32) -> Vec<u8> {
+    (0..n).fold(vec![1], |power, _| partial_product(&power, 2, 0))
+}
+
+#[cfg(not(test))]
+fn main() {
+    let a = power_of_two(64);
+    let width = 2 * a.len() + 1;
+    println!("{:>width$}", to_string(&a), width = width);
+    println!("{:>width$}", format!("× {}", to_string(&a)), width = width);
+    println!("{:>width$}", "-".repeat(width), width = width);
+    for (places, &digit) in a.iter().enumerate() {
+        let row = to_string(&partial_product(&a, digit, places));
+        println!("{:>width$}", row, width = width);
+    }
+    println!("{:>width$}", "-".repeat(width), width = width);
+    println!("{:>width$}", to_string(&multiply(&a, &a)), width = width);
+    println!("\n2^64 * 2^64 = {}", to_string(&karatsuba(&a, &a)));
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::bignum::BigUint;
+    use super::utils::rng::Rng;
+    use super::{add, karatsuba, multiply, parse, power_of_two, subtract, to_string};
+
+    fn number(text: &str) -> Vec<u8> {
+        parse(text).unwrap()
+    }
+
+    #[test]
+    fn task() {
+        let a = power_of_two(64);
+        assert_eq!(to_string(&a), "18446744073709551616");
+        assert_eq!(to_string(&multiply(&a, &a)), "340282366920938463463374607431768211456");
+        assert_eq!(karatsuba(&a, &a), power_of_two(128));
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(number("00120"), [0, 2, 1]);
+        assert_eq!(to_string(&number("000")), "0");
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("12a"), None);
+        assert_eq!(to_string(&add(&number("999"), &number("1"))), "1000");
+        assert_eq!(to_string(&subtract(&number("1000"), &number("1"))), "999");
+        assert_eq!(to_string(&multiply(&number("123"), &number("456"))), "56088");
+        assert_eq!(to_string(&multiply(&number("123"), &number("0"))), "0");
+        assert_eq!(to_string(&multiply(&number("0"), &number("456"))), "0");
+    }
+
+    #[test]
+    fn against_bignum() {
+        let mut rng = Rng::new(87);
+        for _ in 0..50 {
+            let (length_a, length_b) = (rng.gen_range(1usize, 200), rng.gen_range(1usize, 200));
+            let mut random = |length: usize| -> String {
+                (0..length).map(|_| (b'0' + rng.gen_range(0u8, 10)) as char).collect()
+            };
+            let (a, b) = (random(length_a), random(length_b));
+            let big = &a.parse::<BigUint>().unwrap() * &b.parse::<BigUint>().unwrap();
+            let product = multiply(&number(&a), &number(&b));
+            assert_eq!(to_string(&product), big.to_string(), "{} * {}", a, b);
+            assert_eq!(karatsuba(&number(&a), &number(&b)), product, "{} * {}", a, b);
+        }
+    }
+
+    #[test]
+    fn long_karatsuba() {
+        let a = number(&"9".repeat(500));
+        let b = number(&"12345678901234567890".repeat(30));
+        let expected = &to_string(&a).parse::<BigUint>().unwrap()
+            * &to_string(&b).parse::<BigUint>().unwrap();
+        assert_eq!(to_string(&karatsuba(&a, &b)), expected.to_string());
+    }
+}
@@ -0,0 +1,195 @@
+//! Implements http://rosettacode.org/wiki/LU_decomposition
+//!
+//! Factors a square matrix A as P A = L U, with L lower triangular with ones on its diagonal, U
+//! upper triangular and P a permutation matrix.  It's Gaussian elimination that keeps the
+//! multipliers (Doolittle's method): the multiple of row k subtracted from row i to make a zero
+//! below U's diagonal is L's element (i, k).  The rows are swapped so that each pivot is the
+//! biggest left in its column, and P records how they ended up.
+//!
+//! Once A is factored, A x = b is L y = P b, which is solved from the top down, and then U x = y
+//! from the bottom up: n^2 operations for each b, against n^3 to eliminate again.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::lu_decomposition::{decompose, examples};
+//!
+//! let a = &examples()[0];
+//! let lu = decompose(a);
+//! assert_eq!((lu.l[(1, 0)], lu.l[(2, 0)], lu.l[(2, 1)]), (0.5, 0.5, -1.0));
+//! assert_eq!((lu.u[(0, 0)], lu.u[(1, 1)], lu.u[(2, 2)]), (2.0, 1.0, -2.0));
+//! assert_eq!(&lu.p() * a, &lu.l * &lu.u);
+//! let x = lu.solve(&[9.0, 13.0, 2.0]).unwrap();
+//! assert_eq!(x, [1.0, 1.0, 1.0]);
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::matrix::Matrix;
+
+pub struct Lu {
+    pub l: Matrix<f64>,
+    pub u: Matrix<f64>,
+    // The row of A that's row i of P A, from which p() makes P
+    permutation: Vec<usize>,
+}
+
+pub fn decompose(a: &Matrix<f64>) -> Lu {
+    assert!(a.is_square(), "only square matrices have LU decompositions, not {}x{}", a.rows(),
+            a.cols());
+    let n = a.rows();
+    let mut u = a.clone();
+    let mut l = Matrix::identity(n);
+    let mut permutation: Vec<usize> = (0..n).collect();
+    for k in 0..n {
+        // The first of any that are equally big, which is the one the task's example takes
+        let pivot = (k + 1..n).fold(k, |best, i| {
+            if u[(i, k)].abs() > u[(best, k)].abs() { i } else { best }
+        });
+        if pivot != k {
+            u.swap_rows(k, pivot);
+            permutation.swap(k, pivot);
+            // The multipliers swap along with their rows, but not L's diagonal
+            for j in 0..k {
+                let (above, below) = (l[(k, j)], l[(pivot, j)]);
+                l[(k, j)] = below;
+                l[(pivot, j)] = above;
+            }
+        }
+        // A column of zeros is already eliminated, and leaves U singular
+        if u[(k, k)] == 0.0 {
+            continue;
+        }
+        for i in k + 1..n {
+            let factor = u[(i, k)] / u[(k, k)];
+            l[(i, k)] = factor;
+            u[(i, k)] = 0.0;
+            for j in k + 1..n {
+                u[(i, j)] -= factor * u[(k, j)];
+            }
+        }
+    }
+    Lu { l, u, permutation }
+}
+
+impl Lu {
+    pub fn p(&self) -> Matrix<f64> {
+        let n = self.permutation.len();
+        Matrix::from_fn(n, n, |i, j| if self.permutation[i] == j { 1.0 } else { 0.0 })
+    }
+
+    // The x with A x = b, or None if A is singular
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = self.permutation.len();
+        assert_eq!(b.len(), n, "{} equations but {} right hand sides", n, b.len());
+        if (0..n).any(|i| self.u[(i, i)] == 0.0) {
+            return None;
+        }
+        let mut y = vec![0.0; n];
+        for i in 0..n {
+            let known: f64 = (0..i).map(|j| self.l[(i, j)] * y[j]).sum();
+            y[i] = b[self.permutation[i]] - known;
+        }
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            let known: f64 = (i + 1..n).map(|j| self.u[(i, j)] * x[j]).sum();
+            x[i] = (y[i] - known) / self.u[(i, i)];
+        }
+        Some(x)
+    }
+}
+
+// The task's two matrices
+pub fn examples() -> Vec<Matrix<f64>> {
+    vec![
+        Matrix::from_rows(vec![vec![1.0, 3.0, 5.0], vec![2.0, 4.0, 7.0], vec![1.0, 1.0, 0.0]]),
+        Matrix::from_rows(vec![vec![11.0, 9.0, 24.0, 2.0], vec![1.0, 5.0, 2.0, 6.0],
+                               vec![3.0, 17.0, 18.0, 1.0], vec![2.0, 5.0, 7.0, 1.0]]),
+    ]
+}
+
+#[cfg(not(test))]
+fn main() {
+    for (i, a) in examples().iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let lu = decompose(a);
+        println!("A\n{:.5}\n\nL\n{:.5}\n\nU\n{:.5}\n\nP\n{:.5}", a, lu.l, lu.u, lu.p());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{decompose, examples, Matrix};
+
+    fn assert_close(a: &Matrix<f64>, b: &Matrix<f64>, tolerance: f64) {
+        let difference = (a - b).into_vec();
+        assert!(difference.iter().all(|x| x.abs() < tolerance), "\n{}\n\nisn't\n\n{}", a, b);
+    }
+
+    #[test]
+    fn task() {
+        let examples = examples();
+        let lu = decompose(&examples[0]);
+        assert_close(&lu.l, &Matrix::from_rows(vec![vec![1.0, 0.0, 0.0], vec![0.5, 1.0, 0.0],
+                                                    vec![0.5, -1.0, 1.0]]), 1e-12);
+        assert_close(&lu.u, &Matrix::from_rows(vec![vec![2.0, 4.0, 7.0], vec![0.0, 1.0, 1.5],
+                                                    vec![0.0, 0.0, -2.0]]), 1e-12);
+        assert_eq!(lu.p(), Matrix::from_rows(vec![vec![0.0, 1.0, 0.0], vec![1.0, 0.0, 0.0],
+                                                 vec![0.0, 0.0, 1.0]]));
+
+        // The task gives these to five places
+        let lu = decompose(&examples[1]);
+        let l = Matrix::from_rows(vec![vec![1.0, 0.0, 0.0, 0.0],
+                                       vec![0.27273, 1.0, 0.0, 0.0],
+                                       vec![0.09091, 0.2875, 1.0, 0.0],
+                                       vec![0.18182, 0.23125, 0.0036, 1.0]]);
+        let u = Matrix::from_rows(vec![vec![11.0, 9.0, 24.0, 2.0],
+                                       vec![0.0, 14.54545, 11.45455, 0.45455],
+                                       vec![0.0, 0.0, -3.475, 5.6875],
+                                       vec![0.0, 0.0, 0.0, 0.51079]]);
+        assert_close(&lu.l, &l, 1e-5);
+        assert_close(&lu.u, &u, 1e-5);
+        assert_eq!(lu.p(), Matrix::from_rows(vec![vec![1.0, 0.0, 0.0, 0.0],
+                                                 vec![0.0, 0.0, 1.0, 0.0],
+                                                 vec![0.0, 1.0, 0.0, 0.0],
+                                                 vec![0.0, 0.0, 0.0, 1.0]]));
+    }
+
+    #[test]
+    fn random_factorizations() {
+        let mut rng = Rng::new(91);
+        for n in 1..30 {
+            let a = Matrix::from_fn(n, n, |_, _| rng.next_f64() * 20.0 - 10.0);
+            let lu = decompose(&a);
+            assert_close(&(&lu.p() * &a), &(&lu.l * &lu.u), 1e-10);
+            for i in 0..n {
+                assert_eq!(lu.l[(i, i)], 1.0);
+                for j in i + 1..n {
+                    assert_eq!((lu.l[(i, j)], lu.u[(j, i)]), (0.0, 0.0));
+                }
+                // Partial pivoting keeps the multipliers at most 1
+                assert!(lu.l.row(i).iter().all(|x| x.abs() <= 1.0));
+            }
+
+            let b: Vec<f64> = (0..n).map(|_| rng.next_f64() * 10.0 - 5.0).collect();
+            let x = lu.solve(&b).unwrap();
+            let ax = &a * &Matrix::new(n, 1, x);
+            assert_close(&ax, &Matrix::new(n, 1, b), 1e-9);
+        }
+    }
+
+    #[test]
+    fn singular() {
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0],
+                                       vec![1.0, 0.0, 1.0]]);
+        let lu = decompose(&a);
+        assert_close(&(&lu.p() * &a), &(&lu.l * &lu.u), 1e-12);
+        assert_eq!(lu.solve(&[1.0, 2.0, 3.0]), None);
+        let zeros = decompose(&Matrix::zeros(2, 2));
+        assert_eq!((zeros.l, zeros.u), (Matrix::identity(2), Matrix::zeros(2, 2)));
+    }
+}
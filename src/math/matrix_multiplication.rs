@@ -0,0 +1,224 @@
+//! Implements http://rosettacode.org/wiki/Matrix_multiplication
+//!
+//! Three ways of multiplying utils::matrix::Matrix values, which all give the same product:
+//!
+//! naive() is the definition, element (i, j) the dot product of row i of a and column j of b.
+//! Walking down the columns of b takes a cache miss an element once b is too big to stay in cache.
+//!
+//! blocked() multiplies BLOCK x BLOCK tiles at a time instead, small enough that a tile of each of
+//! a, b and the product all fit in cache together, and in i, k, j order within a tile so that the
+//! innermost loop runs along rows.  It's the same n^3 multiplications, just fewer misses.
+//!
+//! strassen() splits each matrix into four quarters and makes the product's quarters out of seven
+//! products of quarters rather than eight,
+//!
+//! ```text
+//!     M1 = (A11 + A22)(B11 + B22)    C11 = M1 + M4 - M5 + M7
+//!     M2 = (A21 + A22) B11           C12 = M3 + M5
+//!     M3 = A11 (B12 - B22)           C21 = M2 + M4
+//!     M4 = A22 (B21 - B11)           C22 = M1 - M2 + M3 + M6
+//!     M5 = (A11 + A12) B22
+//!     M6 = (A21 - A11)(B11 + B12)
+//!     M7 = (A12 - A22)(B21 + B22)
+//! ```
+//!
+//! which recursively makes n^2.81 multiplications.  The extra additions don't pay for small
+//! matrices, so below STRASSEN_THRESHOLD rows it multiplies them blocked().  Odd sizes are padded
+//! with a row and column of zeros, and the padding cut off the product again.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::matrix_multiplication::utils::matrix::Matrix;
+//! use tasks::matrix_multiplication::{blocked, naive, strassen_with_threshold};
+//!
+//! let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+//! let b = Matrix::from_rows(vec![vec![5, 6], vec![7, 8]]);
+//! let product = Matrix::from_rows(vec![vec![19, 22], vec![43, 50]]);
+//! assert_eq!(naive(&a, &b), product);
+//! assert_eq!(blocked(&a, &b), product);
+//! assert_eq!(strassen_with_threshold(&a, &b, 1), product);
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::matrix::{Element, Matrix};
+use std::ops::Neg;
+
+// The side of the tiles blocked() multiplies, 64 * 64 * 8 bytes = 32KB of f64s for each
+const BLOCK: usize = 64;
+
+// The size below which strassen() stops splitting.  Copying out the quarters costs about what the
+// multiplication it saves is worth, up to a few hundred rows (see benches/matrix.rs).
+const STRASSEN_THRESHOLD: usize = 256;
+
+fn check_sizes<T>(a: &Matrix<T>, b: &Matrix<T>) {
+    assert_eq!(a.cols(), b.rows(), "can't multiply a {}x{} matrix by a {}x{} one", a.rows(),
+               a.cols(), b.rows(), b.cols());
+}
+
+pub fn naive<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    check_sizes(a, b);
+    Matrix::from_fn(a.rows(), b.cols(), |i, j| {
+        (0..a.cols()).fold(T::zero(), |sum, k| sum + a[(i, k)] * b[(k, j)])
+    })
+}
+
+pub fn blocked<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    check_sizes(a, b);
+    let (n, m, p) = (a.rows(), a.cols(), b.cols());
+    let mut product = Matrix::zeros(n, p);
+    for i0 in (0..n).step_by(BLOCK) {
+        for k0 in (0..m).step_by(BLOCK) {
+            for j0 in (0..p).step_by(BLOCK) {
+                for i in i0..n.min(i0 + BLOCK) {
+                    for k in k0..m.min(k0 + BLOCK) {
+                        let a_ik = a[(i, k)];
+                        let b_row = &b.row(k)[j0..p.min(j0 + BLOCK)];
+                        let row = &mut product.row_mut(i)[j0..p.min(j0 + BLOCK)];
+                        for (x, &b_kj) in row.iter_mut().zip(b_row) {
+                            *x = *x + a_ik * b_kj;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    product
+}
+
+// Strassen's subtractions go below zero, so it's only for types with negative numbers
+pub fn strassen<T: Element + Neg<Output = T>>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    strassen_with_threshold(a, b, STRASSEN_THRESHOLD)
+}
+
+// strassen(), multiplying matrices of fewer than `threshold` rows blocked()
+pub fn strassen_with_threshold<T: Element + Neg<Output = T>>(a: &Matrix<T>,
+                                                             b: &Matrix<T>,
+                                                             threshold: usize)
+                                                             -> Matrix<T> {
+    check_sizes(a, b);
+    // Both square and the same size, so that the quarters line up
+    let n = a.rows().max(a.cols()).max(b.cols());
+    let product = square(&pad(a, n), &pad(b, n), threshold.max(1));
+    product.submatrix(0..a.rows(), 0..b.cols())
+}
+
+// `m` in the top left corner of an n x n matrix of zeros
+fn pad<T: Element>(m: &Matrix<T>, n: usize) -> Matrix<T> {
+    if m.rows() == n && m.cols() == n {
+        return m.clone();
+    }
+    Matrix::from_fn(n, n, |i, j| if i < m.rows() && j < m.cols() { m[(i, j)] } else { T::zero() })
+}
+
+fn square<T: Element + Neg<Output = T>>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize)
+                                        -> Matrix<T> {
+    let n = a.rows();
+    if n < threshold || n < 2 {
+        return blocked(a, b);
+    }
+    if n % 2 == 1 {
+        return square(&pad(a, n + 1), &pad(b, n + 1), threshold).submatrix(0..n, 0..n);
+    }
+    let half = n / 2;
+    let quarters = |m: &Matrix<T>| {
+        (m.submatrix(0..half, 0..half), m.submatrix(0..half, half..n),
+         m.submatrix(half..n, 0..half), m.submatrix(half..n, half..n))
+    };
+    let (a11, a12, a21, a22) = quarters(a);
+    let (b11, b12, b21, b22) = quarters(b);
+    let multiply = |x: Matrix<T>, y: Matrix<T>| square(&x, &y, threshold);
+    let m1 = multiply(&a11 + &a22, &b11 + &b22);
+    let m2 = multiply(&a21 + &a22, b11.clone());
+    let m3 = multiply(a11.clone(), &b12 - &b22);
+    let m4 = multiply(a22.clone(), &b21 - &b11);
+    let m5 = multiply(&a11 + &a12, b22.clone());
+    let m6 = multiply(&a21 - &a11, &b11 + &b12);
+    let m7 = multiply(&a12 - &a22, &b21 + &b22);
+    let c11 = &(&(&m1 + &m4) - &m5) + &m7;
+    let c12 = &m3 + &m5;
+    let c21 = &m2 + &m4;
+    let c22 = &(&(&m1 - &m2) + &m3) + &m6;
+    Matrix::from_fn(n, n, |i, j| match (i < half, j < half) {
+        (true, true) => c11[(i, j)],
+        (true, false) => c12[(i, j - half)],
+        (false, true) => c21[(i - half, j)],
+        (false, false) => c22[(i - half, j - half)],
+    })
+}
+
+#[cfg(not(test))]
+fn main() {
+    let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+    let b = Matrix::from_rows(vec![vec![-3, -8, 3], vec![-2, 1, 4]]);
+    println!("{}\n\ntimes\n\n{}\n\nis\n\n{}", a, b, naive(&a, &b));
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{blocked, naive, strassen, strassen_with_threshold, Matrix};
+
+    fn random(rng: &mut Rng, rows: usize, cols: usize) -> Matrix<i64> {
+        Matrix::from_fn(rows, cols, |_, _| rng.gen_range(-100i64, 100))
+    }
+
+    #[test]
+    fn task() {
+        let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let b = Matrix::from_rows(vec![vec![-3, -8, 3], vec![-2, 1, 4]]);
+        let product = Matrix::from_rows(vec![vec![-7, -6, 11], vec![-17, -20, 25]]);
+        assert_eq!(naive(&a, &b), product);
+        assert_eq!(blocked(&a, &b), product);
+        assert_eq!(strassen(&a, &b), product);
+        assert_eq!(strassen_with_threshold(&a, &b, 1), product);
+    }
+
+    #[test]
+    fn all_agree() {
+        // Integers, so that the products agree exactly whatever order they're added up in
+        let mut rng = Rng::new(89);
+        for &(n, m, p) in &[(1, 1, 1), (3, 5, 2), (7, 7, 7), (16, 16, 16), (65, 130, 33),
+                            (100, 1, 100), (1, 100, 1), (129, 129, 129)] {
+            let (a, b) = (random(&mut rng, n, m), random(&mut rng, m, p));
+            let product = naive(&a, &b);
+            assert_eq!(product, &a * &b, "{}x{} by {}x{}", n, m, m, p);
+            assert_eq!(blocked(&a, &b), product, "{}x{} by {}x{}", n, m, m, p);
+            assert_eq!(strassen(&a, &b), product, "{}x{} by {}x{}", n, m, m, p);
+            // Small thresholds on big matrices would make a lot of tiny multiplications
+            let thresholds: &[usize] = if n.max(m).max(p) <= 16 { &[1, 2, 5] } else { &[8, 33] };
+            for &threshold in thresholds {
+                assert_eq!(strassen_with_threshold(&a, &b, threshold), product,
+                           "{}x{} by {}x{}, threshold {}", n, m, m, p, threshold);
+            }
+        }
+    }
+
+    #[test]
+    fn floats() {
+        let mut rng = Rng::new(89);
+        let mut random = |n| Matrix::from_fn(n, n, |_, _| rng.next_f64() - 0.5);
+        let (a, b) = (random(50), random(50));
+        let product = naive(&a, &b);
+        for other in &[blocked(&a, &b), strassen_with_threshold(&a, &b, 4)] {
+            let difference = other - &product;
+            assert!(difference.into_vec().iter().all(|x| x.abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let (a, b) = (Matrix::<i64>::zeros(2, 0), Matrix::<i64>::zeros(0, 3));
+        assert_eq!(naive(&a, &b), Matrix::zeros(2, 3));
+        assert_eq!(blocked(&a, &b), Matrix::zeros(2, 3));
+        assert_eq!(strassen(&a, &b), Matrix::zeros(2, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't multiply a 2x3 matrix by a 2x3 one")]
+    fn mismatched() {
+        blocked(&Matrix::<i64>::zeros(2, 3), &Matrix::zeros(2, 3));
+    }
+}
@@ -0,0 +1,231 @@
+//! Implements http://rosettacode.org/wiki/Miller-Rabin_primality_test
+//!
+//! Write n - 1 as d 2^s with d odd.  If n is prime, then for any witness a from 2 to n - 2 either
+//! a^d = 1 mod n, or squaring a^d s - 1 times or fewer reaches n - 1, because the only square roots
+//! of 1 modulo a prime are 1 and -1.  A witness for which neither happens proves n composite, and
+//! at least three quarters of them do for any odd composite n.
+//!
+//! So k random witnesses leave a chance of at most 4^-k that a composite passes, which is what
+//! is_probable_prime() does for a BigUint.  For a u64 there's no need to guess: the first twelve
+//! primes as witnesses are known to catch every composite below 3.3 * 10^24, so is_prime() is
+//! exact.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::miller_rabin_primality_test::utils::bignum::BigUint;
+//! use tasks::miller_rabin_primality_test::utils::rng::Rng;
+//! use tasks::miller_rabin_primality_test::{is_prime, is_probable_prime};
+//!
+//! // 561 is a Carmichael number, and 3215031751 a strong pseudoprime to bases 2, 3, 5 and 7
+//! assert!(!is_prime(561) && !is_prime(3_215_031_751));
+//! assert!(is_prime(18_446_744_073_709_551_557));
+//! // 2^89 - 1 is a Mersenne prime, and 2^83 - 1 isn't
+//! let mut rng = Rng::new(42);
+//! let mersenne = |p| &BigUint::from(2u32).pow(p) - &BigUint::one();
+//! assert!(is_probable_prime(&mersenne(89), 20, &mut rng));
+//! assert!(!is_probable_prime(&mersenne(83), 20, &mut rng));
+//! ```
+
+// rsa.rs uses it from here, since it can't be declared twice
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::bignum::BigUint;
+use self::utils::rng::Rng;
+
+// Enough witnesses to decide every u64
+static WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    (a as u128 * b as u128 % n as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, n: u64) -> u64 {
+    let mut result = 1 % n;
+    base %= n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+    result
+}
+
+// Whether `a` shows the odd n, with n - 1 = d 2^s, to be composite
+fn witnesses_composite(a: u64, n: u64, d: u64, s: u32) -> bool {
+    let mut x = pow_mod(a, d, n);
+    if x == 1 || x == n - 1 {
+        return false;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return false;
+        }
+    }
+    true
+}
+
+// Whether n is prime, exactly
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    !WITNESSES.iter().any(|&a| witnesses_composite(a, n, d, s))
+}
+
+// The number, if it fits in a u64
+fn to_u64(n: &BigUint) -> Option<u64> {
+    let bytes = n.to_bytes_be();
+    if bytes.len() > 8 {
+        return None;
+    }
+    Some(bytes.iter().fold(0, |n, &byte| n << 8 | byte as u64))
+}
+
+// Whether n is prime, wrongly for a composite with a chance of at most 4^-rounds.  Numbers that
+// fit in a u64 get is_prime()'s exact answer instead.
+pub fn is_probable_prime(n: &BigUint, rounds: usize, rng: &mut Rng) -> bool {
+    if let Some(n) = to_u64(n) {
+        return is_prime(n);
+    }
+    let one = BigUint::one();
+    let two = BigUint::from(2u32);
+    if !n.bit(0) {
+        return false;
+    }
+    let n_minus_one = n - &one;
+    let s = (0..).take_while(|&i| !n_minus_one.bit(i)).count();
+    let d = &n_minus_one / &two.pow(s as u32);
+    // Witnesses from 2 to n - 2, from random bytes with plenty to spare so that reducing them is
+    // nearly uniform
+    let range = n - &BigUint::from(3u32);
+    'witnesses: for _ in 0..rounds {
+        let bytes: Vec<u8> = (0..n.bits() / 8 + 8).map(|_| rng.next_u32() as u8).collect();
+        let a = &(&BigUint::from_bytes_be(&bytes) % &range) + &two;
+        let mut x = a.pow_mod(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = &(&x * &x) % n;
+            if x == n_minus_one {
+                continue 'witnesses;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+#[cfg(not(test))]
+fn main() {
+    let primes: Vec<String> = (0..100).filter(|&n| is_prime(n)).map(|n| n.to_string()).collect();
+    println!("Primes below 100: {}", primes.join(" "));
+    for &n in &[561, 3_215_031_751, 18_446_744_073_709_551_557] {
+        println!("{} is {}", n, if is_prime(n) { "prime" } else { "composite" });
+    }
+    let mut rng = Rng::from_time();
+    // Mersenne numbers, 2^p - 1 for prime p, of which these are the prime ones up to 2^607 - 1
+    let two = BigUint::from(2u32);
+    for p in (2..608).filter(|&p| is_prime(p)) {
+        let mersenne = &two.pow(p as u32) - &BigUint::one();
+        if is_probable_prime(&mersenne, 20, &mut rng) {
+            print!("2^{} - 1 ", p);
+        }
+    }
+    println!("are probably prime");
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::bignum::BigUint;
+    use super::utils::rng::Rng;
+    use super::{is_prime, is_probable_prime};
+
+    fn sieve(limit: usize) -> Vec<bool> {
+        let mut prime = vec![true; limit];
+        prime[0] = false;
+        prime[1] = false;
+        for i in 2..limit {
+            if prime[i] {
+                for multiple in (i * i..limit).step_by(i) {
+                    prime[multiple] = false;
+                }
+            }
+        }
+        prime
+    }
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn small_numbers() {
+        for (n, &prime) in sieve(100_000).iter().enumerate() {
+            assert_eq!(is_prime(n as u64), prime, "{}", n);
+        }
+    }
+
+    #[test]
+    fn u64s() {
+        let primes = [2_147_483_647, 1_000_000_007, (1 << 61) - 1,
+                      18_446_744_073_709_551_533, 18_446_744_073_709_551_557];
+        assert!(primes.iter().all(|&n| is_prime(n)));
+        // Carmichael numbers, and strong pseudoprimes to the first few primes as witnesses
+        let composites = [561, 41_041, 825_265, 3_215_031_751, 2_152_302_898_747,
+                          3_474_749_660_383, 341_550_071_728_321, 3_825_123_056_546_413_051,
+                          u64::MAX, 4_294_967_297, 2_147_483_647 * 1_000_000_007];
+        assert!(composites.iter().all(|&n| !is_prime(n)));
+    }
+
+    #[test]
+    fn big_numbers() {
+        let mut rng = Rng::new(100);
+        let two = BigUint::from(2u32);
+        let mersenne = |p| &two.pow(p) - &BigUint::one();
+        for &p in &[61, 89, 107, 127, 521] {
+            assert!(is_probable_prime(&mersenne(p), 20, &mut rng), "2^{} - 1", p);
+        }
+        // 2^67 - 1 is 193707721 * 761838257287, and 2^128 + 1 is the composite Fermat number F7
+        assert!(!is_probable_prime(&mersenne(67), 20, &mut rng));
+        assert!(!is_probable_prime(&(&two.pow(128) + &BigUint::one()), 20, &mut rng));
+        assert!(!is_probable_prime(&two.pow(100), 20, &mut rng));
+        assert!(!is_probable_prime(&(&mersenne(89) * &mersenne(107)), 20, &mut rng));
+        // The modulus of rsa.rs
+        assert!(!is_probable_prime(&big("9516311845790656153499716760847001433441357"), 20,
+                                   &mut rng));
+    }
+
+    #[test]
+    fn past_u64() {
+        let mut rng = Rng::new(100);
+        // Small numbers as BigUints take is_prime()'s route
+        let sieve = sieve(3000);
+        for n in 0..3000u32 {
+            assert_eq!(is_probable_prime(&BigUint::from(n), 5, &mut rng), sieve[n as usize]);
+        }
+        // 2^64 + 13 is the first prime past u64::MAX, and its neighbours can't take that route
+        let two_to_64 = &BigUint::from(u64::MAX) + &BigUint::one();
+        let primes: Vec<u32> = (0..14u32)
+            .filter(|&i| is_probable_prime(&(&two_to_64 + &BigUint::from(i)), 20, &mut rng))
+            .collect();
+        assert_eq!(primes, [13]);
+        // Products of the two biggest primes that fit in a u64
+        let (p, q) = (BigUint::from(18_446_744_073_709_551_557u64),
+                      BigUint::from(18_446_744_073_709_551_533u64));
+        assert!(!is_probable_prime(&(&p * &q), 20, &mut rng));
+        assert!(!is_probable_prime(&(&p * &p), 20, &mut rng));
+    }
+}
@@ -0,0 +1,186 @@
+//! Implements http://rosettacode.org/wiki/Numerical_integration
+//!
+//! Each rule estimates the area under f over one of n equal steps of width h from a to b, and
+//! integrate() adds them up:
+//!
+//! ```text
+//!     left rectangle     h f(x)
+//!     right rectangle    h f(x + h)
+//!     midpoint           h f(x + h/2)
+//!     trapezium          h (f(x) + f(x + h)) / 2
+//!     Simpson's          h (f(x) + 4 f(x + h/2) + f(x + h)) / 6
+//! ```
+//!
+//! The rectangles are exact for constants, the midpoint and trapezium rules for straight lines,
+//! and Simpson's rule, which fits a parabola through each step, for cubics.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::numerical_integration::{Integrator, Simpson, Trapezium, INTEGRATORS};
+//!
+//! let cube = |x: f64| x * x * x;
+//! assert!((Simpson.integrate(&cube, 0.0, 1.0, 100) - 0.25).abs() < 1e-12);
+//! assert!((Trapezium.integrate(&cube, 0.0, 1.0, 100) - 0.250025).abs() < 1e-12);
+//! let names: Vec<String> = INTEGRATORS.iter().map(|integrator| integrator.to_string()).collect();
+//! assert_eq!(names, ["left rectangle", "right rectangle", "midpoint", "trapezium", "Simpson's"]);
+//! ```
+
+use std::fmt;
+
+pub trait Integrator: fmt::Display {
+    // The estimate of the area under f from x to x + h
+    fn step(&self, f: &dyn Fn(f64) -> f64, x: f64, h: f64) -> f64;
+
+    // The integral of f from a to b, in n steps
+    fn integrate(&self, f: &dyn Fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+        assert!(n > 0, "integrating takes at least one step");
+        let h = (b - a) / n as f64;
+        // Each x from a and i, rather than adding up h, which would add up its rounding too
+        (0..n).map(|i| self.step(f, a + i as f64 * h, h)).sum()
+    }
+}
+
+pub struct LeftRectangle;
+pub struct RightRectangle;
+pub struct Midpoint;
+pub struct Trapezium;
+pub struct Simpson;
+
+impl Integrator for LeftRectangle {
+    fn step(&self, f: &dyn Fn(f64) -> f64, x: f64, h: f64) -> f64 {
+        h * f(x)
+    }
+}
+
+impl Integrator for RightRectangle {
+    fn step(&self, f: &dyn Fn(f64) -> f64, x: f64, h: f64) -> f64 {
+        h * f(x + h)
+    }
+}
+
+impl Integrator for Midpoint {
+    fn step(&self, f: &dyn Fn(f64) -> f64, x: f64, h: f64) -> f64 {
+        h * f(x + h / 2.0)
+    }
+}
+
+impl Integrator for Trapezium {
+    fn step(&self, f: &dyn Fn(f64) -> f64, x: f64, h: f64) -> f64 {
+        h * (f(x) + f(x + h)) / 2.0
+    }
+}
+
+impl Integrator for Simpson {
+    fn step(&self, f: &dyn Fn(f64) -> f64, x: f64, h: f64) -> f64 {
+        h * (f(x) + 4.0 * f(x + h / 2.0) + f(x + h)) / 6.0
+    }
+}
+
+macro_rules! names {
+    ($($integrator:ident $name:expr),*) => ($(
+        impl fmt::Display for $integrator {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.pad($name)
+            }
+        }
+    )*)
+}
+
+names!(LeftRectangle "left rectangle", RightRectangle "right rectangle", Midpoint "midpoint",
+       Trapezium "trapezium", Simpson "Simpson's");
+
+pub static INTEGRATORS: [&(dyn Integrator + Sync); 5] =
+    [&LeftRectangle, &RightRectangle, &Midpoint, &Trapezium, &Simpson];
+
+// One of the task's integrals, and what it comes to
+pub struct Example {
+    pub name: &'static str,
+    pub f: fn(f64) -> f64,
+    pub a: f64,
+    pub b: f64,
+    pub steps: usize,
+    pub exact: f64,
+}
+
+pub fn examples() -> Vec<Example> {
+    vec![
+        Example { name: "x^3", f: |x| x * x * x, a: 0.0, b: 1.0, steps: 100, exact: 0.25 },
+        Example { name: "1/x", f: |x| 1.0 / x, a: 1.0, b: 100.0, steps: 1000,
+                  exact: 100f64.ln() },
+        Example { name: "x", f: |x| x, a: 0.0, b: 5000.0, steps: 5_000_000, exact: 12_500_000.0 },
+        Example { name: "x", f: |x| x, a: 0.0, b: 6000.0, steps: 6_000_000, exact: 18_000_000.0 },
+    ]
+}
+
+#[cfg(not(test))]
+fn main() {
+    for example in examples() {
+        println!("{} from {} to {} in {} steps, exactly {}", example.name, example.a, example.b,
+                 example.steps, example.exact);
+        for integrator in INTEGRATORS.iter() {
+            let area = integrator.integrate(&example.f, example.a, example.b, example.steps);
+            println!("    {:16} {:.6}", integrator, area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{examples, Integrator, Midpoint, Simpson, Trapezium, INTEGRATORS};
+
+    #[test]
+    fn task() {
+        // What each rule comes to on each of the task's integrals
+        let expected = [
+            [0.245025, 0.255025, 0.2499875, 0.250025, 0.25],
+            [4.654991, 4.556981, 4.604763, 4.605986, 4.605170],
+            [12_499_997.5, 12_500_002.5, 12_500_000.0, 12_500_000.0, 12_500_000.0],
+            [17_999_997.0, 18_000_003.0, 18_000_000.0, 18_000_000.0, 18_000_000.0],
+        ];
+        for (example, expected) in examples().iter().zip(&expected) {
+            for (integrator, &expected) in INTEGRATORS.iter().zip(expected) {
+                let area = integrator.integrate(&example.f, example.a, example.b, example.steps);
+                assert!((area - expected).abs() <= 1e-6 * expected.abs().max(1.0),
+                        "{} of {}: {} isn't {}", integrator, example.name, area, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_for_their_degree() {
+        let line = |x: f64| 3.0 * x - 1.0;
+        let cubic = |x: f64| x * x * x - 2.0 * x * x + 5.0;
+        let cubic_integral = |x: f64| x * x * x * x / 4.0 - 2.0 * x * x * x / 3.0 + 5.0 * x;
+        for &n in &[1, 2, 7] {
+            assert!((Midpoint.integrate(&line, -1.0, 2.0, n) - 1.5).abs() < 1e-12);
+            assert!((Trapezium.integrate(&line, -1.0, 2.0, n) - 1.5).abs() < 1e-12);
+            let area = Simpson.integrate(&cubic, -1.0, 2.0, n);
+            assert!((area - (cubic_integral(2.0) - cubic_integral(-1.0))).abs() < 1e-12);
+        }
+        for integrator in INTEGRATORS.iter() {
+            assert!((integrator.integrate(&|_| 2.0, 0.0, 3.0, 5) - 6.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn convergence() {
+        // Halving the step quarters the trapezium rule's error, and divides Simpson's by 16
+        let f = |x: f64| x.sin();
+        let exact = 1.0 - 1f64.cos();
+        let error = |integrator: &dyn Integrator, n| {
+            (integrator.integrate(&f, 0.0, 1.0, n) - exact).abs()
+        };
+        let ratio = error(&Trapezium, 10) / error(&Trapezium, 20);
+        assert!((ratio - 4.0).abs() < 0.01, "{}", ratio);
+        let ratio = error(&Simpson, 10) / error(&Simpson, 20);
+        assert!((ratio - 16.0).abs() < 0.1, "{}", ratio);
+    }
+
+    #[test]
+    fn names() {
+        let names: Vec<String> = INTEGRATORS.iter().map(|i| i.to_string()).collect();
+        assert_eq!(names, ["left rectangle", "right rectangle", "midpoint", "trapezium",
+                           "Simpson's"]);
+        assert_eq!(format!("[{:>10}]", Simpson), "[ Simpson's]");
+    }
+}
@@ -0,0 +1,130 @@
+//! Implements http://rosettacode.org/wiki/Polynomial_regression
+//! with the least squares fit from qr_decomposition.rs
+//!
+//! The polynomial of degree n closest to the points, in the sense of the smallest sum of squared
+//! errors, has the coefficients c solving the overdetermined system V c = y, where V's row i is
+//! 1, x_i, x_i^2, ..., x_i^n.  Solving that through V's QR decomposition, rather than the normal
+//! equations V^T V c = V^T y, avoids squaring how badly conditioned V is.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::polynomial_regression::{evaluate, fit, to_string};
+//!
+//! let xs: Vec<f64> = (0..11).map(|x| x as f64).collect();
+//! let ys = [1.0, 6.0, 17.0, 34.0, 57.0, 86.0, 121.0, 162.0, 209.0, 262.0, 321.0];
+//! let coefficients = fit(&xs, &ys, 2);
+//! assert_eq!(to_string(&coefficients, 3), "3.000x^2 + 2.000x + 1.000");
+//! assert!((evaluate(&coefficients, 11.0) - 386.0).abs() < 1e-9);
+//! ```
+
+#[allow(dead_code)]
+#[path = "qr_decomposition.rs"]
+mod qr_decomposition;
+
+// The coefficients, constant first, of the polynomial of `degree` that fits the points best.
+// Panics unless there are more distinct xs than the degree, which a unique fit needs.
+pub fn fit(xs: &[f64], ys: &[f64], degree: usize) -> Vec<f64> {
+    assert_eq!(xs.len(), ys.len(), "{} xs but {} ys", xs.len(), ys.len());
+    assert!(xs.len() > degree, "{} points can't fit a polynomial of degree {}", xs.len(), degree);
+    qr_decomposition::polyfit(xs, ys, degree).unwrap_or_else(|| {
+        panic!("too few distinct xs to fit a polynomial of degree {}", degree)
+    })
+}
+
+// The polynomial at x, by Horner's rule
+pub fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c)
+}
+
+// The polynomial the usual way round, highest power first, to `precision` places
+pub fn to_string(coefficients: &[f64], precision: usize) -> String {
+    let mut text = String::new();
+    for (power, &c) in coefficients.iter().enumerate().rev() {
+        let c = format!("{:.*}", precision, c);
+        if c.trim_start_matches('-').trim_matches(|d| d == '0' || d == '.').is_empty() {
+            continue;
+        }
+        if text.is_empty() {
+            text.push_str(&c);
+        } else if let Some(positive) = c.strip_prefix('-') {
+            text.push_str(" - ");
+            text.push_str(positive);
+        } else {
+            text.push_str(" + ");
+            text.push_str(&c);
+        }
+        match power {
+            0 => {}
+            1 => text.push('x'),
+            _ => text.push_str(&format!("x^{}", power)),
+        }
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (xs, ys) = qr_decomposition::fit_example();
+    println!("{}", to_string(&fit(&xs, &ys, 2), 3));
+}
+
+#[cfg(test)]
+mod test {
+    use super::qr_decomposition::utils::rng::Rng;
+    use super::{evaluate, fit, to_string};
+
+    fn assert_close(a: &[f64], b: &[f64], tolerance: f64) {
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < tolerance), "{:?} isn't {:?}", a, b);
+    }
+
+    #[test]
+    fn task() {
+        let xs: Vec<f64> = (0..11).map(|x| x as f64).collect();
+        let ys = [1.0, 6.0, 17.0, 34.0, 57.0, 86.0, 121.0, 162.0, 209.0, 262.0, 321.0];
+        let coefficients = fit(&xs, &ys, 2);
+        assert_close(&coefficients, &[1.0, 2.0, 3.0], 1e-10);
+        assert_eq!(to_string(&coefficients, 3), "3.000x^2 + 2.000x + 1.000");
+        for (&x, &y) in xs.iter().zip(&ys) {
+            assert!((evaluate(&coefficients, x) - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn exact_polynomials() {
+        // Points on a polynomial of the degree fitted are fitted exactly, however many there are
+        let mut rng = Rng::new(95);
+        for degree in 0..6 {
+            let polynomial: Vec<f64> =
+                (0..degree + 1).map(|_| rng.next_f64() * 4.0 - 2.0).collect();
+            let xs: Vec<f64> = (0..degree + 1 + rng.gen_range(0, 10))
+                .map(|_| rng.next_f64() * 6.0 - 3.0)
+                .collect();
+            let ys: Vec<f64> = xs.iter().map(|&x| evaluate(&polynomial, x)).collect();
+            assert_close(&fit(&xs, &ys, degree), &polynomial, 1e-8);
+        }
+    }
+
+    #[test]
+    fn least_squares() {
+        // The best constant is the mean, and the best line through symmetric points is level
+        assert_close(&fit(&[1.0, 2.0, 3.0, 4.0], &[2.0, 7.0, 1.0, 6.0], 0), &[4.0], 1e-12);
+        assert_close(&fit(&[-1.0, 0.0, 1.0], &[1.0, 0.0, 1.0], 1), &[2.0 / 3.0, 0.0], 1e-12);
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(to_string(&[-1.0, 0.0, 2.5, -1.0], 1), "-1.0x^3 + 2.5x^2 - 1.0");
+        assert_eq!(to_string(&[0.0001, 0.0], 2), "0");
+        assert_eq!(evaluate(&[], 3.0), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too few distinct xs to fit a polynomial of degree 2")]
+    fn repeated_points() {
+        fit(&[1.0, 1.0, 2.0, 2.0], &[1.0, 2.0, 3.0, 4.0], 2);
+    }
+}
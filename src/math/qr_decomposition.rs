@@ -0,0 +1,199 @@
+//! Implements http://rosettacode.org/wiki/QR_decomposition
+//!
+//! Factors an m x n matrix A, with m >= n, as A = Q R with Q orthogonal and R upper triangular,
+//! using Householder reflections.  The reflection
+//!
+//! ```text
+//!     H = I - 2 v v^T / (v^T v),    v = x - alpha e1,    alpha = -sign(x1) |x|
+//! ```
+//!
+//! takes the vector x to alpha e1, zeros below the first element.  Reflecting the rows below the
+//! diagonal of each column in turn leaves R, and Q is the product of the reflections, each of
+//! which is its own inverse.  The sign of alpha is the one that keeps x - alpha e1 from losing
+//! precision to cancellation.
+//!
+//! Since Q doesn't change lengths, the least squares solution of an overdetermined A x = b, the x
+//! making |A x - b| smallest, comes from R x = Q^T b in the top n rows, and the task uses that to
+//! fit a polynomial, as polynomial_regression.rs does too.  eigenvalues.rs repeats the
+//! factorization to find eigenvalues.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::qr_decomposition::{example, fit_example, polyfit, qr};
+//!
+//! let a = example();
+//! let (q, r) = qr(&a);
+//! let product = &q * &r;
+//! assert!((0..3).all(|i| (0..3).all(|j| (product[(i, j)] - a[(i, j)]).abs() < 1e-9)));
+//! assert!((r[(0, 0)].abs() - 14.0).abs() < 1e-9 && r[(1, 0)] == 0.0);
+//!
+//! let (x, y) = fit_example();
+//! let coefficients = polyfit(&x, &y, 2).unwrap();
+//! assert!(coefficients.iter().zip(&[1.0, 2.0, 3.0]).all(|(c, d)| (c - d).abs() < 1e-9));
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::matrix::Matrix;
+
+// (Q, R) with Q R = a
+pub fn qr(a: &Matrix<f64>) -> (Matrix<f64>, Matrix<f64>) {
+    let (m, n) = (a.rows(), a.cols());
+    assert!(m >= n, "a {}x{} matrix has more columns than rows", m, n);
+    let mut q = Matrix::identity(m);
+    let mut r = a.clone();
+    for k in 0..n.min(m.saturating_sub(1)) {
+        let x: Vec<f64> = (k..m).map(|i| r[(i, k)]).collect();
+        // Already zero below the diagonal
+        if x[1..].iter().all(|&x| x == 0.0) {
+            continue;
+        }
+        let length = x.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let alpha = if x[0] > 0.0 { -length } else { length };
+        let mut v = x;
+        v[0] -= alpha;
+        let v_squared: f64 = v.iter().map(|x| x * x).sum();
+        // R = H R, on the rows from k down
+        for j in 0..n {
+            let dot: f64 = (k..m).map(|i| v[i - k] * r[(i, j)]).sum();
+            let factor = 2.0 * dot / v_squared;
+            for i in k..m {
+                r[(i, j)] -= factor * v[i - k];
+            }
+        }
+        // Q = Q H, on the columns from k on
+        for i in 0..m {
+            let dot: f64 = (k..m).map(|j| q[(i, j)] * v[j - k]).sum();
+            let factor = 2.0 * dot / v_squared;
+            for j in k..m {
+                q[(i, j)] -= factor * v[j - k];
+            }
+        }
+        for i in k + 1..m {
+            r[(i, k)] = 0.0;
+        }
+    }
+    (q, r)
+}
+
+// The x making |a x - b| smallest, or None if a's columns aren't independent
+pub fn least_squares(a: &Matrix<f64>, b: &[f64]) -> Option<Vec<f64>> {
+    assert_eq!(a.rows(), b.len(), "{} equations but {} right hand sides", a.rows(), b.len());
+    let (q, r) = qr(a);
+    let n = a.cols();
+    let scale = (0..n).map(|i| r[(i, i)].abs()).fold(0.0, f64::max);
+    if (0..n).any(|i| r[(i, i)].abs() <= 1e-12 * scale) {
+        return None;
+    }
+    let qtb: Vec<f64> = (0..n).map(|i| (0..a.rows()).map(|j| q[(j, i)] * b[j]).sum()).collect();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let known: f64 = (i + 1..n).map(|j| r[(i, j)] * x[j]).sum();
+        x[i] = (qtb[i] - known) / r[(i, i)];
+    }
+    Some(x)
+}
+
+// The coefficients, constant first, of the polynomial of `degree` fitting the points best
+pub fn polyfit(x: &[f64], y: &[f64], degree: usize) -> Option<Vec<f64>> {
+    // The Vandermonde matrix, whose row i is 1, x_i, x_i^2, ...
+    let a = Matrix::from_fn(x.len(), degree + 1, |i, j| x[i].powi(j as i32));
+    least_squares(&a, y)
+}
+
+// The task's matrix
+pub fn example() -> Matrix<f64> {
+    Matrix::from_rows(vec![vec![12.0, -51.0, 4.0], vec![6.0, 167.0, -68.0],
+                           vec![-4.0, 24.0, -41.0]])
+}
+
+// The points of the task's polynomial fit, which lie on 3x^2 + 2x + 1
+pub fn fit_example() -> (Vec<f64>, Vec<f64>) {
+    let x = (0..11).map(|x| x as f64).collect();
+    let y = vec![1.0, 6.0, 17.0, 34.0, 57.0, 86.0, 121.0, 162.0, 209.0, 262.0, 321.0];
+    (x, y)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (q, r) = qr(&example());
+    println!("Q\n{:.3}\n\nR\n{:.3}", q, r);
+    let (x, y) = fit_example();
+    let coefficients = polyfit(&x, &y, 2).unwrap();
+    let coefficients: Vec<String> = coefficients.iter().map(|c| format!("{:.3}", c)).collect();
+    println!("\nThe polynomial fit is {}", coefficients.join(" "));
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{example, fit_example, least_squares, polyfit, qr, Matrix};
+
+    fn assert_close(a: &Matrix<f64>, b: &Matrix<f64>, tolerance: f64) {
+        let difference = (a - b).into_vec();
+        assert!(difference.iter().all(|x| x.abs() < tolerance), "\n{}\n\nisn't\n\n{}", a, b);
+    }
+
+    // Q is orthogonal, R upper triangular, and they make a
+    fn check(a: &Matrix<f64>, q: &Matrix<f64>, r: &Matrix<f64>) {
+        assert_close(&(q * r), a, 1e-10);
+        assert_close(&(&q.transpose() * q), &Matrix::identity(a.rows()), 1e-12);
+        for i in 0..r.rows() {
+            assert!(r.row(i)[..i.min(r.cols())].iter().all(|&x| x == 0.0));
+        }
+    }
+
+    #[test]
+    fn task() {
+        let (q, r) = qr(&example());
+        // The task gives Q to three places
+        let expected = Matrix::from_rows(vec![vec![-0.857, 0.394, 0.331],
+                                              vec![-0.429, -0.903, -0.034],
+                                              vec![0.286, -0.171, 0.943]]);
+        assert_close(&q, &expected, 5e-4);
+        let expected = Matrix::from_rows(vec![vec![-14.0, -21.0, 14.0], vec![0.0, -175.0, 70.0],
+                                              vec![0.0, 0.0, -35.0]]);
+        assert_close(&r, &expected, 1e-10);
+        check(&example(), &q, &r);
+    }
+
+    #[test]
+    fn fit() {
+        let (x, y) = fit_example();
+        let coefficients = polyfit(&x, &y, 2).unwrap();
+        for (c, expected) in coefficients.iter().zip(&[1.0, 2.0, 3.0]) {
+            assert!((c - expected).abs() < 1e-10, "{:?}", coefficients);
+        }
+        // A line through points that aren't on one
+        let line = polyfit(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0], 1).unwrap();
+        assert!((line[0] - 1.0 / 3.0).abs() < 1e-12 && line[1].abs() < 1e-12, "{:?}", line);
+    }
+
+    #[test]
+    fn random_matrices() {
+        let mut rng = Rng::new(93);
+        for &(m, n) in &[(1, 1), (2, 1), (4, 4), (10, 3), (25, 25), (40, 12)] {
+            let a = Matrix::from_fn(m, n, |_, _| rng.next_f64() * 2.0 - 1.0);
+            let (q, r) = qr(&a);
+            check(&a, &q, &r);
+            // At the least squares solution the residual is orthogonal to the columns
+            let b: Vec<f64> = (0..m).map(|_| rng.next_f64()).collect();
+            let x = least_squares(&a, &b).unwrap();
+            let residual = &(&a * &Matrix::new(n, 1, x)) - &Matrix::new(m, 1, b);
+            assert_close(&(&a.transpose() * &residual), &Matrix::zeros(n, 1), 1e-12);
+        }
+    }
+
+    #[test]
+    fn dependent_columns() {
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+        let (q, r) = qr(&a);
+        check(&a, &q, &r);
+        assert_eq!(least_squares(&a, &[1.0, 2.0, 3.0]), None);
+        // Columns that are already triangular are left alone
+        let (q, r) = qr(&Matrix::identity(3));
+        assert_eq!((q, r), (Matrix::identity(3), Matrix::identity(3)));
+    }
+}
@@ -0,0 +1,247 @@
+//! http://rosettacode.org/wiki/Roots_of_a_function
+//!
+//! find_roots() steps along the interval looking for where f changes sign, and hands each step it
+//! changes sign over to a RootFinder to home in on the root inside:
+//!
+//! ```text
+//!     bisection   halves the step, keeping the half f changes sign over, until it's narrow enough
+//!     secant      follows the line through the last two guesses to where it crosses zero
+//!     Newton's    follows the tangent at the last guess, which needs f's derivative
+//! ```
+//!
+//! Bisection gains a bit of the root each time and can't fail.  The other two converge far faster
+//! near a root, the secant method with the order of the golden ratio and Newton's quadratically,
+//! but can wander off, so they give up if they leave the step they started from.  A root where f
+//! only touches zero without changing sign, like x^2's, is only found if a step lands on it.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::roots_of_a_function::{find_roots, find_roots_with, Newton};
+//!
+//! let f = |x: f64| x * x * x - 3.0 * x * x + 2.0 * x;
+//! let roots = find_roots(f, -1.0, 3.0, 0.3, 1e-12);
+//! assert_eq!(roots.len(), 3);
+//! assert!(roots.iter().zip(&[0.0, 1.0, 2.0]).all(|(x, root)| (x - root).abs() < 1e-9));
+//! let newton = Newton(|x: f64| 3.0 * x * x - 6.0 * x + 2.0);
+//! assert_eq!(find_roots_with(&newton, f, -1.0, 3.0, 0.3, 1e-12).len(), 3);
+//! ```
+
+// Iterations of the secant method or Newton's to give up after
+const MAX_ITERATIONS: usize = 100;
+
+pub trait RootFinder {
+    // A root of f, to within epsilon, between a and b, where f changes sign, or None if the
+    // method doesn't find one there
+    fn refine(&self, f: &dyn Fn(f64) -> f64, a: f64, b: f64, epsilon: f64) -> Option<f64>;
+}
+
+pub struct Bisection;
+pub struct Secant;
+// Newton's method, given the derivative
+pub struct Newton<D>(pub D);
+
+impl RootFinder for Bisection {
+    fn refine(&self, f: &dyn Fn(f64) -> f64, mut a: f64, mut b: f64, epsilon: f64) -> Option<f64> {
+        let mut fa = f(a);
+        while (b - a).abs() > epsilon {
+            let middle = a + (b - a) / 2.0;
+            // Halving any further gets nowhere
+            if middle == a || middle == b {
+                break;
+            }
+            let fm = f(middle);
+            if fm == 0.0 {
+                return Some(middle);
+            }
+            if (fa < 0.0) == (fm < 0.0) {
+                a = middle;
+                fa = fm;
+            } else {
+                b = middle;
+            }
+        }
+        Some(a + (b - a) / 2.0)
+    }
+}
+
+impl RootFinder for Secant {
+    fn refine(&self, f: &dyn Fn(f64) -> f64, a: f64, b: f64, epsilon: f64) -> Option<f64> {
+        let (mut x0, mut x1) = (a, b);
+        let (mut f0, mut f1) = (f(x0), f(x1));
+        for _ in 0..MAX_ITERATIONS {
+            if f1 == f0 {
+                return None;
+            }
+            let x2 = x1 - f1 * (x1 - x0) / (f1 - f0);
+            if !within(x2, a, b) {
+                return None;
+            }
+            if (x2 - x1).abs() <= epsilon {
+                return Some(x2);
+            }
+            x0 = x1;
+            f0 = f1;
+            x1 = x2;
+            f1 = f(x2);
+        }
+        None
+    }
+}
+
+impl<D: Fn(f64) -> f64> RootFinder for Newton<D> {
+    fn refine(&self, f: &dyn Fn(f64) -> f64, a: f64, b: f64, epsilon: f64) -> Option<f64> {
+        let mut x = a + (b - a) / 2.0;
+        for _ in 0..MAX_ITERATIONS {
+            let slope = (self.0)(x);
+            if slope == 0.0 {
+                return None;
+            }
+            let next = x - f(x) / slope;
+            if !within(next, a, b) {
+                return None;
+            }
+            if (next - x).abs() <= epsilon {
+                return Some(next);
+            }
+            x = next;
+        }
+        None
+    }
+}
+
+fn within(x: f64, a: f64, b: f64) -> bool {
+    a.min(b) <= x && x <= a.max(b)
+}
+
+// The roots of f from start to stop, found a step at a time and refined by `finder`
+pub fn find_roots_with<F>(finder: &dyn RootFinder, f: F, start: f64, stop: f64, step: f64,
+                          epsilon: f64)
+                          -> Vec<f64>
+    where F: Fn(f64) -> f64
+{
+    assert!(step > 0.0, "the step has to be positive, not {}", step);
+    let mut roots = vec![];
+    let steps = ((stop - start) / step).ceil().max(0.0) as usize;
+    // Each x from start and i, so that adding up the step doesn't add up its rounding
+    let x = |i: usize| (start + i as f64 * step).min(stop);
+    let mut previous = f(start);
+    for i in 0..steps {
+        let (a, b) = (x(i), x(i + 1));
+        let next = f(b);
+        if previous == 0.0 {
+            roots.push(a);
+        } else if next != 0.0 && (previous < 0.0) != (next < 0.0) {
+            roots.extend(finder.refine(&f, a, b, epsilon));
+        }
+        previous = next;
+    }
+    if previous == 0.0 {
+        roots.push(x(steps));
+    }
+    roots
+}
+
+// The roots of f from start to stop, refined by bisection
+pub fn find_roots<F>(f: F, start: f64, stop: f64, step: f64, epsilon: f64) -> Vec<f64>
+    where F: Fn(f64) -> f64
+{
+    find_roots_with(&Bisection, f, start, stop, step, epsilon)
+}
+
+#[test]
+fn test_find_roots() {
+    let roots = find_roots(|x: f64| x*x*x - 3.0*x*x + 2.0*x,
+                           -1.0, 3.0, 0.0001, 0.00000001);
+    let expected = [0.0f64, 1.0, 2.0];
+
+    for (&a, &b) in roots.iter().zip(expected.iter()) {
+        assert!((a - b).abs() < 0.0001);
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let f = |x: f64| x * x * x - 3.0 * x * x + 2.0 * x;
+    let derivative = |x: f64| 3.0 * x * x - 6.0 * x + 2.0;
+    let finders: [(&str, &dyn RootFinder); 3] =
+        [("bisection", &Bisection), ("secant", &Secant), ("Newton's", &Newton(derivative))];
+
+    println!("roots of f(x) = x^3 - 3x^2 + 2x from -1 to 3:");
+    for &(name, finder) in &finders {
+        // A step that doesn't land on the roots, so that they need finding
+        let roots: Vec<String> = find_roots_with(finder, f, -1.0, 3.0, 0.3, 1e-12)
+            .iter()
+            .map(|root| format!("{:.12}", root))
+            .collect();
+        println!("    {:9} {}", name, roots.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_roots_with, Bisection, Newton, RootFinder, Secant};
+
+    fn f(x: f64) -> f64 {
+        x * x * x - 3.0 * x * x + 2.0 * x
+    }
+
+    fn derivative(x: f64) -> f64 {
+        3.0 * x * x - 6.0 * x + 2.0
+    }
+
+    #[test]
+    fn every_method() {
+        let finders: [&dyn RootFinder; 3] = [&Bisection, &Secant, &Newton(derivative)];
+        for finder in finders.iter() {
+            for &step in &[0.3, 0.07, 0.45] {
+                let roots = find_roots_with(*finder, f, -1.0, 3.0, step, 1e-12);
+                assert_eq!(roots.len(), 3, "{:?} with a step of {}", roots, step);
+                for (root, expected) in roots.iter().zip(&[0.0, 1.0, 2.0]) {
+                    assert!((root - expected).abs() < 1e-11, "{:?}", roots);
+                }
+            }
+        }
+        // Over a step this long, the secant method heads off away from the root at 2
+        let roots = find_roots_with(&Secant, f, -1.0, 3.0, 1.1, 1e-12);
+        assert_eq!(roots.len(), 2, "{:?}", roots);
+        assert_eq!(find_roots_with(&Bisection, f, -1.0, 3.0, 1.1, 1e-12).len(), 3);
+    }
+
+    #[test]
+    fn tolerances() {
+        // Each method gets the root to within the tolerance asked for
+        let sqrt2 = 2f64.sqrt();
+        let g = |x: f64| x * x - 2.0;
+        let finders: [&dyn RootFinder; 3] = [&Bisection, &Secant, &Newton(|x: f64| 2.0 * x)];
+        for finder in finders.iter() {
+            for &epsilon in &[1e-2, 1e-5, 1e-9, 1e-14] {
+                let root = finder.refine(&g, 1.0, 2.0, epsilon).unwrap();
+                assert!((root - sqrt2).abs() <= epsilon, "{} to within {}", root, epsilon);
+            }
+        }
+        // Bisection narrows the step to the tolerance and takes the middle
+        let root = Bisection.refine(&g, 1.0, 2.0, 0.25).unwrap();
+        assert_eq!(root, 1.375);
+    }
+
+    #[test]
+    fn roots_on_the_steps() {
+        // Steps landing exactly on a root find it without refining, including at either end
+        let roots = find_roots_with(&Bisection, |x: f64| x * (x - 1.0) * (x - 2.0), 0.0, 2.0, 0.5,
+                                    1e-12);
+        assert_eq!(roots, [0.0, 1.0, 2.0]);
+        // Only touching zero between steps, x^2 has no sign change to find
+        assert!(find_roots_with(&Bisection, |x: f64| x * x, -1.0, 1.0, 0.3, 1e-12).is_empty());
+    }
+
+    #[test]
+    fn divergence() {
+        // Newton's method on the cube root overshoots further each time
+        let cbrt = |x: f64| x.cbrt();
+        let newton = Newton(|x: f64| x.abs().powf(-2.0 / 3.0) / 3.0);
+        assert_eq!(newton.refine(&cbrt, -1.0, 2.0, 1e-12), None);
+        assert!(Bisection.refine(&cbrt, -1.0, 2.0, 1e-12).unwrap().abs() < 1e-12);
+        // A flat guess has no tangent to follow
+        assert_eq!(Newton(|_| 0.0).refine(&|x: f64| x - 0.5, 0.0, 2.0, 1e-12), None);
+    }
+}
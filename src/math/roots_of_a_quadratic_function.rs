@@ -0,0 +1,239 @@
+//! Implements http://rosettacode.org/wiki/Roots_of_a_quadratic_function
+//!
+//! The schoolbook formula, x = (-b +- sqrt(b^2 - 4ac)) / 2a, loses the smaller root when b^2 is
+//! much bigger than 4ac: sqrt(b^2 - 4ac) is then nearly |b|, and subtracting the two cancels all
+//! the digits they share.  The task's x^2 - 10^9 x + 1 does it in doubles, giving 0 for the root
+//! near 10^-9.  Adding them instead, with b's sign, cancels nothing:
+//!
+//! ```text
+//!     q = -(b + sign(b) sqrt(b^2 - 4ac)) / 2,    x1 = q / a,    x2 = c / q
+//! ```
+//!
+//! since the roots multiply to c / a.  When b^2 < 4ac the roots are complex, -b / 2a plus or minus
+//! i sqrt(4ac - b^2) / 2a, and nothing cancels.
+//!
+//! The cubic follows Cardano: x = t - b / 3a leaves t^3 + p t + q = 0, whose roots are u + v, and
+//! the two others rotated by the cube roots of unity, where u^3 and v^3 are -q/2 +- sqrt(D) and
+//! D = (q/2)^2 + (p/3)^3.  Again the cube root is taken of the sum that doesn't cancel, and v from
+//! u v = -p/3.  When D < 0 all three roots are real but Cardano's formula reaches them through
+//! complex numbers, so they come from Viete's trigonometric form instead.  Shifting back by b / 3a
+//! cancels digits too when the roots are of very different sizes, so each root is finished off with
+//! Newton's method on the cubic itself.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::roots_of_a_quadratic_function::{naive_quadratic, quadratic};
+//!
+//! let roots = quadratic(1.0, 2.0, 5.0);
+//! assert_eq!((roots[0].to_string(), roots[1].to_string()), ("-1 + 2i".into(), "-1 - 2i".into()));
+//! // The schoolbook formula loses the small root of x^2 - 10^9 x + 1 to cancellation
+//! assert_eq!(naive_quadratic(1.0, -1e9, 1.0), Some((1e9, 0.0)));
+//! assert_eq!(quadratic(1.0, -1e9, 1.0)[1].re, 1e-9);
+//! ```
+
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::f64::consts::PI;
+use self::utils::complex::Complex;
+
+// 1 or -1, with 0 counting as positive so that q is never needlessly 0
+fn sign(x: f64) -> f64 {
+    if x < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+// The roots of a x^2 + b x + c, the one of biggest absolute value first
+pub fn quadratic(a: f64, b: f64, c: f64) -> [Complex; 2] {
+    assert!(a != 0.0, "with a = 0 it isn't a quadratic");
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        let re = -b / (2.0 * a);
+        let im = (-discriminant).sqrt() / (2.0 * a).abs();
+        return [Complex::new(re, im), Complex::new(re, -im)];
+    }
+    let q = -(b + sign(b) * discriminant.sqrt()) / 2.0;
+    if q == 0.0 {
+        // Only when b and c are both 0
+        return [Complex::from(0.0), Complex::from(0.0)];
+    }
+    [Complex::from(q / a), Complex::from(c / q)]
+}
+
+// The real roots of a x^2 + b x + c by the schoolbook formula, for comparison
+pub fn naive_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    Some(((-b + root) / (2.0 * a), (-b - root) / (2.0 * a)))
+}
+
+// The roots of a x^3 + b x^2 + c x + d, the real one first when the other two are complex
+pub fn cubic(a: f64, b: f64, c: f64, d: f64) -> [Complex; 3] {
+    assert!(a != 0.0, "with a = 0 it isn't a cubic");
+    let (b, c, d) = (b / a, c / a, d / a);
+    let shift = b / 3.0;
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let discriminant = q * q / 4.0 + p * p * p / 27.0;
+    if discriminant < 0.0 {
+        // Three real roots, which also means p < 0
+        let m = 2.0 * (-p / 3.0).sqrt();
+        let cosine = (3.0 * q / (p * m)).clamp(-1.0, 1.0);
+        let theta = cosine.acos() / 3.0;
+        let root = |k: f64| Complex::from(m * (theta - 2.0 * PI * k / 3.0).cos() - shift);
+        return [polish(b, c, d, root(0.0)), polish(b, c, d, root(1.0)),
+                polish(b, c, d, root(2.0))];
+    }
+    let u = (-q / 2.0 - sign(q) * discriminant.sqrt()).cbrt();
+    // u is 0 only when p and q are, and then so are all three roots of t^3
+    let v = if u == 0.0 { 0.0 } else { -p / (3.0 * u) };
+    let re = -(u + v) / 2.0 - shift;
+    let im = 3f64.sqrt() / 2.0 * (u - v);
+    [polish(b, c, d, Complex::from(u + v - shift)), polish(b, c, d, Complex::new(re, im)),
+     polish(b, c, d, Complex::new(re, -im))]
+}
+
+// The root of x^3 + b x^2 + c x + d near x, after as many of a few steps of Newton's method as
+// bring the cubic nearer 0
+fn polish(b: f64, c: f64, d: f64, mut x: Complex) -> Complex {
+    let (b, c, d) = (Complex::from(b), Complex::from(c), Complex::from(d));
+    let value = |x: Complex| ((x + b) * x + c) * x + d;
+    let slope = |x: Complex| (x.scale(3.0) + b.scale(2.0)) * x + c;
+    let mut residual = value(x).norm_sqr();
+    for _ in 0..3 {
+        let s = slope(x);
+        if residual == 0.0 || s.norm_sqr() == 0.0 {
+            break;
+        }
+        let next = x - value(x) / s;
+        let next_residual = value(next).norm_sqr();
+        if next_residual >= residual {
+            break;
+        }
+        x = next;
+        residual = next_residual;
+    }
+    x
+}
+
+#[cfg(not(test))]
+fn main() {
+    // The task's example of cancellation, and the same for doubles
+    for &b in &[-1e5, -1e9] {
+        println!("x^2 {:+e}x + 1", b);
+        let (large, small) = naive_quadratic(1.0, b, 1.0).unwrap();
+        println!("    naive  {:e}, {:e}", large, small);
+        let [large, small] = quadratic(1.0, b, 1.0);
+        println!("    stable {:e}, {:e}", large.re, small.re);
+    }
+    let roots = quadratic(1.0, 2.0, 5.0);
+    println!("x^2 + 2x + 5\n    {}, {}", roots[0], roots[1]);
+    let roots = cubic(1.0, -6.0, 11.0, -6.0);
+    println!("x^3 - 6x^2 + 11x - 6\n    {:.6}, {:.6}, {:.6}", roots[0].re, roots[1].re,
+             roots[2].re);
+    let roots = cubic(1.0, 0.0, 0.0, -1.0);
+    println!("x^3 - 1\n    {:.6}, {:.6}, {:.6}", roots[0], roots[1], roots[2]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::complex::Complex;
+    use super::utils::rng::Rng;
+    use super::{cubic, naive_quadratic, quadratic};
+
+    // The polynomial with these coefficients, highest power first, at z
+    fn evaluate(coefficients: &[f64], z: Complex) -> Complex {
+        coefficients.iter().fold(Complex::from(0.0), |sum, &c| sum * z + Complex::from(c))
+    }
+
+    fn assert_roots(roots: &[Complex], expected: &[Complex], tolerance: f64) {
+        let mut roots = roots.to_vec();
+        let mut expected = expected.to_vec();
+        let by_parts = |x: &Complex, y: &Complex| {
+            (x.re, x.im).partial_cmp(&(y.re, y.im)).unwrap()
+        };
+        roots.sort_by(by_parts);
+        expected.sort_by(by_parts);
+        assert!(roots.iter().zip(&expected).all(|(&x, &y)| (x - y).abs() <= tolerance),
+                "{:?} isn't {:?}", roots, expected);
+    }
+
+    #[test]
+    fn cancellation() {
+        // The task's coefficients, where the naive small root loses most or all of its digits
+        for &b in &[-1e5, -1e9, -1e12] {
+            let [large, small] = quadratic(1.0, b, 1.0);
+            // 1 / |b| is the small root's leading term, and the next is 1 / |b|^3
+            let exact = 1.0 / -b + 1.0 / (-b * b * b);
+            assert!((small.re - exact).abs() <= 1e-15 * exact, "{} isn't {}", small.re, exact);
+            assert!((large.re - (-b - 1.0 / -b)).abs() <= 1e-15 * -b);
+            assert!(large.im == 0.0 && small.im == 0.0);
+            let (_, naive) = naive_quadratic(1.0, b, 1.0).unwrap();
+            assert!((naive - exact).abs() > 1e-12 * exact, "{} with b = {}", naive, b);
+        }
+        assert_eq!(naive_quadratic(1.0, -1e9, 1.0), Some((1e9, 0.0)));
+        // And with b positive, where the other root cancels
+        let [large, small] = quadratic(1.0, 1e9, 1.0);
+        assert_eq!((large.re, small.re), (-1e9, -1e-9));
+    }
+
+    #[test]
+    fn quadratics() {
+        assert_eq!(quadratic(1.0, 2.0, 5.0), [Complex::new(-1.0, 2.0), Complex::new(-1.0, -2.0)]);
+        assert_eq!(quadratic(-1.0, 0.0, -1.0), [Complex::new(0.0, 1.0), Complex::new(0.0, -1.0)]);
+        assert_eq!(quadratic(1.0, -2.0, 1.0), [Complex::from(1.0); 2]);
+        assert_eq!(quadratic(1.0, -3.0, 0.0), [Complex::from(3.0), Complex::from(0.0)]);
+        assert_eq!(quadratic(2.0, 0.0, -8.0), [Complex::from(-2.0), Complex::from(2.0)]);
+        assert_eq!(quadratic(5.0, 0.0, 0.0), [Complex::from(0.0); 2]);
+        assert_eq!(naive_quadratic(1.0, 2.0, 5.0), None);
+    }
+
+    #[test]
+    fn cubics() {
+        let real = |x| Complex::from(x);
+        assert_roots(&cubic(1.0, -6.0, 11.0, -6.0), &[real(1.0), real(2.0), real(3.0)], 1e-12);
+        let im = 3f64.sqrt() / 2.0;
+        let roots = cubic(1.0, 0.0, 0.0, -1.0);
+        assert_eq!(roots[0], real(1.0));
+        assert_roots(&roots, &[real(1.0), Complex::new(-0.5, im), Complex::new(-0.5, -im)],
+                     1e-15);
+        assert_eq!(cubic(3.0, 0.0, 0.0, 0.0), [real(0.0); 3]);
+        // 2 (x + 1)^2 (x - 3), with a double root
+        assert_roots(&cubic(2.0, -2.0, -10.0, -6.0), &[real(-1.0), real(-1.0), real(3.0)], 1e-7);
+        // (x - 1)^3, shifted to t^3
+        assert_roots(&cubic(1.0, -3.0, 3.0, -1.0), &[real(1.0); 3], 1e-15);
+    }
+
+    #[test]
+    fn random_polynomials() {
+        // Each root makes the polynomial 0, up to rounding the size of its terms
+        let mut rng = Rng::new(99);
+        for _ in 0..1000 {
+            let coefficients: Vec<f64> = (0..4).map(|_| rng.next_f64() * 20.0 - 10.0).collect();
+            let (a, b, c, d) = (coefficients[0], coefficients[1], coefficients[2], coefficients[3]);
+            for &root in &cubic(a, b, c, d) {
+                let size: f64 =
+                    (0..4).map(|i| coefficients[i].abs() * root.abs().powi(3 - i as i32)).sum();
+                assert!(evaluate(&coefficients, root).abs() <= 1e-12 * size,
+                        "{} for {:?}", root, coefficients);
+            }
+            for &root in &quadratic(a, b, c) {
+                let size = a.abs() * root.norm_sqr() + b.abs() * root.abs() + c.abs();
+                assert!(evaluate(&coefficients[..3], root).abs() <= 1e-14 * size);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "with a = 0 it isn't a quadratic")]
+    fn not_quadratic() {
+        quadratic(0.0, 1.0, 1.0);
+    }
+}
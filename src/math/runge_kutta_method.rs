@@ -0,0 +1,130 @@
+//! Implements http://rosettacode.org/wiki/Runge-Kutta_method
+//!
+//! The classical fourth order Runge-Kutta method steps the solution of dy/dt = f(t, y) from t to
+//! t + h with a weighted average of four slopes:
+//!
+//! ```text
+//!     k1 = f(t, y)
+//!     k2 = f(t + h/2, y + h k1 / 2)
+//!     k3 = f(t + h/2, y + h k2 / 2)
+//!     k4 = f(t + h, y + h k3)
+//!     y(t + h) = y + h (k1 + 2 k2 + 2 k3 + k4) / 6
+//! ```
+//!
+//! Each step's error goes as h^5, so the total over a fixed interval goes as h^4.  y is a slice so
+//! that the same steps solve systems of equations, and higher order equations written as systems.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::runge_kutta_method::{solve, task_equation, task_solution};
+//!
+//! let points = solve(task_equation, 0.0, &[1.0], 0.1, 100);
+//! let (t, ref y) = points[100];
+//! assert!((t - 10.0).abs() < 1e-9);
+//! assert_eq!(task_solution(10.0), 676.0);
+//! assert!((y[0] - 676.0).abs() < 1e-3);
+//! ```
+
+// y + h k, element by element
+fn offset(y: &[f64], h: f64, k: &[f64]) -> Vec<f64> {
+    y.iter().zip(k).map(|(y, k)| y + h * k).collect()
+}
+
+// The solution of y' = f(t, y) at t + h, from y at t
+pub fn rk4_step<F>(f: &F, t: f64, y: &[f64], h: f64) -> Vec<f64>
+    where F: Fn(f64, &[f64]) -> Vec<f64>
+{
+    let k1 = f(t, y);
+    let k2 = f(t + h / 2.0, &offset(y, h / 2.0, &k1));
+    let k3 = f(t + h / 2.0, &offset(y, h / 2.0, &k2));
+    let k4 = f(t + h, &offset(y, h, &k3));
+    (0..y.len())
+        .map(|i| y[i] + h * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]) / 6.0)
+        .collect()
+}
+
+// (t, y) from y0 at t0 and after each of `steps` steps of h
+pub fn solve<F>(f: F, t0: f64, y0: &[f64], h: f64, steps: usize) -> Vec<(f64, Vec<f64>)>
+    where F: Fn(f64, &[f64]) -> Vec<f64>
+{
+    let mut solution = Vec::with_capacity(steps + 1);
+    solution.push((t0, y0.to_vec()));
+    for i in 0..steps {
+        // Each t from t0 and i, so that adding up h doesn't add up its rounding
+        let y = rk4_step(&f, t0 + i as f64 * h, &solution[i].1, h);
+        solution.push((t0 + (i + 1) as f64 * h, y));
+    }
+    solution
+}
+
+// The task's equation, y' = t sqrt(y) with y(0) = 1, for one variable
+pub fn task_equation(t: f64, y: &[f64]) -> Vec<f64> {
+    vec![t * y[0].sqrt()]
+}
+
+// Its exact solution
+pub fn task_solution(t: f64) -> f64 {
+    (t * t + 4.0).powi(2) / 16.0
+}
+
+#[cfg(not(test))]
+fn main() {
+    // Steps of 0.1 from 0 to 10, printing every whole t
+    for (i, &(t, ref y)) in solve(task_equation, 0.0, &[1.0], 0.1, 100).iter().enumerate() {
+        if i % 10 == 0 {
+            println!("y({:2}) = {:12.6}    error {:.3e}", t, y[0], (y[0] - task_solution(t)).abs());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rk4_step, solve, task_equation, task_solution};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn task() {
+        let solution = solve(task_equation, 0.0, &[1.0], 0.1, 100);
+        assert_eq!(solution.len(), 101);
+        for &(t, ref y) in &solution {
+            let exact = task_solution(t);
+            assert!((y[0] - exact).abs() < 1e-6 * exact, "y({}) = {}, not {}", t, y[0], exact);
+        }
+        let (t, ref y) = solution[100];
+        assert!((t - 10.0).abs() < 1e-12);
+        // The task's output, 675.999949 with an error of 5.1e-5
+        assert!((y[0] - 675.999949).abs() < 1e-6, "{}", y[0]);
+    }
+
+    #[test]
+    fn exact_for_cubics() {
+        // The slope only depends on t, so the step is Simpson's rule, which is exact for cubics
+        let f = |t: f64, _: &[f64]| vec![3.0 * t * t - 2.0 * t + 1.0];
+        let y = rk4_step(&f, 1.0, &[2.0], 0.5);
+        let exact = |t: f64| t * t * t - t * t + t;
+        assert!((y[0] - (2.0 + exact(1.5) - exact(1.0))).abs() < 1e-14);
+    }
+
+    #[test]
+    fn harmonic_oscillator() {
+        // y'' = -y as the system (y, y')' = (y', -y), which goes round once every 2 pi
+        let f = |_: f64, y: &[f64]| vec![y[1], -y[0]];
+        let solution = solve(f, 0.0, &[1.0, 0.0], 2.0 * PI / 1000.0, 1000);
+        for &(t, ref y) in &solution {
+            assert!((y[0] - t.cos()).abs() < 1e-10 && (y[1] + t.sin()).abs() < 1e-10,
+                    "{:?} at {}", y, t);
+        }
+    }
+
+    #[test]
+    fn fourth_order() {
+        // Halving the step divides the error at the end by 16
+        let error = |steps| {
+            let h = 10.0 / steps as f64;
+            let y = &solve(task_equation, 0.0, &[1.0], h, steps)[steps].1;
+            (y[0] - task_solution(10.0)).abs()
+        };
+        let ratio = error(50) / error(100);
+        assert!((ratio - 16.0).abs() < 1.0, "{}", ratio);
+    }
+}
@@ -0,0 +1,61 @@
+//! Implements http://rosettacode.org/wiki/Sieve_of_Eratosthenes
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::sieve_eratosthenes::simple_sieve;
+//!
+//! assert_eq!(simple_sieve(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+//! assert_eq!(simple_sieve(1), []);
+//! ```
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+#[cfg(all(test, feature = "mem-profile"))]
+#[global_allocator]
+static METER: utils::memory::Meter = utils::memory::Meter;
+
+fn int_sqrt(n: usize) -> usize {
+    (n as f64).sqrt() as usize
+}
+
+// Return the prime numbers up to limit
+pub fn simple_sieve(limit: usize) -> Vec<usize> {
+    if limit < 2 {
+        return vec![];
+    }
+
+    let mut primes = vec![true; limit + 1];
+
+    for prime in 2..int_sqrt(limit) + 1 + 1 {
+        if primes[prime] {
+            for multiple in (prime * prime..limit + 1).step_by(prime) {
+                primes[multiple] = false
+            }
+        }
+    }
+
+    (2..limit + 1).filter(|&n| primes[n]).collect()
+}
+
+#[cfg(not(test))]
+fn main() {
+    println!("{:?}", simple_sieve(100))
+}
+
+#[test]
+fn test_basic() {
+    let primes = simple_sieve(30);
+    assert!(primes == [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+}
+
+// A byte for each number up to the limit, and then the primes
+#[test]
+#[cfg_attr(not(feature = "mem-profile"), ignore)]
+fn test_memory() {
+    let limit = 1_000_000;
+    let primes = utils::memory::assert_peak_at_most(3 * limit, || simple_sieve(limit));
+    assert_eq!(primes.len(), 78498);
+}
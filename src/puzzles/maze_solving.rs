@@ -0,0 +1,224 @@
+//! Implements http://rosettacode.org/wiki/Maze_solving
+//! on a maze made as in http://rosettacode.org/wiki/Maze_generation
+//!
+//! The maze is carved by a depth-first walk from the top left cell that knocks down the wall into
+//! a random unvisited neighbour, and backs up when there isn't one.  That visits every cell and
+//! never makes a loop, so there's exactly one way between any two cells.  The solver finds it the
+//! same way, by walking depth first and backing out of dead ends.  With the trace feature,
+//! ROSETTA_TRACE=1 shows each step it takes and each dead end it backs out of.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::maze_solving::utils::rng::Rng;
+//! use tasks::maze_solving::Maze;
+//!
+//! let maze = Maze::generate(3, 1, &mut Rng::new(42));
+//! let path = maze.solve((0, 0), (2, 0));
+//! assert_eq!(path, [(0, 0), (1, 0), (2, 0)]);
+//! assert_eq!(maze.render(&path), "+---+---+---+\n| *   *   * |\n+---+---+---+\n");
+//! ```
+// pub, for the Rng of the example above
+#[macro_use]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use self::utils::rng::Rng;
+
+type Cell = (usize, usize); // (x, y)
+
+pub struct Maze {
+    width: usize,
+    height: usize,
+    // Whether the wall to the right of and below each cell is knocked down, by index
+    east: Vec<bool>,
+    south: Vec<bool>,
+}
+
+impl Maze {
+    pub fn generate(width: usize, height: usize, rng: &mut Rng) -> Maze {
+        assert!(width > 0 && height > 0, "a maze needs cells, not {}x{}", width, height);
+        let mut maze = Maze {
+            width,
+            height,
+            east: vec![false; width * height],
+            south: vec![false; width * height],
+        };
+        let mut visited = vec![false; width * height];
+        visited[0] = true;
+        let mut stack = vec![(0, 0)];
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<Cell> =
+                maze.neighbors(cell).into_iter().filter(|&n| !visited[maze.index(n)]).collect();
+            match rng.choose(&unvisited) {
+                Some(&next) => {
+                    maze.open(cell, next);
+                    visited[maze.index(next)] = true;
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        maze
+    }
+
+    fn index(&self, (x, y): Cell) -> usize {
+        y * self.width + x
+    }
+
+    // The cells next to `cell`, walls or not
+    fn neighbors(&self, (x, y): Cell) -> Vec<Cell> {
+        let mut cells = Vec::new();
+        if x > 0 {
+            cells.push((x - 1, y));
+        }
+        if y > 0 {
+            cells.push((x, y - 1));
+        }
+        if x + 1 < self.width {
+            cells.push((x + 1, y));
+        }
+        if y + 1 < self.height {
+            cells.push((x, y + 1));
+        }
+        cells
+    }
+
+    fn open(&mut self, a: Cell, b: Cell) {
+        let (first, second) = if a < b { (a, b) } else { (b, a) };
+        let i = self.index(first);
+        if first.1 == second.1 {
+            self.east[i] = true;
+        } else {
+            self.south[i] = true;
+        }
+    }
+
+    // Whether there's no wall between the neighbours a and b
+    pub fn is_open(&self, a: Cell, b: Cell) -> bool {
+        let (first, second) = if a < b { (a, b) } else { (b, a) };
+        let i = self.index(first);
+        if first.1 == second.1 { self.east[i] } else { self.south[i] }
+    }
+
+    // The way from `from` to `to`, including both
+    pub fn solve(&self, from: Cell, to: Cell) -> Vec<Cell> {
+        let mut visited = vec![false; self.width * self.height];
+        visited[self.index(from)] = true;
+        let mut path = vec![from];
+        while let Some(&cell) = path.last() {
+            if cell == to {
+                return path;
+            }
+            let next = self.neighbors(cell)
+                .into_iter()
+                .find(|&n| !visited[self.index(n)] && self.is_open(cell, n));
+            match next {
+                Some(next) => {
+                    trace!("{:?} to {:?}", cell, next);
+                    visited[self.index(next)] = true;
+                    path.push(next);
+                }
+                None => {
+                    path.pop();
+                    trace!("dead end at {:?}, back to {:?}", cell, path.last());
+                }
+            }
+        }
+        unreachable!("every cell of the maze is reachable")
+    }
+
+    // The maze as text, with the cells on `path` marked
+    pub fn render(&self, path: &[Cell]) -> String {
+        let mut text = String::new();
+        text.push_str(&"+---".repeat(self.width));
+        text.push_str("+\n");
+        for y in 0..self.height {
+            text.push('|');
+            for x in 0..self.width {
+                text.push_str(if path.contains(&(x, y)) { " * " } else { "   " });
+                text.push(if self.east[self.index((x, y))] { ' ' } else { '|' });
+            }
+            text.push_str("\n+");
+            for x in 0..self.width {
+                text.push_str(if self.south[self.index((x, y))] { "   +" } else { "---+" });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let (width, height) = (16, 8);
+    let maze = Maze::generate(width, height, &mut Rng::from_time());
+    let path = maze.solve((0, 0), (width - 1, height - 1));
+    print!("{}", maze.render(&path));
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::Maze;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn perfect() {
+        // Every cell reachable, through one fewer passages than cells, which leaves no loops
+        let mut rng = Rng::new(50);
+        for &(width, height) in &[(1, 1), (1, 7), (5, 1), (10, 10), (30, 12)] {
+            let maze = Maze::generate(width, height, &mut rng);
+            let passages = maze.east.iter().chain(&maze.south).filter(|&&open| open).count();
+            assert_eq!(passages, width * height - 1);
+            let mut seen = vec![false; width * height];
+            seen[0] = true;
+            let mut queue: VecDeque<_> = vec![(0, 0)].into();
+            while let Some(cell) = queue.pop_front() {
+                for next in maze.neighbors(cell) {
+                    if maze.is_open(cell, next) && !seen[maze.index(next)] {
+                        seen[maze.index(next)] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            assert!(seen.iter().all(|&s| s));
+        }
+    }
+
+    #[test]
+    fn solved() {
+        let mut rng = Rng::new(50);
+        let maze = Maze::generate(20, 15, &mut rng);
+        for _ in 0..20 {
+            let from = (rng.gen_range(0, 20), rng.gen_range(0, 15));
+            let to = (rng.gen_range(0, 20), rng.gen_range(0, 15));
+            let path = maze.solve(from, to);
+            assert_eq!((path[0], *path.last().unwrap()), (from, to));
+            for step in path.windows(2) {
+                assert!(maze.neighbors(step[0]).contains(&step[1]));
+                assert!(maze.is_open(step[0], step[1]), "through a wall at {:?}", step);
+            }
+            let mut cells = path.clone();
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), path.len(), "{:?} goes round", path);
+        }
+        assert_eq!(maze.solve((3, 4), (3, 4)), [(3, 4)]);
+    }
+
+    #[test]
+    fn rendered() {
+        let maze = Maze { width: 2, height: 2, east: vec![true, false, false, false],
+                          south: vec![false, true, false, false] };
+        let path = maze.solve((0, 0), (1, 1));
+        assert_eq!(path, [(0, 0), (1, 0), (1, 1)]);
+        assert_eq!(maze.render(&path), "+---+---+\n\
+                                        | *   * |\n\
+                                        +---+   +\n\
+                                        |   | * |\n\
+                                        +---+---+\n");
+    }
+}
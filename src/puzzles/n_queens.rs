@@ -1,17 +1,25 @@
-// Implements http://rosettacode.org/wiki/N-queens_problem
-#![feature(test)]
-#![feature(core)]
-
-extern crate test;
+//! Implements http://rosettacode.org/wiki/N-queens_problem
+//!
+//! With the trace feature, ROSETTA_TRACE=1 shows each queen as it's placed and each solution as
+//! it's found.  The benchmarks are in benches/puzzles.rs.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::n_queens::{n_queens, semi_parallel_n_queens};
+//!
+//! assert_eq!(n_queens(8), 92);
+//! assert_eq!(semi_parallel_n_queens(8), 92);
+//! ```
+
+#[macro_use]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 use std::vec::Vec;
-use std::iter::AdditiveIterator;
 use std::thread::spawn;
 use std::sync::mpsc::channel;
 
-#[cfg(test)]
-use test::Bencher;
-
 #[cfg(not(test))]
 fn main() {
     for num in 0i32..16 {
@@ -32,9 +40,9 @@ fn main() {
 
 // Solves n-queens using a depth-first, backtracking solution.
 // Returns the number of solutions for a given n.
-fn n_queens(n: i32) -> usize {
+pub fn n_queens(n: i32) -> usize {
     // Pass off to our helper function.
-    return n_queens_helper((1 << n as usize) -1, 0, 0, 0);
+    n_queens_helper((1 << n as usize) -1, 0, 0, 0)
 }
 
 // The meat of the algorithm is in here, a recursive helper function
@@ -82,7 +90,8 @@ fn n_queens_helper(all_ones: i32, left_diags: i32, columns: i32, right_diags: i3
 
         // We then XOR that integer with the validSpots to flip it to 0
         // in valid_spots.
-        valid_spots = valid_spots ^ spot;
+        valid_spots ^= spot;
+        trace!("row {}: queen in column {}", columns.count_ones(), spot.trailing_zeros());
 
         // Make a recursive call. This is where we infer the conflicts
         // for the next row.
@@ -95,7 +104,7 @@ fn n_queens_helper(all_ones: i32, left_diags: i32, columns: i32, right_diags: i3
 
             // For columns we simply mark this column as filled by ORing
             // in the currentSpot.
-            (columns | spot),
+            columns | spot,
 
             // This is the same as the left_diag shift, except we shift
             // right because these conflicts are caused by right diagonals.
@@ -104,6 +113,9 @@ fn n_queens_helper(all_ones: i32, left_diags: i32, columns: i32, right_diags: i3
 
     // If columns is all blocked (i.e. if it is all ones) then we
     // have arrived at a solution because we have placed n queens.
+    if columns == all_ones {
+        trace!("solution");
+    }
     solutions + ((columns == all_ones) as usize)
 }
 
@@ -112,7 +124,7 @@ fn n_queens_helper(all_ones: i32, left_diags: i32, columns: i32, right_diags: i3
 //
 // This is much slower for smaller numbers (under 16~17) but outperforms
 // the sequential algorithm after that.
-fn semi_parallel_n_queens(n: i32) -> usize {
+pub fn semi_parallel_n_queens(n: i32) -> usize {
     let all_ones = (1 << n as usize) - 1;
     let (columns, left_diags, right_diags) = (0, 0, 0);
 
@@ -121,18 +133,18 @@ fn semi_parallel_n_queens(n: i32) -> usize {
     while valid_spots != 0 {
         let (tx, rx) = channel();
         let spot = -valid_spots & valid_spots;
-        valid_spots = valid_spots ^ spot;
+        valid_spots ^= spot;
         receivers.push(rx);
 
         spawn( move || -> () {
             tx.send(n_queens_helper(all_ones,
                                     (left_diags | spot) << 1,
-                                    (columns | spot),
+                                    columns | spot,
                                     (right_diags | spot) >> 1)).unwrap();
         });
     }
 
-    receivers.iter().map(|r| r.recv().unwrap()).sum() + ((columns == all_ones) as usize)
+    receivers.iter().map(|r| r.recv().unwrap()).sum::<usize>() + ((columns == all_ones) as usize)
 }
 
 // Tests
@@ -140,7 +152,7 @@ fn semi_parallel_n_queens(n: i32) -> usize {
 #[test]
 fn test_n_queens() {
     let real = vec!(1, 1, 0, 0, 2, 10, 4, 40, 92);
-    for num in (0..9i32) {
+    for num in 0..9i32 {
         assert_eq!(n_queens(num), real[num as usize]);
     }
 }
@@ -148,17 +160,7 @@ fn test_n_queens() {
 #[test]
 fn test_parallel_n_queens() {
     let real = vec!(1, 1, 0, 0, 2, 10, 4, 40, 92);
-    for num in (0..9i32) {
+    for num in 0..9i32 {
         assert_eq!(semi_parallel_n_queens(num), real[num as usize]);
     }
 }
-
-#[bench]
-fn bench_n_queens(b: &mut Bencher) {
-    b.iter(|| { test::black_box(n_queens(16)); });
-}
-
-#[bench]
-fn bench_semi_parallel_n_queens(b: &mut Bencher) {
-    b.iter(|| { test::black_box(semi_parallel_n_queens(16)); });
-}
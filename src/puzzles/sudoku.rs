@@ -1,10 +1,14 @@
 // http://rosettacode.org/wiki/Sudoku
-#![feature(unicode)]
-#![feature(core)]
+// With the trace feature, ROSETTA_TRACE=1 shows each guess the solver makes, and where it found
+// out a guess was wrong.
 
-use std::{fmt, iter};
+#[macro_use]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::fmt;
 use std::str::FromStr;
-use std::num::Int;
 
 const BOARD_WIDTH: usize = 9;
 const BOARD_HEIGHT: usize = 9;
@@ -12,13 +16,13 @@ const GROUP_WIDTH: usize = 3;
 const GROUP_HEIGHT: usize = 3;
 const MAX_NUMBER: usize = 9;
 
-type BITS = u16;
-const MASK_ALL: BITS = 0x1ff;
+type Bits = u16;
+const MASK_ALL: Bits = 0x1ff;
 const INVALID_CELL: usize = !0;
 
-#[derive(Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
 struct Sudoku {
-    map: [[BITS; BOARD_WIDTH]; BOARD_HEIGHT]
+    map: [[Bits; BOARD_WIDTH]; BOARD_HEIGHT]
 }
 
 impl Sudoku {
@@ -29,7 +33,7 @@ impl Sudoku {
     fn get(&self, x: usize, y: usize) -> usize {
         match self.map[y][x].count_ones() {
             0 => INVALID_CELL,
-            1 => self.map[y][x].trailing_zeros() + 1,
+            1 => self.map[y][x].trailing_zeros() as usize + 1,
             _ => 0
         }
     }
@@ -47,7 +51,7 @@ impl FromStr for Sudoku {
         let mut sudoku = Sudoku::new();
 
         for (y, line) in s.lines().filter(|l| !l.is_empty()).enumerate() {
-            let line = line.trim_matches(CharExt::is_whitespace);
+            let line = line.trim_matches(char::is_whitespace);
             for (x, c) in line.chars().enumerate() {
                 if let Some(d) = c.to_digit(10) {
                     if d != 0 { sudoku.set(x, y, d); }
@@ -65,25 +69,25 @@ impl fmt::Display for Sudoku {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let hbar = "+---+---+---+";
 
-        for y in (0 .. BOARD_HEIGHT) {
+        for y in 0 .. BOARD_HEIGHT {
             if y % GROUP_HEIGHT == 0 {
-                try!(writeln!(f, "{}", hbar));
+                writeln!(f, "{}", hbar)?;
             }
 
-            for x in (0 .. BOARD_WIDTH) {
+            for x in 0 .. BOARD_WIDTH {
                 if x % GROUP_WIDTH == 0 {
-                    try!(write!(f, "|"));
+                    write!(f, "|")?;
                 }
 
                 match self.get(x, y) {
-                    INVALID_CELL => try!(write!(f, "!")),
-                    0 => try!(write!(f, " ")),
-                    d => try!(write!(f, "{}", d))
+                    INVALID_CELL => write!(f, "!")?,
+                    0 => write!(f, " ")?,
+                    d => write!(f, "{}", d)?
                 }
             }
-            try!(writeln!(f, "|"));
+            writeln!(f, "|")?;
         }
-        try!(writeln!(f, "{}", hbar));
+        writeln!(f, "{}", hbar)?;
 
         Ok(())
     }
@@ -99,8 +103,8 @@ fn solve_sudoku(mut puzzle: Sudoku) -> Vec<Sudoku> {
 
         // If the number at cell (x, y) is uniquely determined, that number must
         // not have appeared at the cells in the same row/column/group.
-        for y in (0 .. BOARD_HEIGHT) {
-            for x in (0 .. BOARD_WIDTH) {
+        for y in 0 .. BOARD_HEIGHT {
+            for x in 0 .. BOARD_WIDTH {
                 if puzzle.map[y][x].count_ones() != 1 { continue }
 
                 let (x0, y0) = ((x / GROUP_WIDTH) * GROUP_WIDTH,
@@ -122,11 +126,11 @@ fn solve_sudoku(mut puzzle: Sudoku) -> Vec<Sudoku> {
 
         // If `n` appears only once at the cell in the row/column/group, the
         // number of the cell must be `n`.
-        for n in (0 .. MAX_NUMBER) {
+        for n in 0 .. MAX_NUMBER {
             let bit = 1 << n;
 
             // Check each rows
-            for y in (0 .. BOARD_HEIGHT) {
+            for y in 0 .. BOARD_HEIGHT {
                 let next = {
                     let mut it = (0 .. BOARD_WIDTH)
                         .filter(|&x| puzzle.map[y][x] & bit != 0);
@@ -138,7 +142,7 @@ fn solve_sudoku(mut puzzle: Sudoku) -> Vec<Sudoku> {
             }
 
             // Check each column
-            for x in (0 .. BOARD_WIDTH) {
+            for x in 0 .. BOARD_WIDTH {
                 let next = {
                     let mut it = (0 .. BOARD_HEIGHT)
                         .filter(|&y| puzzle.map[y][x] & bit != 0);
@@ -150,8 +154,8 @@ fn solve_sudoku(mut puzzle: Sudoku) -> Vec<Sudoku> {
             }
 
             // Check each group
-            for y0 in iter::range_step(0, BOARD_HEIGHT, GROUP_WIDTH) {
-                for x0 in iter::range_step(0, BOARD_WIDTH, GROUP_HEIGHT) {
+            for y0 in (0 .. BOARD_HEIGHT).step_by(GROUP_WIDTH) {
+                for x0 in (0 .. BOARD_WIDTH).step_by(GROUP_HEIGHT) {
                     let next = {
                         let mut it = idx_in_grp
                             .iter()
@@ -173,11 +177,14 @@ fn solve_sudoku(mut puzzle: Sudoku) -> Vec<Sudoku> {
 
     let it = (0 .. BOARD_HEIGHT * BOARD_WIDTH)
         .map(|i| (i % BOARD_WIDTH, i / BOARD_WIDTH))
-        .map(|(x, y)| (x, y, puzzle.map[y][x].count_ones() as BITS))
+        .map(|(x, y)| (x, y, puzzle.map[y][x].count_ones() as Bits))
         .collect::<Vec<_>>();
 
     // If some cells have no possible number, there is no answer.
-    if it.iter().any(|&(_x, _y, cnt)| cnt == 0) { return vec![]; }
+    if it.iter().any(|&(_x, _y, cnt)| cnt == 0) {
+        trace!("dead end");
+        return vec![];
+    }
 
     // If all cells have exact one possible number, this is a answer.
     if it.iter().all(|&(_x, _y, cnt)| cnt == 1) { return vec![puzzle]; }
@@ -185,24 +192,25 @@ fn solve_sudoku(mut puzzle: Sudoku) -> Vec<Sudoku> {
     // Find the first undetermined cell.
     let (x, y, _cnt) = *it.iter()
         .filter(|& &(_x, _y, cnt)| cnt > 1)
-        .min_by(|& &(_x, _y, cnt)| cnt)
+        .min_by_key(|& &(_x, _y, cnt)| cnt)
         .unwrap();
 
     let mut answers = vec![];
-    for n in (0 .. MAX_NUMBER) {
+    for n in 0 .. MAX_NUMBER {
         let bit = 1 << n;
         if puzzle.map[y][x] & bit == 0 { continue }
 
         // Assuming the number at (x, y) is `n`, try to solve the problem again.
         // If some answers are found, append them to the `answers`.
+        trace!("guessing {} at ({}, {})", n + 1, x, y);
         let mut p2 = puzzle;
         p2.map[y][x] = bit;
-        answers.extend(solve_sudoku(p2).into_iter());
+        answers.extend(solve_sudoku(p2));
     }
     answers
 }
 
-const INPUT: &'static str = "
+const INPUT: &str = "
     850002400
     720000009
     004000000
@@ -226,7 +234,7 @@ fn main() {
 }
 
 #[cfg(test)]
-const SOLUTION: &'static str = "
+const SOLUTION: &str = "
     859612437
     723854169
     164379528
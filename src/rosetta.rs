@@ -0,0 +1,328 @@
+// Runs any task in the crate by name, so there's no need to hunt for the right binary:
+//
+//     cargo run --bin rosetta -- metered_concurrency --permits 2
+//     cargo run --bin rosetta -- --list
+//
+// It can also run every solution to a task with the same arguments, and the same file on standard
+// input if given one, to see whether they agree and which is quickest:
+//
+//     cargo run --release --bin rosetta -- compare hamming_numbers
+//
+// Every task is still its own binary.  When started through cargo, the task is run with
+// `cargo run` as well, so it gets rebuilt if need be, with the features it needs (such as `net`)
+// turned on; otherwise the binary is expected to have been built alongside this one.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[allow(dead_code)]
+mod rosetta_meta;
+
+// What the command line asks for
+#[derive(Debug, PartialEq)]
+enum Action {
+    List,
+    Run(String, Vec<String>), // A task, and the arguments to pass on to it
+    // A task whose variants to compare, the file to give them as input, and their arguments
+    Compare(String, Option<String>, Vec<String>),
+}
+
+// Parse the arguments following the program name.
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Action, String> {
+    match args.next() {
+        Some(ref flag) if flag == "--list" => Ok(Action::List),
+        Some(ref command) if command == "compare" => {
+            let task = args.next().ok_or_else(|| "no task given to compare".to_string())?;
+            if rosetta_meta::find(&task).is_none() {
+                return Err(format!("no such task: {} (try --list)", task));
+            }
+            let mut args: Vec<String> = args.collect();
+            let input = match args.first().map(String::as_str) {
+                Some("--input") if args.len() > 1 => Some(args.drain(..2).nth(1).unwrap()),
+                Some("--input") => return Err("--input needs a file".to_string()),
+                _ => None,
+            };
+            Ok(Action::Compare(task, input, args))
+        }
+        Some(task) => match rosetta_meta::find(&task) {
+            Some(_) => Ok(Action::Run(task, args.collect())),
+            None => Err(format!("no such task: {} (try --list)", task)),
+        },
+        None => Err("no task given".to_string()),
+    }
+}
+
+// The command that runs `task`.
+fn task_command(task: &rosetta_meta::Task) -> Command {
+    match env::var_os("CARGO") {
+        Some(cargo) => {
+            let mut command = Command::new(cargo);
+            command.args(["run", "--quiet", "--bin", task.name]);
+            if !task.features.is_empty() {
+                command.args(["--features", &task.features.join(",")]);
+            }
+            command.arg("--");
+            command
+        }
+        None => Command::new(sibling(task.name)),
+    }
+}
+
+// The path `task`'s binary would have if it was built along with this one.
+fn sibling(task: &str) -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.with_file_name(format!("{}{}", task, env::consts::EXE_SUFFIX))
+}
+
+// What running one variant of a task did
+struct Run {
+    name: &'static str,
+    output: String, // What it printed to standard output
+    time: Duration,
+    success: bool,
+}
+
+// Run every variant of `task` with `args`, and the contents of `input` on standard input, and
+// report on the runs.  Returns whether they all succeeded and printed the same.
+fn compare(task: &str, input: Option<&str>, args: &[String]) -> Result<bool, String> {
+    let variants = rosetta_meta::variants(task);
+    if variants.len() < 2 {
+        return Err(format!("{} has no other variants to compare it with", task));
+    }
+    let input = match input {
+        Some(path) => fs::read(path).map_err(|error| format!("couldn't read {}: {}", path, error))?,
+        None => Vec::new(),
+    };
+    build(&variants)?;
+    let mut runs = Vec::new();
+    for variant in variants {
+        runs.push(run_variant(variant, &input, args)?);
+    }
+    print!("{}", report(&runs));
+    Ok(runs.iter().all(|run| run.success && run.output == runs[0].output))
+}
+
+// Build the variants first when started through cargo, so that building them isn't timed along
+// with running them
+fn build(variants: &[&rosetta_meta::Task]) -> Result<(), String> {
+    let cargo = match env::var_os("CARGO") {
+        Some(cargo) => cargo,
+        None => return Ok(()),
+    };
+    let mut command = Command::new(cargo);
+    command.args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let mut features: Vec<&str> = Vec::new();
+    for variant in variants {
+        command.args(["--bin", variant.name]);
+        features.extend(variant.features);
+    }
+    features.sort();
+    features.dedup();
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    match command.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err("couldn't build the variants".to_string()),
+        Err(error) => Err(format!("couldn't run cargo: {}", error)),
+    }
+}
+
+fn run_variant(task: &'static rosetta_meta::Task, input: &[u8], args: &[String])
+               -> Result<Run, String> {
+    let start = Instant::now();
+    let mut child = Command::new(sibling(task.name)).args(args)
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()
+        .map_err(|error| format!("couldn't run {}: {}", task.name, error))?;
+    // Written from another thread, so that neither side waits for ever on a full pipe.  A task
+    // that doesn't read its input is fine too.
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_vec();
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(&input);
+    });
+    let output = child.wait_with_output()
+        .map_err(|error| format!("couldn't run {}: {}", task.name, error))?;
+    let time = start.elapsed();
+    let _ = writer.join();
+    Ok(Run {
+        name: task.name,
+        output: String::from_utf8_lossy(&output.stdout).into_owned(),
+        time,
+        success: output.status.success(),
+    })
+}
+
+// A line for each run, with how long it took, and whether it printed the same as the first or,
+// if not, the first line where they differ
+fn report(runs: &[Run]) -> String {
+    let width = runs.iter().map(|run| run.name.len()).max().unwrap_or(0);
+    let mut report = String::new();
+    for (i, run) in runs.iter().enumerate() {
+        report.push_str(&format!("{:<width$}  {:>8.3}s", run.name, run.time.as_secs_f64(),
+                                 width = width));
+        if !run.success {
+            report.push_str("  failed");
+        }
+        if i > 0 {
+            match first_difference(&runs[0].output, &run.output) {
+                None => report.push_str("  same output"),
+                Some((line, first, this)) => {
+                    report.push_str(&format!("  different output, from line {}:\n", line));
+                    for (name, text) in [(runs[0].name, first), (run.name, this)] {
+                        report.push_str(&format!("    {:<width$}  {}\n", name,
+                                                 text.unwrap_or("(no more output)"),
+                                                 width = width));
+                    }
+                    continue;
+                }
+            }
+        }
+        report.push('\n');
+    }
+    report
+}
+
+// The number of the first line, counting from 1, where `a` and `b` differ, and that line of each
+// of them, which is None past the end of one
+fn first_difference<'a>(a: &'a str, b: &'a str)
+                        -> Option<(usize, Option<&'a str>, Option<&'a str>)> {
+    let (mut a_lines, mut b_lines) = (a.lines(), b.lines());
+    let mut line = 0;
+    loop {
+        line += 1;
+        match (a_lines.next(), b_lines.next()) {
+            (None, None) => return None,
+            (a_line, b_line) if a_line != b_line => return Some((line, a_line, b_line)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let action = match parse_args(env::args().skip(1)) {
+        Ok(action) => action,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: rosetta <task> [args...] | rosetta --list");
+            eprintln!("       rosetta compare <task> [--input FILE] [args...]");
+            std::process::exit(1);
+        }
+    };
+    match action {
+        Action::List => {
+            for task in rosetta_meta::tasks() {
+                println!("{:<40} {:<16} {}", task.name, task.category.unwrap_or(""),
+                         task.url.unwrap_or(""));
+            }
+        }
+        Action::Run(name, args) => {
+            let task = rosetta_meta::find(&name).unwrap();
+            let status = match task_command(task).args(&args).status() {
+                Ok(status) => status,
+                Err(error) => {
+                    eprintln!("couldn't run {}: {}", name, error);
+                    if !task.enabled() {
+                        eprintln!("it's only built with --features {}", task.features.join(","));
+                    }
+                    std::process::exit(1);
+                }
+            };
+            // Pass on the task's exit code, or report failure if it was killed
+            std::process::exit(status.code().unwrap_or(1));
+        }
+        Action::Compare(task, input, args) => {
+            match compare(&task, input.as_deref(), &args) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let parse = |args: &[&str]| parse_args(args.iter().map(|s| s.to_string()));
+    assert_eq!(parse(&["--list"]), Ok(Action::List));
+    assert_eq!(parse(&["metered_concurrency", "--permits", "2"]),
+               Ok(Action::Run("metered_concurrency".to_string(),
+                              vec!["--permits".to_string(), "2".to_string()])));
+    assert_eq!(parse(&["100_doors"]), Ok(Action::Run("100_doors".to_string(), vec![])));
+    assert!(parse(&[]).is_err());
+    assert!(parse(&["no_such_task"]).is_err());
+    assert_eq!(parse(&["compare", "hamming_numbers"]),
+               Ok(Action::Compare("hamming_numbers".to_string(), None, vec![])));
+    assert_eq!(parse(&["compare", "100_doors", "--input", "doors.txt", "-v"]),
+               Ok(Action::Compare("100_doors".to_string(), Some("doors.txt".to_string()),
+                                  vec!["-v".to_string()])));
+    assert!(parse(&["compare"]).is_err());
+    assert!(parse(&["compare", "100_doors", "--input"]).is_err());
+    assert!(parse(&["compare", "no_such_task"]).is_err());
+}
+
+#[test]
+fn test_compare_without_variants() {
+    assert_eq!(compare("rosetta_meta", None, &[]),
+               Err("rosetta_meta has no other variants to compare it with".to_string()));
+}
+
+#[test]
+fn test_first_difference() {
+    assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+    assert_eq!(first_difference("a\nb\n", "a\nc\n"), Some((2, Some("b"), Some("c"))));
+    assert_eq!(first_difference("a\n", "a\nb\n"), Some((2, None, Some("b"))));
+}
+
+#[test]
+fn test_report() {
+    let run = |name, output: &str, millis, success| Run {
+        name,
+        output: output.to_string(),
+        time: Duration::from_millis(millis),
+        success,
+    };
+    let runs = [run("hamming", "1\n2\n", 1500, true), run("hamming_alt", "1\n2\n", 250, true),
+                run("hamming_bad", "1\n3\n", 5, false)];
+    assert_eq!(report(&runs),
+               "hamming         1.500s\n\
+                hamming_alt     0.250s  same output\n\
+                hamming_bad     0.005s  failed  different output, from line 2:\n    \
+                hamming      2\n    \
+                hamming_bad  3\n");
+}
+
+#[test]
+fn test_sibling() {
+    let path = sibling("metered_concurrency");
+    assert_eq!(path.parent(), env::current_exe().unwrap().parent());
+    assert!(path.ends_with(format!("metered_concurrency{}", env::consts::EXE_SUFFIX)));
+}
+
+#[test]
+fn test_task_command() {
+    let command = task_command(rosetta_meta::find("100_doors").unwrap());
+    let networked = task_command(rosetta_meta::find("dns_query").unwrap());
+    match env::var_os("CARGO") {
+        Some(cargo) => {
+            assert_eq!(command.get_program(), cargo);
+            let args: Vec<_> = command.get_args().map(|arg| arg.to_str().unwrap()).collect();
+            assert_eq!(args, ["run", "--quiet", "--bin", "100_doors", "--"]);
+            let args: Vec<_> = networked.get_args().map(|arg| arg.to_str().unwrap()).collect();
+            assert_eq!(args, ["run", "--quiet", "--bin", "dns_query", "--features", "net", "--"]);
+        }
+        None => assert_eq!(PathBuf::from(command.get_program()), sibling("100_doors")),
+    }
+}
@@ -0,0 +1,184 @@
+// Registry of every task in the crate, for tooling that needs to know which tasks exist.
+// Each task is its own binary, so the table is generated by build.rs from the [[bin]] entries of
+// Cargo.toml rather than registered by the tasks themselves.  Other binaries can use it with
+// `mod rosetta_meta;` and call rosetta_meta::tasks().
+//
+// Run on its own, it prints the registry (or, given a task's name, just that task) as
+// tab-separated name, URL, path, category, feature, variant and status columns.
+//
+// Some tasks are only built with cargo features that a default build leaves out: `net`, `gui`,
+// `slow` and `unix-only` (see Cargo.toml).  They're in the registry either way, with the features
+// they need.
+//
+// A task can have several solutions, such as a recursive one and an iterative one.  Each of the
+// others is a variant of the first, and variants() finds them all, for `rosetta compare` to run.
+//
+// A task that isn't finished yet is marked as a draft in its source with the #[rosetta_task]
+// attribute (see rosetta_task/src/lib.rs), and every other task is complete.
+
+pub struct Task {
+    pub name: &'static str,                // Name of the binary, which is how the task is run
+    pub url: Option<&'static str>,         // Its Rosetta Code page, for the tasks that have one
+    pub path: &'static str,                // Source file, relative to the crate root
+    pub category: Option<&'static str>,    // The directory under src/ it's in, if it's a task
+    pub features: &'static [&'static str], // The cargo features it's only built with
+    pub variant_of: Option<&'static str>,  // The task it's another solution to, if it is one
+    pub status: Status,                    // Whether it's finished
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Complete,
+    Draft,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Complete => "complete",
+            Status::Draft => "draft",
+        }
+    }
+}
+
+// Every cargo feature a task can need
+pub static FEATURES: &[&str] = &["std", "net", "gui", "slow", "unix-only", "mem-profile",
+                                 "trace"];
+
+impl Task {
+    // Whether the task is built along with this binary, which it is if every feature it needs is
+    // turned on.
+    pub fn enabled(&self) -> bool {
+        self.features.iter().all(|&feature| enabled(feature))
+    }
+}
+
+fn enabled(feature: &str) -> bool {
+    match feature {
+        "std" => cfg!(feature = "std"),
+        "net" => cfg!(feature = "net"),
+        "gui" => cfg!(feature = "gui"),
+        "slow" => cfg!(feature = "slow"),
+        "unix-only" => cfg!(feature = "unix-only"),
+        "mem-profile" => cfg!(feature = "mem-profile"),
+        "trace" => cfg!(feature = "trace"),
+        _ => false,
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/tasks.rs"));
+
+// Return every task, in the order Cargo.toml lists them
+pub fn tasks() -> &'static [Task] {
+    TASKS
+}
+
+// Look a task up by the name of its binary.
+pub fn find(name: &str) -> Option<&'static Task> {
+    tasks().iter().find(|task| task.name == name)
+}
+
+// Every solution to the task called `name`, which may be any one of them: the first solution,
+// and then its variants.  It's empty if there's no such task.
+pub fn variants(name: &str) -> Vec<&'static Task> {
+    let first = match find(name) {
+        Some(task) => task.variant_of.unwrap_or(task.name),
+        None => return Vec::new(),
+    };
+    tasks().iter().filter(|task| task.name == first || task.variant_of == Some(first)).collect()
+}
+
+#[cfg(not(test))]
+#[allow(dead_code)]
+fn main() {
+    let selected: Vec<&Task> = match std::env::args().nth(1) {
+        Some(name) => match find(&name) {
+            Some(task) => vec![task],
+            None => {
+                eprintln!("no such task: {}", name);
+                std::process::exit(1);
+            }
+        },
+        None => tasks().iter().collect(),
+    };
+    for task in selected {
+        let features = if task.features.is_empty() { "-".to_string() } else {
+            task.features.join(",")
+        };
+        println!("{}\t{}\t{}\t{}\t{}\t{}\t{}", task.name, task.url.unwrap_or("-"), task.path,
+                 task.category.unwrap_or("-"), features, task.variant_of.unwrap_or("-"),
+                 task.status.name());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find, tasks, variants, Status, FEATURES};
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    #[test]
+    fn every_task_exists() {
+        for task in tasks() {
+            assert!(Path::new(task.path).exists(), "{} has no source at {}", task.name, task.path);
+        }
+        let names: HashSet<_> = tasks().iter().map(|task| task.name).collect();
+        assert_eq!(names.len(), tasks().len());
+    }
+
+    #[test]
+    fn find_task() {
+        let task = find("metered_concurrency").unwrap();
+        assert_eq!(task.url, Some("http://rosettacode.org/wiki/Metered_concurrency"));
+        assert_eq!(task.path, "src/concurrency/metered_concurrency.rs");
+        assert_eq!(task.category, Some("concurrency"));
+        assert_eq!(find("rosetta_meta").unwrap().url, None);
+        assert_eq!(find("rosetta_meta").unwrap().category, None);
+        assert!(find("no_such_task").is_none());
+    }
+
+    #[test]
+    fn features() {
+        for task in tasks() {
+            for feature in task.features {
+                assert!(FEATURES.contains(feature), "{} needs unknown feature {}", task.name,
+                        feature);
+            }
+            // What a task needs the network for can't be counted on in a default build
+            if task.category == Some("net") {
+                assert!(task.features.contains(&"net"), "{} doesn't need net", task.name);
+            }
+        }
+        assert_eq!(find("metered_concurrency").unwrap().features, ["std"]);
+        assert!(find("metered_concurrency").unwrap().enabled());
+        assert!(find("100_doors").unwrap().features.is_empty());
+        assert!(find("100_doors").unwrap().enabled());
+        assert_eq!(find("dns_query").unwrap().enabled(), cfg!(feature = "net"));
+    }
+
+    #[test]
+    fn find_variants() {
+        for task in tasks() {
+            if let Some(first) = task.variant_of {
+                let first = find(first).unwrap_or_else(|| {
+                    panic!("{} is a variant of {}, which doesn't exist", task.name, first)
+                });
+                assert_eq!(first.variant_of, None, "{} is a variant of a variant", task.name);
+            }
+        }
+        let names = |name| variants(name).iter().map(|task| task.name).collect::<Vec<_>>();
+        assert_eq!(names("hamming_numbers"), ["hamming_numbers", "hamming_numbers_alt"]);
+        assert_eq!(names("hamming_numbers_alt"), ["hamming_numbers", "hamming_numbers_alt"]);
+        assert_eq!(names("rosetta_meta"), ["rosetta_meta"]);
+        assert!(names("no_such_task").is_empty());
+    }
+
+    #[test]
+    fn status() {
+        assert_eq!(find("24_game_solve").unwrap().status, Status::Draft);
+        assert_eq!(find("24_game_solve").unwrap().url,
+                   Some("http://rosettacode.org/wiki/24_game/Solve"));
+        assert_eq!(find("100_doors").unwrap().status, Status::Complete);
+        assert_eq!(Status::Draft.name(), "draft");
+    }
+}
@@ -1,5 +1,10 @@
 //Implements http://rosettacode.org/wiki/Sorting_algorithms/Bubble_sort
 
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
 /// Progress through the slice and 'bubble' elements up until they are in order.
 fn bubble_sort<T: PartialOrd>(v: &mut [T]) {
     (1..v.len()+1).rev().all(|length| {
@@ -25,6 +30,8 @@ fn main() {
 
 #[cfg(test)]
 mod test {
+    use utils::property;
+
     fn check_sort<T: PartialOrd>(v: &mut [T]) {
         super::bubble_sort(v);
 
@@ -68,4 +75,9 @@ mod test {
         let numbers = &mut [-1i32, 0, 3, 6, 99];
         check_sort(numbers);
     }
+
+    #[test]
+    fn any_vector() {
+        property::sorts_in_place(super::bubble_sort);
+    }
 }
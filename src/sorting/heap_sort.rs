@@ -1,7 +1,25 @@
-// Implements http://rosettacode.org/wiki/Sorting_algorithms/Heapsort
+//! Implements http://rosettacode.org/wiki/Sorting_algorithms/Heapsort
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::heap_sort::heap_sort;
+//!
+//! let mut numbers = [6, 34, 3, 9, -2, 3];
+//! heap_sort(&mut numbers);
+//! assert_eq!(numbers, [-2, 3, 3, 6, 9, 34]);
+//! ```
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+#[cfg(all(test, feature = "mem-profile"))]
+#[global_allocator]
+static METER: utils::memory::Meter = utils::memory::Meter;
 
 // This is ported from the Dart heap sort implementation
-fn heap_sort<T: Ord>(a: &mut [T]) {
+pub fn heap_sort<T: Ord>(a: &mut [T]) {
     let count = a.len();
 
     if count == 0 {
@@ -32,7 +50,7 @@ fn heapify<T: Ord>(a: &mut [T], count: usize) {
     }
 
     // start is assigned the index in 'a' of the last parent node
-    let mut start:i32 = count as i32 - 2 / 2; // binary heap
+    let mut start:i32 = (count as i32 - 2) / 2; // binary heap
     
     while start >= 0 {        
         // sift down the node at index 'start' to the proper place
@@ -50,12 +68,12 @@ fn sift_down<T: Ord>(a: &mut [T], start: usize, end: usize) {
     // while the root has at least one child
     while (root*2 + 1) <= end { 
         // root*2+1 points to the left child
-        let mut child:usize = root*2 + 1 as usize;
+        let mut child:usize = root*2 + 1;
         
         // if the chile has a sibling and the child's value is less that its sibling's...
-        if child + 1 <= end && a[child] < a[child + 1] {
+        if child < end && a[child] < a[child + 1] {
             // .. then point to the right child instead
-            child = child + 1;
+            child += 1;
         }
         
         // out of max-heap order
@@ -87,6 +105,7 @@ pub fn main() {
 #[cfg(test)]
 mod test {
     use super::heap_sort;
+    use super::utils::{memory, property};
     
     #[test]
     fn sorted() {
@@ -122,4 +141,17 @@ mod test {
         heap_sort(&mut arr);
         assert!(arr.is_empty());
     }
+
+    #[test]
+    fn any() {
+        property::sorts_in_place(heap_sort);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "mem-profile"), ignore)]
+    fn in_place() {
+        let mut numbers: Vec<u32> = (0..1000).rev().collect();
+        memory::assert_peak_at_most(0, || heap_sort(&mut numbers));
+        assert_eq!(numbers, (0..1000).collect::<Vec<u32>>());
+    }
 }
@@ -1,9 +1,22 @@
-// Implements http://rosettacode.org/wiki/Sorting_algorithms/Merge_sort
+//! Implements http://rosettacode.org/wiki/Sorting_algorithms/Merge_sort
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::merge_sort::merge_sort;
+//!
+//! assert_eq!(merge_sort(&[6, 34, 3, 9, -2, 3]), [-2, 3, 3, 6, 9, 34]);
+//! assert_eq!(merge_sort(&["b", "c", "a"]), ["a", "b", "c"]);
+//! ```
 
 // This is an idiomatic-but-slow implementation. A more efficient implementation
 // would use `unsafe` to avoid allocating so many temporary vectors.
 
-fn merge_sort<E: PartialOrd + Clone>(arr: &[E]) -> Vec<E> {
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+pub fn merge_sort<E: PartialOrd + Clone>(arr: &[E]) -> Vec<E> {
     if arr.len() <= 1 {
         return arr.to_vec();
     }
@@ -47,6 +60,7 @@ pub fn main() {
 #[cfg(test)]
 mod test {
     use super::merge_sort;
+    use super::utils::property;
 
     #[test]
     fn sorted() {
@@ -65,4 +79,9 @@ mod test {
         let arr = [12i32, 54, 2, 93, 13, 43, 15, 299, 234];
         assert_eq!(merge_sort(&arr), vec![2i32, 12, 13, 15, 43, 54, 93, 234, 299]);
     }
+
+    #[test]
+    fn any() {
+        property::sorts(merge_sort);
+    }
 }
@@ -1,14 +1,21 @@
-//Implements http://rosettacode.org/wiki/Sorting_algorithms/Quicksort
-// Used by the tests
-#![allow(unused_features)]
-
-extern crate rand;
+//! Implements http://rosettacode.org/wiki/Sorting_algorithms/Quicksort
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::quick_sort::quick_sort;
+//!
+//! let mut numbers = [6, 34, 3, 9, -2, 3];
+//! quick_sort(&mut numbers);
+//! assert_eq!(numbers, [-2, 3, 3, 6, 9, 34]);
+//! ```
 #[cfg(test)]
-use rand::{thread_rng, Rng};
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 // We use in place quick sort
 // For details see http://en.wikipedia.org/wiki/Quicksort#In-place_version
-fn quick_sort<T: Ord>(v: &mut[T]) {
+pub fn quick_sort<T: Ord>(v: &mut[T]) {
     let len = v.len();
     if len < 2 {
         return;
@@ -33,7 +40,7 @@ fn partition<T: Ord>(v: &mut [T]) -> usize {
     v.swap(pivot_index, len - 1);
 
     let mut store_index = 0;
-    for i in (0..len - 1) {
+    for i in 0..len - 1 {
         if v[i] <= v[len - 1] {
             v.swap(i, store_index);
             store_index += 1;
@@ -64,7 +71,7 @@ fn main() {
 #[cfg(test)]
 fn check_sort<T: Ord>(v: &[T]) {
     if v.len() > 1 {
-        for i in (0..v.len()-1) {
+        for i in 0..v.len()-1 {
             assert!(v[i] <= v[i+1]);
         }
     }
@@ -81,7 +88,7 @@ fn test_rosetta_vector() {
 fn test_empty_vector() {
     let mut numbers: Vec<i32> = Vec::new();
     quick_sort(&mut numbers[..]);
-    check_sort(&mut numbers[..]);
+    check_sort(&numbers[..]);
 }
 
 #[test]
@@ -114,8 +121,13 @@ fn test_already_sorted_vector() {
 
 #[test]
 fn test_random_numbers() {
-    let mut rng = thread_rng();
-    let mut numbers : Vec<i32> = rng.gen_iter::<i32>().take(500).collect();
+    let mut rng = utils::rng::Rng::from_time();
+    let mut numbers : Vec<i32> = (0..500).map(|_| rng.next_u32() as i32).collect();
     quick_sort(&mut numbers[..]);
-    check_sort(&mut numbers[..]);
+    check_sort(&numbers[..]);
+}
+
+#[test]
+fn test_any_vector() {
+    utils::property::sorts_in_place(quick_sort);
 }
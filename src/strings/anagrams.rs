@@ -1,12 +1,11 @@
 // Implements http://rosettacode.org/wiki/Anagrams
-#![allow(unused_features)] // feature(io) is used only in main
-
-#![feature(std_misc)]
-#![feature(old_path)]
-#![feature(old_io)]
+// The words are the task's unixdict.txt, from tests/fixtures/.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 #[cfg(not(test))]
-use std::old_io::{File, BufferedReader};
+use std::io::BufRead;
 use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 
@@ -52,8 +51,7 @@ fn largest_groups(groups: &HashMap<String, HashSet<String>>)
 
 #[cfg(not(test))]
 fn main () {
-    let path = Path::new("src/resources/unixdict.txt");
-    let mut reader = BufferedReader::new(File::open(&path));
+    let reader = utils::fixture::open("unixdict.txt");
     let lines = reader.lines().map(|l| l.unwrap());
 
     let anagram_groups = anagrams(lines);
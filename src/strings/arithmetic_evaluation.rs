@@ -0,0 +1,172 @@
+//! Implements http://rosettacode.org/wiki/Arithmetic_evaluation
+//!
+//! An expression is parsed into a tree with utils::parse, and then the tree is evaluated, rather
+//! than the text being evaluated as it's read.  The grammar has the usual precedence, with a minus
+//! sign binding tighter than any operator, and its operators associate to the left:
+//!
+//! ```text
+//!     expression = term (("+" | "-") term)*
+//!     term       = factor (("*" | "/") factor)*
+//!     factor     = number | "(" expression ")" | "-" factor
+//! ```
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::arithmetic_evaluation::evaluate;
+//!
+//! assert_eq!(evaluate("(1 + 3) * 7 - 2 * -3"), Ok(34.0));
+//! assert_eq!(evaluate("8 / 4 / 2"), Ok(1.0));
+//! assert!(evaluate("(1 + 3").is_err());
+//! ```
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::parse::{self, char, delimited, many, map, or, pair, preceded, spaces, take_while1,
+                   token, try_map};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, PartialEq)]
+enum Expression {
+    Number(f64),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self) -> f64 {
+        match *self {
+            Expression::Number(n) => n,
+            Expression::Negate(ref operand) => -operand.evaluate(),
+            Expression::Binary(operator, ref left, ref right) => {
+                let (left, right) = (left.evaluate(), right.evaluate());
+                match operator {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    Operator::Multiply => left * right,
+                    Operator::Divide => left / right,
+                }
+            }
+        }
+    }
+}
+
+fn expression<'a>(input: &'a str) -> parse::Result<'a, Expression> {
+    let add = map(token(char('+')), |_| Operator::Add);
+    let subtract = map(token(char('-')), |_| Operator::Subtract);
+    operations(term, or(add, subtract))(input)
+}
+
+fn term<'a>(input: &'a str) -> parse::Result<'a, Expression> {
+    let multiply = map(token(char('*')), |_| Operator::Multiply);
+    let divide = map(token(char('/')), |_| Operator::Divide);
+    operations(factor, or(multiply, divide))(input)
+}
+
+fn factor<'a>(input: &'a str) -> parse::Result<'a, Expression> {
+    let digits = take_while1("a number", |c| c.is_ascii_digit() || c == '.');
+    let number = token(try_map(digits, "a number", |digits: &str| digits.parse().ok()));
+    let bracketed = delimited(token(char('(')), expression, token(char(')')));
+    let negated = map(preceded(token(char('-')), factor),
+                      |operand| Expression::Negate(Box::new(operand)));
+    or(or(map(number, Expression::Number), bracketed), negated)(input)
+}
+
+// One or more `operand`s with an `operator` between each one and the next, as a tree that
+// applies the operators from left to right
+fn operations<'a, P, Q>(operand: P, operator: Q)
+                        -> impl Fn(&'a str) -> parse::Result<'a, Expression>
+    where P: Fn(&'a str) -> parse::Result<'a, Expression>,
+          Q: Fn(&'a str) -> parse::Result<'a, Operator>
+{
+    move |input: &'a str| {
+        let (first, input) = operand(input)?;
+        let (rest, input) = many(pair(&operator, &operand))(input)?;
+        let tree = rest.into_iter().fold(first, |left, (operator, right)| {
+            Expression::Binary(operator, Box::new(left), Box::new(right))
+        });
+        Ok((tree, input))
+    }
+}
+
+// Parse and evaluate `text`, or say where it's wrong
+pub fn evaluate(text: &str) -> Result<f64, String> {
+    match parse::parse(preceded(spaces, expression), text) {
+        Ok(tree) => Ok(tree.evaluate()),
+        Err(error) => Err(error.describe(text)),
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let mut expressions: Vec<String> = std::env::args().skip(1).collect();
+    if expressions.is_empty() {
+        expressions.push("(1 + 3) * 7 - 10 / 4".to_string());
+    }
+    for text in &expressions {
+        match evaluate(text) {
+            Ok(value) => println!("{} = {}", text, value),
+            Err(message) => println!("{}: {}", text, message),
+        }
+    }
+}
+
+#[test]
+fn test_evaluate() {
+    let cases = [
+        ("(1 + 3) * 7 - 10 / 4", 25.5),
+        ("2 * (3 + 4) * 5", 70.0),
+        (" 1.5", 1.5),
+        // The operators associate to the left
+        ("8 - 4 - 2", 2.0),
+        ("8 / 4 / 2", 1.0),
+        ("1 + 2 * 3 - 4", 3.0),
+        ("((((7))))", 7.0),
+        // A minus sign binds tighter than any operator
+        ("-2 * 3", -6.0),
+        ("2 - -3", 5.0),
+        ("--4", 4.0),
+        ("-(1 + 2) * -(3 - 5)", -6.0),
+        ("1 / -4", -0.25),
+    ];
+    for &(text, value) in &cases {
+        assert_eq!(evaluate(text), Ok(value), "{}", text);
+    }
+}
+
+#[test]
+fn test_tree() {
+    use self::Expression::{Binary, Negate, Number};
+    assert_eq!(parse::parse(expression, "1+2*3"),
+               Ok(Binary(Operator::Add, Box::new(Number(1.0)),
+                         Box::new(Binary(Operator::Multiply, Box::new(Number(2.0)),
+                                         Box::new(Number(3.0)))))));
+    assert_eq!(parse::parse(expression, "-1-2"),
+               Ok(Binary(Operator::Subtract, Box::new(Negate(Box::new(Number(1.0)))),
+                         Box::new(Number(2.0)))));
+}
+
+#[test]
+fn test_errors() {
+    let cases = [
+        ("(1 + 2", "line 1, column 7: expected ')'"),
+        ("1 + * 2", "line 1, column 5: expected a number, '(' or '-'"),
+        ("1.2.3", "line 1, column 1: expected a number, '(' or '-'"),
+        ("2 3", "line 1, column 3: expected the end of the text"),
+        ("", "line 1, column 1: expected a number, '(' or '-'"),
+        ("1 +", "line 1, column 4: expected a number, '(' or '-'"),
+        ("(1 + 2))", "line 1, column 8: expected the end of the text"),
+        ("- -", "line 1, column 4: expected a number, '(' or '-'"),
+        ("1 +\n  x", "line 2, column 3: expected a number, '(' or '-'"),
+    ];
+    for &(text, message) in &cases {
+        assert_eq!(evaluate(text).unwrap_err(), message, "{:?}", text);
+    }
+}
@@ -0,0 +1,334 @@
+//! Implements http://rosettacode.org/wiki/Base64_encode_data
+//! and, with base64_decode.rs, http://rosettacode.org/wiki/Base64_decode_data
+//!
+//! Base64 (RFC 4648) writes each three bytes as four of 64 symbols, six bits each.  At the end, one
+//! byte left over makes two symbols and two bytes make three, and the standard alphabet pads them
+//! out to four with = signs.  The URL-safe alphabet has - and _ in place of + and /, which mean
+//! something in URLs and file names, and usually leaves the padding off.
+//!
+//! Encoder and Decoder take their input in pieces of any size, so a file can be converted as it's
+//! read, and encode() and decode() do it all at once.  Decoding skips whitespace, since encoded
+//! data is often split into lines.
+//!
+//! ```text
+//!     base64 [FILE]
+//! ```
+//!
+//! encodes the file, or the task's quote without one.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::base64::{decode, encode, DecodeError, Encoder, STANDARD, URL_SAFE};
+//!
+//! assert_eq!(encode(b"Many hands", &STANDARD), "TWFueSBoYW5kcw==");
+//! assert_eq!(encode(b"Many hands", &URL_SAFE), "TWFueSBoYW5kcw");
+//! assert_eq!(decode("TWFueSBo\nYW5kcw==", &STANDARD), Ok(b"Many hands".to_vec()));
+//! assert_eq!(decode("TW#u", &STANDARD), Err(DecodeError::Symbol('#', 2)));
+//!
+//! // The same, in pieces
+//! let mut text = String::new();
+//! let mut encoder = Encoder::new(&STANDARD);
+//! encoder.update(b"Many ", &mut text);
+//! encoder.update(b"hands", &mut text);
+//! encoder.finish(&mut text);
+//! assert_eq!(text, "TWFueSBoYW5kcw==");
+//! ```
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use std::fmt;
+
+pub struct Alphabet {
+    symbols: &'static [u8; 64],
+    padded: bool,
+}
+
+pub static STANDARD: Alphabet = Alphabet {
+    symbols: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    padded: true,
+};
+
+pub static URL_SAFE: Alphabet = Alphabet {
+    symbols: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    padded: false,
+};
+
+impl Alphabet {
+    fn value(&self, symbol: u8) -> Option<u32> {
+        self.symbols.iter().position(|&s| s == symbol).map(|value| value as u32)
+    }
+}
+
+pub struct Encoder<'a> {
+    alphabet: &'a Alphabet,
+    // The bytes that don't make three yet
+    pending: [u8; 3],
+    count: usize,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(alphabet: &'a Alphabet) -> Encoder<'a> {
+        Encoder { alphabet, pending: [0; 3], count: 0 }
+    }
+
+    // Write the symbols for `data` to `output`, keeping back up to two bytes for the next piece
+    pub fn update(&mut self, data: &[u8], output: &mut String) {
+        for &byte in data {
+            self.pending[self.count] = byte;
+            self.count += 1;
+            if self.count == 3 {
+                self.write(3, output);
+                self.count = 0;
+            }
+        }
+    }
+
+    // The pending bytes as a group of symbols, padded if the alphabet is
+    fn write(&self, bytes: usize, output: &mut String) {
+        let [a, b, c] = self.pending;
+        let group = (a as u32) << 16 | (b as u32) << 8 | c as u32;
+        for i in 0..bytes + 1 {
+            output.push(self.alphabet.symbols[(group >> (18 - 6 * i) & 63) as usize] as char);
+        }
+        if self.alphabet.padded {
+            for _ in bytes..3 {
+                output.push('=');
+            }
+        }
+    }
+
+    pub fn finish(mut self, output: &mut String) {
+        if self.count > 0 {
+            for byte in &mut self.pending[self.count..] {
+                *byte = 0;
+            }
+            self.write(self.count, output);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    // A character that isn't in the alphabet, and where it is
+    Symbol(char, usize),
+    // Padding where there shouldn't be any, or none where there should
+    Padding(usize),
+    // A single symbol at the end, which isn't enough for a byte
+    Truncated,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::Symbol(c, at) => write!(f, "{:?} at {} isn't base64", c, at),
+            DecodeError::Padding(at) => write!(f, "the padding at {} is wrong", at),
+            DecodeError::Truncated => write!(f, "the data stops halfway through a byte"),
+        }
+    }
+}
+
+pub struct Decoder<'a> {
+    alphabet: &'a Alphabet,
+    // The bits of the symbols that don't make three bytes yet
+    group: u32,
+    count: usize,
+    padding: usize,
+    // How many characters have been read, to say where errors are
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(alphabet: &'a Alphabet) -> Decoder<'a> {
+        Decoder { alphabet, group: 0, count: 0, padding: 0, position: 0 }
+    }
+
+    // Write the bytes of `text` to `output`, keeping back the symbols that don't make a whole one
+    pub fn update(&mut self, text: &str, output: &mut Vec<u8>) -> Result<(), DecodeError> {
+        for c in text.chars() {
+            let at = self.position;
+            self.position += 1;
+            if c.is_whitespace() {
+                continue;
+            }
+            if c == '=' && self.alphabet.padded {
+                // Padding makes up a group of two or three symbols, and nothing comes after it
+                self.padding += 1;
+                if self.count < 2 || self.count + self.padding > 4 {
+                    return Err(DecodeError::Padding(at));
+                }
+                if self.count + self.padding == 4 {
+                    self.flush(output);
+                }
+                continue;
+            }
+            let value = if c.is_ascii() { self.alphabet.value(c as u8) } else { None };
+            let value = value.ok_or(DecodeError::Symbol(c, at))?;
+            if self.padding > 0 {
+                return Err(DecodeError::Padding(at));
+            }
+            self.group = self.group << 6 | value;
+            self.count += 1;
+            if self.count == 4 {
+                self.flush(output);
+            }
+        }
+        Ok(())
+    }
+
+    // The bytes of the symbols so far, of which there are one fewer than symbols
+    fn flush(&mut self, output: &mut Vec<u8>) {
+        let group = self.group << (6 * (4 - self.count));
+        let bytes = [(group >> 16) as u8, (group >> 8) as u8, group as u8];
+        output.extend_from_slice(&bytes[..self.count - 1]);
+        self.group = 0;
+        self.count = 0;
+    }
+
+    pub fn finish(mut self, output: &mut Vec<u8>) -> Result<(), DecodeError> {
+        match self.count {
+            0 => Ok(()),
+            1 => Err(DecodeError::Truncated),
+            _ if self.alphabet.padded => Err(DecodeError::Padding(self.position)),
+            _ => {
+                self.flush(output);
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn encode(data: &[u8], alphabet: &Alphabet) -> String {
+    let mut text = String::with_capacity(data.len().div_ceil(3) * 4);
+    let mut encoder = Encoder::new(alphabet);
+    encoder.update(data, &mut text);
+    encoder.finish(&mut text);
+    text
+}
+
+pub fn decode(text: &str, alphabet: &Alphabet) -> Result<Vec<u8>, DecodeError> {
+    let mut data = Vec::with_capacity(text.len() / 4 * 3);
+    let mut decoder = Decoder::new(alphabet);
+    decoder.update(text, &mut data)?;
+    decoder.finish(&mut data)?;
+    Ok(data)
+}
+
+// The quote the decoding task decodes
+pub static QUOTE: &str = "To err is human, but to really foul things up you need a computer.\n    \
+                          -- Paul R. Ehrlich";
+
+#[cfg(not(test))]
+fn main() {
+    use std::fs::File;
+    use std::io::Read;
+
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            println!("{}", encode(QUOTE.as_bytes(), &STANDARD));
+            return;
+        }
+    };
+    let mut file = File::open(&path).unwrap_or_else(|e| panic!("can't open {}: {}", path, e));
+    let mut encoder = Encoder::new(&STANDARD);
+    let mut buffer = [0; 4096];
+    let mut text = String::new();
+    loop {
+        let read = file.read(&mut buffer).unwrap();
+        if read == 0 {
+            break;
+        }
+        encoder.update(&buffer[..read], &mut text);
+        print!("{}", text);
+        text.clear();
+    }
+    encoder.finish(&mut text);
+    println!("{}", text);
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::property;
+    use super::{decode, encode, DecodeError, Decoder, Encoder, QUOTE, STANDARD, URL_SAFE};
+
+    #[test]
+    fn rfc_4648() {
+        let vectors = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"),
+                       ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+        for &(data, text) in &vectors {
+            assert_eq!(encode(data.as_bytes(), &STANDARD), text);
+            assert_eq!(decode(text, &STANDARD), Ok(data.as_bytes().to_vec()));
+            let unpadded = text.trim_end_matches('=');
+            assert_eq!(encode(data.as_bytes(), &URL_SAFE), unpadded);
+            assert_eq!(decode(unpadded, &URL_SAFE), Ok(data.as_bytes().to_vec()));
+        }
+    }
+
+    #[test]
+    fn task() {
+        let encoded = "VG8gZXJyIGlzIGh1bWFuLCBidXQgdG8gcmVhbGx5IGZvdWwgdGhpbmdzIHVwIHlvdSBuZWVk\
+                       IGEgY29tcHV0ZXIuCiAgICAtLSBQYXVsIFIuIEVocmxpY2g=";
+        assert_eq!(encode(QUOTE.as_bytes(), &STANDARD), encoded);
+        assert_eq!(decode(encoded, &STANDARD).unwrap(), QUOTE.as_bytes());
+    }
+
+    #[test]
+    fn alphabets() {
+        let data = [0xfb, 0xff, 0xbf];
+        assert_eq!(encode(&data, &STANDARD), "+/+/");
+        assert_eq!(encode(&data, &URL_SAFE), "-_-_");
+        assert_eq!(encode(&data[..2], &STANDARD), "+/8=");
+        assert_eq!(encode(&data[..2], &URL_SAFE), "-_8");
+        assert_eq!(decode("-_-_", &URL_SAFE), Ok(data.to_vec()));
+        assert_eq!(decode("-_-_", &STANDARD), Err(DecodeError::Symbol('-', 0)));
+        assert_eq!(decode("+/+/", &URL_SAFE), Err(DecodeError::Symbol('+', 0)));
+    }
+
+    #[test]
+    fn bad_input() {
+        assert_eq!(decode("Zm9v\nYmFy\n", &STANDARD), Ok(b"foobar".to_vec()));
+        assert_eq!(decode("Zm9vY", &STANDARD), Err(DecodeError::Truncated));
+        assert_eq!(decode("Zg", &STANDARD), Err(DecodeError::Padding(2)));
+        assert_eq!(decode("Z===", &STANDARD), Err(DecodeError::Padding(1)));
+        assert_eq!(decode("Zg===", &STANDARD), Err(DecodeError::Padding(4)));
+        assert_eq!(decode("Zg==Zg==", &STANDARD), Err(DecodeError::Padding(4)));
+        assert_eq!(decode("Zm9v!", &STANDARD), Err(DecodeError::Symbol('!', 4)));
+        assert_eq!(decode("Zm9vé", &STANDARD), Err(DecodeError::Symbol('é', 4)));
+        assert_eq!(DecodeError::Truncated.to_string(), "the data stops halfway through a byte");
+    }
+
+    #[test]
+    fn pieces() {
+        // However the input is split up, the output is the same
+        let data: Vec<u8> = (0..100u32).map(|i| (i * 37 % 256) as u8).collect();
+        let whole = encode(&data, &STANDARD);
+        for piece in 1..8 {
+            let mut text = String::new();
+            let mut encoder = Encoder::new(&STANDARD);
+            for chunk in data.chunks(piece) {
+                encoder.update(chunk, &mut text);
+            }
+            encoder.finish(&mut text);
+            assert_eq!(text, whole);
+
+            let mut decoded = Vec::new();
+            let mut decoder = Decoder::new(&STANDARD);
+            for chunk in whole.as_bytes().chunks(piece) {
+                decoder.update(std::str::from_utf8(chunk).unwrap(), &mut decoded).unwrap();
+            }
+            decoder.finish(&mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn roundtrips() {
+        property::roundtrips(|data: &Vec<u8>| encode(data, &STANDARD),
+                             |text| decode(text, &STANDARD).unwrap());
+        property::roundtrips(|data: &Vec<u8>| encode(data, &URL_SAFE),
+                             |text| decode(text, &URL_SAFE).unwrap());
+    }
+}
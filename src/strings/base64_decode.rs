@@ -0,0 +1,25 @@
+// Implements http://rosettacode.org/wiki/Base64_decode_data
+// with the Decoder base64.rs shares with the encoding task
+//
+//     base64_decode [TEXT]
+//
+// Without any text, it decodes the task's quote.
+
+#[allow(dead_code)]
+#[path = "base64.rs"]
+mod base64;
+
+#[cfg(not(test))]
+fn main() {
+    let text = std::env::args().nth(1).unwrap_or_else(|| {
+        "VG8gZXJyIGlzIGh1bWFuLCBidXQgdG8gcmVhbGx5IGZvdWwgdGhpbmdzIHVwIHlvdSBuZWVk\
+         IGEgY29tcHV0ZXIuCiAgICAtLSBQYXVsIFIuIEVocmxpY2g=".to_string()
+    });
+    match base64::decode(&text, &base64::STANDARD) {
+        Ok(data) => println!("{}", String::from_utf8_lossy(&data)),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,13 +1,13 @@
 // http://rosettacode.org/wiki/FASTA_format
 // Ported and adapted from rosettacode D example
-#![feature(old_io)]
-#![feature(old_path)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
-use std::old_io::fs::File;
-use std::old_io::BufferedReader;
+use std::io::BufRead;
 
-// We use a type parameter bound `<T: Buffer>` to accept all kinds of buffers
-fn format_fasta<T: Buffer>(reader: &mut T) -> String {
+// We use a type parameter bound `<T: BufRead>` to accept all kinds of buffers
+fn format_fasta<T: BufRead>(reader: &mut T) -> String {
     reader.lines().map(|l| l.unwrap()).fold(String::new(), |mut out, line| {
         // We need to trim new lines
         let ln = line.trim();
@@ -31,8 +31,7 @@ fn format_fasta<T: Buffer>(reader: &mut T) -> String {
 }
 
 fn read_file() -> String {
-    let file = File::open(&Path::new("src/resources/test_data.fasta"));
-    format_fasta(&mut BufferedReader::new(file))
+    format_fasta(&mut utils::fixture::open("test_data.fasta"))
 }
 
 #[cfg(not(test))]
@@ -1,14 +1,8 @@
 // http://rosettacode.org/wiki/I_before_E_except_after_C
-#![allow(unused_features)] // feature(io, path, fs) are used only in main
-#![feature(fs, io, path, unicode)]
-
-use std::char::CharExt;
-#[cfg(not(test))]
-use std::fs::File;
-#[cfg(not(test))]
-use std::io::Read;
-#[cfg(not(test))]
-use std::path::Path;
+// The words are the task's unixdict.txt, from tests/fixtures/.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
 enum MatchState { 
     Nothing,    //Nothing of interest seen so far
@@ -40,7 +34,7 @@ fn count_occurrences(data: &str) -> Occurrences {
     let mut saw_ie = false; let mut saw_ei = false;
     let mut state = MatchState::Nothing;
     for c in data.chars() {
-        state = match (state, c.to_lowercase()) {
+        state = match (state, c.to_ascii_lowercase()) {
             (_, '\n') | (_, '\r') => { 
                 if saw_cie {result.cie += 1; saw_cie = false;}
                 if saw_cei {result.cei += 1; saw_cei = false;}
@@ -65,10 +59,7 @@ fn count_occurrences(data: &str) -> Occurrences {
 
 #[cfg(not(test))]
 fn main () {
-    let path = Path::new("src/resources/unixdict.txt");
-    let mut file = File::open(&path).unwrap();
-    let mut data = String::new();
-    file.read_to_string(&mut data).unwrap();
+    let data = utils::fixture::read("unixdict.txt");
     let occ = count_occurrences(&data);
     println!("I before E when not preceded by C is {} (ie: {}, cie: {})", 
         if occ.ie > 2 * occ.cie {"plausible"} else {"implausible"}, occ.ie, occ.cie);
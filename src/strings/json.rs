@@ -0,0 +1,70 @@
+//! Implements http://rosettacode.org/wiki/JSON
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::json::decode;
+//!
+//! let contact = decode("{\"name\": \"Alan\", \"city\": \"Tokyo\"}").unwrap();
+//! assert_eq!(format!("{:?}", contact), "Contact { name: \"Alan\", city: \"Tokyo\" }");
+//! assert!(decode("{\"name\": \"Alan\"}").is_err());
+//! ```
+extern crate rustc_serialize;
+use rustc_serialize::{json, Decodable, Decoder, Encodable, Encoder};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Contact {
+    name: String,
+    city: String
+}
+
+// What #[derive(RustcEncodable, RustcDecodable)] used to write, before the compiler dropped them
+impl Encodable for Contact {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_struct("Contact", 2, |s| {
+            s.emit_struct_field("name", 0, |s| self.name.encode(s))?;
+            s.emit_struct_field("city", 1, |s| self.city.encode(s))
+        })
+    }
+}
+
+impl Decodable for Contact {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Contact, D::Error> {
+        d.read_struct("Contact", 2, |d| {
+            Ok(Contact {
+                name: d.read_struct_field("name", 0, Decodable::decode)?,
+                city: d.read_struct_field("city", 1, Decodable::decode)?,
+            })
+        })
+    }
+}
+
+// Decode a contact, or say what's wrong with the JSON
+pub fn decode(json_str: &str) -> json::DecodeResult<Contact> {
+    json::decode(json_str)
+}
+
+#[cfg(not(test))]
+fn main() {
+    // Encode contact to json
+    let c = Contact { name: "John".to_string(), city: "Paris".to_string() };
+    let json = json::encode(&c);
+    println!("Encoded: {:?}", &(json.unwrap()));
+
+    // Decode json to contact
+    let json_str = "{\"name\":\"Alan\", \"city\":\"Tokyo\"}";
+    let contact = decode(json_str).unwrap();
+    println!("Decoded: {:?}", contact);
+}
+
+#[test]
+fn test_coherence() {
+    let c = Contact { name: "John".to_string(), city: "Paris".to_string() };
+    assert!(json::decode::<Contact>(&(json::encode(&c)).unwrap()) == Ok(c));
+}
+
+#[test]
+fn test_decode() {
+    let json_str = "{\"name\":\"Alan\", \"city\":\"Tokyo\"}";
+    let contact = decode(json_str).unwrap();
+    assert!(contact == Contact { name: "Alan".to_string(), city: "Tokyo".to_string() });
+}
@@ -1,14 +1,9 @@
 // Implements http://rosettacode.org/wiki/Letter_frequency
-#![allow(unused_features)] 
-#![feature(std_misc)]
-#![feature(old_io)]
-#![feature(old_path)]
+// The letters counted are those of the task's unixdict.txt, from tests/fixtures/.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
-
-#[cfg(not(test))]
-use std::old_io::fs::File;
-#[cfg(not(test))]
-use std::old_io::BufferedReader;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 
@@ -27,16 +22,19 @@ fn count_chars<T>(chars: T) -> HashMap<char, usize>
 
 #[cfg(not(test))]
 fn main() {
-    let file = File::open(&Path::new("resources/unixdict.txt"));
-    let mut reader = BufferedReader::new(file);
-
-    println!("{:?}", count_chars(reader.chars().map(|c| c.unwrap())));
+    let text = utils::fixture::read("unixdict.txt");
+    let counts = count_chars(text.chars());
+    let mut letters: Vec<_> = counts.iter().collect();
+    letters.sort();
+    for (letter, count) in letters {
+        println!("{:?}: {}", letter, count);
+    }
 }
 
 #[test]
 fn test_empty() {
     let map = count_chars("".chars());
-    assert!(map.len() == 0);
+    assert!(map.is_empty());
 }
 
 #[test]
@@ -44,7 +42,7 @@ fn test_basic() {
     let map = count_chars("aaaabbbbc".chars());
 
     assert!(map.len() == 3);
-    assert!(map['a'] == 4);
-    assert!(map['b'] == 4);
-    assert!(map['c'] == 1);
+    assert!(map[&'a'] == 4);
+    assert!(map[&'b'] == 4);
+    assert!(map[&'c'] == 1);
 }
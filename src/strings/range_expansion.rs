@@ -1,10 +1,9 @@
 // Implements http://rosettacode.org/wiki/Range_expansion
-#![feature(plugin)]
-#![plugin(regex_macros)]
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
 
-#[macro_use]
-
-extern crate regex;
+use utils::regex::Regex;
 
 #[cfg(not(test))]
 fn main() {
@@ -14,29 +13,34 @@ fn main() {
 
 // Expand a string containing numbers and ranges, into a vector of numbers
 fn expand_range(range: &str) -> Vec<isize> {
+    let number = Regex::new(r"^-?\d+$").unwrap();
+    let pair = Regex::new(r"^(-?\d+)-(-?\d+)$").unwrap();
     let mut result = vec![];
 
     for item in range.split(',') {
-        result.extend(expand_item(item).into_iter());
+        result.extend(expand_item(item, &number, &pair));
     }
 
     result
 }
 
 // Expand a single element, which can be a number or a range.
-fn expand_item(item: &str) -> Vec<isize> {
+fn expand_item(item: &str, number: &Regex, pair: &Regex) -> Vec<isize> {
     // Handle the case of a single number
-    for cap in regex!(r"^(-?\d+)$").captures_iter(item) {
-        return vec![cap.at(0).and_then(|s| s.parse().ok()).unwrap()]
+    if number.is_match(item) {
+        return vec![item.parse().unwrap()];
     }
 
     // Handle the case of a range
-    for cap in regex!(r"^(-?\d+)-(-?\d+)$").captures_iter(item) {
-        let left: isize = cap.at(1).and_then(|s| s.parse().ok()).unwrap();
-        let right: isize = cap.at(2).and_then(|s| s.parse().ok()).unwrap();
+    if let Some(groups) = pair.captures(item) {
+        let bound = |group: Option<(usize, usize)>| -> isize {
+            let (start, end) = group.unwrap();
+            item[start..end].parse().unwrap()
+        };
+        let (left, right) = (bound(groups[1]), bound(groups[2]));
 
         // Generate and collect a range between them
-        return (left..right+1).collect()
+        return (left..right + 1).collect();
     }
 
     panic!("The item `{}` is not a number or a range!", item);
@@ -53,7 +57,7 @@ fn test_basic() {
 }
 
 #[test]
-#[should_fail]
+#[should_panic]
 fn test_wrong() {
     let range = "one-five,six";
     assert!(expand_range(range) == vec![1, 2, 3, 4, 5, 6]);
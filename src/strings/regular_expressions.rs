@@ -0,0 +1,88 @@
+// Implements http://rosettacode.org/wiki/Regular_expressions
+// Matching a string against a regular expression, and substituting part of it, with utils::regex,
+// which compiles the pattern to an automaton rather than backtracking.
+//
+//     regular_expressions [PATTERN TEXT [REPLACEMENT]]
+//
+// Without arguments it does the task's examples.  In a replacement, $1 stands for what the first
+// group matched, and so on.
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+mod utils;
+
+use utils::regex::Regex;
+
+// What matching `pattern` against `text` finds, and `text` with `replacement` for each match,
+// described for printing
+fn describe(pattern: &str, text: &str, replacement: Option<&str>) -> Result<Vec<String>, String> {
+    let regex = Regex::new(pattern).map_err(|err| format!("bad pattern {:?}: {}", pattern, err))?;
+    let mut lines = Vec::new();
+    match regex.captures(text) {
+        Some(groups) => {
+            let (start, end) = groups[0].unwrap();
+            lines.push(format!("{:?} matches {:?} at {}: {:?}", pattern, text, start,
+                               &text[start..end]));
+            for (i, group) in groups.iter().enumerate().skip(1) {
+                match *group {
+                    Some((start, end)) => lines.push(format!("  ${} = {:?}", i, &text[start..end])),
+                    None => lines.push(format!("  ${} didn't match", i)),
+                }
+            }
+        }
+        None => lines.push(format!("{:?} doesn't match {:?}", pattern, text)),
+    }
+    if let Some(replacement) = replacement {
+        lines.push(format!("replacing it with {:?} gives {:?}", replacement,
+                           regex.replace_all(text, replacement)));
+    }
+    Ok(lines)
+}
+
+#[cfg(not(test))]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let examples = [
+        ("string$", "I am a string", None),
+        ("original", "I am the original string", Some("modified")),
+        (r"(\w+)@(\w+)\.com", "write to someone@example.com", Some("$1 at $2")),
+    ];
+    let result = match args.len() {
+        0 => examples.iter()
+            .map(|&(pattern, text, replacement)| describe(pattern, text, replacement))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|lines| lines.concat()),
+        2 | 3 => describe(&args[0], &args[1], args.get(2).map(|s| s.as_str())),
+        _ => Err("usage: regular_expressions [PATTERN TEXT [REPLACEMENT]]".to_string()),
+    };
+    match result {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[test]
+fn test_describe() {
+    assert_eq!(describe("string$", "I am a string", None),
+               Ok(vec!["\"string$\" matches \"I am a string\" at 7: \"string\"".to_string()]));
+    assert_eq!(describe("original", "I am the original string", Some("modified")),
+               Ok(vec!["\"original\" matches \"I am the original string\" at 9: \"original\""
+                           .to_string(),
+                       "replacing it with \"modified\" gives \"I am the modified string\""
+                           .to_string()]));
+    assert_eq!(describe("(a)|(b)", "b", None),
+               Ok(vec!["\"(a)|(b)\" matches \"b\" at 0: \"b\"".to_string(),
+                       "  $1 didn't match".to_string(),
+                       "  $2 = \"b\"".to_string()]));
+    assert_eq!(describe("^x", "yx", Some("z")),
+               Ok(vec!["\"^x\" doesn't match \"yx\"".to_string(),
+                       "replacing it with \"z\" gives \"yx\"".to_string()]));
+    assert_eq!(describe("(x", "x", None),
+               Err("bad pattern \"(x\": unmatched '(' at 0".to_string()));
+}
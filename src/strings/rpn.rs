@@ -0,0 +1,363 @@
+//! Implements http://rosettacode.org/wiki/Parsing/RPN_calculator_algorithm
+//!
+//! Along with shunting_yard.rs (http://rosettacode.org/wiki/Parsing/Shunting-yard_algorithm) and
+//! rpn_to_infix.rs (http://rosettacode.org/wiki/Parsing/RPN_to_infix_conversion), which use this
+//! file's tokenizer and operator table, and its functions for the three of them.  Each function
+//! gives its result along with a table of the steps it took to get there, which is what the tasks
+//! ask to be shown.
+//!
+//! The operators are the tasks' + - * / and ^, which is exponentiation:
+//!
+//! ```text
+//!     operator   precedence   associativity
+//!     ^          4            right
+//!     * /        3            left
+//!     + -        2            left
+//!
+//!     rpn [EXPRESSION]
+//! ```
+//!
+//! Without an expression, it evaluates the task's one.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::rpn::{evaluate, rpn_text, to_infix, to_rpn, tokenize};
+//!
+//! let (rpn, _) = to_rpn(&tokenize("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3").unwrap()).unwrap();
+//! assert_eq!(rpn_text(&rpn), "3 4 2 * 1 5 - 2 3 ^ ^ / +");
+//! let (value, _) = evaluate(&rpn).unwrap();
+//! assert_eq!(value, 3.0001220703125);
+//! let (infix, _) = to_infix(&tokenize("3 4 2 * 1 5 - 2 3 ^ ^ / +").unwrap()).unwrap();
+//! assert_eq!(infix, "3 + 4 * 2 / ( 1 - 5 ) ^ 2 ^ 3");
+//! assert!(evaluate(&tokenize("1 +").unwrap()).is_err());
+//! ```
+
+// A token of an expression in either notation.  A number keeps its text, so that it's shown as
+// it was written.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Number(String),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Operator {
+    pub symbol: char,
+    pub precedence: u8,
+    pub right_associative: bool,
+}
+
+pub static OPERATORS: &[Operator] = &[
+    Operator { symbol: '^', precedence: 4, right_associative: true },
+    Operator { symbol: '*', precedence: 3, right_associative: false },
+    Operator { symbol: '/', precedence: 3, right_associative: false },
+    Operator { symbol: '+', precedence: 2, right_associative: false },
+    Operator { symbol: '-', precedence: 2, right_associative: false },
+];
+
+impl Operator {
+    fn apply(&self, left: f64, right: f64) -> f64 {
+        match self.symbol {
+            '^' => left.powf(right),
+            '*' => left * right,
+            '/' => left / right,
+            '+' => left + right,
+            _ => left - right,
+        }
+    }
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match *self {
+            Token::Number(ref text) => text.clone(),
+            Token::Operator(ref operator) => operator.symbol.to_string(),
+            Token::Open => "(".to_string(),
+            Token::Close => ")".to_string(),
+        }
+    }
+}
+
+// The tokens of `text`.  They don't need spaces between them.
+pub fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_whitespace() => continue,
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = i + 1;
+                while let Some(&(j, d)) = chars.peek() {
+                    if !d.is_ascii_digit() && d != '.' {
+                        break;
+                    }
+                    end = j + 1;
+                    chars.next();
+                }
+                let number = &text[i..end];
+                if number.parse::<f64>().is_err() {
+                    return Err(format!("{} at {} isn't a number", number, i));
+                }
+                Token::Number(number.to_string())
+            }
+            c => match OPERATORS.iter().find(|operator| operator.symbol == c) {
+                Some(&operator) => Token::Operator(operator),
+                None => return Err(format!("unexpected {:?} at {}", c, i)),
+            },
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+// `texts` with a space between each one and the next
+fn spaced<T: AsRef<str>>(texts: &[T]) -> String {
+    texts.iter().map(|text| text.as_ref()).collect::<Vec<_>>().join(" ")
+}
+
+// Lines of `rows` with their columns lined up under `headings`
+fn table(headings: &[&str], rows: &[Vec<String>]) -> Vec<String> {
+    let mut widths: Vec<usize> = headings.iter().map(|heading| heading.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells.iter().zip(&widths)
+            .map(|(cell, &width)| format!("{:<1$}", cell, width))
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    let mut lines = vec![line(headings.to_vec())];
+    lines.extend(rows.iter().map(|row| line(row.iter().map(|cell| cell.as_str()).collect())));
+    lines
+}
+
+// The value of the RPN expression `tokens`, and the table of the stack after each token
+pub fn evaluate(tokens: &[Token]) -> Result<(f64, Vec<String>), String> {
+    let mut stack: Vec<f64> = Vec::new();
+    let mut rows = Vec::new();
+    for token in tokens {
+        let action = match *token {
+            Token::Number(ref text) => {
+                stack.push(text.parse().unwrap());
+                "Push num onto top of stack"
+            }
+            Token::Operator(ref operator) => {
+                let (right, left) = match (stack.pop(), stack.pop()) {
+                    (Some(right), Some(left)) => (right, left),
+                    _ => return Err(format!("not enough operands for {}", operator.symbol)),
+                };
+                stack.push(operator.apply(left, right));
+                "Apply op to top of stack"
+            }
+            Token::Open | Token::Close => return Err("brackets in an RPN expression".to_string()),
+        };
+        let texts: Vec<String> = stack.iter().map(|x| x.to_string()).collect();
+        rows.push(vec![token.text(), action.to_string(), spaced(&texts)]);
+    }
+    match stack[..] {
+        [value] => Ok((value, table(&["Token", "Action", "Stack"], &rows))),
+        [] => Err("nothing to evaluate".to_string()),
+        _ => Err(format!("{} operands left over", stack.len() - 1)),
+    }
+}
+
+// The infix expression `tokens` in RPN, by Dijkstra's shunting-yard algorithm, and the table of
+// what it does with each token
+pub fn to_rpn(tokens: &[Token]) -> Result<(Vec<Token>, Vec<String>), String> {
+    let mut output: Vec<Token> = Vec::new();
+    let mut operators: Vec<Token> = Vec::new();
+    let mut rows = Vec::new();
+    {
+        let mut step = |token: String, action: &str, output: &[Token], operators: &[Token]| {
+            let output: Vec<String> = output.iter().map(Token::text).collect();
+            let operators: Vec<String> = operators.iter().map(Token::text).collect();
+            rows.push(vec![token, action.to_string(), spaced(&output), spaced(&operators)]);
+        };
+        for token in tokens {
+            let text = token.text();
+            match *token {
+                Token::Number(_) => {
+                    output.push(token.clone());
+                    step(text, "Add number to output", &output, &operators);
+                }
+                Token::Operator(ref operator) => {
+                    // Operators before it that bind tighter are done first
+                    while let Some(&Token::Operator(top)) = operators.last() {
+                        if top.precedence < operator.precedence
+                            || top.precedence == operator.precedence && operator.right_associative
+                        {
+                            break;
+                        }
+                        output.push(operators.pop().unwrap());
+                        step(text.clone(), "Pop op from stack to output", &output, &operators);
+                    }
+                    operators.push(token.clone());
+                    step(text, "Push op onto stack", &output, &operators);
+                }
+                Token::Open => {
+                    operators.push(Token::Open);
+                    step(text, "Push ( onto stack", &output, &operators);
+                }
+                Token::Close => {
+                    loop {
+                        match operators.pop() {
+                            Some(Token::Open) => break,
+                            Some(operator) => {
+                                output.push(operator);
+                                step(text.clone(), "Pop op from stack to output", &output,
+                                     &operators);
+                            }
+                            None => return Err("unmatched )".to_string()),
+                        }
+                    }
+                    step(text, "Pop ( from stack", &output, &operators);
+                }
+            }
+        }
+        while let Some(operator) = operators.pop() {
+            if operator == Token::Open {
+                return Err("unmatched (".to_string());
+            }
+            output.push(operator);
+            step(String::new(), "Pop op from stack to output", &output, &operators);
+        }
+    }
+    Ok((output, table(&["Token", "Action", "Output", "Operator stack"], &rows)))
+}
+
+// The RPN expression `tokens` as infix, with only the brackets the operators' precedence and
+// associativity need, and the table of the part-expressions on the stack after each token
+pub fn to_infix(tokens: &[Token]) -> Result<(String, Vec<String>), String> {
+    // Each part-expression, with the precedence of its outermost operator, if it has one
+    let mut stack: Vec<(String, Option<Operator>)> = Vec::new();
+    let mut rows = Vec::new();
+    for token in tokens {
+        match *token {
+            Token::Number(ref text) => stack.push((text.clone(), None)),
+            Token::Operator(operator) => {
+                let (right, left) = match (stack.pop(), stack.pop()) {
+                    (Some(right), Some(left)) => (right, left),
+                    _ => return Err(format!("not enough operands for {}", operator.symbol)),
+                };
+                // A side needs brackets if its operator binds less tightly, or as tightly but
+                // it's on the side the operator doesn't associate towards
+                let bracket = |(text, inner): (String, Option<Operator>), on_right: bool| {
+                    match inner {
+                        Some(inner) if inner.precedence < operator.precedence
+                            || inner.precedence == operator.precedence
+                                && on_right != operator.right_associative => {
+                            format!("( {} )", text)
+                        }
+                        _ => text,
+                    }
+                };
+                let text = format!("{} {} {}", bracket(left, false), operator.symbol,
+                                   bracket(right, true));
+                stack.push((text, Some(operator)));
+            }
+            Token::Open | Token::Close => return Err("brackets in an RPN expression".to_string()),
+        }
+        let parts: Vec<String> = stack.iter().map(|(text, _)| format!("[{}]", text)).collect();
+        rows.push(vec![token.text(), spaced(&parts)]);
+    }
+    match stack.len() {
+        1 => Ok((stack.pop().unwrap().0, table(&["Token", "Stack"], &rows))),
+        0 => Err("nothing to convert".to_string()),
+        n => Err(format!("{} operands left over", n - 1)),
+    }
+}
+
+// The RPN expression `tokens` as text
+pub fn rpn_text(tokens: &[Token]) -> String {
+    spaced(&tokens.iter().map(Token::text).collect::<Vec<_>>())
+}
+
+// The expressions the tasks use
+pub static RPN_EXAMPLE: &str = "3 4 2 * 1 5 - 2 3 ^ ^ / +";
+pub static INFIX_EXAMPLE: &str = "3 + 4 * 2 / ( 1 - 5 ) ^ 2 ^ 3";
+
+#[cfg(not(test))]
+fn main() {
+    let expression = std::env::args().nth(1).unwrap_or_else(|| RPN_EXAMPLE.to_string());
+    match tokenize(&expression).and_then(|tokens| evaluate(&tokens)) {
+        Ok((value, steps)) => {
+            for line in steps {
+                println!("{}", line);
+            }
+            println!("{} = {}", expression, value);
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{evaluate, rpn_text, to_infix, to_rpn, tokenize, Token, INFIX_EXAMPLE,
+                RPN_EXAMPLE};
+
+    fn tokens(text: &str) -> Vec<Token> {
+        tokenize(text).unwrap()
+    }
+
+    #[test]
+    fn tokenizing() {
+        assert_eq!(rpn_text(&tokens("(1.5+22)*3^4")), "( 1.5 + 22 ) * 3 ^ 4");
+        assert_eq!(tokenize("1 % 2"), Err("unexpected '%' at 2".to_string()));
+        assert_eq!(tokenize("1.2.3"), Err("1.2.3 at 0 isn't a number".to_string()));
+    }
+
+    #[test]
+    fn evaluating() {
+        let (value, steps) = evaluate(&tokens(RPN_EXAMPLE)).unwrap();
+        assert_eq!(value, 3.0001220703125);
+        assert_eq!(steps[..4], ["Token  Action                      Stack",
+                                "3      Push num onto top of stack  3",
+                                "4      Push num onto top of stack  3 4",
+                                "2      Push num onto top of stack  3 4 2"]);
+        assert_eq!(steps.last().unwrap(), "+      Apply op to top of stack    3.0001220703125");
+        assert_eq!(steps.len(), 14);
+        assert_eq!(evaluate(&tokens("1 +")).unwrap_err(), "not enough operands for +");
+        assert_eq!(evaluate(&tokens("1 2")).unwrap_err(), "1 operands left over");
+    }
+
+    #[test]
+    fn shunting_yard() {
+        let (rpn, steps) = to_rpn(&tokens(INFIX_EXAMPLE)).unwrap();
+        assert_eq!(rpn_text(&rpn), RPN_EXAMPLE);
+        assert_eq!(steps[0], format!("Token  Action{0:23}Output{0:21}Operator stack", ""));
+        assert_eq!(steps[1], "3      Add number to output         3");
+        assert_eq!(steps[4], format!("*      Push op onto stack           3 4{:24}+ *", ""));
+        assert_eq!(steps.last().unwrap(),
+                   "       Pop op from stack to output  3 4 2 * 1 5 - 2 3 ^ ^ / +");
+        assert_eq!(rpn_text(&to_rpn(&tokens("1 - 2 - 3")).unwrap().0), "1 2 - 3 -");
+        assert_eq!(rpn_text(&to_rpn(&tokens("2 ^ 3 ^ 2")).unwrap().0), "2 3 2 ^ ^");
+        assert_eq!(to_rpn(&tokens("(1 + 2")).unwrap_err(), "unmatched (");
+        assert_eq!(to_rpn(&tokens("1 + 2)")).unwrap_err(), "unmatched )");
+    }
+
+    #[test]
+    fn rpn_to_infix() {
+        let (infix, steps) = to_infix(&tokens(RPN_EXAMPLE)).unwrap();
+        assert_eq!(infix, INFIX_EXAMPLE);
+        assert_eq!(steps[4], "*      [3] [4 * 2]");
+        let (infix, _) = to_infix(&tokens("1 2 + 3 4 + ^ 5 6 + ^")).unwrap();
+        assert_eq!(infix, "( ( 1 + 2 ) ^ ( 3 + 4 ) ) ^ ( 5 + 6 )");
+        // Only the brackets associativity needs
+        assert_eq!(to_infix(&tokens("1 2 3 - -")).unwrap().0, "1 - ( 2 - 3 )");
+        assert_eq!(to_infix(&tokens("1 2 - 3 -")).unwrap().0, "1 - 2 - 3");
+        assert_eq!(to_infix(&tokens("2 3 ^ 4 ^")).unwrap().0, "( 2 ^ 3 ) ^ 4");
+        // And converting back and forth gets the same expression
+        let (rpn, _) = to_rpn(&tokens(&infix)).unwrap();
+        assert_eq!(to_infix(&rpn).unwrap().0, infix);
+    }
+}
@@ -0,0 +1,32 @@
+// Implements http://rosettacode.org/wiki/Parsing/RPN_to_infix_conversion
+// with the tokenizer and operator table rpn.rs shares with shunting_yard.rs
+//
+//     rpn_to_infix [EXPRESSION]...
+//
+// Without an expression, it converts the task's two.
+
+#[allow(dead_code)]
+#[path = "rpn.rs"]
+mod rpn;
+
+#[cfg(not(test))]
+fn main() {
+    let mut expressions: Vec<String> = std::env::args().skip(1).collect();
+    if expressions.is_empty() {
+        expressions = vec![rpn::RPN_EXAMPLE.to_string(), "1 2 + 3 4 + ^ 5 6 + ^".to_string()];
+    }
+    for expression in expressions {
+        match rpn::tokenize(&expression).and_then(|tokens| rpn::to_infix(&tokens)) {
+            Ok((infix, steps)) => {
+                for line in steps {
+                    println!("{}", line);
+                }
+                println!("{} => {}\n", expression, infix);
+            }
+            Err(message) => {
+                eprintln!("{}: {}", expression, message);
+                std::process::exit(1);
+            }
+        }
+    }
+}
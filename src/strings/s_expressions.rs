@@ -0,0 +1,296 @@
+//! Implements http://rosettacode.org/wiki/S-Expressions
+//!
+//! S-expressions are read into a Value with utils::parse, and written back out either on one line,
+//! with Display, or pretty-printed to fit in a width, with lists that don't fit broken over several
+//! lines.  Either way, what's written reads back in as the same value.
+//!
+//! A value is one of:
+//!
+//! ```text
+//!     a list            (data "quoted data" 123 4.5)
+//!     a string          "quoted data", with \" \\ \n and \t escapes
+//!     an integer        123, which has to fit in an i64
+//!     a float           4.5, -1e10 or .5, anything numeric that isn't an integer
+//!     an atom           data or !@#, a word of any other characters but brackets, quotes and
+//!                       whitespace
+//! ```
+//!
+//! and 'x is read as (quote x).  Being meant for text from anywhere, reading never panics: a
+//! mistake is an error that says where it is, and lists can only be nested MAX_DEPTH deep, so that
+//! neither reading a value nor dropping it runs out of stack.
+//!
+//! ```
+//! # extern crate tasks;
+//! use tasks::s_expressions::{read, Value};
+//!
+//! let value = read("(data \"quoted data\" 123 4.5)").unwrap();
+//! assert_eq!(value, Value::List(vec![Value::Atom("data".to_string()),
+//!                                    Value::String("quoted data".to_string()),
+//!                                    Value::Int(123),
+//!                                    Value::Float(4.5)]));
+//! assert_eq!(read(&value.to_string()), Ok(value));
+//! assert!(read("(data").is_err());
+//! ```
+
+// tiny_lisp.rs uses it from here, since it can't be declared twice
+#[allow(dead_code)]
+#[path = "../utils/mod.rs"]
+pub mod utils;
+
+use std::fmt;
+
+use self::utils::parse::{self, char, delimited, many, map, or, preceded, satisfy, spaces,
+                         take_while1, terminated, token, try_map};
+
+// How deeply lists can be nested, counting (quote x) as a list
+pub const MAX_DEPTH: usize = 256;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Atom(String),
+    String(String),
+    Int(i64),
+    Float(f64),
+    List(Vec<Value>),
+}
+
+// The value for a word, which is a number if it looks like one
+fn word(text: &str) -> Value {
+    if let Ok(n) = text.parse() {
+        return Value::Int(n);
+    }
+    // Rust would read "inf" and "NaN" as floats too, so they have to start like a number, and
+    // ones too big to be anything but infinite are atoms, which they'd be written back out as
+    let digits = text.trim_start_matches(&['-', '+'][..]).trim_start_matches('.');
+    if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        match text.parse::<f64>() {
+            Ok(x) if x.is_finite() => return Value::Float(x),
+            _ => {}
+        }
+    }
+    Value::Atom(text.to_string())
+}
+
+fn is_word(c: char) -> bool {
+    !c.is_whitespace() && !"()\"'".contains(c)
+}
+
+// The parser for the value at the start of `input`, inside `depth` lists
+fn value<'a>(input: &'a str, depth: usize) -> parse::Result<'a, Value> {
+    let words = token(map(take_while1("an atom", is_word), word));
+    let list = map(preceded(token(char('(')), |input| inside(input, depth)), Value::List);
+    let quoted = map(preceded(token(char('\'')), |input| quote(input, depth)), |value| {
+        Value::List(vec![Value::Atom("quote".to_string()), value])
+    });
+    or(or(or(list, string), quoted), words)(input)
+}
+
+// What's in a list, after its opening bracket
+fn inside<'a>(input: &'a str, depth: usize) -> parse::Result<'a, Vec<Value>> {
+    if depth == MAX_DEPTH {
+        return Err(parse::Error::new(input, "lists nested less deeply"));
+    }
+    terminated(many(|input| value(input, depth + 1)), token(char(')')))(input)
+}
+
+// What's quoted, after the quote
+fn quote<'a>(input: &'a str, depth: usize) -> parse::Result<'a, Value> {
+    if depth == MAX_DEPTH {
+        return Err(parse::Error::new(input, "quotes nested less deeply"));
+    }
+    value(input, depth + 1)
+}
+
+fn string<'a>(input: &'a str) -> parse::Result<'a, Value> {
+    let plain = satisfy("a character", |c| c != '"' && c != '\\');
+    let escaped = preceded(char('\\'), try_map(satisfy("an escape", |_| true), "an escape", |c| {
+        match c {
+            '"' | '\\' => Some(c),
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            _ => None,
+        }
+    }));
+    let characters = delimited(char('"'), many(or(plain, escaped)), token(char('"')));
+    map(characters, |characters| Value::String(characters.into_iter().collect()))(input)
+}
+
+// Read the one value in `text`, or say where it's wrong
+pub fn read(text: &str) -> Result<Value, String> {
+    parse::parse(preceded(spaces, |input| value(input, 0)), text)
+        .map_err(|error| error.describe(text))
+}
+
+// Read all the values in `text`, one after another, which may be none
+pub fn read_all(text: &str) -> Result<Vec<Value>, String> {
+    parse::parse(preceded(spaces, many(|input| value(input, 0))), text)
+        .map_err(|error| error.describe(text))
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Atom(ref name) => write!(f, "{}", name),
+            Value::String(ref text) => {
+                write!(f, "\"")?;
+                for c in text.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Value::Int(n) => write!(f, "{}", n),
+            // Debug always has a point or an exponent, so that 123.0 doesn't come back an integer
+            Value::Float(x) => write!(f, "{:?}", x),
+            Value::List(ref items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl Value {
+    // The value written out so that no line is wider than `width`, where it can be.  A list that
+    // doesn't fit on the rest of its line has its first item after the bracket, and each item
+    // after that on a line of its own, lined up under the first.
+    pub fn pretty(&self, width: usize) -> String {
+        let mut text = String::new();
+        self.write_pretty(&mut text, 0, width);
+        text
+    }
+
+    fn write_pretty(&self, text: &mut String, column: usize, width: usize) {
+        let flat = self.to_string();
+        let items = match *self {
+            Value::List(ref items) if !items.is_empty() && column + flat.len() > width => items,
+            _ => return text.push_str(&flat),
+        };
+        text.push('(');
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                text.push('\n');
+                text.push_str(&" ".repeat(column + 1));
+            }
+            item.write_pretty(text, column + 1, width);
+        }
+        text.push(')');
+    }
+}
+
+// The task's data
+pub const SAMPLE: &str = "((data \"quoted data\" 123 4.5)
+ (data (!@# (4.5) \"(more\" \"data)\")))";
+
+#[cfg(not(test))]
+fn main() {
+    let value = read(SAMPLE).unwrap();
+    println!("{:?}", value);
+    println!("{}", value);
+    println!("{}", value.pretty(40));
+}
+
+#[cfg(test)]
+mod test {
+    use super::utils::rng::Rng;
+    use super::{read, read_all, Value, MAX_DEPTH, SAMPLE};
+
+    fn atom(name: &str) -> Value {
+        Value::Atom(name.to_string())
+    }
+
+    fn string(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    #[test]
+    fn sample() {
+        let value = read(SAMPLE).unwrap();
+        assert_eq!(value,
+                   Value::List(vec![
+                       Value::List(vec![atom("data"), string("quoted data"), Value::Int(123),
+                                        Value::Float(4.5)]),
+                       Value::List(vec![atom("data"),
+                                        Value::List(vec![atom("!@#"),
+                                                         Value::List(vec![Value::Float(4.5)]),
+                                                         string("(more"), string("data)")])]),
+                   ]));
+        assert_eq!(value.to_string(),
+                   "((data \"quoted data\" 123 4.5) (data (!@# (4.5) \"(more\" \"data)\")))");
+        assert_eq!(value.pretty(40), SAMPLE);
+        assert_eq!(read(&value.to_string()), Ok(value));
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(read(" -17 "), Ok(Value::Int(-17)));
+        assert_eq!(read("123.0"), Ok(Value::Float(123.0)));
+        assert_eq!(read("-.5e3"), Ok(Value::Float(-500.0)));
+        assert_eq!(read("inf"), Ok(atom("inf")));
+        assert_eq!(read("1e999"), Ok(atom("1e999")));
+        assert_eq!(read("1.2.3"), Ok(atom("1.2.3")));
+        assert_eq!(read("\"a \\\"b\\\"\\n\\\\\""), Ok(string("a \"b\"\n\\")));
+        assert_eq!(read("'(a 'b)"),
+                   Ok(Value::List(vec![atom("quote"),
+                                       Value::List(vec![atom("a"),
+                                                        Value::List(vec![atom("quote"),
+                                                                         atom("b")])])])));
+        assert_eq!(read("()"), Ok(Value::List(vec![])));
+        assert_eq!(read_all(" 1 (a) \"b\"c "),
+                   Ok(vec![Value::Int(1), Value::List(vec![atom("a")]), string("b"), atom("c")]));
+        assert_eq!(read_all(""), Ok(vec![]));
+        assert_eq!(Value::Float(123.0).to_string(), "123.0");
+        assert_eq!(string("tab\tquote\"").to_string(), "\"tab\\tquote\\\"\"");
+    }
+
+    #[test]
+    fn pretty() {
+        let value = read("(define (square x) (* x x))").unwrap();
+        assert_eq!(value.pretty(80), "(define (square x) (* x x))");
+        assert_eq!(value.pretty(20), "(define\n (square x)\n (* x x))");
+        assert_eq!(value.pretty(0), "(define\n (square\n  x)\n (*\n  x\n  x))");
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| read(text).unwrap_err();
+        assert_eq!(error("(a b"), "line 1, column 5: expected ')'");
+        assert_eq!(error("(a\n \"b)"), "line 2, column 5: expected '\"'");
+        assert_eq!(error("\"\\q\""), "line 1, column 3: expected an escape");
+        assert_eq!(error("a)"), "line 1, column 2: expected the end of the text");
+        assert_eq!(error(""), "line 1, column 1: expected '(', '\"', '\\'' or an atom");
+        assert_eq!(error("'"), "line 1, column 2: expected '(', '\"', '\\'' or an atom");
+        let deep = "(".repeat(MAX_DEPTH + 1) + &")".repeat(MAX_DEPTH + 1);
+        assert_eq!(error(&deep),
+                   format!("line 1, column {}: expected lists nested less deeply",
+                           MAX_DEPTH + 2));
+        assert!(read(&deep[1..deep.len() - 1]).is_ok());
+    }
+
+    #[test]
+    fn fuzz() {
+        // Texts made of bits of S-expressions never make reading panic, and whatever reads
+        // without an error writes out as something that reads back as the same value
+        let pieces = ["(", ")", " ", "\n", "'", "\"", "\\", "a", "-", "1", ".", "e", "x\"y"];
+        let mut rng = Rng::new(69);
+        for _ in 0..5000 {
+            let length = rng.gen_range(0, 20);
+            let text: String = (0..length).map(|_| *rng.choose(&pieces).unwrap()).collect();
+            if let Ok(value) = read(&text) {
+                assert_eq!(read(&value.to_string()).as_ref(), Ok(&value), "{:?}", text);
+                assert_eq!(read(&value.pretty(10)).as_ref(), Ok(&value), "{:?}", text);
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+// Implements http://rosettacode.org/wiki/Parsing/Shunting-yard_algorithm
+// with the tokenizer and operator table rpn.rs shares with rpn_to_infix.rs
+//
+//     shunting_yard [EXPRESSION]
+//
+// Without an expression, it converts the task's one.
+
+#[allow(dead_code)]
+#[path = "rpn.rs"]
+mod rpn;
+
+#[cfg(not(test))]
+fn main() {
+    let expression = std::env::args().nth(1).unwrap_or_else(|| rpn::INFIX_EXAMPLE.to_string());
+    match rpn::tokenize(&expression).and_then(|tokens| rpn::to_rpn(&tokens)) {
+        Ok((tokens, steps)) => {
+            for line in steps {
+                println!("{}", line);
+            }
+            println!("{} => {}", expression, rpn::rpn_text(&tokens));
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    }
+}
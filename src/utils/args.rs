@@ -0,0 +1,163 @@
+//! Command line arguments, parsed the same way by every task that takes any:
+//!
+//! ```
+//! # extern crate utils;
+//! # use utils::args::Args;
+//! # fn main() -> Result<(), String> {
+//! # let args = ["--size=12", "maze.txt", "--animate"].iter().map(|arg| arg.to_string());
+//! let mut args = Args::new(args); // Args::from_env() in a task
+//! let animate = args.flag("--animate");
+//! let size: usize = args.value("--size")?.unwrap_or(10);
+//! let files: Vec<String> = args.free()?;
+//! # assert_eq!((animate, size, files), (true, 12, vec!["maze.txt".to_string()]));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Options can come anywhere among the other arguments, as `--size 10` or `--size=10`, and
+//! everything after a `--` is a free argument, whatever it looks like.  Ask for the flags and
+//! options before the free arguments: free() fails on anything left that looks like an option, so
+//! that a misspelt option is reported rather than taken for a file name.  Negative numbers don't
+//! look like options.
+//!
+//! Errors are messages for the user, which main() usually hands to exit_with_usage().
+
+use super::alloc::string::String;
+use super::alloc::vec::Vec;
+use super::core::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Args {
+    args: Vec<String>, // Those before any `--` that haven't been asked for yet
+    rest: Vec<String>, // Those after it
+}
+
+impl Args {
+    // The arguments to parse, without the name of the program
+    pub fn new<I: IntoIterator<Item = String>>(args: I) -> Args {
+        let mut args: Vec<String> = args.into_iter().collect();
+        let rest = match args.iter().position(|arg| arg == "--") {
+            Some(i) => args.split_off(i).into_iter().skip(1).collect(),
+            None => Vec::new(),
+        };
+        Args { args, rest }
+    }
+
+    // The arguments the program was run with
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Args {
+        Args::new(std::env::args().skip(1))
+    }
+
+    // Whether the flag `name` was given, such as "-v" or "--verbose"
+    pub fn flag(&mut self, name: &str) -> bool {
+        let before = self.args.len();
+        self.args.retain(|arg| arg != name);
+        self.args.len() < before
+    }
+
+    // The value of the option `name`, if it was given.  If it was given more than once, the last
+    // value counts.
+    pub fn value<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        let mut found = None;
+        let mut i = 0;
+        while i < self.args.len() {
+            if self.args[i] == name {
+                if i + 1 == self.args.len() {
+                    return Err(format!("missing value for {}", name));
+                }
+                found = Some(self.args.remove(i + 1));
+                self.args.remove(i);
+            } else if self.args[i].strip_prefix(name).is_some_and(|rest| rest.starts_with('=')) {
+                found = Some(String::from(&self.args.remove(i)[name.len() + 1..]));
+            } else {
+                i += 1;
+            }
+        }
+        match found {
+            Some(value) => match value.parse() {
+                Ok(parsed) => Ok(Some(parsed)),
+                Err(_) => Err(format!("invalid value for {}: {}", name, value)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    // The arguments that aren't flags or options, in order, once those have been asked for
+    pub fn free<T: FromStr>(self) -> Result<Vec<T>, String> {
+        if let Some(option) = self.args.iter().find(|arg| is_option(arg)) {
+            return Err(format!("unknown option {}", option));
+        }
+        self.args.into_iter().chain(self.rest).map(|arg| match arg.parse() {
+            Ok(parsed) => Ok(parsed),
+            Err(_) => Err(format!("invalid argument: {}", arg)),
+        }).collect()
+    }
+
+    // Fail if there are any arguments left, for programs that take no free arguments
+    pub fn finish(self) -> Result<(), String> {
+        match self.free::<String>()?.first() {
+            Some(arg) => Err(format!("unexpected argument: {}", arg)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn is_option(arg: &str) -> bool {
+    arg.len() > 1 && arg.starts_with('-') && arg.parse::<f64>().is_err()
+}
+
+// Print what was wrong with the arguments, and how to run the program, and exit
+#[cfg(feature = "std")]
+pub fn exit_with_usage(message: &str, usage: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: {}", usage);
+    std::process::exit(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Args;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn flags() {
+        let mut parsed = args(&["-v", "file", "--all", "-v"]);
+        assert!(parsed.flag("-v"));
+        assert!(parsed.flag("--all"));
+        assert!(!parsed.flag("--quiet"));
+        assert_eq!(parsed.free(), Ok(vec!["file".to_string()]));
+    }
+
+    #[test]
+    fn values() {
+        let mut parsed = args(&["--size", "10", "x", "--seed=-3", "--size=20"]);
+        assert_eq!(parsed.value("--size"), Ok(Some(20u32)));
+        assert_eq!(parsed.value("--seed"), Ok(Some(-3i64)));
+        assert_eq!(parsed.value::<u32>("--width"), Ok(None));
+        assert_eq!(parsed.free(), Ok(vec!["x".to_string()]));
+        assert_eq!(args(&["--size"]).value::<u32>("--size"),
+                   Err("missing value for --size".to_string()));
+        assert_eq!(args(&["--size", "big"]).value::<u32>("--size"),
+                   Err("invalid value for --size: big".to_string()));
+        // Not an option that --size is a prefix of
+        let mut parsed = args(&["--sizes=3"]);
+        assert_eq!(parsed.value::<u32>("--size"), Ok(None));
+        assert_eq!(parsed.free::<String>(), Err("unknown option --sizes=3".to_string()));
+    }
+
+    #[test]
+    fn free() {
+        assert_eq!(args(&["1.5", "-2"]).free(), Ok(vec![1.5, -2.0]));
+        assert_eq!(args(&["-", "--", "--size"]).free(),
+                   Ok(vec!["-".to_string(), "--size".to_string()]));
+        assert_eq!(args(&["--verbose"]).free::<String>(),
+                   Err("unknown option --verbose".to_string()));
+        assert_eq!(args(&["x"]).free::<u8>(), Err("invalid argument: x".to_string()));
+        assert_eq!(args(&[]).finish(), Ok(()));
+        assert_eq!(args(&["x"]).finish(), Err("unexpected argument: x".to_string()));
+    }
+}
@@ -0,0 +1,718 @@
+//! Arbitrary-precision integers, written from scratch rather than taken from the num crate, so
+//! that they double as a solution to
+//! http://rosettacode.org/wiki/Arbitrary-precision_integers_(included) (see
+//! arbitrary_precision_integers.rs).
+//!
+//! BigUint holds a natural number as 32 bit digits ("limbs"), least significant first.  BigInt
+//! adds a sign to one.  Multiplication is the schoolbook method and division is Knuth's
+//! algorithm D (The Art of Computer Programming, volume 2, section 4.3.1), which are quadratic
+//! but fast enough for numbers with tens of thousands of digits.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::bignum::BigUint;
+//!
+//! let n: BigUint = "340282366920938463463374607431768211456".parse().unwrap();
+//! assert_eq!(n, BigUint::from(2u32).pow(128));
+//! assert_eq!((&n - &BigUint::one()).to_string(), "340282366920938463463374607431768211455");
+//! let (quotient, remainder) = n.div_rem(&BigUint::from(1_000_000u32));
+//! assert_eq!((quotient.to_string(), remainder.to_string()),
+//!            ("340282366920938463463374607431768".to_string(), "211456".to_string()));
+//! ```
+
+use super::alloc::string::ToString;
+use super::alloc::vec::Vec;
+use super::core::cmp::Ordering;
+use super::core::fmt;
+use super::core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use super::core::str::{self, FromStr};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+// Largest power of ten that fits in a limb, for converting to and from decimal
+static DECIMAL_BASE: u32 = 1_000_000_000;
+static DECIMAL_DIGITS: usize = 9;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>, // Least significant first, never ending in a zero
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool, // Never set for zero
+    magnitude: BigUint,
+}
+
+// The error from parsing a string that isn't a decimal number
+#[derive(Debug, PartialEq)]
+pub struct ParseBigIntError;
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid decimal number")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseBigIntError {}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn one() -> BigUint {
+        BigUint { limbs: vec![1] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // Return the number of bits needed to write the number, which is 0 for zero
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    // Raise the number to the power `exp`, by repeated squaring.
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // Whether bit `i` is set, counting from 0 for the least significant
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs.get(i / 32).is_some_and(|&limb| limb >> (i % 32) & 1 == 1)
+    }
+
+    // The number to the power `exp`, modulo `modulus`, by repeated squaring from the exponent's
+    // top bit down, which keeps every product below modulus^2.  Panics if `modulus` is 0.
+    pub fn pow_mod(&self, exp: &BigUint, modulus: &BigUint) -> BigUint {
+        assert!(!modulus.is_zero(), "division by zero");
+        let base = self % modulus;
+        let mut result = &BigUint::one() % modulus;
+        for i in (0..exp.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exp.bit(i) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        result
+    }
+
+    // The number whose digits in base 256 are `bytes`, most significant first
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let limbs = bytes.rchunks(4)
+            .map(|chunk| chunk.iter().fold(0, |limb, &byte| limb << 8 | byte as u32))
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+
+    // The number's digits in base 256, most significant first, which are none for zero
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.limbs.iter().rev().flat_map(|limb| limb.to_be_bytes())
+            .collect();
+        let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+        bytes.drain(..zeros);
+        bytes
+    }
+
+    // Divide by `divisor`, returning the quotient and the remainder.  Panics if `divisor` is 0.
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "division by zero");
+        if *self < *divisor {
+            return (BigUint::zero(), self.clone());
+        }
+        if divisor.limbs.len() == 1 {
+            let (quotient, remainder) = self.div_rem_limb(divisor.limbs[0]);
+            return (quotient, BigUint::from(remainder));
+        }
+        let (quotient, remainder) = divide(&self.limbs, &divisor.limbs);
+        (BigUint::from_limbs(quotient), BigUint::from_limbs(remainder))
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    fn div_rem_limb(&self, divisor: u32) -> (BigUint, u32) {
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder = 0u64;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let n = (remainder << 32) | limb as u64;
+            quotient[i] = (n / divisor as u64) as u32;
+            remainder = n % divisor as u64;
+        }
+        (BigUint::from_limbs(quotient), remainder as u32)
+    }
+
+    // Multiply by `factor` and add `addend`, in place.
+    fn mul_add_limb(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in &mut self.limbs {
+            let n = *limb as u64 * factor as u64 + carry;
+            *limb = n as u32;
+            carry = n >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+// Divide `u` by `v` (at least two limbs long, with `u` at least as long) with algorithm D.
+fn divide(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    // Shift both so the top bit of the divisor is set, which keeps the estimates of each
+    // quotient digit within 2 of the real thing
+    let shift = v.last().unwrap().leading_zeros();
+    let v = shift_left(v, shift);
+    let mut u = shift_left(u, shift);
+    u.push(0);
+    let n = v.len();
+    let m = u.len() - n - 1;
+    let mut quotient = vec![0; m + 1];
+    let (top, next) = (v[n - 1] as u64, v[n - 2] as u64);
+    for j in (0..m + 1).rev() {
+        // Estimate the digit from the top two limbs of what's left, and correct it from the next
+        let n2 = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+        let mut estimate = n2 / top;
+        let mut rest = n2 % top;
+        while estimate >> 32 != 0 || estimate * next > ((rest << 32) | u[j + n - 2] as u64) {
+            estimate -= 1;
+            rest += top;
+            if rest >> 32 != 0 {
+                break;
+            }
+        }
+        // Subtract estimate * v from the current window of u
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = estimate * v[i] as u64 + carry;
+            carry = product >> 32;
+            let t = u[i + j] as i64 - borrow - (product & 0xffff_ffff) as i64;
+            u[i + j] = t as u32;
+            borrow = (t < 0) as i64;
+        }
+        let t = u[j + n] as i64 - borrow - carry as i64;
+        u[j + n] = t as u32;
+        // Rarely, the estimate is still one too many, and v has to be added back
+        if t < 0 {
+            estimate -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = estimate as u32;
+    }
+    u.truncate(n);
+    (quotient, shift_right(&u, shift))
+}
+
+// `limbs` shifted left by fewer than 32 bits, one limb longer if the top bits don't fit
+fn shift_left(limbs: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+    let mut shifted = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        shifted.push((limb << shift) | carry);
+        carry = limb >> (32 - shift);
+    }
+    if carry != 0 {
+        shifted.push(carry);
+    }
+    shifted
+}
+
+fn shift_right(limbs: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+    let mut shifted = vec![0; limbs.len()];
+    for i in 0..limbs.len() {
+        let high = limbs.get(i + 1).map_or(0, |&limb| limb << (32 - shift));
+        shifted[i] = (limbs[i] >> shift) | high;
+    }
+    shifted
+}
+
+impl From<u32> for BigUint {
+    fn from(n: u32) -> BigUint {
+        BigUint::from(n as u64)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> BigUint {
+        BigUint::from_limbs(vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        // Without leading zeros, the longer number is the larger
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut sum = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0u64;
+        for (i, &limb) in long.iter().enumerate() {
+            let n = limb as u64 + short.get(i).map_or(0, |&limb| limb as u64) + carry;
+            sum.push(n as u32);
+            carry = n >> 32;
+        }
+        if carry > 0 {
+            sum.push(carry as u32);
+        }
+        BigUint { limbs: sum }
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    // Panics if `other` is larger, since the difference isn't a natural number.
+    fn sub(self, other: &BigUint) -> BigUint {
+        assert!(*self >= *other, "subtraction overflow");
+        let mut difference = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let n = limb as i64 - other.limbs.get(i).map_or(0, |&limb| limb as i64) - borrow;
+            difference.push(n as u32);
+            borrow = (n < 0) as i64;
+        }
+        BigUint::from_limbs(difference)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut product = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                // Can't overflow: (2^32 - 1)^2 + 2 * (2^32 - 1) < 2^64
+                let n = a as u64 * b as u64 + product[i + j] as u64 + carry;
+                product[i + j] = n as u32;
+                carry = n >> 32;
+            }
+            product[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(product)
+    }
+}
+
+impl Div for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Split off nine decimal digits at a time
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_limb(DECIMAL_BASE);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+        let mut digits = match chunks.pop() {
+            Some(top) => top.to_string(),
+            None => "0".to_string(),
+        };
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<BigUint, ParseBigIntError> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+        let mut n = BigUint::zero();
+        for chunk in s.as_bytes().chunks(DECIMAL_DIGITS) {
+            // Only digits, so the chunk is valid UTF-8 and a valid u32
+            let value = str::from_utf8(chunk).unwrap().parse().unwrap();
+            n.mul_add_limb(10u32.pow(chunk.len() as u32), value);
+        }
+        Ok(BigUint::from_limbs(n.limbs))
+    }
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn one() -> BigInt {
+        BigInt::from(BigUint::one())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // Return the absolute value
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    pub fn pow(&self, exp: u32) -> BigInt {
+        BigInt::new(self.negative && exp % 2 == 1, self.magnitude.pow(exp))
+    }
+
+    // Divide by `divisor`, rounding toward zero like the primitive integers do, so that the
+    // remainder has the same sign as `self`.  Panics if `divisor` is 0.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        let (quotient, remainder) = self.magnitude.div_rem(&divisor.magnitude);
+        (BigInt::new(self.negative != divisor.negative, quotient),
+         BigInt::new(self.negative, remainder))
+    }
+
+    fn new(negative: bool, magnitude: BigUint) -> BigInt {
+        BigInt { negative: negative && !magnitude.is_zero(), magnitude }
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(n: BigUint) -> BigInt {
+        BigInt::new(false, n)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> BigInt {
+        BigInt::new(n < 0, BigUint::from(n.unsigned_abs()))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.magnitude)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, &self.magnitude + &other.magnitude);
+        }
+        // Opposite signs: the larger magnitude wins
+        if self.magnitude >= other.magnitude {
+            BigInt::new(self.negative, &self.magnitude - &other.magnitude)
+        } else {
+            BigInt::new(other.negative, &other.magnitude - &self.magnitude)
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(self.negative != other.negative, &self.magnitude * &other.magnitude)
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negative, "", &self.magnitude.to_string())
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<BigInt, ParseBigIntError> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(&b'-') => (true, &s[1..]),
+            Some(&b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        Ok(BigInt::new(negative, digits.parse()?))
+    }
+}
+
+// The operators on values rather than references, in terms of the ones above
+macro_rules! by_value {
+    ($t:ident: $($trait_:ident $method:ident),*) => ($(
+        impl $trait_ for $t {
+            type Output = $t;
+
+            fn $method(self, other: $t) -> $t {
+                (&self).$method(&other)
+            }
+        }
+    )*)
+}
+
+by_value!(BigUint: Add add, Sub sub, Mul mul, Div div, Rem rem);
+by_value!(BigInt: Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+#[cfg(test)]
+mod test {
+    use super::super::rng::Rng;
+    use super::{BigInt, BigUint, ParseBigIntError};
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    fn int(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn decimal() {
+        for s in ["0", "7", "4294967296", "1000000000", "999999999999999999",
+                  "123456789012345678901234567890123456789"].iter() {
+            assert_eq!(big(s).to_string(), *s);
+        }
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(format!("{:>6}", big("42")), "    42");
+        assert_eq!(int("-42").to_string(), "-42");
+        assert_eq!(int("+42").to_string(), "42");
+        assert_eq!(int("-0").to_string(), "0");
+        for s in ["", "-", "12a", " 1", "1.5"].iter() {
+            assert_eq!(s.parse::<BigInt>(), Err(ParseBigIntError));
+        }
+        assert_eq!("-1".parse::<BigUint>(), Err(ParseBigIntError));
+    }
+
+    // Check the operations against u128 arithmetic, on numbers of up to 64 bits
+    #[test]
+    fn matches_u128() {
+        let mut rng = Rng::new(28);
+        for _ in 0..2000 {
+            let bits = rng.gen_range(1, 65);
+            let a = rng.next_u64() >> (64 - bits);
+            let b = rng.next_u64() >> rng.gen_range(0, 64);
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            let (a, b) = (a as u128, b as u128);
+            assert_eq!((&x + &y).to_string(), (a + b).to_string());
+            assert_eq!((&x * &y).to_string(), (a * b).to_string());
+            if a >= b {
+                assert_eq!((&x - &y).to_string(), (a - b).to_string());
+            }
+            if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                assert_eq!((&x / &y).to_string(), quotient.to_string());
+                assert_eq!((&x % &y).to_string(), remainder.to_string());
+            }
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+        }
+    }
+
+    // Division of long numbers, checked by multiplying back
+    #[test]
+    fn long_division() {
+        let mut rng = Rng::new(2);
+        for _ in 0..300 {
+            let (m, n) = (rng.gen_range(1, 40), rng.gen_range(1, 20));
+            let u = BigUint::from_limbs((0..m).map(|_| rng.next_u32()).collect());
+            // Divisors with small top limbs, which need the most correcting in divide()
+            let mut limbs: Vec<u32> = (0..n).map(|_| rng.next_u32()).collect();
+            limbs.push(rng.gen_range(1, 4));
+            let v = BigUint::from_limbs(limbs);
+            let (q, r) = u.div_rem(&v);
+            assert!(r < v);
+            assert_eq!(&(&q * &v) + &r, u);
+        }
+        // A quotient with every bit set
+        let u = big("340282366920938463463374607431768211455");
+        let v = big("18446744073709551617");
+        assert_eq!(u.div_rem(&v), (big("18446744073709551615"), BigUint::zero()));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn divide_by_zero() {
+        let _ = big("1") / BigUint::zero();
+    }
+
+    #[test]
+    #[should_panic(expected = "subtraction overflow")]
+    fn negative_difference() {
+        let _ = big("1") - big("2");
+    }
+
+    #[test]
+    fn powers() {
+        assert_eq!(big("2").pow(100).to_string(), "1267650600228229401496703205376");
+        assert_eq!(big("2").pow(100).bits(), 101);
+        assert_eq!(big("10").pow(0), BigUint::one());
+        assert_eq!(BigUint::zero().pow(5), BigUint::zero());
+        assert_eq!(int("-3").pow(3), int("-27"));
+        assert_eq!(int("-3").pow(4), int("81"));
+    }
+
+    #[test]
+    fn modular_powers() {
+        assert_eq!(big("4").pow_mod(&big("13"), &big("497")), big("445"));
+        assert_eq!(big("2").pow_mod(&big("100"), &big("1000000007")), big("976371285"));
+        assert_eq!(big("5").pow_mod(&BigUint::zero(), &big("7")), BigUint::one());
+        assert_eq!(big("5").pow_mod(&big("3"), &BigUint::one()), BigUint::zero());
+        let a = big("2988348162058574136915891421498819466320163312926952423791023078876139");
+        let b = big("2351399303373464486466122544523690094744975233415544072992656881240319");
+        assert_eq!(a.pow_mod(&b, &big("10").pow(40)),
+                   big("1527229998585248450016808958343740453059"));
+        let mut rng = Rng::new(79);
+        for _ in 0..200 {
+            let (a, b, n) = (rng.next_u64() as u128, rng.gen_range(0, 300u32),
+                             rng.gen_range(1, 1u64 << 62) as u128);
+            let expected = (0..b).fold(1 % n, |power, _| power * (a % n) % n);
+            let (x, m) = (BigUint::from(a as u64), BigUint::from(n as u64));
+            let result = x.pow_mod(&BigUint::from(b), &m);
+            assert_eq!(result.to_string(), expected.to_string());
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(BigUint::from_bytes_be(b"Rosetta Code"), big("25512506514985639724585018469"));
+        assert_eq!(big("25512506514985639724585018469").to_bytes_be(), b"Rosetta Code");
+        assert_eq!(BigUint::from_bytes_be(&[0, 0, 1, 0]), big("256"));
+        assert_eq!(BigUint::from_bytes_be(&[]), BigUint::zero());
+        assert!(BigUint::zero().to_bytes_be().is_empty());
+        assert!(big("4294967296").bit(32) && !big("4294967296").bit(31));
+    }
+
+    #[test]
+    fn signed() {
+        let values = [-17i64, -5, -1, 0, 1, 3, 17, i64::MIN + 1, i64::MAX];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                let (a, b) = (a as i128, b as i128);
+                assert_eq!((&x + &y).to_string(), (a + b).to_string());
+                assert_eq!((&x - &y).to_string(), (a - b).to_string());
+                assert_eq!((&x * &y).to_string(), (a * b).to_string());
+                if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                    assert_eq!((&x / &y).to_string(), quotient.to_string());
+                    assert_eq!((&x % &y).to_string(), remainder.to_string());
+                }
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+            }
+        }
+        assert_eq!(-BigInt::zero(), BigInt::zero());
+        assert!(!(int("5") - int("5")).is_negative());
+    }
+}
@@ -0,0 +1,188 @@
+// Somewhere for the visual tasks (fractals, cellular automata, mazes) to draw, so that the same
+// drawing code can print to a terminal or, through the wasm/ crate, fill a canvas in a browser.
+// A task draws on any Canvas; its main() usually hands it a TextCanvas and prints that.
+
+use super::alloc::string::String;
+use super::alloc::vec::Vec;
+use super::core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color { r: 0, g: 0, b: 0 };
+    pub const WHITE: Color = Color { r: 255, g: 255, b: 255 };
+
+    pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    // How bright the color looks, from 0 for black to 255 for white (ITU-R BT.601 luma)
+    pub fn brightness(&self) -> u8 {
+        ((299 * self.r as u32 + 587 * self.g as u32 + 114 * self.b as u32) / 1000) as u8
+    }
+}
+
+pub trait Canvas {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+
+    // Set the pixel `x` from the left and `y` from the top
+    fn set(&mut self, x: usize, y: usize, color: Color);
+
+    fn fill(&mut self, color: Color) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.set(x, y, color);
+            }
+        }
+    }
+}
+
+// Pixels as RGBA bytes, row by row, which is the layout of a browser's ImageData
+#[derive(Clone, Debug, PartialEq)]
+pub struct PixelCanvas {
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+}
+
+impl PixelCanvas {
+    // A canvas of the given size, all black
+    pub fn new(width: usize, height: usize) -> PixelCanvas {
+        let mut rgba = vec![0; width * height * 4];
+        for alpha in rgba.iter_mut().skip(3).step_by(4) {
+            *alpha = 255;
+        }
+        PixelCanvas { width, height, rgba }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        let i = self.offset(x, y);
+        Color::rgb(self.rgba[i], self.rgba[i + 1], self.rgba[i + 2])
+    }
+
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    pub fn into_rgba(self) -> Vec<u8> {
+        self.rgba
+    }
+
+    fn offset(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} canvas", x, y,
+                self.width, self.height);
+        (y * self.width + x) * 4
+    }
+}
+
+impl Canvas for PixelCanvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: Color) {
+        let i = self.offset(x, y);
+        self.rgba[i..i + 3].copy_from_slice(&[color.r, color.g, color.b]);
+    }
+}
+
+// Characters standing in for pixels, darker colors as sparser characters, for printing
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextCanvas {
+    width: usize,
+    height: usize,
+    brightness: Vec<u8>, // Row by row
+}
+
+// From black to white
+static SHADES: &[u8] = b" .:-=+*#%@";
+
+impl TextCanvas {
+    // A canvas of the given size in characters, all black
+    pub fn new(width: usize, height: usize) -> TextCanvas {
+        TextCanvas { width, height, brightness: vec![0; width * height] }
+    }
+}
+
+impl Canvas for TextCanvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: Color) {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} canvas", x, y,
+                self.width, self.height);
+        self.brightness[y * self.width + x] = color.brightness();
+    }
+}
+
+impl fmt::Display for TextCanvas {
+    // One line per row, without trailing spaces
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.brightness.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let line: String = row.iter().map(|&brightness| {
+                SHADES[brightness as usize * (SHADES.len() - 1) / 255] as char
+            }).collect();
+            f.write_str(line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Canvas, Color, PixelCanvas, TextCanvas};
+
+    #[test]
+    fn colors() {
+        assert_eq!(Color::BLACK.brightness(), 0);
+        assert_eq!(Color::WHITE.brightness(), 255);
+        // Green looks brighter than red, which looks brighter than blue
+        let (r, g, b) = (Color::rgb(255, 0, 0), Color::rgb(0, 255, 0), Color::rgb(0, 0, 255));
+        assert!(g.brightness() > r.brightness() && r.brightness() > b.brightness());
+    }
+
+    #[test]
+    fn pixels() {
+        let mut canvas = PixelCanvas::new(3, 2);
+        assert_eq!(canvas.rgba().len(), 24);
+        assert_eq!(&canvas.rgba()[..8], &[0, 0, 0, 255, 0, 0, 0, 255]);
+        canvas.set(2, 1, Color::rgb(1, 2, 3));
+        assert_eq!(canvas.get(2, 1), Color::rgb(1, 2, 3));
+        assert_eq!(&canvas.rgba()[20..], &[1, 2, 3, 255]);
+        canvas.fill(Color::WHITE);
+        assert!(canvas.into_rgba().iter().all(|&byte| byte == 255));
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside a 3x2 canvas")]
+    fn outside() {
+        PixelCanvas::new(3, 2).set(3, 0, Color::WHITE);
+    }
+
+    #[test]
+    fn text() {
+        let mut canvas = TextCanvas::new(4, 3);
+        canvas.set(0, 0, Color::WHITE);
+        canvas.set(1, 0, Color::rgb(128, 128, 128));
+        canvas.set(3, 2, Color::WHITE);
+        assert_eq!(canvas.to_string(), "@=\n\n   @");
+    }
+}
@@ -0,0 +1,118 @@
+// For testing tasks that read input and print output.  Instead of using standard input and output
+// directly from main(), such a task does its work in
+//
+//     pub fn run(input: impl Read, output: impl Write) -> io::Result<()>
+//
+// which main() hands standard input and output:
+//
+//     fn main() {
+//         let stdin = io::stdin();
+//         run(stdin.lock(), io::stdout()).unwrap();
+//     }
+//
+// and which its tests hand canned input, to check what it wrote:
+//
+//     assert_eq!(utils::capture::output_of(run, "1 2\n"), "3\n");
+
+use std::io::{self, Cursor, Write};
+use std::sync::{Arc, Mutex};
+
+// Collects everything written to it.  Clones share what they collect, so it can be handed to
+// several threads, or kept to look at once run() has it.
+#[derive(Clone, Default)]
+pub struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    pub fn new() -> Captured {
+        Captured::default()
+    }
+
+    // What has been written so far.  Panics if it isn't UTF-8.
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).expect("output isn't UTF-8")
+    }
+}
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Run a task's run() with `input` as its input, and return what it wrote.  Panics if it fails.
+pub fn output_of<F>(run: F, input: &str) -> String
+    where F: FnOnce(Cursor<Vec<u8>>, Captured) -> io::Result<()>
+{
+    let output = Captured::new();
+    if let Err(err) = run(Cursor::new(input.as_bytes().to_vec()), output.clone()) {
+        panic!("run() failed: {} (after writing {:?})", err, output.text());
+    }
+    output.text()
+}
+
+// Run a task's run() with `input` as its input, for input it should fail on, and return the
+// error.  Panics if it succeeds.
+pub fn error_of<F>(run: F, input: &str) -> io::Error
+    where F: FnOnce(Cursor<Vec<u8>>, Captured) -> io::Result<()>
+{
+    let output = Captured::new();
+    match run(Cursor::new(input.as_bytes().to_vec()), output.clone()) {
+        Ok(()) => panic!("run() succeeded, writing {:?}", output.text()),
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, BufRead, Read, Write};
+    use std::thread;
+
+    use super::{error_of, output_of, Captured};
+
+    // Echoes each line of its input in upper case, refusing empty ones
+    fn shout(input: impl Read, mut output: impl Write) -> io::Result<()> {
+        for line in io::BufReader::new(input).lines() {
+            let line = line?;
+            if line.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "nothing to shout"));
+            }
+            writeln!(output, "{}", line.to_uppercase())?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn output() {
+        assert_eq!(output_of(shout, "hello\nworld\n"), "HELLO\nWORLD\n");
+        assert_eq!(output_of(shout, ""), "");
+    }
+
+    #[test]
+    fn error() {
+        assert_eq!(error_of(shout, "hello\n\n").to_string(), "nothing to shout");
+    }
+
+    #[test]
+    #[should_panic(expected = "run() failed: nothing to shout (after writing \"A\\n\")")]
+    fn unexpected_error() {
+        output_of(shout, "a\n\n");
+    }
+
+    #[test]
+    fn shared() {
+        let output = Captured::new();
+        let threads: Vec<_> = (0..4).map(|_| {
+            let mut output = output.clone();
+            thread::spawn(move || write!(output, "ab").unwrap())
+        }).collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(output.text(), "abababab");
+    }
+}
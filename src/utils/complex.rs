@@ -0,0 +1,183 @@
+//! Complex numbers over f64, for the tasks that need them (the Fourier transform, the roots of
+//! polynomials, eigenvalues) without pulling in the num crate.
+//!
+//! The arithmetic only needs core, but core has no square roots or trigonometry, so abs(), arg()
+//! and from_polar() are only there with the std feature.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::complex::Complex;
+//!
+//! let z = Complex::new(3.0, 4.0);
+//! assert_eq!(z * z.conj(), Complex::from(25.0));
+//! assert_eq!(z.abs(), 5.0);
+//! assert_eq!((z / Complex::new(0.0, 1.0)).to_string(), "4 - 3i");
+//! ```
+
+use super::core::fmt;
+use super::core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    // The number with absolute value `r` at angle `theta` from the positive real axis
+    #[cfg(feature = "std")]
+    pub fn from_polar(r: f64, theta: f64) -> Complex {
+        Complex { re: r * theta.cos(), im: r * theta.sin() }
+    }
+
+    pub fn conj(self) -> Complex {
+        Complex { re: self.re, im: -self.im }
+    }
+
+    // The square of abs(), which doesn't need a square root
+    pub fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    #[cfg(feature = "std")]
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    // The angle from the positive real axis, in (-pi, pi]
+    #[cfg(feature = "std")]
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn scale(self, factor: f64) -> Complex {
+        Complex { re: self.re * factor, im: self.im * factor }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Complex {
+        Complex { re, im: 0.0 }
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex { re: self.re + other.re, im: self.im + other.im }
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex { re: self.re - other.re, im: self.im - other.im }
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    // Times the conjugate over the conjugate, which makes the denominator real
+    fn div(self, other: Complex) -> Complex {
+        let denominator = other.norm_sqr();
+        let numerator = self * other.conj();
+        Complex { re: numerator.re / denominator, im: numerator.im / denominator }
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Complex {
+        Complex { re: -self.re, im: -self.im }
+    }
+}
+
+impl AddAssign for Complex {
+    fn add_assign(&mut self, other: Complex) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Complex {
+    fn sub_assign(&mut self, other: Complex) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Complex {
+    fn mul_assign(&mut self, other: Complex) {
+        *self = *self * other;
+    }
+}
+
+impl fmt::Display for Complex {
+    // As a + bi or a - bi, with the precision, if there is one, applied to both parts
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (sign, im) = if self.im.is_sign_negative() { ('-', -self.im) } else { ('+', self.im) };
+        match f.precision() {
+            Some(precision) => {
+                write!(f, "{:.*} {} {:.*}i", precision, self.re, sign, precision, im)
+            }
+            None => write!(f, "{} {} {}i", self.re, sign, im),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::core::f64::consts::PI;
+    use super::Complex;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Complex::new(1.0, 2.0), Complex::new(3.0, -1.0));
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert_eq!((a * b) / b, a);
+        assert_eq!(-a, Complex::new(-1.0, -2.0));
+        assert_eq!(a * a.conj(), Complex::from(a.norm_sqr()));
+        assert_eq!(a.scale(2.0), a + a);
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= Complex::new(0.0, 1.0);
+        assert_eq!(c, Complex::new(1.0, 3.0));
+    }
+
+    #[test]
+    fn polar() {
+        let i = Complex::from_polar(1.0, PI / 2.0);
+        assert!((i - Complex::new(0.0, 1.0)).abs() < 1e-15);
+        assert_eq!(Complex::new(3.0, 4.0).abs(), 5.0);
+        assert_eq!(Complex::new(-1.0, 0.0).arg(), PI);
+        let z = Complex::new(-2.0, 1.5);
+        assert!((Complex::from_polar(z.abs(), z.arg()) - z).abs() < 1e-15);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Complex::new(1.0, -2.5).to_string(), "1 - 2.5i");
+        assert_eq!(Complex::new(0.0, 1.0).to_string(), "0 + 1i");
+        assert_eq!(format!("{:.3}", Complex::new(1.0, 2.0f64.sqrt())), "1.000 + 1.414i");
+    }
+}
@@ -0,0 +1,161 @@
+//! What the hashes (utils::md5, utils::sha1 and utils::sha256) have in common.  Each is a Digest,
+//! which is fed a message in pieces of any size and then finalized into its digest, and each splits
+//! the message into 64-byte blocks with Blocks, which also pads the end of it the same way for all
+//! three: a 1 bit, 0 bits up to 56 bytes into a block, and the message's length in bits.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::digest::{hex, Digest};
+//! use utils::sha256::Sha256;
+//!
+//! let mut hasher = Sha256::default();
+//! hasher.update(b"Rosetta ");
+//! hasher.update(b"code");
+//! assert_eq!(hex(&hasher.finalize()),
+//!            "764faf5c61ac315f1497f9dfa542713965b785e5cc2f707d6468d7d1124cdfcf");
+//! ```
+
+use super::alloc::string::String;
+
+pub trait Digest: Default {
+    // The digest, such as [u8; 16] for MD5
+    type Output: AsRef<[u8]>;
+
+    // How many bytes it hashes at a time, which HMAC pads keys to
+    const BLOCK_SIZE: usize = 64;
+
+    // Hash the next part of the message
+    fn update(&mut self, data: &[u8]);
+
+    // The digest of the whole message
+    fn finalize(self) -> Self::Output;
+
+    // The digest of a whole message at once
+    fn digest(message: &[u8]) -> Self::Output {
+        let mut hasher = Self::default();
+        hasher.update(message);
+        hasher.finalize()
+    }
+}
+
+// A digest the way they're usually written, in lower case hexadecimal
+pub fn hex(digest: &[u8]) -> String {
+    let digits = b"0123456789abcdef";
+    let mut text = String::with_capacity(2 * digest.len());
+    for &byte in digest {
+        text.push(digits[(byte >> 4) as usize] as char);
+        text.push(digits[(byte & 15) as usize] as char);
+    }
+    text
+}
+
+// A message, a block at a time, keeping the start of a block until the rest of it comes
+#[derive(Clone)]
+pub struct Blocks {
+    block: [u8; 64],
+    buffered: usize,
+    // The length of the message so far, in bytes
+    length: u64,
+}
+
+impl Default for Blocks {
+    fn default() -> Blocks {
+        Blocks::new()
+    }
+}
+
+impl Blocks {
+    pub fn new() -> Blocks {
+        Blocks { block: [0; 64], buffered: 0, length: 0 }
+    }
+
+    // Add `data` to the message, calling `compress` with each block it completes
+    pub fn update<F: FnMut(&[u8])>(&mut self, mut data: &[u8], mut compress: F) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let n = data.len().min(64 - self.buffered);
+            self.block[self.buffered..self.buffered + n].copy_from_slice(&data[..n]);
+            self.buffered += n;
+            data = &data[n..];
+            if self.buffered < 64 {
+                return;
+            }
+            compress(&self.block);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(block);
+        }
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    // Pad the end of the message, with its length in bits little-endian for MD5 and big-endian
+    // for the SHAs, calling `compress` with the last block or two
+    pub fn finish<F: FnMut(&[u8])>(&mut self, big_endian: bool, compress: F) {
+        let bits = self.length.wrapping_mul(8);
+        let padding = if self.buffered < 56 { 56 - self.buffered } else { 120 - self.buffered };
+        let mut tail = [0; 72];
+        tail[0] = 0x80;
+        let length = if big_endian { bits.to_be_bytes() } else { bits.to_le_bytes() };
+        tail[padding..padding + 8].copy_from_slice(&length);
+        self.update(&tail[..padding + 8], compress);
+        debug_assert_eq!(self.buffered, 0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::alloc::vec::Vec;
+    use super::{hex, Blocks};
+
+    // The blocks a message of `length` bytes is hashed in, fed to Blocks `piece` bytes at a time
+    fn blocks(length: usize, piece: usize, big_endian: bool) -> Vec<Vec<u8>> {
+        let message: Vec<u8> = (0..length).map(|i| i as u8 + 1).collect();
+        let mut blocks = Vec::new();
+        let mut splitter = Blocks::new();
+        for chunk in message.chunks(piece) {
+            splitter.update(chunk, |block| blocks.push(block.to_vec()));
+        }
+        splitter.finish(big_endian, |block| blocks.push(block.to_vec()));
+        blocks
+    }
+
+    #[test]
+    fn padding() {
+        let empty = blocks(0, 1, true);
+        assert_eq!(empty.len(), 1);
+        assert_eq!(empty[0][0], 0x80);
+        assert!(empty[0][1..].iter().all(|&byte| byte == 0));
+
+        let three = blocks(3, 1, false);
+        assert_eq!(three[0][..4], [1, 2, 3, 0x80]);
+        assert_eq!(three[0][56..], [24, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(blocks(3, 1, true)[0][56..], [0, 0, 0, 0, 0, 0, 0, 24]);
+
+        // The length doesn't fit after 56 bytes, so it takes another block
+        assert_eq!(blocks(55, 7, true).len(), 1);
+        assert_eq!(blocks(56, 7, true).len(), 2);
+        assert_eq!(blocks(64, 7, true).len(), 2);
+        assert_eq!(blocks(120, 7, true)[2][62..], [3, 192]);
+    }
+
+    #[test]
+    fn pieces() {
+        // The blocks are the same however the message is split up
+        for &length in &[1, 63, 64, 65, 200] {
+            let whole = blocks(length, length, true);
+            for &piece in &[1, 5, 64, 100] {
+                assert_eq!(blocks(length, piece, true), whole, "{} in {}", length, piece);
+            }
+        }
+    }
+
+    #[test]
+    fn hex_digits() {
+        assert_eq!(hex(&[0x00, 0x0f, 0xa5, 0xff]), "000fa5ff");
+        assert_eq!(hex(&[]), "");
+    }
+}
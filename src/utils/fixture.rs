@@ -0,0 +1,67 @@
+// Finding the sample inputs in tests/fixtures/, such as the word list unixdict.txt, which the
+// tasks that read files share with their tests.  They're found from the crate root that cargo
+// builds the task in rather than from the current directory, so a task finds them wherever it's
+// run from, and so do its tests.  When that crate is another one that includes utils, such as
+// no_std/, they're looked for in the directories above it:
+//
+//     let words = utils::fixture::read("unixdict.txt");
+//
+// A new fixture is a file put in tests/fixtures/ under the name it's read by.  tests/fixtures.rs
+// runs the tasks that read them from another directory, to check they really don't depend on it.
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+// The nearest directory holding tests/fixtures, from the crate being built up
+fn fixtures() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir.ancestors()
+        .find(|dir| dir.join("tests").join("fixtures").is_dir())
+        .unwrap_or(manifest_dir);
+    root.join("tests").join("fixtures")
+}
+
+// The path of the fixture called `name`
+pub fn path(name: &str) -> PathBuf {
+    fixtures().join(name)
+}
+
+// The contents of the fixture called `name`.  Panics if it can't be read, since every task that
+// reads one is lost without it.
+pub fn read(name: &str) -> String {
+    let path = path(name);
+    fs::read_to_string(&path).unwrap_or_else(|error| {
+        panic!("can't read fixture {}: {}", path.display(), error)
+    })
+}
+
+// The fixture called `name`, opened for reading a line or a few bytes at a time.  Panics if it
+// can't be opened.
+pub fn open(name: &str) -> BufReader<File> {
+    let path = path(name);
+    match File::open(&path) {
+        Ok(file) => BufReader::new(file),
+        Err(error) => panic!("can't open fixture {}: {}", path.display(), error),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{open, path, read};
+    use std::io::BufRead;
+
+    #[test]
+    fn fixtures() {
+        assert!(path("unixdict.txt").ends_with("tests/fixtures/unixdict.txt"));
+        assert!(path("unixdict.txt").is_absolute());
+        assert!(read("test_data.fasta").starts_with(">Rosetta_Example_1"));
+        assert_eq!(open("fib1000.txt").lines().count(), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't read fixture")]
+    fn missing() {
+        read("no_such_fixture.txt");
+    }
+}
@@ -0,0 +1,244 @@
+//! Graphs as adjacency lists, for the graph tasks (Dijkstra's algorithm, topological sort,
+//! minimum spanning trees, strongly connected components) to build on.  Nodes are numbered from 0,
+//! so that per node data can live in a Vec indexed by node, and every edge carries a weight of
+//! type W, which is () for unweighted graphs.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::graph::Graph;
+//!
+//! let graph = Graph::directed(5).with_edges(vec![(0, 1, ()), (0, 2, ()), (1, 3, ()), (2, 3, ())]);
+//! assert_eq!(graph.bfs(0).collect::<Vec<_>>(), [0, 1, 2, 3]);
+//! assert_eq!(graph.dfs(0).collect::<Vec<_>>(), [0, 1, 3, 2]);
+//! // Nothing leads to node 4
+//! assert!(graph.dfs(0).all(|node| node != 4));
+//! ```
+
+use super::alloc::collections::VecDeque;
+use super::alloc::vec::Vec;
+use super::core::ops::Range;
+
+pub type Node = usize;
+
+#[derive(Clone, Debug)]
+pub struct Graph<W = ()> {
+    directed: bool,
+    adjacency: Vec<Vec<(Node, W)>>, // For each node, where its edges go and their weights
+    edges: usize,
+}
+
+impl<W: Clone> Graph<W> {
+    // A graph of `nodes` nodes and no edges, in which an edge only leads from its first node to
+    // its second
+    pub fn directed(nodes: usize) -> Graph<W> {
+        Graph { directed: true, adjacency: vec![Vec::new(); nodes], edges: 0 }
+    }
+
+    // A graph of `nodes` nodes and no edges, in which edges lead both ways
+    pub fn undirected(nodes: usize) -> Graph<W> {
+        Graph { directed: false, adjacency: vec![Vec::new(); nodes], edges: 0 }
+    }
+
+    // Add each of the (from, to, weight) `edges`, for building a graph in one expression.
+    pub fn with_edges<I: IntoIterator<Item = (Node, Node, W)>>(mut self, edges: I) -> Graph<W> {
+        for (from, to, weight) in edges {
+            self.add_edge(from, to, weight);
+        }
+        self
+    }
+
+    // Add a node without any edges, returning it
+    pub fn add_node(&mut self) -> Node {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    // Add an edge from `from` to `to`, and in an undirected graph from `to` to `from` as well.
+    // Parallel edges are kept, so the same two nodes can be joined with different weights.
+    pub fn add_edge(&mut self, from: Node, to: Node, weight: W) {
+        let nodes = self.adjacency.len();
+        assert!(from < nodes && to < nodes, "edge ({}, {}) in a graph of {} nodes", from, to,
+                nodes);
+        if !self.directed && from != to {
+            self.adjacency[to].push((from, weight.clone()));
+        }
+        self.adjacency[from].push((to, weight));
+        self.edges += 1;
+    }
+
+    // The same graph with every edge going the other way
+    pub fn reversed(&self) -> Graph<W> {
+        if !self.directed {
+            return self.clone();
+        }
+        let mut reversed = Graph::directed(self.node_count());
+        for from in self.nodes() {
+            for &(to, ref weight) in self.edges(from) {
+                reversed.add_edge(to, from, weight.clone());
+            }
+        }
+        reversed
+    }
+}
+
+impl<W> Graph<W> {
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    // Return the number of edges, counting each edge of an undirected graph once
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    pub fn nodes(&self) -> Range<Node> {
+        0..self.adjacency.len()
+    }
+
+    // Return the edges leaving `node`, as (neighbor, weight) pairs in the order they were added
+    pub fn edges(&self, node: Node) -> &[(Node, W)] {
+        &self.adjacency[node]
+    }
+
+    // Return the nodes the edges leaving `node` lead to
+    pub fn neighbors(&self, node: Node) -> impl Iterator<Item = Node> + '_ {
+        self.adjacency[node].iter().map(|&(neighbor, _)| neighbor)
+    }
+
+    // Iterate over the nodes reachable from `start`, nearest first (breadth-first search).
+    pub fn bfs(&self, start: Node) -> Bfs<'_, W> {
+        let mut visited = vec![false; self.node_count()];
+        visited[start] = true;
+        Bfs { graph: self, queue: vec![start].into_iter().collect(), visited }
+    }
+
+    // Iterate over the nodes reachable from `start`, following each path as far as it goes before
+    // backtracking (depth-first search, in preorder).
+    pub fn dfs(&self, start: Node) -> Dfs<'_, W> {
+        Dfs { graph: self, stack: vec![start], visited: vec![false; self.node_count()] }
+    }
+}
+
+pub struct Bfs<'a, W: 'a> {
+    graph: &'a Graph<W>,
+    queue: VecDeque<Node>,
+    visited: Vec<bool>, // Set once a node is queued, so it's only queued once
+}
+
+impl<'a, W> Iterator for Bfs<'a, W> {
+    type Item = Node;
+
+    fn next(&mut self) -> Option<Node> {
+        let node = self.queue.pop_front()?;
+        for neighbor in self.graph.neighbors(node) {
+            if !self.visited[neighbor] {
+                self.visited[neighbor] = true;
+                self.queue.push_back(neighbor);
+            }
+        }
+        Some(node)
+    }
+}
+
+pub struct Dfs<'a, W: 'a> {
+    graph: &'a Graph<W>,
+    stack: Vec<Node>,
+    visited: Vec<bool>, // Set once a node is returned, since it may be stacked more than once
+}
+
+impl<'a, W> Iterator for Dfs<'a, W> {
+    type Item = Node;
+
+    fn next(&mut self) -> Option<Node> {
+        while let Some(node) = self.stack.pop() {
+            if self.visited[node] {
+                continue;
+            }
+            self.visited[node] = true;
+            // Stacked in reverse, so that neighbors are visited in the order of their edges
+            let unvisited: Vec<Node> = self.graph.neighbors(node)
+                                           .filter(|&neighbor| !self.visited[neighbor]).collect();
+            self.stack.extend(unvisited.into_iter().rev());
+            return Some(node);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Graph;
+
+    //   0 → 1 → 3
+    //   ↓   ↓   ↑
+    //   2 → 4   5 (which can't be reached from 0)
+    fn example() -> Graph {
+        Graph::directed(6).with_edges(vec![(0, 1, ()), (0, 2, ()), (1, 3, ()), (1, 4, ()),
+                                           (2, 4, ()), (5, 3, ())])
+    }
+
+    #[test]
+    fn build() {
+        let mut graph = example();
+        assert!(graph.is_directed());
+        assert_eq!((graph.node_count(), graph.edge_count()), (6, 6));
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(graph.neighbors(3).count(), 0);
+        let node = graph.add_node();
+        assert_eq!(node, 6);
+        graph.add_edge(node, 0, ());
+        assert_eq!(graph.neighbors(6).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(graph.nodes().count(), 7);
+    }
+
+    #[test]
+    fn undirected() {
+        let graph = Graph::undirected(3).with_edges(vec![(0, 1, 5), (1, 2, 7), (2, 2, 1)]);
+        assert!(!graph.is_directed());
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.edges(1), &[(0, 5), (2, 7)]);
+        // A loop is only listed once
+        assert_eq!(graph.edges(2), &[(1, 7), (2, 1)]);
+        assert_eq!(graph.reversed().edges(1), graph.edges(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "edge (0, 3) in a graph of 3 nodes")]
+    fn edge_to_nowhere() {
+        Graph::directed(3).add_edge(0, 3, ());
+    }
+
+    #[test]
+    fn reversed() {
+        let graph = Graph::directed(3).with_edges(vec![(0, 1, 'a'), (0, 2, 'b'), (2, 1, 'c')]);
+        let reversed = graph.reversed();
+        assert_eq!(reversed.edges(1), &[(0, 'a'), (2, 'c')]);
+        assert_eq!(reversed.edges(2), &[(0, 'b')]);
+        assert!(reversed.edges(0).is_empty());
+        assert_eq!(reversed.edge_count(), 3);
+    }
+
+    #[test]
+    fn breadth_first() {
+        let graph = example();
+        assert_eq!(graph.bfs(0).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(graph.bfs(5).collect::<Vec<_>>(), vec![5, 3]);
+        assert_eq!(graph.reversed().bfs(3).collect::<Vec<_>>(), vec![3, 1, 5, 0]);
+    }
+
+    #[test]
+    fn depth_first() {
+        let graph = example();
+        assert_eq!(graph.dfs(0).collect::<Vec<_>>(), vec![0, 1, 3, 4, 2]);
+        assert_eq!(graph.dfs(4).collect::<Vec<_>>(), vec![4]);
+        // Cycles don't trip it up
+        let cycle = Graph::undirected(4).with_edges(vec![(0, 1, ()), (1, 2, ()), (2, 3, ()),
+                                                         (3, 0, ())]);
+        assert_eq!(cycle.dfs(0).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(cycle.bfs(0).collect::<Vec<_>>(), vec![0, 1, 3, 2]);
+    }
+}
@@ -0,0 +1,295 @@
+//! Binary heaps, for the tasks that need to keep taking out the smallest of a changing set of
+//! items: Dijkstra's algorithm, A* search, Huffman coding and the Priority queue task.
+//!
+//! PriorityQueue holds any items, each pushed with a priority, and pops the lowest priority
+//! first.  Items of equal priority come out in the order they went in, so that what a task prints
+//! doesn't depend on how the heap happens to break ties.
+//!
+//! NodeQueue is for graph searches, where the items are nodes numbered from 0 (as in utils::graph)
+//! and finding a shorter path to a node lowers its priority.  It holds each node at most once, and
+//! keeps track of where each one is in the heap, so that lowering a node's priority moves it up in
+//! place rather than pushing it again.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::heap::{NodeQueue, PriorityQueue};
+//!
+//! let mut tasks = PriorityQueue::new();
+//! tasks.extend(vec![("feed cat", 4), ("make tea", 3), ("clear drains", 3)]);
+//! assert_eq!(tasks.pop(), Some(("make tea", 3)));
+//! assert_eq!(tasks.pop(), Some(("clear drains", 3)));
+//!
+//! let mut frontier = NodeQueue::new(3);
+//! frontier.push_or_decrease(2, 10);
+//! frontier.push_or_decrease(1, 7);
+//! frontier.push_or_decrease(2, 5);
+//! assert_eq!(frontier.pop(), Some((2, 5)));
+//! assert_eq!(frontier.len(), 1);
+//! ```
+
+use super::alloc::vec::Vec;
+
+// The heap is stored in a Vec, with the children of the entry at i at 2i + 1 and 2i + 2.  Both
+// queues keep their heaps with these, which take whether one entry goes before another, and call
+// `moved` with each entry's new index as it moves.
+
+fn sift_up<E, F, M>(heap: &mut [E], mut i: usize, before: F, mut moved: M)
+    where F: Fn(&E, &E) -> bool,
+          M: FnMut(&E, usize)
+{
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if !before(&heap[i], &heap[parent]) {
+            break;
+        }
+        heap.swap(i, parent);
+        moved(&heap[i], i);
+        i = parent;
+    }
+    if let Some(entry) = heap.get(i) {
+        moved(entry, i);
+    }
+}
+
+fn sift_down<E, F, M>(heap: &mut [E], mut i: usize, before: F, mut moved: M)
+    where F: Fn(&E, &E) -> bool,
+          M: FnMut(&E, usize)
+{
+    loop {
+        let (left, right) = (2 * i + 1, 2 * i + 2);
+        let mut first = i;
+        if left < heap.len() && before(&heap[left], &heap[first]) {
+            first = left;
+        }
+        if right < heap.len() && before(&heap[right], &heap[first]) {
+            first = right;
+        }
+        if first == i {
+            break;
+        }
+        heap.swap(i, first);
+        moved(&heap[i], i);
+        i = first;
+    }
+    if let Some(entry) = heap.get(i) {
+        moved(entry, i);
+    }
+}
+
+struct Entry<P, T> {
+    priority: P,
+    order: u64, // How many items were pushed before this one, to break ties
+    item: T,
+}
+
+fn entry_before<P: Ord, T>(a: &Entry<P, T>, b: &Entry<P, T>) -> bool {
+    (&a.priority, a.order) < (&b.priority, b.order)
+}
+
+pub struct PriorityQueue<P, T> {
+    heap: Vec<Entry<P, T>>,
+    pushed: u64,
+}
+
+impl<P: Ord, T> PriorityQueue<P, T> {
+    pub fn new() -> PriorityQueue<P, T> {
+        PriorityQueue { heap: Vec::new(), pushed: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn push(&mut self, item: T, priority: P) {
+        self.heap.push(Entry { priority, order: self.pushed, item });
+        self.pushed += 1;
+        let last = self.heap.len() - 1;
+        sift_up(&mut self.heap, last, entry_before, |_, _| {});
+    }
+
+    // Take out the item with the lowest priority, along with its priority
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let entry = self.heap.swap_remove(0);
+        sift_down(&mut self.heap, 0, entry_before, |_, _| {});
+        Some((entry.item, entry.priority))
+    }
+
+    // The item that pop() would take out, and its priority
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.heap.first().map(|entry| (&entry.item, &entry.priority))
+    }
+}
+
+impl<P: Ord, T> Default for PriorityQueue<P, T> {
+    fn default() -> PriorityQueue<P, T> {
+        PriorityQueue::new()
+    }
+}
+
+impl<P: Ord, T> Extend<(T, P)> for PriorityQueue<P, T> {
+    fn extend<I: IntoIterator<Item = (T, P)>>(&mut self, items: I) {
+        for (item, priority) in items {
+            self.push(item, priority);
+        }
+    }
+}
+
+pub struct NodeQueue<P> {
+    heap: Vec<(P, usize)>,         // Each queued node, with its priority
+    positions: Vec<Option<usize>>, // Where each node is in the heap, if it's queued
+}
+
+impl<P: Ord> NodeQueue<P> {
+    // An empty queue for the nodes 0 to `nodes` - 1
+    pub fn new(nodes: usize) -> NodeQueue<P> {
+        NodeQueue { heap: Vec::new(), positions: vec![None; nodes] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, node: usize) -> bool {
+        self.positions[node].is_some()
+    }
+
+    // The priority `node` is queued with, if it's queued
+    pub fn priority(&self, node: usize) -> Option<&P> {
+        self.positions[node].map(|i| &self.heap[i].0)
+    }
+
+    // Queue `node` with `priority`, or if it's already queued with a higher priority, lower it to
+    // `priority`.  Returns whether it did either, which it doesn't if the node is queued with a
+    // priority no higher already.
+    pub fn push_or_decrease(&mut self, node: usize, priority: P) -> bool {
+        let i = match self.positions[node] {
+            Some(i) if self.heap[i].0 <= priority => return false,
+            Some(i) => {
+                self.heap[i].0 = priority;
+                i
+            }
+            None => {
+                self.heap.push((priority, node));
+                self.heap.len() - 1
+            }
+        };
+        let positions = &mut self.positions;
+        sift_up(&mut self.heap, i, |a, b| a < b, |&(_, node), i| positions[node] = Some(i));
+        true
+    }
+
+    // Take out the node with the lowest priority, along with its priority
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (priority, node) = self.heap.swap_remove(0);
+        self.positions[node] = None;
+        let positions = &mut self.positions;
+        sift_down(&mut self.heap, 0, |a, b| a < b, |&(_, node), i| positions[node] = Some(i));
+        Some((node, priority))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NodeQueue, PriorityQueue};
+    use super::super::rng::Rng;
+
+    #[test]
+    fn lowest_first() {
+        let mut queue = PriorityQueue::new();
+        queue.extend(vec![("c", 3), ("a", 1), ("d", 4), ("b", 2)]);
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.peek(), Some((&"a", &1)));
+        let mut popped = Vec::new();
+        while let Some((item, _)) = queue.pop() {
+            popped.push(item);
+        }
+        assert_eq!(popped, vec!["a", "b", "c", "d"]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn ties_in_order() {
+        let mut queue = PriorityQueue::new();
+        for (i, &priority) in [2, 1, 2, 1, 2].iter().enumerate() {
+            queue.push(i, priority);
+        }
+        let order: Vec<usize> = (0..5).map(|_| queue.pop().unwrap().0).collect();
+        assert_eq!(order, vec![1, 3, 0, 2, 4]);
+    }
+
+    #[test]
+    fn sorts_like_sort() {
+        let mut rng = Rng::new(46);
+        let mut numbers: Vec<u32> = (0..500).map(|_| rng.gen_range(0, 100)).collect();
+        let mut queue = PriorityQueue::new();
+        queue.extend(numbers.iter().map(|&n| (n, n)));
+        let mut popped = Vec::new();
+        while let Some((n, _)) = queue.pop() {
+            popped.push(n);
+        }
+        numbers.sort();
+        assert_eq!(popped, numbers);
+    }
+
+    #[test]
+    fn decrease_node() {
+        let mut queue = NodeQueue::new(5);
+        assert!(queue.push_or_decrease(0, 10));
+        assert!(queue.push_or_decrease(1, 20));
+        assert!(queue.push_or_decrease(2, 30));
+        assert!(!queue.push_or_decrease(1, 25));
+        assert!(queue.push_or_decrease(2, 5));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.priority(2), Some(&5));
+        assert!(!queue.contains(3));
+        assert_eq!(queue.pop(), Some((2, 5)));
+        assert!(!queue.contains(2));
+        assert_eq!(queue.pop(), Some((0, 10)));
+        // A node can be queued again once it's been popped
+        assert!(queue.push_or_decrease(2, 1));
+        assert_eq!(queue.pop(), Some((2, 1)));
+        assert_eq!(queue.pop(), Some((1, 20)));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn nodes_in_order() {
+        let mut rng = Rng::new(7);
+        let mut queue = NodeQueue::new(100);
+        let mut lowest = vec![u32::MAX; 100];
+        for _ in 0..1000 {
+            let (node, priority) = (rng.gen_range(0, 100), rng.gen_range(0, 10000));
+            queue.push_or_decrease(node, priority);
+            lowest[node] = lowest[node].min(priority);
+        }
+        let mut expected: Vec<(usize, u32)> = lowest.iter().cloned().enumerate()
+                                                    .filter(|&(_, p)| p != u32::MAX)
+                                                    .collect();
+        expected.sort_by_key(|&(node, priority)| (priority, node));
+        let mut popped = Vec::new();
+        while let Some(entry) = queue.pop() {
+            popped.push(entry);
+        }
+        assert_eq!(popped.len(), expected.len());
+        let priorities = |entries: &[(usize, u32)]| {
+            entries.iter().map(|&(_, p)| p).collect::<Vec<_>>()
+        };
+        assert_eq!(priorities(&popped), priorities(&expected));
+    }
+}
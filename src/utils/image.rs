@@ -0,0 +1,162 @@
+// An image in memory, for the graphics tasks to draw on and save.  It's a Canvas like the others,
+// and can be encoded as a binary PPM, the simplest format there is, or as an uncompressed BMP,
+// which more viewers open.  With std there are also writers, to save it to a file:
+//
+//     image.write_ppm(File::create("mandelbrot.ppm")?)?;
+
+use super::alloc::vec::Vec;
+use super::canvas::{Canvas, Color};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>, // Row by row, from the top
+}
+
+impl Image {
+    // An image of the given size, all black
+    pub fn new(width: usize, height: usize) -> Image {
+        Image { width, height, pixels: vec![Color::BLACK; width * height] }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        self.pixels[self.offset(x, y)]
+    }
+
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    // The image as a binary PPM (P6): a text header, then the red, green and blue of each pixel
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.reserve(self.pixels.len() * 3);
+        for color in &self.pixels {
+            ppm.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+        ppm
+    }
+
+    // The image as a 24-bit uncompressed BMP.  Its rows are stored from the bottom up, each pixel
+    // as blue, green and red, with each row padded to a multiple of four bytes.
+    pub fn to_bmp(&self) -> Vec<u8> {
+        let row_size = (self.width * 3).div_ceil(4) * 4;
+        let image_size = row_size * self.height;
+        let header_size = 14 + 40;
+        let mut bmp = Vec::with_capacity(header_size + image_size);
+        // The file header
+        bmp.extend_from_slice(b"BM");
+        push_u32(&mut bmp, (header_size + image_size) as u32);
+        push_u32(&mut bmp, 0); // Reserved
+        push_u32(&mut bmp, header_size as u32); // Where the pixels start
+        // The BITMAPINFOHEADER
+        push_u32(&mut bmp, 40);
+        push_u32(&mut bmp, self.width as u32);
+        push_u32(&mut bmp, self.height as u32);
+        bmp.extend_from_slice(&[1, 0, 24, 0]); // One plane, 24 bits per pixel
+        push_u32(&mut bmp, 0); // No compression
+        push_u32(&mut bmp, image_size as u32);
+        push_u32(&mut bmp, 2835); // 72 dots per inch, across and down, in dots per meter
+        push_u32(&mut bmp, 2835);
+        push_u32(&mut bmp, 0); // No palette
+        push_u32(&mut bmp, 0);
+        for row in self.pixels.chunks(self.width.max(1)).rev() {
+            for color in row {
+                bmp.extend_from_slice(&[color.b, color.g, color.r]);
+            }
+            bmp.resize(bmp.len() + row_size - self.width * 3, 0);
+        }
+        bmp
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(&self.to_ppm())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_bmp<W: Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(&self.to_bmp())
+    }
+
+    fn offset(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} image", x, y,
+                self.width, self.height);
+        y * self.width + x
+    }
+}
+
+// Little endian, as BMP has everything
+fn push_u32(bytes: &mut Vec<u8>, n: u32) {
+    bytes.extend_from_slice(&n.to_le_bytes());
+}
+
+impl Canvas for Image {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: Color) {
+        let i = self.offset(x, y);
+        self.pixels[i] = color;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::canvas::{Canvas, Color};
+    use super::Image;
+
+    #[test]
+    fn pixels() {
+        let mut image = Image::new(3, 2);
+        assert!(image.pixels().iter().all(|&color| color == Color::BLACK));
+        image.set(2, 1, Color::rgb(1, 2, 3));
+        assert_eq!(image.get(2, 1), Color::rgb(1, 2, 3));
+        assert_eq!(image.pixels()[5], Color::rgb(1, 2, 3));
+        image.fill(Color::WHITE);
+        assert_eq!(image.get(0, 0), Color::WHITE);
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 2) is outside a 3x2 image")]
+    fn outside() {
+        Image::new(3, 2).get(0, 2);
+    }
+
+    #[test]
+    fn ppm() {
+        let mut image = Image::new(2, 1);
+        image.set(0, 0, Color::rgb(1, 2, 3));
+        image.set(1, 0, Color::rgb(4, 5, 6));
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+        let mut written = Vec::new();
+        image.write_ppm(&mut written).unwrap();
+        assert_eq!(written, image.to_ppm());
+    }
+
+    #[test]
+    fn bmp() {
+        let mut image = Image::new(1, 2);
+        image.set(0, 0, Color::rgb(1, 2, 3));
+        image.set(0, 1, Color::rgb(4, 5, 6));
+        let bmp = image.to_bmp();
+        // Each one-pixel row is padded from 3 bytes to 4
+        assert_eq!(bmp.len(), 54 + 8);
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(&bmp[2..6], &62u32.to_le_bytes());
+        assert_eq!(&bmp[10..14], &54u32.to_le_bytes());
+        assert_eq!(&bmp[18..26], &[1, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(&bmp[28..30], &[24, 0]);
+        // The bottom row first
+        assert_eq!(&bmp[54..], &[6, 5, 4, 0, 3, 2, 1, 0]);
+        assert_eq!(Image::new(0, 0).to_bmp().len(), 54);
+    }
+}
@@ -0,0 +1,14 @@
+// The utils modules built on their own, so that `cargo test --bin utils` runs their tests.  This
+// is the root rather than mod.rs so that memory.rs's counting allocator can be installed once
+// here, as the tasks that measure install it for themselves.
+
+#[macro_use]
+#[allow(dead_code)]
+#[path = "mod.rs"]
+mod utils;
+
+#[cfg(feature = "mem-profile")]
+#[global_allocator]
+static METER: utils::memory::Meter = utils::memory::Meter;
+
+fn main() {}
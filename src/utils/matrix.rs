@@ -0,0 +1,347 @@
+//! A dense two dimensional matrix, for the tasks that do linear algebra (Gaussian elimination,
+//! LU, QR and Cholesky decompositions, matrix arithmetic) so that each of them doesn't need its
+//! own storage and indexing.  The elements are stored row by row in a single Vec, and indexed
+//! with `m[(row, col)]`.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::matrix::Matrix;
+//!
+//! let fibonacci = Matrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]);
+//! let power = fibonacci.pow(10);
+//! assert_eq!(power[(0, 1)], 55);
+//! assert_eq!(power.transpose(), power);
+//! assert_eq!(&fibonacci * &Matrix::identity(2), fibonacci);
+//! ```
+
+use super::alloc::string::{String, ToString};
+use super::alloc::vec::Vec;
+use super::core::fmt;
+use super::core::ops::{Add, Index, IndexMut, Mul, Neg, Range, Sub};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>, // Row-major: element (i, j) is at i * cols + j
+}
+
+// Numbers a matrix can do arithmetic on
+pub trait Element: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+}
+
+macro_rules! element {
+    ($($t:ty)*) => ($(
+        impl Element for $t {
+            fn zero() -> $t { 0 as $t }
+            fn one() -> $t { 1 as $t }
+        }
+    )*)
+}
+
+element! { i32 i64 isize u32 u64 usize f32 f64 }
+
+impl<T> Matrix<T> {
+    // A matrix of `rows` rows and `cols` columns from its elements, row by row
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+        assert_eq!(data.len(), rows * cols, "a {}x{} matrix needs {} elements", rows, cols,
+                   rows * cols);
+        Matrix { rows, cols, data }
+    }
+
+    // A matrix whose element (i, j) is f(i, j)
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(rows: usize, cols: usize, mut f: F) -> Matrix<T> {
+        let data = (0..rows * cols).map(|k| f(k / cols, k % cols)).collect();
+        Matrix { rows, cols, data }
+    }
+
+    // A matrix from a Vec of its rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Matrix<T> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        let n = rows.len();
+        let mut data = Vec::with_capacity(n * cols);
+        for row in rows {
+            assert_eq!(row.len(), cols, "rows of different lengths");
+            data.extend(row);
+        }
+        Matrix { rows: n, cols, data }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    // Return row `i` as a slice
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    // Swap two rows, as in pivoting
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.cols {
+            self.data.swap(a * self.cols + j, b * self.cols + j);
+        }
+    }
+
+    // Return the elements, row by row
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    // A matrix with every element set to `value`
+    pub fn filled(rows: usize, cols: usize, value: T) -> Matrix<T> {
+        Matrix { rows, cols, data: vec![value; rows * cols] }
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+
+    // Return a copy of the block of the matrix in `rows` and `cols`, such as the minor
+    // m.submatrix(1..n, 1..n).
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Matrix<T> {
+        assert!(rows.end <= self.rows && cols.end <= self.cols, "submatrix out of bounds");
+        let (first_row, first_col) = (rows.start, cols.start);
+        Matrix::from_fn(rows.len(), cols.len(), |i, j| self[(first_row + i, first_col + j)].clone())
+    }
+
+    // Return column `j`
+    pub fn col(&self, j: usize) -> Vec<T> {
+        (0..self.rows).map(|i| self[(i, j)].clone()).collect()
+    }
+}
+
+impl<T: Element> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::filled(rows, cols, T::zero())
+    }
+
+    pub fn identity(n: usize) -> Matrix<T> {
+        Matrix::from_fn(n, n, |i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    // Multiply every element by `factor`
+    pub fn scale(&self, factor: T) -> Matrix<T> {
+        self.map(|x| x * factor)
+    }
+
+    // Raise a square matrix to the power `exp`, by repeated squaring.
+    pub fn pow(&self, mut exp: u32) -> Matrix<T> {
+        assert!(self.is_square(), "only square matrices have powers");
+        let mut base = self.clone();
+        let mut result = Matrix::identity(self.rows);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    fn map<F: Fn(T) -> T>(&self, f: F) -> Matrix<T> {
+        Matrix { rows: self.rows, cols: self.cols, data: self.data.iter().map(|&x| f(x)).collect() }
+    }
+
+    fn zip<F: Fn(T, T) -> T>(&self, other: &Matrix<T>, f: F) -> Matrix<T> {
+        assert!(self.rows == other.rows && self.cols == other.cols,
+                "can't combine a {}x{} matrix with a {}x{} one",
+                self.rows, self.cols, other.rows, other.cols);
+        let data = self.data.iter().zip(&other.data).map(|(&a, &b)| f(a, b)).collect();
+        Matrix { rows: self.rows, cols: self.cols, data }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.rows && j < self.cols, "({}, {}) is outside a {}x{} matrix", i, j,
+                self.rows, self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.rows && j < self.cols, "({}, {}) is outside a {}x{} matrix", i, j,
+                self.rows, self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T: Element> Add for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, other: &Matrix<T>) -> Matrix<T> {
+        self.zip(other, |a, b| a + b)
+    }
+}
+
+impl<T: Element> Sub for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, other: &Matrix<T>) -> Matrix<T> {
+        self.zip(other, |a, b| a - b)
+    }
+}
+
+impl<T: Element + Neg<Output = T>> Neg for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        self.map(|x| -x)
+    }
+}
+
+impl<T: Element> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(self.cols, other.rows, "can't multiply a {}x{} matrix by a {}x{} one",
+                   self.rows, self.cols, other.rows, other.cols);
+        let mut product = Matrix::zeros(self.rows, other.cols);
+        // In i, k, j order the inner loop runs along rows of both `other` and the product
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..other.cols {
+                    product[(i, j)] = product[(i, j)] + a * other[(k, j)];
+                }
+            }
+        }
+        product
+    }
+}
+
+// The operators on values rather than references, in terms of the ones above
+macro_rules! by_value {
+    ($($trait_:ident $method:ident),*) => ($(
+        impl<T: Element> $trait_ for Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, other: Matrix<T>) -> Matrix<T> {
+                (&self).$method(&other)
+            }
+        }
+    )*)
+}
+
+by_value!(Add add, Sub sub, Mul mul);
+
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    // One row per line, with the columns lined up.  Precision and the like apply to every
+    // element.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<String> = self.data.iter().map(|x| match f.precision() {
+            Some(precision) => format!("{:.*}", precision, x),
+            None => x.to_string(),
+        }).collect();
+        let width = cells.iter().map(|cell| cell.len()).max().unwrap_or(0);
+        for (i, row) in cells.chunks(self.cols.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row: Vec<String> = row.iter().map(|cell| format!("{:>1$}", cell, width)).collect();
+            write!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Matrix;
+
+    fn example() -> Matrix<i64> {
+        Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    #[test]
+    fn construct_and_index() {
+        let mut m = example();
+        assert_eq!((m.rows(), m.cols()), (2, 3));
+        assert_eq!(m[(1, 0)], 4);
+        assert_eq!(m.row(1), &[4, 5, 6]);
+        assert_eq!(m.col(2), vec![3, 6]);
+        m[(0, 2)] = 7;
+        m.row_mut(1)[1] = 8;
+        assert_eq!(m, Matrix::new(2, 3, vec![1, 2, 7, 4, 8, 6]));
+        assert_eq!(Matrix::from_fn(2, 3, |i, j| (i * 3 + j + 1) as i64), example());
+        assert_eq!(Matrix::filled(1, 2, 'x').into_vec(), vec!['x', 'x']);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 2x3 matrix")]
+    fn out_of_bounds() {
+        // In bounds of the storage, but not of the row
+        let _ = example()[(0, 3)];
+    }
+
+    #[test]
+    #[should_panic(expected = "rows of different lengths")]
+    fn ragged_rows() {
+        Matrix::from_rows(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn transpose_and_submatrix() {
+        let m = example();
+        assert_eq!(m.transpose(), Matrix::from_rows(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+        assert_eq!(m.transpose().transpose(), m);
+        assert_eq!(m.submatrix(0..2, 1..3), Matrix::from_rows(vec![vec![2, 3], vec![5, 6]]));
+        assert_eq!(m.submatrix(1..2, 0..0).cols(), 0);
+        let mut swapped = m.clone();
+        swapped.swap_rows(0, 1);
+        assert_eq!(swapped.row(0), m.row(1));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let m = example();
+        let product = &m * &m.transpose();
+        assert_eq!(product, Matrix::from_rows(vec![vec![14, 32], vec![32, 77]]));
+        assert_eq!(&Matrix::identity(2) * &m, m);
+        assert_eq!(&m * &Matrix::identity(3), m);
+        assert_eq!(&m + &m, m.scale(2));
+        assert_eq!(&m - &m, Matrix::zeros(2, 3));
+        assert_eq!(-&m, m.scale(-1));
+        // Powers of [[1, 1], [1, 0]] hold the Fibonacci numbers
+        let fibonacci = Matrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]).pow(10);
+        assert_eq!(fibonacci, Matrix::from_rows(vec![vec![89, 55], vec![55, 34]]));
+        assert_eq!(fibonacci.pow(0), Matrix::identity(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't multiply a 2x3 matrix by a 2x3 one")]
+    fn mismatched_product() {
+        let _ = example() * example();
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Matrix::from_rows(vec![vec![1, -20], vec![300, 4]]).to_string(),
+                   "  1 -20\n300   4");
+        assert_eq!(format!("{:.1}", Matrix::from_rows(vec![vec![0.5, 2.0]])), "0.5 2.0");
+        assert_eq!(Matrix::<f64>::zeros(0, 0).to_string(), "");
+    }
+}
@@ -0,0 +1,108 @@
+// MD5 as RFC 1321 specifies it, for md5-implementation.rs and for HMAC in hmac_pbkdf2.rs.  Md5
+// is a Digest, hashing a message fed to it in pieces of any size.  Unlike the SHAs, its words,
+// and the message's length in the padding, are little-endian.
+
+use super::digest::{Blocks, Digest};
+
+// The integer parts of the sines of 1 to 64 (in radians) times 2^32
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+// How far each step rotates, which is the same for every fourth step of a round
+const SHIFTS: [[u32; 4]; 4] = [[7, 12, 17, 22], [5, 9, 14, 20], [4, 11, 16, 23], [6, 10, 15, 21]];
+
+const INITIAL: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+#[derive(Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    blocks: Blocks,
+}
+
+impl Default for Md5 {
+    fn default() -> Md5 {
+        Md5 { state: INITIAL, blocks: Blocks::new() }
+    }
+}
+
+impl Digest for Md5 {
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        let Md5 { ref mut state, ref mut blocks } = *self;
+        blocks.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 16] {
+        let Md5 { ref mut state, ref mut blocks } = self;
+        blocks.finish(false, |block| compress(state, block));
+        let mut digest = [0; 16];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+// Mix a 64-byte block into the state, in four rounds of sixteen steps
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut words = [0u32; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let sum = a.wrapping_add(f).wrapping_add(K[i]).wrapping_add(words[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(sum.rotate_left(SHIFTS[i / 16][i % 4]));
+    }
+    for (word, value) in state.iter_mut().zip(&[a, b, c, d]) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::alloc::vec::Vec;
+    use super::super::digest::{hex, Digest};
+    use super::Md5;
+
+    #[test]
+    fn streaming() {
+        // However the message is split up, across the blocks and the padding's boundaries, the
+        // digest is the same
+        let message: Vec<u8> = (0..300u32).map(|i| (i * 7 % 251) as u8).collect();
+        for &length in &[55, 56, 63, 64, 65, 119, 120, 128, 300] {
+            let whole = Md5::digest(&message[..length]);
+            for &piece in &[1, 3, 17, 64, 100] {
+                let mut hasher = Md5::default();
+                for chunk in message[..length].chunks(piece) {
+                    hasher.update(chunk);
+                }
+                hasher.update(&[]);
+                assert_eq!(hasher.finalize(), whole, "{} bytes in pieces of {}", length, piece);
+            }
+        }
+        let mut hasher = Md5::default();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(hex(&hasher.finalize()), "7707d6ae4e027c70eea2a935c2296f21");
+    }
+}
@@ -0,0 +1,179 @@
+// Measuring how much memory a task allocates, for the tasks whose point is to use little of it:
+// a sieve that needs a byte per number, a sort that works in place.  A task that measures installs
+// Meter as its global allocator with the mem-profile feature on, so that every allocation goes
+// through it and is counted, and a test can check the most a call ever had allocated at once:
+//
+//     #[cfg(all(test, feature = "mem-profile"))]
+//     #[global_allocator]
+//     static METER: utils::memory::Meter = utils::memory::Meter;
+//
+//     #[test]
+//     #[cfg_attr(not(feature = "mem-profile"), ignore)]
+//     fn in_place() {
+//         let mut numbers: Vec<u32> = (0..1000).rev().collect();
+//         utils::memory::assert_peak_at_most(0, || heap_sort(&mut numbers));
+//     }
+//
+// Without the feature nothing is counted, so such tests are ignored then, and are run with
+// `cargo test --features mem-profile`.  The allocator is left to the task rather than installed
+// here because a binary can only have one, and some include utils more than once.
+//
+// The counts are kept per thread, since tests run side by side on threads of their own.  So what
+// a call allocates on threads it starts isn't counted, and memory freed on another thread than the
+// one that allocated it counts as freed where it was freed.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hint::black_box;
+
+// The system allocator, counting what each thread has allocated
+pub struct Meter;
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) }; // Bytes allocated and not yet freed
+    static PEAK: Cell<isize> = const { Cell::new(0) };    // The most CURRENT has been
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// Add `bytes` to what the current thread has allocated, which is negative for memory freed.  The
+// counts are left alone while the thread is being torn down and they're gone.
+fn count(bytes: isize, allocations: usize) {
+    let _ = CURRENT.try_with(|current| {
+        current.set(current.get() + bytes);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+    });
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + allocations));
+}
+
+unsafe impl GlobalAlloc for Meter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let block = System.alloc(layout);
+        if !block.is_null() {
+            count(layout.size() as isize, 1);
+        }
+        block
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let block = System.alloc_zeroed(layout);
+        if !block.is_null() {
+            count(layout.size() as isize, 1);
+        }
+        block
+    }
+
+    unsafe fn dealloc(&self, block: *mut u8, layout: Layout) {
+        System.dealloc(block, layout);
+        count(-(layout.size() as isize), 0);
+    }
+
+    unsafe fn realloc(&self, block: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let moved = System.realloc(block, layout, new_size);
+        if !moved.is_null() {
+            count(new_size as isize - layout.size() as isize, 1);
+        }
+        moved
+    }
+}
+
+// What a call allocated
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    pub peak: usize,        // The most bytes it had allocated at once
+    pub allocations: usize, // How many times it allocated or grew an allocation
+    pub leaked: isize,      // How many more bytes were allocated than freed by the time it returned
+}
+
+// Whether allocations are being counted, which they are once the binary installs Meter
+pub fn enabled() -> bool {
+    let before = ALLOCATIONS.with(Cell::get);
+    drop(black_box(Box::new(0u8)));
+    ALLOCATIONS.with(Cell::get) != before
+}
+
+// Call `f`, and say how much it allocated, on this thread, over what was allocated already.
+// Unless Meter is installed, the usage is always zero.
+pub fn measure<R, F: FnOnce() -> R>(f: F) -> (R, Usage) {
+    let start = CURRENT.with(Cell::get);
+    let start_allocations = ALLOCATIONS.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(start));
+    let result = f();
+    let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+    let usage = Usage {
+        peak: (peak - start) as usize,
+        allocations: ALLOCATIONS.with(Cell::get) - start_allocations,
+        leaked: CURRENT.with(Cell::get) - start,
+    };
+    (result, usage)
+}
+
+// Call `f`, and panic if it ever had more than `limit` bytes allocated at once
+pub fn assert_peak_at_most<R, F: FnOnce() -> R>(limit: usize, f: F) -> R {
+    let (result, usage) = measure(f);
+    assert!(usage.peak <= limit, "allocated {} bytes at once, more than the {} allowed",
+            usage.peak, limit);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{assert_peak_at_most, enabled, measure, Usage};
+    use std::panic;
+
+    // The tests that count only count in `cargo test --bin utils`, and the tasks' binaries that
+    // install Meter.  The rest, which include these tests with utils, pass them by.
+
+    #[test]
+    #[cfg_attr(not(feature = "mem-profile"), ignore)]
+    fn counts() {
+        if !enabled() {
+            return;
+        }
+        let (_, usage) = measure(|| {
+            let small = vec![0u8; 1000];
+            drop(vec![0u8; 5000]);
+            small
+        });
+        assert_eq!(usage.peak, 6000);
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.leaked, 1000);
+        let (_, nothing) = measure(|| 1 + 1);
+        assert_eq!(nothing, Usage::default());
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "mem-profile"), ignore)]
+    fn nested() {
+        if !enabled() {
+            return;
+        }
+        let (inner, outer) = measure(|| {
+            let before = vec![0u8; 3000];
+            let (_, inner) = measure(|| drop(vec![0u8; 100]));
+            drop(before);
+            inner
+        });
+        assert_eq!(inner.peak, 100);
+        // The inner call's allocation came on top of the 3000 bytes already there
+        assert_eq!(outer.peak, 3100);
+        assert_eq!(outer.leaked, 0);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "mem-profile"), ignore)]
+    fn too_much() {
+        if !enabled() {
+            return;
+        }
+        let error = panic::catch_unwind(|| assert_peak_at_most(10, || vec![0u8; 64])).unwrap_err();
+        assert_eq!(error.downcast_ref::<String>().unwrap(),
+                   "allocated 64 bytes at once, more than the 10 allowed");
+    }
+
+    #[test]
+    fn without_the_feature() {
+        if !enabled() {
+            assert_eq!(measure(|| vec![0u8; 100]).1, Usage::default());
+        }
+    }
+}
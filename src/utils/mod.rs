@@ -0,0 +1,58 @@
+// Code shared between tasks, for the things several of them need.  A task, from its category's
+// directory, uses it with
+//
+//     #[path = "../utils/mod.rs"]
+//     #[allow(dead_code)]
+//     mod utils;
+//
+// and then, for instance, utils::rng::Rng or utils::matrix::Matrix.  The modules' tests run with
+// `cargo test --bin utils`.
+//
+// The modules most tasks use start with documentation whose examples run as doc tests, with
+// `cargo test --manifest-path no_std/Cargo.toml --doc`: cargo only runs them for a library, and
+// no_std/ is where src/utils is one.  Each starts with a hidden `# extern crate utils;`, so that
+// it uses that library wherever it's found: a crate with doc tests of its own that includes
+// src/utils as a module, such as wasm/, runs them too, and has no_std/ as a dev-dependency for
+// them.  The tasks are binaries, so the examples in their documentation run from tasks/, a
+// library of the tasks that have an API of their own.
+//
+// None of it needs more of the standard library than core and alloc, so with the std feature
+// turned off it also builds as a no_std library, for embedded targets: see no_std/Cargo.toml.
+// The few parts that do need std, such as seeding a generator from the time, capturing what a
+// task prints, reading the fixtures or animating it in a terminal, are left out then.
+// The modules take everything from core and alloc through `super::`, which works both when this
+// is a module of a task and when it is a crate of its own.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+// A no_std crate has core already
+#[cfg(feature = "std")]
+extern crate core;
+
+pub mod args;
+pub mod bignum;
+pub mod canvas;
+pub mod complex;
+#[cfg(feature = "std")]
+pub mod capture;
+pub mod digest;
+#[cfg(feature = "std")]
+pub mod fixture;
+pub mod graph;
+pub mod heap;
+pub mod image;
+pub mod matrix;
+pub mod md5;
+#[cfg(feature = "std")]
+pub mod memory;
+pub mod parse;
+pub mod property;
+pub mod regex;
+pub mod rng;
+pub mod sha1;
+pub mod sha256;
+#[cfg(feature = "std")]
+pub mod term;
+#[macro_use]
+pub mod trace;
@@ -0,0 +1,413 @@
+//! Parser combinators, for the tasks that parse text (arithmetic expressions, S-expressions, JSON,
+//! configuration files): parsers for a character or a word, and functions that put parsers together
+//! into ones for sequences, alternatives and lists.  A parser is just a function from the text left
+//! to parse to what it parsed and the text after it, so simple grammars are built up in place:
+//!
+//! ```
+//! # extern crate utils;
+//! # use utils::parse::{char, delimited, parse, separated, take_while1, try_map};
+//! let integer = try_map(take_while1("a digit", |c| c.is_ascii_digit()), "an integer",
+//!                       |digits| digits.parse::<u32>().ok());
+//! let list = delimited(char('['), separated(integer, char(',')), char(']'));
+//! assert_eq!(parse(list, "[1,2,3]"), Ok(vec![1, 2, 3]));
+//! ```
+//!
+//! and recursive ones as ordinary functions that call each other, with signatures like
+//! `fn expression<'a>(input: &'a str) -> Result<'a, Expression>`.
+//!
+//! As in Parsec, an alternative is only tried if the parser before it failed without getting
+//! anywhere.  Once a parser is partway through, it's committed, and where it went wrong is the
+//! error: "line 3, column 9: expected ']'" rather than a complaint about the start of the list.
+
+use super::alloc::string::{String, ToString};
+use super::alloc::vec::Vec;
+use super::core::fmt;
+use super::core::result;
+#[cfg(feature = "std")]
+use std::error;
+
+// What running a parser on some text gives: what it parsed and the text after it, or why it failed
+pub type Result<'a, T> = result::Result<(T, &'a str), Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    left: usize,           // How many bytes of the text were left where the parser failed
+    expected: Vec<String>, // What would have been fine there instead
+}
+
+// Where something is in a text.  Lines and columns count from 1, and columns in characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub offset: usize, // In bytes, from the start
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Error {
+    // An error for a parser that wanted `what` at the start of `input`, such as "a digit"
+    pub fn new(input: &str, what: &str) -> Error {
+        Error { left: input.len(), expected: vec![what.to_string()] }
+    }
+
+    // What the parser wanted where it failed
+    pub fn expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    // Where it failed, in `text`, the whole text that was being parsed
+    pub fn position(&self, text: &str) -> Position {
+        let offset = text.len().saturating_sub(self.left);
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Position {
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    // The error with its position in `text`, for showing to the user
+    pub fn describe(&self, text: &str) -> String {
+        let position = self.position(text);
+        format!("line {}, column {}: {}", position.line, position.column, self)
+    }
+
+    // The error to report when two alternatives both fail: the one that got further, or both if
+    // they failed at the same place
+    fn or(mut self, other: Error) -> Error {
+        if other.left < self.left {
+            return other;
+        }
+        if other.left == self.left {
+            for what in other.expected {
+                if !self.expected.contains(&what) {
+                    self.expected.push(what);
+                }
+            }
+        }
+        self
+    }
+
+    // Whether the parser that returned this failed right at the start of `input`, without parsing
+    // anything, so that something else can be tried there instead
+    fn at_start_of(&self, input: &str) -> bool {
+        self.left == input.len()
+    }
+}
+
+impl fmt::Display for Error {
+    // "expected a digit", "expected '+' or '-'" or "expected 'a', 'b' or 'c'"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected ")?;
+        for (i, what) in self.expected.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", if i + 1 == self.expected.len() { " or " } else { ", " })?;
+            }
+            write!(f, "{}", what)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {}
+
+// Parse the whole of `text` with `parser`, so that anything left over after it is an error
+pub fn parse<'a, T, P>(parser: P, text: &'a str) -> result::Result<T, Error>
+    where P: Fn(&'a str) -> Result<'a, T>
+{
+    let (value, rest) = parser(text)?;
+    end(rest)?;
+    Ok(value)
+}
+
+// Parse nothing, at the end of the text
+pub fn end<'a>(input: &'a str) -> Result<'a, ()> {
+    if input.is_empty() {
+        Ok(((), input))
+    } else {
+        Err(Error::new(input, "the end of the text"))
+    }
+}
+
+// The character `c`
+pub fn char<'a>(c: char) -> impl Fn(&'a str) -> Result<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(next) if next == c => Ok((c, &input[c.len_utf8()..])),
+        _ => Err(Error::new(input, &format!("{:?}", c))),
+    }
+}
+
+// A character `test` accepts, which is described as `what` in errors
+pub fn satisfy<'a, F>(what: &'static str, test: F) -> impl Fn(&'a str) -> Result<'a, char>
+    where F: Fn(char) -> bool
+{
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if test(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => Err(Error::new(input, what)),
+    }
+}
+
+// The exact text `word`
+pub fn tag<'a>(word: &'static str) -> impl Fn(&'a str) -> Result<'a, &'a str> {
+    move |input: &'a str| {
+        if input.starts_with(word) {
+            Ok(input.split_at(word.len()))
+        } else {
+            Err(Error::new(input, &format!("{:?}", word)))
+        }
+    }
+}
+
+// As many characters as `test` accepts, and at least one, which is described as `what` in errors
+pub fn take_while1<'a, F>(what: &'static str, test: F) -> impl Fn(&'a str) -> Result<'a, &'a str>
+    where F: Fn(char) -> bool
+{
+    move |input: &'a str| match input.find(|c| !test(c)).unwrap_or(input.len()) {
+        0 => Err(Error::new(input, what)),
+        end => Ok(input.split_at(end)),
+    }
+}
+
+// Any whitespace, or none
+pub fn spaces<'a>(input: &'a str) -> Result<'a, &'a str> {
+    let rest = input.trim_start();
+    Ok((&input[..input.len() - rest.len()], rest))
+}
+
+// `parser`, and any whitespace after it.  Grammars that allow spaces between their tokens wrap each
+// token in this, and skip any spaces at the very start with preceded(spaces, ...).
+pub fn token<'a, T, P>(parser: P) -> impl Fn(&'a str) -> Result<'a, T>
+    where P: Fn(&'a str) -> Result<'a, T>
+{
+    move |input: &'a str| {
+        let (value, input) = parser(input)?;
+        Ok((value, input.trim_start()))
+    }
+}
+
+// What `parser` parses, converted by `f`
+pub fn map<'a, T, U, P, F>(parser: P, f: F) -> impl Fn(&'a str) -> Result<'a, U>
+    where P: Fn(&'a str) -> Result<'a, T>, F: Fn(T) -> U
+{
+    move |input: &'a str| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+// What `parser` parses, converted by `f`, which fails if it returns None.  The failure is
+// described as `what` and points at where `parser` started, as in "expected a number" for digits
+// that don't fit.
+pub fn try_map<'a, T, U, P, F>(parser: P, what: &'static str, f: F)
+                              -> impl Fn(&'a str) -> Result<'a, U>
+    where P: Fn(&'a str) -> Result<'a, T>, F: Fn(T) -> Option<U>
+{
+    move |input: &'a str| {
+        let (value, rest) = parser(input)?;
+        match f(value) {
+            Some(converted) => Ok((converted, rest)),
+            None => Err(Error::new(input, what)),
+        }
+    }
+}
+
+// `first` and then `second`, giving what both parsed
+pub fn pair<'a, T, U, P, Q>(first: P, second: Q) -> impl Fn(&'a str) -> Result<'a, (T, U)>
+    where P: Fn(&'a str) -> Result<'a, T>, Q: Fn(&'a str) -> Result<'a, U>
+{
+    move |input: &'a str| {
+        let (a, input) = first(input)?;
+        let (b, input) = second(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+// `first` and then `second`, giving what `second` parsed
+pub fn preceded<'a, T, U, P, Q>(first: P, second: Q) -> impl Fn(&'a str) -> Result<'a, U>
+    where P: Fn(&'a str) -> Result<'a, T>, Q: Fn(&'a str) -> Result<'a, U>
+{
+    map(pair(first, second), |(_, b)| b)
+}
+
+// `first` and then `second`, giving what `first` parsed
+pub fn terminated<'a, T, U, P, Q>(first: P, second: Q) -> impl Fn(&'a str) -> Result<'a, T>
+    where P: Fn(&'a str) -> Result<'a, T>, Q: Fn(&'a str) -> Result<'a, U>
+{
+    map(pair(first, second), |(a, _)| a)
+}
+
+// `parser` between `open` and `close`, such as brackets, giving what `parser` parsed
+pub fn delimited<'a, O, T, C, P, Q, R>(open: P, parser: Q, close: R)
+                                      -> impl Fn(&'a str) -> Result<'a, T>
+    where P: Fn(&'a str) -> Result<'a, O>,
+          Q: Fn(&'a str) -> Result<'a, T>,
+          R: Fn(&'a str) -> Result<'a, C>
+{
+    preceded(open, terminated(parser, close))
+}
+
+// `first`, or if that fails without getting anywhere, `second`
+pub fn or<'a, T, P, Q>(first: P, second: Q) -> impl Fn(&'a str) -> Result<'a, T>
+    where P: Fn(&'a str) -> Result<'a, T>, Q: Fn(&'a str) -> Result<'a, T>
+{
+    move |input: &'a str| match first(input) {
+        Err(ref error) if error.at_start_of(input) => {
+            second(input).map_err(|other| error.clone().or(other))
+        }
+        result => result,
+    }
+}
+
+// `parser` if it's there, or nothing
+pub fn optional<'a, T, P>(parser: P) -> impl Fn(&'a str) -> Result<'a, Option<T>>
+    where P: Fn(&'a str) -> Result<'a, T>
+{
+    move |input: &'a str| match parser(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(ref error) if error.at_start_of(input) => Ok((None, input)),
+        Err(error) => Err(error),
+    }
+}
+
+// `parser` as many times as it can be, which may be none.  It stops if `parser` succeeds without
+// parsing anything, which it would otherwise do for ever.
+pub fn many<'a, T, P>(parser: P) -> impl Fn(&'a str) -> Result<'a, Vec<T>>
+    where P: Fn(&'a str) -> Result<'a, T>
+{
+    move |mut input: &'a str| {
+        let mut values = Vec::new();
+        loop {
+            match parser(input) {
+                Ok((_, rest)) if rest.len() == input.len() => return Ok((values, input)),
+                Ok((value, rest)) => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(ref error) if error.at_start_of(input) => return Ok((values, input)),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+// `parser` as many times as it can be, and at least once
+pub fn many1<'a, T, P>(parser: P) -> impl Fn(&'a str) -> Result<'a, Vec<T>>
+    where P: Fn(&'a str) -> Result<'a, T>
+{
+    move |input: &'a str| {
+        let (first, input) = parser(input)?;
+        let (rest, input) = many(&parser)(input)?;
+        let mut values = vec![first];
+        values.extend(rest);
+        Ok((values, input))
+    }
+}
+
+// Any number of `item`s, which may be none, with a `separator` between each one and the next,
+// such as the elements of a list with commas between them.  A separator has to be followed by an
+// item.
+pub fn separated<'a, T, S, P, Q>(item: P, separator: Q) -> impl Fn(&'a str) -> Result<'a, Vec<T>>
+    where P: Fn(&'a str) -> Result<'a, T>, Q: Fn(&'a str) -> Result<'a, S>
+{
+    move |input: &'a str| {
+        let (first, mut input) = match item(input) {
+            Ok(parsed) => parsed,
+            Err(ref error) if error.at_start_of(input) => return Ok((Vec::new(), input)),
+            Err(error) => return Err(error),
+        };
+        let mut values = vec![first];
+        loop {
+            match separator(input) {
+                Ok((_, rest)) => {
+                    let (value, rest) = item(rest)?;
+                    values.push(value);
+                    input = rest;
+                }
+                Err(ref error) if error.at_start_of(input) => return Ok((values, input)),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{char, delimited, many, many1, map, optional, or, pair, parse, satisfy, separated,
+                spaces, tag, take_while1, token, try_map, Error, Position, Result};
+
+    fn integer<'a>(input: &'a str) -> Result<'a, u32> {
+        try_map(take_while1("a digit", |c| c.is_ascii_digit()), "an integer",
+                |digits: &str| digits.parse().ok())(input)
+    }
+
+    #[test]
+    fn characters() {
+        assert_eq!(char('a')("abc"), Ok(('a', "bc")));
+        assert_eq!(char('é')("é!"), Ok(('é', "!")));
+        assert_eq!(char('a')("b").unwrap_err().to_string(), "expected 'a'");
+        assert_eq!(satisfy("a vowel", |c| "aeiou".contains(c))("ex"), Ok(('e', "x")));
+        assert_eq!(satisfy("a vowel", |c| "aeiou".contains(c))("").unwrap_err().to_string(),
+                   "expected a vowel");
+        assert_eq!(tag("let")("let x"), Ok(("let", " x")));
+        assert_eq!(tag("let")("le").unwrap_err().to_string(), "expected \"let\"");
+        assert_eq!(take_while1("a digit", |c| c.is_ascii_digit())("42x"), Ok(("42", "x")));
+        assert!(take_while1("a digit", |c| c.is_ascii_digit())("x").is_err());
+        assert_eq!(spaces(" \n x"), Ok((" \n ", "x")));
+        assert_eq!(token(char('a'))("a  b"), Ok(('a', "b")));
+    }
+
+    #[test]
+    fn combinations() {
+        assert_eq!(integer("123;"), Ok((123, ";")));
+        assert_eq!(integer("99999999999").unwrap_err().to_string(), "expected an integer");
+        assert_eq!(pair(char('a'), integer)("a1"), Ok((('a', 1), "")));
+        assert_eq!(map(integer, |n| n * 2)("21"), Ok((42, "")));
+        assert_eq!(delimited(char('('), integer, char(')'))("(7)"), Ok((7, "")));
+        assert_eq!(optional(char('-'))("5"), Ok((None, "5")));
+        assert_eq!(optional(char('-'))("-5"), Ok((Some('-'), "5")));
+        assert_eq!(many(char('a'))("aab"), Ok((vec!['a', 'a'], "b")));
+        assert_eq!(many(char('a'))("b"), Ok((vec![], "b")));
+        // A parser that parses nothing doesn't go on for ever
+        assert_eq!(many(spaces)("x"), Ok((vec![], "x")));
+        assert!(many1(char('a'))("b").is_err());
+        assert_eq!(many1(char('a'))("ab"), Ok((vec!['a'], "b")));
+    }
+
+    #[test]
+    fn lists() {
+        let list = || delimited(token(char('[')), separated(token(integer), token(char(','))),
+                                char(']'));
+        assert_eq!(parse(list(), "[1, 2 ,3]"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse(list(), "[ ]"), Ok(vec![]));
+        // After a comma, another element has to follow
+        let error = parse(list(), "[1, 2, ]").unwrap_err();
+        assert_eq!(error.to_string(), "expected a digit");
+        assert_eq!(error.position("[1, 2, ]").offset, 7);
+        assert_eq!(parse(list(), "[1] x").unwrap_err().to_string(), "expected the end of the text");
+    }
+
+    #[test]
+    fn alternatives() {
+        let sign = || or(char('+'), char('-'));
+        assert_eq!(sign()("-"), Ok(('-', "")));
+        assert_eq!(sign()("*").unwrap_err().to_string(), "expected '+' or '-'");
+        let three = or(or(char('a'), char('b')), char('c'));
+        assert_eq!(three("d").unwrap_err().to_string(), "expected 'a', 'b' or 'c'");
+        // Once "ab" is partway through, "ac" isn't tried, and the error is where "ab" went wrong
+        let committed = or(pair(char('a'), char('b')), pair(char('a'), char('c')));
+        let error = committed("ac").unwrap_err();
+        assert_eq!(error.expected(), ["'b'"]);
+        assert_eq!(error.position("ac").offset, 1);
+        assert_eq!(or(tag("ab"), tag("ac"))("ac"), Ok(("ac", "")));
+    }
+
+    #[test]
+    fn positions() {
+        let text = "[1,\n 22,\n x]";
+        let items = separated(token(integer), token(char(',')));
+        let error = parse(delimited(token(char('[')), items, char(']')), text).unwrap_err();
+        assert_eq!(error.position(text), Position { offset: 10, line: 3, column: 2 });
+        assert_eq!(error.describe(text), "line 3, column 2: expected a digit");
+        // Columns count characters rather than bytes
+        let position = Error::new("x", "y").position("éx");
+        assert_eq!(position, Position { offset: 2, line: 1, column: 2 });
+    }
+}
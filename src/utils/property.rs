@@ -0,0 +1,322 @@
+// Property based testing, as in Haskell's QuickCheck: rather than checking a task against a few
+// examples picked by hand, check that something holds of its results for a hundred random inputs,
+// and when it doesn't, shrink the failing input down to a small one that still fails.  Random
+// inputs find the cases nobody thought to write down (the empty list, duplicates, negative
+// numbers), which is where the subtle bugs are.
+//
+// Most tasks only need one of the ready made properties, one line in a test:
+//
+//     utils::property::sorts_in_place(heap_sort);
+//     utils::property::roundtrips(|s: &String| compress(s), |c| decompress(c));
+//
+// and check() takes any other property of inputs that are Arbitrary.
+
+use super::alloc::string::String;
+use super::alloc::vec::Vec;
+use super::core::char;
+use super::core::fmt::Debug;
+use super::rng::Rng;
+
+// How many inputs check() tries
+pub static CASES: usize = 100;
+
+// How many times a failing input is shrunk at most, in case shrinking goes round in circles
+static MAX_SHRINKS: usize = 1000;
+
+// Types check() can generate inputs of
+pub trait Arbitrary: Clone + Debug {
+    // A random value, roughly as big as `size`: numbers about that far from zero, collections
+    // about that long
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self;
+
+    // Values a little simpler than this one, with the simplest first
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+// Check that `property` holds of CASES inputs, from small to large, panicking with the simplest
+// failing input it can find if it doesn't.  The inputs are the same on every run, so that a
+// failure can always be reproduced.
+pub fn check<T: Arbitrary, F: Fn(&T) -> bool>(property: F) {
+    let mut rng = Rng::new(0);
+    for size in 0..CASES {
+        let input = T::arbitrary(&mut rng, size);
+        if !property(&input) {
+            let simplest = shrink_failure(input.clone(), &property);
+            panic!("property failed for {:?} (shrunk from {:?})", simplest, input);
+        }
+    }
+}
+
+// Keep taking the first simpler input that still fails, until none does
+fn shrink_failure<T: Arbitrary, F: Fn(&T) -> bool>(mut failing: T, property: &F) -> T {
+    for _ in 0..MAX_SHRINKS {
+        match failing.shrink().into_iter().find(|simpler| !property(simpler)) {
+            Some(simpler) => failing = simpler,
+            None => break,
+        }
+    }
+    failing
+}
+
+// Check that `sort` sorts: what it returns is in order, and has the same elements as what it was
+// given.
+pub fn sorts<F: Fn(&[i32]) -> Vec<i32>>(sort: F) {
+    check(|v: &Vec<i32>| is_sorted_permutation(v, &sort(v)))
+}
+
+// The same for a sort that sorts a slice in place
+pub fn sorts_in_place<F: Fn(&mut [i32])>(sort: F) {
+    check(|v: &Vec<i32>| {
+        let mut sorted = v.clone();
+        sort(&mut sorted);
+        is_sorted_permutation(v, &sorted)
+    })
+}
+
+fn is_sorted_permutation(input: &[i32], output: &[i32]) -> bool {
+    let mut expected = input.to_vec();
+    expected.sort();
+    output == &expected[..]
+}
+
+// Check that `decode` undoes `encode`, as it should for encodings, compression and ciphers
+pub fn roundtrips<T, E, F, G>(encode: F, decode: G)
+    where T: Arbitrary + PartialEq, F: Fn(&T) -> E, G: Fn(&E) -> T
+{
+    check(|input: &T| decode(&encode(input)) == *input)
+}
+
+// Check that `invariant` holds of a data structure made by `new` after each operation of any
+// sequence of them, as a binary search tree's keys must stay in order through inserts and
+// removes.  An operation is any Arbitrary value, such as an Option<i32> for "push this" or "pop",
+// and `apply` carries it out.
+pub fn keeps_invariant<S, O, N, A, I>(new: N, apply: A, invariant: I)
+    where O: Arbitrary, N: Fn() -> S, A: Fn(&mut S, &O), I: Fn(&S) -> bool
+{
+    check(|operations: &Vec<O>| {
+        let mut structure = new();
+        operations.iter().all(|operation| {
+            apply(&mut structure, operation);
+            invariant(&structure)
+        })
+    })
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng, _: usize) -> bool {
+        rng.gen_bool(0.5)
+    }
+
+    fn shrink(&self) -> Vec<bool> {
+        if *self { vec![false] } else { Vec::new() }
+    }
+}
+
+macro_rules! arbitrary_int {
+    ($($t:ty)*) => ($(
+        impl Arbitrary for $t {
+            // Mostly small numbers, now and then one from anywhere in the type's range
+            fn arbitrary(rng: &mut Rng, size: usize) -> $t {
+                if rng.gen_bool(0.1) {
+                    rng.next_u64() as $t
+                } else {
+                    let low = if <$t>::MIN == 0 { 0 } else { -(size as i64) };
+                    rng.gen_range(low, size as i64 + 1) as $t
+                }
+            }
+
+            // Towards zero, in big steps and then small ones
+            fn shrink(&self) -> Vec<$t> {
+                let x = *self;
+                if x == 0 {
+                    return Vec::new();
+                }
+                let mut simpler = vec![0, x / 2, if x > 0 { x - 1 } else { x + 1 }];
+                simpler.dedup();
+                simpler
+            }
+        }
+    )*)
+}
+
+arbitrary_int! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+
+impl Arbitrary for char {
+    // Mostly printable ASCII, now and then any character at all
+    fn arbitrary(rng: &mut Rng, _: usize) -> char {
+        if rng.gen_bool(0.8) {
+            return rng.gen_range(b' ', b'~' + 1) as char;
+        }
+        loop {
+            if let Some(ch) = char::from_u32(rng.gen_range(0, 0x110000)) {
+                return ch;
+            }
+        }
+    }
+
+    fn shrink(&self) -> Vec<char> {
+        if *self == 'a' { Vec::new() } else { vec!['a'] }
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Vec<T> {
+        let len = rng.gen_range(0, size + 1);
+        (0..len).map(|_| T::arbitrary(rng, size)).collect()
+    }
+
+    // Without either half, then without each element, then with each element shrunk
+    fn shrink(&self) -> Vec<Vec<T>> {
+        let mut simpler = Vec::new();
+        if self.len() > 1 {
+            let half = self.len() / 2;
+            simpler.push(self[half..].to_vec());
+            simpler.push(self[..half].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut without = self.clone();
+            without.remove(i);
+            simpler.push(without);
+        }
+        for (i, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut with = self.clone();
+                with[i] = smaller;
+                simpler.push(with);
+            }
+        }
+        simpler
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut Rng, size: usize) -> String {
+        Vec::<char>::arbitrary(rng, size).into_iter().collect()
+    }
+
+    fn shrink(&self) -> Vec<String> {
+        let chars: Vec<char> = self.chars().collect();
+        chars.shrink().into_iter().map(|chars| chars.into_iter().collect()).collect()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Option<T> {
+        if rng.gen_bool(0.25) { None } else { Some(T::arbitrary(rng, size)) }
+    }
+
+    fn shrink(&self) -> Vec<Option<T>> {
+        match *self {
+            Some(ref x) => Some(None).into_iter().chain(x.shrink().into_iter().map(Some)).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+macro_rules! arbitrary_tuple {
+    ($($name:ident $index:tt),*) => (
+        impl<$($name: Arbitrary),*> Arbitrary for ($($name,)*) {
+            fn arbitrary(rng: &mut Rng, size: usize) -> ($($name,)*) {
+                ($($name::arbitrary(rng, size),)*)
+            }
+
+            // With one item shrunk at a time
+            fn shrink(&self) -> Vec<($($name,)*)> {
+                let mut simpler = Vec::new();
+                $(for item in self.$index.shrink() {
+                    let mut tuple = self.clone();
+                    tuple.$index = item;
+                    simpler.push(tuple);
+                })*
+                simpler
+            }
+        }
+    )
+}
+
+arbitrary_tuple!(A 0, B 1);
+arbitrary_tuple!(A 0, B 1, C 2);
+
+#[cfg(test)]
+mod test {
+    use super::{check, keeps_invariant, roundtrips, sorts, sorts_in_place, Arbitrary};
+    use super::super::rng::Rng;
+
+    #[test]
+    fn generate() {
+        let mut rng = Rng::new(1);
+        for size in 0..50 {
+            let v = Vec::<i32>::arbitrary(&mut rng, size);
+            assert!(v.len() <= size);
+            let (a, b): (u8, Option<bool>) = Arbitrary::arbitrary(&mut rng, size);
+            let _ = (a, b);
+            let s = String::arbitrary(&mut rng, size);
+            assert!(s.chars().count() <= size);
+        }
+        // Small inputs are small
+        assert_eq!(Vec::<i32>::arbitrary(&mut rng, 0), vec![]);
+    }
+
+    #[test]
+    fn shrink() {
+        assert_eq!(10i32.shrink(), vec![0, 5, 9]);
+        assert_eq!((-3i64).shrink(), vec![0, -1, -2]);
+        assert_eq!(1u8.shrink(), vec![0]);
+        assert!(0usize.shrink().is_empty());
+        assert_eq!(vec![2u32, 0].shrink(), vec![vec![0], vec![2], vec![0], vec![2], vec![0, 0],
+                                               vec![1, 0]]);
+        assert_eq!(Some(true).shrink(), vec![None, Some(false)]);
+        assert_eq!((true, 'b').shrink(), vec![(false, 'b'), (true, 'a')]);
+        assert_eq!("ab".to_string().shrink(), vec!["b", "a", "b", "a", "aa"]);
+    }
+
+    #[test]
+    fn properties_that_hold() {
+        check(|&(a, b): &(i64, i64)| a.wrapping_add(b) == b.wrapping_add(a));
+        sorts(|v| {
+            let mut sorted = v.to_vec();
+            sorted.sort();
+            sorted
+        });
+        sorts_in_place(|v| v.sort());
+        roundtrips(|s: &String| s.chars().rev().collect::<String>(),
+                   |s| s.chars().rev().collect());
+        keeps_invariant(Vec::new, |v: &mut Vec<i32>, &x: &i32| v.push(x), |v| !v.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed for [10] ")]
+    fn shrinks_what_fails() {
+        check(|v: &Vec<i32>| v.iter().all(|&x| x < 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed for [0, 0] ")]
+    fn finds_broken_sorts() {
+        // Loses duplicates
+        sorts_in_place(|v| {
+            v.sort();
+            if v.windows(2).any(|pair| pair[0] == pair[1]) {
+                v[0] += 1;
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed for [None] ")]
+    fn finds_broken_invariants() {
+        // A stack whose length goes wrong when popping an empty stack
+        keeps_invariant(|| (Vec::new(), 0),
+                        |&mut (ref mut stack, ref mut len): &mut (Vec<i32>, usize),
+                         push: &Option<i32>| {
+                            match *push {
+                                Some(x) => stack.push(x),
+                                None => { stack.pop(); }
+                            }
+                            *len = stack.len() + push.is_none() as usize;
+                        },
+                        |&(ref stack, len)| stack.len() == len);
+    }
+}
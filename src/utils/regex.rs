@@ -0,0 +1,690 @@
+//! Regular expressions, for the tasks that match text against patterns, without the regex crate.
+//! A pattern is compiled to a nondeterministic finite automaton (Thompson's construction), which
+//! is run by keeping track of every state it could be in at once, stepping them all along the
+//! text together, with Pike's extension for remembering where groups matched (see
+//! https://swtch.com/~rsc/regexp/regexp2.html).  Nothing is ever backtracked over, so matching
+//! takes time proportional to the length of the text times the size of the pattern, however the
+//! pattern is written: (a?){n}a{n} against n a's, which takes a backtracking matcher 2^n steps,
+//! takes this one about n^2.
+//!
+//! The syntax is the usual one, less what can't be matched that way, such as backreferences:
+//!
+//! ```text
+//! a \. \n \t      a character, or one that's escaped
+//! .               any character but a newline
+//! [a-z_] [^0-9]   a character in a class, or one that isn't
+//! \d \w \s        a digit, a word character or whitespace, and \D \W \S for anything else
+//! xy x|y          one thing and then another, or one thing or another
+//! x* x+ x?        repetition, which matches as much as it can, or with another ?, as little
+//! (x) (?:x)       a group, which captures what it matches unless it starts with ?:
+//! ^ $             the start and end of the text
+//! ```
+//!
+//! Of the matches there are, the one found is the leftmost, and the first of those that a
+//! backtracking matcher would try, so it's the same match as Perl's or the regex crate's.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::regex::Regex;
+//!
+//! let date = Regex::new(r"(\d\d\d\d)-(\d\d)-(\d\d)").unwrap();
+//! let text = "released on 2026-10-14, fixed on 2026-10-15";
+//! assert_eq!(date.find(text), Some((12, 22)));
+//! let groups = date.captures(text).unwrap();
+//! assert_eq!(groups[1].map(|(start, end)| &text[start..end]), Some("2026"));
+//! assert_eq!(date.find_all(text).len(), 2);
+//! assert_eq!(date.replace_all("2026-10-14", "$3/$2/$1"), "14/10/2026");
+//! assert!(Regex::new("a(b").is_err());
+//! ```
+
+use super::alloc::boxed::Box;
+use super::alloc::string::String;
+use super::alloc::vec::Vec;
+use super::core::{fmt, mem};
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Clone, Debug)]
+pub struct Regex {
+    program: Vec<Inst>,
+    groups: usize, // Including the whole match, as group 0
+}
+
+// Where each group matched, in bytes: the whole match first, and then each group, which is None if
+// it wasn't part of the match
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+// A mistake in a pattern, and where it is, in bytes from the start
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    pub position: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {}
+
+// Characters in any of some ranges, or with `negated`, in none of them
+#[derive(Clone, Debug, PartialEq)]
+struct Class {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl Class {
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|&(low, high)| low <= c && c <= high) != self.negated
+    }
+}
+
+static DIGITS: &[(char, char)] = &[('0', '9')];
+static WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+static SPACE: &[(char, char)] = &[('\t', '\r'), (' ', ' ')];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Repeat {
+    Star,
+    Plus,
+    Question,
+}
+
+// A parsed pattern
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat(Repeat, bool, Box<Node>), // Greedy or not
+    Group(Option<usize>, Box<Node>), // The group's number, if it captures
+}
+
+// An instruction of the compiled automaton.  Each state is one of these, and goes on to the next
+// one, or for Split and Jump, the ones they say.
+#[derive(Clone, Debug, PartialEq)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Split(usize, usize), // Both, with the first preferred
+    Jump(usize),
+    Save(usize), // Remembers the position, as the start or end of a group
+    Start,
+    End,
+    Match,
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    position: usize,
+    groups: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.pattern[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn error<T>(&self, at: usize, message: &'static str) -> Result<T, Error> {
+        Err(Error { position: at, message })
+    }
+
+    // x|y|..., up to the end of the pattern or a closing bracket
+    fn alternation(&mut self) -> Result<Node, Error> {
+        let mut alternatives = vec![self.concatenation()?];
+        while self.peek() == Some('|') {
+            self.next();
+            alternatives.push(self.concatenation()?);
+        }
+        Ok(if alternatives.len() == 1 { alternatives.pop().unwrap() } else {
+            Node::Alternate(alternatives)
+        })
+    }
+
+    fn concatenation(&mut self) -> Result<Node, Error> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            nodes.push(self.repetition()?);
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    fn repetition(&mut self) -> Result<Node, Error> {
+        let node = self.atom()?;
+        let repeat = match self.peek() {
+            Some('*') => Repeat::Star,
+            Some('+') => Repeat::Plus,
+            Some('?') => Repeat::Question,
+            _ => return Ok(node),
+        };
+        self.next();
+        let greedy = self.peek() != Some('?');
+        if !greedy {
+            self.next();
+        }
+        match self.peek() {
+            Some('*') | Some('+') | Some('?') => self.error(self.position, "nothing to repeat"),
+            _ => Ok(Node::Repeat(repeat, greedy, Box::new(node))),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Node, Error> {
+        let start = self.position;
+        match self.next() {
+            Some('(') => {
+                let number = if self.pattern[self.position..].starts_with("?:") {
+                    self.position += 2;
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups)
+                };
+                let inside = self.alternation()?;
+                if self.next() != Some(')') {
+                    return self.error(start, "unmatched '('");
+                }
+                Ok(Node::Group(number, Box::new(inside)))
+            }
+            Some('[') => self.class(start),
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('\\') => match self.escape(start)? {
+                Ok(c) => Ok(Node::Char(c)),
+                Err(class) => Ok(Node::Class(class)),
+            },
+            Some('*') | Some('+') | Some('?') => self.error(start, "nothing to repeat"),
+            Some(c) => Ok(Node::Char(c)),
+            None => unreachable!(),
+        }
+    }
+
+    // What follows a backslash: a character, or a class such as \d
+    fn escape(&mut self, start: usize) -> Result<Result<char, Class>, Error> {
+        let class = |ranges: &[(char, char)], negated| {
+            Err(Class { ranges: ranges.to_vec(), negated })
+        };
+        Ok(match self.next() {
+            None => return self.error(start, "trailing '\\'"),
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('d') => class(DIGITS, false),
+            Some('D') => class(DIGITS, true),
+            Some('w') => class(WORD, false),
+            Some('W') => class(WORD, true),
+            Some('s') => class(SPACE, false),
+            Some('S') => class(SPACE, true),
+            Some(c) if c.is_alphanumeric() => return self.error(start, "unknown escape"),
+            Some(c) => Ok(c),
+        })
+    }
+
+    // A class, after its opening bracket.  A ']' straight after the bracket, or its '^', is in the
+    // class, as is a '-' at either end.
+    fn class(&mut self, start: usize) -> Result<Node, Error> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next();
+        }
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let at = self.position;
+            let low = match self.next() {
+                None => return self.error(start, "unmatched '['"),
+                Some(']') if !first => break,
+                Some('\\') => match self.escape(at)? {
+                    Ok(c) => c,
+                    Err(Class { negated: false, ranges: more }) => {
+                        ranges.extend(more);
+                        first = false;
+                        continue;
+                    }
+                    Err(_) => return self.error(at, "negated class in a class"),
+                },
+                Some(c) => c,
+            };
+            first = false;
+            let rest = &self.pattern[self.position..];
+            if !rest.starts_with('-') || rest.starts_with("-]") {
+                ranges.push((low, low));
+                continue;
+            }
+            self.next();
+            let high = match self.next() {
+                Some('\\') => match self.escape(at)? {
+                    Ok(c) => c,
+                    Err(_) => return self.error(at, "class in a range"),
+                },
+                Some(c) => c,
+                None => return self.error(start, "unmatched '['"),
+            };
+            if high < low {
+                return self.error(at, "range out of order");
+            }
+            ranges.push((low, high));
+        }
+        Ok(Node::Class(Class { ranges, negated }))
+    }
+}
+
+// Add the instructions for `node` to `program`
+fn compile(node: &Node, program: &mut Vec<Inst>) {
+    match *node {
+        Node::Char(c) => program.push(Inst::Char(c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(ref class) => program.push(Inst::Class(class.clone())),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::Concat(ref nodes) => {
+            for node in nodes {
+                compile(node, program);
+            }
+        }
+        Node::Alternate(ref nodes) => {
+            // Each but the last is tried as a Split's preferred branch, after which it jumps to
+            // the end, which the jumps are patched to once it's known
+            let mut jumps = Vec::new();
+            for (i, node) in nodes.iter().enumerate() {
+                if i + 1 < nodes.len() {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(node, program);
+                    jumps.push(program.len());
+                    program.push(Inst::Jump(0));
+                    program[split] = Inst::Split(split + 1, program.len());
+                } else {
+                    compile(node, program);
+                }
+            }
+            for jump in jumps {
+                program[jump] = Inst::Jump(program.len());
+            }
+        }
+        Node::Repeat(ref repeat, greedy, ref node) => {
+            // The Split at `at`, which goes on to `repeat` the node or `skip` it, preferring to
+            // repeat it if it's greedy.  It's a Match until it's known where to skip to.
+            let split = |program: &mut Vec<Inst>, at: usize, repeat, skip| {
+                program[at] = if greedy {
+                    Inst::Split(repeat, skip)
+                } else {
+                    Inst::Split(skip, repeat)
+                };
+            };
+            match *repeat {
+                // Split: node, Jump back to the Split
+                Repeat::Star => {
+                    let start = program.len();
+                    program.push(Inst::Match);
+                    compile(node, program);
+                    program.push(Inst::Jump(start));
+                    let end = program.len();
+                    split(program, start, start + 1, end);
+                }
+                // node, Split back to it
+                Repeat::Plus => {
+                    let start = program.len();
+                    compile(node, program);
+                    let at = program.len();
+                    program.push(Inst::Match);
+                    split(program, at, start, at + 1);
+                }
+                // Split: node
+                Repeat::Question => {
+                    let start = program.len();
+                    program.push(Inst::Match);
+                    compile(node, program);
+                    let end = program.len();
+                    split(program, start, start + 1, end);
+                }
+            }
+        }
+        Node::Group(None, ref node) => compile(node, program),
+        Node::Group(Some(number), ref node) => {
+            program.push(Inst::Save(2 * number));
+            compile(node, program);
+            program.push(Inst::Save(2 * number + 1));
+        }
+    }
+}
+
+// The states the automaton is in, most preferred first, with where each one's groups matched.
+// The ones that don't take a character are only gone through on the way to the ones that do, but
+// they're remembered too, so that none is gone through twice.
+struct Threads {
+    threads: Vec<(usize, Vec<Option<usize>>)>,
+    visited: Vec<usize>,
+    present: Vec<bool>,
+}
+
+impl Threads {
+    fn new(states: usize) -> Threads {
+        Threads { threads: Vec::new(), visited: Vec::new(), present: vec![false; states] }
+    }
+
+    // Whether `pc` has been gone through, marking it as gone through if not
+    fn visit(&mut self, pc: usize) -> bool {
+        if self.present[pc] {
+            return true;
+        }
+        self.present[pc] = true;
+        self.visited.push(pc);
+        false
+    }
+
+    // The threads, leaving none
+    fn take(&mut self) -> Vec<(usize, Vec<Option<usize>>)> {
+        for pc in self.visited.drain(..) {
+            self.present[pc] = false;
+        }
+        mem::take(&mut self.threads)
+    }
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Regex, Error> {
+        let mut parser = Parser { pattern, position: 0, groups: 0 };
+        let node = parser.alternation()?;
+        if parser.position < pattern.len() {
+            return parser.error(parser.position, "unmatched ')'");
+        }
+        let mut program = vec![Inst::Save(0)];
+        compile(&node, &mut program);
+        program.push(Inst::Save(1));
+        program.push(Inst::Match);
+        Ok(Regex { program, groups: parser.groups + 1 })
+    }
+
+    // How many groups the pattern has, counting the whole match as group 0
+    pub fn groups(&self) -> usize {
+        self.groups
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    // Where the match in `text` starts and ends, in bytes
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.find_at(text, 0)
+    }
+
+    // The first match that starts at or after `start`, although ^ still only matches at 0
+    pub fn find_at(&self, text: &str, start: usize) -> Option<(usize, usize)> {
+        self.run(text, start).0.map(|groups| groups[0].unwrap())
+    }
+
+    // Where each group matched, for the match in `text`
+    pub fn captures(&self, text: &str) -> Option<Captures> {
+        self.run(text, 0).0
+    }
+
+    // The matches in `text`, one after another, with none overlapping
+    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        self.captures_all(text).into_iter().map(|groups| groups[0].unwrap()).collect()
+    }
+
+    // Where the groups matched, for each of the matches find_all() finds
+    pub fn captures_all(&self, text: &str) -> Vec<Captures> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while let Some(groups) = self.run(text, start).0 {
+            let (from, to) = groups[0].unwrap();
+            matches.push(groups);
+            // After an empty match, the next one has to start further on
+            start = if to > from {
+                to
+            } else {
+                match text[to..].chars().next() {
+                    Some(c) => to + c.len_utf8(),
+                    None => break,
+                }
+            };
+        }
+        matches
+    }
+
+    // `text` with each match replaced by `replacement`, in which $0 to $9 stand for what the
+    // groups matched, and $$ for a $
+    pub fn replace_all(&self, text: &str, replacement: &str) -> String {
+        let mut replaced = String::new();
+        let mut last = 0;
+        for groups in self.captures_all(text) {
+            let (from, to) = groups[0].unwrap();
+            replaced.push_str(&text[last..from]);
+            let mut chars = replacement.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c != '$' {
+                    replaced.push(c);
+                    continue;
+                }
+                match chars.peek().cloned() {
+                    Some('$') => {
+                        chars.next();
+                        replaced.push('$');
+                    }
+                    Some(d) if d.is_ascii_digit() => {
+                        chars.next();
+                        let group = d as usize - '0' as usize;
+                        if let Some(&Some((start, end))) = groups.get(group) {
+                            replaced.push_str(&text[start..end]);
+                        }
+                    }
+                    _ => replaced.push('$'),
+                }
+            }
+            last = to;
+        }
+        replaced.push_str(&text[last..]);
+        replaced
+    }
+
+    // Run the automaton over `text`, starting at `start`, giving where the groups matched, if
+    // there's a match, and how many states it went through to find out, for the tests to check
+    // that that's proportional to the length of the text
+    fn run(&self, text: &str, start: usize) -> (Option<Captures>, usize) {
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut matched: Option<Vec<Option<usize>>> = None;
+        let mut steps = 0;
+        let mut position = start;
+        loop {
+            // A match could start here, unless one has already been found further left
+            if matched.is_none() {
+                let slots = vec![None; 2 * self.groups];
+                steps += self.add(&mut current, 0, slots, text, position);
+            }
+            if current.threads.is_empty() {
+                break;
+            }
+            let c = text[position..].chars().next();
+            let after = position + c.map_or(0, |c| c.len_utf8());
+            for (pc, slots) in current.take() {
+                let takes = match (&self.program[pc], c) {
+                    (&Inst::Char(expected), Some(c)) => c == expected,
+                    (Inst::Any, Some(c)) => c != '\n',
+                    (Inst::Class(class), Some(c)) => class.matches(c),
+                    (Inst::Match, _) => {
+                        // The threads after this one are less preferred, so they're dropped
+                        matched = Some(slots);
+                        break;
+                    }
+                    _ => false,
+                };
+                if takes {
+                    steps += self.add(&mut next, pc + 1, slots, text, after);
+                }
+            }
+            if c.is_none() {
+                break;
+            }
+            mem::swap(&mut current, &mut next);
+            position = after;
+        }
+        let groups = matched.map(|slots| {
+            slots.chunks(2).map(|pair| match (pair[0], pair[1]) {
+                (Some(start), Some(end)) => Some((start, end)),
+                _ => None,
+            }).collect()
+        });
+        (groups, steps)
+    }
+
+    // Add the thread at `pc` to `threads`, following its Splits, Jumps, Saves and anchors at
+    // `position` to the states that take a character or match, giving how many states that was
+    fn add(&self, threads: &mut Threads, pc: usize, slots: Vec<Option<usize>>, text: &str,
+           position: usize)
+           -> usize {
+        let mut steps = 0;
+        // A stack rather than recursion, with the preferred branch of a Split on top
+        let mut stack = vec![(pc, slots)];
+        while let Some((pc, mut slots)) = stack.pop() {
+            if threads.visit(pc) {
+                continue;
+            }
+            steps += 1;
+            match self.program[pc] {
+                Inst::Split(first, second) => {
+                    stack.push((second, slots.clone()));
+                    stack.push((first, slots));
+                }
+                Inst::Jump(to) => stack.push((to, slots)),
+                Inst::Save(slot) => {
+                    slots[slot] = Some(position);
+                    stack.push((pc + 1, slots));
+                }
+                Inst::Start => {
+                    if position == 0 {
+                        stack.push((pc + 1, slots));
+                    }
+                }
+                Inst::End => {
+                    if position == text.len() {
+                        stack.push((pc + 1, slots));
+                    }
+                }
+                _ => threads.threads.push((pc, slots)),
+            }
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::alloc::string::String;
+    use super::{Error, Regex};
+
+    fn find(pattern: &str, text: &str) -> Option<String> {
+        let regex = Regex::new(pattern).unwrap();
+        regex.find(text).map(|(start, end)| text[start..end].into())
+    }
+
+    #[test]
+    fn matching() {
+        let cases = [
+            ("abc", "xabcx", Some("abc")),
+            ("a.c", "abc a\nc", Some("abc")),
+            ("a|b|c", "xxc", Some("c")),
+            ("ab*", "abbbc", Some("abbb")),
+            ("ab+", "ac abb", Some("abb")),
+            ("colou?r", "color", Some("color")),
+            ("a*?b", "aaab", Some("aaab")),
+            ("a+?", "aaa", Some("a")),
+            ("(a|ab)(c|bcd)", "abcd", Some("abcd")),
+            ("[a-c]+", "xxbcaz", Some("bca")),
+            ("[^a-c ]+", "abc def", Some("def")),
+            ("[]a]+", "x]a]", Some("]a]")),
+            ("[a-]+", "x-a-", Some("-a-")),
+            ("\\d+\\.\\d*", "pi is 3.14", Some("3.14")),
+            ("[\\w.]+@\\w+", "name: a.b@c", Some("a.b@c")),
+            ("\\S+\\s\\S+", "one  two three", Some("two three")),
+            ("^ab", "abab", Some("ab")),
+            ("^b", "ab", None),
+            ("b$", "abab", Some("b")),
+            ("a$", "ab", None),
+            ("^$", "", Some("")),
+            ("x*", "abc", Some("")),
+            ("(?:ab)+", "ababa", Some("abab")),
+            ("é+", "caféé", Some("éé")),
+        ];
+        for &(pattern, text, found) in &cases {
+            assert_eq!(find(pattern, text).as_deref(), found, "{} in {:?}", pattern, text);
+        }
+    }
+
+    #[test]
+    fn captures() {
+        let regex = Regex::new("(\\d+)-(\\d+)|(x)").unwrap();
+        assert_eq!(regex.groups(), 4);
+        assert_eq!(regex.captures("at 10-20"), Some(vec![Some((3, 8)), Some((3, 5)), Some((6, 8)),
+                                                        None]));
+        assert_eq!(regex.captures("ax"), Some(vec![Some((1, 2)), None, None, Some((1, 2))]));
+        // A group in a loop is where it last matched
+        let regex = Regex::new("(a|b)*").unwrap();
+        assert_eq!(regex.captures("abba"), Some(vec![Some((0, 4)), Some((3, 4))]));
+    }
+
+    #[test]
+    fn replacing() {
+        let regex = Regex::new("(\\w+)@(\\w+)").unwrap();
+        assert_eq!(regex.replace_all("a@b, c@d", "$2 at $1 ($$)"), "b at a ($), d at c ($)");
+        let regex = Regex::new("x*").unwrap();
+        assert_eq!(regex.find_all("axxb"), vec![(0, 0), (1, 3), (3, 3), (4, 4)]);
+        assert_eq!(regex.replace_all("axxb", "-"), "-a--b-");
+    }
+
+    #[test]
+    fn errors() {
+        let error = |pattern| Regex::new(pattern).unwrap_err();
+        assert_eq!(error("a(b"), Error { position: 1, message: "unmatched '('" });
+        assert_eq!(error("a)b"), Error { position: 1, message: "unmatched ')'" });
+        assert_eq!(error("[ab"), Error { position: 0, message: "unmatched '['" });
+        assert_eq!(error("*a"), Error { position: 0, message: "nothing to repeat" });
+        assert_eq!(error("a**"), Error { position: 2, message: "nothing to repeat" });
+        assert_eq!(error("a\\"), Error { position: 1, message: "trailing '\\'" });
+        assert_eq!(error("\\q"), Error { position: 0, message: "unknown escape" });
+        assert_eq!(error("[z-a]"), Error { position: 1, message: "range out of order" });
+        assert_eq!(error("(").to_string(), "unmatched '(' at 0");
+    }
+
+    #[test]
+    fn linear_time() {
+        // (a?){n}a{n} against n a's, and (a*)*b against n a's, are the patterns that make a
+        // backtracking matcher take exponential time.  Here the steps taken grow with the length
+        // of the text times the size of the pattern, and no faster.
+        // Each state is gone through at most once for each position in the text.
+        for n in [10, 20, 40].iter().cloned() {
+            let pattern = "a?".repeat(n) + &"a".repeat(n);
+            let regex = Regex::new(&pattern).unwrap();
+            let (groups, steps) = regex.run(&"a".repeat(n), 0);
+            assert_eq!(groups.unwrap()[0], Some((0, n)));
+            assert!(steps <= regex.program.len() * (n + 1), "{} steps for n = {}", steps, n);
+        }
+        let regex = Regex::new("(a*)*b").unwrap();
+        for n in [1000, 10000, 100000].iter().cloned() {
+            let (groups, steps) = regex.run(&"a".repeat(n), 0);
+            assert_eq!(groups, None);
+            assert!(steps <= regex.program.len() * (n + 1), "{} steps for n = {}", steps, n);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! A small seedable random number generator, for tasks that need randomness (shuffling, mazes,
+//! Monte Carlo methods) without pulling in the rand crate, and whose tests need the same numbers
+//! on every run.
+//!
+//! It is PCG32 (http://www.pcg-random.org/): a 64 bit linear congruential generator whose output
+//! is permuted down to 32 bits.  It is fast and statistically sound, but not cryptographically
+//! secure.
+//!
+//! ```
+//! # extern crate utils;
+//! use utils::rng::Rng;
+//!
+//! let mut rng = Rng::new(42);
+//! let roll = rng.gen_range(1, 7);
+//! assert!(1 <= roll && roll < 7);
+//! // The same seed gives the same numbers
+//! assert_eq!(Rng::new(42).gen_range(1, 7), roll);
+//! let mut cards: Vec<u32> = (1..=10).collect();
+//! rng.shuffle(&mut cards);
+//! cards.sort();
+//! assert_eq!(cards, (1..=10).collect::<Vec<_>>());
+//! ```
+
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static MULTIPLIER: u64 = 6364136223846793005;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rng {
+    state: u64,
+    inc: u64, // Selects the stream, always odd
+}
+
+impl Rng {
+    // A generator that always produces the same numbers for the same `seed`
+    pub fn new(seed: u64) -> Rng {
+        Rng::with_stream(seed, 0)
+    }
+
+    // A generator seeded with `seed` on one of 2^63 independent streams: generators on
+    // different streams produce different numbers even when seeded alike.
+    pub fn with_stream(seed: u64, stream: u64) -> Rng {
+        let mut rng = Rng { state: 0, inc: (stream << 1) | 1 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    // A generator seeded from the time, for when the numbers should differ from run to run
+    #[cfg(feature = "std")]
+    pub fn from_time() -> Rng {
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        Rng::new(since_epoch.as_secs() ^ ((since_epoch.subsec_nanos() as u64) << 32))
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(MULTIPLIER).wrapping_add(self.inc);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    // A number in [0, 1), with all 53 bits of precision
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // A number in [low, high), every one equally likely.  Panics if the range is empty.
+    pub fn gen_range<T: Uniform>(&mut self, low: T, high: T) -> T {
+        T::sample(self, low, high)
+    }
+
+    // True with probability `p`
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    // Put `items` in a random order, with every order equally likely (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(0, i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    // One of `items` at random, or None if there are none
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.gen_range(0, items.len())])
+        }
+    }
+
+    // A number in [0, n), without the bias of just taking next_u64() % n: numbers from the last,
+    // incomplete multiple of n are rejected.
+    fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+}
+
+// Types gen_range() can produce
+pub trait Uniform: Copy {
+    fn sample(rng: &mut Rng, low: Self, high: Self) -> Self;
+}
+
+macro_rules! uniform_int {
+    ($($t:ty)*) => ($(
+        impl Uniform for $t {
+            fn sample(rng: &mut Rng, low: $t, high: $t) -> $t {
+                assert!(low < high, "empty range");
+                // Wide enough for the difference of any two values of any of the types
+                let width = (high as i128 - low as i128) as u64;
+                (low as i128 + rng.below(width) as i128) as $t
+            }
+        }
+    )*)
+}
+
+uniform_int! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+
+impl Uniform for f64 {
+    fn sample(rng: &mut Rng, low: f64, high: f64) -> f64 {
+        assert!(low < high, "empty range");
+        low + rng.next_f64() * (high - low)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rng;
+
+    #[test]
+    fn reference_output() {
+        // From the PCG reference implementation's pcg32-demo
+        let mut rng = Rng::with_stream(42, 54);
+        let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u32(), x);
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert!(Rng::new(7).next_u64() != Rng::new(8).next_u64());
+        assert!(Rng::with_stream(7, 1).next_u64() != Rng::with_stream(7, 2).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [0; 6];
+        for _ in 0..6000 {
+            seen[rng.gen_range(0, 6)] += 1;
+        }
+        // Every value turns up, about equally often
+        assert!(seen.iter().all(|&n| n > 900 && n < 1100), "{:?}", seen);
+        for _ in 0..1000 {
+            let x = rng.gen_range(-5i32, 5);
+            assert!((-5..5).contains(&x));
+            let y = rng.gen_range(i64::MIN, i64::MAX);
+            assert!(y < i64::MAX);
+            let f = rng.gen_range(1.5, 2.5);
+            assert!((1.5..2.5).contains(&f));
+            let unit = rng.next_f64();
+            assert!((0.0..1.0).contains(&unit));
+        }
+        assert_eq!(rng.gen_range(3u8, 4), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn empty_range() {
+        Rng::new(1).gen_range(3, 3);
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut items: Vec<u32> = (0..50).collect();
+        Rng::new(3).shuffle(&mut items);
+        assert!(items != (0..50).collect::<Vec<_>>());
+        let mut again: Vec<u32> = (0..50).collect();
+        Rng::new(3).shuffle(&mut again);
+        assert_eq!(items, again);
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+        // Nothing to do for short slices
+        Rng::new(3).shuffle(&mut [0u8; 0]);
+        Rng::new(3).shuffle(&mut [1]);
+    }
+
+    #[test]
+    fn choose() {
+        let mut rng = Rng::new(5);
+        let items = ["a", "b", "c"];
+        for _ in 0..100 {
+            assert!(items.contains(rng.choose(&items).unwrap()));
+        }
+        assert_eq!(rng.choose::<u8>(&[]), None);
+    }
+}
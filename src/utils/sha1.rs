@@ -0,0 +1,90 @@
+// SHA-1 as FIPS 180-4 specifies it, for sha1.rs and for HMAC in hmac_pbkdf2.rs.  Sha1 is a
+// Digest, hashing a message fed to it in pieces of any size.  Each 64-byte block is expanded into
+// 80 words, which the compression function mixes into the five words of state in four rounds of
+// twenty steps, each round with its own function and constant.
+
+use super::digest::{Blocks, Digest};
+
+const INITIAL: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    blocks: Blocks,
+}
+
+impl Default for Sha1 {
+    fn default() -> Sha1 {
+        Sha1 { state: INITIAL, blocks: Blocks::new() }
+    }
+}
+
+impl Digest for Sha1 {
+    type Output = [u8; 20];
+
+    fn update(&mut self, data: &[u8]) {
+        let Sha1 { ref mut state, ref mut blocks } = *self;
+        blocks.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 20] {
+        let Sha1 { ref mut state, ref mut blocks } = self;
+        blocks.finish(true, |block| compress(state, block));
+        let mut digest = [0; 20];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut w = [0u32; 80];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, &word) in w.iter().enumerate() {
+        let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a827999),
+            1 => (b ^ c ^ d, 0x6ed9eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = t;
+    }
+    for (word, value) in state.iter_mut().zip(&[a, b, c, d, e]) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::alloc::vec::Vec;
+    use super::super::digest::Digest;
+    use super::Sha1;
+
+    #[test]
+    fn streaming() {
+        let message: Vec<u8> = (0..300u32).map(|i| (i * 7 % 251) as u8).collect();
+        for &length in &[0, 55, 56, 64, 119, 120, 300] {
+            let whole = Sha1::digest(&message[..length]);
+            for &piece in &[1, 17, 64, 100] {
+                let mut hasher = Sha1::default();
+                for chunk in message[..length].chunks(piece) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.finalize(), whole, "{} bytes in pieces of {}", length, piece);
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// SHA-256 as FIPS 180-4 specifies it, for sha256.rs and for HMAC and PBKDF2 in hmac_pbkdf2.rs.
+// Sha256 is a Digest, hashing a message fed to it in pieces of any size.  Each 64-byte block is
+// expanded into a schedule of 64 words, which the compression function mixes into the eight words
+// of state, one round per word.  Unlike MD5, everything is big-endian.
+
+use super::digest::{Blocks, Digest};
+
+// The first 32 bits of the fractional parts of the cube roots of the first 64 primes
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// The first 32 bits of the fractional parts of the square roots of the first 8 primes
+const INITIAL: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    blocks: Blocks,
+}
+
+impl Default for Sha256 {
+    fn default() -> Sha256 {
+        Sha256 { state: INITIAL, blocks: Blocks::new() }
+    }
+}
+
+impl Digest for Sha256 {
+    type Output = [u8; 32];
+
+    fn update(&mut self, data: &[u8]) {
+        let Sha256 { ref mut state, ref mut blocks } = *self;
+        blocks.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        let Sha256 { ref mut state, ref mut blocks } = self;
+        blocks.finish(true, |block| compress(state, block));
+        let mut digest = [0; 32];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    // The message schedule: the block's sixteen words, and 48 more mixed from earlier ones
+    let mut w = [0u32; 64];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let t1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(K[i]).wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(majority);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (word, value) in state.iter_mut().zip(&[a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::alloc::vec::Vec;
+    use super::super::digest::Digest;
+    use super::Sha256;
+
+    #[test]
+    fn incremental() {
+        // However the message is split up, across the blocks and the padding's boundaries, the
+        // digest is the same
+        let message: Vec<u8> = (0..300u32).map(|i| (i * 7 % 251) as u8).collect();
+        for &length in &[0, 55, 56, 63, 64, 65, 119, 120, 128, 300] {
+            let whole = Sha256::digest(&message[..length]);
+            for &piece in &[1, 3, 17, 64, 100] {
+                let mut hasher = Sha256::default();
+                for chunk in message[..length].chunks(piece) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.finalize(), whole, "{} bytes in pieces of {}", length, piece);
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// For the tasks that animate in a terminal (cellular automata, ants, pendulums): a Screen to draw
+// each frame on, a Terminal to show it with ANSI escape codes, and a FrameTimer to keep the frames
+// coming at a steady rate.  A task's animation loop is usually
+//
+//     let mut terminal = Terminal::new(io::stdout());
+//     let mut timer = FrameTimer::new(20);
+//     let mut screen = Screen::new(80, 48);
+//     loop {
+//         world.draw(&mut screen);
+//         terminal.draw(&screen)?;
+//         world.step();
+//         timer.wait();
+//     }
+//
+// Colors are 24-bit, which most terminals understand.
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::canvas::{Canvas, Color};
+
+static CLEAR: &str = "\x1b[2J";
+static HOME: &str = "\x1b[H";
+static RESET: &str = "\x1b[0m";
+static HIDE_CURSOR: &str = "\x1b[?25l";
+static SHOW_CURSOR: &str = "\x1b[?25h";
+
+// Colored pixels, two to a character of the terminal: the upper one is the color of a '▀' and the
+// lower one the color behind it, which makes them roughly square.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Screen {
+    width: usize,
+    height: usize,
+    colors: Vec<Color>, // Row by row
+}
+
+impl Screen {
+    // A screen of the given size in pixels, all black.  It takes up `width` columns and half as
+    // many lines as `height`, rounded up.
+    pub fn new(width: usize, height: usize) -> Screen {
+        Screen { width, height, colors: vec![Color::BLACK; width * height] }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        self.colors[self.offset(x, y)]
+    }
+
+    // The escape codes and characters that show the screen, from where the cursor is, leaving
+    // the colors reset at the end of each line.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for top in (0..self.height).step_by(2) {
+            if top > 0 {
+                out.push('\n');
+            }
+            let (mut fg, mut bg) = (None, None);
+            for x in 0..self.width {
+                let upper = self.get(x, top);
+                let lower = if top + 1 < self.height { self.get(x, top + 1) } else { Color::BLACK };
+                if fg != Some(upper) {
+                    out.push_str(&format!("\x1b[38;2;{};{};{}m", upper.r, upper.g, upper.b));
+                    fg = Some(upper);
+                }
+                if bg != Some(lower) {
+                    out.push_str(&format!("\x1b[48;2;{};{};{}m", lower.r, lower.g, lower.b));
+                    bg = Some(lower);
+                }
+                out.push('▀');
+            }
+            out.push_str(RESET);
+        }
+        out
+    }
+
+    fn offset(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} screen", x, y,
+                self.width, self.height);
+        y * self.width + x
+    }
+}
+
+impl Canvas for Screen {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: Color) {
+        let i = self.offset(x, y);
+        self.colors[i] = color;
+    }
+}
+
+// A terminal being animated in.  Each frame is drawn over the last from the top left corner, with
+// the cursor hidden until the Terminal is dropped.
+pub struct Terminal<W: Write> {
+    out: W,
+    cleared: bool,
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W) -> Terminal<W> {
+        Terminal { out, cleared: false }
+    }
+
+    // Blank the whole terminal, and put the cursor in the top left corner
+    pub fn clear(&mut self) -> io::Result<()> {
+        write!(self.out, "{}{}{}", HIDE_CURSOR, CLEAR, HOME)?;
+        self.cleared = true;
+        self.out.flush()
+    }
+
+    // Show `screen` as the next frame, clearing the terminal first if this is the first one
+    pub fn draw(&mut self, screen: &Screen) -> io::Result<()> {
+        if !self.cleared {
+            self.clear()?;
+        }
+        write!(self.out, "{}{}", HOME, screen.render())?;
+        self.out.flush()
+    }
+
+    // Put the cursor at column `x` and line `y`, counting from 0, to write text there
+    pub fn move_to(&mut self, x: usize, y: usize) -> io::Result<()> {
+        write!(self.out, "\x1b[{};{}H", y + 1, x + 1)
+    }
+}
+
+impl<W: Write> Write for Terminal<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+impl<W: Write> Drop for Terminal<W> {
+    // Leave the terminal as it was found, with the prompt below the last frame
+    fn drop(&mut self) {
+        if self.cleared {
+            let _ = writeln!(self.out, "{}{}", RESET, SHOW_CURSOR);
+            let _ = self.out.flush();
+        }
+    }
+}
+
+// Paces an animation at a number of frames a second.  Frames that take too long are not made up
+// for: the next one just comes a whole frame later.
+pub struct FrameTimer {
+    period: Duration,
+    next: Instant,
+}
+
+impl FrameTimer {
+    pub fn new(frames_per_second: u32) -> FrameTimer {
+        assert!(frames_per_second > 0, "no frames per second");
+        let period = Duration::from_secs(1) / frames_per_second;
+        FrameTimer { period, next: Instant::now() + period }
+    }
+
+    // Sleep until it's time for the next frame
+    pub fn wait(&mut self) {
+        let now = Instant::now();
+        if now < self.next {
+            thread::sleep(self.next - now);
+            self.next += self.period;
+        } else {
+            self.next = now + self.period;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{FrameTimer, Screen, Terminal};
+    use super::super::canvas::{Canvas, Color};
+    use super::super::capture::Captured;
+
+    #[test]
+    fn render() {
+        let mut screen = Screen::new(2, 3);
+        screen.set(0, 0, Color::WHITE);
+        screen.set(1, 2, Color::rgb(1, 2, 3));
+        assert_eq!(screen.get(0, 0), Color::WHITE);
+        assert_eq!(screen.render(),
+                   "\x1b[38;2;255;255;255m\x1b[48;2;0;0;0m▀\x1b[38;2;0;0;0m▀\x1b[0m\n\
+                    \x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀\x1b[38;2;1;2;3m▀\x1b[0m");
+        assert_eq!(Screen::new(0, 0).render(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 3) is outside a 2x3 screen")]
+    fn outside() {
+        Screen::new(2, 3).set(0, 3, Color::WHITE);
+    }
+
+    #[test]
+    fn terminal() {
+        let output = Captured::new();
+        let mut screen = Screen::new(1, 1);
+        {
+            let mut terminal = Terminal::new(output.clone());
+            terminal.draw(&screen).unwrap();
+            screen.fill(Color::WHITE);
+            terminal.draw(&screen).unwrap();
+            terminal.move_to(4, 2).unwrap();
+        }
+        assert_eq!(output.text(),
+                   "\x1b[?25l\x1b[2J\x1b[H\
+                    \x1b[H\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀\x1b[0m\
+                    \x1b[H\x1b[38;2;255;255;255m\x1b[48;2;0;0;0m▀\x1b[0m\
+                    \x1b[3;5H\x1b[0m\x1b[?25h\n");
+        // Nothing to undo if nothing was drawn
+        let output = Captured::new();
+        drop(Terminal::new(output.clone()));
+        assert_eq!(output.text(), "");
+    }
+
+    #[test]
+    fn timer() {
+        let start = Instant::now();
+        let mut timer = FrameTimer::new(100);
+        for _ in 0..5 {
+            timer.wait();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
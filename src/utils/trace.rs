@@ -0,0 +1,55 @@
+// Watching a task work step by step, for the searches and backtracking ones, where what's
+// interesting is the way to the answer rather than the answer.  A task calls
+//
+//     trace!("row {}: queen in column {}", row, column);
+//
+// at each step, and a build with the trace feature prints the steps to standard error, each with
+// the task's name, when the ROSETTA_TRACE environment variable is set:
+//
+//     ROSETTA_TRACE=1 cargo run --features trace --bin n_queens
+//
+// Without the feature, trace!() compiles to nothing, and its arguments aren't evaluated.  They're
+// still checked by the compiler either way, so a trace can't rot while nobody builds with it.
+//
+// The macro comes in with the utils module when it's declared with #[macro_use]:
+//
+//     #[macro_use]
+//     #[allow(dead_code)]
+//     #[path = "../utils/mod.rs"]
+//     mod utils;
+//
+// It isn't exported from the crate root, since a crate that includes utils more than once, as
+// wasm/ does through the tasks it pulls in, would then have a trace! for each copy.
+
+#[allow(unused_macros)]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "trace") {
+            // Looked up once for each trace!() rather than at every step
+            static ON: ::std::sync::OnceLock<bool> = ::std::sync::OnceLock::new();
+            let on = *ON.get_or_init(|| match ::std::env::var_os("ROSETTA_TRACE") {
+                Some(value) => !value.is_empty() && value != "0",
+                None => false,
+            });
+            if on {
+                eprintln!("[{}] {}", module_path!(), format_args!($($arg)*));
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn arguments_unused_unless_tracing() {
+        let mut evaluated = false;
+        let mut step = || {
+            evaluated = true;
+            1
+        };
+        trace!("step {}", step());
+        // Tests don't set ROSETTA_TRACE
+        assert!(!evaluated || std::env::var_os("ROSETTA_TRACE").is_some());
+        trace!("no arguments");
+    }
+}
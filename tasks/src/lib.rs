@@ -0,0 +1,221 @@
+// The tasks that have an API of their own, for the examples in their documentation: see
+// Cargo.toml.  Each is included by path, as a module named after its binary, and its examples use
+// it from here, under `tasks::`.  The tasks that only have a main(), or don't build on a current
+// toolchain yet, aren't in it.
+
+// Each task includes src/utils itself, and the tasks that use it from the crate root need it too
+#![allow(clippy::duplicate_mod)]
+// Every task's header starts with its Rosetta Code page, as "Implements <url>"
+#![allow(rustdoc::bare_urls)]
+
+extern crate libc;
+extern crate rand;
+extern crate rosetta_task;
+extern crate rustc_serialize;
+
+// The tasks refer to utils from the crate root, as they do when they're built on their own
+#[path = "../../src/utils/mod.rs"]
+#[allow(dead_code)]
+mod utils;
+
+#[path = "../../src/io/a_plus_b.rs"]
+#[allow(dead_code)]
+pub mod a_plus_b;
+#[path = "../../src/language/accumulator_factory.rs"]
+#[allow(dead_code)]
+pub mod accumulator_factory;
+#[path = "../../src/crypto/aes.rs"]
+#[allow(dead_code)]
+pub mod aes;
+#[path = "../../src/math/aks_test_for_primes.rs"]
+#[allow(dead_code)]
+pub mod aks_test_for_primes;
+#[path = "../../src/strings/arithmetic_evaluation.rs"]
+#[allow(dead_code)]
+pub mod arithmetic_evaluation;
+#[path = "../../src/math/arithmetic_integers.rs"]
+#[allow(dead_code)]
+pub mod arithmetic_integers;
+#[path = "../../src/compiler/ast_interp.rs"]
+#[allow(dead_code)]
+pub mod ast_interp;
+#[path = "../../src/strings/base64.rs"]
+#[allow(dead_code)]
+pub mod base64;
+#[path = "../../src/graphics/bitmap.rs"]
+#[allow(dead_code)]
+pub mod bitmap;
+#[path = "../../src/interpreters/brainfuck.rs"]
+#[allow(dead_code)]
+pub mod brainfuck;
+#[path = "../../src/crypto/chaocipher.rs"]
+#[allow(dead_code)]
+pub mod chaocipher;
+#[path = "../../src/concurrency/checkpoint_synchronization.rs"]
+#[allow(dead_code)]
+pub mod checkpoint_synchronization;
+#[path = "../../src/math/cholesky_decomposition.rs"]
+#[allow(dead_code)]
+pub mod cholesky_decomposition;
+#[path = "../../src/compiler/codegen.rs"]
+#[allow(dead_code)]
+pub mod codegen;
+#[path = "../../src/concurrency/concurrent_computing_ordered.rs"]
+#[allow(dead_code)]
+pub mod concurrent_computing_ordered;
+#[path = "../../src/graphics/conways_game_of_life.rs"]
+#[allow(dead_code)]
+pub mod conways_game_of_life;
+#[path = "../../src/crypto/diffie_hellman.rs"]
+#[allow(dead_code)]
+pub mod diffie_hellman;
+#[path = "../../src/math/eigenvalues.rs"]
+#[allow(dead_code)]
+pub mod eigenvalues;
+#[path = "../../src/crypto/enigma.rs"]
+#[allow(dead_code)]
+pub mod enigma;
+#[path = "../../src/concurrency/events.rs"]
+#[allow(dead_code)]
+pub mod events;
+#[path = "../../src/math/fast_fourier_transform.rs"]
+#[allow(dead_code)]
+pub mod fast_fourier_transform;
+#[path = "../../src/games/24_game.rs"]
+#[allow(dead_code)]
+pub mod game_24;
+#[path = "../../src/games/24_game_rpn.rs"]
+#[allow(dead_code)]
+pub mod game_24_rpn;
+#[path = "../../src/games/24_game_solve.rs"]
+#[allow(dead_code)]
+pub mod game_24_solve;
+#[path = "../../src/math/gaussian_elimination.rs"]
+#[allow(dead_code)]
+pub mod gaussian_elimination;
+#[path = "../../src/math/hamming_numbers.rs"]
+#[allow(dead_code)]
+pub mod hamming_numbers;
+#[path = "../../src/math/hamming_numbers_alt.rs"]
+#[allow(dead_code)]
+pub mod hamming_numbers_alt;
+#[path = "../../src/algorithms/hash_join.rs"]
+#[allow(dead_code)]
+pub mod hash_join;
+#[path = "../../src/sorting/heap_sort.rs"]
+#[allow(dead_code)]
+pub mod heap_sort;
+#[path = "../../src/crypto/hmac_pbkdf2.rs"]
+#[allow(dead_code)]
+pub mod hmac_pbkdf2;
+#[path = "../../src/io/input_loop.rs"]
+#[allow(dead_code)]
+pub mod input_loop;
+#[path = "../../src/strings/json.rs"]
+#[allow(dead_code)]
+pub mod json;
+#[path = "../../src/compiler/lexer.rs"]
+#[allow(dead_code)]
+pub mod lexer;
+#[path = "../../src/math/long_multiplication.rs"]
+#[allow(dead_code)]
+pub mod long_multiplication;
+#[path = "../../src/math/lu_decomposition.rs"]
+#[allow(dead_code)]
+pub mod lu_decomposition;
+#[path = "../../src/graphics/mandelbrot_set.rs"]
+#[allow(dead_code)]
+pub mod mandelbrot_set;
+#[path = "../../src/math/matrix_multiplication.rs"]
+#[allow(dead_code)]
+pub mod matrix_multiplication;
+#[path = "../../src/puzzles/maze_solving.rs"]
+#[allow(dead_code)]
+pub mod maze_solving;
+#[path = "../../src/sorting/merge-sort.rs"]
+#[allow(dead_code)]
+pub mod merge_sort;
+#[path = "../../src/concurrency/metered_concurrency.rs"]
+#[allow(dead_code)]
+pub mod metered_concurrency;
+#[path = "../../src/math/miller_rabin_primality_test.rs"]
+#[allow(dead_code)]
+pub mod miller_rabin_primality_test;
+#[path = "../../src/puzzles/n_queens.rs"]
+#[allow(dead_code)]
+pub mod n_queens;
+#[path = "../../src/math/numerical_integration.rs"]
+#[allow(dead_code)]
+pub mod numerical_integration;
+#[path = "../../src/crypto/one_time_pad.rs"]
+#[allow(dead_code)]
+pub mod one_time_pad;
+#[path = "../../src/concurrency/parallel_word_count.rs"]
+#[allow(dead_code)]
+pub mod parallel_word_count;
+#[path = "../../src/io/parse_command_line_arguments.rs"]
+#[allow(dead_code)]
+pub mod parse_command_line_arguments;
+#[path = "../../src/compiler/parser.rs"]
+#[allow(dead_code)]
+pub mod parser;
+#[path = "../../src/crypto/playfair.rs"]
+#[allow(dead_code)]
+pub mod playfair;
+#[path = "../../src/math/polynomial_regression.rs"]
+#[allow(dead_code)]
+pub mod polynomial_regression;
+#[path = "../../src/concurrency/producer_consumer.rs"]
+#[allow(dead_code)]
+pub mod producer_consumer;
+#[path = "../../src/math/qr_decomposition.rs"]
+#[allow(dead_code)]
+pub mod qr_decomposition;
+#[path = "../../src/sorting/quick_sort.rs"]
+#[allow(dead_code)]
+pub mod quick_sort;
+#[path = "../../src/crypto/rc4.rs"]
+#[allow(dead_code)]
+pub mod rc4;
+#[path = "../../src/concurrency/rendezvous.rs"]
+#[allow(dead_code)]
+pub mod rendezvous;
+#[path = "../../src/math/roots_of_a_function.rs"]
+#[allow(dead_code)]
+pub mod roots_of_a_function;
+#[path = "../../src/math/roots_of_a_quadratic_function.rs"]
+#[allow(dead_code)]
+pub mod roots_of_a_quadratic_function;
+#[path = "../../src/strings/rpn.rs"]
+#[allow(dead_code)]
+pub mod rpn;
+#[path = "../../src/crypto/rsa.rs"]
+#[allow(dead_code)]
+pub mod rsa;
+#[path = "../../src/math/runge_kutta_method.rs"]
+#[allow(dead_code)]
+pub mod runge_kutta_method;
+#[path = "../../src/strings/s_expressions.rs"]
+#[allow(dead_code)]
+pub mod s_expressions;
+#[path = "../../src/math/sieve_eratosthenes.rs"]
+#[allow(dead_code)]
+pub mod sieve_eratosthenes;
+#[path = "../../src/concurrency/thread_pool.rs"]
+#[allow(dead_code)]
+pub mod thread_pool;
+#[path = "../../src/interpreters/tiny_lisp.rs"]
+#[allow(dead_code)]
+pub mod tiny_lisp;
+#[path = "../../src/interpreters/universal_turing_machine.rs"]
+#[allow(dead_code)]
+pub mod universal_turing_machine;
+#[path = "../../src/crypto/vigenere.rs"]
+#[allow(dead_code)]
+pub mod vigenere;
+#[path = "../../src/compiler/vm.rs"]
+#[allow(dead_code)]
+pub mod vm;
+#[path = "../../src/graphics/write_ppm.rs"]
+#[allow(dead_code)]
+pub mod write_ppm;
@@ -0,0 +1,73 @@
+// Runs the tasks that read their input from tests/fixtures/, from a directory other than the crate
+// root, to check that they find their fixtures wherever they're run from (as the tasks do with
+// utils::fixture), and that what they make of them is right.
+//
+// To add a task, add a test below that runs it with output() and checks a few of its lines.
+
+use std::env;
+use std::process::Command;
+
+// What the binary `bin` prints, run from the temporary directory
+fn output(bin: &str) -> String {
+    let output = Command::new(bin).current_dir(env::temp_dir()).output().unwrap();
+    assert!(output.status.success(), "{} failed: {}", bin, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn anagrams() {
+    let output = output(env!("CARGO_BIN_EXE_anagrams"));
+    let groups: Vec<Vec<&str>> = output.lines().map(|line| line.split_whitespace().collect())
+                                       .collect();
+    assert_eq!(groups.len(), 6);
+    assert!(groups.iter().all(|group| group.len() == 5));
+    assert!(groups.iter().any(|group| group.contains(&"abel") && group.contains(&"elba")));
+}
+
+#[test]
+fn fasta() {
+    assert_eq!(output(env!("CARGO_BIN_EXE_fasta")),
+               "Rosetta_Example_1: THERECANBENOSPACE\n\
+                Rosetta_Example_2: THERECANBESEVERALLINESBUTTHEYALLMUSTBECONCATENATED\n");
+}
+
+#[test]
+fn letter_frequency() {
+    let output = output(env!("CARGO_BIN_EXE_letter_frequency"));
+    let total: usize = output.lines().map(|line| {
+        line.rsplit(": ").next().unwrap().parse::<usize>().unwrap()
+    }).sum();
+    // Every byte of unixdict.txt is ASCII, so there are as many characters as bytes
+    assert_eq!(total, 206403);
+    assert!(output.lines().any(|line| line == "'\\n': 25104"));
+}
+
+#[test]
+fn i_before_e_except_after_c() {
+    assert_eq!(output(env!("CARGO_BIN_EXE_i_before_e_except_after_c")),
+               "I before E when not preceded by C is plausible (ie: 463, cie: 24)\n\
+                E before I when preceded by C is implausible (cei: 13, ei: 213)\n");
+}
+
+#[test]
+fn benford() {
+    let output = output(env!("CARGO_BIN_EXE_benford"));
+    assert!(output.lines().any(|line| line.starts_with("1        30.1%      30.1%")),
+            "benford printed:\n{}", output);
+}
+
+#[test]
+fn synchronous_concurrency() {
+    let output = output(env!("CARGO_BIN_EXE_synchronous_concurrency"));
+    assert!(output.starts_with("Jabberwocky\nBy Lewis Carroll\n\n"));
+    assert!(output.ends_with("\nTotal Lines: 37\n"),
+            "synchronous_concurrency printed:\n{}", output);
+}
+
+#[test]
+fn parallel_word_count() {
+    let output = output(env!("CARGO_BIN_EXE_parallel_word_count"));
+    assert!(output.starts_with("171 words, 94 of them different"),
+            "parallel_word_count printed:\n{}", output);
+    assert!(output.lines().any(|line| line == "      3 jabberwock"));
+}
@@ -0,0 +1,10 @@
+/* 12 factorial is 479001600 */
+
+n = 12;
+result = 1;
+i = 1;
+while (i <= n) {
+    result = result * i;
+    i = i + 1;
+}
+print(result);
@@ -0,0 +1,5 @@
+Datasize: 0 Strings: 1
+"Hello, World!\n"
+    0 push  0
+    5 prts
+    6 halt
@@ -0,0 +1,4 @@
+/*
+  Hello world
+ */
+print("Hello, World!\n");
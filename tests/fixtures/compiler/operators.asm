@@ -0,0 +1,78 @@
+Datasize: 1 Strings: 2
+" "
+"\n"
+    0 push  1
+    5 neg
+    6 push  2
+   11 neg
+   12 push  3
+   17 mul
+   18 sub
+   19 store [0]
+   24 fetch [0]
+   29 prti
+   30 push  0
+   35 prts
+   36 fetch [0]
+   41 push  1
+   46 add
+   47 neg
+   48 push  4
+   53 mod
+   54 prti
+   55 push  0
+   60 prts
+   61 fetch [0]
+   66 not
+   67 prti
+   68 fetch [0]
+   73 not
+   74 not
+   75 prti
+   76 push  0
+   81 not
+   82 prti
+   83 push  1
+   88 prts
+   89 push  1
+   94 push  2
+   99 lt
+  100 push  2
+  105 push  1
+  110 gt
+  111 eq
+  112 prti
+  113 push  7
+  118 push  2
+  123 div
+  124 push  7
+  129 push  2
+  134 mod
+  135 sub
+  136 prti
+  137 push  1
+  142 push  0
+  147 push  0
+  152 and
+  153 or
+  154 prti
+  155 push  1
+  160 push  0
+  165 or
+  166 push  0
+  171 and
+  172 prti
+  173 push  1
+  178 prts
+  179 fetch [0]
+  184 push  5
+  189 ge
+  190 jz     (15) 206
+  195 push  121
+  200 prtc
+  201 jmp    (10) 212
+  206 push  110
+  211 prtc
+  212 push  10
+  217 prtc
+  218 halt
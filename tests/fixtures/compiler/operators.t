@@ -0,0 +1,7 @@
+/* Unary operators, precedence, and the empty statement */
+x = -1 - -2 * +3;                           /* 5 */
+;
+print(x, " ", -(x + 1) % 4, " ", !x, !!x, !0, "\n");
+print(1 < 2 == 2 > 1, 7 / 2 - 7 % 2, 1 || 0 && 0, (1 || 0) && 0, "\n");
+if (x >= 5) putc('y'); else putc('n');
+putc(10);
@@ -0,0 +1,10 @@
+Datasize: 0 Strings: 2
+"\nHello World\nGood Bye\nok\n"
+"Print a slash n - \\n.\n"
+    0 push  42
+    5 prti
+    6 push  0
+   11 prts
+   12 push  1
+   17 prts
+   18 halt
@@ -0,0 +1,4 @@
+/*** test printing, embedded \n and comments with lots of '*' ***/
+print(42);
+print("\nHello World\nGood Bye\nok\n");
+print("Print a slash n - \\n.\n");
@@ -0,0 +1,118 @@
+// Runs tasks and compares what they print with the golden files in tests/expected/, one per task.
+//
+// Lines are compared with trailing whitespace trimmed.  A golden line starting with `re:` is
+// instead a regular expression the whole output line must match, for output that differs from run
+// to run, such as timings.  For tasks whose threads print in no particular order, every golden
+// line just has to match a different output line, wherever it is.
+//
+// To add a task, add a golden!() line below and run its test with GOLDEN_BLESS=1 set: the missing
+// golden file is written from the output, and any `re:` lines can then be put in by hand.
+
+extern crate regex;
+
+use regex::Regex;
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::Command;
+
+// How the output of a task is checked
+#[derive(Clone, Copy, PartialEq)]
+enum Order {
+    Ordered,   // Line for line
+    Unordered, // Each golden line matches some output line
+}
+
+// A line of a golden file
+enum Expected {
+    Literal(String),
+    Pattern(Regex),
+}
+
+impl Expected {
+    fn parse(line: &str) -> Expected {
+        if let Some(pattern) = line.strip_prefix("re:") {
+            Expected::Pattern(Regex::new(&format!("^(?:{})$", pattern)).unwrap())
+        } else {
+            Expected::Literal(line.trim_end().to_string())
+        }
+    }
+
+    fn matches(&self, line: &str) -> bool {
+        match *self {
+            Expected::Literal(ref literal) => literal == line.trim_end(),
+            Expected::Pattern(ref regex) => regex.is_match(line.trim_end()),
+        }
+    }
+}
+
+// Run the binary `bin` of `task` with `args`, and check its output against the task's golden file.
+fn check(task: &str, bin: &str, args: &[&str], order: Order) {
+    let output = Command::new(bin).args(args).output().unwrap();
+    assert!(output.status.success(), "{} failed: {}", task,
+            String::from_utf8_lossy(&output.stderr));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "expected", &format!("{}.txt", task)]
+        .iter().collect();
+    let mut golden = String::new();
+    match File::open(&path) {
+        Ok(mut file) => file.read_to_string(&mut golden).unwrap(),
+        Err(_) if env::var_os("GOLDEN_BLESS").is_some() => {
+            File::create(&path).unwrap().write_all(actual.as_bytes()).unwrap();
+            return;
+        }
+        Err(error) => panic!("can't read {}: {} (set GOLDEN_BLESS=1 to write it)",
+                             path.display(), error),
+    };
+    let expected: Vec<_> = golden.lines().map(Expected::parse).collect();
+    let actual: Vec<_> = actual.lines().collect();
+    assert_eq!(actual.len(), expected.len(), "{} printed:\n{}", task, actual.join("\n"));
+    match order {
+        Order::Ordered => {
+            for (i, (line, expected)) in actual.iter().zip(&expected).enumerate() {
+                assert!(expected.matches(line), "{} line {} doesn't match: {}", task, i + 1, line);
+            }
+        }
+        Order::Unordered => {
+            // Literal lines could be claimed by a pattern that also matches them, so they go first
+            let (literals, patterns): (Vec<_>, Vec<_>) = expected.iter().partition(|expected| {
+                match **expected { Expected::Literal(_) => true, Expected::Pattern(_) => false }
+            });
+            let mut unclaimed = actual.clone();
+            for expected in literals.into_iter().chain(patterns) {
+                match unclaimed.iter().position(|line| expected.matches(line)) {
+                    Some(i) => { unclaimed.remove(i); }
+                    None => panic!("{} printed:\n{}\nbut nothing left matches a golden line", task,
+                                   actual.join("\n")),
+                }
+            }
+        }
+    }
+}
+
+macro_rules! golden {
+    ($test:ident, $task:expr) => {
+        golden!($test, $task, [], Order::Ordered);
+    };
+    ($test:ident, $task:expr, [$($arg:expr),*], $order:expr) => {
+        #[test]
+        fn $test() {
+            check($task, env!(concat!("CARGO_BIN_EXE_", $task)), &[$($arg),*], $order);
+        }
+    };
+}
+
+golden!(doors, "100_doors");
+golden!(gray_code, "gray_code");
+golden!(josephus_problem, "josephus_problem");
+golden!(leap_year, "leap_year");
+golden!(lu_decomposition, "lu_decomposition");
+golden!(pascals_triangle, "pascals_triangle");
+golden!(power_set, "power_set");
+golden!(rot13, "rot13");
+golden!(towers_of_hanoi, "towers_of_hanoi");
+golden!(zig_zag_matrix, "zig-zag_matrix");
+// Takes a while, since the phased and rate limited demos don't depend on the options
+golden!(metered_concurrency, "metered_concurrency",
+        ["--permits", "2", "--workers", "3", "--hold-ms", "10"], Order::Unordered);
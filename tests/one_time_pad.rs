@@ -0,0 +1,48 @@
+// Runs one_time_pad's commands on pad files in the temporary directory, the way two people with
+// a copy of the same pad each would, to check that the pages get used up in the files and that a
+// page is never used twice.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// What one_time_pad prints when run with `args`, or what it complains of if it fails
+fn run(args: &[&str]) -> Result<String, String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_one_time_pad")).args(args).output().unwrap();
+    if output.status.success() {
+        Ok(String::from_utf8(output.stdout).unwrap().trim_end().to_string())
+    } else {
+        Err(String::from_utf8(output.stderr).unwrap().trim_end().to_string())
+    }
+}
+
+fn pad(side: &str) -> PathBuf {
+    env::temp_dir().join(format!("one_time_pad_test_{}_{}.1tp", side, std::process::id()))
+}
+
+#[test]
+fn one_time_pad() {
+    let (alice, bob) = (pad("alice"), pad("bob"));
+    let (a, b) = (alice.to_str().unwrap(), bob.to_str().unwrap());
+    let _ = fs::remove_file(&alice);
+    let _ = fs::remove_file(&bob);
+
+    run(&["generate", a, "3"]).unwrap();
+    assert!(run(&["generate", a, "3"]).is_err(), "a pad was overwritten");
+    fs::copy(&alice, &bob).unwrap();
+    assert_eq!(run(&["list", a]), Ok("Pages left: 1 2 3".to_string()));
+
+    let ciphertext = run(&["encrypt", a, "Meet", "at", "noon"]).unwrap();
+    assert!(ciphertext.starts_with("1 "), "{}", ciphertext);
+    assert_eq!(run(&["list", a]), Ok("Pages left: 2 3".to_string()));
+    assert_eq!(run(&["decrypt", b, &ciphertext]), Ok("MEETATNOON".to_string()));
+    assert_eq!(run(&["decrypt", b, &ciphertext]), Err("page 1 has been used already".to_string()));
+    assert!(fs::read_to_string(&bob).unwrap().lines().nth(1).unwrap().starts_with('-'));
+
+    let reply = run(&["encrypt", b, "OK"]).unwrap();
+    assert!(reply.starts_with("2 "), "{}", reply);
+    assert_eq!(run(&["decrypt", a, &reply]), Ok("OK".to_string()));
+    fs::remove_file(&alice).unwrap();
+    fs::remove_file(&bob).unwrap();
+}
@@ -0,0 +1,94 @@
+// The visual tasks in a browser.  Build with wasm-pack (https://rustwasm.github.io/wasm-pack/):
+//
+//     wasm-pack build --target web wasm
+//
+// then serve the wasm directory, for instance with `python3 -m http.server --directory wasm`,
+// and open index.html.
+//
+// This is a crate of its own, only ever built for wasm32-unknown-unknown, so that none of the
+// tasks or dependencies that need an operating system come into it.  The tasks it shows are
+// included by path, and draw on a PixelCanvas (see src/utils/canvas.rs) whose bytes go straight
+// into the canvas element's ImageData.
+
+// Each task includes src/utils itself, and this crate needs it too
+#![allow(clippy::duplicate_mod)]
+
+extern crate wasm_bindgen;
+
+use wasm_bindgen::prelude::*;
+
+// The tasks refer to utils from the crate root, as they do when they're built on their own
+#[path = "../../src/utils/mod.rs"]
+#[allow(dead_code)]
+mod utils;
+#[path = "../../src/graphics/conways_game_of_life.rs"]
+#[allow(dead_code)]
+mod conways_game_of_life;
+#[path = "../../src/graphics/mandelbrot_set.rs"]
+#[allow(dead_code)]
+mod mandelbrot_set;
+
+use conways_game_of_life::Life;
+use utils::canvas::PixelCanvas;
+use utils::rng::Rng;
+
+// The Mandelbrot set, as `width` x `height` RGBA pixels
+#[wasm_bindgen]
+pub fn mandelbrot(width: usize, height: usize) -> Vec<u8> {
+    let mut canvas = PixelCanvas::new(width, height);
+    mandelbrot_set::draw(&mut canvas);
+    canvas.into_rgba()
+}
+
+// A Game of Life board, drawn on a canvas of a fixed size
+#[wasm_bindgen]
+pub struct GameOfLife {
+    life: Life,
+    canvas: PixelCanvas,
+}
+
+#[wasm_bindgen]
+impl GameOfLife {
+    // A random board of `cols` x `rows` cells, a quarter of them alive, to be drawn on a canvas
+    // of `width` x `height` pixels
+    #[wasm_bindgen(constructor)]
+    pub fn new(cols: usize, rows: usize, width: usize, height: usize, seed: u32) -> GameOfLife {
+        GameOfLife {
+            life: Life::random(cols, rows, 0.25, &mut Rng::new(seed as u64)),
+            canvas: PixelCanvas::new(width, height),
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.life.step();
+    }
+
+    pub fn population(&self) -> usize {
+        self.life.population()
+    }
+
+    // The board as RGBA pixels
+    pub fn pixels(&mut self) -> Vec<u8> {
+        self.life.draw(&mut self.canvas);
+        self.canvas.rgba().to_vec()
+    }
+}
+
+#[test]
+fn test_mandelbrot() {
+    let pixels = mandelbrot(70, 50);
+    assert_eq!(pixels.len(), 70 * 50 * 4);
+    // (-1, 0) is in the set, drawn in white
+    let i = (25 * 70 + 30) * 4;
+    assert_eq!(&pixels[i..i + 4], &[255, 255, 255, 255]);
+}
+
+#[test]
+fn test_game_of_life() {
+    let mut game = GameOfLife::new(40, 30, 80, 60, 1);
+    let start = game.pixels();
+    assert_eq!(start.len(), 80 * 60 * 4);
+    assert!(game.population() > 0);
+    game.step();
+    assert!(game.pixels() != start);
+}
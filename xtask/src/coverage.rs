@@ -0,0 +1,238 @@
+// `cargo xtask coverage`: compare the tasks in the crate's registry (see src/rosetta_meta.rs) with
+// the task list on Rosetta Code, and report
+//
+// * the tasks on Rosetta Code that the crate has no solution for,
+// * the tasks the crate solves that have no Rust solution on Rosetta Code yet, so ours could be
+//   submitted, and
+// * the crate's tasks whose page isn't a task (any more), for instance because it was renamed.
+//
+// The task lists come from the MediaWiki API, fetched with curl.
+
+use rustc_serialize::json::Json;
+use std::collections::BTreeSet;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+pub static API: &str = "https://rosettacode.org/w/api.php";
+
+pub fn run() -> Result<(), String> {
+    let local = local_tasks()?;
+    let mut tasks = category_members("Programming_Tasks")?;
+    tasks.extend(category_members("Draft_Programming_Tasks")?);
+    let rust = category_members("Rust")?;
+    let report = Report::new(&local, &tasks, &rust);
+    println!("{} of {} tasks implemented", tasks.len() - report.unimplemented.len(), tasks.len());
+    print_section("Not implemented", &report.unimplemented);
+    print_section("Implemented here, but not on Rosetta Code", &report.unsubmitted);
+    print_section("Not a task on Rosetta Code", &report.unknown);
+    Ok(())
+}
+
+fn print_section(heading: &str, titles: &BTreeSet<String>) {
+    println!();
+    println!("{} ({}):", heading, titles.len());
+    for title in titles {
+        println!("    {}", title);
+    }
+}
+
+// The crate's view of Rosetta Code, set against the real thing
+#[derive(Debug, PartialEq)]
+struct Report {
+    unimplemented: BTreeSet<String>, // Tasks the crate doesn't solve
+    unsubmitted: BTreeSet<String>,   // Tasks solved here but missing a Rust solution there
+    unknown: BTreeSet<String>,       // Pages the crate links to that aren't tasks
+}
+
+impl Report {
+    // Compare the titles of the `local` tasks with all of the `tasks` on Rosetta Code, and the
+    // pages that have a `rust` solution.
+    fn new(local: &[String], tasks: &[String], rust: &[String]) -> Report {
+        let local: BTreeSet<_> = local.iter().cloned().collect();
+        let tasks: BTreeSet<_> = tasks.iter().cloned().collect();
+        let rust: BTreeSet<_> = rust.iter().cloned().collect();
+        Report {
+            unimplemented: tasks.difference(&local).cloned().collect(),
+            unsubmitted: local.intersection(&tasks).filter(|title| !rust.contains(*title))
+                              .cloned().collect(),
+            unknown: local.difference(&tasks).cloned().collect(),
+        }
+    }
+}
+
+// The titles of the Rosetta Code pages of the crate's tasks
+fn local_tasks() -> Result<Vec<String>, String> {
+    Ok(parse_registry(&registry()?))
+}
+
+// The task registry, as printed by the rosetta_meta binary: a line of tab-separated name, URL
+// (or `-`), path, category (or `-`), features, variant and status for each task
+pub fn registry() -> Result<String, String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["run", "--quiet", "--bin", "rosetta_meta"])
+        .current_dir(root)
+        .output()
+        .map_err(|error| format!("couldn't run rosetta_meta: {}", error))?;
+    if !output.status.success() {
+        return Err(format!("rosetta_meta failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Pick the page titles out of rosetta_meta's name, URL and path columns, skipping tasks that
+// don't have a page.
+fn parse_registry(registry: &str) -> Vec<String> {
+    registry.lines().filter_map(|line| line.split('\t').nth(1)).filter_map(title_from_url).collect()
+}
+
+// The title of the page at `url`, such as "Metered concurrency" for
+// http://rosettacode.org/wiki/Metered_concurrency
+pub fn title_from_url(url: &str) -> Option<String> {
+    let page = url.split("/wiki/").nth(1)?;
+    let page = percent_decode(page).replace('_', " ");
+    // MediaWiki titles always start with a capital
+    let mut chars = page.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = if bytes[i] == b'%' {
+            s.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+pub fn percent_encode(s: &str) -> String {
+    s.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (byte as char).to_string(),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
+// The titles of every page in a category, following the API's continuation markers to get past
+// its limit on how many are returned at once.
+fn category_members(category: &str) -> Result<Vec<String>, String> {
+    let mut titles = Vec::new();
+    let mut continue_from: Option<String> = None;
+    loop {
+        let mut url = format!("{}?action=query&list=categorymembers&cmtitle=Category:{}\
+                               &cmlimit=500&format=json", API, category);
+        if let Some(ref from) = continue_from {
+            url.push_str("&cmcontinue=");
+            url.push_str(&percent_encode(from));
+        }
+        let (page, next) = parse_members(&fetch(&url)?)?;
+        titles.extend(page);
+        match next {
+            Some(next) => continue_from = Some(next),
+            None => return Ok(titles),
+        }
+    }
+}
+
+pub fn fetch(url: &str) -> Result<String, String> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", url])
+        .output()
+        .map_err(|error| format!("couldn't run curl: {}", error))?;
+    if !output.status.success() {
+        return Err(format!("couldn't fetch {}: {}", url, String::from_utf8_lossy(&output.stderr)));
+    }
+    String::from_utf8(output.stdout).map_err(|_| format!("{} isn't UTF-8", url))
+}
+
+// The titles in one response to a categorymembers query, and where the next one continues from
+// if there are more.
+fn parse_members(response: &str) -> Result<(Vec<String>, Option<String>), String> {
+    let json = Json::from_str(response).map_err(|error| format!("bad response: {}", error))?;
+    let members = json.find_path(&["query", "categorymembers"]).and_then(Json::as_array)
+                      .ok_or_else(|| "no category members in response".to_string())?;
+    let titles = members.iter().filter_map(|member| member.find("title").and_then(Json::as_string))
+                        .map(str::to_string).collect();
+    let next = json.find_path(&["continue", "cmcontinue"]).and_then(Json::as_string)
+                   .map(str::to_string);
+    Ok((titles, next))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_members, parse_registry, percent_encode, title_from_url, Report};
+
+    fn strings(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(title_from_url("http://rosettacode.org/wiki/Metered_concurrency"),
+                   Some("Metered concurrency".to_string()));
+        assert_eq!(title_from_url("http://rosettacode.org/wiki/Rot-13"),
+                   Some("Rot-13".to_string()));
+        assert_eq!(title_from_url("http://rosettacode.org/wiki/Man_or_boy_test"),
+                   Some("Man or boy test".to_string()));
+        assert_eq!(title_from_url("http://rosettacode.org/wiki/Knapsack_problem/0-1"),
+                   Some("Knapsack problem/0-1".to_string()));
+        assert_eq!(title_from_url("http://rosettacode.org/wiki/Closures%2FValue_capture"),
+                   Some("Closures/Value capture".to_string()));
+        assert_eq!(title_from_url("http://rosettacode.org/wiki/ackermann_function"),
+                   Some("Ackermann function".to_string()));
+        assert_eq!(title_from_url("-"), None);
+    }
+
+    #[test]
+    fn registry() {
+        let registry = "100_doors\thttp://rosettacode.org/wiki/100_doors\t\
+                        src/puzzles/100_doors.rs\tpuzzles\t-\t-\tcomplete\n\
+                        rosetta\t-\tsrc/rosetta.rs\t-\t-\t-\tcomplete\n";
+        assert_eq!(parse_registry(registry), strings(&["100 doors"]));
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(percent_encode("page|4142|Some title"), "page%7C4142%7CSome%20title");
+    }
+
+    #[test]
+    fn members() {
+        let response = r#"{"continue":{"cmcontinue":"page|41|Zig","continue":"-||"},
+                           "query":{"categorymembers":[{"pageid":1,"ns":0,"title":"100 doors"},
+                                                        {"pageid":2,"ns":0,"title":"24 game"}]}}"#;
+        assert_eq!(parse_members(response),
+                   Ok((strings(&["100 doors", "24 game"]), Some("page|41|Zig".to_string()))));
+        let last = r#"{"query":{"categorymembers":[{"ns":0,"title":"Zig-zag matrix"}]}}"#;
+        assert_eq!(parse_members(last), Ok((strings(&["Zig-zag matrix"]), None)));
+        assert!(parse_members("{}").is_err());
+    }
+
+    #[test]
+    fn report() {
+        let local = strings(&["100 doors", "Metered concurrency", "Old task", "100 doors"]);
+        let tasks = strings(&["100 doors", "24 game", "Metered concurrency"]);
+        let rust = strings(&["100 doors", "24 game"]);
+        let report = Report::new(&local, &tasks, &rust);
+        assert_eq!(report.unimplemented, strings(&["24 game"]).into_iter().collect());
+        assert_eq!(report.unsubmitted, strings(&["Metered concurrency"]).into_iter().collect());
+        assert_eq!(report.unknown, strings(&["Old task"]).into_iter().collect());
+    }
+}
@@ -0,0 +1,352 @@
+// `cargo xtask export [--offline]`: write tasks.json, at the root of the crate, describing each
+// task in the registry (see src/rosetta_meta.rs) for tools outside the crate, such as dashboards
+// and whatever keeps the wiki in sync with it.  It is an array with an object per task:
+//
+//     {
+//       "benches": true,               Whether benches/ benchmarks it
+//       "categories": ["Sorting Algorithms"],
+//       "category": "sorting",         The directory under src/ it's in
+//       "features": [],                The cargo features it's only built with, such as "net"
+//       "golden": false,               Whether tests/golden.rs checks its output
+//       "module": "merge_sort",        Its name as a module, as benches/ include it
+//       "name": "merge-sort",          The name of its binary
+//       "path": "src/sorting/merge-sort.rs",
+//       "status": "complete",          Or "draft", if it's marked as still being worked on
+//       "tests": true,                 Whether it has unit tests
+//       "title": "Sorting algorithms/Merge sort",
+//       "url": "http://rosettacode.org/wiki/Sorting_algorithms/Merge_sort",
+//       "variant_of": null             The task it's another solution to, if it is one
+//     }
+//
+// The categories are the ones its page is in on Rosetta Code, fetched from the MediaWiki API, or
+// left empty with --offline, and the category is the crate's own.  Tasks without a page have a
+// null title and URL, and the tools that aren't tasks a null category.
+
+use coverage::{fetch, percent_encode, registry, title_from_url, API};
+use rustc_serialize::json::{Json, ToJson};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+static USAGE: &str = "Usage: cargo xtask export [--offline]";
+
+// How many pages the API gives the categories of in one request
+static TITLES_PER_REQUEST: usize = 50;
+
+pub fn run(args: &[&str]) -> Result<(), String> {
+    let offline = match *args {
+        [] => false,
+        ["--offline"] => true,
+        _ => return Err(USAGE.to_string()),
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let benches = bench_sources(&root.join("benches"))?;
+    let mut tasks = Vec::new();
+    for entry in parse_registry(&registry()?) {
+        let source = fs::read_to_string(root.join(&entry.path))
+            .map_err(|error| format!("couldn't read {}: {}", entry.path, error))?;
+        tasks.push(Task {
+            module: module_name(&entry.path),
+            title: entry.url.as_ref().and_then(|url| title_from_url(url)),
+            tests: has_tests(&source),
+            benches: benches.iter().any(|bench| benchmarks(bench, &entry.path)),
+            golden: root.join("tests/expected").join(format!("{}.txt", entry.name)).exists(),
+            categories: Vec::new(),
+            category: entry.category,
+            features: entry.features,
+            variant_of: entry.variant_of,
+            status: entry.status,
+            name: entry.name,
+            path: entry.path,
+            url: entry.url,
+        });
+    }
+    if !offline {
+        let titles: Vec<String> = tasks.iter().filter_map(|task| task.title.clone()).collect();
+        let categories = categories(&titles)?;
+        for task in &mut tasks {
+            if let Some(found) = task.title.as_ref().and_then(|title| categories.get(title)) {
+                task.categories = found.clone();
+            }
+        }
+    }
+    let json = Json::Array(tasks.iter().map(Task::to_json).collect());
+    let path = root.join("tasks.json");
+    fs::write(&path, format!("{}\n", json.pretty()))
+        .map_err(|error| format!("couldn't write {}: {}", path.display(), error))?;
+    println!("Wrote {} tasks to {}", tasks.len(), path.display());
+    Ok(())
+}
+
+// One line of the registry
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub url: Option<String>,
+    pub path: String,
+    pub category: Option<String>,
+    pub features: Vec<String>,
+    pub variant_of: Option<String>,
+    pub status: String, // "complete" or "draft"
+}
+
+pub fn parse_registry(registry: &str) -> Vec<Entry> {
+    registry.lines().filter_map(|line| {
+        let mut columns = line.split('\t');
+        let (name, url, path) = (columns.next()?, columns.next()?, columns.next()?);
+        let column = |value: &str| if value == "-" { None } else { Some(value.to_string()) };
+        Some(Entry {
+            name: name.to_string(),
+            url: column(url),
+            path: path.to_string(),
+            category: columns.next().and_then(column),
+            features: columns.next().and_then(column).map_or(Vec::new(), |features| {
+                features.split(',').map(str::to_string).collect()
+            }),
+            variant_of: columns.next().and_then(column),
+            status: columns.next().unwrap_or("complete").to_string(),
+        })
+    }).collect()
+}
+
+#[derive(Debug, PartialEq)]
+struct Task {
+    name: String,
+    module: String,
+    path: String,
+    url: Option<String>,
+    title: Option<String>,
+    categories: Vec<String>,
+    category: Option<String>,
+    features: Vec<String>,
+    variant_of: Option<String>,
+    status: String,
+    tests: bool,
+    benches: bool,
+    golden: bool,
+}
+
+impl ToJson for Task {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("name".to_string(), self.name.to_json());
+        object.insert("module".to_string(), self.module.to_json());
+        object.insert("path".to_string(), self.path.to_json());
+        object.insert("url".to_string(), self.url.to_json());
+        object.insert("title".to_string(), self.title.to_json());
+        object.insert("categories".to_string(), self.categories.to_json());
+        object.insert("category".to_string(), self.category.to_json());
+        object.insert("features".to_string(), self.features.to_json());
+        object.insert("variant_of".to_string(), self.variant_of.to_json());
+        object.insert("status".to_string(), self.status.to_json());
+        object.insert("tests".to_string(), self.tests.to_json());
+        object.insert("benches".to_string(), self.benches.to_json());
+        object.insert("golden".to_string(), self.golden.to_json());
+        Json::Object(object)
+    }
+}
+
+// The name a task's source file is included under as a module, such as merge_sort for
+// src/sorting/merge-sort.rs
+fn module_name(path: &str) -> String {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    file.strip_suffix(".rs").unwrap_or(file).replace('-', "_")
+}
+
+fn has_tests(source: &str) -> bool {
+    source.contains("#[test]")
+}
+
+// Whether a benchmark's source includes the task at `path`, as in
+// `#[path = "../src/sorting/heap_sort.rs"]`
+fn benchmarks(bench: &str, path: &str) -> bool {
+    bench.contains(&format!("\"../{}\"", path))
+}
+
+fn bench_sources(benches: &Path) -> Result<Vec<String>, String> {
+    let read_error = |error| format!("couldn't read {}: {}", benches.display(), error);
+    let mut sources = Vec::new();
+    for file in fs::read_dir(benches).map_err(read_error)? {
+        let path = file.map_err(read_error)?.path();
+        if path.extension().is_some_and(|extension| extension == "rs") {
+            sources.push(fs::read_to_string(&path).map_err(read_error)?);
+        }
+    }
+    Ok(sources)
+}
+
+// The categories of each of the pages with the given titles, by the title asked for even if the
+// page turned out to be a redirect
+fn categories(titles: &[String]) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let mut found: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut renamed = BTreeMap::new();
+    for batch in titles.chunks(TITLES_PER_REQUEST) {
+        let titles: Vec<String> = batch.iter().map(|title| percent_encode(title)).collect();
+        let mut continue_from: Option<String> = None;
+        loop {
+            let mut url = format!("{}?action=query&prop=categories&clshow=!hidden&cllimit=max\
+                                   &redirects&format=json&titles={}", API, titles.join("%7C"));
+            if let Some(ref from) = continue_from {
+                url.push_str("&clcontinue=");
+                url.push_str(&percent_encode(from));
+            }
+            let page = parse_categories(&fetch(&url)?)?;
+            for (title, categories) in page.categories {
+                found.entry(title).or_default().extend(categories);
+            }
+            renamed.extend(page.renamed);
+            match page.next {
+                Some(next) => continue_from = Some(next),
+                None => break,
+            }
+        }
+    }
+    Ok(titles.iter().filter_map(|title| {
+        let page = renamed.get(title).unwrap_or(title);
+        found.get(page).map(|categories| (title.clone(), categories.clone()))
+    }).collect())
+}
+
+// One response to a categories query
+#[derive(Debug, PartialEq)]
+struct CategoriesPage {
+    categories: BTreeMap<String, Vec<String>>, // By the title of each page, without "Category:"
+    renamed: BTreeMap<String, String>,         // The page each redirect asked for went to
+    next: Option<String>,                      // Where the next response continues from
+}
+
+fn parse_categories(response: &str) -> Result<CategoriesPage, String> {
+    let json = Json::from_str(response).map_err(|error| format!("bad response: {}", error))?;
+    let pages = json.find_path(&["query", "pages"]).and_then(Json::as_object)
+                    .ok_or_else(|| "no pages in response".to_string())?;
+    let mut categories = BTreeMap::new();
+    for page in pages.values() {
+        let title = match page.find("title").and_then(Json::as_string) {
+            Some(title) => title.to_string(),
+            None => continue,
+        };
+        let names = page.find("categories").and_then(Json::as_array).map_or(Vec::new(), |list| {
+            list.iter().filter_map(|category| category.find("title").and_then(Json::as_string))
+                .map(|name| name.strip_prefix("Category:").unwrap_or(name).to_string())
+                .collect()
+        });
+        categories.insert(title, names);
+    }
+    let mut renamed = BTreeMap::new();
+    let redirects = json.find_path(&["query", "redirects"]).and_then(Json::as_array);
+    for redirect in redirects.into_iter().flatten() {
+        if let (Some(from), Some(to)) = (redirect.find("from").and_then(Json::as_string),
+                                         redirect.find("to").and_then(Json::as_string)) {
+            renamed.insert(from.to_string(), to.to_string());
+        }
+    }
+    let next = json.find_path(&["continue", "clcontinue"]).and_then(Json::as_string)
+                   .map(str::to_string);
+    Ok(CategoriesPage { categories, renamed, next })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{benchmarks, has_tests, module_name, parse_categories, parse_registry, Entry,
+                Task};
+    use rustc_serialize::json::{Json, ToJson};
+
+    #[test]
+    fn registry() {
+        let registry = "merge-sort\thttp://rosettacode.org/wiki/Sorting_algorithms/Merge_sort\t\
+                        src/sorting/merge-sort.rs\tsorting\t-\t-\tcomplete\n\
+                        dns_query\thttp://rosettacode.org/wiki/DNS_query\tsrc/net/dns_query.rs\t\
+                        net\tnet,std\t-\tcomplete\n\
+                        hamming_numbers_alt\thttp://rosettacode.org/wiki/Hamming_numbers\t\
+                        src/math/hamming_numbers_alt.rs\tmath\t-\thamming_numbers\tdraft\n\
+                        rosetta_meta\t-\tsrc/rosetta_meta.rs\t-\t-\t-\n";
+        assert_eq!(parse_registry(registry), vec![
+            Entry {
+                name: "merge-sort".to_string(),
+                url: Some("http://rosettacode.org/wiki/Sorting_algorithms/Merge_sort".to_string()),
+                path: "src/sorting/merge-sort.rs".to_string(),
+                category: Some("sorting".to_string()),
+                features: Vec::new(),
+                variant_of: None,
+                status: "complete".to_string(),
+            },
+            Entry {
+                name: "dns_query".to_string(),
+                url: Some("http://rosettacode.org/wiki/DNS_query".to_string()),
+                path: "src/net/dns_query.rs".to_string(),
+                category: Some("net".to_string()),
+                features: vec!["net".to_string(), "std".to_string()],
+                variant_of: None,
+                status: "complete".to_string(),
+            },
+            Entry {
+                name: "hamming_numbers_alt".to_string(),
+                url: Some("http://rosettacode.org/wiki/Hamming_numbers".to_string()),
+                path: "src/math/hamming_numbers_alt.rs".to_string(),
+                category: Some("math".to_string()),
+                features: Vec::new(),
+                variant_of: Some("hamming_numbers".to_string()),
+                status: "draft".to_string(),
+            },
+            Entry { name: "rosetta_meta".to_string(), url: None,
+                    path: "src/rosetta_meta.rs".to_string(), category: None,
+                    features: Vec::new(), variant_of: None, status: "complete".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn modules() {
+        assert_eq!(module_name("src/sorting/merge-sort.rs"), "merge_sort");
+        assert_eq!(module_name("src/puzzles/100_doors.rs"), "100_doors");
+    }
+
+    #[test]
+    fn sources() {
+        assert!(has_tests("#[cfg(test)]\nmod test {\n    #[test]\n    fn works() {}\n}\n"));
+        assert!(!has_tests("fn main() {}\n"));
+        let bench = "#[path = \"../src/sorting/heap_sort.rs\"]\n#[allow(dead_code)]\n\
+                     mod heap_sort;\n";
+        assert!(benchmarks(bench, "src/sorting/heap_sort.rs"));
+        assert!(!benchmarks(bench, "src/sorting/sort.rs"));
+    }
+
+    #[test]
+    fn categories() {
+        let response = r#"{"continue":{"clcontinue":"1234|Sorting","continue":"||"},
+            "query":{"redirects":[{"from":"Rot-13","to":"Rot-13 cipher"}],
+                     "pages":{"1":{"pageid":1,"ns":0,"title":"Rot-13 cipher",
+                                   "categories":[{"ns":14,"title":"Category:Encryption"},
+                                                 {"ns":14,"title":"Category:String manipulation"}]},
+                              "2":{"pageid":2,"ns":0,"title":"100 doors"}}}}"#;
+        let page = parse_categories(response).unwrap();
+        assert_eq!(page.categories["Rot-13 cipher"], vec!["Encryption", "String manipulation"]);
+        assert!(page.categories["100 doors"].is_empty());
+        assert_eq!(page.renamed["Rot-13"], "Rot-13 cipher");
+        assert_eq!(page.next, Some("1234|Sorting".to_string()));
+        assert!(parse_categories("{}").is_err());
+    }
+
+    #[test]
+    fn json() {
+        let task = Task {
+            name: "rosetta_meta".to_string(),
+            module: "rosetta_meta".to_string(),
+            path: "src/rosetta_meta.rs".to_string(),
+            url: None,
+            title: None,
+            categories: Vec::new(),
+            category: None,
+            features: Vec::new(),
+            variant_of: None,
+            status: "complete".to_string(),
+            tests: true,
+            benches: false,
+            golden: false,
+        };
+        assert_eq!(task.to_json(), Json::from_str(r#"{"name":"rosetta_meta",
+            "module":"rosetta_meta","path":"src/rosetta_meta.rs","url":null,"title":null,
+            "categories":[],"category":null,"features":[],"variant_of":null,"status":"complete",
+            "tests":true,"benches":false,"golden":false}"#)
+            .unwrap());
+    }
+}
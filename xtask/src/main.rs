@@ -0,0 +1,38 @@
+// Development tasks for the crate, run from anywhere in it with `cargo xtask <command>`:
+//
+//     coverage           Compare the crate's tasks with the ones on Rosetta Code
+//     export             Describe the crate's tasks in tasks.json, for other tools
+//     new-task "<name>" <category>
+//                        Start a solution to the Rosetta Code task called <name>, in
+//                        src/<category>/
+//     status             List the tasks that are still drafts
+//     test [TASK...]     Run the tasks' tests in parallel, with a timeout for each task
+
+extern crate rustc_serialize;
+
+use std::env;
+
+mod coverage;
+mod export;
+mod new_task;
+mod status;
+mod test_runner;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|arg| &arg[..]).collect();
+    let result = match args[..] {
+        ["coverage"] => coverage::run(),
+        ["export", ref rest @ ..] => export::run(rest),
+        ["new-task", task, category] => new_task::run(task, category),
+        ["status"] => status::run(),
+        ["test", ref rest @ ..] => test_runner::run(rest),
+        _ => Err("Usage: cargo xtask coverage | cargo xtask export [--offline] | \
+                  cargo xtask new-task \"<task name>\" <category> | cargo xtask status | \
+                  cargo xtask test [TASK...]".to_string()),
+    };
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
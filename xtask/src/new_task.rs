@@ -0,0 +1,225 @@
+// `cargo xtask new-task "<Rosetta Code task name>" <category>`: start a solution to a task, by
+// creating src/<category>/<task_name>.rs with the usual header and a main and test to fill in, and
+// adding its [[bin]] entry to Cargo.toml.  The categories are listed in src/lib.rs.  The task
+// registry (see src/rosetta_meta.rs) is generated from Cargo.toml, so the new task is registered
+// along with it.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+pub fn run(task: &str, category: &str) -> Result<(), String> {
+    let name = snake_case(task);
+    if name.is_empty() {
+        return Err(format!("can't make a file name out of {:?}", task));
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    if category.contains('/') || !root.join("src").join(category).is_dir() {
+        return Err(format!("no such category: {} (see src/lib.rs)", category));
+    }
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|error| format!("couldn't read {}: {}", manifest_path.display(), error))?;
+    let path = format!("src/{}/{}.rs", category, name);
+    let url = url(task);
+    let manifest = add_bin(&manifest, &name, &path, &url)?;
+    // Fails rather than overwriting a solution that is already there
+    let mut source = OpenOptions::new().write(true).create_new(true).open(root.join(&path))
+        .map_err(|error| format!("couldn't create {}: {}", path, error))?;
+    source.write_all(skeleton(&name, &url).as_bytes())
+        .map_err(|error| format!("couldn't write {}: {}", path, error))?;
+    fs::write(&manifest_path, manifest)
+        .map_err(|error| format!("couldn't write {}: {}", manifest_path.display(), error))?;
+    println!("Created {}, run it with `cargo run --bin {}`", path, name);
+    Ok(())
+}
+
+// The name of the binary and source file for `task`, such as "sorting_algorithms_bubble_sort"
+// for "Sorting algorithms/Bubble sort"
+fn snake_case(task: &str) -> String {
+    let mut name = String::new();
+    for c in task.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+// The Rosetta Code page of `task`
+fn url(task: &str) -> String {
+    format!("http://rosettacode.org/wiki/{}", task.trim().replace(' ', "_"))
+}
+
+fn skeleton(name: &str, url: &str) -> String {
+    format!("// Implements {}
+
+#[cfg(not(test))]
+fn main() {{
+}}
+
+#[test]
+fn test_{}() {{
+}}
+", url, name)
+}
+
+// Return `manifest` with a [[bin]] entry for the task added, keeping the entries in alphabetical
+// order.
+fn add_bin(manifest: &str, name: &str, path: &str, url: &str) -> Result<String, String> {
+    let entry = format!("[[bin]]\n# {}\nname = \"{}\"\npath = \"{}\"\n", url, name, path);
+    let lines: Vec<&str> = manifest.lines().collect();
+    // Where the new entry goes: before the first entry that sorts after it or, failing that,
+    // after the last entry
+    let mut insert_at = None;
+    let mut after_last_bin = None;
+    let mut in_bin = false;
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            if line == "[[bin]]" {
+                after_last_bin = None;
+            } else if in_bin {
+                after_last_bin = Some(i);
+            }
+            in_bin = line == "[[bin]]";
+        } else if let (true, Some(existing)) = (in_bin, bin_name(line)) {
+            if existing == name {
+                return Err(format!("Cargo.toml already has a binary called {}", name));
+            }
+            if insert_at.is_none() && existing.to_lowercase() > name.to_lowercase() {
+                // Back up to the entry's [[bin]] line
+                insert_at = (0..i).rev().find(|&j| lines[j].trim() == "[[bin]]");
+            }
+        }
+    }
+    let mut result = String::new();
+    match insert_at.or(after_last_bin) {
+        Some(i) => {
+            for line in &lines[..i] {
+                result.push_str(line);
+                result.push('\n');
+            }
+            // Entries are separated by a blank line
+            if !result.ends_with("\n\n") {
+                result.push('\n');
+            }
+            result.push_str(&entry);
+            result.push('\n');
+            for line in &lines[i..] {
+                result.push_str(line);
+                result.push('\n');
+            }
+        }
+        None => {
+            result.push_str(manifest.trim_end());
+            result.push_str("\n\n");
+            result.push_str(&entry);
+        }
+    }
+    Ok(result)
+}
+
+// The name in a `name = "..."` line
+fn bin_name(line: &str) -> Option<&str> {
+    let value = line.strip_prefix("name")?.trim_start().strip_prefix('=')?.trim();
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add_bin, skeleton, snake_case, url};
+
+    #[test]
+    fn names() {
+        assert_eq!(snake_case("100 doors"), "100_doors");
+        assert_eq!(snake_case("Sorting algorithms/Bubble sort"), "sorting_algorithms_bubble_sort");
+        assert_eq!(snake_case("A+B"), "a_b");
+        assert_eq!(snake_case("Zig-zag matrix "), "zig_zag_matrix");
+        assert_eq!(snake_case("Knapsack problem/0-1"), "knapsack_problem_0_1");
+        assert_eq!(snake_case("?"), "");
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(url("Metered concurrency"), "http://rosettacode.org/wiki/Metered_concurrency");
+        assert_eq!(url("Knapsack problem/0-1"), "http://rosettacode.org/wiki/Knapsack_problem/0-1");
+    }
+
+    #[test]
+    fn source() {
+        let source = skeleton("gray_code", "http://rosettacode.org/wiki/Gray_code");
+        assert!(source.starts_with("// Implements http://rosettacode.org/wiki/Gray_code\n"));
+        assert!(source.contains("#[cfg(not(test))]\nfn main() {\n"));
+        assert!(source.contains("#[test]\nfn test_gray_code() {\n"));
+    }
+
+    static MANIFEST: &str = "[package]
+name = \"rust-rosetta\"
+
+[[bin]]
+# http://rosettacode.org/wiki/100_doors
+name = \"100_doors\"
+path = \"src/100_doors.rs\"
+
+[[bin]]
+# http://rosettacode.org/wiki/Gray_code
+name = \"gray_code\"
+path = \"src/gray_code.rs\"
+
+[[bench]]
+name = \"sorting\"
+";
+
+    #[test]
+    fn insert_between() {
+        let manifest = add_bin(MANIFEST, "bubble_sort", "src/bubble_sort.rs",
+                               "http://rosettacode.org/wiki/Bubble_sort").unwrap();
+        assert!(manifest.contains("path = \"src/100_doors.rs\"
+
+[[bin]]
+# http://rosettacode.org/wiki/Bubble_sort
+name = \"bubble_sort\"
+path = \"src/bubble_sort.rs\"
+
+[[bin]]
+# http://rosettacode.org/wiki/Gray_code
+"), "{}", manifest);
+    }
+
+    #[test]
+    fn insert_last() {
+        let manifest = add_bin(MANIFEST, "zig_zag", "src/zig_zag.rs", "http://z").unwrap();
+        assert!(manifest.contains("path = \"src/gray_code.rs\"
+
+[[bin]]
+# http://z
+name = \"zig_zag\"
+path = \"src/zig_zag.rs\"
+
+[[bench]]
+"), "{}", manifest);
+        // With nothing after the last entry
+        let manifest = add_bin("[[bin]]\nname = \"a\"\n", "b", "src/b.rs", "http://b").unwrap();
+        assert_eq!(manifest, "[[bin]]\nname = \"a\"\n\n[[bin]]\n# http://b\nname = \"b\"\n\
+                              path = \"src/b.rs\"\n");
+    }
+
+    #[test]
+    fn already_there() {
+        assert!(add_bin(MANIFEST, "gray_code", "src/gray_code.rs", "http://g").is_err());
+        // The package's name doesn't count
+        assert!(add_bin(MANIFEST, "rust-rosetta", "src/r.rs", "http://r").is_ok());
+    }
+
+    #[test]
+    fn real_manifest() {
+        let manifest = include_str!("../../Cargo.toml");
+        let added = add_bin(manifest, "metered_concurrency_2", "src/m.rs", "http://m").unwrap();
+        assert!(added.contains("path = \"src/concurrency/metered_concurrency.rs\"\n\
+                                required-features = [\"std\"]\n\n[[bin]]\n# http://m\n"));
+        assert!(add_bin(manifest, "metered_concurrency", "src/m.rs", "http://m").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+// `cargo xtask status`: list the tasks that are marked as drafts with #[rosetta_task(status =
+// "draft")], which is what's left to finish (see rosetta_task/src/lib.rs).
+
+use coverage::registry;
+use export::{parse_registry, Entry};
+
+pub fn run() -> Result<(), String> {
+    let tasks = parse_registry(&registry()?);
+    let drafts = drafts(&tasks);
+    for task in &drafts {
+        println!("{:<30} {}", task.name, task.path);
+    }
+    println!("{} of {} tasks are drafts", drafts.len(), tasks.len());
+    Ok(())
+}
+
+fn drafts(tasks: &[Entry]) -> Vec<&Entry> {
+    tasks.iter().filter(|task| task.status == "draft").collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::drafts;
+    use export::parse_registry;
+
+    #[test]
+    fn only_drafts() {
+        let registry = "100_doors\thttp://rosettacode.org/wiki/100_doors\t\
+                        src/puzzles/100_doors.rs\tpuzzles\t-\t-\tcomplete\n\
+                        24_game_solve\thttp://rosettacode.org/wiki/24_game/Solve\t\
+                        src/games/24_game_solve.rs\tgames\t-\t-\tdraft\n";
+        let tasks = parse_registry(registry);
+        let names: Vec<&str> = drafts(&tasks).iter().map(|task| &task.name[..]).collect();
+        assert_eq!(names, ["24_game_solve"]);
+    }
+}
@@ -0,0 +1,345 @@
+// `cargo xtask test [--timeout SECONDS] [--jobs N] [TASK...]`: run the tests of every task, or of
+// the given ones, several tasks at a time.  Unlike `cargo test`, which runs one test binary after
+// another and waits as long as each takes, a task whose tests run for longer than the timeout
+// (60 seconds unless given) is killed and reported, and the others carry on.
+//
+// The test binaries are built first with `cargo test --no-run`, which says where it put them, and
+// then run directly.  Each task's output is kept, and printed only if it fails or times out.
+
+use rustc_serialize::json::Json;
+use std::collections::VecDeque;
+use std::env;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, sleep, spawn};
+use std::time::{Duration, Instant};
+
+static USAGE: &str = "Usage: cargo xtask test [--timeout SECONDS] [--jobs N] [TASK...]";
+
+// How often a running task is checked on
+static POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+// How long to wait for a task's output pipes to close once it has exited or been killed
+static CLOSE_PATIENCE: Duration = Duration::from_secs(1);
+
+pub fn run(args: &[&str]) -> Result<(), String> {
+    let options = parse_args(args)?;
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let binaries = build(root, &options.tasks)?;
+    if binaries.is_empty() {
+        return Err("no tests to run".to_string());
+    }
+    let results = run_all(binaries, &options, root);
+    let summary = Summary::new(&results);
+    for (name, outcome) in &results {
+        if let Outcome::Failed(output) | Outcome::TimedOut(output) = outcome {
+            println!();
+            println!("---- {} ----", name);
+            print!("{}", output);
+        }
+    }
+    println!();
+    println!("{}", summary);
+    if summary.passed == results.len() {
+        Ok(())
+    } else {
+        Err(format!("failed: {}", summary.failures.join(", ")))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    timeout: Duration,  // How long each task's tests may run for
+    jobs: usize,        // How many tasks' tests run at once
+    tasks: Vec<String>, // The tasks to test, or none for all of them
+}
+
+fn parse_args(args: &[&str]) -> Result<Options, String> {
+    let mut options = Options {
+        timeout: Duration::from_secs(60),
+        jobs: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        tasks: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        match arg {
+            "--timeout" | "--jobs" => {
+                let number = args.next().and_then(|value| value.parse::<u64>().ok())
+                                 .filter(|&number| number > 0)
+                                 .ok_or_else(|| format!("{} needs a positive number\n{}", arg,
+                                                        USAGE))?;
+                if arg == "--timeout" {
+                    options.timeout = Duration::from_secs(number);
+                } else {
+                    options.jobs = number as usize;
+                }
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}\n{}", arg, USAGE)),
+            task => options.tasks.push(task.to_string()),
+        }
+    }
+    Ok(options)
+}
+
+// Build the test binaries of `tasks`, or the ones `cargo test` would run, returning each one's
+// name and path
+fn build(root: &Path, tasks: &[String]) -> Result<Vec<(String, PathBuf)>, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.args(["test", "--no-run", "--message-format=json"]).current_dir(root)
+           .stderr(Stdio::inherit());
+    for task in tasks {
+        command.args(["--bin", task]);
+    }
+    let output = command.output().map_err(|error| format!("couldn't run cargo: {}", error))?;
+    if !output.status.success() {
+        return Err("couldn't build the tests".to_string());
+    }
+    Ok(parse_artifacts(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// Pick the test binaries out of the messages `cargo test --no-run --message-format=json` prints,
+// one JSON object per line
+fn parse_artifacts(messages: &str) -> Vec<(String, PathBuf)> {
+    messages.lines().filter_map(|line| Json::from_str(line).ok()).filter_map(|message| {
+        if message.find("reason").and_then(Json::as_string) != Some("compiler-artifact") ||
+           message.find_path(&["profile", "test"]).and_then(Json::as_boolean) != Some(true) {
+            return None;
+        }
+        let name = message.find_path(&["target", "name"]).and_then(Json::as_string)?;
+        let executable = message.find("executable").and_then(Json::as_string)?;
+        Some((name.to_string(), PathBuf::from(executable)))
+    }).collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Passed,
+    Failed(String),   // With what it printed
+    TimedOut(String), // With what it printed before it was killed
+}
+
+// Run each of the `binaries`, `options.jobs` at a time, reporting each as it finishes.  Returns
+// the outcomes in the order the binaries were given.
+fn run_all(binaries: Vec<(String, PathBuf)>, options: &Options, root: &Path)
+           -> Vec<(String, Outcome)> {
+    let count = binaries.len();
+    let queue = Arc::new(Mutex::new(binaries.into_iter().enumerate().collect::<VecDeque<_>>()));
+    let (tx, rx) = channel();
+    for _ in 0..options.jobs.min(count) {
+        let queue = queue.clone();
+        let tx = tx.clone();
+        let timeout = options.timeout;
+        let root = root.to_path_buf();
+        spawn(move || {
+            loop {
+                let next = queue.lock().unwrap().pop_front();
+                let (i, (name, path)) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let start = Instant::now();
+                let mut command = Command::new(path);
+                command.current_dir(&root);
+                let outcome = run_with_timeout(command, timeout);
+                tx.send((i, name, outcome, start.elapsed())).unwrap();
+            }
+        });
+    }
+    drop(tx);
+    let mut results: Vec<Option<(String, Outcome)>> = (0..count).map(|_| None).collect();
+    for (i, name, outcome, elapsed) in rx.iter() {
+        let status = match outcome {
+            Outcome::Passed => "ok".to_string(),
+            Outcome::Failed(_) => "FAILED".to_string(),
+            Outcome::TimedOut(_) => format!("TIMED OUT after {}s", options.timeout.as_secs()),
+        };
+        println!("test {} ... {} ({:.1}s)", name, status, elapsed.as_secs_f64());
+        results[i] = Some((name, outcome));
+    }
+    results.into_iter().map(|result| result.unwrap()).collect()
+}
+
+// Run `command`, killing it if it isn't done after `timeout`
+fn run_with_timeout(mut command: Command, timeout: Duration) -> Outcome {
+    let mut child = match command.stdin(Stdio::null()).stdout(Stdio::piped())
+                                 .stderr(Stdio::piped()).spawn() {
+        Ok(child) => child,
+        Err(error) => return Outcome::Failed(format!("couldn't run it: {}\n", error)),
+    };
+    let stdout = Collector::new(child.stdout.take().unwrap());
+    let stderr = Collector::new(child.stderr.take().unwrap());
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if start.elapsed() < timeout => sleep(POLL_INTERVAL),
+            Ok(None) | Err(_) => {
+                kill(&mut child);
+                break None;
+            }
+        }
+    };
+    let output = stdout.text() + &stderr.text();
+    match status {
+        Some(status) if status.success() => Outcome::Passed,
+        Some(_) => Outcome::Failed(output),
+        None => Outcome::TimedOut(output),
+    }
+}
+
+// Collects what comes out of a pipe as it comes, on a thread of its own, so that a task that
+// prints a lot doesn't block on a full pipe
+struct Collector {
+    bytes: Arc<Mutex<Vec<u8>>>,
+    closed: Receiver<()>,
+}
+
+impl Collector {
+    fn new<R: Read + Send + 'static>(mut pipe: R) -> Collector {
+        let bytes = Arc::new(Mutex::new(Vec::new()));
+        let (tx, closed) = channel();
+        let collected = bytes.clone();
+        spawn(move || {
+            let mut buf = [0; 4096];
+            // Whatever was read before an error is still worth showing
+            while let Ok(n @ 1..) = pipe.read(&mut buf) {
+                collected.lock().unwrap().extend_from_slice(&buf[..n]);
+            }
+            let _ = tx.send(());
+        });
+        Collector { bytes, closed }
+    }
+
+    // What came out, once the pipe is closed.  A process the task started may keep it open after
+    // the task is killed, so this only waits so long for that.
+    fn text(self) -> String {
+        let _ = self.closed.recv_timeout(CLOSE_PATIENCE);
+        let bytes = self.bytes.lock().unwrap();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+fn kill(child: &mut Child) {
+    // It may have finished in the meantime, which is fine
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+// How the run went, as printed at the end
+#[derive(Debug, PartialEq)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    timed_out: usize,
+    failures: Vec<String>, // The names of the tasks that failed or timed out
+}
+
+impl Summary {
+    fn new(results: &[(String, Outcome)]) -> Summary {
+        let mut summary = Summary { passed: 0, failed: 0, timed_out: 0, failures: Vec::new() };
+        for (name, outcome) in results {
+            match outcome {
+                Outcome::Passed => summary.passed += 1,
+                Outcome::Failed(_) => summary.failed += 1,
+                Outcome::TimedOut(_) => summary.timed_out += 1,
+            }
+            if *outcome != Outcome::Passed {
+                summary.failures.push(name.clone());
+            }
+        }
+        summary
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} tasks: {} passed, {} failed, {} timed out",
+               self.passed + self.failed + self.timed_out, self.passed, self.failed,
+               self.timed_out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_args, parse_artifacts, run_with_timeout, Options, Outcome, Summary};
+    use std::path::PathBuf;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn args() {
+        let options = parse_args(&["--timeout", "5", "heap_sort", "--jobs", "2", "rot13"])
+            .unwrap();
+        assert_eq!(options, Options {
+            timeout: Duration::from_secs(5),
+            jobs: 2,
+            tasks: vec!["heap_sort".to_string(), "rot13".to_string()],
+        });
+        let defaults = parse_args(&[]).unwrap();
+        assert_eq!(defaults.timeout, Duration::from_secs(60));
+        assert!(defaults.jobs >= 1 && defaults.tasks.is_empty());
+        assert!(parse_args(&["--timeout"]).is_err());
+        assert!(parse_args(&["--timeout", "0"]).is_err());
+        assert!(parse_args(&["--jobs", "many"]).is_err());
+        assert!(parse_args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn artifacts() {
+        let messages = concat!(
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"rot13"},"#,
+            r#""profile":{"test":true},"executable":"/target/debug/deps/rot13-1a2b"}"#, "\n",
+            // Built to be depended on, not tested
+            r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"rand"},"#,
+            r#""profile":{"test":false},"executable":null}"#, "\n",
+            r#"{"reason":"build-finished","success":true}"#, "\n",
+            "not json\n");
+        assert_eq!(parse_artifacts(messages),
+                   vec![("rot13".to_string(), PathBuf::from("/target/debug/deps/rot13-1a2b"))]);
+    }
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn outcomes() {
+        let timeout = Duration::from_secs(10);
+        assert_eq!(run_with_timeout(shell("echo fine"), timeout), Outcome::Passed);
+        assert_eq!(run_with_timeout(shell("echo out; echo err >&2; exit 101"), timeout),
+                   Outcome::Failed("out\nerr\n".to_string()));
+        match run_with_timeout(Command::new("/no/such/test"), timeout) {
+            Outcome::Failed(message) => assert!(message.starts_with("couldn't run it: ")),
+            outcome => panic!("{:?}", outcome),
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn timeout() {
+        let start = Instant::now();
+        // The sleep outlives the shell that is killed, and holds its output open
+        let outcome = run_with_timeout(shell("echo started; sleep 10"),
+                                       Duration::from_millis(200));
+        assert_eq!(outcome, Outcome::TimedOut("started\n".to_string()));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn summary() {
+        let results = vec![("a".to_string(), Outcome::Passed),
+                           ("b".to_string(), Outcome::TimedOut(String::new())),
+                           ("c".to_string(), Outcome::Failed(String::new())),
+                           ("d".to_string(), Outcome::Passed)];
+        let summary = Summary::new(&results);
+        assert_eq!(summary.failures, vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(summary.to_string(), "4 tasks: 2 passed, 1 failed, 1 timed out");
+    }
+}